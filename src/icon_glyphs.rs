@@ -0,0 +1,198 @@
+//! Codepoints of the glyphs `icon` draws, checked against the bundled font.
+//!
+//! `icon.rs` is regenerated from `fonts/fonts.toml` by the build script, so the
+//! registry lives here. A test keeps it in step with the generated functions.
+
+use crate::icon;
+
+/// Every glyph drawn through `icon`, by the name of the function that draws it
+pub const GLYPHS: &[(&str, char)] = &[
+    ("code", '\u{F1C9}'),
+    ("cog", '\u{2699}'),
+    ("collapsed", '\u{25B8}'),
+    ("copy", '\u{F0C5}'),
+    ("edit", '\u{270E}'),
+    ("expanded", '\u{25BE}'),
+    ("global", '\u{1F30E}'),
+    ("home", '\u{2302}'),
+    ("info", '\u{E705}'),
+    ("plus", '\u{2B}'),
+    ("preview", '\u{1F304}'),
+    ("save", '\u{1F4BE}'),
+    ("swap", '\u{F0EC}'),
+    ("theme", '\u{E032}'),
+    ("trash", '\u{F1F8}'),
+    ("type_icon", '\u{F0F7}'),
+];
+
+/// Names of registered glyphs the font has no outline for, shown as tofu boxes if drawn
+pub fn missing_glyphs(font: &[u8]) -> Result<Vec<&'static str>, String> {
+    let cmap = CharacterMap::parse(font)?;
+    Ok(GLYPHS.iter()
+        .filter(|(_, codepoint)| cmap.glyph_id(*codepoint) == 0)
+        .map(|(name, _)| *name)
+        .collect())
+}
+
+/// Warn on stderr about icons the bundled font can't draw
+pub fn check_bundled_font() {
+    match missing_glyphs(icon::FONT) {
+        Ok(missing) => {
+            for name in missing {
+                eprintln!("Icon font has no glyph for icon::{}", name);
+            }
+        }
+        Err(e) => eprintln!("Could not read the icon font: {}", e),
+    }
+}
+
+/// The Unicode subtable of a TrueType `cmap`, read in place
+enum CharacterMap<'a> {
+    /// Segmented coverage, Basic Multilingual Plane only
+    Format4(&'a [u8]),
+    /// Groups of consecutive codepoints, all planes
+    Format12(&'a [u8]),
+}
+
+impl<'a> CharacterMap<'a> {
+    fn parse(font: &'a [u8]) -> Result<Self, String> {
+        let table_count = read_u16(font, 4)? as usize;
+        let mut cmap = None;
+        for i in 0..table_count {
+            let record = 12 + i * 16;
+            if font.get(record..record + 4) == Some(&b"cmap"[..]) {
+                cmap = Some(read_u32(font, record + 8)? as usize);
+            }
+        }
+        let cmap = cmap.ok_or("Font has no cmap table")?;
+
+        // Prefer a full-Unicode subtable, since some icons sit outside the BMP
+        let mut best: Option<(u8, usize)> = None;
+        for i in 0..read_u16(font, cmap + 2)? as usize {
+            let record = cmap + 4 + i * 8;
+            let platform = read_u16(font, record)?;
+            let encoding = read_u16(font, record + 2)?;
+            let rank = match (platform, encoding) {
+                (3, 10) | (0, 4) | (0, 6) => 2,
+                (3, 1) | (0, 3) => 1,
+                _ => continue,
+            };
+            if best.is_none_or(|(best_rank, _)| rank > best_rank) {
+                best = Some((rank, cmap + read_u32(font, record + 4)? as usize));
+            }
+        }
+        let (_, subtable) = best.ok_or("Font has no Unicode cmap subtable")?;
+
+        let data = font.get(subtable..).ok_or("cmap subtable is out of bounds")?;
+        match read_u16(data, 0)? {
+            4 => Ok(Self::Format4(data)),
+            12 => Ok(Self::Format12(data)),
+            format => Err(format!("Unsupported cmap subtable format {}", format)),
+        }
+    }
+
+    /// Glyph index for `c`, 0 being the font's missing-glyph box
+    fn glyph_id(&self, c: char) -> u32 {
+        let c = c as u32;
+        match self {
+            Self::Format4(data) => {
+                if c > 0xFFFF {
+                    return 0;
+                }
+                Self::format4_glyph(data, c).unwrap_or(0)
+            }
+            Self::Format12(data) => Self::format12_glyph(data, c).unwrap_or(0),
+        }
+    }
+
+    fn format4_glyph(data: &[u8], c: u32) -> Result<u32, String> {
+        let segments = read_u16(data, 6)? as usize / 2;
+        let ends = 14;
+        let starts = ends + segments * 2 + 2;
+        let deltas = starts + segments * 2;
+        let range_offsets = deltas + segments * 2;
+
+        for i in 0..segments {
+            if c > read_u16(data, ends + i * 2)? as u32 {
+                continue;
+            }
+            let start = read_u16(data, starts + i * 2)? as u32;
+            if c < start {
+                return Ok(0);
+            }
+            let delta = read_u16(data, deltas + i * 2)? as u32;
+            let range_offset = read_u16(data, range_offsets + i * 2)? as usize;
+            if range_offset == 0 {
+                return Ok((c + delta) & 0xFFFF);
+            }
+            let glyph = read_u16(data, range_offsets + i * 2 + range_offset + (c - start) as usize * 2)? as u32;
+            return Ok(if glyph == 0 { 0 } else { (glyph + delta) & 0xFFFF });
+        }
+        Ok(0)
+    }
+
+    fn format12_glyph(data: &[u8], c: u32) -> Result<u32, String> {
+        let groups = read_u32(data, 12)? as usize;
+        for i in 0..groups {
+            let group = 16 + i * 12;
+            let start = read_u32(data, group)?;
+            let end = read_u32(data, group + 4)?;
+            if (start..=end).contains(&c) {
+                return Ok(read_u32(data, group + 8)? + (c - start));
+            }
+        }
+        Ok(0)
+    }
+}
+
+fn read_u16(data: &[u8], at: usize) -> Result<u16, String> {
+    data.get(at..at + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .ok_or_else(|| "Font data is truncated".to_string())
+}
+
+fn read_u32(data: &[u8], at: usize) -> Result<u32, String> {
+    data.get(at..at + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "Font data is truncated".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_font_has_every_icon() {
+        assert_eq!(missing_glyphs(icon::FONT), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_registry_matches_generated_icons() {
+        let source = include_str!("icon.rs");
+        let mut generated = Vec::new();
+        let mut name = None;
+        for line in source.lines() {
+            if let Some(rest) = line.strip_prefix("pub fn ") {
+                name = rest.split('<').next();
+            } else if let (Some(n), Some(hex)) = (name, line.trim().strip_prefix("icon(\"\\u{")) {
+                let hex = hex.split('}').next().unwrap();
+                let codepoint = char::from_u32(u32::from_str_radix(hex, 16).unwrap()).unwrap();
+                generated.push((n, codepoint));
+                name = None;
+            }
+        }
+        assert_eq!(generated, GLYPHS);
+    }
+
+    #[test]
+    fn test_unmapped_codepoint_is_missing() {
+        let cmap = CharacterMap::parse(icon::FONT).unwrap();
+        assert_eq!(cmap.glyph_id('Z'), 0);
+        assert_ne!(cmap.glyph_id('\u{2699}'), 0);
+    }
+
+    #[test]
+    fn test_garbage_is_an_error() {
+        assert!(missing_glyphs(b"not a font").is_err());
+    }
+}
@@ -1,13 +1,25 @@
-use iced::{event, window, Element, Size, Subscription, Task, Theme};
-use iced::widget::{button, checkbox, column, combo_box, container, space::horizontal as horizontal_space, pick_list, progress_bar, radio, row, slider, text, text_input, toggler};
-use std::collections::BTreeMap;
+use iced::{event, keyboard, window, Element, Subscription, Task, Theme};
+use iced::widget::{button, column, container, space::horizontal as horizontal_space, pick_list, row, text};
 use widget_helper::panegrid_dashboard::{PaneDock, PaneMsg};
+use theme_slots::{Slot, ThemeSlots};
+use windows::{WindowEnum, Windows};
 
 mod icon;
+mod icon_glyphs;
+mod palette_file;
+mod settings;
+mod showcase;
+mod theme_editor;
+mod theme_slots;
 mod widget;
 mod widget_helper;
+mod windows;
 
 fn main() {
+    if cfg!(debug_assertions) {
+        icon_glyphs::check_bundled_font();
+    }
+
     iced::daemon(ThemeViewer::new, ThemeViewer::update, ThemeViewer::view)
         .title(ThemeViewer::title)
         .theme(ThemeViewer::theme)
@@ -18,73 +30,92 @@ fn main() {
 }
 
 struct ThemeViewer {
-    windows: BTreeMap<window::Id, Window>,
-    widget_builder: widget_helper::WidgetVisualizer,
+    windows: Windows,
+    /// Built when its window first opens or a message first reaches it, so a session that only
+    /// uses the showcase never pays for it
+    widget_builder: Option<widget_helper::WidgetVisualizer>,
     pane: Option<PaneDock>,
     themes: Vec<Theme>,
     theme: Option<Theme>,
-    checkboxes: bool,
-    text_input: String,
-    password: String,
-    show_password: bool,
-    disabled_value: String,
-    radio_value: Option<RadioOption>,
-    slider_value: f32,
-    picklist: Option<Language>,
-    combobox: Option<Language>,
-    combobox_state: iced::widget::combo_box::State<Language>,
-    toggler: bool,
+    theme_slots: ThemeSlots,
+    /// Why the last A/B toggle did nothing
+    theme_slot_status: Option<String>,
+    showcase: showcase::Showcase,
+    settings: settings::AppSettings,
+    settings_dirty: bool,
+    /// A write is running. Changes made meanwhile wait for it and go out as one later write.
+    settings_saving: bool,
+    palette_status: Option<String>,
+    pending_palette: Option<(String, iced::theme::Palette)>,
+    theme_editor: Option<theme_editor::ThemeEditor>,
 }
 
 #[derive(Clone, Debug)]
 enum Message {
     ChooseTheme(Theme),
+    CaptureThemeSlot(Slot),
+    ToggleThemeSlots,
     ShowWidgetBuilder,
-    ButtonPressed,
-    CheckBox(bool),
-    EnteringText(String),
-    EnteringPassword(String),
-    ShowPassword(bool),
-    RadioSelected(RadioOption),
-    UpdateSlider(f32),
-    PickListSelection(Language),
-    ComboBoxSelection(Language),
-    ToggleToggler(bool),
+    Showcase(showcase::Message),
+    ResetShowcase,
 
     // Widget Builder Messages
     WidgetHelper(widget_helper::Message),
     Pane(PaneMsg),
 
     //window handles
-    WindowClosed(iced::window::Id),
-    RequestOpenWindow(WindowEnum),
-    WindowOpened(iced::window::Id, WindowEnum),
+    Window(windows::Message),
+    SaveSettings,
+    SettingsSaved(Result<(), String>),
+    SetStartupTheme,
+
+    // Palette sharing
+    ExportPalette,
+    ImportPalette,
+    PaletteSaved(Result<std::path::PathBuf, String>),
+    PaletteLoaded(Result<(String, iced::theme::Palette), String>),
+    ConfirmReplacePalette,
+    CancelReplacePalette,
+    ThemeEditor(theme_editor::Message),
 }
 
 impl ThemeViewer {
     fn new() -> (Self, Task<Message>) {
+        let started = std::time::Instant::now();
         let themes = Theme::ALL.to_vec();
 
+        let settings = settings::AppSettings::load();
+
         let theme_viewer = Self {
-            windows: BTreeMap::new(),
-            widget_builder: widget_helper::WidgetVisualizer::new(),
+            windows: Windows::default(),
+            widget_builder: None,
             pane: None,
             themes: themes,
-            theme: Some(iced::theme::Theme::Dark),
-            checkboxes: true,
-            text_input: String::new(),
-            password: String::new(),
-            show_password: false,
-            disabled_value: String::new(),
-            radio_value: None,
-            slider_value: 1_f32,
-            picklist: None,
-            combobox: None,
-            combobox_state: iced::widget::combo_box::State::new(Language::ALL.to_vec()),
-            toggler: false,
+            theme: Some(settings.startup_theme()),
+            theme_slots: settings.theme_slots(),
+            theme_slot_status: None,
+            showcase: showcase::Showcase::from_state(&settings.showcase),
+            settings,
+            settings_dirty: false,
+            settings_saving: false,
+            palette_status: None,
+            pending_palette: None,
+            theme_editor: None,
         };
 
-        (theme_viewer, Task::done(Message::RequestOpenWindow(WindowEnum::Main)))
+        println!("Started in {:?}", started.elapsed());
+        (theme_viewer, Task::done(Message::Window(windows::Message::RequestOpen(WindowEnum::Main))))
+    }
+
+    /// The settings to write now, or None while there's nothing new or a write is still
+    /// running. Later changes stay marked dirty and are written once that one finishes.
+    fn start_settings_save(&mut self) -> Option<settings::AppSettings> {
+        if !self.settings_dirty || self.settings_saving {
+            return None;
+        }
+        self.settings_dirty = false;
+        self.settings_saving = true;
+        Some(self.settings.clone())
     }
 
     fn theme(&self, _window_id: window::Id) -> Theme {
@@ -92,63 +123,139 @@ impl ThemeViewer {
     }
 
     fn title(&self, window_id: window::Id) -> String {
-        self.windows.get(&window_id).map(|window| window.title.clone()).unwrap_or_default()
+        self.windows.title(window_id)
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::ChooseTheme(theme) => {
+                // Edits would switch back to the edited theme, so picking another ends them
+                if self.theme_editor.as_ref().is_some_and(|editor| editor.name.trim() != theme.to_string()) {
+                    self.theme_editor = None;
+                }
                 self.theme = Some(theme);
                 Task::none()
             }
-            Message::ShowWidgetBuilder => {
-                Task::done(Message::RequestOpenWindow(WindowEnum::WidgetVisualizer))
+            Message::CaptureThemeSlot(slot) => {
+                let theme = self.theme.clone().unwrap_or(Theme::Dark);
+                self.theme_slots.capture(slot, theme);
+                self.theme_slot_status = None;
+                if self.settings.record_theme_slots(&self.theme_slots) {
+                    self.settings_dirty = true;
+                }
+                Task::none()
+            }
+            Message::ToggleThemeSlots => {
+                let current = self.theme.clone().unwrap_or(Theme::Dark);
+                match self.theme_slots.toggle(&current) {
+                    Ok(theme) => {
+                        self.theme = Some(theme);
+                        self.theme_slot_status = None;
+                    }
+                    Err(hint) => self.theme_slot_status = Some(hint),
+                }
+                Task::none()
+            }
+            Message::ExportPalette => {
+                let theme = self.theme.clone().unwrap_or(Theme::Dark);
+                match palette_file::palette_to_toml(&theme.palette()) {
+                    Ok(contents) => {
+                        let default_name = format!("{}.toml", theme.to_string().to_lowercase().replace(' ', "_"));
+                        Task::perform(
+                            widget_helper::file_io::save_text_file(contents, default_name, "Palette", &["toml"]),
+                            Message::PaletteSaved,
+                        )
+                    }
+                    Err(e) => {
+                        self.palette_status = Some(format!("Failed to export palette: {}", e));
+                        Task::none()
+                    }
+                }
             }
-            Message::ButtonPressed => {
-                println!("Button pressed!");
+            Message::ImportPalette => {
+                Task::perform(
+                    palette_file::load_palette_file(),
+                    Message::PaletteLoaded,
+                )
+            }
+            Message::PaletteSaved(result) => {
+                self.palette_status = Some(match result {
+                    Ok(path) => format!("Saved palette to {}", path.display()),
+                    Err(e) => e,
+                });
                 Task::none()
             }
-            Message::CheckBox(b) => {
-                self.checkboxes = b;
+            Message::PaletteLoaded(Ok((name, palette))) => {
+                if self.themes.iter().any(|t| t.to_string() == name) {
+                    self.palette_status = Some(format!("A theme named \"{}\" already exists.", name));
+                    self.pending_palette = Some((name, palette));
+                } else {
+                    self.add_palette_theme(name, palette);
+                }
                 Task::none()
             }
-            Message::EnteringText(msg) => {
-                self.text_input = msg;
+            Message::PaletteLoaded(Err(e)) => {
+                self.palette_status = Some(e);
                 Task::none()
             }
-            Message::EnteringPassword(msg) => {
-                self.password = msg;
+            Message::ConfirmReplacePalette => {
+                if let Some((name, palette)) = self.pending_palette.take() {
+                    self.themes.retain(|t| t.to_string() != name);
+                    self.add_palette_theme(name, palette);
+                }
                 Task::none()
             }
-            Message::ShowPassword(b) => {
-                self.show_password = b;
+            Message::CancelReplacePalette => {
+                self.pending_palette = None;
+                self.palette_status = None;
                 Task::none()
             }
-            Message::RadioSelected(selection) => {
-                self.radio_value = Some(selection);
+            Message::ThemeEditor(theme_editor::Message::Open) => {
+                let current = self.theme.clone().unwrap_or(Theme::Dark);
+                self.theme_editor = Some(theme_editor::ThemeEditor::new(&current, &self.themes));
                 Task::none()
             }
-            Message::UpdateSlider(num) => {
-                self.slider_value = num;
+            Message::ThemeEditor(theme_editor::Message::Close) => {
+                self.theme_editor = None;
                 Task::none()
             }
-            Message::PickListSelection(language) => {
-                self.picklist = Some(language);
+            Message::ThemeEditor(msg) => {
+                if let Some(editor) = &mut self.theme_editor {
+                    editor.update(&msg);
+                }
+                self.apply_theme_edit();
                 Task::none()
             }
-
-            Message::ComboBoxSelection(language) => {
-                self.combobox = Some(language);
+            Message::ShowWidgetBuilder => {
+                Task::done(Message::Window(windows::Message::RequestOpen(WindowEnum::WidgetVisualizer)))
+            }
+            Message::Showcase(msg) => {
+                self.showcase.update(msg);
+                self.sync_showcase();
+                Task::none()
+            }
+            Message::ResetShowcase => {
+                self.showcase = showcase::Showcase::new();
+                self.sync_showcase();
                 Task::none()
             }
-            Message::ToggleToggler(b) => {
-                self.toggler = b;
+
+            // Keyboard events reach every window's subscriptions, so keys pressed in another
+            // window don't get to edit the builder's tree
+            Message::WidgetHelper(widget_helper::Message::KeyPressed(..))
+                if self.windows.focused_type().is_some_and(|focused| *focused != WindowEnum::WidgetVisualizer) =>
+            {
                 Task::none()
             }
 
             // Widget Helper
             Message::WidgetHelper(msg) => {
-                match widget_helper::WidgetVisualizer::update(&mut self.widget_builder, msg) {
+                let action = widget_helper::WidgetVisualizer::update(self.widget_builder(), msg);
+                self.sync_disabled_hints();
+                self.sync_keybindings();
+                self.sync_code_budget();
+                self.sync_code_font();
+                match action {
                     widget_helper::Action::Run(task) => {
                         return task.map(Message::WidgetHelper)
                     }
@@ -158,61 +265,32 @@ impl ThemeViewer {
             }
 
             //window handles
-            Message::WindowClosed(window_id) => {
-                self.windows.remove(&window_id);
-                if self.windows.is_empty() {
-                    iced::exit()
+            Message::Window(msg) => {
+                if let windows::Message::RequestOpen(WindowEnum::WidgetVisualizer) = msg {
+                    self.widget_builder();
                 }
-                else {
-                    Task::none()
+                let (task, settings_changed) = self.windows.update(msg, &mut self.settings);
+                if settings_changed {
+                    self.settings_dirty = true;
                 }
+                task.map(Message::Window)
+            }
+            Message::SaveSettings => match self.start_settings_save() {
+                Some(settings) => Task::perform(async move { settings.save() }, Message::SettingsSaved),
+                None => Task::none(),
             },
-            Message::RequestOpenWindow(window_type) => {
-                match window_type {
-                    WindowEnum::Main => { 
-                        let (_id, open) = iced::window::open(
-                            iced::window::Settings {
-                                position: window::Position::Centered,
-                                size: Size::new(700_f32, 1000_f32),
-                                min_size: Some(Size::new(700_f32, 975_f32)),
-                                exit_on_close_request: true,
-                                ..iced::window::Settings::default()
-                            }
-                        );
-                        return open.map(|id| Message::WindowOpened(id, WindowEnum::Main))
-                    }
-                    WindowEnum::WidgetVisualizer => {
-                        let mut windows = self.windows.iter().enumerate();
-                        if let Some(id) = windows.position(|(_, w)| w.1.windowtype == WindowEnum::WidgetVisualizer ) {
-                            let window_id = self.windows.iter().nth(id).unwrap().0.clone();
-
-                            return iced::Task::batch([
-                                    window::minimize(window_id, false),
-                                    window::gain_focus( window_id )
-                            ]);
-                        }
-
-                        let (_id, open) = iced::window::open(window::Settings {
-                            size: Size::new(1920_f32, 1080_f32),
-                            min_size: Some(Size::new(700_f32, 975_f32)),
-                            ..window::Settings::default()
-                        });
-                        return open.map(|id| Message::WindowOpened(id, WindowEnum::WidgetVisualizer))
-                    }
+            Message::SettingsSaved(result) => {
+                self.settings_saving = false;
+                if let Err(e) = result {
+                    eprintln!("Failed to save settings: {}", e);
                 }
-            },
-            Message::WindowOpened(window_id, window_type) => {
-                let title = match window_type {
-                    WindowEnum::Main => { String::from("Theme Viewer") }
-                    WindowEnum::WidgetVisualizer => { String::from("UI Builder") }
-                };
-
-                let new_window = Window::new(window_id, title, window_type);
-
-                self.windows.insert(window_id, new_window);
-
                 Task::none()
-            },
+            }
+            Message::SetStartupTheme => {
+                self.settings.startup_theme = self.theme.as_ref().map(|theme| theme.to_string());
+                self.settings_dirty = true;
+                Task::none()
+            }
             Message::Pane(m) => {
                 if let Some(dock) = &mut self.pane {
                     return dock.update(m).map(Message::Pane);
@@ -222,6 +300,102 @@ impl ThemeViewer {
         }
     }
 
+    /// The widget builder, built with the saved settings the first time it's needed
+    fn widget_builder(&mut self) -> &mut widget_helper::WidgetVisualizer {
+        let settings = &self.settings;
+        let themes = &self.themes;
+        self.widget_builder.get_or_insert_with(|| {
+            let started = std::time::Instant::now();
+            let mut widget_builder = widget_helper::WidgetVisualizer::new();
+            widget_builder.set_disabled_hints(settings.disabled_hints.iter().copied());
+            widget_builder.set_keybinding_overrides(&settings.keybindings);
+            widget_builder.set_code_budget(settings.code_budget);
+            widget_builder.set_code_font(settings.code_font.clone());
+            for theme in themes.iter().filter(|theme| !Theme::ALL.contains(theme)) {
+                widget_builder.offer_theme(theme.clone(), None);
+            }
+            println!("Built the widget builder in {:?}", started.elapsed());
+            widget_builder
+        })
+    }
+
+    /// Copies the builder's switched-off hint rules into the settings when they change
+    fn sync_disabled_hints(&mut self) {
+        let Some(widget_builder) = &self.widget_builder else { return };
+        let disabled = widget_builder.disabled_hints();
+        let unchanged = disabled.len() == self.settings.disabled_hints.len()
+            && self.settings.disabled_hints.iter().all(|rule| disabled.contains(rule));
+        if !unchanged {
+            let mut rules: Vec<_> = disabled.iter().copied().collect();
+            rules.sort_by_key(|rule| widget_helper::hints::HintRule::ALL.iter().position(|r| r == rule));
+            self.settings.disabled_hints = rules;
+            self.settings_dirty = true;
+        }
+    }
+
+    /// Copies the showcase controls into the settings when they change
+    fn sync_showcase(&mut self) {
+        let state = self.showcase.state();
+        if state != self.settings.showcase {
+            self.settings.showcase = state;
+            self.settings_dirty = true;
+        }
+    }
+
+    /// Copies rebound shortcuts into the settings when they change
+    fn sync_keybindings(&mut self) {
+        let Some(widget_builder) = &self.widget_builder else { return };
+        let overrides = widget_builder.keybindings().overrides();
+        if overrides != self.settings.keybindings {
+            self.settings.keybindings = overrides;
+            self.settings_dirty = true;
+        }
+    }
+
+    /// Copies the code size limits into the settings when they change
+    fn sync_code_budget(&mut self) {
+        let Some(widget_builder) = &self.widget_builder else { return };
+        let budget = widget_builder.code_budget();
+        if budget != self.settings.code_budget {
+            self.settings.code_budget = budget;
+            self.settings_dirty = true;
+        }
+    }
+
+    /// Copies the code view font into the settings when it changes
+    fn sync_code_font(&mut self) {
+        let Some(widget_builder) = &self.widget_builder else { return };
+        if *widget_builder.code_font() != self.settings.code_font {
+            self.settings.code_font = widget_builder.code_font().clone();
+            self.settings_dirty = true;
+        }
+    }
+
+    fn add_palette_theme(&mut self, name: String, palette: iced::theme::Palette) {
+        let theme = Theme::custom(name.clone(), palette);
+        self.themes.push(theme.clone());
+        if let Some(widget_builder) = &mut self.widget_builder {
+            widget_builder.offer_theme(theme.clone(), None);
+        }
+        self.theme = Some(theme);
+        self.palette_status = Some(format!("Imported palette \"{}\"", name));
+    }
+
+    /// Puts the edited theme into the theme list and shows it. The builder's picker gets it too.
+    fn apply_theme_edit(&mut self) {
+        let Some(editor) = &mut self.theme_editor else { return };
+        match editor.apply(&mut self.themes) {
+            Ok((theme, previous)) => {
+                editor.error = None;
+                if let Some(widget_builder) = &mut self.widget_builder {
+                    widget_builder.offer_theme(theme.clone(), previous.as_deref());
+                }
+                self.theme = Some(theme);
+            }
+            Err(e) => editor.error = Some(e),
+        }
+    }
+
     fn view<'a>(&'a self, window_id: window::Id) -> Element<'a, Message> {
 
         let open_widget_visualizer = button("Open Widget Visualizer").on_press(Message::ShowWidgetBuilder);
@@ -232,205 +406,83 @@ impl ThemeViewer {
             Message::ChooseTheme
         );
 
-        let theme_selection = column![
-            text("Theme").size(18),
-            theme_pick_list
-        ].spacing(5);
-
-        let buttons = container(
-            column![
-                text("Buttons:").size(18),
-                row![
-                    column![
-                        button("Primary").style(button::primary).on_press(Message::ButtonPressed).width(100),
-                        button("Disabled").style(button::primary).width(100),
-                    ].spacing(5),
-                    column![
-                        button("Secondary").style(button::secondary).on_press(Message::ButtonPressed).width(100),
-                        button("Disabled").style(button::secondary).width(100),
-                    ].spacing(5),
-                    column![
-                        button("Success").style(button::success).on_press(Message::ButtonPressed).width(100),
-                        button("Disabled").style(button::success).width(100)
-                    ].spacing(5),
-                    column![
-                        button("Warning").style(button::warning).on_press(Message::ButtonPressed).width(100),
-                        button("Disabled").style(button::warning).width(100)
-                    ].spacing(5),
-                    column![
-                        button("Danger").style(button::danger).on_press(Message::ButtonPressed).width(100),
-                        button("Disabled").style(button::danger).width(100)
-                    ].spacing(5),
-                    column![
-                        button("Text").style(button::text).on_press(Message::ButtonPressed).width(100),
-                        button("Disabled").style(button::text).width(100)
-                    ].spacing(5),
-                ].spacing(10),
-            ]
-            .spacing(10)
-            .padding(10)
-        )
-        .style(container::bordered_box)
-        .padding(
-            iced::Padding {
-                top: 0_f32, 
-                right: 10_f32,
-                bottom: 10_f32,
-                left: 10_f32
-            }
-        )
-        .width(iced::Length::Fill);
-
-        let checkboxes = container(
-            column![
-                text("Checkboxes:").size(18),
-                row![
-                    column![
-                        checkbox("Primary", self.checkboxes).style(checkbox::primary).on_toggle(Message::CheckBox).width(130),
-                        checkbox("Primary", self.checkboxes).style(checkbox::primary).width(130)
-                    ].spacing(5),
-                    column![
-                        checkbox("Secondary", self.checkboxes).style(checkbox::secondary).on_toggle(Message::CheckBox).width(130),
-                        checkbox("Secondary", self.checkboxes).style(checkbox::secondary).width(130)
-                    ].spacing(5),
-                    column![
-                        checkbox("Success", self.checkboxes).style(checkbox::success).on_toggle(Message::CheckBox).width(130),
-                        checkbox("Success", self.checkboxes).style(checkbox::success).width(130)
-                    ].spacing(5),
-                    column![
-                        checkbox("Danger", self.checkboxes).style(checkbox::danger).on_toggle(Message::CheckBox).width(130),
-                        checkbox("Danger", self.checkboxes).style(checkbox::danger).width(130)
-                    ].spacing(5),
-                ],
-            ]
-            .spacing(10)
-            .padding(10)
-        )
-        .style(container::bordered_box)
-        .padding(
-            iced::Padding {
-                top: 0_f32, 
-                right: 10_f32,
-                bottom: 10_f32,
-                left: 10_f32
-            }
-        )
-        .width(iced::Length::Fill);
+        let palette_status: Element<'a, Message> = match (&self.pending_palette, &self.palette_status) {
+            (Some(_), Some(status)) => row![
+                text(status),
+                button("Replace").style(button::danger).on_press(Message::ConfirmReplacePalette),
+                button("Cancel").style(button::secondary).on_press(Message::CancelReplacePalette),
+            ].spacing(10).align_y(iced::Alignment::Center).into(),
+            (_, Some(status)) => text(status).into(),
+            _ => row![].into(),
+        };
 
-        let range = std::ops::RangeInclusive::new(1_f32,100_f32);
+        // Only built-in themes can be found again on the next start
+        let current = self.theme.clone().unwrap_or(Theme::Dark);
+        let startup_control: Element<'a, Message> = if self.settings.startup_theme() == current {
+            text("Startup theme").size(14).into()
+        } else if Theme::ALL.contains(&current) {
+            button("Set as default").style(button::secondary).on_press(Message::SetStartupTheme).into()
+        } else {
+            row![].into()
+        };
 
-        let form_controls = container(
-            column![
-                text("Form Controls:").size(18),
-
-                // Text Inputs
-                text("Text Inputs: "),
-                column![
-                    text_input("Text input", &self.text_input).on_input(Message::EnteringText).width(650)
-                ].spacing(5),
-                column![
-                    row![
-                        text_input("Password", &self.password).on_input(Message::EnteringPassword).secure(!self.show_password),
-                        checkbox("Show Password", self.show_password).on_toggle(Message::ShowPassword)
-                    ].align_y(iced::Alignment::Center).spacing(10).width(640),
-                ].spacing(5),
-                column![
-                    text_input("Disabled Text Input", &self.disabled_value).width(650)
-                ].spacing(5),
-                column![
-
-                ].spacing(5),
-
-                // Radio Buttons
-                text("Radio Buttons: "),
-                row![
-                    radio(
-                        "Option 1", 
-                        RadioOption::Option1, 
-                        self.radio_value, 
-                        Message::RadioSelected
-                    ).width(150),
-                    radio(
-                        "Option 2", 
-                        RadioOption::Option2, 
-                        self.radio_value, 
-                        Message::RadioSelected
-                    ).width(150),
-                    radio(
-                        "Option 3", 
-                        RadioOption::Option3, 
-                        self.radio_value, 
-                        Message::RadioSelected
-                    ).width(150),
-                ],
+        let theme_editor: Element<'a, Message> = match &self.theme_editor {
+            Some(editor) => editor.view().map(Message::ThemeEditor),
+            None => row![].into(),
+        };
 
-                
-                // Slider
-                text("Slider: "),
-                row![
-                    slider(
-                        range.clone(),
-                         self.slider_value,
-                         Message::UpdateSlider),
-                ].width(650),
-
-                // Progress Bar
-                text("Progress Bar: "),
-                row![
-                    progress_bar(
-                        range.clone(), 
-                        self.slider_value)
-                ].width(650),
+        let active_slot = self.theme_slots.active_slot(&current);
+        let slot_button = |slot: Slot| {
+            let label = match self.theme_slots.get(slot) {
+                Some(theme) => format!("{}: {}", slot, theme),
+                None => format!("{}: empty", slot),
+            };
+            button(text(label).size(14))
+                .style(if active_slot == Some(slot) { button::primary } else { button::secondary })
+                .on_press(Message::CaptureThemeSlot(slot))
+        };
+        let caption = match active_slot {
+            Some(slot) => format!("Theme ({})", slot),
+            None => "Theme".to_string(),
+        };
+        let slot_status: Element<'a, Message> = match &self.theme_slot_status {
+            Some(status) => text(status).size(14).into(),
+            None => row![].into(),
+        };
 
-                
-                // Pick List
-                text("Pick List: "),
-                row![
-                    pick_list(
-                        Language::ALL, 
-                        self.picklist, 
-                        Message::PickListSelection)
-                ].width(650),
-
-                // Combo Box
-                text("Combo Box: "),
-                row![
-                    combo_box(
-                        &self.combobox_state, 
-                        "Select", 
-                        self.combobox.as_ref(), 
-                        Message::ComboBoxSelection)
-                ].width(650),
-
-                // Toggler
-                text("Toggler: "),
-                toggler(self.toggler).on_toggle(Message::ToggleToggler),
-            ]
-            .spacing(10)
-            .padding(10)
-        )
-        .style(container::bordered_box)
-        .padding(
-            iced::Padding {
-                top: 0_f32, 
-                right: 10_f32,
-                bottom: 10_f32,
-                left: 10_f32
-            }
-        )
-        .width(iced::Length::Fill);
+        let theme_selection = column![
+            text(caption).size(18),
+            row![
+                theme_pick_list,
+                startup_control,
+                button("Export palette").style(button::secondary).on_press(Message::ExportPalette),
+                button("Import palette").style(button::secondary).on_press(Message::ImportPalette),
+                button("Edit palette")
+                    .style(button::secondary)
+                    .on_press_maybe(self.theme_editor.is_none().then_some(Message::ThemeEditor(theme_editor::Message::Open))),
+            ].spacing(10).align_y(iced::Alignment::Center),
+            palette_status,
+            theme_editor,
+            row![
+                slot_button(Slot::A),
+                slot_button(Slot::B),
+                button(text("Toggle A/B (F7)").size(14)).style(button::secondary).on_press(Message::ToggleThemeSlots),
+                slot_status,
+            ].spacing(10).align_y(iced::Alignment::Center),
+        ].spacing(5);
 
         let main_window_content = container(
             column![
                 row![
                     theme_selection,
                     horizontal_space(),
-                    open_widget_visualizer,
+                    column![
+                        open_widget_visualizer,
+                        button("Reset demo state").style(button::secondary).on_press(Message::ResetShowcase),
+                    ].spacing(5).align_x(iced::Alignment::End),
                 ],
                 
-                buttons,
-                checkboxes,
-                form_controls
+                self.showcase.view().map(Message::Showcase),
             ].spacing(10)
         )
         .padding(15)
@@ -448,7 +500,11 @@ impl ThemeViewer {
                         }
                     }
 
-                    self.widget_builder.view().map(Message::WidgetHelper)
+                    match &self.widget_builder {
+                        Some(widget_builder) => widget_builder.view().map(Message::WidgetHelper),
+                        // Opening the window builds it first, so this only shows for a frame at most
+                        None => container(text("Loading the UI builder…")).padding(15).into(),
+                    }
                 }
             }
             None => { 
@@ -472,76 +528,143 @@ impl ThemeViewer {
                 .unwrap_or(iced::Subscription::none()),
 
             event::listen_with(handle_event),
+
+            // Builder shortcuts only while its window is open
+            match &self.widget_builder {
+                Some(widget_builder) if self.windows.is_open(&WindowEnum::WidgetVisualizer) => {
+                    widget_builder.subscription().map(Message::WidgetHelper)
+                }
+                _ => Subscription::none(),
+            },
+
+            // Debounce window move/resize bursts into a single write
+            if self.settings_dirty {
+                iced::time::every(std::time::Duration::from_secs(1)).map(|_| Message::SaveSettings)
+            } else {
+                Subscription::none()
+            },
         ])
     }   
 }
 
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum RadioOption {
-    Option1,
-    Option2,
-    Option3,
+fn handle_event(event: event::Event, _status: event::Status, id: iced::window::Id) -> Option<Message> {
+    match event {
+        event::Event::Window(window::Event::Closed) => Some(Message::Window(windows::Message::Closed(id))),
+        event::Event::Window(window::Event::Moved(position)) => Some(Message::Window(windows::Message::Moved(id, position))),
+        event::Event::Window(window::Event::Resized(size)) => Some(Message::Window(windows::Message::Resized(id, size))),
+        event::Event::Window(window::Event::Focused) => Some(Message::Window(windows::Message::Focused(id))),
+        event::Event::Window(window::Event::Unfocused) => Some(Message::Window(windows::Message::Unfocused(id))),
+        // F7 isn't bound to anything in text inputs, so it works while one is focused too
+        event::Event::Keyboard(keyboard::Event::KeyPressed { key: keyboard::Key::Named(keyboard::key::Named::F7), .. }) => {
+            Some(Message::ToggleThemeSlots)
+        }
+        _ => None,
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_switch_leaves_showcase_state_alone() {
+        let (mut viewer, _) = ThemeViewer::new();
+        viewer.update(Message::Showcase(showcase::Message::UpdateSlider(55.0)));
+        let saved = viewer.settings.showcase.clone();
+        viewer.settings_dirty = false;
+
+        viewer.update(Message::ChooseTheme(Theme::Nord));
+        assert_eq!(viewer.showcase.state(), saved);
+        assert_eq!(viewer.settings.showcase, saved);
+        assert!(!viewer.settings_dirty);
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Language {
-    Rust,
-    Java,
-    CPlusPlus,
-    C,
-    CSharp
-}
+    #[test]
+    fn test_reset_clears_saved_showcase_state() {
+        let (mut viewer, _) = ThemeViewer::new();
+        viewer.update(Message::Showcase(showcase::Message::EnteringText("contrast check".to_string())));
+        assert_eq!(viewer.settings.showcase.text_input, "contrast check");
 
-impl Language {
-    const ALL: [Language; 5] = [
-        Language::Rust,
-        Language::Java,
-        Language::CPlusPlus,
-        Language::C,
-        Language::CSharp,
-    ];
-}
+        viewer.update(Message::ResetShowcase);
+        assert_eq!(viewer.settings.showcase, showcase::ShowcaseState::default());
+        assert_eq!(viewer.showcase.state(), showcase::ShowcaseState::default());
+    }
 
-impl std::fmt::Display for Language {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Language::Rust => write!(f, "Rust"),
-            Language::Java => write!(f, "Java"),
-            Language::CPlusPlus => write!(f, "CPlusPlus"),
-            Language::C => write!(f, "C"),
-            Language::CSharp => write!(f, "CSharp"),
-        }
+    #[test]
+    fn test_builder_edits_reach_the_shared_visualizer() {
+        let (mut viewer, _) = ThemeViewer::new();
+        let before = viewer.widget_builder().revision();
+        viewer.update(Message::WidgetHelper(widget_helper::Message::AppNameChanged("Synced".to_string())));
+        assert!(viewer.widget_builder().revision() > before);
     }
 
-}
+    #[test]
+    fn test_builder_is_built_on_first_use() {
+        let (mut viewer, _) = ThemeViewer::new();
+        assert!(viewer.widget_builder.is_none());
+        viewer.update(Message::ChooseTheme(Theme::Nord));
+        viewer.update(Message::Showcase(showcase::Message::UpdateSlider(30.0)));
+        assert!(viewer.widget_builder.is_none());
 
-#[derive(Default, Debug, Clone, PartialEq)]
-pub enum WindowEnum {
-    #[default]
-    Main,
-    WidgetVisualizer
-}
+        viewer.update(Message::Window(windows::Message::RequestOpen(WindowEnum::WidgetVisualizer)));
+        assert!(viewer.widget_builder.is_some());
+    }
 
-#[derive(Debug, Clone,)]
-pub struct Window {
-    pub title: String,
-    pub windowtype: WindowEnum,
-}
+    #[test]
+    fn test_message_before_the_builder_opens_builds_it_with_saved_settings() {
+        let (mut viewer, _) = ThemeViewer::new();
+        viewer.settings.code_budget.max_lines = 123;
+        viewer.settings_dirty = false;
+
+        viewer.update(Message::WidgetHelper(widget_helper::Message::AppNameChanged("Early".to_string())));
+        let widget_builder = viewer.widget_builder.as_ref().expect("built by the message");
+        assert_eq!(widget_builder.code_budget(), viewer.settings.code_budget);
+        assert!(widget_builder.revision() > 0);
+        // Building it copies the settings in, so there is nothing new to save
+        assert!(!viewer.settings_dirty);
+    }
 
-impl Window {
-    pub fn new(_id: window::Id, title: String, window_type: WindowEnum) -> Self {
-        Self {
-            title: title,
-            windowtype: window_type,
-        }
+    #[test]
+    fn test_one_settings_write_at_a_time() {
+        let (mut viewer, _) = ThemeViewer::new();
+        viewer.settings_dirty = false;
+        assert!(viewer.start_settings_save().is_none());
+
+        viewer.update(Message::ChooseTheme(Theme::Light));
+        viewer.update(Message::SetStartupTheme);
+        let first = viewer.start_settings_save().expect("dirty settings are written");
+        assert!(!viewer.settings_dirty);
+
+        // Changes while the write runs wait for it, and only the newest state goes out
+        viewer.update(Message::ChooseTheme(Theme::Nord));
+        viewer.update(Message::SetStartupTheme);
+        viewer.update(Message::ChooseTheme(Theme::Dracula));
+        viewer.update(Message::SetStartupTheme);
+        assert!(viewer.start_settings_save().is_none());
+        assert!(viewer.settings_dirty);
+
+        viewer.update(Message::SettingsSaved(Ok(())));
+        let second = viewer.start_settings_save().expect("the pending change is written next");
+        assert_eq!(second.startup_theme.as_deref(), Some("Dracula"));
+        assert_eq!(first.startup_theme.as_deref(), Some("Light"));
     }
-}
 
-fn handle_event(event: event::Event, _status: event::Status, id: iced::window::Id) -> Option<Message> {
-    match event {
-        event::Event::Window(window::Event::Closed) => Some(Message::WindowClosed(id)),
-        _ => None,
+    #[test]
+    fn test_theme_slot_toggle() {
+        let (mut viewer, _) = ThemeViewer::new();
+        viewer.update(Message::ChooseTheme(Theme::Nord));
+        viewer.update(Message::CaptureThemeSlot(Slot::A));
+        viewer.update(Message::ToggleThemeSlots);
+        assert!(viewer.theme_slot_status.is_some());
+        assert_eq!(viewer.theme, Some(Theme::Nord));
+
+        viewer.update(Message::ChooseTheme(Theme::Dracula));
+        viewer.update(Message::CaptureThemeSlot(Slot::B));
+        viewer.update(Message::ToggleThemeSlots);
+        assert_eq!(viewer.theme, Some(Theme::Nord));
+        assert!(viewer.theme_slot_status.is_none());
+        assert_eq!(viewer.settings.theme_a.as_deref(), Some("Nord"));
+        assert_eq!(viewer.settings.theme_b.as_deref(), Some("Dracula"));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,147 @@
+use iced::theme::Palette;
+use iced::Color;
+use serde::{Deserialize, Serialize};
+
+/// On-disk palette: one hex string per color. Keys are optional here so a
+/// missing one can be reported by name instead of as a generic parse error.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PaletteFile {
+    background: Option<String>,
+    text: Option<String>,
+    primary: Option<String>,
+    success: Option<String>,
+    warning: Option<String>,
+    danger: Option<String>,
+}
+
+pub fn palette_to_toml(palette: &Palette) -> Result<String, String> {
+    let file = PaletteFile {
+        background: Some(color_to_hex(palette.background)),
+        text: Some(color_to_hex(palette.text)),
+        primary: Some(color_to_hex(palette.primary)),
+        success: Some(color_to_hex(palette.success)),
+        warning: Some(color_to_hex(palette.warning)),
+        danger: Some(color_to_hex(palette.danger)),
+    };
+    toml::to_string_pretty(&file).map_err(|e| e.to_string())
+}
+
+pub fn palette_from_toml(contents: &str) -> Result<Palette, String> {
+    let file: PaletteFile = toml::from_str(contents).map_err(|e| format!("Invalid palette file: {}", e))?;
+
+    let color = |key: &str, value: &Option<String>| -> Result<Color, String> {
+        let value = value.as_ref().ok_or_else(|| format!("Missing key `{}`", key))?;
+        parse_hex(value).map_err(|e| format!("`{}`: {}", key, e))
+    };
+
+    Ok(Palette {
+        background: color("background", &file.background)?,
+        text: color("text", &file.text)?,
+        primary: color("primary", &file.primary)?,
+        success: color("success", &file.success)?,
+        warning: color("warning", &file.warning)?,
+        danger: color("danger", &file.danger)?,
+    })
+}
+
+/// Ask for a palette file and parse it. The theme is named after the file stem.
+pub async fn load_palette_file() -> Result<(String, Palette), String> {
+    let (path, contents) = crate::widget_helper::file_io::open_text_file("Palette", &["toml"]).await?;
+    let name = path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Imported".to_string());
+    let palette = palette_from_toml(&contents)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    Ok((name, palette))
+}
+
+/// Parses `RRGGBB` or `RRGGBBAA`, with or without a leading `#`
+pub fn parse_hex(s: &str) -> Result<Color, String> {
+    let trimmed = s.trim();
+    let digits = trimmed.strip_prefix('#').unwrap_or(trimmed);
+
+    if !matches!(digits.len(), 6 | 8) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("malformed hex color \"{}\", expected 6 or 8 hex digits", s));
+    }
+
+    let byte = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|e| e.to_string());
+    let (r, g, b) = (byte(0)?, byte(2)?, byte(4)?);
+    let a = if digits.len() == 8 { byte(6)? } else { 255 };
+
+    Ok(Color::from_rgba8(r, g, b, a as f32 / 255.0))
+}
+
+/// `#RRGGBB`, or `#RRGGBBAA` when the color isn't fully opaque
+pub fn color_to_hex(c: Color) -> String {
+    let [r, g, b, a] = c.into_rgba8();
+    if a == 255 {
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    } else {
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_palette() -> Palette {
+        Palette {
+            background: Color::from_rgb8(0x20, 0x22, 0x25),
+            text: Color::from_rgb8(0xEE, 0xEE, 0xEE),
+            primary: Color::from_rgb8(0x5E, 0x7C, 0xE2),
+            success: Color::from_rgb8(0x12, 0x66, 0x4F),
+            warning: Color::from_rgb8(0xFF, 0xC1, 0x4E),
+            danger: Color::from_rgba8(0xC3, 0x42, 0x3F, 0x80 as f32 / 255.0),
+        }
+    }
+
+    #[test]
+    fn test_palette_round_trip() {
+        let palette = sample_palette();
+        let toml = palette_to_toml(&palette).unwrap();
+        assert!(toml.contains("primary = \"#5E7CE2\""));
+        assert!(toml.contains("danger = \"#C3423F80\""));
+        assert_eq!(palette_from_toml(&toml).unwrap(), palette);
+    }
+
+    #[test]
+    fn test_parse_hex_prefix_optional() {
+        assert_eq!(parse_hex("#FF8000").unwrap(), parse_hex("ff8000").unwrap());
+        assert_eq!(parse_hex("  #ff8000 ").unwrap(), Color::from_rgb8(255, 128, 0));
+    }
+
+    #[test]
+    fn test_parse_hex_six_and_eight_digits() {
+        assert_eq!(parse_hex("#000000").unwrap().a, 1.0);
+        assert_eq!(parse_hex("#00000000").unwrap().a, 0.0);
+        assert_eq!(parse_hex("#FFFFFF80").unwrap(), Color::from_rgba8(255, 255, 255, 128.0 / 255.0));
+    }
+
+    #[test]
+    fn test_parse_hex_malformed() {
+        assert!(parse_hex("#FFF").is_err());
+        assert!(parse_hex("#FFFFFFF").is_err());
+        assert!(parse_hex("#GGGGGG").is_err());
+        assert!(parse_hex("").is_err());
+        assert!(parse_hex("#ÿÿÿ").is_err());
+    }
+
+    #[test]
+    fn test_missing_key_named_in_error() {
+        let toml = palette_to_toml(&sample_palette()).unwrap();
+        let without_warning: String = toml.lines()
+            .filter(|l| !l.starts_with("warning"))
+            .map(|l| format!("{}\n", l))
+            .collect();
+        assert_eq!(palette_from_toml(&without_warning).unwrap_err(), "Missing key `warning`");
+    }
+
+    #[test]
+    fn test_malformed_value_named_in_error() {
+        let toml = palette_to_toml(&sample_palette()).unwrap().replace("#5E7CE2", "#5E7C");
+        let err = palette_from_toml(&toml).unwrap_err();
+        assert!(err.starts_with("`primary`:"), "{}", err);
+    }
+}
@@ -0,0 +1,227 @@
+use iced::{Point, Size, Theme};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::showcase::ShowcaseState;
+use crate::theme_slots::{Slot, ThemeSlots};
+use crate::windows::WindowEnum;
+use crate::widget_helper::hints::HintRule;
+use crate::widget_helper::code_metrics::CodeBudget;
+use crate::widget_helper::code_font::CodeFontSettings;
+use crate::widget_helper::keybindings::KeyBindingOverride;
+
+/// Positions further out than this are treated as off-screen and ignored
+const MAX_COORDINATE: f32 = 16_000.0;
+const MIN_COORDINATE: f32 = -2_000.0;
+
+/// Last known size and position of a window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowPlacement {
+    pub width: f32,
+    pub height: f32,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+}
+
+impl WindowPlacement {
+    /// Stored size, never smaller than `min`
+    pub fn size(&self, min: Size) -> Size {
+        Size::new(self.width.max(min.width), self.height.max(min.height))
+    }
+
+    /// Stored position, unless it would put the window somewhere unreachable
+    pub fn position(&self) -> Option<Point> {
+        let (x, y) = (self.x?, self.y?);
+        let on_screen = |v: f32| v.is_finite() && v > MIN_COORDINATE && v < MAX_COORDINATE;
+        if on_screen(x) && on_screen(y) {
+            Some(Point::new(x, y))
+        } else {
+            None
+        }
+    }
+}
+
+/// Persistent app settings, stored as TOML in the user's config directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub main_window: Option<WindowPlacement>,
+    pub builder_window: Option<WindowPlacement>,
+    /// Builder hints switched off with "Don't show again"
+    pub disabled_hints: Vec<HintRule>,
+    /// Shortcuts changed from their defaults
+    pub keybindings: Vec<KeyBindingOverride>,
+    /// Soft limits on the generated code's size
+    pub code_budget: CodeBudget,
+    /// Font families and size of the generated code view
+    pub code_font: CodeFontSettings,
+    /// Name of the built-in theme the app starts on
+    pub startup_theme: Option<String>,
+    /// Built-in themes captured into the A/B comparison slots
+    pub theme_a: Option<String>,
+    pub theme_b: Option<String>,
+    /// Controls in the main window, as they were left
+    pub showcase: ShowcaseState,
+}
+
+impl AppSettings {
+    /// Loads saved settings. A missing or corrupted file falls back to defaults.
+    pub fn load() -> Self {
+        let Some(path) = settings_path() else { return Self::default() };
+        let Ok(contents) = std::fs::read_to_string(&path) else { return Self::default() };
+        Self::parse(&contents, &path)
+    }
+
+    /// Settings from the file's contents. Missing entries take their defaults.
+    fn parse(contents: &str, path: &std::path::Path) -> Self {
+        match toml::from_str(contents) {
+            Ok(settings) => settings,
+            Err(e) => {
+                eprintln!("Ignoring unreadable settings file {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = settings_path().ok_or_else(|| "No config directory available".to_string())?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, contents).map_err(|e| e.to_string())
+    }
+
+    /// The saved startup theme, or Dark when none is saved or the name is unknown
+    pub fn startup_theme(&self) -> Theme {
+        self.startup_theme.as_deref()
+            .and_then(builtin_theme)
+            .unwrap_or(Theme::Dark)
+    }
+
+    /// The saved A/B slots, skipping names that aren't built-in themes
+    pub fn theme_slots(&self) -> ThemeSlots {
+        ThemeSlots::new(
+            self.theme_a.as_deref().and_then(builtin_theme),
+            self.theme_b.as_deref().and_then(builtin_theme),
+        )
+    }
+
+    /// Records the slots for the next start. Custom themes can't be found again, so they aren't saved.
+    /// Returns whether anything changed.
+    pub fn record_theme_slots(&mut self, slots: &ThemeSlots) -> bool {
+        let name = |slot| slots.get(slot)
+            .filter(|theme| Theme::ALL.contains(theme))
+            .map(|theme| theme.to_string());
+        let (a, b) = (name(Slot::A), name(Slot::B));
+        let changed = a != self.theme_a || b != self.theme_b;
+        self.theme_a = a;
+        self.theme_b = b;
+        changed
+    }
+
+    pub fn placement(&self, window_type: &WindowEnum) -> Option<&WindowPlacement> {
+        match window_type {
+            WindowEnum::Main => self.main_window.as_ref(),
+            WindowEnum::WidgetVisualizer => self.builder_window.as_ref(),
+        }
+    }
+
+    fn placement_mut(&mut self, window_type: &WindowEnum, default_size: Size) -> &mut WindowPlacement {
+        let slot = match window_type {
+            WindowEnum::Main => &mut self.main_window,
+            WindowEnum::WidgetVisualizer => &mut self.builder_window,
+        };
+        slot.get_or_insert(WindowPlacement {
+            width: default_size.width,
+            height: default_size.height,
+            x: None,
+            y: None,
+        })
+    }
+
+    pub fn record_size(&mut self, window_type: &WindowEnum, size: Size) {
+        let placement = self.placement_mut(window_type, size);
+        placement.width = size.width;
+        placement.height = size.height;
+    }
+
+    pub fn record_position(&mut self, window_type: &WindowEnum, position: Point, current_size: Size) {
+        let placement = self.placement_mut(window_type, current_size);
+        placement.x = Some(position.x);
+        placement.y = Some(position.y);
+    }
+}
+
+fn builtin_theme(name: &str) -> Option<Theme> {
+    Theme::ALL.iter().find(|theme| theme.to_string() == name).cloned()
+}
+
+fn settings_path() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if let Some(config) = std::env::var_os("XDG_CONFIG_HOME") {
+        Some(PathBuf::from(config))
+    } else {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+    };
+
+    base.map(|dir| dir.join("theme-viewer").join("settings.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn placement_at(x: f32, y: f32) -> WindowPlacement {
+        WindowPlacement { width: 800.0, height: 600.0, x: Some(x), y: Some(y) }
+    }
+
+    #[test]
+    fn test_off_screen_positions_are_ignored() {
+        assert_eq!(placement_at(120.0, 80.0).position(), Some(Point::new(120.0, 80.0)));
+        assert_eq!(placement_at(-100.0, 0.0).position(), Some(Point::new(-100.0, 0.0)));
+        for (x, y) in [
+            (MAX_COORDINATE, 0.0),
+            (0.0, MIN_COORDINATE),
+            (-32_000.0, -32_000.0),
+            (f32::NAN, 0.0),
+            (0.0, f32::INFINITY),
+        ] {
+            assert_eq!(placement_at(x, y).position(), None, "({x}, {y})");
+        }
+        let unplaced = WindowPlacement { x: None, ..placement_at(0.0, 0.0) };
+        assert_eq!(unplaced.position(), None);
+    }
+
+    #[test]
+    fn test_unknown_startup_theme_falls_back_to_dark() {
+        let mut settings = AppSettings { startup_theme: Some("Nord".to_string()), ..AppSettings::default() };
+        assert_eq!(settings.startup_theme(), Theme::Nord);
+        settings.startup_theme = Some("My Custom Theme".to_string());
+        assert_eq!(settings.startup_theme(), Theme::Dark);
+        settings.startup_theme = None;
+        assert_eq!(settings.startup_theme(), Theme::Dark);
+    }
+
+    #[test]
+    fn test_corrupt_file_falls_back_to_defaults() {
+        let settings = AppSettings::parse("main_window = { width = \"wide\" }\n[[[", Path::new("settings.toml"));
+        assert!(settings.main_window.is_none());
+        assert!(settings.keybindings.is_empty());
+        assert_eq!(settings.startup_theme, None);
+    }
+
+    #[test]
+    fn test_partial_file_keeps_what_it_has() {
+        let contents = "startup_theme = \"Nord\"\n\n[builder_window]\nwidth = 1200.0\nheight = 900.0\n";
+        let settings = AppSettings::parse(contents, Path::new("settings.toml"));
+        assert_eq!(settings.startup_theme(), Theme::Nord);
+        let placement = settings.builder_window.unwrap();
+        assert_eq!((placement.width, placement.height), (1200.0, 900.0));
+        assert_eq!(placement.position(), None);
+        assert!(settings.main_window.is_none());
+        assert!(settings.disabled_hints.is_empty());
+    }
+}
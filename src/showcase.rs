@@ -0,0 +1,522 @@
+use iced::widget::{button, checkbox, column, combo_box, container, pick_list, progress_bar, radio, row, slider, svg, text, text_input, toggler};
+use iced::{gradient, Background, Degrees, Element, Theme};
+use serde::{Deserialize, Deserializer, Serialize};
+
+const TINT_SAMPLE_SVG: &[u8] = include_bytes!("../assets/tint_sample.svg");
+
+/// State of the sample widgets shown in the main window
+pub struct Showcase {
+    checkboxes: bool,
+    text_input: String,
+    password: String,
+    show_password: bool,
+    disabled_value: String,
+    radio_value: Option<RadioOption>,
+    slider_value: f32,
+    picklist: Option<Language>,
+    combobox: Option<Language>,
+    combobox_state: combo_box::State<Language>,
+    toggler: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    ButtonPressed,
+    CheckBox(bool),
+    EnteringText(String),
+    EnteringPassword(String),
+    ShowPassword(bool),
+    RadioSelected(RadioOption),
+    UpdateSlider(f32),
+    PickListSelection(Language),
+    ComboBoxSelection(Language),
+    ToggleToggler(bool),
+}
+
+/// The part of the showcase kept in the settings file between runs. The password
+/// is left out so it never ends up on disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShowcaseState {
+    pub checkboxes: bool,
+    pub text_input: String,
+    pub show_password: bool,
+    #[serde(deserialize_with = "known_or_none")]
+    pub radio_value: Option<SavedRadioOption>,
+    pub slider_value: f32,
+    #[serde(deserialize_with = "known_or_none")]
+    pub picklist: Option<SavedLanguage>,
+    #[serde(deserialize_with = "known_or_none")]
+    pub combobox: Option<SavedLanguage>,
+    pub toggler: bool,
+}
+
+impl Default for ShowcaseState {
+    fn default() -> Self {
+        Showcase::new().state()
+    }
+}
+
+/// Serialized form of `RadioOption`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SavedRadioOption {
+    Option1,
+    Option2,
+    Option3,
+}
+
+/// Serialized form of `Language`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SavedLanguage {
+    Rust,
+    Java,
+    CPlusPlus,
+    C,
+    CSharp,
+}
+
+/// Reads an optional value, treating one this version doesn't know (like a removed
+/// variant) as unset instead of failing the whole settings file
+fn known_or_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    Ok(Option::<toml::Value>::deserialize(deserializer)?.and_then(|value| value.try_into().ok()))
+}
+
+impl Showcase {
+    pub fn new() -> Self {
+        Self {
+            checkboxes: true,
+            text_input: String::new(),
+            password: String::new(),
+            show_password: false,
+            disabled_value: String::new(),
+            radio_value: None,
+            slider_value: 1_f32,
+            picklist: None,
+            combobox: None,
+            combobox_state: combo_box::State::new(Language::ALL.to_vec()),
+            toggler: false,
+        }
+    }
+
+    /// A showcase with the controls set as they were saved
+    pub fn from_state(state: &ShowcaseState) -> Self {
+        Self {
+            checkboxes: state.checkboxes,
+            text_input: state.text_input.clone(),
+            show_password: state.show_password,
+            radio_value: state.radio_value.map(RadioOption::from),
+            slider_value: state.slider_value.clamp(1.0, 100.0),
+            picklist: state.picklist.map(Language::from),
+            combobox: state.combobox.map(Language::from),
+            toggler: state.toggler,
+            ..Self::new()
+        }
+    }
+
+    pub fn state(&self) -> ShowcaseState {
+        ShowcaseState {
+            checkboxes: self.checkboxes,
+            text_input: self.text_input.clone(),
+            show_password: self.show_password,
+            radio_value: self.radio_value.map(SavedRadioOption::from),
+            slider_value: self.slider_value,
+            picklist: self.picklist.map(SavedLanguage::from),
+            combobox: self.combobox.map(SavedLanguage::from),
+            toggler: self.toggler,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::ButtonPressed => println!("Button pressed!"),
+            Message::CheckBox(b) => self.checkboxes = b,
+            Message::EnteringText(msg) => self.text_input = msg,
+            Message::EnteringPassword(msg) => self.password = msg,
+            Message::ShowPassword(b) => self.show_password = b,
+            Message::RadioSelected(selection) => self.radio_value = Some(selection),
+            Message::UpdateSlider(num) => self.slider_value = num,
+            Message::PickListSelection(language) => self.picklist = Some(language),
+            Message::ComboBoxSelection(language) => self.combobox = Some(language),
+            Message::ToggleToggler(b) => self.toggler = b,
+        }
+    }
+
+    /// The button, checkbox and form control sections, top to bottom
+    pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        let buttons = container(
+            column![
+                text("Buttons:").size(18),
+                row![
+                    column![
+                        button("Primary").style(button::primary).on_press(Message::ButtonPressed).width(100),
+                        button("Disabled").style(button::primary).width(100),
+                    ].spacing(5),
+                    column![
+                        button("Secondary").style(button::secondary).on_press(Message::ButtonPressed).width(100),
+                        button("Disabled").style(button::secondary).width(100),
+                    ].spacing(5),
+                    column![
+                        button("Success").style(button::success).on_press(Message::ButtonPressed).width(100),
+                        button("Disabled").style(button::success).width(100)
+                    ].spacing(5),
+                    column![
+                        button("Warning").style(button::warning).on_press(Message::ButtonPressed).width(100),
+                        button("Disabled").style(button::warning).width(100)
+                    ].spacing(5),
+                    column![
+                        button("Danger").style(button::danger).on_press(Message::ButtonPressed).width(100),
+                        button("Disabled").style(button::danger).width(100)
+                    ].spacing(5),
+                    column![
+                        button("Text").style(button::text).on_press(Message::ButtonPressed).width(100),
+                        button("Disabled").style(button::text).width(100)
+                    ].spacing(5),
+                ].spacing(10),
+            ]
+            .spacing(10)
+            .padding(10)
+        )
+        .style(container::bordered_box)
+        .padding(
+            iced::Padding {
+                top: 0_f32, 
+                right: 10_f32,
+                bottom: 10_f32,
+                left: 10_f32
+            }
+        )
+        .width(iced::Length::Fill);
+
+        let checkboxes = container(
+            column![
+                text("Checkboxes:").size(18),
+                row![
+                    column![
+                        checkbox("Primary", self.checkboxes).style(checkbox::primary).on_toggle(Message::CheckBox).width(130),
+                        checkbox("Primary", self.checkboxes).style(checkbox::primary).width(130)
+                    ].spacing(5),
+                    column![
+                        checkbox("Secondary", self.checkboxes).style(checkbox::secondary).on_toggle(Message::CheckBox).width(130),
+                        checkbox("Secondary", self.checkboxes).style(checkbox::secondary).width(130)
+                    ].spacing(5),
+                    column![
+                        checkbox("Success", self.checkboxes).style(checkbox::success).on_toggle(Message::CheckBox).width(130),
+                        checkbox("Success", self.checkboxes).style(checkbox::success).width(130)
+                    ].spacing(5),
+                    column![
+                        checkbox("Danger", self.checkboxes).style(checkbox::danger).on_toggle(Message::CheckBox).width(130),
+                        checkbox("Danger", self.checkboxes).style(checkbox::danger).width(130)
+                    ].spacing(5),
+                ],
+            ]
+            .spacing(10)
+            .padding(10)
+        )
+        .style(container::bordered_box)
+        .padding(
+            iced::Padding {
+                top: 0_f32, 
+                right: 10_f32,
+                bottom: 10_f32,
+                left: 10_f32
+            }
+        )
+        .width(iced::Length::Fill);
+
+        let range = std::ops::RangeInclusive::new(1_f32,100_f32);
+
+        let form_controls = container(
+            column![
+                text("Form Controls:").size(18),
+
+                // Text Inputs
+                text("Text Inputs: "),
+                column![
+                    text_input("Text input", &self.text_input).on_input(Message::EnteringText).width(650)
+                ].spacing(5),
+                column![
+                    row![
+                        text_input("Password", &self.password).on_input(Message::EnteringPassword).secure(!self.show_password),
+                        checkbox("Show Password", self.show_password).on_toggle(Message::ShowPassword)
+                    ].align_y(iced::Alignment::Center).spacing(10).width(640),
+                ].spacing(5),
+                column![
+                    text_input("Disabled Text Input", &self.disabled_value).width(650)
+                ].spacing(5),
+                column![
+
+                ].spacing(5),
+
+                // Radio Buttons
+                text("Radio Buttons: "),
+                row![
+                    radio(
+                        "Option 1", 
+                        RadioOption::Option1, 
+                        self.radio_value, 
+                        Message::RadioSelected
+                    ).width(150),
+                    radio(
+                        "Option 2", 
+                        RadioOption::Option2, 
+                        self.radio_value, 
+                        Message::RadioSelected
+                    ).width(150),
+                    radio(
+                        "Option 3", 
+                        RadioOption::Option3, 
+                        self.radio_value, 
+                        Message::RadioSelected
+                    ).width(150),
+                ],
+
+                
+                // Slider
+                text("Slider: "),
+                row![
+                    slider(
+                        range.clone(),
+                         self.slider_value,
+                         Message::UpdateSlider),
+                ].width(650),
+
+                // Progress Bar
+                text("Progress Bar: "),
+                row![
+                    progress_bar(
+                        range.clone(), 
+                        self.slider_value)
+                ].width(650),
+
+                
+                // Pick List
+                text("Pick List: "),
+                row![
+                    pick_list(
+                        Language::ALL, 
+                        self.picklist, 
+                        Message::PickListSelection)
+                ].width(650),
+
+                // Combo Box
+                text("Combo Box: "),
+                row![
+                    combo_box(
+                        &self.combobox_state, 
+                        "Select", 
+                        self.combobox.as_ref(), 
+                        Message::ComboBoxSelection)
+                ].width(650),
+
+                // Toggler
+                text("Toggler: "),
+                toggler(self.toggler).on_toggle(Message::ToggleToggler),
+
+                // Svg tinting, switch themes to see the tinted icons follow along
+                text("SVG (untinted, theme text color, theme primary): "),
+                row![
+                    svg(svg::Handle::from_memory(TINT_SAMPLE_SVG)).width(32).height(32),
+                    svg(svg::Handle::from_memory(TINT_SAMPLE_SVG)).width(32).height(32)
+                        .style(|theme: &Theme, _status| svg::Style { color: Some(theme.palette().text) }),
+                    svg(svg::Handle::from_memory(TINT_SAMPLE_SVG)).width(32).height(32)
+                        .style(|theme: &Theme, _status| svg::Style { color: Some(theme.palette().primary) }),
+                ].spacing(20),
+
+                // Gradient background, stops taken from the theme so it follows theme switches
+                text("Gradient Background (primary strong to weak): "),
+                container(text("Hero section"))
+                    .center_y(32)
+                    .padding([0, 10])
+                    .width(650)
+                    .style(|theme: &Theme| {
+                        let primary = theme.extended_palette().primary;
+                        container::Style {
+                            text_color: Some(primary.strong.text),
+                            background: Some(Background::Gradient(
+                                gradient::Linear::new(Degrees(90.0))
+                                    .add_stop(0.0, primary.strong.color)
+                                    .add_stop(1.0, primary.weak.color)
+                                    .into(),
+                            )),
+                            ..container::Style::default()
+                        }
+                    }),
+            ]
+            .spacing(10)
+            .padding(10)
+        )
+        .style(container::bordered_box)
+        .padding(
+            iced::Padding {
+                top: 0_f32, 
+                right: 10_f32,
+                bottom: 10_f32,
+                left: 10_f32
+            }
+        )
+        .width(iced::Length::Fill);
+
+        column![
+            buttons,
+            checkboxes,
+            form_controls
+        ].spacing(10).into()
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadioOption {
+    Option1,
+    Option2,
+    Option3,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Java,
+    CPlusPlus,
+    C,
+    CSharp
+}
+
+impl Language {
+    const ALL: [Language; 5] = [
+        Language::Rust,
+        Language::Java,
+        Language::CPlusPlus,
+        Language::C,
+        Language::CSharp,
+    ];
+}
+
+impl From<RadioOption> for SavedRadioOption {
+    fn from(option: RadioOption) -> Self {
+        match option {
+            RadioOption::Option1 => Self::Option1,
+            RadioOption::Option2 => Self::Option2,
+            RadioOption::Option3 => Self::Option3,
+        }
+    }
+}
+
+impl From<SavedRadioOption> for RadioOption {
+    fn from(option: SavedRadioOption) -> Self {
+        match option {
+            SavedRadioOption::Option1 => Self::Option1,
+            SavedRadioOption::Option2 => Self::Option2,
+            SavedRadioOption::Option3 => Self::Option3,
+        }
+    }
+}
+
+impl From<Language> for SavedLanguage {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::Rust => Self::Rust,
+            Language::Java => Self::Java,
+            Language::CPlusPlus => Self::CPlusPlus,
+            Language::C => Self::C,
+            Language::CSharp => Self::CSharp,
+        }
+    }
+}
+
+impl From<SavedLanguage> for Language {
+    fn from(language: SavedLanguage) -> Self {
+        match language {
+            SavedLanguage::Rust => Self::Rust,
+            SavedLanguage::Java => Self::Java,
+            SavedLanguage::CPlusPlus => Self::CPlusPlus,
+            SavedLanguage::C => Self::C,
+            SavedLanguage::CSharp => Self::CSharp,
+        }
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Language::Rust => write!(f, "Rust"),
+            Language::Java => write!(f, "Java"),
+            Language::CPlusPlus => write!(f, "CPlusPlus"),
+            Language::C => write!(f, "C"),
+            Language::CSharp => write!(f, "CSharp"),
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_match_main_window() {
+        let showcase = Showcase::new();
+        assert!(showcase.checkboxes);
+        assert_eq!(showcase.slider_value, 1.0);
+        assert_eq!(showcase.radio_value, None);
+        assert!(!showcase.toggler);
+    }
+
+    #[test]
+    fn test_every_message_updates_its_control() {
+        let mut showcase = Showcase::new();
+        showcase.update(Message::ButtonPressed);
+        showcase.update(Message::CheckBox(false));
+        showcase.update(Message::EnteringText("hello".to_string()));
+        showcase.update(Message::EnteringPassword("hunter2".to_string()));
+        showcase.update(Message::ShowPassword(true));
+        showcase.update(Message::RadioSelected(RadioOption::Option2));
+        showcase.update(Message::UpdateSlider(42.0));
+        showcase.update(Message::PickListSelection(Language::Java));
+        showcase.update(Message::ComboBoxSelection(Language::C));
+        showcase.update(Message::ToggleToggler(true));
+
+        assert!(!showcase.checkboxes);
+        assert_eq!(showcase.text_input, "hello");
+        assert_eq!(showcase.password, "hunter2");
+        assert!(showcase.show_password);
+        assert_eq!(showcase.radio_value, Some(RadioOption::Option2));
+        assert_eq!(showcase.slider_value, 42.0);
+        assert_eq!(showcase.picklist, Some(Language::Java));
+        assert_eq!(showcase.combobox, Some(Language::C));
+        assert!(showcase.toggler);
+        assert!(showcase.disabled_value.is_empty());
+    }
+
+    #[test]
+    fn test_state_round_trips_without_password() {
+        let mut showcase = Showcase::new();
+        showcase.update(Message::EnteringText("a long line to check contrast".to_string()));
+        showcase.update(Message::EnteringPassword("hunter2".to_string()));
+        showcase.update(Message::RadioSelected(RadioOption::Option3));
+        showcase.update(Message::UpdateSlider(64.0));
+        showcase.update(Message::ComboBoxSelection(Language::CSharp));
+
+        let saved = toml::to_string(&showcase.state()).unwrap();
+        assert!(!saved.contains("hunter2"));
+
+        let restored = Showcase::from_state(&toml::from_str(&saved).unwrap());
+        assert_eq!(restored.state(), showcase.state());
+        assert!(restored.password.is_empty());
+    }
+
+    #[test]
+    fn test_state_tolerates_missing_and_unknown_fields() {
+        let state: ShowcaseState = toml::from_str("slider_value = 30.0\npicklist = \"Cobol\"\nfuture_field = 1\n").unwrap();
+        assert_eq!(state.slider_value, 30.0);
+        assert_eq!(state.picklist, None);
+        assert!(state.checkboxes);
+
+        let empty: ShowcaseState = toml::from_str("").unwrap();
+        assert_eq!(empty, ShowcaseState::default());
+    }
+}
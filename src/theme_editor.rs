@@ -0,0 +1,223 @@
+use iced::theme::Palette;
+use iced::widget::{button, column, row, text, text_input};
+use iced::{Color, Element, Length, Theme};
+use widgets::color_picker;
+
+/// A palette color the editor offers a picker for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Background,
+    Text,
+    Primary,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl Role {
+    pub const ALL: [Role; 6] = [Role::Background, Role::Text, Role::Primary, Role::Success, Role::Warning, Role::Danger];
+
+    pub fn get(self, palette: &Palette) -> Color {
+        match self {
+            Role::Background => palette.background,
+            Role::Text => palette.text,
+            Role::Primary => palette.primary,
+            Role::Success => palette.success,
+            Role::Warning => palette.warning,
+            Role::Danger => palette.danger,
+        }
+    }
+
+    pub fn set(self, palette: &mut Palette, color: Color) {
+        match self {
+            Role::Background => palette.background = color,
+            Role::Text => palette.text = color,
+            Role::Primary => palette.primary = color,
+            Role::Success => palette.success = color,
+            Role::Warning => palette.warning = color,
+            Role::Danger => palette.danger = color,
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Role::Background => "Background",
+            Role::Text => "Text",
+            Role::Primary => "Primary",
+            Role::Success => "Success",
+            Role::Warning => "Warning",
+            Role::Danger => "Danger",
+        })
+    }
+}
+
+/// A custom theme being edited. Every change goes straight into the theme list, replacing the
+/// entry it went in under last time, so renaming it never leaves the old name behind.
+#[derive(Debug, Clone)]
+pub struct ThemeEditor {
+    pub name: String,
+    pub palette: Palette,
+    /// Name the theme list holds the edited theme under, None before it first goes in
+    applied: Option<String>,
+    /// Why the last edit couldn't go into the list
+    pub error: Option<String>,
+}
+
+impl ThemeEditor {
+    /// Starts from `from`'s colors. A custom theme is edited in place, a built-in one is copied
+    /// under a new name.
+    pub fn new(from: &Theme, themes: &[Theme]) -> Self {
+        let (name, applied) = if Theme::ALL.contains(from) {
+            (unused_name(&format!("{} Custom", from), themes), None)
+        } else {
+            (from.to_string(), Some(from.to_string()))
+        };
+        Self { name, palette: from.palette(), applied, error: None }
+    }
+
+    pub fn theme(&self) -> Theme {
+        Theme::custom(self.name.trim().to_string(), self.palette)
+    }
+
+    /// Puts the edited theme into `themes`, in place of the entry it last went in as. Returns
+    /// the theme and the name it replaced. Errors, leaving the list alone, while the name is
+    /// blank or another theme's.
+    pub fn apply(&mut self, themes: &mut Vec<Theme>) -> Result<(Theme, Option<String>), String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("A theme needs a name".to_string());
+        }
+        if themes.iter().any(|theme| theme.to_string() == name && self.applied.as_deref() != Some(name)) {
+            return Err(format!("A theme named \"{}\" already exists", name));
+        }
+        let theme = self.theme();
+        let previous = self.applied.replace(name.to_string());
+        match previous.as_deref().and_then(|old| themes.iter().position(|theme| theme.to_string() == old)) {
+            Some(index) => themes[index] = theme.clone(),
+            None => themes.push(theme.clone()),
+        }
+        Ok((theme, previous))
+    }
+
+    pub fn update(&mut self, message: &Message) {
+        match message {
+            Message::NameChanged(name) => self.name = name.clone(),
+            Message::ColorChanged(role, color) => role.set(&mut self.palette, *color),
+            Message::Open | Message::Close => {}
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let pickers = row(Role::ALL.map(|role| {
+            column![
+                text(role.to_string()).size(14),
+                color_picker::ColorButton::new(role.get(&self.palette), move |color| Message::ColorChanged(role, color))
+                    .title(role.to_string())
+                    .width(Length::Fill)
+                    .height(Length::Fixed(40.0))
+                    .show_hex(),
+            ]
+            .spacing(3)
+            .width(Length::Fill)
+            .into()
+        }))
+        .spacing(10);
+
+        let error: Element<'_, Message> = match &self.error {
+            Some(error) => text(error).size(14).style(text::danger).into(),
+            None => row![].into(),
+        };
+
+        column![
+            row![
+                text("Name").size(14),
+                text_input("Theme name", &self.name).on_input(Message::NameChanged).width(Length::Fixed(220.0)),
+                button("Done").style(button::secondary).on_press(Message::Close),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+            error,
+            pickers,
+        ]
+        .spacing(5)
+        .into()
+    }
+}
+
+/// `base`, numbered from 2 if a theme in `themes` already has it
+fn unused_name(base: &str, themes: &[Theme]) -> String {
+    let taken = |name: &str| themes.iter().any(|theme| theme.to_string() == name);
+    let mut name = base.to_string();
+    let mut n = 2;
+    while taken(&name) {
+        name = format!("{} {}", base, n);
+        n += 1;
+    }
+    name
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Starts editing from the current theme
+    Open,
+    NameChanged(String),
+    ColorChanged(Role, Color),
+    Close,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renaming_replaces_the_entry() {
+        let mut themes = Theme::ALL.to_vec();
+        let mut editor = ThemeEditor::new(&Theme::Nord, &themes);
+        assert_eq!(editor.name, "Nord Custom");
+
+        editor.update(&Message::ColorChanged(Role::Primary, Color::from_rgb(1.0, 0.0, 0.0)));
+        let (theme, previous) = editor.apply(&mut themes).unwrap();
+        assert_eq!(previous, None);
+        assert_eq!(theme.palette().primary, Color::from_rgb(1.0, 0.0, 0.0));
+        assert_eq!(themes.len(), Theme::ALL.len() + 1);
+
+        editor.update(&Message::NameChanged("Brand".to_string()));
+        let (_, previous) = editor.apply(&mut themes).unwrap();
+        assert_eq!(previous.as_deref(), Some("Nord Custom"));
+        assert_eq!(themes.len(), Theme::ALL.len() + 1);
+        assert_eq!(themes.iter().filter(|theme| theme.to_string() == "Brand").count(), 1);
+        assert!(!themes.iter().any(|theme| theme.to_string() == "Nord Custom"));
+    }
+
+    #[test]
+    fn test_names_in_use_are_refused() {
+        let mut themes = Theme::ALL.to_vec();
+        let mut editor = ThemeEditor::new(&Theme::Dark, &themes);
+        editor.apply(&mut themes).unwrap();
+
+        editor.update(&Message::NameChanged("Dracula".to_string()));
+        assert!(editor.apply(&mut themes).is_err());
+        editor.update(&Message::NameChanged("  ".to_string()));
+        assert!(editor.apply(&mut themes).is_err());
+        assert_eq!(themes.len(), Theme::ALL.len() + 1);
+
+        // A second copy of the same built-in theme gets a numbered name
+        assert_eq!(ThemeEditor::new(&Theme::Dark, &themes).name, "Dark Custom 2");
+    }
+
+    #[test]
+    fn test_custom_theme_is_edited_in_place() {
+        let mut themes = Theme::ALL.to_vec();
+        let custom = Theme::custom("Mine".to_string(), Theme::Light.palette());
+        themes.push(custom.clone());
+
+        let mut editor = ThemeEditor::new(&custom, &themes);
+        assert_eq!(editor.name, "Mine");
+        editor.update(&Message::ColorChanged(Role::Danger, Color::BLACK));
+        editor.apply(&mut themes).unwrap();
+        assert_eq!(themes.len(), Theme::ALL.len() + 1);
+        assert_eq!(themes.last().unwrap().palette().danger, Color::BLACK);
+    }
+}
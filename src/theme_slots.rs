@@ -0,0 +1,111 @@
+use iced::Theme;
+
+/// One of the two themes the main window flips between
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    A,
+    B,
+}
+
+impl Slot {
+    pub fn other(self) -> Self {
+        match self {
+            Slot::A => Slot::B,
+            Slot::B => Slot::A,
+        }
+    }
+}
+
+impl std::fmt::Display for Slot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Slot::A => write!(f, "A"),
+            Slot::B => write!(f, "B"),
+        }
+    }
+}
+
+/// Two captured themes for A/B comparison. Picking a theme the usual way leaves them alone.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeSlots {
+    a: Option<Theme>,
+    b: Option<Theme>,
+    /// Slot the current theme came from, if it did
+    active: Option<Slot>,
+}
+
+impl ThemeSlots {
+    pub fn new(a: Option<Theme>, b: Option<Theme>) -> Self {
+        Self { a, b, active: None }
+    }
+
+    pub fn get(&self, slot: Slot) -> Option<&Theme> {
+        match slot {
+            Slot::A => self.a.as_ref(),
+            Slot::B => self.b.as_ref(),
+        }
+    }
+
+    /// Stores `theme` in `slot`, which becomes the active one since it now holds the current theme
+    pub fn capture(&mut self, slot: Slot, theme: Theme) {
+        match slot {
+            Slot::A => self.a = Some(theme),
+            Slot::B => self.b = Some(theme),
+        }
+        self.active = Some(slot);
+    }
+
+    /// The theme from the other slot, or from A when the current theme came from neither.
+    /// Errors while a slot is still empty.
+    pub fn toggle(&mut self, current: &Theme) -> Result<Theme, String> {
+        let (Some(_), Some(_)) = (&self.a, &self.b) else {
+            return Err("Capture a theme into both A and B to toggle between them".to_string());
+        };
+        let next = self.active_slot(current).map_or(Slot::A, Slot::other);
+        self.active = Some(next);
+        Ok(self.get(next).cloned().expect("both slots are filled"))
+    }
+
+    /// Slot the current theme was taken from, None once another theme was picked
+    pub fn active_slot(&self, current: &Theme) -> Option<Slot> {
+        self.active.filter(|&slot| self.get(slot) == Some(current))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_flips_between_slots() {
+        let mut slots = ThemeSlots::default();
+        slots.capture(Slot::A, Theme::Nord);
+        slots.capture(Slot::B, Theme::Dracula);
+        assert_eq!(slots.active_slot(&Theme::Dracula), Some(Slot::B));
+
+        assert_eq!(slots.toggle(&Theme::Dracula), Ok(Theme::Nord));
+        assert_eq!(slots.active_slot(&Theme::Nord), Some(Slot::A));
+        assert_eq!(slots.toggle(&Theme::Nord), Ok(Theme::Dracula));
+    }
+
+    #[test]
+    fn test_toggle_needs_both_slots() {
+        let mut slots = ThemeSlots::default();
+        slots.capture(Slot::A, Theme::Nord);
+        assert!(slots.toggle(&Theme::Nord).is_err());
+        assert_eq!(slots.get(Slot::A), Some(&Theme::Nord));
+        assert_eq!(slots.active_slot(&Theme::Nord), Some(Slot::A));
+    }
+
+    #[test]
+    fn test_picking_another_theme_keeps_slots() {
+        let mut slots = ThemeSlots::default();
+        slots.capture(Slot::A, Theme::Nord);
+        slots.capture(Slot::B, Theme::Dracula);
+
+        // The picker moved on to a third theme, so no slot is active and toggling starts at A
+        assert_eq!(slots.active_slot(&Theme::Light), None);
+        assert_eq!(slots.toggle(&Theme::Light), Ok(Theme::Nord));
+        assert_eq!(slots.get(Slot::B), Some(&Theme::Dracula));
+    }
+}
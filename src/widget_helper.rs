@@ -1,9 +1,9 @@
 use iced::{
     alignment::{Horizontal, Vertical}, widget::{
-        button, checkbox, column, container, space, pick_list, progress_bar, radio, row, scrollable, slider, text, text_input, toggler, rule, vertical_slider, Space, tooltip, svg, image, pin, stack, mouse_area, combo_box, qr_code, markdown, text_editor,
+        button, checkbox, column, container, space, pick_list, progress_bar, radio, row, scrollable, slider, text, text_input, toggler, rule, vertical_slider, Space, tooltip, svg, image, pin, stack, mouse_area, responsive, combo_box, qr_code, markdown, text_editor,
     }, Alignment, Background, Border, Color, Element, Font, Length, Padding, Shadow, Theme, Vector, ContentFit, Point, mouse::Interaction,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use crate::{widget::generic_overlay::overlay_button, widget_helper::styles::stylefn_builders};
 mod controls;
@@ -15,9 +15,80 @@ use views::type_editor::{self, TypeEditorView};
 pub mod type_system;
 use type_system::TypeSystem;
 pub mod panegrid_dashboard;
-use code_generator::{CodeGenerator, build_code_view_with_height};
+mod layout_report;
+pub mod file_io;
+mod project_export;
+mod command_palette;
+mod code_search;
+pub mod code_metrics;
+pub mod hints;
+mod history;
+pub mod keybindings;
+mod preview_viewport;
+mod design_tokens;
+mod project_diff;
+mod contrast;
+mod numeric_input;
+mod bulk_rename;
+mod color_names;
+mod undo;
+mod message_wiring;
+mod opener;
+mod sections;
+mod enum_form;
+use enum_form::{EnumFormMessage, NewEnumForm};
+mod pane_width;
+use pane_width::PaneWidths;
+pub mod code_font;
+mod type_references;
+use type_references::TypeReferences;
+mod code_generation;
+mod window_preset;
+use window_preset::{WindowPreset, WindowSettings};
+mod asset_library;
+use asset_library::{Asset, AssetLibrary, AssetMessage, AssetRename};
+mod panic_guard;
+mod option_import;
+use option_import::{ImportedOptions, OptionImportForm, OptionImportMessage};
+mod reference_image;
+mod tags;
+mod min_size;
+mod notification_history;
+mod file_header;
+mod named_styles;
+mod printable;
+mod fill_portions;
+mod view_import;
+use view_import::{ViewImportForm, ViewImportMessage};
+mod workspace;
+use workspace::{Workspace, WorkspaceMessage};
+mod project_file;
+use project_file::{LoadedProject, ProjectFile};
+use tags::TagCount;
+use file_header::{FileHeader, FileHeaderMessage};
+use named_styles::{ContainerLook, StyleMessage, StyleRegistry};
+use printable::{PrintFormat, PrintSettings};
+use notification_history::{NotificationEntry, NotificationHistory, NotificationMessage};
+use reference_image::{ColorTarget, ReferenceMessage, ReferencePanel, REFERENCE_PANEL_WIDTH};
+use panic_guard::{CaughtPanic, GuardedPanel, PanicGuards};
+use code_generation::{CodeGeneration, GeneratedCode, GenerationSettings, GenerationSnapshot};
+use code_font::{CodeFontSettings, CodeFontSize, CodeViewFont};
+use code_generator::{CodeGenerator, CodeStyle, StubStyle, Token, build_searchable_code_view, uses_file_dialogs};
 use widgets::tree::{tree_handle, branch, DropInfo, DropPosition, Branch};
 use iced::widget::themer;
+use iced::{event, keyboard, Event, Subscription};
+use command_palette::{CommandPalette, PaletteAction, COMMAND_PALETTE_INPUT};
+use code_search::{CodeSearch, CODE_VIEW_SCROLLABLE};
+use code_metrics::{CodeBudget, CodeMetric, CodeMetrics};
+use hints::{Hint, HintFix, HintRule, RefusedAdd};
+use history::PropertyHistory;
+use undo::UndoStack;
+use message_wiring::WiringProblem;
+use numeric_input::{NumericField, NumericInputs};
+use bulk_rename::{BulkRename, BulkRenameEdit, MAX_ZERO_PAD};
+use keybindings::{KeyAction, KeyBindingOverride, KeyCombo, Keybindings};
+use preview_viewport::{PreviewViewport, ViewportMessage, PREVIEW_VIEWPORT};
+use design_tokens::{PaletteRole, TokenFormat, TokenImport, TokenImportEdit, TokenSet};
 use crate::icon;
 
 // ============================================================================
@@ -59,6 +130,18 @@ pub enum PropertyChange {
     BorderRadius(f32),
     BorderColor(Color),
     BackgroundColor(Color),
+    GradientEnabled(bool),
+    GradientStart(Color),
+    GradientEnd(Color),
+    GradientAngle(f32),
+    FloatAnchor(Option<FloatAnchor>),
+    FloatOffsetX(f32),
+    FloatOffsetY(f32),
+    SectionAnchor(bool),
+    SectionName(String),
+    BreakpointEnabled(bool),
+    BreakpointWidth(f32),
+    StackBelowBreakpoint(bool),
     HasShadow(bool),
     ShadowOffsetX(f32),
     ShadowOffsetY(f32),
@@ -112,6 +195,7 @@ pub enum PropertyChange {
     // Radio properties
     RadioSelectedIndex(usize),
     RadioOptions(Vec<String>),
+    RadioOptionsEdit(OptionListEdit),
     RadioLabel(String),
     RadioSize(f32),
     RadioSpacing(f32),
@@ -137,6 +221,9 @@ pub enum PropertyChange {
     TogglerLabel(String),
     TogglerSize(f32),
     TogglerSpacing(f32),
+
+    // Checkbox, Toggler and Radio
+    LabelPosition(LabelPosition),
     
     // PickList properties
     PickListSelected(Option<String>),
@@ -158,6 +245,11 @@ pub enum PropertyChange {
     // Svg
     SvgPath(String),
     SvgFit(ContentFitChoice),
+    SvgTint(SvgTint),
+    SvgTintColor(Color),
+    // Image + Svg picked from the asset library, with the entry's id
+    ImageAsset(Option<Uuid>, String),
+    SvgAsset(Option<Uuid>, String),
     // Tooltip
     TooltipText(String),
     TooltipPosition(TooltipPosition),
@@ -174,6 +266,10 @@ pub enum PropertyChange {
     ComboBoxSize(f32),
     ComboBoxPadding(f32),
     ComboBoxEnumId(Option<Uuid>),
+    ComboBoxInputBackground(Option<Color>),
+    ComboBoxInputTextColor(Option<Color>),
+    ComboBoxMenuBackground(Option<Color>),
+    ComboBoxMenuHeight(Option<f32>),
     
     // Markdown
     MarkdownContent(text_editor::Action),
@@ -200,6 +296,31 @@ pub enum PropertyChange {
     MouseAreaOnExit(bool),
     MouseAreaInteraction(Option<MouseInteraction>),
 
+    // File dialog capability of Buttons and MouseAreas
+    FileDialog(FileDialog),
+    FileDialogPathText(Option<WidgetId>),
+
+    // Conditional style of Containers and TextInputs
+    StyleCondition(Option<StyleCondition>),
+    ConditionBorderColor(Color),
+    ConditionBackgroundColor(Color),
+    PreviewCondition(bool),
+
+    // Preview data, never generated
+    PreviewText(Option<String>),
+    PreviewProgress(Option<f32>),
+    PreviewSelection(Option<String>),
+
+    // Tags, never generated. Adding and removing one leaves a widget's other tags alone,
+    // so the same change can go to a whole selection.
+    Tags(Vec<String>),
+    AddTag(String),
+    RemoveTag(String),
+
+    // Named style, with the look it carries so the widget's own fields keep drawing it.
+    // None unlinks the widget and leaves the look inline.
+    NamedStyle(Option<Uuid>, ContainerLook),
+
     //Do Nothing
     Noop
 }
@@ -375,6 +496,18 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
         PropertyChange::AlignY(v) => properties.align_y = v,
 
         PropertyChange::BackgroundColor(value) => properties.background_color = value,
+        PropertyChange::GradientEnabled(value) => properties.gradient_enabled = value,
+        PropertyChange::GradientStart(value)   => properties.gradient_start = value,
+        PropertyChange::GradientEnd(value)     => properties.gradient_end = value,
+        PropertyChange::GradientAngle(value)   => properties.gradient_angle = value.rem_euclid(360.0),
+        PropertyChange::FloatAnchor(value)     => properties.float_anchor = value,
+        PropertyChange::FloatOffsetX(value)    => properties.float_offset_x = value.max(0.0),
+        PropertyChange::FloatOffsetY(value)    => properties.float_offset_y = value.max(0.0),
+        PropertyChange::SectionAnchor(value)   => properties.section_anchor = value,
+        PropertyChange::SectionName(value)     => properties.section_name = value,
+        PropertyChange::BreakpointEnabled(value) => properties.breakpoint_enabled = value,
+        PropertyChange::BreakpointWidth(value) => properties.breakpoint_width = value.max(1.0),
+        PropertyChange::StackBelowBreakpoint(value) => properties.stack_below_breakpoint = value,
 
         PropertyChange::TextContent(value)          => properties.text_content = value,
         PropertyChange::TextSize(value)             => properties.text_size = value,
@@ -441,6 +574,11 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
                 properties.radio_selected_index = 0;
             }
         },
+        PropertyChange::RadioOptionsEdit(edit) => {
+            properties.radio_selected_index = edit
+                .apply(&mut properties.radio_options, Some(properties.radio_selected_index))
+                .unwrap_or(0);
+        },
         PropertyChange::RadioLabel(value)   => properties.radio_label = value,
         PropertyChange::RadioSize(value)    => properties.radio_size = value,
         PropertyChange::RadioSpacing(value) => properties.radio_spacing = value,
@@ -472,11 +610,18 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
         PropertyChange::TogglerLabel(value)     => properties.toggler_label = value,
         PropertyChange::TogglerSize(value)      => properties.toggler_size = value,
         PropertyChange::TogglerSpacing(value)   => properties.toggler_spacing = value,
+        PropertyChange::LabelPosition(value)    => properties.label_position = value,
         
         // PickList properties
         PropertyChange::PickListSelected(value)     => properties.picklist_selected = value,
         PropertyChange::PickListPlaceholder(value)  => properties.picklist_placeholder = value,
-        PropertyChange::PickListOptions(value)      => properties.picklist_options = value,
+        PropertyChange::PickListOptions(value)      => {
+            properties.picklist_options = value;
+            // A selection the new list no longer has would show a value the user can't pick
+            if properties.picklist_selected.as_ref().is_some_and(|s| !properties.picklist_options.contains(s)) {
+                properties.picklist_selected = None;
+            }
+        }
 
         // Rule properties
         PropertyChange::RuleThickness(v)   => properties.rule_thickness  = v,
@@ -496,6 +641,18 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
         // Svg properties
         PropertyChange::SvgPath(v)          => properties.svg_path = v,
         PropertyChange::SvgFit(v)           => properties.svg_fit = v,
+        PropertyChange::SvgTint(v)          => properties.svg_tint = v,
+        PropertyChange::SvgTintColor(v)     => properties.svg_tint_color = v,
+
+        // Library assets
+        PropertyChange::ImageAsset(id, path) => {
+            properties.library_asset = id;
+            properties.image_path = path;
+        }
+        PropertyChange::SvgAsset(id, path) => {
+            properties.library_asset = id;
+            properties.svg_path = path;
+        }
 
         // Tooltip properties
         PropertyChange::TooltipText(v)      => properties.tooltip_text = v,
@@ -505,6 +662,9 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
         PropertyChange::ComboBoxSelected(v) => properties.combobox_selected = v,
         PropertyChange::ComboBoxPlaceholder(v) => properties.combobox_placeholder = v,
         PropertyChange::ComboBoxState(v) => {
+            if properties.combobox_selected.as_ref().is_some_and(|s| !v.contains(s)) {
+                properties.combobox_selected = None;
+            }
             properties.combobox_options = v.clone();
             // Recreate state with new options
             properties.combobox_state = combo_box::State::new(v);
@@ -515,6 +675,10 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
         PropertyChange::ComboBoxUseOnClose(v) => properties.combobox_use_on_close = v,
         PropertyChange::ComboBoxSize(v) => properties.combobox_size = v,
         PropertyChange::ComboBoxPadding(v) => properties.combobox_padding = v,
+        PropertyChange::ComboBoxInputBackground(v) => properties.combobox_input_background = v,
+        PropertyChange::ComboBoxInputTextColor(v) => properties.combobox_input_text_color = v,
+        PropertyChange::ComboBoxMenuBackground(v) => properties.combobox_menu_background = v,
+        PropertyChange::ComboBoxMenuHeight(v) => properties.combobox_menu_height = v,
         PropertyChange::ComboBoxEnumId(id) => {
             //Set referenced_enum Id
             properties.referenced_enum = id;
@@ -539,7 +703,11 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
                 properties.markdown_source.perform(action);
 
                 if is_edit {
-                    properties.markdown_content = markdown::Content::parse(&properties.markdown_source.text()).items().to_vec();
+                    if properties.markdown_source.line_count() > MARKDOWN_DEBOUNCE_LINES {
+                        properties.markdown_edited_at = Some(std::time::Instant::now());
+                    } else {
+                        properties.reparse_markdown();
+                    }
                 }
         },
         PropertyChange::MarkdownTextSize(v) => properties.markdown_text_size = v,
@@ -549,6 +717,24 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
         
         PropertyChange::ThemerTheme(v) => properties.themer_theme = v,
 
+        PropertyChange::PreviewText(v)       => properties.preview_text = v,
+        PropertyChange::PreviewProgress(v)   => {
+            let (lo, hi) = (properties.progress_min.min(properties.progress_max), properties.progress_min.max(properties.progress_max));
+            properties.preview_progress = v.map(|v| v.clamp(lo, hi));
+        }
+        PropertyChange::PreviewSelection(v)  => properties.preview_selection = v,
+
+        PropertyChange::Tags(v) => properties.tags = v,
+        PropertyChange::AddTag(tag) => {
+            tags::add(&mut properties.tags, &tag);
+        }
+        PropertyChange::RemoveTag(tag) => properties.tags.retain(|t| *t != tag),
+
+        PropertyChange::NamedStyle(id, look) => {
+            properties.named_style = id;
+            look.apply(properties);
+        }
+
         PropertyChange::Noop => {},
 
         PropertyChange::MouseAreaOnPress(b) => properties.mousearea_on_press = b,
@@ -563,14 +749,207 @@ pub fn apply_property_change(properties: &mut Properties, change: PropertyChange
         PropertyChange::MouseAreaOnMove(b) => properties.mousearea_on_move = b,
         PropertyChange::MouseAreaOnExit(b) => properties.mousearea_on_exit = b,
         PropertyChange::MouseAreaInteraction(interaction) => properties.mousearea_interaction = interaction,
+
+        PropertyChange::FileDialog(v) => properties.file_dialog = v,
+        PropertyChange::FileDialogPathText(id) => properties.file_dialog_path_text = id,
+
+        PropertyChange::StyleCondition(condition) => properties.style_condition = condition,
+        PropertyChange::ConditionBorderColor(color) => properties.condition_border_color = color,
+        PropertyChange::ConditionBackgroundColor(color) => properties.condition_background_color = color,
+        PropertyChange::PreviewCondition(b) => properties.preview_condition = b,
         
         _ => {} // Placeholder for properties not implemented
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash,)]
+/// One side of a padding box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaddingSide { Top, Right, Bottom, Left }
+
+/// The change an edit to one side makes, given how the sides are linked
+pub fn padding_side_change(mode: PaddingMode, side: PaddingSide, value: f32) -> PropertyChange {
+    match (mode, side) {
+        (PaddingMode::Uniform, _) => PropertyChange::PaddingUniform(value),
+        (PaddingMode::Symmetric, PaddingSide::Top | PaddingSide::Bottom) => PropertyChange::PaddingVertical(value),
+        (PaddingMode::Symmetric, PaddingSide::Left | PaddingSide::Right) => PropertyChange::PaddingHorizontal(value),
+        (PaddingMode::Individual, PaddingSide::Top) => PropertyChange::PaddingTop(value),
+        (PaddingMode::Individual, PaddingSide::Right) => PropertyChange::PaddingRight(value),
+        (PaddingMode::Individual, PaddingSide::Bottom) => PropertyChange::PaddingBottom(value),
+        (PaddingMode::Individual, PaddingSide::Left) => PropertyChange::PaddingLeft(value),
+    }
+}
+
+/// Link toggle order: Uniform -> Symmetric -> Individual -> Uniform
+pub fn next_padding_mode(mode: PaddingMode) -> PaddingMode {
+    match mode {
+        PaddingMode::Uniform    => PaddingMode::Symmetric,
+        PaddingMode::Symmetric  => PaddingMode::Individual,
+        PaddingMode::Individual => PaddingMode::Uniform,
+    }
+}
+
+/// One edit to an ordered list of option labels (radio, pick list, combo box)
+#[derive(Debug, Clone)]
+pub enum OptionListEdit {
+    Rename(usize, String),
+    Add,
+    Remove(usize),
+    MoveUp(usize),
+    MoveDown(usize),
+}
+
+impl OptionListEdit {
+    /// Applies the edit and returns where the previously `selected` entry ended up.
+    /// Renames to a label that's already in the list are ignored.
+    pub fn apply(self, options: &mut Vec<String>, selected: Option<usize>) -> Option<usize> {
+        match self {
+            OptionListEdit::Rename(index, label) => {
+                let duplicate = options.iter().enumerate().any(|(i, o)| i != index && *o == label);
+                if index < options.len() && !duplicate {
+                    options[index] = label;
+                }
+                selected
+            }
+            OptionListEdit::Add => {
+                let mut n = options.len() + 1;
+                while options.contains(&format!("Option {}", n)) {
+                    n += 1;
+                }
+                options.push(format!("Option {}", n));
+                selected
+            }
+            OptionListEdit::Remove(index) => {
+                if index >= options.len() || options.len() <= 1 {
+                    return selected;
+                }
+                options.remove(index);
+                match selected {
+                    Some(s) if s == index => Some(0),
+                    Some(s) if s > index => Some(s - 1),
+                    other => other,
+                }
+            }
+            OptionListEdit::MoveUp(index) => {
+                if index == 0 || index >= options.len() {
+                    return selected;
+                }
+                options.swap(index, index - 1);
+                match selected {
+                    Some(s) if s == index => Some(index - 1),
+                    Some(s) if s == index - 1 => Some(index),
+                    other => other,
+                }
+            }
+            OptionListEdit::MoveDown(index) => {
+                if index + 1 >= options.len() {
+                    return selected;
+                }
+                options.swap(index, index + 1);
+                match selected {
+                    Some(s) if s == index => Some(index + 1),
+                    Some(s) if s == index + 1 => Some(index),
+                    other => other,
+                }
+            }
+        }
+    }
+}
+
+/// Labels that are empty or repeated, reported by the option list editor
+pub fn invalid_option_labels(options: &[String]) -> Vec<usize> {
+    options.iter().enumerate()
+        .filter(|(i, o)| o.trim().is_empty() || options[..*i].contains(o))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct WidgetId(pub usize);
 
+/// Why a change to the widget tree was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HierarchyError {
+    /// The widget isn't in the tree, or no longer is
+    WidgetNotFound(WidgetId),
+    /// The parent can't hold this kind of widget, or any children at all
+    InvalidParent { parent: WidgetType, child: WidgetType },
+    /// The parent already holds as many children as it can
+    ChildLimitExceeded { parent: WidgetType, max: usize },
+    /// The root holds exactly one Column or Row
+    RootConstraint,
+    /// The root can't be moved, deleted or wrapped
+    RootLocked,
+    /// A widget can't move into itself or its own descendants
+    CycleDetected,
+    EmptySelection,
+    /// Wrapping needs every selected widget under the same parent
+    SelectionSpansParents,
+    /// The wrapper takes an exact number of children
+    SelectionCount { container: WidgetType, needed: usize },
+    /// The tree changed since a hint's fix was offered
+    HintOutdated,
+    /// Only a widget with exactly one child can be replaced by it
+    UnwrapChildCount { container: WidgetType, count: usize },
+}
+
+impl std::fmt::Display for HierarchyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HierarchyError::WidgetNotFound(id) => write!(f, "Widget #{} no longer exists", id.0),
+            HierarchyError::InvalidParent { parent, child } => write!(f, "A {:?} can't hold a {:?}", parent, child),
+            HierarchyError::ChildLimitExceeded { parent, max: 1 } => write!(f, "A {:?} can only hold one child", parent),
+            HierarchyError::ChildLimitExceeded { parent, max } => write!(f, "A {:?} can only hold {} children", parent, max),
+            HierarchyError::RootConstraint => write!(f, "The root can only hold a single Column or Row"),
+            HierarchyError::RootLocked => write!(f, "The root can't be moved, deleted or wrapped"),
+            HierarchyError::CycleDetected => write!(f, "A widget can't be moved inside itself"),
+            HierarchyError::EmptySelection => write!(f, "No widgets selected"),
+            HierarchyError::SelectionSpansParents => write!(f, "Selected widgets must have the same parent"),
+            HierarchyError::SelectionCount { container, needed: 1 } => write!(f, "A {:?} wraps exactly one widget", container),
+            HierarchyError::SelectionCount { container, needed } => write!(f, "A {:?} wraps exactly {} widgets", container, needed),
+            HierarchyError::HintOutdated => write!(f, "The layout changed since this suggestion was made"),
+            HierarchyError::UnwrapChildCount { container, count } => write!(f, "Only a {:?} with exactly one child can be unwrapped, this one has {}", container, count),
+        }
+    }
+}
+
+/// Why a `parent_type` already holding `existing` children would refuse one more `widget_type`, if it would
+fn check_child_fit(parent_type: WidgetType, is_root: bool, existing: usize, widget_type: WidgetType) -> Result<(), HierarchyError> {
+    let invalid = HierarchyError::InvalidParent { parent: parent_type, child: widget_type };
+    let limit = |max: usize| {
+        if existing < max { Ok(()) }
+        else { Err(HierarchyError::ChildLimitExceeded { parent: parent_type, max }) }
+    };
+
+    if !can_have_children(&parent_type) { return Err(invalid); }
+
+    if is_root {
+        if existing > 0 || !matches!(widget_type, WidgetType::Column | WidgetType::Row) {
+            return Err(HierarchyError::RootConstraint);
+        }
+        return Ok(());
+    }
+
+    match parent_type {
+        WidgetType::Scrollable => {
+            limit(1)?;
+            if matches!(widget_type, WidgetType::Column | WidgetType::Row | WidgetType::Container) { Ok(()) }
+            else { Err(invalid) }
+        }
+        WidgetType::Container => limit(1),
+        WidgetType::Tooltip   => limit(2),
+        WidgetType::MouseArea => limit(1),
+        _ => Ok(()),
+    }
+}
+
+/// Wrappers offered by "Wrap in…" on a single widget
+pub const SINGLE_WRAPPERS: [WidgetType; 4] = [
+    WidgetType::Container,
+    WidgetType::Row,
+    WidgetType::Column,
+    WidgetType::Scrollable,
+];
+
 /// Central widget hierarchy manager - Simplified to use only IDs
 #[derive(Debug, Clone,)]
 pub struct WidgetHierarchy {
@@ -578,6 +957,9 @@ pub struct WidgetHierarchy {
     selected_ids: HashSet<WidgetId>,
     next_id: usize,
     common_properties: Option<CommonProperties>,
+    /// Session-only, never written out with the layout
+    history: PropertyHistory,
+    numeric_inputs: NumericInputs,
 }
 
 impl WidgetHierarchy {
@@ -589,7 +971,9 @@ impl WidgetHierarchy {
             root: Widget::new(root_type, WidgetId(0)),
             selected_ids,
             next_id: 1,
-            common_properties: None
+            common_properties: None,
+            history: PropertyHistory::default(),
+            numeric_inputs: NumericInputs::default(),
         }
     }
     
@@ -597,6 +981,18 @@ impl WidgetHierarchy {
         &self.root
     }
     
+    pub fn history(&self) -> &PropertyHistory {
+        &self.history
+    }
+
+    pub fn numeric_inputs(&self) -> &NumericInputs {
+        &self.numeric_inputs
+    }
+
+    pub fn numeric_inputs_mut(&mut self) -> &mut NumericInputs {
+        &mut self.numeric_inputs
+    }
+
     pub fn selected_ids(&self) -> &HashSet<WidgetId> {
         &self.selected_ids
     }
@@ -609,7 +1005,41 @@ impl WidgetHierarchy {
         
         self.common_properties = Some(self.get_common_properties());
     }
-    
+
+    /// Puts back a tree from the undo stack. Ids keep counting up from the newest ever handed out,
+    /// so the steps that were undone stay valid to redo.
+    pub fn restore(&mut self, root: Widget, selected: &HashSet<WidgetId>) {
+        self.root = root;
+        self.set_selected_ids(selected.clone());
+    }
+
+    /// A hierarchy around a tree read from a project file. State the file doesn't hold, like
+    /// combo box state and parsed markdown, is rebuilt, and ids continue past the highest one.
+    pub fn from_root(mut root: Widget) -> Result<Self, String> {
+        fn prepare(widget: &mut Widget, seen: &mut HashSet<WidgetId>) -> Result<(), String> {
+            if !seen.insert(widget.id) {
+                return Err(format!("widget id {} is used twice", widget.id.0));
+            }
+            let props = &mut widget.properties;
+            props.combobox_state = combo_box::State::new(props.combobox_options.clone());
+            if widget.widget_type == WidgetType::Markdown {
+                props.reparse_markdown();
+            }
+            for child in &mut widget.children {
+                prepare(child, seen)?;
+            }
+            Ok(())
+        }
+        let mut seen = HashSet::new();
+        prepare(&mut root, &mut seen)?;
+
+        let mut hierarchy = Self::new(root.widget_type);
+        hierarchy.next_id = seen.iter().map(|id| id.0).max().unwrap_or(0) + 1;
+        hierarchy.selected_ids = HashSet::from([root.id]);
+        hierarchy.root = root;
+        Ok(hierarchy)
+    }
+
     pub fn get_single_selected(&self) -> Option<&Widget> {
         if self.selected_ids.len() == 1 {
             let id = self.selected_ids.iter().next()?;
@@ -654,39 +1084,17 @@ impl WidgetHierarchy {
     }
 
     pub fn can_add_child(&self, parent_id: WidgetId, widget_type: WidgetType) -> bool {
-        if let Some(parent) = self.get_widget_by_id(parent_id) {
-            if !can_have_children(&parent.widget_type) { return false; }
-
-            if parent_id == self.root.id {
-                return parent.children.is_empty()
-                    && matches!(widget_type, WidgetType::Column | WidgetType::Row);
-            }
+        self.check_add_child(parent_id, widget_type).is_ok()
+    }
 
-            match parent.widget_type {
-                WidgetType::Scrollable => {
-                    if !parent.children.is_empty() { return false; }
-                    matches!(widget_type, WidgetType::Column | WidgetType::Row | WidgetType::Container)
-                }
-                WidgetType::Container => parent.children.is_empty(),
-                WidgetType::Tooltip   => parent.children.len() < 2, // <= 2 children
-                WidgetType::MouseArea => parent.children.is_empty(),
-                _ => true,
-            }
-        } else { false }
+    /// Why `add_child` would refuse this widget, if it would
+    fn check_add_child(&self, parent_id: WidgetId, widget_type: WidgetType) -> Result<(), HierarchyError> {
+        let parent = self.get_widget_by_id(parent_id).ok_or(HierarchyError::WidgetNotFound(parent_id))?;
+        check_child_fit(parent.widget_type, parent_id == self.root.id, parent.children.len(), widget_type)
     }
     
-    pub fn add_child(&mut self, parent_id: WidgetId, widget_type: WidgetType) -> Result<WidgetId, String> {
-        if !self.can_add_child(parent_id, widget_type) {
-            if parent_id == self.root.id {
-                if self.root.children.is_empty() {
-                    return Err("Root container can only have Column or Row as its first child".to_string());
-                } else {
-                    return Err("Root container can only have one child".to_string());
-                }
-            } else {
-                return Err(format!("Cannot add {:?} to this parent", widget_type));
-            }
-        }
+    pub fn add_child(&mut self, parent_id: WidgetId, widget_type: WidgetType) -> Result<WidgetId, HierarchyError> {
+        self.check_add_child(parent_id, widget_type)?;
 
         let child_id = WidgetId(self.next_id);
         self.next_id += 1;
@@ -727,16 +1135,54 @@ impl WidgetHierarchy {
             parent.children.push(child);
             Ok(child_id)
         } else {
-            Err("Parent widget not found".to_string())
+            Err(HierarchyError::WidgetNotFound(parent_id))
+        }
+    }
+    
+    /// Same as `add_child`, but the new widget starts from the project defaults
+    pub fn add_child_with_defaults(
+        &mut self,
+        parent_id: WidgetId,
+        widget_type: WidgetType,
+        defaults: &ProjectDefaults,
+    ) -> Result<WidgetId, HierarchyError> {
+        let child_id = self.add_child(parent_id, widget_type)?;
+        if let Some(child) = self.get_widget_by_id_mut(child_id) {
+            defaults.apply_to_new(widget_type, &mut child.properties);
+        }
+        Ok(child_id)
+    }
+
+    /// Ids of every widget the given default applies to
+    pub fn ids_for_default(&self, property: DefaultProperty) -> Vec<WidgetId> {
+        fn collect(widget: &Widget, property: DefaultProperty, ids: &mut Vec<WidgetId>) {
+            if property.applies_to(widget.widget_type) {
+                ids.push(widget.id);
+            }
+            for child in &widget.children {
+                collect(child, property, ids);
+            }
         }
+        let mut ids = Vec::new();
+        collect(&self.root, property, &mut ids);
+        ids
     }
     
-    pub fn delete_widget(&mut self, id: WidgetId) -> Result<(), String> {
+    pub fn delete_widget(&mut self, id: WidgetId) -> Result<(), HierarchyError> {
         if id == self.root.id {
-            return Err("Cannot delete root widget".to_string());
+            return Err(HierarchyError::RootLocked);
         }
         
         if let Some(parent_id) = self.find_parent_id(id) {
+            if let Some(widget) = self.get_widget_by_id(id) {
+                let mut removed = Vec::new();
+                collect_ids(widget, &mut removed);
+                for removed_id in removed {
+                    self.history.forget(removed_id);
+                    self.numeric_inputs.forget(removed_id);
+                }
+            }
+
             if let Some(parent) = self.get_widget_by_id_mut(parent_id) {
                 parent.children.retain(|child| child.id != id);
                 
@@ -750,13 +1196,23 @@ impl WidgetHierarchy {
                 
                 Ok(())
             } else {
-                Err("Parent widget not found".to_string())
+                Err(HierarchyError::WidgetNotFound(parent_id))
             }
         } else {
-            Err("Cannot find parent of widget".to_string())
+            Err(HierarchyError::WidgetNotFound(id))
         }
     }
     
+    /// Anchor of a floating Stack layer. None unless the widget sits directly in a Stack,
+    /// since the float settings only mean something there.
+    pub fn float_anchor(&self, id: WidgetId) -> Option<FloatAnchor> {
+        let parent = self.find_parent_id(id).and_then(|parent_id| self.get_widget_by_id(parent_id))?;
+        if parent.widget_type != WidgetType::Stack {
+            return None;
+        }
+        self.get_widget_by_id(id)?.properties.float_anchor
+    }
+
     pub fn find_parent_id(&self, child_id: WidgetId) -> Option<WidgetId> {
         fn find_parent(widget: &Widget, target_id: WidgetId) -> Option<WidgetId> {
             for child in &widget.children {
@@ -772,7 +1228,57 @@ impl WidgetHierarchy {
         find_parent(&self.root, child_id)
     }
 
+    /// Ids from the root down to `id`, inclusive. Empty if `id` isn't in the tree.
+    pub fn find_path(&self, id: WidgetId) -> Vec<WidgetId> {
+        let mut path = Vec::new();
+        let mut current = Some(id);
+        while let Some(current_id) = current {
+            if !self.widget_exists(current_id) {
+                return Vec::new();
+            }
+            path.push(current_id);
+            current = self.find_parent_id(current_id);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Whether a long markdown document is waiting to be parsed
+    pub fn has_pending_markdown(&self) -> bool {
+        fn pending(widget: &Widget) -> bool {
+            widget.properties.markdown_edited_at.is_some() || widget.children.iter().any(pending)
+        }
+        pending(&self.root)
+    }
+
+    /// Parses markdown documents whose last edit is at least `MARKDOWN_DEBOUNCE` old
+    pub fn reparse_markdown(&mut self, now: std::time::Instant) {
+        fn reparse(widget: &mut Widget, now: std::time::Instant) {
+            if let Some(edited_at) = widget.properties.markdown_edited_at
+                && now.saturating_duration_since(edited_at) >= MARKDOWN_DEBOUNCE
+            {
+                widget.properties.reparse_markdown();
+            }
+            for child in &mut widget.children {
+                reparse(child, now);
+            }
+        }
+        reparse(&mut self.root, now);
+    }
+
+    /// Applies the change and logs it in the widget's history. The old value is
+    /// read first, then compared with what the change actually left behind.
     pub fn apply_property_change(&mut self, id: WidgetId, change: PropertyChange, type_system: &TypeSystem) {
+        let before = self.get_widget_by_id(id).and_then(|w| history::snapshot(&w.properties, &change));
+        self.apply_property_change_untracked(id, change.clone(), type_system);
+
+        let after = self.get_widget_by_id(id).and_then(|w| history::snapshot(&w.properties, &change));
+        if let (Some(before), Some(after)) = (before, after) {
+            self.history.record(id, before, after.value, std::time::Instant::now());
+        }
+    }
+
+    fn apply_property_change_untracked(&mut self, id: WidgetId, change: PropertyChange, type_system: &TypeSystem) {
         // Special handling for scrollable direction changes
         if let PropertyChange::ScrollableDirection(new_dir) = change.clone() {
             if let Some(widget) = self.get_widget_by_id_mut(id) {
@@ -835,62 +1341,34 @@ impl WidgetHierarchy {
         id: WidgetId,
         new_parent_id: WidgetId,
         mut new_index: usize,
-    ) -> Result<(), String> {
+    ) -> Result<(), HierarchyError> {
         if id == self.root.id {
-            return Err("Cannot move root widget".into());
-        }
-        if !self.widget_exists(id) {
-            return Err("Widget to move not found".into());
+            return Err(HierarchyError::RootLocked);
         }
+        let Some(moving_ty) = self.get_widget_by_id(id).map(|w| w.widget_type) else {
+            return Err(HierarchyError::WidgetNotFound(id));
+        };
         if !self.widget_exists(new_parent_id) {
-            return Err("New parent not found".into());
-        }
-
-        // Prevent cycles: cannot move a node into its own subtree
-        if self.is_descendant(id, new_parent_id) {
-            return Err("Cannot move a widget into its own descendant".into());
-        }
-
-        // Parent capability checks
-        let new_parent_ty = self.get_widget_by_id(new_parent_id).unwrap().widget_type;
-        if !can_have_children(&new_parent_ty) {
-            return Err(format!("{new_parent_ty:?} cannot have children"));
+            return Err(HierarchyError::WidgetNotFound(new_parent_id));
         }
 
-        if matches!(new_parent_ty, WidgetType::Tooltip) {
-            let count = self.get_widget_by_id(new_parent_id).unwrap().children.len();
-            if count >= 2 && self.find_parent_id(id) != Some(new_parent_id) {
-                return Err("Tooltip can only contain two children".into());
-            }
+        // Prevent cycles: cannot move a node into itself or its own subtree
+        if id == new_parent_id || self.is_descendant(id, new_parent_id) {
+            return Err(HierarchyError::CycleDetected);
         }
 
-        if matches!(new_parent_ty, WidgetType::MouseArea) {
-            let count = self.get_widget_by_id(new_parent_id).unwrap().children.len();
-            if count >= 1 && self.find_parent_id(id) != Some(new_parent_id) {
-                return Err("MouseArea can only contain one child".into());
-            }
+        // Reordering among the same siblings can't break the parent's rules; anything else
+        // must fit the new parent the same way a freshly added widget would
+        let old_parent_id = self.find_parent_id(id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        if old_parent_id != new_parent_id {
+            self.check_add_child(new_parent_id, moving_ty)?;
         }
-
-        // Root container constraints
         if new_parent_id == self.root.id {
-            // Only Column/Row allowed under root
-            let moving_ty = self.get_widget_by_id(id).unwrap().widget_type;
-            if !matches!(moving_ty, WidgetType::Column | WidgetType::Row) {
-                return Err("Root can only contain Column or Row".into());
-            }
-            // Root can have only one child (unless we're reordering the same one)
-            let root_children = &self.root.children;
-            let already_under_root = self.find_parent_id(id) == Some(self.root.id);
-            if !already_under_root && !root_children.is_empty() {
-                return Err("Root container can only have one child".into());
-            }
-            // Clamp index for root (0 or existing 0)
             new_index = 0;
         }
 
         // Detach node from current parent
-        let old_parent_id = self.find_parent_id(id).ok_or("Old parent not found")?;
-        let node = self.remove_and_return(id).ok_or("Failed to detach node")?;
+        let node = self.remove_and_return(id).ok_or(HierarchyError::WidgetNotFound(id))?;
 
         // If moving within the same parent and we removed a lower index, fix target index
         if old_parent_id == new_parent_id {
@@ -903,12 +1381,27 @@ impl WidgetHierarchy {
         }
 
         // Insert into new parent
-        let parent = self.get_widget_by_id_mut(new_parent_id).ok_or("New parent not found")?;
+        let parent = self.get_widget_by_id_mut(new_parent_id).ok_or(HierarchyError::WidgetNotFound(new_parent_id))?;
         parent.children.insert(new_index, node);
 
         Ok(())
     }
 
+    /// Moves `id` `step` places among its siblings, stopping at either end. Returns whether it moved.
+    pub fn move_among_siblings(&mut self, id: WidgetId, step: isize) -> Result<bool, HierarchyError> {
+        if id == self.root.id {
+            return Err(HierarchyError::RootLocked);
+        }
+        let parent_id = self.find_parent_id(id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        let siblings = &self.get_widget_by_id(parent_id).ok_or(HierarchyError::WidgetNotFound(parent_id))?.children;
+        let index = siblings.iter().position(|child| child.id == id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        let Some(target) = index.checked_add_signed(step).filter(|&target| target < siblings.len()) else {
+            return Ok(false);
+        };
+        self.move_widget(id, parent_id, target)?;
+        Ok(true)
+    }
+
     fn is_descendant(&self, ancestor: WidgetId, candidate: WidgetId) -> bool {
         fn walk(w: &Widget, anc: WidgetId, cand: WidgetId) -> bool {
             if w.id == anc {
@@ -1093,15 +1586,15 @@ impl WidgetHierarchy {
     }
 
     /// Validates that all selected widgets can be wrapped together
-    pub fn validate_wrapping(&self) -> Result<WidgetId, String> {
+    pub fn validate_wrapping(&self) -> Result<WidgetId, HierarchyError> {
         // Need at least one widget selected
         if self.selected_ids.is_empty() {
-            return Err("No widgets selected".to_string());
+            return Err(HierarchyError::EmptySelection);
         }
         
         // Can't wrap root
         if self.selected_ids.contains(&self.root.id) {
-            return Err("Cannot wrap root widget".to_string());
+            return Err(HierarchyError::RootLocked);
         }
         
         // All selected widgets must share the same parent
@@ -1111,7 +1604,7 @@ impl WidgetHierarchy {
             .collect();
             
         if parent_ids.len() != 1 {
-            return Err("Selected widgets must have the same parent".to_string());
+            return Err(HierarchyError::SelectionSpansParents);
         }
         
         let parent_id = *parent_ids.iter().next().unwrap();
@@ -1122,18 +1615,18 @@ impl WidgetHierarchy {
     pub fn wrap_selected_in_container(
         &mut self, 
         container_type: WidgetType
-    ) -> Result<WidgetId, String> {
+    ) -> Result<WidgetId, HierarchyError> {
         // Validate before making any changes
         let parent_id = self.validate_wrapping()?;
         
         // Validation for tooltip - takes 2 children
         if container_type == WidgetType::Tooltip && self.selected_ids.len() != 2 {
-            return Err("Tooltip takes 2 widgets selected".to_string());
+            return Err(HierarchyError::SelectionCount { container: container_type, needed: 2 });
         }
 
         // Validation for MouseArea - takes 1 child
         if container_type == WidgetType::MouseArea && self.selected_ids.len() != 1 {
-            return Err("MouseArea takes 1 widget selected".to_string());
+            return Err(HierarchyError::SelectionCount { container: container_type, needed: 1 });
         }
         
         // CRITICAL: Extract data from self before taking mutable borrow
@@ -1142,7 +1635,7 @@ impl WidgetHierarchy {
         self.next_id += 1;
         
         let parent = self.get_widget_by_id_mut(parent_id)
-            .ok_or("Parent not found")?;
+            .ok_or(HierarchyError::WidgetNotFound(parent_id))?;
         
         // Find indices of selected widgets in parent's children
         let mut selected_indices: Vec<usize> = parent.children
@@ -1153,7 +1646,7 @@ impl WidgetHierarchy {
             .collect();
         
         if selected_indices.is_empty() {
-            return Err("No valid widgets to wrap".to_string());
+            return Err(HierarchyError::EmptySelection);
         }
         
         selected_indices.sort_unstable();  // Ensure consistent order
@@ -1179,6 +1672,152 @@ impl WidgetHierarchy {
         
         Ok(wrapper_id)
     }
+
+    /// Parent and index of `id`, if a new `wrapper_type` could take its place and hold it
+    fn check_wrap(&self, id: WidgetId, wrapper_type: WidgetType) -> Result<(WidgetId, usize), HierarchyError> {
+        if id == self.root.id {
+            return Err(HierarchyError::RootLocked);
+        }
+        let widget = self.get_widget_by_id(id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        let parent_id = self.find_parent_id(id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        let parent = self.get_widget_by_id(parent_id).ok_or(HierarchyError::WidgetNotFound(parent_id))?;
+
+        // The wrapper takes the widget's slot, so the parent is checked as if the widget were gone
+        check_child_fit(parent.widget_type, parent_id == self.root.id, parent.children.len() - 1, wrapper_type)?;
+        check_child_fit(wrapper_type, false, 0, widget.widget_type)?;
+
+        let index = parent.children.iter().position(|child| child.id == id)
+            .ok_or(HierarchyError::WidgetNotFound(id))?;
+        Ok((parent_id, index))
+    }
+
+    /// Wrappers from `SINGLE_WRAPPERS` that `id` can be wrapped in where it stands
+    pub fn wrap_choices(&self, id: WidgetId) -> Vec<WidgetType> {
+        SINGLE_WRAPPERS.into_iter()
+            .filter(|&wrapper_type| self.check_wrap(id, wrapper_type).is_ok())
+            .collect()
+    }
+
+    /// Puts a new `wrapper_type` where `id` is and moves `id` inside it. The selection stays on `id`.
+    pub fn wrap_widget(&mut self, id: WidgetId, wrapper_type: WidgetType) -> Result<WidgetId, HierarchyError> {
+        let (parent_id, index) = self.check_wrap(id, wrapper_type)?;
+
+        let wrapper_id = WidgetId(self.next_id);
+        self.next_id += 1;
+
+        let parent = self.get_widget_by_id_mut(parent_id)
+            .ok_or(HierarchyError::WidgetNotFound(parent_id))?;
+        let widget = parent.children.remove(index);
+        let mut wrapper = Widget::new(wrapper_type, wrapper_id);
+        wrapper.children.push(widget);
+        parent.children.insert(index, wrapper);
+
+        if wrapper_type == WidgetType::Scrollable {
+            self.sanitize_subtree_for_scrollable(wrapper_id);
+        }
+
+        Ok(wrapper_id)
+    }
+
+    /// Replaces `id` with its only child, which keeps its own id. Returns the child's id.
+    pub fn unwrap_widget(&mut self, id: WidgetId) -> Result<WidgetId, HierarchyError> {
+        if id == self.root.id {
+            return Err(HierarchyError::RootLocked);
+        }
+        let widget = self.get_widget_by_id(id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        let [child] = widget.children.as_slice() else {
+            return Err(HierarchyError::UnwrapChildCount { container: widget.widget_type, count: widget.children.len() });
+        };
+        let (child_id, child_type) = (child.id, child.widget_type);
+        let was_scrollable = widget.widget_type == WidgetType::Scrollable;
+
+        let parent_id = self.find_parent_id(id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        let parent = self.get_widget_by_id(parent_id).ok_or(HierarchyError::WidgetNotFound(parent_id))?;
+        check_child_fit(parent.widget_type, parent_id == self.root.id, parent.children.len() - 1, child_type)?;
+
+        let parent = self.get_widget_by_id_mut(parent_id)
+            .ok_or(HierarchyError::WidgetNotFound(parent_id))?;
+        let index = parent.children.iter().position(|c| c.id == id)
+            .ok_or(HierarchyError::WidgetNotFound(id))?;
+        let mut wrapper = parent.children.remove(index);
+        parent.children.insert(index, wrapper.children.remove(0));
+
+        // Sizes held back by the removed scrollable come back, unless another one still encloses them
+        if was_scrollable {
+            self.restore_subtree_after_scrollable(child_id);
+            if let Some((scrollable_id, _)) = self.get_scrollable_ancestor_info(child_id) {
+                self.sanitize_subtree_for_scrollable(scrollable_id);
+            }
+        }
+
+        self.history.forget(id);
+        self.numeric_inputs.forget(id);
+        if self.selected_ids.remove(&id) {
+            self.selected_ids.insert(child_id);
+        }
+
+        Ok(child_id)
+    }
+
+    /// Puts a copy of `id` and everything under it right after it, and selects the copy.
+    /// Returns the copy's id. Refused where the parent couldn't take another child of its type.
+    pub fn duplicate_subtree(&mut self, id: WidgetId) -> Result<WidgetId, HierarchyError> {
+        let parent_id = self.check_duplicate(id)?;
+        let mut copy = self.get_widget_by_id(id).ok_or(HierarchyError::WidgetNotFound(id))?.clone();
+        let mut renumbered = HashMap::new();
+        self.renumber_copy(&mut copy, &mut renumbered);
+        // Links between widgets inside the copy follow it, links out of it stay as they were
+        fn relink(widget: &mut Widget, renumbered: &HashMap<WidgetId, WidgetId>) {
+            if let Some(target) = widget.properties.file_dialog_path_text
+                && let Some(&new) = renumbered.get(&target)
+            {
+                widget.properties.file_dialog_path_text = Some(new);
+            }
+            for child in &mut widget.children {
+                relink(child, renumbered);
+            }
+        }
+        relink(&mut copy, &renumbered);
+        let copy_id = copy.id;
+
+        let parent = self.get_widget_by_id_mut(parent_id)
+            .ok_or(HierarchyError::WidgetNotFound(parent_id))?;
+        let index = parent.children.iter().position(|child| child.id == id)
+            .ok_or(HierarchyError::WidgetNotFound(id))?;
+        parent.children.insert(index + 1, copy);
+
+        self.set_selected_ids(HashSet::from([copy_id]));
+        Ok(copy_id)
+    }
+
+    /// Parent `id`'s copy would go into, if it has room for one
+    fn check_duplicate(&self, id: WidgetId) -> Result<WidgetId, HierarchyError> {
+        if id == self.root.id {
+            return Err(HierarchyError::RootLocked);
+        }
+        let widget = self.get_widget_by_id(id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        let parent_id = self.find_parent_id(id).ok_or(HierarchyError::WidgetNotFound(id))?;
+        self.check_add_child(parent_id, widget.widget_type)?;
+        Ok(parent_id)
+    }
+
+    /// Gives a copied subtree ids nothing has had yet, recording old to new in `renumbered`.
+    /// Combo box state and parsed markdown are rebuilt rather than shared with the original.
+    fn renumber_copy(&mut self, widget: &mut Widget, renumbered: &mut HashMap<WidgetId, WidgetId>) {
+        let id = WidgetId(self.next_id);
+        self.next_id += 1;
+        renumbered.insert(widget.id, id);
+        widget.id = id;
+
+        let props = &mut widget.properties;
+        props.combobox_state = combo_box::State::new(props.combobox_options.clone());
+        if widget.widget_type == WidgetType::Markdown {
+            props.reparse_markdown();
+        }
+        for child in &mut widget.children {
+            self.renumber_copy(child, renumbered);
+        }
+    }
     
     /// Gets all widgets that are currently selected
     pub fn get_selected_widgets(&self) -> Vec<&Widget> {
@@ -1201,6 +1840,14 @@ impl WidgetHierarchy {
         CommonProperties::from_widgets(&selected)
     }
     
+    /// Applies the same change to each of `ids`. Widgets the change doesn't fit, like a slider
+    /// range on a Text, ignore it the way a single widget would.
+    pub fn apply_property_change_multi(&mut self, ids: &[WidgetId], change: &PropertyChange, type_system: &TypeSystem) {
+        for &widget_id in ids {
+            self.apply_property_change(widget_id, change.clone(), type_system);
+        }
+    }
+
     /// Applies a property change to all currently selected widgets
     pub fn apply_property_to_all_selected(
         &mut self, 
@@ -1209,10 +1856,7 @@ impl WidgetHierarchy {
     ) {
         // Clone the selected IDs to avoid borrow checker issues
         let selected_ids: Vec<WidgetId> = self.selected_ids.iter().copied().collect();
-        
-        for widget_id in selected_ids {
-            self.apply_property_change(widget_id, change.clone(), type_system);
-        }
+        self.apply_property_change_multi(&selected_ids, &change, type_system);
 
         match change {
             // Height / Width
@@ -1316,6 +1960,116 @@ impl WidgetHierarchy {
 
     }
 
+    /// Walks the whole tree and counts widgets by type, tag, depth and interactivity
+    pub fn stats(&self) -> HierarchyStats {
+        let mut stats = HierarchyStats::default();
+        Self::collect_stats(&self.root, 1, &mut stats);
+        stats
+    }
+
+    fn collect_stats(widget: &Widget, depth: usize, stats: &mut HierarchyStats) {
+        stats.total += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+        *stats.by_type.entry(widget.widget_type.to_string()).or_insert(0) += 1;
+        for tag in &widget.properties.tags {
+            *stats.by_tag.entry(tag.clone()).or_insert(0) += 1;
+        }
+        if is_interactive(&widget.widget_type) {
+            stats.interactive += 1;
+        }
+        for child in &widget.children {
+            Self::collect_stats(child, depth + 1, stats);
+        }
+    }
+
+}
+
+/// House-style starting values for newly created widgets
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProjectDefaults {
+    pub text_size: f32,
+    pub layout_spacing: f32,
+    pub padding: f32,
+    pub border_radius: f32,
+    pub button_style: ButtonStyleType,
+}
+
+impl Default for ProjectDefaults {
+    fn default() -> Self {
+        Self {
+            text_size: 16.0,
+            layout_spacing: 0.0,
+            padding: 0.0,
+            border_radius: 5.0,
+            button_style: ButtonStyleType::Primary,
+        }
+    }
+}
+
+impl ProjectDefaults {
+    /// Only called for freshly created widgets, existing ones are never touched
+    fn apply_to_new(&self, widget_type: WidgetType, properties: &mut Properties) {
+        let fresh = ProjectDefaults::default();
+        if DefaultProperty::TextSize.applies_to(widget_type) && self.text_size != fresh.text_size {
+            properties.text_size = self.text_size;
+        }
+        if DefaultProperty::Spacing.applies_to(widget_type) && self.layout_spacing != fresh.layout_spacing {
+            properties.spacing = self.layout_spacing;
+        }
+        if DefaultProperty::Padding.applies_to(widget_type) && self.padding != fresh.padding {
+            properties.padding_mode = PaddingMode::Uniform;
+            properties.padding = Padding::new(self.padding);
+        }
+        if DefaultProperty::BorderRadius.applies_to(widget_type) && self.border_radius != fresh.border_radius {
+            properties.border_radius = self.border_radius;
+        }
+        if DefaultProperty::ButtonStyle.applies_to(widget_type) {
+            properties.button_style = self.button_style;
+        }
+    }
+
+    /// The property change that brings an existing widget in line with this default
+    pub fn change_for(&self, property: DefaultProperty) -> PropertyChange {
+        match property {
+            DefaultProperty::TextSize    => PropertyChange::TextSize(self.text_size),
+            DefaultProperty::Spacing     => PropertyChange::Spacing(self.layout_spacing),
+            DefaultProperty::Padding     => PropertyChange::PaddingUniform(self.padding),
+            DefaultProperty::BorderRadius => PropertyChange::BorderRadius(self.border_radius),
+            DefaultProperty::ButtonStyle => PropertyChange::ButtonStyle(self.button_style),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultProperty {
+    TextSize,
+    Spacing,
+    Padding,
+    BorderRadius,
+    ButtonStyle,
+}
+
+impl DefaultProperty {
+    pub fn applies_to(self, widget_type: WidgetType) -> bool {
+        match self {
+            DefaultProperty::TextSize    => widget_type == WidgetType::Text,
+            DefaultProperty::Spacing     => matches!(widget_type, WidgetType::Row | WidgetType::Column),
+            DefaultProperty::Padding     => matches!(widget_type, WidgetType::Container | WidgetType::Row | WidgetType::Column),
+            DefaultProperty::BorderRadius => widget_type == WidgetType::Container,
+            DefaultProperty::ButtonStyle => widget_type == WidgetType::Button,
+        }
+    }
+}
+
+/// Widget counts gathered by `WidgetHierarchy::stats`
+#[derive(Debug, Clone, Default)]
+pub struct HierarchyStats {
+    pub total: usize,
+    pub interactive: usize,
+    pub max_depth: usize,
+    pub by_type: std::collections::BTreeMap<String, usize>,
+    /// Widgets carrying each tag
+    pub by_tag: std::collections::BTreeMap<String, usize>,
 }
 
 // ============================================================================
@@ -1334,16 +2088,318 @@ pub struct WidgetVisualizer {
     custom_themes: stylefn_builders::CustomThemes,
     type_system: TypeSystem,
     type_editor: TypeEditorView,
+    /// Widgets bound to each enum, for the type editor's References
+    type_references: TypeReferences,
+    notification: Option<String>,
+    /// Notifications shown this session, for copying one after it's gone
+    notification_history: NotificationHistory,
+    /// Where the last export this session was saved, for reopening after its notification is gone
+    last_export: Option<std::path::PathBuf>,
+    pinned_inspectors: Vec<WidgetId>,
+    /// Pinned inspectors widened to fit their groups side by side
+    wide_inspectors: HashSet<WidgetId>,
+    /// Which properties panels are laid out in two columns, per widget
+    editor_widths: PaneWidths<WidgetId>,
+    /// Tree to compare the current one against
+    comparison_snapshot: Option<Comparison>,
+    /// Project file being read in the background. Editing waits until it arrives.
+    loading_project: Option<std::path::PathBuf>,
+    show_comparison: bool,
+    markdown_preview_open: bool,
+    tree_rows: TreeRows,
+    /// Tag the tree is narrowed to, with the widgets it shows for it
+    tag_filter: Option<(String, HashSet<WidgetId>)>,
+    /// Tag being typed in the properties panel, shared by the single and bulk editors
+    tag_draft: String,
+    selection_focus: SelectionFocus,
+    /// Modifiers currently held, read when a tree click arrives
+    modifiers: keyboard::Modifiers,
+    doc_comments: bool,
+    code_style: CodeStyle,
+    stub_style: StubStyle,
+    derive_default: bool,
+    view_builders: bool,
+    /// Split the view into helper methods, one per layout with more than `view_helper_children`
+    /// children and per named widget
+    view_helpers: bool,
+    view_helper_children: usize,
+    multi_window: bool,
+    layout_only: bool,
+    /// How the generated app opens its window
+    window_settings: WindowSettings,
+    /// Estimated minimum window size and the revision it was worked out at. Read from
+    /// `view`, hence the `Cell`; a revision behind the builder's marks it stale.
+    min_size_estimate: std::cell::Cell<Option<(u64, (f32, f32))>>,
+    /// License header written at the top of generated files
+    file_header: FileHeader,
+    /// Editor for the header template, kept in step with `file_header.template`
+    file_header_template: text_editor::Content,
+    /// Line width and format of the printable export
+    print_settings: PrintSettings,
+    /// Files Image and Svg widgets pick their paths from
+    asset_library: AssetLibrary,
+    asset_rename: Option<AssetRename>,
+    /// Open screens. The shown one's tree, undo and code are the fields here, the others parked.
+    workspace: Workspace,
+    /// Container looks shared by name, generated as one style function each
+    named_styles: StyleRegistry,
+    /// Widget whose look is being saved as a style, with the name typed so far
+    style_promote: Option<(WidgetId, String)>,
+    /// Import form open under a widget's option list
+    option_import: Option<OptionImportForm>,
+    project_defaults: ProjectDefaults,
+    show_padding_overlay: bool,
+    /// Badges with each filling child's portion of its Row or Column
+    show_fill_portions: bool,
+    /// Narrows the add widget palette by type name or alias
+    add_widget_filter: String,
+    /// Live preview shows preview values instead of the generated initial state
+    show_preview_data: bool,
+    /// Window width the preview lays breakpoint rows out for
+    breakpoint_preview_width: f32,
+    /// Inline "Create new enum" form open in a ComboBox's properties
+    enum_form: Option<NewEnumForm>,
+    preview_viewport: PreviewViewport,
+    /// Tokens from the last applied import, diffed against the next one
+    design_tokens: Option<TokenSet>,
+    token_import: Option<TokenImport>,
+    /// Custom themes built from design tokens, offered next to the built-in ones
+    imported_themes: Vec<Theme>,
+    command_palette: Option<CommandPalette>,
+    /// Form pasted view code is read back into a layout from
+    view_import: Option<ViewImportForm>,
+    bulk_rename: BulkRename,
+    /// Names from before the last bulk rename, restored together by its undo
+    last_bulk_rename: Option<Vec<(WidgetId, String)>>,
+    code_search: CodeSearch,
+    code_budget: CodeBudget,
+    code_font: CodeFontSettings,
+    /// Code panel contents, generated in the background
+    code_generation: CodeGeneration,
+    /// Image colors are sampled from, kept decoded for the session
+    reference: ReferencePanel,
+    /// Bumped by every update that may change what the panels show. Every window, docked or
+    /// popped out, draws from this one visualizer, so a panel compares this number instead of
+    /// keeping its own copy of the tree.
+    revision: u64,
+    /// Panels that panicked and show their recovery view instead
+    panic_guards: PanicGuards,
+    /// Installed family the code view uses, None for `Font::MONOSPACE`
+    code_font_family: Option<&'static str>,
+    hint: Option<Hint>,
+    hint_dont_show_again: bool,
+    /// Most recent add a Container turned down, cleared by the next successful add
+    refused_add: Option<RefusedAdd>,
+    /// Hints closed this session, per rule and widget
+    dismissed_hints: HashSet<(HintRule, WidgetId)>,
+    /// Containers whose contrast suggestion was dismissed, with the background it was dismissed for
+    dismissed_contrast: HashMap<WidgetId, Color>,
+    /// Rules switched off with "Don't show again", persisted by the app settings
+    disabled_hints: HashSet<HintRule>,
+    show_history: bool,
+    /// Session-only snapshots of the layout, browsed from the History overlay
+    undo: UndoStack,
+    /// Undo entry under the mouse, the only one whose structure diff is worked out
+    hovered_undo: Option<usize>,
+    keybindings: Keybindings,
+    /// Action waiting for its new shortcut in the settings panel
+    capturing_key: Option<KeyAction>,
+    /// Why the last captured shortcut was refused, shown next to its action
+    keybinding_conflict: Option<(KeyAction, String)>,
 }
 
-impl Default for WidgetVisualizer {
-    fn default() -> Self {
-        let hierarchy = WidgetHierarchy::new(WidgetType::Container);
-        Self {
-            hierarchy,
-            theme: Theme::Light,
-            app_name: "App".to_string(),
-            app_window_title: String::new(),
+/// Fixed height of a single tree row, used to map the scroll offset to row indices
+const TREE_ROW_HEIGHT: f32 = 36.0;
+/// Rows rendered above and below the visible range
+const TREE_ROW_MARGIN: usize = 20;
+/// Below this many rows every branch is fully built
+const TREE_WINDOWING_THRESHOLD: usize = 200;
+
+/// The rows the tree shows, in order: the root, then every widget whose ancestors are all
+/// expanded. Rebuilt on structural change and when a branch is collapsed or expanded.
+#[derive(Debug, Clone, Default)]
+struct TreeRows {
+    order: Vec<WidgetId>,
+    index_of: HashMap<WidgetId, usize>,
+    /// One past the last row of each widget's shown subtree
+    end_of: HashMap<WidgetId, usize>,
+    /// Branches showing only their own row
+    collapsed: HashSet<WidgetId>,
+    scroll_offset: f32,
+    viewport_height: f32,
+}
+
+/// Rows built while the tree is windowed, between spacers standing in for the rest
+#[derive(Debug, Clone, PartialEq)]
+struct TreeWindow {
+    rows: std::ops::Range<usize>,
+    top: f32,
+    bottom: f32,
+}
+
+impl TreeRows {
+    fn rebuild(&mut self, root: &Widget) {
+        self.order.clear();
+        self.index_of.clear();
+        self.end_of.clear();
+        self.push(root);
+    }
+
+    fn push(&mut self, widget: &Widget) {
+        self.index_of.insert(widget.id, self.order.len());
+        self.order.push(widget.id);
+        if self.shows_children(widget) {
+            for child in &widget.children {
+                self.push(child);
+            }
+        }
+        self.end_of.insert(widget.id, self.order.len());
+    }
+
+    /// Whether the tree lists `widget`'s children under it
+    fn shows_children(&self, widget: &Widget) -> bool {
+        can_have_children(&widget.widget_type) && !self.collapsed.contains(&widget.id)
+    }
+
+    /// Rows a selection moves through: the shown rows below the root
+    fn selectable(&self) -> &[WidgetId] {
+        self.order.get(1..).unwrap_or_default()
+    }
+
+    /// Rows in view plus a margin either side, None while the tree is short enough to build
+    /// whole. Rows above the range whose subtree reaches into it are built too, to hold
+    /// their children, so the top spacer leaves room for them.
+    fn window(&self) -> Option<TreeWindow> {
+        if self.order.len() < TREE_WINDOWING_THRESHOLD {
+            return None;
+        }
+        let first = (self.scroll_offset / TREE_ROW_HEIGHT) as usize;
+        let visible = (self.viewport_height / TREE_ROW_HEIGHT).ceil() as usize;
+        let start = first.saturating_sub(TREE_ROW_MARGIN).min(self.order.len());
+        let end = (first + visible.max(1) + TREE_ROW_MARGIN).min(self.order.len());
+        let ancestors = self.order[..start].iter().filter(|id| self.end_of[id] > start).count();
+        Some(TreeWindow {
+            rows: start..end,
+            top: (start - ancestors) as f32 * TREE_ROW_HEIGHT,
+            bottom: (self.order.len() - end) as f32 * TREE_ROW_HEIGHT,
+        })
+    }
+
+    /// Whether `id`'s row or any row below it in its subtree falls in `window`
+    fn reaches(&self, window: &TreeWindow, id: WidgetId) -> bool {
+        match (self.index_of.get(&id), self.end_of.get(&id)) {
+            (Some(&index), Some(&end)) => index < window.rows.end && end > window.rows.start,
+            _ => false,
+        }
+    }
+}
+
+/// A tree the current one is compared against, and where it came from
+#[derive(Debug, Clone)]
+struct Comparison {
+    root: Widget,
+    /// Project file the tree was read from, None for a snapshot of this tab
+    source: Option<std::path::PathBuf>,
+}
+
+/// Anchor and primary row of the tree selection. The selected set itself lives in the hierarchy.
+#[derive(Debug, Clone, Default)]
+struct SelectionFocus {
+    /// Fixed end of shift-click and shift+arrow ranges
+    anchor: Option<WidgetId>,
+    /// Most recently clicked row, the one single-target actions apply to
+    primary: Option<WidgetId>,
+}
+
+impl SelectionFocus {
+    /// Selection after a tree click. The tree reports the set it would select on its own,
+    /// so the clicked row is the one that joined it, or left it on a ctrl-click.
+    fn click(
+        &mut self,
+        previous: &HashSet<WidgetId>,
+        reported: HashSet<WidgetId>,
+        order: &[WidgetId],
+        modifiers: keyboard::Modifiers,
+    ) -> HashSet<WidgetId> {
+        let clicked = reported.difference(previous).next().copied()
+            .or_else(|| if modifiers.command() { previous.difference(&reported).next().copied() } else { None })
+            .or_else(|| if reported.len() == 1 { reported.iter().next().copied() } else { None });
+        let Some(clicked) = clicked else {
+            self.reset_to(&reported);
+            return reported;
+        };
+
+        if modifiers.shift()
+            && let Some(anchor) = self.anchor
+            && let Some(range) = selection_range(order, anchor, clicked)
+        {
+            self.primary = Some(clicked);
+            return range;
+        }
+
+        if modifiers.command() {
+            let mut selected = previous.clone();
+            if !selected.remove(&clicked) {
+                selected.insert(clicked);
+            }
+            self.anchor = Some(clicked);
+            self.primary = selected.contains(&clicked).then_some(clicked);
+            return selected;
+        }
+
+        self.anchor = Some(clicked);
+        self.primary = Some(clicked);
+        reported
+    }
+
+    /// Selection after moving the primary end `step` rows through `order`, keeping the anchor.
+    /// None when nothing is selected or the move would leave the tree.
+    fn extend(&mut self, step: isize, order: &[WidgetId]) -> Option<HashSet<WidgetId>> {
+        let primary = self.primary?;
+        let anchor = *self.anchor.get_or_insert(primary);
+        let index = order.iter().position(|&id| id == primary)?;
+        let next = index.checked_add_signed(step).filter(|&next| next < order.len())?;
+        self.primary = Some(order[next]);
+        selection_range(order, anchor, order[next])
+    }
+
+    /// Points anchor and primary at the only selected widget, or clears them
+    fn reset_to(&mut self, selected: &HashSet<WidgetId>) {
+        let single = if selected.len() == 1 { selected.iter().next().copied() } else { None };
+        self.anchor = single;
+        self.primary = single;
+    }
+}
+
+/// Every row from `a` to `b` inclusive, in either direction
+fn selection_range(order: &[WidgetId], a: WidgetId, b: WidgetId) -> Option<HashSet<WidgetId>> {
+    let a = order.iter().position(|&id| id == a)?;
+    let b = order.iter().position(|&id| id == b)?;
+    Some(order[a.min(b)..=a.max(b)].iter().copied().collect())
+}
+
+/// History entries listed in the properties panel
+const HISTORY_SHOWN: usize = 20;
+
+/// Upper bound on pinned inspectors, keeps the layout manageable
+const MAX_PINNED_INSPECTORS: usize = 3;
+const PINNED_INSPECTOR_WIDTH: f32 = 400.0;
+/// Enough for the properties to go into two columns
+const WIDE_INSPECTOR_WIDTH: f32 = 640.0;
+
+/// Markdown documents longer than this are parsed once typing pauses instead of on every edit
+const MARKDOWN_DEBOUNCE_LINES: usize = 1000;
+const MARKDOWN_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+impl Default for WidgetVisualizer {
+    fn default() -> Self {
+        let hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let undo = UndoStack::new(&hierarchy, std::time::Instant::now());
+        Self {
+            hierarchy,
+            theme: Theme::Light,
+            app_name: "App".to_string(),
+            app_window_title: String::new(),
             multiple_windows: false,
             highlight_selected: true,
             left_pane: LeftPane::Home,
@@ -1351,16 +2407,592 @@ impl Default for WidgetVisualizer {
             custom_themes: stylefn_builders::CustomThemes::new(&Theme::Light),
             type_system: TypeSystem::new(),
             type_editor: TypeEditorView::new(),
+            type_references: TypeReferences::default(),
+            notification: None,
+            notification_history: NotificationHistory::default(),
+            last_export: None,
+            pinned_inspectors: Vec::new(),
+            wide_inspectors: HashSet::new(),
+            editor_widths: PaneWidths::default(),
+            comparison_snapshot: None,
+            loading_project: None,
+            show_comparison: false,
+            markdown_preview_open: true,
+            tree_rows: TreeRows::default(),
+            tag_filter: None,
+            tag_draft: String::new(),
+            selection_focus: SelectionFocus::default(),
+            modifiers: keyboard::Modifiers::default(),
+            doc_comments: false,
+            code_style: CodeStyle::Verbose,
+            stub_style: StubStyle::Comment,
+            derive_default: false,
+            view_builders: false,
+            view_helpers: false,
+            view_helper_children: 4,
+            multi_window: false,
+            layout_only: false,
+            window_settings: WindowSettings::default(),
+            min_size_estimate: std::cell::Cell::new(None),
+            file_header: FileHeader::default(),
+            file_header_template: text_editor::Content::with_text(file_header::DEFAULT_TEMPLATE),
+            print_settings: PrintSettings::default(),
+            asset_library: AssetLibrary::default(),
+            workspace: Workspace::default(),
+            named_styles: StyleRegistry::default(),
+            style_promote: None,
+            asset_rename: None,
+            option_import: None,
+            project_defaults: ProjectDefaults::default(),
+            show_padding_overlay: false,
+            show_fill_portions: false,
+            add_widget_filter: String::new(),
+            show_preview_data: true,
+            breakpoint_preview_width: 1200.0,
+            enum_form: None,
+            preview_viewport: PreviewViewport::default(),
+            design_tokens: None,
+            token_import: None,
+            imported_themes: Vec::new(),
+            command_palette: None,
+            view_import: None,
+            bulk_rename: BulkRename::default(),
+            last_bulk_rename: None,
+            code_search: CodeSearch::default(),
+            code_budget: CodeBudget::default(),
+            code_font: CodeFontSettings::default(),
+            code_generation: CodeGeneration::default(),
+            reference: ReferencePanel::default(),
+            revision: 0,
+            panic_guards: PanicGuards::default(),
+            code_font_family: None,
+            hint: None,
+            hint_dont_show_again: false,
+            refused_add: None,
+            dismissed_hints: HashSet::new(),
+            dismissed_contrast: HashMap::new(),
+            disabled_hints: HashSet::new(),
+            show_history: false,
+            undo,
+            hovered_undo: None,
+            keybindings: Keybindings::default(),
+            capturing_key: None,
+            keybinding_conflict: None,
         }
     }
 }
 
 impl WidgetVisualizer {
     pub fn new() -> Self {
-        Self::default()
+        let mut visualizer = Self::default();
+        visualizer.tree_rows.rebuild(visualizer.hierarchy.root());
+        visualizer.code_generation.invalidate(std::time::Instant::now());
+        visualizer
     }
     
     pub fn update(&mut self, message: Message) -> Action {
+        // Shortcuts turn into the message their action stands for
+        let message = match message {
+            // The open project is about to be replaced, so shortcuts wait for the new one
+            Message::KeyPressed(..) if self.loading_project.is_some() => return Action::None,
+            Message::KeyPressed(key, modifiers) => match self.resolve_key(key, modifiers) {
+                Some(message) => message,
+                None => return Action::None,
+            },
+            message => message,
+        };
+
+        let structural = matches!(
+            message,
+            Message::TreeMove(_) | Message::DeleteWidget(_) | Message::DeleteSelection | Message::AddChild(..) |
+            Message::SwapKind(_) | Message::WrapSelectedInContainer(_) | Message::WrapWidget(..) |
+            Message::UnwrapWidget(_) | Message::DuplicateWidget(_) | Message::MoveAmongSiblings(..) | Message::GenerateStressHierarchy |
+            Message::ApplyHint(_) | Message::JumpToUndoStep(_) | Message::Undo | Message::Redo |
+            Message::ProjectOpened(Ok(_)) |
+            Message::Workspace(
+                WorkspaceMessage::Select(_) | WorkspaceMessage::New | WorkspaceMessage::Release |
+                WorkspaceMessage::ConfirmClose
+            )
+        );
+        // Everything else may change the generated code
+        let regenerates = !matches!(
+            message,
+            Message::GenerateCode(_) | Message::CodeGenerated(..) | Message::ReparseMarkdown(_) |
+            Message::TreeScrolled(_) | Message::TreeSetExpanded(..) | Message::ModifiersChanged(_) | Message::PreviewViewport(_) |
+            Message::UndoStepHovered(_) | Message::CodeSearchChanged(_) | Message::CodeSearchSubmitted |
+            Message::ReferenceImage(ReferenceMessage::PointerMoved(_) | ReferenceMessage::PointerLeft) |
+            Message::TagDraftChanged(_) | Message::TagFilterChanged(_) | Message::Notification(_) |
+            Message::AddWidgetFilterChanged(_) | Message::FocusWidgetFilter | Message::ProjectChosen(_) |
+            Message::Styles(StyleMessage::StartPromote(_) | StyleMessage::NameChanged(_) | StyleMessage::CancelPromote) |
+            Message::Workspace(
+                WorkspaceMessage::Press(_) | WorkspaceMessage::Hover(_) | WorkspaceMessage::StartRename(_) |
+                WorkspaceMessage::RenameEdited(_) | WorkspaceMessage::CancelRename | WorkspaceMessage::CancelClose
+            ) |
+            Message::Noop
+        );
+        let rebinds_types = structural || matches!(message, Message::PropertyChanged(..) | Message::EnumForm(..));
+        // Sizes and labels feed the text overflow rule
+        let refreshes_hint = structural || matches!(
+            message,
+            Message::DismissHint | Message::HintRuleToggled(..) | Message::PropertyChanged(..) |
+            Message::NumericEdited(..) | Message::NumericCommitted(_) |
+            Message::BatchNumericEdited(..) | Message::BatchNumericCommitted(_) |
+            Message::ApplyBulkRename | Message::UndoBulkRename
+        );
+        // Edits made while a project loads would be thrown away once it arrives
+        if self.loading_project.is_some() && rebinds_types && !matches!(message, Message::ProjectOpened(_)) {
+            return Action::None;
+        }
+        let undo_step = self.undo_label(&message);
+        let previous_notification = self.notification.clone();
+        let action = self.handle_message(message);
+        if self.notification != previous_notification
+            && let Some(notification) = &self.notification
+        {
+            self.notification_history.push(NotificationEntry::new(notification.clone(), std::time::SystemTime::now()));
+        }
+        // Steps that turned out to change nothing, like a refused add, aren't recorded
+        if let Some((label, coalesce)) = undo_step
+            && self.undo.current().root != *self.hierarchy.root()
+        {
+            self.undo.record(label, coalesce, &self.hierarchy, std::time::Instant::now());
+        }
+        if structural {
+            self.tree_rows.rebuild(self.hierarchy.root());
+        }
+        if refreshes_hint {
+            self.refresh_hint();
+        }
+        if rebinds_types {
+            self.type_references.rebuild(self.hierarchy.root());
+        }
+        if regenerates {
+            self.revision += 1;
+            self.code_generation.invalidate(std::time::Instant::now());
+            self.refresh_tag_filter();
+        }
+        action
+    }
+
+    /// Puts the tree back as it was at undo step `index`. Widgets the step brings back are
+    /// selected, otherwise what's selected stays where it still exists, otherwise what was
+    /// selected back then.
+    fn jump_to_undo_step(&mut self, index: usize) {
+        let Some(entry) = self.undo.jump(index) else { return };
+        let restored = undo::restored_roots(self.hierarchy.root(), &entry.root);
+        let selected = if restored.is_empty() { self.hierarchy.selected_ids().clone() } else { restored };
+        self.hierarchy.restore(entry.root.clone(), &selected);
+        if self.hierarchy.selected_ids().is_empty() {
+            self.hierarchy.set_selected_ids(entry.selected.clone());
+        }
+        self.selection_focus.reset_to(self.hierarchy.selected_ids());
+        self.named_styles.follow(self.hierarchy.root());
+    }
+
+    /// Works out again which widgets the tag filter shows, after an edit that may have
+    /// tagged, untagged or moved some
+    fn refresh_tag_filter(&mut self) {
+        if let Some((tag, visible)) = &mut self.tag_filter {
+            *visible = tags::visible_ids(self.hierarchy.root(), tag);
+        }
+    }
+
+    /// Whether the tree shows `id` under the current tag filter
+    fn shows_in_tree(&self, id: WidgetId) -> bool {
+        self.tag_filter.as_ref().is_none_or(|(_, visible)| visible.contains(&id))
+    }
+
+    /// Changes whenever an update may have changed what the panels show
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Smallest window the layout fits in, worked out again only after the tree changed
+    fn estimated_min_size(&self) -> (f32, f32) {
+        if let Some((revision, size)) = self.min_size_estimate.get()
+            && revision == self.revision
+        {
+            return size;
+        }
+        let size = min_size::estimate_min_size(self.hierarchy.root());
+        self.min_size_estimate.set(Some((self.revision, size)));
+        size
+    }
+
+    /// Description of the undo step `message` makes, with the widget and property later edits
+    /// coalesce on. None for messages that don't edit the layout.
+    fn undo_label(&self, message: &Message) -> Option<(String, Option<(WidgetId, &'static str)>)> {
+        let subject = |id: WidgetId| {
+            self.hierarchy.get_widget_by_id(id).map_or_else(|| "widget".to_string(), undo::describe)
+        };
+        let changed = |id: WidgetId, change: &PropertyChange| {
+            let widget = self.hierarchy.get_widget_by_id(id)?;
+            let key = history::snapshot(&widget.properties, change)?.key;
+            let target = if widget.properties.widget_name.is_empty() {
+                format!("{:?}", widget.widget_type)
+            } else {
+                widget.properties.widget_name.clone()
+            };
+            Some((format!("Changed {} on {}", key, target), Some((id, key))))
+        };
+        let numeric = |field: NumericField| {
+            let widget = self.hierarchy.get_widget_by_id(field.widget)?;
+            changed(field.widget, &field.property.change(&widget.properties, 0.0))
+        };
+        let deleted = |id: WidgetId| {
+            let size = self.hierarchy.get_widget_by_id(id).map_or(1, undo::subtree_size);
+            if size > 1 {
+                format!("Deleted {} ({} widgets)", subject(id), size)
+            } else {
+                format!("Deleted {}", subject(id))
+            }
+        };
+        let plain = |label: String| Some((label, None));
+
+        match message {
+            Message::PropertyChanged(id, change) => changed(*id, change),
+            Message::EnumForm(EnumFormMessage::Create) => {
+                let form = self.enum_form.as_ref()?;
+                plain(format!("Bound {} to new enum {}", subject(form.widget_id), form.name.trim()))
+            }
+            Message::NumericCommitted(field) => numeric(*field),
+            // Typing into another field commits the one left behind
+            Message::NumericEdited(field, _) => {
+                numeric(self.hierarchy.numeric_inputs().draft_field().filter(|left| left != field)?)
+            }
+            Message::BatchNumericCommitted(field) => {
+                let widget = self.hierarchy.get_widget_by_id(field.widget)?;
+                let change = field.property.change(&widget.properties, 0.0);
+                self.undo_label(&Message::BatchPropertyChanged(change))
+            }
+            Message::BatchNumericEdited(field, _) => {
+                let left = self.hierarchy.numeric_inputs().draft_field().filter(|left| left != field)?;
+                self.undo_label(&Message::BatchNumericCommitted(left))
+            }
+            Message::BatchPropertyChanged(change) => {
+                let first = self.hierarchy.selected_ids().iter().min_by_key(|id| id.0).copied()?;
+                let widget = self.hierarchy.get_widget_by_id(first)?;
+                let key = history::snapshot(&widget.properties, change)?.key;
+                let count = self.hierarchy.selected_ids().len();
+                Some((format!("Changed {} on {} widgets", key, count), Some((first, key))))
+            }
+            Message::OptionImport(OptionImportMessage::Apply) => {
+                let form = self.option_import.as_ref()?;
+                changed(form.widget_id, &form.target.change(Vec::new()))
+            }
+            Message::ReferenceImage(ReferenceMessage::Sample) => {
+                let (id, change) = self.reference.sampled_change()?;
+                changed(id, &change)
+            }
+            Message::Assets(AssetMessage::Use(id, asset_id)) => {
+                changed(*id, &self.asset_library.get(*asset_id)?.property_change())
+            }
+            Message::Styles(StyleMessage::Promote) => {
+                let (id, name) = self.style_promote.as_ref()?;
+                plain(format!("Saved {} as style {}", subject(*id), name.trim()))
+            }
+            Message::Styles(StyleMessage::Delete(style_id)) => {
+                plain(format!("Deleted style {}", self.named_styles.get(*style_id)?.name))
+            }
+            Message::Assets(AssetMessage::Relinked(asset_id, Ok(_))) => {
+                let asset = self.asset_library.get(*asset_id)?;
+                plain(format!("Moved asset {}", asset.name))
+            }
+            Message::RevertProperty(id, seq) => {
+                let entry = self.hierarchy.history().find(*id, *seq)?;
+                plain(format!("Reverted {} on {}", entry.key, subject(*id)))
+            }
+            Message::DeleteWidget(id) => plain(deleted(*id)),
+            Message::DeleteSelection => {
+                let root_id = self.hierarchy.root().id;
+                let selected: Vec<WidgetId> = self.hierarchy.selected_ids().iter()
+                    .copied()
+                    .filter(|&id| id != root_id)
+                    .collect();
+                match selected.as_slice() {
+                    [] => None,
+                    [id] => plain(deleted(*id)),
+                    _ => plain(format!("Deleted {} widgets", selected.len())),
+                }
+            }
+            Message::AddChild(parent_id, widget_type) => {
+                plain(format!("Added {:?} to {}", widget_type, subject(*parent_id)))
+            }
+            Message::TreeMove(drop_info) => match drop_info.dragged_ids.as_slice() {
+                [id] => plain(format!("Moved {}", subject(WidgetId(*id)))),
+                ids => plain(format!("Moved {} widgets", ids.len())),
+            },
+            Message::SwapKind(id) => plain(format!("Swapped kind of {}", subject(*id))),
+            Message::WrapSelectedInContainer(container_type) => plain(format!(
+                "Wrapped {} widgets in {:?}",
+                self.hierarchy.selected_ids().len(),
+                container_type,
+            )),
+            Message::WrapWidget(id, wrapper_type) => {
+                plain(format!("Wrapped {} in {:?}", subject(*id), wrapper_type))
+            }
+            Message::UnwrapWidget(id) => plain(format!("Unwrapped {}", subject(*id))),
+            Message::DuplicateWidget(id) => plain(format!("Duplicated {}", subject(*id))),
+            Message::MoveAmongSiblings(id, step) => {
+                plain(format!("Moved {} {}", subject(*id), if *step < 0 { "up" } else { "down" }))
+            }
+            Message::ApplyHint(fix) => plain(format!("Applied hint: {}", fix.label())),
+            Message::ApplyBulkRename => {
+                plain(format!("Renamed {} widgets", self.hierarchy.selected_ids().len()))
+            }
+            Message::UndoBulkRename => plain("Undid bulk rename".to_string()),
+            Message::ApplyContrastSuggestion(id) => plain(format!("Fixed text contrast in {}", subject(*id))),
+            Message::ApplyDefaultToExisting(property) => {
+                plain(format!("Applied default {:?} to existing widgets", property))
+            }
+            Message::GenerateStressHierarchy => plain("Generated stress hierarchy".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Rules the user switched off, for saving in the app settings
+    pub fn disabled_hints(&self) -> &HashSet<HintRule> {
+        &self.disabled_hints
+    }
+
+    pub fn set_disabled_hints(&mut self, rules: impl IntoIterator<Item = HintRule>) {
+        self.disabled_hints = rules.into_iter().collect();
+        self.refresh_hint();
+    }
+
+    pub fn keybindings(&self) -> &Keybindings {
+        &self.keybindings
+    }
+
+    pub fn set_keybinding_overrides(&mut self, overrides: &[KeyBindingOverride]) {
+        self.keybindings = Keybindings::with_overrides(overrides);
+    }
+
+    pub fn code_budget(&self) -> CodeBudget {
+        self.code_budget
+    }
+
+    pub fn set_code_budget(&mut self, budget: CodeBudget) {
+        self.code_budget = budget;
+    }
+
+    pub fn code_font(&self) -> &CodeFontSettings {
+        &self.code_font
+    }
+
+    /// Adds a theme made in the main window to the theme picker, in place of the one named
+    /// `replacing` when it was renamed. A preview showing the old one switches to it.
+    pub fn offer_theme(&mut self, theme: Theme, replacing: Option<&str>) {
+        let name = theme.to_string();
+        let replaces = |candidate: &Theme| {
+            let candidate = candidate.to_string();
+            candidate == name || replacing == Some(candidate.as_str())
+        };
+        match self.imported_themes.iter().position(|imported| replaces(imported)) {
+            Some(index) => self.imported_themes[index] = theme.clone(),
+            None => self.imported_themes.push(theme.clone()),
+        }
+        if replaces(&self.theme) {
+            self.custom_themes.theme = theme.clone();
+            self.theme = theme;
+            self.revision += 1;
+            self.code_generation.invalidate(std::time::Instant::now());
+        }
+    }
+
+    pub fn set_code_font(&mut self, settings: CodeFontSettings) {
+        self.code_font = settings;
+        self.resolve_code_font();
+    }
+
+    /// Looks the family list up against the installed fonts again
+    fn resolve_code_font(&mut self) {
+        let installed = code_font::installed_families();
+        self.code_font_family = code_font::pick_family(&self.code_font.families, &installed).map(code_font::intern);
+    }
+
+    fn code_view_font(&self) -> CodeViewFont {
+        CodeViewFont::new(self.code_font_family, self.code_font.size)
+    }
+
+    /// The widget single-target actions apply to: the last clicked row while it is still
+    /// selected, otherwise the first selected row in tree order, shown rows first
+    fn primary_selection(&self) -> Option<WidgetId> {
+        let selected = self.hierarchy.selected_ids();
+        self.selection_focus.primary
+            .filter(|id| selected.contains(id))
+            .or_else(|| self.tree_rows.order.iter().copied().find(|id| selected.contains(id)))
+            .or_else(|| {
+                let mut widgets = Vec::new();
+                code_generator::collect_tree_order(self.hierarchy.root(), &mut widgets);
+                widgets.into_iter().map(|widget| widget.id).find(|id| selected.contains(id))
+            })
+    }
+
+    /// Selects `id` alone, as clicking its row would
+    fn select_only(&mut self, id: WidgetId) {
+        let selected = HashSet::from([id]);
+        self.selection_focus.reset_to(&selected);
+        self.hierarchy.set_selected_ids(selected);
+    }
+
+    /// The message bound to a key press, or None. While a shortcut is being captured the press
+    /// becomes the new binding instead.
+    fn resolve_key(&mut self, key: keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+        if let Some(action) = self.capturing_key {
+            self.capture_key(action, &key, modifiers);
+            return None;
+        }
+
+        // Up and Down in an empty numeric field bring back its recent values
+        if modifiers.is_empty() && self.hierarchy.numeric_inputs().can_recall() {
+            use iced::keyboard::key::{Key, Named};
+            match key {
+                Key::Named(Named::ArrowUp) => return Some(Message::NumericRecall(true)),
+                Key::Named(Named::ArrowDown) => return Some(Message::NumericRecall(false)),
+                _ => {}
+            }
+        }
+
+        let combo = KeyCombo::from_event(&key, modifiers)?;
+        match self.keybindings.action_for(&combo)? {
+            KeyAction::DeleteSelection => Some(Message::DeleteSelection),
+            KeyAction::OpenPalette | KeyAction::OpenPaletteAlternate => Some(Message::OpenCommandPalette),
+            KeyAction::ZoomIn => Some(Message::PreviewViewport(ViewportMessage::ZoomIn)),
+            KeyAction::ZoomOut => Some(Message::PreviewViewport(ViewportMessage::ZoomOut)),
+            KeyAction::ExtendSelectionUp => Some(Message::ExtendSelection(-1)),
+            KeyAction::ExtendSelectionDown => Some(Message::ExtendSelection(1)),
+            KeyAction::Undo => Some(Message::Undo),
+            KeyAction::Redo => Some(Message::Redo),
+            KeyAction::Duplicate => self.primary_selection().map(Message::DuplicateWidget),
+            // The palette and the tree are only on screen in the home pane
+            KeyAction::FocusWidgetFilter => (self.left_pane == LeftPane::Home).then_some(Message::FocusWidgetFilter),
+            KeyAction::SelectPrevious => (self.left_pane == LeftPane::Home).then_some(Message::TreeStep(-1)),
+            KeyAction::SelectNext => (self.left_pane == LeftPane::Home).then_some(Message::TreeStep(1)),
+            KeyAction::SelectParent => (self.left_pane == LeftPane::Home).then_some(Message::TreeSelectParent),
+            KeyAction::SelectFirstChild => (self.left_pane == LeftPane::Home).then_some(Message::TreeSelectFirstChild),
+            KeyAction::MoveUp => self.primary_selection().map(|id| Message::MoveAmongSiblings(id, -1)),
+            KeyAction::MoveDown => self.primary_selection().map(|id| Message::MoveAmongSiblings(id, 1)),
+        }
+    }
+
+    fn capture_key(&mut self, action: KeyAction, key: &keyboard::Key, modifiers: keyboard::Modifiers) {
+        use iced::keyboard::key::{Key, Named};
+
+        if matches!(key, Key::Named(Named::Escape)) && modifiers.is_empty() {
+            self.capturing_key = None;
+            self.keybinding_conflict = None;
+            return;
+        }
+        // Modifiers on their own: keep waiting for the rest of the combo
+        let Some(combo) = KeyCombo::from_event(key, modifiers) else { return };
+
+        match self.keybindings.set(action, combo) {
+            Ok(()) => {
+                self.capturing_key = None;
+                self.keybinding_conflict = None;
+            }
+            Err(e) => self.keybinding_conflict = Some((action, e)),
+        }
+    }
+
+    /// Sets a numeric field's property to a committed value
+    fn apply_numeric(&mut self, field: NumericField, value: f32) {
+        let Some(widget) = self.hierarchy.get_widget_by_id(field.widget) else { return };
+        let change = field.property.change(&widget.properties, value);
+        let restyles = ContainerLook::is_changed_by(&change);
+        self.hierarchy.apply_property_change(field.widget, change, &self.type_system);
+        if restyles {
+            self.sync_named_style(field.widget);
+        }
+    }
+
+    /// Sets a numeric field committed in the batch editor on every selected widget
+    fn apply_numeric_to_selection(&mut self, field: NumericField, value: f32) {
+        let Some(widget) = self.hierarchy.get_widget_by_id(field.widget) else { return };
+        let change = field.property.change(&widget.properties, value);
+        let selected: Vec<WidgetId> = self.hierarchy.selected_ids().iter().copied().collect();
+        let restyles = ContainerLook::is_changed_by(&change);
+        self.hierarchy.apply_property_change_multi(&selected, &change, &self.type_system);
+        if restyles {
+            for id in selected {
+                self.sync_named_style(id);
+            }
+        }
+    }
+
+    /// Carries a styled widget's edited look to its style and the style's other users
+    fn sync_named_style(&mut self, id: WidgetId) {
+        let Some(widget) = self.hierarchy.get_widget_by_id(id) else { return };
+        let Some(style_id) = widget.properties.named_style else { return };
+        let look = ContainerLook::of(&widget.properties);
+        self.named_styles.set_look(style_id, look);
+        let others: Vec<WidgetId> = named_styles::users(self.hierarchy.root(), style_id)
+            .into_iter()
+            .map(|user| user.id)
+            .filter(|&user| user != id)
+            .collect();
+        for user in others {
+            self.hierarchy.apply_property_change(user, PropertyChange::NamedStyle(Some(style_id), look), &self.type_system);
+        }
+    }
+
+    fn refresh_hint(&mut self) {
+        let hint = hints::suggest(&self.hierarchy, self.refused_add, &self.disabled_hints, &self.dismissed_hints);
+        if hint != self.hint {
+            self.hint_dont_show_again = false;
+        }
+        self.hint = hint;
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let reparse = if self.hierarchy.has_pending_markdown() {
+            iced::time::every(MARKDOWN_DEBOUNCE / 2).map(Message::ReparseMarkdown)
+        } else {
+            Subscription::none()
+        };
+        let generate = if self.code_generation.has_pending() {
+            iced::time::every(code_generation::GENERATION_DEBOUNCE / 2).map(Message::GenerateCode)
+        } else {
+            Subscription::none()
+        };
+        Subscription::batch([self.input_subscription(), reparse, generate])
+    }
+
+    fn input_subscription(&self) -> Subscription<Message> {
+        use iced::keyboard::key::{Key, Named};
+
+        if self.command_palette.is_some() {
+            // The palette's text_input has focus and captures Escape, so listen to captured events too
+            event::listen_with(|event, _status, _window| match event {
+                Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) => match key.as_ref() {
+                    Key::Named(Named::ArrowUp) => Some(Message::CommandPaletteMove(-1)),
+                    Key::Named(Named::ArrowDown) => Some(Message::CommandPaletteMove(1)),
+                    Key::Named(Named::Escape) => Some(Message::CloseCommandPalette),
+                    _ => None,
+                },
+                _ => None,
+            })
+        } else {
+            // on_key_press only sees keys no widget captured, so a focused text_input keeps its keys.
+            // Bindings are resolved in update, where the current keybindings are available.
+            Subscription::batch([
+                keyboard::on_key_press(|key, modifiers| Some(Message::KeyPressed(key, modifiers))),
+                // Ctrl+scroll zooms the preview. Scrollables inside it may capture the wheel, so captured events count too.
+                event::listen_with(|event, _status, _window| match event {
+                    Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                        Some(Message::ModifiersChanged(modifiers))
+                    }
+                    Event::Mouse(iced::mouse::Event::WheelScrolled { delta }) => {
+                        Some(Message::PreviewViewport(ViewportMessage::Wheel(delta)))
+                    }
+                    _ => None,
+                }),
+            ])
+        }
+    }
+
+    fn handle_message(&mut self, message: Message) -> Action {
         match message {
             Message::TreeMove(drop_info) => {
                 if let Some(target_external_id) = drop_info.target_id {
@@ -1381,11 +3013,13 @@ impl WidgetVisualizer {
                                     let target_child_count = self.hierarchy.get_widget_by_id(target_id)
                                         .map(|w| w.children.len())
                                         .unwrap_or(0);
-                                    let _ = self.hierarchy.move_widget(
+                                    if let Err(e) = self.hierarchy.move_widget(
                                         dragged_id, 
                                         target_id, 
                                         target_child_count
-                                    );
+                                    ) {
+                                        self.notification = Some(format!("Couldn't move widget: {}", e));
+                                    }
                                 }
                             }
                         }
@@ -1410,7 +3044,9 @@ impl WidgetVisualizer {
                                         _ => target_index,
                                     };
                                     
-                                    let _ = self.hierarchy.move_widget(dragged_id, parent_id, insert_index);
+                                    if let Err(e) = self.hierarchy.move_widget(dragged_id, parent_id, insert_index) {
+                                        self.notification = Some(format!("Couldn't move widget: {}", e));
+                                    }
                                 }
                             }
                         }
@@ -1422,26 +3058,120 @@ impl WidgetVisualizer {
                 let widget_ids: HashSet<WidgetId> = external_ids.iter()
                     .map(|&id| WidgetId(id))
                     .collect();
+                self.selection_focus.reset_to(&widget_ids);
                 self.hierarchy.set_selected_ids(widget_ids);
             }
+            Message::TreeSelect(external_ids) => {
+                let reported: HashSet<WidgetId> = external_ids.iter()
+                    .map(|&id| WidgetId(id))
+                    .collect();
+                // Ranges run through the rows on screen, never the root or a collapsed branch's children
+                let selected = self.selection_focus.click(
+                    self.hierarchy.selected_ids(),
+                    reported,
+                    self.tree_rows.selectable(),
+                    self.modifiers,
+                );
+                self.hierarchy.set_selected_ids(selected);
+            }
+            Message::TreeStep(step) => {
+                let rows: Vec<WidgetId> = self.tree_rows.selectable().iter().copied().filter(|&id| self.shows_in_tree(id)).collect();
+                let next = match self.primary_selection().and_then(|id| rows.iter().position(|&row| row == id)) {
+                    Some(index) => index.checked_add_signed(step).and_then(|next| rows.get(next)),
+                    None => rows.first(),
+                };
+                if let Some(&next) = next {
+                    self.select_only(next);
+                }
+            }
+            Message::TreeSelectParent => {
+                let Some(widget) = self.primary_selection().and_then(|id| self.hierarchy.get_widget_by_id(id)) else {
+                    return Action::None;
+                };
+                if !widget.children.is_empty() && self.tree_rows.shows_children(widget) {
+                    return self.handle_message(Message::TreeSetExpanded(widget.id, false));
+                }
+                if let Some(parent_id) = self.hierarchy.find_parent_id(widget.id) {
+                    self.select_only(parent_id);
+                }
+            }
+            Message::TreeSelectFirstChild => {
+                let Some(widget) = self.primary_selection().and_then(|id| self.hierarchy.get_widget_by_id(id)) else {
+                    return Action::None;
+                };
+                if !widget.children.is_empty() && can_have_children(&widget.widget_type) && !self.tree_rows.shows_children(widget) {
+                    return self.handle_message(Message::TreeSetExpanded(widget.id, true));
+                }
+                let first = widget.children.iter().find(|child| self.shows_in_tree(child.id)).map(|child| child.id);
+                if let Some(first) = first {
+                    self.select_only(first);
+                }
+            }
+            Message::MoveAmongSiblings(id, step) => {
+                if let Err(e) = self.hierarchy.move_among_siblings(id, step) {
+                    self.notification = Some(format!("Couldn't move widget: {}", e));
+                }
+            }
+            Message::ExtendSelection(step) => {
+                if self.selection_focus.primary.is_none() {
+                    self.selection_focus.primary = self.primary_selection();
+                }
+                if let Some(selected) = self.selection_focus.extend(step, self.tree_rows.selectable()) {
+                    self.hierarchy.set_selected_ids(selected);
+                }
+            }
+            Message::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers;
+                self.preview_viewport.update(ViewportMessage::ModifiersChanged(modifiers));
+            }
             
             Message::DeleteWidget(id) => {
-                let _ = self.hierarchy.delete_widget(id);
+                if let Err(e) = self.hierarchy.delete_widget(id) {
+                    self.notification = Some(format!("Couldn't delete widget: {}", e));
+                }
+            }
+            Message::DeleteSelection => {
+                let root_id = self.hierarchy.root().id;
+                let selected: Vec<WidgetId> = self.hierarchy.selected_ids().iter()
+                    .copied()
+                    .filter(|&id| id != root_id)
+                    .collect();
+                // Children of an already deleted widget are gone with it, so those errors are expected
+                for id in selected {
+                    match self.hierarchy.delete_widget(id) {
+                        Ok(()) | Err(HierarchyError::WidgetNotFound(_)) => {}
+                        Err(e) => self.notification = Some(format!("Couldn't delete widget: {}", e)),
+                    }
+                }
             }
             
             Message::AddChild(parent_id, widget_type) => {
                 println!("Adding {:?} to parent {:?}", widget_type, parent_id);
-                if let Ok(new_id) = self.hierarchy.add_child(parent_id, widget_type) {
-                    println!("Successfully added with id {:?}", new_id);
-                    // Debug print the tree
-                    self.debug_print_widget(&self.hierarchy.root(), 0);
-                } else {
-                    println!("Failed to add child");
+                match self.hierarchy.add_child_with_defaults(parent_id, widget_type, &self.project_defaults) {
+                    Ok(new_id) => {
+                        println!("Successfully added with id {:?}", new_id);
+                        self.refused_add = None;
+                        // Debug print the tree
+                        self.debug_print_widget(&self.hierarchy.root(), 0);
+                    }
+                    // A full Container gets its own hint offering to wrap the contents instead
+                    Err(HierarchyError::ChildLimitExceeded { parent: WidgetType::Container, .. }) => {
+                        self.refused_add = Some(RefusedAdd { container: parent_id, widget_type });
+                    }
+                    Err(e) => {
+                        self.notification = Some(format!("Couldn't add {:?}: {}", widget_type, e));
+                    }
                 }
             }
             
             Message::PropertyChanged(id, change) => {
+                if let PropertyChange::AddTag(_) = change {
+                    self.tag_draft.clear();
+                }
                 self.hierarchy.apply_property_change(id, change.clone(), &self.type_system);
+                if ContainerLook::is_changed_by(&change) {
+                    self.sync_named_style(id);
+                }
 
                 match self.hierarchy.get_widget_by_id(id) {
                     Some(widget) => { 
@@ -1463,6 +3193,61 @@ impl WidgetVisualizer {
                 }
             }
 
+            Message::NumericEdited(field, text) => {
+                if let Some((left, value)) = self.hierarchy.numeric_inputs_mut().edit(field, text) {
+                    self.apply_numeric(left, value);
+                }
+            }
+
+            Message::NumericCommitted(field) => {
+                if let Some(value) = self.hierarchy.numeric_inputs_mut().commit(field) {
+                    self.apply_numeric(field, value);
+                }
+            }
+
+            Message::BatchNumericEdited(field, text) => {
+                if let Some((left, value)) = self.hierarchy.numeric_inputs_mut().edit(field, text) {
+                    self.apply_numeric_to_selection(left, value);
+                }
+            }
+
+            Message::BatchNumericCommitted(field) => {
+                if let Some(value) = self.hierarchy.numeric_inputs_mut().commit(field) {
+                    self.apply_numeric_to_selection(field, value);
+                }
+            }
+
+            Message::NumericRecall(older) => {
+                self.hierarchy.numeric_inputs_mut().recall(older);
+            }
+
+            Message::EditBulkRename(edit) => {
+                self.bulk_rename.edit(edit);
+            }
+            Message::ApplyBulkRename => {
+                match self.bulk_rename.preview(&self.hierarchy) {
+                    Ok(rows) => {
+                        let mut previous = Vec::new();
+                        for row in rows {
+                            let Some(widget) = self.hierarchy.get_widget_by_id(row.id) else { continue };
+                            previous.push((row.id, widget.properties.widget_name.clone()));
+                            self.hierarchy.apply_property_change(row.id, PropertyChange::WidgetName(row.new_name), &self.type_system);
+                        }
+                        self.notification = Some(format!("Renamed {} widgets", previous.len()));
+                        self.last_bulk_rename = Some(previous);
+                    }
+                    Err(e) => self.notification = Some(format!("Couldn't rename widgets: {}", e)),
+                }
+            }
+            Message::UndoBulkRename => {
+                if let Some(previous) = self.last_bulk_rename.take() {
+                    for (id, name) in previous {
+                        self.hierarchy.apply_property_change(id, PropertyChange::WidgetName(name), &self.type_system);
+                    }
+                    self.notification = Some("Bulk rename undone".to_string());
+                }
+            }
+
             Message::SwapKind(id) => {
                 self.hierarchy.swap_kind(id);
             }
@@ -1473,7 +3258,15 @@ impl WidgetVisualizer {
             }
             
             Message::TextInputChanged(id, value) => {
-                self.hierarchy.apply_property_change(id, PropertyChange::TextInputValue(value), &self.type_system);
+                // Typing over preview text edits the preview text, so the initial value stays empty
+                let shows_preview = self.show_preview_data && self.hierarchy.get_widget_by_id(id)
+                    .is_some_and(|widget| widget.properties.preview_text.is_some());
+                let change = if shows_preview {
+                    PropertyChange::PreviewText(Some(value))
+                } else {
+                    PropertyChange::TextInputValue(value)
+                };
+                self.hierarchy.apply_property_change(id, change, &self.type_system);
             }
 
             Message::Submitted(id) => { println!("{:?}, text_input submitted.", id); }
@@ -1535,13 +3328,44 @@ impl WidgetVisualizer {
                 // Do nothing - for preview-only interactions
             }
             Message::LinkClicked(url) => { println!("url clicked: {}", url) }
-
-            Message::GenerateFullCode => {
-                // You could open this in a modal/overlay
-                // For now, we'll just log it
-                let mut generator = CodeGenerator::new(&self.hierarchy, self.theme.clone(), Some(&self.type_system));
-                let tokens = generator.generate_app_code();
-                let code = tokens.iter().map(|t| t.text.clone()).collect::<String>();
+            Message::MarkdownPreviewToggled(open) => self.markdown_preview_open = open,
+            Message::ReparseMarkdown(now) => self.hierarchy.reparse_markdown(now),
+            Message::GenerateCode(now) => {
+                // A run that panicked isn't retried on every edit, only from its recovery view
+                if self.code_generation.is_due(now) && self.panic_guards.tripped(GuardedPanel::CodeGeneration).is_none() {
+                    return Action::Run(self.start_code_generation());
+                }
+            }
+            Message::CodeGenerated(run, Ok(code)) => {
+                self.code_generation.finish(run, code);
+            }
+            Message::CodeGenerated(run, Err(caught)) => {
+                self.code_generation.abandon(run);
+                self.panic_guards.trip(caught);
+            }
+            Message::RecoverPanel(panel) => {
+                match panel {
+                    GuardedPanel::Properties => {
+                        self.selection_focus.reset_to(&HashSet::new());
+                        self.hierarchy.set_selected_ids(HashSet::new());
+                        self.pinned_inspectors.clear();
+                    }
+                    GuardedPanel::Preview => {
+                        self.preview_viewport = PreviewViewport::default();
+                        self.right_pane = RightPane::Code;
+                    }
+                    GuardedPanel::CodeGeneration => {
+                        self.code_generation.invalidate(std::time::Instant::now());
+                    }
+                }
+                self.panic_guards.reset(panel);
+            }
+
+            Message::GenerateFullCode => {
+                // You could open this in a modal/overlay
+                // For now, we'll just log it
+                let mut generator = CodeGenerator::new(&self.hierarchy, self.theme.clone(), Some(&self.type_system));
+                let code = generator.generate_app_source();
                 println!("Generated Code:\n{}", code);
             }
             
@@ -1550,6 +3374,18 @@ impl WidgetVisualizer {
                     let _ = clipboard.set_text(code);
                 }
             }
+            Message::CodeSearchChanged(query) => {
+                let code = code_generator::source_of(&self.full_code_tokens());
+                if let Some(offset) = self.code_search.set_query(&code, query) {
+                    return Action::Run(iced::widget::operation::scroll_to(CODE_VIEW_SCROLLABLE, offset));
+                }
+            }
+            Message::CodeSearchSubmitted => {
+                let code = code_generator::source_of(&self.full_code_tokens());
+                if let Some(offset) = self.code_search.step(&code, self.modifiers.shift()) {
+                    return Action::Run(iced::widget::operation::scroll_to(CODE_VIEW_SCROLLABLE, offset));
+                }
+            }
             
 
             Message::ThemeChanged(theme) => {
@@ -1564,6 +3400,48 @@ impl WidgetVisualizer {
             Message::ToggleRadioLayout => { // To switch between column/row for radio widget code generation
 
             }
+            // Command palette
+            // Keybindings
+            Message::KeyPressed(..) => {} // Resolved in update
+            Message::CaptureKeybinding(action) => {
+                self.capturing_key = Some(action);
+                self.keybinding_conflict = None;
+            }
+            Message::CancelKeyCapture => {
+                self.capturing_key = None;
+                self.keybinding_conflict = None;
+            }
+            Message::ResetKeybinding(action) => {
+                self.capturing_key = None;
+                self.keybinding_conflict = self.keybindings.reset(action).err().map(|e| (action, e));
+            }
+
+            Message::OpenCommandPalette => {
+                self.command_palette = Some(CommandPalette::new(self.palette_actions()));
+                return Action::Run(iced::widget::operation::focus(COMMAND_PALETTE_INPUT));
+            }
+            Message::CloseCommandPalette => {
+                self.command_palette = None;
+            }
+            Message::CommandPaletteQueryChanged(query) => {
+                if let Some(palette) = &mut self.command_palette {
+                    palette.set_query(query);
+                }
+            }
+            Message::CommandPaletteMove(delta) => {
+                if let Some(palette) = &mut self.command_palette {
+                    palette.move_highlight(delta);
+                }
+            }
+            Message::CommandPaletteRun(index) => {
+                if let Some(palette) = self.command_palette.take() {
+                    if let Some(action) = palette.filtered().get(index) {
+                        // Go through update so structural changes rebuild the tree rows
+                        return self.update(action.message.clone());
+                    }
+                }
+            }
+
             Message::OpenHome => {
                 // Should Open / Focus the Home Page
                 self.left_pane = LeftPane::Home;
@@ -1592,12 +3470,159 @@ impl WidgetVisualizer {
             Message::AppStructName2Changed(value) => {
                 self.app_name = value;
             }
+            Message::WindowPresetChosen(preset) => self.window_settings = preset.settings(),
+            Message::WindowSettingsChanged(settings) => self.window_settings = settings,
+            Message::FileHeader(message) => {
+                let header = &mut self.file_header;
+                match message {
+                    FileHeaderMessage::Toggled(enabled) => header.enabled = enabled,
+                    FileHeaderMessage::AuthorChanged(author) => header.author = author,
+                    FileHeaderMessage::LicensePicked(license) => header.license = license.to_string(),
+                    FileHeaderMessage::LicenseChanged(license) => header.license = license,
+                    FileHeaderMessage::YearChanged(year) => {
+                        if let Ok(year) = year.trim().parse() {
+                            header.year = year;
+                        }
+                    }
+                    FileHeaderMessage::TemplateEdited(action) => {
+                        self.file_header_template.perform(action);
+                        header.template = self.file_header_template.text();
+                    }
+                    FileHeaderMessage::ResetTemplate => {
+                        header.template = file_header::DEFAULT_TEMPLATE.to_string();
+                        self.file_header_template = text_editor::Content::with_text(file_header::DEFAULT_TEMPLATE);
+                    }
+                }
+            }
             Message::MultipleWindowsToggled(checked) => {
                 self.multiple_windows = checked;
             }
             Message::OutlineSelectedWidgetsToggled(b) => {
                 self.highlight_selected = b;
             }
+            Message::DocCommentsToggled(b) => {
+                self.doc_comments = b;
+            }
+            Message::CodeStyleChanged(style) => {
+                self.code_style = style;
+            }
+            Message::StubStyleChanged(style) => {
+                self.stub_style = style;
+            }
+            Message::DeriveDefaultToggled(b) => {
+                self.derive_default = b;
+            }
+            Message::ViewBuildersToggled(b) => {
+                self.view_builders = b;
+            }
+            Message::ViewHelpersToggled(b) => {
+                self.view_helpers = b;
+            }
+            Message::ViewHelperChildrenChanged(value) => {
+                if let Ok(children) = value.trim().parse::<usize>() {
+                    self.view_helper_children = children;
+                }
+            }
+            Message::MultiWindowToggled(b) => {
+                self.multi_window = b;
+            }
+            Message::LayoutOnlyToggled(b) => {
+                self.layout_only = b;
+            }
+            Message::PaddingOverlayToggled(b) => {
+                self.show_padding_overlay = b;
+            }
+            Message::FillPortionOverlayToggled(b) => {
+                self.show_fill_portions = b;
+            }
+            Message::AddWidgetFilterChanged(filter) => {
+                self.add_widget_filter = filter;
+            }
+            Message::FocusWidgetFilter => {
+                return Action::Run(iced::widget::operation::focus(views::add_widgets::ADD_WIDGET_FILTER_INPUT));
+            }
+            Message::PreviewDataToggled(b) => {
+                self.show_preview_data = b;
+            }
+            Message::BreakpointPreviewWidth(width) => {
+                self.breakpoint_preview_width = width;
+            }
+            Message::PreviewViewport(msg) => {
+                if let Some(offset) = self.preview_viewport.update(msg) {
+                    return Action::Run(iced::widget::operation::scroll_to(PREVIEW_VIEWPORT, offset));
+                }
+            }
+
+            // Project defaults
+            Message::DefaultTextSizeChanged(v) => self.project_defaults.text_size = v,
+            Message::DefaultSpacingChanged(v) => self.project_defaults.layout_spacing = v,
+            Message::DefaultPaddingChanged(v) => self.project_defaults.padding = v,
+            Message::DefaultBorderRadiusChanged(v) => self.project_defaults.border_radius = v,
+            Message::DefaultButtonStyleChanged(style) => self.project_defaults.button_style = style,
+            Message::ApplyDefaultToExisting(property) => {
+                let change = self.project_defaults.change_for(property);
+                for id in self.hierarchy.ids_for_default(property) {
+                    self.hierarchy.apply_property_change(id, change.clone(), &self.type_system);
+                }
+            }
+            Message::CodeBudgetChanged(metric, limit) => self.code_budget.set_limit(metric, limit),
+            Message::ResetCodeBudget => self.code_budget = CodeBudget::default(),
+            Message::CodeFontFamiliesChanged(families) => {
+                self.code_font.families = families;
+                self.resolve_code_font();
+            }
+            Message::CodeFontSizeChanged(size) => self.code_font.size = size,
+            Message::ResetCodeFont => self.set_code_font(CodeFontSettings::default()),
+
+            // Design tokens
+            Message::ImportDesignTokens => {
+                return Action::Run(iced::Task::perform(
+                    file_io::open_text_file("Design tokens", &["json", "toml"]),
+                    Message::DesignTokensLoaded,
+                ));
+            }
+            Message::DesignTokensLoaded(Ok((path, contents))) => {
+                match design_tokens::parse_tokens(&contents, TokenFormat::from_path(&path)) {
+                    Ok(tokens) => {
+                        let name = path.file_stem()
+                            .map(|stem| stem.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| "Design Tokens".to_string());
+                        self.token_import = Some(TokenImport::new(name, tokens, self.design_tokens.as_ref()));
+                        self.left_pane = LeftPane::Settings;
+                    }
+                    Err(e) => self.notification = Some(format!("{}: {}", path.display(), e)),
+                }
+            }
+            Message::DesignTokensLoaded(Err(e)) => {
+                self.notification = Some(e);
+            }
+            Message::EditTokenImport(edit) => {
+                if let Some(import) = &mut self.token_import {
+                    import.edit(edit);
+                }
+            }
+            Message::ApplyDesignTokens => {
+                if let Some(import) = self.token_import.take() {
+                    let palette = design_tokens::palette(&import.tokens, &import.mapping, self.theme.palette());
+                    let theme = Theme::custom(import.name.clone(), palette);
+                    self.imported_themes.retain(|t| t.to_string() != import.name);
+                    self.imported_themes.push(theme.clone());
+                    self.custom_themes.theme = theme.clone();
+                    self.theme = theme;
+
+                    if let Some(spacing) = import.spacing() {
+                        self.project_defaults.layout_spacing = spacing;
+                    }
+                    if let Some(radius) = import.radius() {
+                        self.project_defaults.border_radius = radius;
+                    }
+                    self.notification = Some(format!("Applied design tokens \"{}\"", import.name));
+                    self.design_tokens = Some(import.tokens);
+                }
+            }
+            Message::CancelDesignTokens => {
+                self.token_import = None;
+            }
 
             Message::ForwardThemeMessages(msg) => {
                 let task = self.custom_themes.update(msg);
@@ -1608,12 +3633,413 @@ impl WidgetVisualizer {
             Message::OpenTypeEditor => {
                 self.left_pane = LeftPane::Types;
             }
+            Message::TypeEditor(type_editor::Message::SelectWidget(id)) => {
+                self.handle_message(Message::SelectWidgets(HashSet::from([id.0])));
+                self.left_pane = LeftPane::Home;
+            }
             Message::TypeEditor(msg) => {
                 let task = type_editor::update(msg, &mut self.type_system, &mut self.type_editor)
                     .map(Message::TypeEditor);
 
                 return Action::Run(task);
             }
+            Message::EditEnum(enum_id) => {
+                self.type_editor.focus_enum(&self.type_system, enum_id);
+                self.left_pane = LeftPane::Types;
+            }
+            Message::EnumForm(msg) => match msg {
+                EnumFormMessage::Open(id) => {
+                    self.enum_form = Some(NewEnumForm::new(id));
+                }
+                EnumFormMessage::NameChanged(name) => {
+                    if let Some(form) = &mut self.enum_form {
+                        form.name = name;
+                        form.error = None;
+                    }
+                }
+                EnumFormMessage::VariantsEdited(action) => {
+                    if let Some(form) = &mut self.enum_form {
+                        form.variants.perform(action);
+                        form.error = None;
+                    }
+                }
+                EnumFormMessage::Create => {
+                    if let Some(form) = &mut self.enum_form {
+                        match enum_form::create_enum(&mut self.type_system, &form.name, &form.variants.text()) {
+                            Ok(enum_id) => {
+                                let widget_id = form.widget_id;
+                                self.enum_form = None;
+                                self.type_editor.sync_with_type_system(&self.type_system);
+                                // Same path as picking the enum, which rebuilds the combo box options
+                                return self.handle_message(Message::PropertyChanged(
+                                    widget_id,
+                                    PropertyChange::ComboBoxEnumId(Some(enum_id)),
+                                ));
+                            }
+                            Err(e) => form.error = Some(e),
+                        }
+                    }
+                }
+                EnumFormMessage::Cancel => {
+                    self.enum_form = None;
+                }
+            },
+
+            // Option list import
+            Message::OptionImport(msg) => match msg {
+                OptionImportMessage::Open(widget_id, target) => {
+                    self.option_import = Some(OptionImportForm::new(widget_id, target));
+                }
+                OptionImportMessage::TextEdited(action) => {
+                    if let Some(form) = &mut self.option_import {
+                        form.text.perform(action);
+                        form.error = None;
+                    }
+                }
+                OptionImportMessage::AppendToggled(append) => {
+                    if let Some(form) = &mut self.option_import {
+                        form.append = append;
+                    }
+                }
+                OptionImportMessage::ChooseFile => {
+                    return Action::Run(iced::Task::perform(
+                        file_io::open_text_file("Option lists", &["csv", "txt"]),
+                        |result| Message::OptionImport(OptionImportMessage::FileLoaded(result)),
+                    ));
+                }
+                OptionImportMessage::FileLoaded(Ok((path, contents))) => {
+                    if let Some(form) = &mut self.option_import {
+                        form.text = text_editor::Content::with_text(&contents);
+                        form.source = Some(path);
+                        form.error = None;
+                    }
+                }
+                OptionImportMessage::FileLoaded(Err(e)) => {
+                    self.notification = Some(e);
+                }
+                OptionImportMessage::Apply => {
+                    let Some(form) = &mut self.option_import else { return Action::None };
+                    let Some(widget) = self.hierarchy.get_widget_by_id(form.widget_id) else {
+                        self.option_import = None;
+                        return Action::None;
+                    };
+                    let current = form.target.current(&widget.properties);
+                    let imported = ImportedOptions::parse(&form.text.text(), current, form.append);
+                    let added = imported.options.len() - if form.append { current.len() } else { 0 };
+                    if added == 0 {
+                        form.error = Some("Nothing to import, every value was empty or a duplicate".to_string());
+                        return Action::None;
+                    }
+
+                    let mut report = imported.report(added);
+                    if form.target.is_too_long(imported.options.len()) {
+                        report.push_str(". A list this long may be slow to draw, a ComboBox filters as you type instead");
+                    }
+                    let (widget_id, change) = (form.widget_id, form.target.change(imported.options));
+                    self.option_import = None;
+                    self.notification = Some(report);
+                    return self.handle_message(Message::PropertyChanged(widget_id, change));
+                }
+                OptionImportMessage::Cancel => {
+                    self.option_import = None;
+                }
+                OptionImportMessage::Export(widget_id, target) => {
+                    if let Some(widget) = self.hierarchy.get_widget_by_id(widget_id) {
+                        let contents = option_import::export_text(target.current(&widget.properties));
+                        let default_name = format!("{}_options.txt", code_generator::to_snake_case(&widget.name));
+                        return Action::Run(iced::Task::perform(
+                            file_io::save_text_file(contents, default_name, "Text", &["txt"]),
+                            Message::FileSaved,
+                        ));
+                    }
+                }
+            },
+
+            // Reference image eyedropper
+            Message::ReferenceImage(msg) => match msg {
+                ReferenceMessage::Target(widget_id, change) => {
+                    self.reference.open = true;
+                    self.reference.target = Some(ColorTarget { widget_id, change });
+                }
+                ReferenceMessage::ChooseImage => {
+                    return Action::Run(iced::Task::perform(
+                        file_io::pick_reference_image(reference_image::REFERENCE_EXTENSIONS),
+                        |result| Message::ReferenceImage(ReferenceMessage::Chosen(result)),
+                    ));
+                }
+                ReferenceMessage::Chosen(Ok(path)) => {
+                    self.reference.loading = true;
+                    self.reference.error = None;
+                    return Action::Run(iced::Task::perform(
+                        reference_image::load(path),
+                        |result| Message::ReferenceImage(ReferenceMessage::Loaded(result)),
+                    ));
+                }
+                ReferenceMessage::Chosen(Err(_)) => {}
+                ReferenceMessage::Loaded(result) => {
+                    self.reference.loading = false;
+                    match result {
+                        Ok(image) => {
+                            self.reference.image = Some(image);
+                            self.reference.hover = None;
+                        }
+                        Err(e) => self.reference.error = Some(e),
+                    }
+                }
+                ReferenceMessage::PointerMoved(point) => {
+                    self.reference.hover = Some(point);
+                }
+                ReferenceMessage::PointerLeft => {
+                    self.reference.hover = None;
+                }
+                ReferenceMessage::Sample => {
+                    if let Some((widget_id, change)) = self.reference.sampled_change() {
+                        // Later clicks keep filling the same property
+                        self.reference.target = Some(ColorTarget { widget_id, change: change.clone() });
+                        return self.handle_message(Message::PropertyChanged(widget_id, change));
+                    }
+                }
+                ReferenceMessage::AverageToggled(average) => {
+                    self.reference.average = average;
+                }
+                ReferenceMessage::Close => {
+                    self.reference.open = false;
+                    self.reference.target = None;
+                    self.reference.hover = None;
+                }
+            },
+
+            // Asset library
+            Message::Assets(msg) => match msg {
+                AssetMessage::Open => {
+                    self.left_pane = LeftPane::Assets;
+                }
+                AssetMessage::AddFiles => {
+                    return Action::Run(iced::Task::perform(
+                        file_io::pick_asset_files(asset_library::ASSET_EXTENSIONS),
+                        |result| Message::Assets(AssetMessage::Added(result)),
+                    ));
+                }
+                AssetMessage::AddFolder => {
+                    return Action::Run(iced::Task::perform(
+                        file_io::pick_asset_folder(asset_library::ASSET_EXTENSIONS),
+                        |result| Message::Assets(AssetMessage::Added(result)),
+                    ));
+                }
+                AssetMessage::Added(Ok(paths)) => {
+                    let picked = paths.len();
+                    let added = self.asset_library.add_files(paths);
+                    self.left_pane = LeftPane::Assets;
+                    self.notification = Some(match picked - added {
+                        0 => format!("Added {} asset(s) to the library", added),
+                        skipped => format!("Added {} asset(s), {} already in the library", added, skipped),
+                    });
+                }
+                AssetMessage::Added(Err(e)) => {
+                    self.notification = Some(e);
+                }
+                AssetMessage::StartRename(id) => {
+                    if let Some(asset) = self.asset_library.get(id) {
+                        self.asset_rename = Some(AssetRename { id, name: asset.name.clone(), error: None });
+                    }
+                }
+                AssetMessage::RenameEdited(name) => {
+                    if let Some(rename) = &mut self.asset_rename {
+                        rename.name = name;
+                        rename.error = None;
+                    }
+                }
+                AssetMessage::CommitRename => {
+                    if let Some(rename) = &mut self.asset_rename {
+                        match self.asset_library.rename(rename.id, &rename.name) {
+                            Ok(()) => self.asset_rename = None,
+                            Err(e) => rename.error = Some(e),
+                        }
+                    }
+                }
+                AssetMessage::CancelRename => {
+                    self.asset_rename = None;
+                }
+                AssetMessage::Remove(id) => {
+                    if let Some(asset) = self.asset_library.remove(id) {
+                        self.notification = Some(format!("Removed {} from the library, widgets using it keep its path", asset.name));
+                    }
+                }
+                AssetMessage::Relink(id) => {
+                    if let Some(asset) = self.asset_library.get(id) {
+                        return Action::Run(iced::Task::perform(
+                            file_io::pick_asset_file(asset.kind.extensions()),
+                            move |result| Message::Assets(AssetMessage::Relinked(id, result)),
+                        ));
+                    }
+                }
+                AssetMessage::Relinked(id, Ok(path)) => {
+                    match self.asset_library.relink(id, path) {
+                        Ok(previous) => self.follow_asset(id, &previous.to_string_lossy()),
+                        Err(e) => self.notification = Some(e),
+                    }
+                }
+                AssetMessage::Relinked(_, Err(e)) => {
+                    self.notification = Some(e);
+                }
+                AssetMessage::CheckFiles => {
+                    self.asset_library.check_files();
+                    self.notification = Some(match self.asset_library.missing_count() {
+                        0 => "Every asset's file was found".to_string(),
+                        missing => format!("{} asset file(s) are missing", missing),
+                    });
+                }
+                AssetMessage::Use(widget_id, asset_id) => {
+                    if let Some(asset) = self.asset_library.get(asset_id) {
+                        return self.handle_message(Message::PropertyChanged(widget_id, asset.property_change()));
+                    }
+                }
+            },
+
+            Message::Styles(msg) => match msg {
+                StyleMessage::StartPromote(id) => {
+                    self.style_promote = Some((id, String::new()));
+                }
+                StyleMessage::NameChanged(name) => {
+                    if let Some((_, draft)) = &mut self.style_promote {
+                        *draft = name;
+                    }
+                }
+                StyleMessage::Promote => {
+                    let Some((id, name)) = self.style_promote.take() else { return Action::None };
+                    let Some(widget) = self.hierarchy.get_widget_by_id(id) else { return Action::None };
+                    let look = ContainerLook::of(&widget.properties);
+                    match self.named_styles.promote(&name, look) {
+                        Ok(style) => {
+                            let change = PropertyChange::NamedStyle(Some(style.id), look);
+                            self.notification = Some(format!("Saved the look as style {}", style.name));
+                            self.hierarchy.apply_property_change(id, change, &self.type_system);
+                        }
+                        Err(e) => {
+                            self.notification = Some(format!("Couldn't save the style: {}", e));
+                            self.style_promote = Some((id, name));
+                        }
+                    }
+                }
+                StyleMessage::CancelPromote => {
+                    self.style_promote = None;
+                }
+                StyleMessage::Delete(style_id) => {
+                    if let Some(style) = self.named_styles.remove(style_id) {
+                        let users: Vec<(WidgetId, ContainerLook)> = named_styles::users(self.hierarchy.root(), style_id)
+                            .into_iter()
+                            .map(|user| (user.id, ContainerLook::of(&user.properties)))
+                            .collect();
+                        for &(id, look) in &users {
+                            self.hierarchy.apply_property_change(id, PropertyChange::NamedStyle(None, look), &self.type_system);
+                        }
+                        self.notification = Some(format!(
+                            "Deleted style {}, {} container(s) keep its look inline", style.name, users.len(),
+                        ));
+                    }
+                }
+            },
+
+            // View code import
+            Message::ViewImport(msg) => match msg {
+                ViewImportMessage::Open => {
+                    self.view_import = Some(ViewImportForm::new());
+                }
+                ViewImportMessage::CodeEdited(action) => {
+                    if let Some(form) = &mut self.view_import {
+                        form.code.perform(action);
+                        form.error = None;
+                    }
+                }
+                ViewImportMessage::Import => {
+                    let Some(form) = &mut self.view_import else { return Action::None };
+                    match view_import::import_view(&form.code.text()) {
+                        Ok(imported) => {
+                            let report = match imported.warnings.len() {
+                                0 => format!("Imported {} widgets into a new tab", imported.widget_count),
+                                n => format!("Imported {} widgets into a new tab, with {} warnings", imported.widget_count, n),
+                            };
+                            // Warnings stay up so what was left out can be fixed by hand
+                            if imported.warnings.is_empty() {
+                                self.view_import = None;
+                            } else {
+                                form.warnings = imported.warnings;
+                                form.error = None;
+                            }
+                            self.open_tab(imported.hierarchy);
+                            self.notification = Some(report);
+                        }
+                        Err(e) => {
+                            form.warnings.clear();
+                            form.error = Some(e);
+                        }
+                    }
+                }
+                ViewImportMessage::Close => {
+                    self.view_import = None;
+                }
+            },
+
+            Message::Workspace(msg) => match msg {
+                WorkspaceMessage::Select(index) => {
+                    self.select_tab(index);
+                }
+                WorkspaceMessage::New => {
+                    self.new_tab();
+                }
+                WorkspaceMessage::Press(index) => {
+                    self.workspace.dragging = Some((index, index));
+                }
+                WorkspaceMessage::Hover(index) => {
+                    if let Some((_, over)) = &mut self.workspace.dragging {
+                        *over = index;
+                    }
+                }
+                WorkspaceMessage::Release => {
+                    // Released over the pressed tab it was a click, elsewhere a drag
+                    let Some((from, to)) = self.workspace.dragging.take() else { return Action::None };
+                    if from == to {
+                        self.select_tab(from);
+                    } else {
+                        self.workspace.move_tab(from, to);
+                    }
+                }
+                WorkspaceMessage::StartRename(index) => {
+                    if let Some(tab) = self.workspace.tabs().get(index) {
+                        self.workspace.rename = Some((index, tab.name.clone()));
+                    }
+                }
+                WorkspaceMessage::RenameEdited(name) => {
+                    if let Some((_, draft)) = &mut self.workspace.rename {
+                        *draft = name;
+                    }
+                }
+                WorkspaceMessage::CommitRename => {
+                    let Some((index, name)) = self.workspace.rename.take() else { return Action::None };
+                    if let Err(e) = self.workspace.rename(index, &name) {
+                        self.notification = Some(format!("Couldn't rename the tab: {}", e));
+                        self.workspace.rename = Some((index, name));
+                    }
+                }
+                WorkspaceMessage::CancelRename => {
+                    self.workspace.rename = None;
+                }
+                WorkspaceMessage::Close(index) => {
+                    if self.tab_has_work(index) {
+                        self.workspace.closing = Some(index);
+                    } else {
+                        self.close_tab(index);
+                    }
+                }
+                WorkspaceMessage::ConfirmClose => {
+                    if let Some(index) = self.workspace.closing.take() {
+                        self.close_tab(index);
+                    }
+                }
+                WorkspaceMessage::CancelClose => {
+                    self.workspace.closing = None;
+                }
+            },
 
             Message::WrapSelectedInContainer(container_type) => {
                 match self.hierarchy.wrap_selected_in_container(container_type) {
@@ -1622,39 +4048,1101 @@ impl WidgetVisualizer {
                                  container_type, wrapper_id);
                     }
                     Err(e) => {
-                        println!("Failed to wrap widgets: {}", e);
-                        // TODO: Show error to user (could add a status message field)
+                        self.notification = Some(format!("Couldn't wrap widgets: {}", e));
+                    }
+                }
+            }
+            
+            Message::WrapWidget(id, wrapper_type) => {
+                if let Err(e) = self.hierarchy.wrap_widget(id, wrapper_type) {
+                    self.notification = Some(format!("Couldn't wrap widget: {}", e));
+                }
+            }
+            Message::UnwrapWidget(id) => {
+                if let Err(e) = self.hierarchy.unwrap_widget(id) {
+                    self.notification = Some(format!("Couldn't unwrap widget: {}", e));
+                }
+            }
+            Message::DuplicateWidget(id) => {
+                if let Err(e) = self.hierarchy.duplicate_subtree(id) {
+                    self.notification = Some(format!("Couldn't duplicate widget: {}", e));
+                }
+            }
+            
+            Message::BatchPropertyChanged(change) => {
+                if let PropertyChange::AddTag(_) = change {
+                    self.tag_draft.clear();
+                }
+                let restyles = ContainerLook::is_changed_by(&change);
+                self.hierarchy.apply_property_to_all_selected(change, &self.type_system);
+                if restyles {
+                    let selected: Vec<WidgetId> = self.hierarchy.selected_ids().iter().copied().collect();
+                    for id in selected {
+                        self.sync_named_style(id);
+                    }
+                }
+            }
+
+            // Tags
+            Message::TagDraftChanged(draft) => {
+                self.tag_draft = draft;
+            }
+            Message::TagFilterChanged(tag) => {
+                self.tag_filter = tag.map(|tag| {
+                    let visible = tags::visible_ids(self.hierarchy.root(), &tag);
+                    (tag, visible)
+                });
+            }
+
+            // Exports
+            Message::ExportLayoutReport => {
+                let report = self.layout_report();
+                let default_name = format!("{}_layout.md", code_generator::to_snake_case(&self.app_name));
+                return Action::Run(iced::Task::perform(
+                    file_io::save_text_file(report, default_name, "Markdown", &["md"]),
+                    Message::FileSaved,
+                ));
+            }
+            Message::ExportPrintable => {
+                let code = code_generator::source_of(&self.full_code().tokens);
+                let printable = printable::generate_printable(
+                    &self.hierarchy,
+                    &self.type_system,
+                    &self.app_name,
+                    &self.theme,
+                    &code,
+                    self.print_settings,
+                );
+                let format = self.print_settings.format;
+                let default_name = format!("{}_print.{}", code_generator::to_snake_case(&self.app_name), format.extensions()[0]);
+                return Action::Run(iced::Task::perform(
+                    file_io::save_text_file(printable, default_name, format.filter_name(), format.extensions()),
+                    Message::FileSaved,
+                ));
+            }
+            Message::PrintLineWidthChanged(value) => {
+                if let Ok(width) = value.trim().parse::<usize>() {
+                    self.print_settings.line_width = width.min(printable::MAX_LINE_WIDTH);
+                }
+            }
+            Message::PrintFormatChanged(format) => {
+                self.print_settings.format = format;
+            }
+            Message::SaveProject => {
+                // Only the copy is made here, serializing and writing run in the task
+                let default_name = format!("{}.json", code_generator::to_snake_case(&self.app_name));
+                return Action::Run(iced::Task::perform(
+                    project_file::save(self.project_file(), default_name),
+                    Message::FileSaved,
+                ));
+            }
+            Message::OpenProject => {
+                if self.loading_project.is_none() {
+                    return Action::Run(iced::Task::perform(
+                        file_io::pick_open_file("Builder project", project_file::EXTENSIONS),
+                        Message::ProjectChosen,
+                    ));
+                }
+            }
+            Message::ProjectChosen(Ok(path)) => {
+                self.loading_project = Some(path.clone());
+                return Action::Run(iced::Task::perform(project_file::read(path), Message::ProjectOpened));
+            }
+            Message::ProjectChosen(Err(e)) => {
+                self.notification = Some(e);
+            }
+            Message::ProjectOpened(Ok(project)) => {
+                self.loading_project = None;
+                let project = std::sync::Arc::try_unwrap(project).unwrap_or_else(|shared| (*shared).clone());
+                self.notification = Some(format!("Opened {}", project.path.display()));
+                self.apply_project(project);
+            }
+            Message::ProjectOpened(Err(e)) => {
+                self.loading_project = None;
+                self.notification = Some(e);
+            }
+            Message::ExportProject => {
+                let export = project_export::build_project(
+                    &self.hierarchy,
+                    &self.type_system,
+                    &self.generation_settings(),
+                    &self.asset_library,
+                );
+                return Action::Run(iced::Task::perform(
+                    file_io::save_project(project_export::project_files(&export), export.asset_copies),
+                    Message::FileSaved,
+                ));
+            }
+            Message::ExportMainRs => {
+                // A bare main.rs has nowhere to put a README, so only the code is written
+                let export = project_export::build_project(
+                    &self.hierarchy,
+                    &self.type_system,
+                    &self.generation_settings(),
+                    &self.asset_library,
+                );
+                return Action::Run(iced::Task::perform(
+                    file_io::save_text_file(export.main_rs, "main.rs".to_string(), "Rust", &["rs"]),
+                    Message::FileSaved,
+                ));
+            }
+            Message::TakeComparisonSnapshot => {
+                self.comparison_snapshot = Some(Comparison { root: self.hierarchy.root().clone(), source: None });
+                self.notification = Some("Snapshot taken, later changes can be compared against it".to_string());
+            }
+            Message::ShowComparison(show) => {
+                self.show_comparison = show && self.comparison_snapshot.is_some();
+            }
+            Message::CompareWithProject => {
+                return Action::Run(iced::Task::perform(
+                    file_io::open_text_file("Builder project", project_file::EXTENSIONS),
+                    Message::ComparisonProjectOpened,
+                ));
+            }
+            Message::ComparisonProjectOpened(Ok((path, contents))) => {
+                match ProjectFile::parse(&contents) {
+                    Ok(file) => {
+                        let root = comparison_root(file, self.workspace.active_name());
+                        self.notification = Some(format!("Comparing with {}", path.display()));
+                        self.comparison_snapshot = Some(Comparison { root, source: Some(path) });
+                        self.show_comparison = true;
+                    }
+                    Err(e) => self.notification = Some(format!("Couldn't compare with {}: {}", path.display(), e)),
+                }
+            }
+            Message::ComparisonProjectOpened(Err(e)) => {
+                self.notification = Some(e);
+            }
+            Message::CopyLayoutReport => {
+                let copied = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(self.layout_report()));
+                self.notification = Some(match copied {
+                    Ok(()) => "Layout report copied to clipboard".to_string(),
+                    Err(e) => format!("Failed to copy layout report: {}", e),
+                });
+            }
+            Message::FileSaved(result) => {
+                self.notification = Some(match result {
+                    Ok(path) => {
+                        let notice = saved_notice(&path);
+                        self.last_export = Some(path);
+                        notice
+                    }
+                    Err(e) => e,
+                });
+            }
+            Message::OpenLastExport => {
+                if let Some(path) = self.last_export.clone() {
+                    return Action::Run(iced::Task::perform(opener::open_path(path), Message::ExportOpened));
+                }
+            }
+            Message::ExportOpened(result) => {
+                if let Err(e) = result {
+                    self.notification = Some(e);
+                }
+            }
+            Message::DismissNotification => {
+                self.notification = None;
+            }
+            Message::Notification(message) => match message {
+                NotificationMessage::ToggleHistory => {
+                    self.notification_history.open = !self.notification_history.open;
+                }
+                NotificationMessage::Copy(index) => {
+                    if let Some(entry) = self.notification_history.get(index) {
+                        return Action::Run(iced::clipboard::write(entry.copy_text()));
+                    }
+                }
+                NotificationMessage::CopyAll => {
+                    return Action::Run(iced::clipboard::write(self.notification_history.copy_all()));
+                }
+                NotificationMessage::Clear => {
+                    self.notification_history = NotificationHistory::default();
+                }
+            },
+
+            // Hints
+            Message::ApplyHint(fix) => {
+                if let Some(hint) = self.hint.take() {
+                    match hints::apply_fix(&mut self.hierarchy, fix, &self.project_defaults, &self.type_system) {
+                        Ok(_) => {
+                            if hint.rule() == HintRule::ContainerToColumn {
+                                self.refused_add = None;
+                            }
+                        }
+                        Err(e) => self.notification = Some(format!("Couldn't apply suggestion: {}", e)),
+                    }
+                }
+            }
+            Message::DismissHint => {
+                if let Some(hint) = self.hint.take() {
+                    if self.hint_dont_show_again {
+                        self.disabled_hints.insert(hint.rule());
+                    } else {
+                        self.dismissed_hints.insert((hint.rule(), hint.target));
+                    }
+                }
+            }
+            Message::HintDontShowAgainToggled(b) => {
+                self.hint_dont_show_again = b;
+            }
+            Message::ApplyContrastSuggestion(id) => {
+                if let Some(suggestion) = contrast::suggest(&self.hierarchy, id, &self.theme) {
+                    for target in suggestion.targets {
+                        self.hierarchy.apply_property_change(target, PropertyChange::TextColor(suggestion.text_color), &self.type_system);
+                    }
+                }
+            }
+            Message::DismissContrastSuggestion(id) => {
+                if let Some(widget) = self.hierarchy.get_widget_by_id(id) {
+                    self.dismissed_contrast.insert(id, widget.properties.background_color);
+                }
+            }
+            // Property history
+            Message::HistoryToggled => {
+                self.show_history = !self.show_history;
+            }
+            Message::RevertProperty(id, seq) => {
+                let revert = self.hierarchy.history().find(id, seq).map(|entry| entry.revert.clone());
+                for change in revert.unwrap_or_default() {
+                    // Same path as an edit in the panel, so the revert is logged too
+                    self.handle_message(Message::PropertyChanged(id, change));
+                }
+            }
+            Message::JumpToUndoStep(index) => {
+                self.jump_to_undo_step(index);
+            }
+            Message::Undo => {
+                if let Some(index) = self.undo.position().checked_sub(1) {
+                    self.jump_to_undo_step(index);
+                }
+            }
+            Message::Redo => {
+                if self.undo.can_redo() {
+                    self.jump_to_undo_step(self.undo.position() + 1);
+                }
+            }
+            Message::UndoStepHovered(index) => {
+                self.hovered_undo = index;
+            }
+
+            Message::HintRuleToggled(rule, enabled) => {
+                if enabled {
+                    self.disabled_hints.remove(&rule);
+                } else {
+                    self.disabled_hints.insert(rule);
+                }
+            }
+
+            // Pinned inspectors
+            Message::PinInspector(id) => {
+                if !self.pinned_inspectors.contains(&id) && self.pinned_inspectors.len() < MAX_PINNED_INSPECTORS {
+                    self.pinned_inspectors.push(id);
+                }
+            }
+            Message::UnpinInspector(id) => {
+                self.pinned_inspectors.retain(|pinned| *pinned != id);
+                self.wide_inspectors.remove(&id);
+            }
+            Message::WidenInspector(id) => {
+                if !self.wide_inspectors.remove(&id) {
+                    self.wide_inspectors.insert(id);
+                }
+            }
+
+            // Tree windowing
+            Message::TreeSetExpanded(id, expanded) => {
+                if expanded {
+                    self.tree_rows.collapsed.remove(&id);
+                } else {
+                    self.tree_rows.collapsed.insert(id);
+                }
+                self.tree_rows.rebuild(self.hierarchy.root());
+            }
+            Message::TreeScrolled(viewport) => {
+                self.tree_rows.scroll_offset = viewport.absolute_offset().y;
+                self.tree_rows.viewport_height = viewport.bounds().height;
+            }
+            Message::GenerateStressHierarchy => {
+                // Debug helper: 50 columns with 19 widgets each, ~1000 widgets total
+                let root_id = self.hierarchy.root().id;
+                let parent_id = match self.hierarchy.root().children.first() {
+                    Some(child) if can_have_children(&child.widget_type) => Ok(child.id),
+                    Some(child) => Err(HierarchyError::InvalidParent { parent: child.widget_type, child: WidgetType::Column }),
+                    None => self.hierarchy.add_child(root_id, WidgetType::Column),
+                };
+                let Ok(parent_id) = parent_id else { return Action::None };
+
+                let leaf_types = [WidgetType::Text, WidgetType::Button, WidgetType::Checkbox, WidgetType::Slider];
+                for _ in 0..50 {
+                    if let Ok(column_id) = self.hierarchy.add_child(parent_id, WidgetType::Column) {
+                        for i in 0..19 {
+                            let _ = self.hierarchy.add_child(column_id, leaf_types[i % leaf_types.len()]);
+                        }
+                    }
+                }
+            }
+        }
+        
+        Action::None
+    }
+    
+    pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        let pane_selection_dock = self.build_pane_selection_dock();
+        let left_panel = match self.left_pane {
+            LeftPane::Home => self.build_left_panel(),
+            LeftPane::Settings => self.build_settings(),
+            LeftPane::Themes => self.custom_themes.view().map(Message::ForwardThemeMessages),
+            LeftPane::Types => type_editor::view(&self.type_system, &self.type_editor, &self.type_references).map(Message::TypeEditor),
+            LeftPane::Assets => self.build_asset_library(),
+        };
+
+        let right_panel = match self.right_pane {
+            RightPane::Preview => self.build_preview_panel(),
+            RightPane::Code => self.build_full_code_content(),
+        };
+        
+        let right_panel: Element<'a, Message> = match &self.hint {
+            Some(hint) => column![
+                self.build_hint_card(hint),
+                right_panel,
+            ].into(),
+            None => right_panel,
+        };
+
+        let right_panel: Element<'a, Message> = if self.notification_history.open {
+            column![
+                self.build_notification_history(),
+                right_panel,
+            ].into()
+        } else {
+            right_panel
+        };
+
+        let right_panel: Element<'a, Message> = match &self.notification {
+            Some(notification) => column![
+                self.build_notification_bar(notification),
+                right_panel,
+            ].into(),
+            None => right_panel,
+        };
+
+        let pinned_inspectors = row(
+            self.pinned_inspectors.iter().map(|&id| self.build_pinned_inspector(id))
+        );
+
+        let mut content = row![
+            pane_selection_dock, 
+            left_panel, 
+        ];
+        if let Some(comparison) = &self.comparison_snapshot && self.show_comparison {
+            content = content.push(self.build_comparison_panel(comparison));
+        }
+        if self.reference.open {
+            content = content.push(self.build_reference_panel());
+        }
+        let content: Element<'a, Message> = column![
+            self.build_tab_strip(),
+            content.push(pinned_inspectors).push(right_panel),
+        ].into();
+
+        if let Some(path) = &self.loading_project {
+            return stack![content, self.build_loading_overlay(path)].into();
+        }
+        match (&self.command_palette, &self.view_import) {
+            (Some(palette), _) => stack![content, self.build_command_palette(palette)].into(),
+            (None, Some(form)) => stack![content, self.build_view_import(form)].into(),
+            (None, None) => content,
+        }
+    }
+
+    /// Every palette action for the current state, each sending the same Message as its button
+    fn palette_actions(&self) -> Vec<PaletteAction> {
+        let mut actions = Vec::new();
+
+        if let Some(selected_id) = self.primary_selection() {
+            for widget_type in WidgetType::ALL {
+                if self.hierarchy.can_add_child(selected_id, widget_type) {
+                    actions.push(PaletteAction::new(
+                        format!("Add {} to selection", widget_type),
+                        Message::AddChild(selected_id, widget_type),
+                    ));
+                }
+            }
+            if self.hierarchy.check_duplicate(selected_id).is_ok() {
+                actions.push(PaletteAction::new("Duplicate selection", Message::DuplicateWidget(selected_id)));
+            }
+            if let Some(parent_id) = self.hierarchy.find_parent_id(selected_id) {
+                actions.push(PaletteAction::new(
+                    "Select parent",
+                    Message::SelectWidgets(HashSet::from([parent_id.0])),
+                ));
+            }
+        }
+        actions.push(PaletteAction::new(
+            "Select root",
+            Message::SelectWidgets(HashSet::from([self.hierarchy.root().id.0])),
+        ));
+
+        // Code generation
+        let code = code_generator::source_of(&self.full_code_tokens());
+        actions.push(PaletteAction::new("Copy code", Message::CopyCode(code)));
+        actions.push(PaletteAction::new("Open project…", Message::OpenProject));
+        actions.push(PaletteAction::new("Save project…", Message::SaveProject));
+        actions.push(PaletteAction::new("Export Cargo project…", Message::ExportProject));
+        actions.push(PaletteAction::new("Export main.rs…", Message::ExportMainRs));
+        actions.push(PaletteAction::new("Export layout report…", Message::ExportLayoutReport));
+        actions.push(PaletteAction::new("Copy layout report", Message::CopyLayoutReport));
+        actions.push(PaletteAction::new("Export printable…", Message::ExportPrintable));
+        actions.push(PaletteAction::new("Import design tokens…", Message::ImportDesignTokens));
+        actions.push(PaletteAction::new("Import view code…", Message::ViewImport(ViewImportMessage::Open)));
+        actions.push(PaletteAction::new("Take comparison snapshot", Message::TakeComparisonSnapshot));
+        actions.push(PaletteAction::new("Compare with project file…", Message::CompareWithProject));
+        if let Some(comparison) = &self.comparison_snapshot {
+            let label = if comparison.source.is_some() { "Show comparison" } else { "Compare with snapshot" };
+            actions.push(PaletteAction::new(label, Message::ShowComparison(true)));
+        }
+        if self.undo.can_undo() {
+            actions.push(PaletteAction::new(format!("Undo {}", self.undo.current().label), Message::Undo));
+        }
+        if let Some(next) = self.undo.entries().get(self.undo.position() + 1) {
+            actions.push(PaletteAction::new(format!("Redo {}", next.label), Message::Redo));
+        }
+        if self.last_bulk_rename.is_some() {
+            actions.push(PaletteAction::new("Undo bulk rename", Message::UndoBulkRename));
+        }
+        if self.last_export.is_some() {
+            actions.push(PaletteAction::new("Open last export", Message::OpenLastExport));
+        }
+        if !self.notification_history.is_empty() {
+            actions.push(PaletteAction::new("Copy notification history", Message::Notification(NotificationMessage::CopyAll)));
+        }
+
+        // Tabs
+        actions.push(PaletteAction::new("New tab", Message::Workspace(WorkspaceMessage::New)));
+        for (index, tab) in self.workspace.tabs().iter().enumerate() {
+            if index != self.workspace.active() {
+                actions.push(PaletteAction::new(
+                    format!("Switch to tab {}", tab.name),
+                    Message::Workspace(WorkspaceMessage::Select(index)),
+                ));
+            }
+        }
+        actions.push(PaletteAction::new(
+            "Rename tab",
+            Message::Workspace(WorkspaceMessage::StartRename(self.workspace.active())),
+        ));
+
+        // Panels
+        actions.push(PaletteAction::new("Show home", Message::OpenHome));
+        actions.push(PaletteAction::new("Show settings", Message::OpenWidgetVisualizerSettings));
+        actions.push(PaletteAction::new("Show theme editor", Message::OpenThemeEditor));
+        actions.push(PaletteAction::new("Show type editor", Message::OpenTypeEditor));
+        actions.push(PaletteAction::new("Show asset library", Message::Assets(AssetMessage::Open)));
+        actions.push(PaletteAction::new("Toggle notification history", Message::Notification(NotificationMessage::ToggleHistory)));
+        actions.push(PaletteAction::new("Add assets…", Message::Assets(AssetMessage::AddFiles)));
+        actions.push(PaletteAction::new("Show preview", Message::OpenPreview));
+        actions.push(PaletteAction::new("Show code view", Message::OpenCodeView));
+        actions.push(PaletteAction::new(
+            "Toggle selection outline",
+            Message::OutlineSelectedWidgetsToggled(!self.highlight_selected),
+        ));
+        actions.push(PaletteAction::new(
+            "Toggle padding overlay",
+            Message::PaddingOverlayToggled(!self.show_padding_overlay),
+        ));
+        actions.push(PaletteAction::new(
+            "Toggle fill portion badges",
+            Message::FillPortionOverlayToggled(!self.show_fill_portions),
+        ));
+        actions.push(PaletteAction::new(
+            "Toggle preview data",
+            Message::PreviewDataToggled(!self.show_preview_data),
+        ));
+
+        actions
+    }
+
+    fn build_command_palette<'a>(&'a self, palette: &'a CommandPalette) -> Element<'a, Message> {
+        let matches = palette.filtered();
+        let highlighted = palette.highlighted.min(matches.len().saturating_sub(1));
+
+        let entries = column(
+            matches.iter().enumerate().map(|(i, action)| {
+                button(text(&action.label).size(14))
+                    .width(Length::Fill)
+                    .style(if i == highlighted { button::primary } else { button::text })
+                    .on_press(Message::CommandPaletteRun(i))
+                    .into()
+            })
+        )
+        .spacing(2);
+
+        let results: Element<'a, Message> = if matches.is_empty() {
+            text("No matching commands").size(14).into()
+        } else {
+            container(scrollable(entries)).max_height(220.0).into()
+        };
+
+        let panel = container(
+            column![
+                text_input("Type a command…", &palette.query)
+                    .id(COMMAND_PALETTE_INPUT)
+                    .on_input(Message::CommandPaletteQueryChanged)
+                    .on_submit(Message::CommandPaletteRun(highlighted))
+                    .padding(8),
+                container(results).max_height(400),
+            ]
+            .spacing(8)
+        )
+        .width(Length::Fixed(500.0))
+        .padding(10)
+        .style(container::bordered_box);
+
+        // Clicking outside closes, clicks inside the panel stay with the panel
+        mouse_area(
+            container(iced::widget::opaque(panel))
+                .center_x(Length::Fill)
+                .height(Length::Fill)
+                .padding(Padding { top: 80.0, right: 0.0, bottom: 0.0, left: 0.0 })
+                .style(|_| container::Style {
+                    background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.3))),
+                    ..Default::default()
+                })
+        )
+        .on_press(Message::CloseCommandPalette)
+        .into()
+    }
+
+    /// Covers the builder while a project is read, so nothing is edited that the project replaces
+    fn build_loading_overlay<'a>(&self, path: &std::path::Path) -> Element<'a, Message> {
+        let name = path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned());
+        let panel = container(
+            column![
+                text("Loading project…").size(16),
+                text(name).size(12).style(text::secondary),
+            ]
+            .spacing(6)
+            .align_x(Alignment::Center)
+        )
+        .padding(20)
+        .style(container::bordered_box);
+
+        iced::widget::opaque(
+            container(panel)
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .style(|_| container::Style {
+                    background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.3))),
+                    ..Default::default()
+                })
+        )
+    }
+
+    /// Modal the view code is pasted into, listing what the last import left out
+    fn build_view_import<'a>(&'a self, form: &'a ViewImportForm) -> Element<'a, Message> {
+        let notes: Element<'a, Message> = if let Some(error) = &form.error {
+            text(error).size(13).style(text::danger).into()
+        } else if form.warnings.is_empty() {
+            text("Paste a view function, its body or a widget expression. Unsupported code becomes a placeholder Text.")
+                .size(13)
+                .into()
+        } else {
+            column![
+                text(format!("Imported into a new tab. {} things were left out:", form.warnings.len())).size(13),
+                container(scrollable(
+                    column(form.warnings.iter().map(|warning| text(warning).size(12).style(text::warning).into()))
+                        .spacing(2)
+                ))
+                .max_height(160.0),
+            ]
+            .spacing(4)
+            .into()
+        };
+
+        let panel = container(
+            column![
+                text("Import view code").size(16),
+                text_editor(&form.code)
+                    .placeholder("column![text(\"Hello\"), button(\"Press\")]")
+                    .on_action(|action| Message::ViewImport(ViewImportMessage::CodeEdited(action)))
+                    .font(Font::MONOSPACE)
+                    .size(13)
+                    .height(Length::Fixed(260.0)),
+                notes,
+                row![
+                    space::horizontal(),
+                    button(text("Close").size(14))
+                        .style(button::secondary)
+                        .on_press(Message::ViewImport(ViewImportMessage::Close)),
+                    button(text("Import").size(14))
+                        .style(button::primary)
+                        .on_press(Message::ViewImport(ViewImportMessage::Import)),
+                ]
+                .spacing(8),
+            ]
+            .spacing(8)
+        )
+        .width(Length::Fixed(640.0))
+        .padding(10)
+        .style(container::bordered_box);
+
+        container(iced::widget::opaque(panel))
+            .center_x(Length::Fill)
+            .height(Length::Fill)
+            .padding(Padding { top: 80.0, right: 0.0, bottom: 0.0, left: 0.0 })
+            .style(|_| container::Style {
+                background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.3))),
+                ..Default::default()
+            })
+            .into()
+    }
+
+    /// A properties panel locked to `widget_id`, independent of the current selection
+    fn build_pinned_inspector<'a>(&'a self, widget_id: WidgetId) -> Element<'a, Message> {
+        let close_button = button(text("Close").size(12))
+            .style(button::text)
+            .on_press(Message::UnpinInspector(widget_id));
+        let is_wide = self.wide_inspectors.contains(&widget_id);
+        let widen_button = button(text(if is_wide { "Narrow" } else { "Widen" }).size(12))
+            .style(button::text)
+            .on_press(Message::WidenInspector(widget_id));
+
+        let content: Element<'a, Message> = match self.hierarchy.get_widget_by_id(widget_id) {
+            // Measured so the groups go side by side once the panel has room for them
+            Some(widget) => responsive(move |size| {
+                let wide = self.editor_widths.is_wide(widget_id, size.width);
+                scrollable(self.build_editor_for_widget(widget, widget_id, wide))
+                    .height(Length::Fill)
+                    .into()
+            })
+            .into(),
+            None => container(text("Widget removed").size(16))
+                .padding(20)
+                .center_x(Length::Fill)
+                .into(),
+        };
+
+        container(
+            column![
+                row![
+                    text("Pinned").size(14),
+                    space::horizontal(),
+                    widen_button,
+                    close_button,
+                ]
+                .align_y(Alignment::Center)
+                .padding(Padding { top: 0.0, right: 5.0, bottom: 0.0, left: 10.0 }),
+                rule::horizontal(2),
+                content,
+            ]
+            .spacing(5)
+        )
+        .width(Length::Fixed(if is_wide { WIDE_INSPECTOR_WIDTH } else { PINNED_INSPECTOR_WIDTH }))
+        .height(Length::Fill)
+        .padding(Padding { top: 10.0, right: 5.0, bottom: 10.0, left: 5.0 })
+        .style(container::bordered_box)
+        .into()
+    }
+
+    /// Differences between the snapshot or project file and the current tree, indented like the tree
+    fn build_comparison_panel<'a>(&'a self, comparison: &'a Comparison) -> Element<'a, Message> {
+        let entries = project_diff::diff(&comparison.root, self.hierarchy.root());
+        let title = match &comparison.source {
+            Some(path) => format!("Changes since {}", path.file_name().unwrap_or(path.as_os_str()).to_string_lossy()),
+            None => "Changes since snapshot".to_string(),
+        };
+
+        let content: Element<'a, Message> = if entries.is_empty() {
+            container(text("No differences").size(16))
+                .padding(20)
+                .center_x(Length::Fill)
+                .into()
+        } else {
+            scrollable(column(entries.into_iter().map(|entry| self.build_diff_entry(entry))).spacing(4))
+                .height(Length::Fill)
+                .into()
+        };
+
+        container(
+            column![
+                row![
+                    text(title).size(14),
+                    space::horizontal(),
+                    button(text("Close").size(12))
+                        .style(button::text)
+                        .on_press(Message::ShowComparison(false)),
+                ]
+                .align_y(Alignment::Center)
+                .padding(Padding { top: 0.0, right: 5.0, bottom: 0.0, left: 10.0 }),
+                rule::horizontal(2),
+                content,
+            ]
+            .spacing(5)
+        )
+        .width(Length::Fixed(320.0))
+        .height(Length::Fill)
+        .padding(Padding { top: 10.0, right: 5.0, bottom: 10.0, left: 5.0 })
+        .style(container::bordered_box)
+        .into()
+    }
+
+    /// Reference image beside the properties. Clicking it fills the targeted color input
+    /// with the color under the cursor.
+    fn build_reference_panel<'a>(&'a self) -> Element<'a, Message> {
+        let reference = &self.reference;
+        let target: Element<'a, Message> = match reference.target.as_ref().and_then(|target| {
+            let widget = self.hierarchy.get_widget_by_id(target.widget_id)?;
+            Some((history::snapshot(&widget.properties, &target.change)?.key, widget))
+        }) {
+            Some((key, widget)) => text(format!("Click to fill {} on {}", key, widget.name)).size(12).into(),
+            None => text("Pick a color input to fill").size(12).style(text::secondary).into(),
+        };
+
+        let picture: Element<'a, Message> = match &reference.image {
+            Some(image) => {
+                let size = image.display_size(REFERENCE_PANEL_WIDTH);
+                mouse_area(
+                    iced::widget::image(image.handle.clone())
+                        .width(size.width)
+                        .height(size.height)
+                        .content_fit(ContentFit::Fill),
+                )
+                .on_move(|point| Message::ReferenceImage(ReferenceMessage::PointerMoved(point)))
+                .on_exit(Message::ReferenceImage(ReferenceMessage::PointerLeft))
+                .on_press(Message::ReferenceImage(ReferenceMessage::Sample))
+                .interaction(Interaction::Crosshair)
+                .into()
+            }
+            None if reference.loading => text("Loading…").size(12).into(),
+            None => text("Load a mockup or screenshot to sample colors from it").size(12).style(text::secondary).into(),
+        };
+
+        let readout: Element<'a, Message> = match reference.hovered_color() {
+            Some(color) => row![
+                container(Space::new().width(16).height(16)).style(move |_: &Theme| container::Style {
+                    background: Some(color.into()),
+                    border: Border { width: 1.0, color: Color::from_rgb(0.5, 0.5, 0.5), radius: 2.0.into() },
+                    ..Default::default()
+                }),
+                text(color_to_hex(color)).size(12).font(Font::MONOSPACE),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center)
+            .into(),
+            None => Space::new().height(16).into(),
+        };
+
+        let mut content = column![
+            row![
+                text("Reference image").size(14),
+                space::horizontal(),
+                button(text("Close").size(12))
+                    .style(button::text)
+                    .on_press(Message::ReferenceImage(ReferenceMessage::Close)),
+            ]
+            .align_y(Alignment::Center),
+            rule::horizontal(2),
+            target,
+            picture,
+            readout,
+            checkbox("Average 3x3 pixels", reference.average)
+                .on_toggle(|average| Message::ReferenceImage(ReferenceMessage::AverageToggled(average)))
+                .size(14)
+                .text_size(12),
+            button(text(if reference.image.is_some() { "Load another image…" } else { "Load image…" }).size(12))
+                .style(button::secondary)
+                .on_press_maybe((!reference.loading).then_some(Message::ReferenceImage(ReferenceMessage::ChooseImage))),
+        ]
+        .spacing(8);
+
+        if let Some(image) = &reference.image {
+            content = content.push(
+                text(format!("{} ({}x{})", image.path.display(), image.width, image.height))
+                    .size(11)
+                    .style(text::secondary),
+            );
+        }
+        if let Some(error) = &reference.error {
+            content = content.push(text(error).size(12).style(text::danger));
+        }
+
+        container(scrollable(content))
+            .width(Length::Fixed(REFERENCE_PANEL_WIDTH + 20.0))
+            .height(Length::Fill)
+            .padding(Padding { top: 10.0, right: 10.0, bottom: 10.0, left: 10.0 })
+            .style(container::bordered_box)
+            .into()
+    }
+
+    fn build_diff_entry<'a>(&self, entry: project_diff::DiffEntry) -> Element<'a, Message> {
+        use project_diff::DiffKind;
+
+        let label: Element<'a, Message> = match entry.current {
+            Some(id) => button(text(entry.label).size(13))
+                .style(button::text)
+                .padding(0)
+                .on_press(Message::SelectWidgets(HashSet::from([id.0])))
+                .into(),
+            None => text(entry.label).size(13).into(),
+        };
+
+        let mut details = column![].spacing(2);
+        let status = match &entry.kind {
+            DiffKind::Added => Some(text("added").size(12).style(text::success)),
+            DiffKind::Removed => Some(text("removed").size(12).style(text::danger)),
+            DiffKind::Matched { moved: Some(moved), .. } => Some(
+                text(format!("moved from {} to {}", moved.from, moved.to)).size(12).style(text::warning)
+            ),
+            DiffKind::Matched { moved: None, .. } => None,
+        };
+        if let Some(status) = status {
+            details = details.push(status);
+        }
+        if let DiffKind::Matched { properties, .. } = entry.kind {
+            for change in properties {
+                details = details.push(text(format!("{}: {} → {}", change.key, change.before, change.after)).size(12));
+            }
+        }
+
+        container(column![label, details].spacing(2))
+            .padding(Padding { top: 0.0, right: 0.0, bottom: 0.0, left: 10.0 + entry.depth as f32 * 12.0 })
+            .into()
+    }
+
+    fn build_notification_bar<'a>(&self, notification: &'a str) -> Element<'a, Message> {
+        // Only the notice of the export itself offers to open it
+        let reports_export = self.last_export.as_ref()
+            .is_some_and(|path| notification == saved_notice(path));
+        let open_button: Option<Element<Message>> = reports_export.then(|| {
+            button(text("Open in editor / file manager").size(12))
+                .style(button::text)
+                .on_press(Message::OpenLastExport)
+                .into()
+        });
+
+        container(
+            row![
+                text(notification).size(14),
+                space::horizontal(),
+                open_button,
+                button(text("Copy").size(12))
+                    .style(button::text)
+                    .on_press(Message::Notification(NotificationMessage::Copy(0))),
+                button(text(format!("History ({})", self.notification_history.len())).size(12))
+                    .style(button::text)
+                    .on_press(Message::Notification(NotificationMessage::ToggleHistory)),
+                button(text("Dismiss").size(12))
+                    .style(button::text)
+                    .on_press(Message::DismissNotification),
+            ]
+            .align_y(Alignment::Center)
+            .spacing(10)
+        )
+        .width(Length::Fill)
+        .padding(Padding { top: 5.0, right: 10.0, bottom: 5.0, left: 10.0 })
+        .style(container::rounded_box)
+        .into()
+    }
+
+    /// Past notifications, newest first. Each copy button sits over the end of its row while
+    /// the row is hovered, so narrow panels keep the whole width for the message.
+    fn build_notification_history<'a>(&'a self) -> Element<'a, Message> {
+        let history = &self.notification_history;
+        let entries = column(history.entries().enumerate().map(|(index, entry)| {
+            let severity: fn(&Theme) -> text::Style = match entry.severity {
+                notification_history::Severity::Error => text::danger,
+                notification_history::Severity::Info => text::secondary,
+            };
+            let line = row![
+                text(entry.timestamp()).size(11).font(Font::MONOSPACE).style(text::secondary),
+                text(entry.severity.to_string()).size(11).style(severity),
+                text(&entry.message).size(12).width(Length::Fill),
+            ]
+            .spacing(8)
+            .padding(Padding { top: 2.0, right: 5.0, bottom: 2.0, left: 5.0 });
+            let copy = container(
+                button(text("Copy").size(11))
+                    .style(button::secondary)
+                    .padding(Padding { top: 1.0, right: 6.0, bottom: 1.0, left: 6.0 })
+                    .on_press(Message::Notification(NotificationMessage::Copy(index)))
+            )
+            .align_right(Length::Fill)
+            .align_y(Vertical::Center);
+            iced::widget::hover(line, copy)
+        }))
+        .spacing(2);
+
+        container(
+            column![
+                row![
+                    text("Notifications").size(14),
+                    space::horizontal(),
+                    button(text("Copy all").size(12))
+                        .style(button::text)
+                        .on_press_maybe((!history.is_empty()).then_some(Message::Notification(NotificationMessage::CopyAll))),
+                    button(text("Clear").size(12))
+                        .style(button::text)
+                        .on_press_maybe((!history.is_empty()).then_some(Message::Notification(NotificationMessage::Clear))),
+                    button(text("Close").size(12))
+                        .style(button::text)
+                        .on_press(Message::Notification(NotificationMessage::ToggleHistory)),
+                ].spacing(10).align_y(Alignment::Center),
+                scrollable(entries).height(Length::Fixed(180.0)),
+            ]
+            .spacing(5)
+        )
+        .width(Length::Fill)
+        .padding(Padding { top: 5.0, right: 10.0, bottom: 5.0, left: 10.0 })
+        .style(container::rounded_box)
+        .into()
+    }
+
+    fn build_hint_card<'a>(&self, hint: &'a Hint) -> Element<'a, Message> {
+        let mut actions = row![
+            checkbox("Don't show again", self.hint_dont_show_again)
+                .on_toggle(Message::HintDontShowAgainToggled)
+                .size(14)
+                .text_size(12),
+            space::horizontal(),
+            button(text("Dismiss").size(12))
+                .style(button::text)
+                .on_press(Message::DismissHint),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        if let Some(fix) = hint.alternative {
+            actions = actions.push(
+                button(text(fix.label()).size(12))
+                    .style(button::secondary)
+                    .on_press(Message::ApplyHint(fix))
+            );
+        }
+        actions = actions.push(
+            button(text(hint.fix.label()).size(12))
+                .style(button::primary)
+                .on_press(Message::ApplyHint(hint.fix))
+        );
+
+        container(
+            column![
+                row![
+                    icon::info(),
+                    text(&hint.message).size(14),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+                actions,
+            ]
+            .spacing(5)
+        )
+        .width(Length::Fill)
+        .padding(Padding { top: 5.0, right: 10.0, bottom: 5.0, left: 10.0 })
+        .style(container::bordered_box)
+        .into()
+    }
+
+    /// The workspace's tabs above the panels, with the project's Open and Save at the end.
+    /// Press a tab and release it over another to move it there, double-click it to rename.
+    fn build_tab_strip<'a>(&'a self) -> Element<'a, Message> {
+        let workspace = &self.workspace;
+        let can_close = workspace.len() > 1;
+        let tabs = row(workspace.tabs().iter().enumerate().map(|(index, tab)| {
+            let label: Element<'a, Message> = match &workspace.rename {
+                Some((renaming, draft)) if *renaming == index => text_input("Tab name", draft)
+                    .on_input(|name| Message::Workspace(WorkspaceMessage::RenameEdited(name)))
+                    .on_submit(Message::Workspace(WorkspaceMessage::CommitRename))
+                    .size(12)
+                    .width(Length::Fixed(140.0))
+                    .into(),
+                _ => text(&tab.name).size(12).into(),
+            };
+            let close = button(text("×").size(12))
+                .style(button::text)
+                .padding(Padding { top: 0.0, right: 4.0, bottom: 0.0, left: 4.0 })
+                .on_press_maybe(can_close.then_some(Message::Workspace(WorkspaceMessage::Close(index))));
+            let is_active = index == workspace.active();
+            let is_drop_target = workspace.dragging.is_some_and(|(from, over)| from != over && over == index);
+            let tab = container(row![label, close].spacing(6).align_y(Alignment::Center))
+                .padding(Padding { top: 4.0, right: 6.0, bottom: 4.0, left: 10.0 })
+                .style(move |theme: &Theme| {
+                    let palette = theme.extended_palette();
+                    let background = if is_active {
+                        palette.background.base.color
+                    } else {
+                        palette.background.weak.color
+                    };
+                    container::Style {
+                        background: Some(Background::Color(background)),
+                        border: Border {
+                            color: if is_drop_target { palette.primary.strong.color } else { palette.background.strong.color },
+                            width: if is_drop_target { 2.0 } else { 1.0 },
+                            radius: 4.0.into(),
+                        },
+                        ..Default::default()
                     }
-                }
-            }
-            
-            Message::BatchPropertyChanged(change) => {
-                self.hierarchy.apply_property_to_all_selected(change, &self.type_system);
-            }
+                });
+            mouse_area(tab)
+                .on_press(Message::Workspace(WorkspaceMessage::Press(index)))
+                .on_enter(Message::Workspace(WorkspaceMessage::Hover(index)))
+                .on_release(Message::Workspace(WorkspaceMessage::Release))
+                .on_double_click(Message::Workspace(WorkspaceMessage::StartRename(index)))
+                .into()
+        }))
+        .spacing(4);
+
+        let mut strip = row![
+            tabs,
+            button(text("+").size(12))
+                .style(button::text)
+                .on_press(Message::Workspace(WorkspaceMessage::New)),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+        if workspace.rename.is_some() {
+            strip = strip.push(
+                button(text("Cancel").size(12))
+                    .style(button::text)
+                    .on_press(Message::Workspace(WorkspaceMessage::CancelRename)),
+            );
         }
-        
-        Action::None
+        strip = strip.push(space::horizontal());
+        if let Some(closing) = workspace.closing
+            && let Some(tab) = workspace.tabs().get(closing)
+        {
+            strip = strip.push(
+                row![
+                    text(format!("Close {}? Its layout and undo history go with it.", tab.name)).size(12),
+                    button(text("Close tab").size(12))
+                        .style(button::danger)
+                        .on_press(Message::Workspace(WorkspaceMessage::ConfirmClose)),
+                    button(text("Keep").size(12))
+                        .style(button::secondary)
+                        .on_press(Message::Workspace(WorkspaceMessage::CancelClose)),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        strip = strip
+            .push(button(text("Open…").size(12)).style(button::text).on_press(Message::OpenProject))
+            .push(button(text("Save…").size(12)).style(button::text).on_press(Message::SaveProject));
+
+        container(strip)
+            .width(Length::Fill)
+            .padding(Padding { top: 4.0, right: 10.0, bottom: 4.0, left: 10.0 })
+            .into()
     }
-    
-    pub fn view<'a>(&'a self) -> Element<'a, Message> {
-        let pane_selection_dock = self.build_pane_selection_dock();
-        let left_panel = match self.left_pane {
-            LeftPane::Home => self.build_left_panel(),
-            LeftPane::Settings => self.build_settings(),
-            LeftPane::Themes => self.custom_themes.view().map(Message::ForwardThemeMessages),
-            LeftPane::Types => type_editor::view(&self.type_system, &self.type_editor).map(Message::TypeEditor)
-        };
 
-        let right_panel = match self.right_pane {
-            RightPane::Preview => self.build_preview_panel(),
-            RightPane::Code => self.build_full_code_content(),
-        };
-        
-        row![
-            pane_selection_dock, 
-            left_panel, 
-            right_panel
-        ].into()
+    fn layout_report(&self) -> String {
+        layout_report::generate_layout_report(
+            &self.hierarchy,
+            &self.type_system,
+            &self.app_name,
+            &self.app_window_title,
+            &self.theme,
+        )
     }
     
     fn build_left_panel<'a>(&'a self) -> Element<'a, Message> {
@@ -1673,7 +5161,7 @@ impl WidgetVisualizer {
                 space::horizontal(),
                 text("Theme").size(18),
                 pick_list(
-                    Theme::ALL,
+                    Theme::ALL.iter().cloned().chain(self.imported_themes.iter().cloned()).collect::<Vec<_>>(),
                     Some(self.theme.clone()),
                     Message::ThemeChanged,
                 ),
@@ -1685,19 +5173,31 @@ impl WidgetVisualizer {
             
             // Widget hierarchy
             column![
-                text("Widget Hierarchy").size(18),
+                row![
+                    text("Widget Hierarchy").size(18),
+                    space::horizontal(),
+                    self.build_undo_button(KeyAction::Undo),
+                    self.build_undo_button(KeyAction::Redo),
+                    overlay_button(
+                        "History",
+                        format!("Undo History ({} of {} steps)", self.undo.entries().len(), undo::MAX_UNDO_STEPS),
+                        self.build_undo_history()
+                    )
+                    .overlay_width(420.0)
+                    .overlay_height(600.0)
+                    .style(button::secondary),
+                ].align_y(Alignment::Center),
+                self.build_tag_filter(),
                 scrollable(
                     self.widget_tree_view()
-                ).height(Length::Fill),
+                )
+                .on_scroll(Message::TreeScrolled)
+                .height(Length::Fill),
             ].spacing(5),
 
             multi_selection_ui,
 
-           if let Some(selected_widget) = self.hierarchy.get_single_selected() {
-                self.build_add_child_controls(selected_widget.id)
-           } else {
-                self.build_add_child_controls(self.hierarchy.root.id)
-           }
+           self.build_add_child_controls(self.primary_selection().unwrap_or(self.hierarchy.root.id))
            
         ]
         .width(Length::Fixed(400.0))
@@ -1801,13 +5301,66 @@ impl WidgetVisualizer {
         .into()
     }
 
+    /// Picks a tag to narrow the tree to, listing each with how many widgets carry it.
+    /// Hidden until the project has a tag.
+    fn build_tag_filter<'a>(&'a self) -> Element<'a, Message> {
+        let counts = self.hierarchy.stats().by_tag;
+        if counts.is_empty() && self.tag_filter.is_none() {
+            return column![].into();
+        }
+        let options: Vec<TagCount> = counts.into_iter().map(|(tag, count)| TagCount { tag, count }).collect();
+        let selected = self.tag_filter.as_ref().map(|(tag, _)| {
+            options.iter().find(|option| option.tag == *tag).cloned().unwrap_or(TagCount { tag: tag.clone(), count: 0 })
+        });
+
+        let mut content = row![
+            text("Tag").size(12),
+            pick_list(options, selected, |option| Message::TagFilterChanged(Some(option.tag)))
+                .placeholder("Show all widgets")
+                .text_size(12),
+        ]
+        .spacing(5)
+        .align_y(Alignment::Center);
+        if self.tag_filter.is_some() {
+            content = content.push(
+                button(text("Clear").size(12))
+                    .style(button::text)
+                    .on_press(Message::TagFilterChanged(None)),
+            );
+        }
+        content.into()
+    }
+
     fn widget_tree_view<'a>(&'a self) -> Element<'a, Message> {
-        self.build_tree()
+        // A filtered tree is short, and its rows no longer line up with the shown order
+        let window = if self.tag_filter.is_none() { self.tree_rows.window() } else { None };
+        match window {
+            Some(window) => column![
+                Space::new().height(window.top),
+                self.build_tree(Some(&window)),
+                Space::new().height(window.bottom),
+            ]
+            .into(),
+            None => self.build_tree(None),
+        }
+    }
+
+    /// Rows built into the tree: the shown children that pass the tag filter and, while the
+    /// tree is windowed, reach into the window
+    fn tree_children<'a>(&'a self, widget: &'a Widget, window: Option<&TreeWindow>) -> Vec<Branch<'a, Message, Theme, iced::Renderer>> {
+        if !self.tree_rows.shows_children(widget) {
+            return Vec::new();
+        }
+        widget.children.iter()
+            .filter(|child| self.shows_in_tree(child.id))
+            .filter(|child| window.is_none_or(|window| self.tree_rows.reaches(window, child.id)))
+            .map(|child| self.build_tree_item(child, window))
+            .collect()
     }
 
-    fn build_tree<'a>(&'a self) -> Element<'a, Message> {
+    fn build_tree<'a>(&'a self, window: Option<&TreeWindow>) -> Element<'a, Message> {
         let widget = self.hierarchy.root();
-        let overlay_content = self.build_editor_for_widget(widget, widget.id);
+        let overlay_content = self.build_editor_for_widget(widget, widget.id, false);
 
         // Determine if this widget can be swapped and the button label
         let swap_label: Option<iced::advanced::widget::Text<'_, Theme, iced::Renderer>> = match widget.widget_type {
@@ -1834,11 +5387,7 @@ impl WidgetVisualizer {
 
         //let place_holder = button("  ").style(button::text);
 
-        let mut children = Vec::new();
-
-        for child in &widget.children {
-            children.push(self.build_tree_item(child));
-        }
+        let children = self.tree_children(widget, window);
 
         let root = branch(
             row![
@@ -1859,7 +5408,7 @@ impl WidgetVisualizer {
                 disabled_delete_button
                 //place_holder
 
-            ].spacing(5)
+            ].spacing(5).height(TREE_ROW_HEIGHT).align_y(Alignment::Center)
         ).block_dragging()
         .with_children(children)
         .with_id(widget.id.0);
@@ -1868,7 +5417,7 @@ impl WidgetVisualizer {
             vec!(root)
         )
         .on_drop(Message::TreeMove)
-        .on_select(|selected_ids| Message::SelectWidgets(selected_ids));
+        .on_select(Message::TreeSelect);
 
         tree = tree.reset_order_state();
     
@@ -1876,17 +5425,50 @@ impl WidgetVisualizer {
 
     }
 
-    fn build_tree_item(&self, widget: &Widget) -> Branch<'_, Message, Theme, iced::Renderer> {     
+    /// Tree label plus the anchor badge of a floating Stack layer
+    fn tree_row_label(&self, widget: &Widget) -> String {
+        let mut label = match self.hierarchy.float_anchor(widget.id) {
+            Some(anchor) => format!("{} [float {}]", tree_label(widget), anchor.badge()),
+            None => tree_label(widget),
+        };
+        if widget.properties.section_anchor && self.hierarchy.find_parent_id(widget.id)
+            .and_then(|parent_id| self.hierarchy.get_widget_by_id(parent_id))
+            .is_some_and(|parent| parent.widget_type == WidgetType::Column)
+        {
+            label = format!("{} [section]", label);
+        }
+        if widget.widget_type == WidgetType::Row && widget.properties.stack_below_breakpoint {
+            label = format!("{} [stacks]", label);
+        }
+        if let Some(path) = asset_library::widget_path(widget)
+            && let Some(asset) = self.asset_library.linked(widget.properties.library_asset, path)
+            && self.asset_library.is_missing(asset.id)
+        {
+            label = format!("{} [missing file]", label);
+        }
+        if self.show_preview_data && let Some(preview) = preview_data_label(widget) {
+            label = format!("{} \"{}\"", label, preview);
+        }
+        label
+    }
 
+    fn build_tree_item<'a>(&'a self, widget: &'a Widget, window: Option<&TreeWindow>) -> Branch<'a, Message, Theme, iced::Renderer> {
         let is_selected = self.hierarchy.selected_ids().contains(&widget.id);
         let selection_count = self.hierarchy.selected_ids().len();   
+        let label_style: fn(&Theme) -> container::Style = if !is_selected {
+            container::transparent
+        } else if self.primary_selection() == Some(widget.id) {
+            styles::container::primary_selection
+        } else {
+            styles::container::secondary_selection
+        };
 
         let is_first_child_of_root = self.hierarchy.root().children.first()
         .map(|c| c.id == widget.id)
         .unwrap_or(false);
 
         // Create the overlay content for this specific widget
-        let overlay_content = self.build_editor_for_widget(widget, widget.id);
+        let overlay_content = self.build_editor_for_widget(widget, widget.id, false);
         
         // Determine if this widget can be swapped and the button label
         let swap_label: Option<iced::advanced::widget::Text<'_, Theme, iced::Renderer>> = match widget.widget_type {
@@ -1919,7 +5501,7 @@ impl WidgetVisualizer {
             Some(overlay_button(
                 "Edit",
                 format!("Editing {}", widget.name),
-                self.build_editor_for_widget(widget, widget.id)
+                self.build_editor_for_widget(widget, widget.id, false)
             )
             .overlay_width(500.0)
             .overlay_height(750.0)
@@ -1933,17 +5515,26 @@ impl WidgetVisualizer {
                 .style(button::text)).into()
         };
 
-        let mut children = Vec::new();
+        let children = self.tree_children(widget, window);
 
-        for child in &widget.children {
-            children.push(self.build_tree_item(child));
-        }
+        // Collapsing is tracked here rather than in the tree, so the shown rows are known
+        let expand_toggle: Option<Element<Message>> = (!widget.children.is_empty()).then(|| {
+            let expanded = self.tree_rows.shows_children(widget);
+            button(if expanded { icon::expanded() } else { icon::collapsed() })
+                .on_press(Message::TreeSetExpanded(widget.id, !expanded))
+                .style(button::text)
+                .into()
+        });
 
         let branch = match widget.widget_type {
-            WidgetType::Row | WidgetType::Column | WidgetType::Container | WidgetType::Scrollable | WidgetType::Tooltip | WidgetType::MouseArea => {
+            widget_type if can_have_children(&widget_type) => {
 
                 let content = row![
-                        container(text(format!("{}", widget.name))).padding(5),
+                        expand_toggle,
+
+                        container(text(self.tree_row_label(widget))).padding(5).style(label_style),
+
+                        tag_chips(&widget.properties.tags),
 
                         space::horizontal(),
 
@@ -1953,7 +5544,7 @@ impl WidgetVisualizer {
                         edit_button,
 
                         delete_button
-                ].spacing(5);
+                ].spacing(5).height(TREE_ROW_HEIGHT).align_y(Alignment::Center);
 
                 if !is_first_child_of_root {
                     branch(
@@ -1973,7 +5564,9 @@ impl WidgetVisualizer {
             }
             _ => {
                 let content = row![
-                        container(text(format!("{}", widget.name))).padding(5),
+                        container(text(self.tree_row_label(widget))).padding(5).style(label_style),
+
+                        tag_chips(&widget.properties.tags),
 
                         space::horizontal(),
 
@@ -1990,7 +5583,7 @@ impl WidgetVisualizer {
                         .style(button::primary),
 
                         delete_button
-                ].spacing(5);
+                ].spacing(5).height(TREE_ROW_HEIGHT).align_y(Alignment::Center);
                 
                 if !is_first_child_of_root {
                     branch(
@@ -2009,179 +5602,29 @@ impl WidgetVisualizer {
     }
     
     fn build_add_child_controls<'a>(&'a self, parent_id: WidgetId) -> Element<'a, Message> {
-        let parent = self.hierarchy.get_widget_by_id(parent_id);
-        if parent.is_none() {
+        if !self.hierarchy.widget_exists(parent_id) {
             return column![].into();
         }
-        let parent = parent.unwrap();
-        
-        let available_types = if parent_id == self.hierarchy.root().id {
-            if self.hierarchy.root().children.is_empty() {
-                vec![WidgetType::Column, WidgetType::Row]
-            } else {
-                vec![]
-            }
-        } else if parent.widget_type == WidgetType::Scrollable {
-            if parent.children.is_empty() {
-                vec![WidgetType::Container, WidgetType::Column, WidgetType::Row]
-            } else {
-                vec![]
-            }
-        } else if parent.widget_type == WidgetType::Container {
-            if parent.children.is_empty() {
-                vec![
-                    WidgetType::Container,
-                    WidgetType::Scrollable,
-                    WidgetType::Row,
-                    WidgetType::Column,
-                    WidgetType::Button,
-                    WidgetType::Text,
-                    WidgetType::TextInput,
-                    WidgetType::Checkbox,
-                    WidgetType::Radio,
-                    WidgetType::Slider,
-                    WidgetType::VerticalSlider,
-                    WidgetType::ProgressBar,
-                    WidgetType::Toggler,
-                    WidgetType::PickList,
-                    WidgetType::Space,
-                    WidgetType::Rule,
-                    WidgetType::Image,
-                    WidgetType::Svg,
-                    WidgetType::Tooltip,
-                    WidgetType::ComboBox,
-                    WidgetType::Markdown,
-                    WidgetType::MouseArea,
-                    WidgetType::Pin,
-                    WidgetType::QRCode,
-                ]
-            } else {
-                vec![]
-            }
-        } else if parent.widget_type == WidgetType::MouseArea {
-            if parent.children.len() < 1 {
-                vec![
-                    WidgetType::Container,
-                    WidgetType::Scrollable,
-                    WidgetType::Row,
-                    WidgetType::Column,
-                    WidgetType::Button,
-                    WidgetType::Text,
-                    WidgetType::TextInput,
-                    WidgetType::Checkbox,
-                    WidgetType::Radio,
-                    WidgetType::Slider,
-                    WidgetType::VerticalSlider,
-                    WidgetType::ProgressBar,
-                    WidgetType::Toggler,
-                    WidgetType::PickList,
-                    WidgetType::Space,
-                    WidgetType::Rule,
-                    WidgetType::Image,
-                    WidgetType::Svg,
-                    WidgetType::Tooltip,
-                    WidgetType::ComboBox,
-                    WidgetType::Markdown,
-                    WidgetType::Pin,
-                    WidgetType::QRCode,
-                ]
-            } else {
-                vec![]
-            }
-        } else if parent.widget_type == WidgetType::Tooltip {
-            if parent.children.len() < 2 {
-                vec![
-                    WidgetType::Container,
-                    WidgetType::Scrollable,
-                    WidgetType::Row,
-                    WidgetType::Column,
-                    WidgetType::Button,
-                    WidgetType::Text,
-                    WidgetType::TextInput,
-                    WidgetType::Checkbox,
-                    WidgetType::Radio,
-                    WidgetType::Slider,
-                    WidgetType::VerticalSlider,
-                    WidgetType::ProgressBar,
-                    WidgetType::Toggler,
-                    WidgetType::PickList,
-                    WidgetType::Image,
-                    WidgetType::Svg,
-                    WidgetType::ComboBox,
-                    WidgetType::Markdown,
-                    WidgetType::MouseArea,
-                    WidgetType::Pin,
-                    WidgetType::QRCode,
-                ]
-            } else {
-                vec![]
-            }
-        } else if parent.widget_type == WidgetType::Row || parent.widget_type == WidgetType::Column {
-            vec![
-                WidgetType::Container,
-                WidgetType::Scrollable,
-                WidgetType::Row,
-                WidgetType::Column,
-                WidgetType::Button,
-                WidgetType::Text,
-                WidgetType::TextInput,
-                WidgetType::Checkbox,
-                WidgetType::Radio,
-                WidgetType::Slider,
-                WidgetType::VerticalSlider,
-                WidgetType::ProgressBar,
-                WidgetType::Toggler,
-                WidgetType::PickList,
-                WidgetType::Space,
-                WidgetType::Rule,
-                WidgetType::Image,
-                WidgetType::Svg,
-                WidgetType::Tooltip,
-                WidgetType::ComboBox,
-                WidgetType::Markdown,
-                WidgetType::MouseArea,
-                WidgetType::Pin,
-                WidgetType::QRCode,
-            ]
-        } else {
-            vec![]
-        };
-        
+        let refusals: Vec<(WidgetType, String)> = WidgetType::ALL
+            .into_iter()
+            .filter_map(|widget_type| {
+                self.hierarchy.check_add_child(parent_id, widget_type).err().map(|e| (widget_type, e.to_string()))
+            })
+            .collect();
+
         // Use the add_widgets view and map its messages
-        crate::widget_helper::views::add_widgets::view(parent_id, &available_types)
+        crate::widget_helper::views::add_widgets::view(&refusals, &self.add_widget_filter)
             .map(move |msg| match msg {
                 crate::widget_helper::views::add_widgets::Message::SelectWidgetType(widget_type) => {
                     Message::AddChild(parent_id, widget_type)
                 }
+                crate::widget_helper::views::add_widgets::Message::FilterChanged(filter) => {
+                    Message::AddWidgetFilterChanged(filter)
+                }
             })
     }
 
     fn build_preview_panel<'a>(&'a self) -> Element<'a, Message> {
-        let widget_preview = self.build_widget_preview(self.hierarchy.root());
-
-        let preview_scoped = themer(
-            Some(self.theme.clone()),
-
-            container(widget_preview)
-                .width(Length::Fill)
-                .height(Length::Fill)
-                // Any style closures here will now see the scoped theme
-                .style(|theme: &Theme| container::Style {
-                    background: Some(Background::Color(theme.palette().background)),
-                    border: Border {
-                        color: theme.extended_palette().background.strong.color,
-                        width: 2.0,
-                        radius: 4.0.into(),
-                    },
-                    ..Default::default()
-                }),
-        )
-        // Optional: set default text color / background for this scope:
-        .text_color(|th| th.palette().text)
-        .background(|th| Background::Color(th.palette().background));
-
-        self.theme.extended_palette().secondary.base.text;
-        
         column![
             row![
                 tooltip(
@@ -2192,6 +5635,13 @@ impl WidgetVisualizer {
                         .center(),
                     tooltip::Position::Right
                 ),
+                space::horizontal(),
+                checkbox("Show Preview Data", self.show_preview_data)
+                    .on_toggle(Message::PreviewDataToggled),
+                checkbox("Show Padding", self.show_padding_overlay)
+                    .on_toggle(Message::PaddingOverlayToggled),
+                checkbox("Show Fill Portions", self.show_fill_portions)
+                    .on_toggle(Message::FillPortionOverlayToggled),
             ]
             .align_y(Alignment::Center)
             .padding(
@@ -2207,7 +5657,11 @@ impl WidgetVisualizer {
             rule::horizontal(5),
             space::horizontal().height(10),
 
-            container(preview_scoped)
+            container(responsive(move |pane| self.panic_guards.view(
+                GuardedPanel::Preview,
+                || format!("the preview of {} widgets", undo::subtree_size(self.hierarchy.root())),
+                || self.build_preview_viewport(pane),
+            )))
             .padding(5)
             .style(|theme: &Theme| container::Style {
                     background: Some(Background::Color(theme.extended_palette().background.weak.color)),
@@ -2218,7 +5672,99 @@ impl WidgetVisualizer {
         .padding(10)
         .into()
     }
-    
+
+    /// The themed preview on a zoomable canvas the pane scrolls over, with the zoom bar on top
+    fn build_preview_viewport<'a>(&'a self, pane: iced::Size) -> Element<'a, Message> {
+        let widget_preview = self.build_widget_preview(self.hierarchy.root());
+        let canvas = self.preview_viewport.canvas_size();
+
+        let preview_scoped = themer(
+            Some(self.theme.clone()),
+
+            container(widget_preview)
+                .width(canvas.width)
+                .height(canvas.height)
+                // Any style closures here will now see the scoped theme
+                .style(|theme: &Theme| container::Style {
+                    background: Some(Background::Color(theme.palette().background)),
+                    border: Border {
+                        color: theme.extended_palette().background.strong.color,
+                        width: 2.0,
+                        radius: 4.0.into(),
+                    },
+                    ..Default::default()
+                }),
+        )
+        // Optional: set default text color / background for this scope:
+        .text_color(|th| th.palette().text)
+        .background(|th| Background::Color(th.palette().background));
+
+        let viewport = scrollable(preview_scoped)
+            .id(PREVIEW_VIEWPORT)
+            .direction(scrollable::Direction::Both {
+                vertical: scrollable::Scrollbar::default(),
+                horizontal: scrollable::Scrollbar::default(),
+            })
+            .on_scroll(|viewport| Message::PreviewViewport(ViewportMessage::Scrolled(viewport.absolute_offset())))
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        // Middle-drag pans
+        let viewport = mouse_area(viewport)
+            .on_move(move |point| Message::PreviewViewport(ViewportMessage::PointerMoved(point, pane)))
+            .on_exit(Message::PreviewViewport(ViewportMessage::PointerLeft))
+            .on_middle_press(Message::PreviewViewport(ViewportMessage::PanStarted))
+            .on_middle_release(Message::PreviewViewport(ViewportMessage::PanEnded))
+            .interaction(if self.preview_viewport.is_panning() { Interaction::Grabbing } else { Interaction::Idle });
+
+        let zoom_button = |label: &'static str, message: ViewportMessage| {
+            button(text(label).size(12))
+                .style(button::secondary)
+                .on_press(Message::PreviewViewport(message))
+        };
+        let zoom_bar = container(
+            row![
+                zoom_button("-", ViewportMessage::ZoomOut),
+                text(format!("{:.0}%", self.preview_viewport.zoom() * 100.0)).size(12),
+                zoom_button("+", ViewportMessage::ZoomIn),
+                zoom_button("100%", ViewportMessage::Reset),
+                zoom_button("Fit", ViewportMessage::Fit(pane)),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center)
+        )
+        .padding(5)
+        .style(container::rounded_box);
+
+        // The same section nav the generated app gets, jumping to the same estimated offsets
+        let sections = sections::sections(self.hierarchy.root());
+        let section_nav: Option<Element<'a, Message>> = (!sections.is_empty()).then(|| {
+            let nav = container(
+                row(sections.into_iter().map(|section| {
+                    button(text(section.name).size(12))
+                        .style(button::secondary)
+                        .on_press(Message::PreviewViewport(ViewportMessage::JumpTo(section.offset)))
+                        .into()
+                }))
+                .spacing(5)
+                .wrap(),
+            )
+            .padding(5)
+            .style(container::rounded_box);
+            container(nav).padding(10).into()
+        });
+
+        stack![
+            viewport,
+            container(zoom_bar)
+                .align_right(Length::Fill)
+                .align_bottom(Length::Fill)
+                .padding(10),
+            section_nav,
+        ]
+        .into()
+    }
+
     fn build_widget_preview<'a>(&'a self, widget: &'a Widget) -> Element<'a, Message> {
         let is_selected = self.hierarchy.selected_ids().contains(&widget.id);
         let props = &widget.properties;
@@ -2277,10 +5823,17 @@ impl WidgetVisualizer {
 
                 // If user sets a style, use that style, otherwise use style from themer
                 container = container.style({
-                    let bg = props.background_color;
-                    let bw = props.border_width;
+                    // The generated code skips the conditional style under a gradient, so the preview does too
+                    let alternate = !props.gradient_enabled && condition_holds(&self.hierarchy, props);
+                    let bg = if alternate && props.condition_background_color.a > 0.0 {
+                        props.condition_background_color
+                    } else {
+                        props.background_color
+                    };
+                    let gradient = props.gradient_enabled.then(|| container_gradient(props));
+                    let bw = if alternate { props.border_width.max(1.0) } else { props.border_width };
                     let br = props.border_radius;
-                    let bc = props.border_color;
+                    let bc = if alternate { props.condition_border_color } else { props.border_color };
                     let has_shadow = props.has_shadow;
                     let sh_off = props.shadow_offset;
                     let sh_blur = props.shadow_blur;
@@ -2289,7 +5842,9 @@ impl WidgetVisualizer {
                     move |_| {
                         let mut st = container::Style::default();
 
-                        if bg.a > 0.0 {
+                        if let Some(gradient) = gradient {
+                            st.background = Some(Background::Gradient(gradient.into()));
+                        } else if bg.a > 0.0 {
                             st.background = Some(Background::Color(bg));
                         }
 
@@ -2315,12 +5870,27 @@ impl WidgetVisualizer {
             }
             
             WidgetType::Row => {
+                let stacked = props.stack_below_breakpoint && self.preview_is_narrow();
+                // Stacked, the children no longer share the row's width
+                let shares = if stacked { vec![None; widget.children.len()] } else { self.fill_shares(widget) };
                 let children: Vec<Element<'a, Message>> = widget.children
                     .iter()
-                    .map(|child| self.build_widget_preview(child))
+                    .zip(shares)
+                    .map(|(child, share)| with_fill_badge(self.build_widget_preview(child), share))
                     .collect();
                 
-                if props.is_wrapping_row {
+                if stacked {
+                    // The generated column branch keeps the row's sizing
+                    let mut stacked = column(children)
+                        .spacing(props.spacing)
+                        .padding(props.padding)
+                        .width(props.width)
+                        .height(props.height);
+                    if props.clip {
+                        stacked = stacked.clip(true);
+                    }
+                    stacked.into()
+                } else if props.is_wrapping_row {
                     // Wrapping rows MUST use row(children) pattern
                     let mut wrapping = row(children)
                         .spacing(props.spacing)
@@ -2355,8 +5925,8 @@ impl WidgetVisualizer {
                         content = content.push(text("Row Item 1"));
                         content = content.push(text("Row Item 2"));
                     } else {
-                        for child in &widget.children {
-                            content = content.push(self.build_widget_preview(child));
+                        for child in children {
+                            content = content.push(child);
                         }
                     }
                     
@@ -2384,8 +5954,8 @@ impl WidgetVisualizer {
                     content = content.push(text("Column Item 1"));
                     content = content.push(text("Column Item 2"));
                 } else {
-                    for child in &widget.children {
-                        content = content.push(self.build_widget_preview(child));
+                    for (child, share) in widget.children.iter().zip(self.fill_shares(widget)) {
+                        content = content.push(with_fill_badge(self.build_widget_preview(child), share));
                     }
                 }
                 
@@ -2482,7 +6052,7 @@ impl WidgetVisualizer {
                 // Create text_input with placeholder and value
                 let mut input = text_input(
                     &props.text_input_placeholder,
-                    &props.text_input_value
+                    props.shown_text_input_value(self.show_preview_data)
                 );
                 
                 // Always add on_input in preview (using Noop since it's just preview)
@@ -2526,35 +6096,68 @@ impl WidgetVisualizer {
                 if props.text_input_alignment != ContainerAlignX::Left {
                     input = input.align_x(props.text_input_alignment);
                 }
+
+                if condition_holds(&self.hierarchy, props) {
+                    let border_color = props.condition_border_color;
+                    let background = props.condition_background_color;
+                    input = input.style(move |theme: &Theme, status| {
+                        let style = text_input::default(theme, status);
+                        text_input::Style {
+                            background: if background.a > 0.0 { Background::Color(background) } else { style.background },
+                            border: Border { color: border_color, ..style.border },
+                            ..style
+                        }
+                    });
+                }
                 
                 input.into()
             }
 
             WidgetType::Checkbox => {
-                checkbox(&props.checkbox_label, props.checkbox_checked)
-                    .size(props.checkbox_size)
-                    .spacing(props.checkbox_spacing)
-                    .width(props.width)
-                    .on_toggle(|_| Message::CheckboxToggled(widget.id, !props.checkbox_checked))
-                    .into()
+                if props.label_position == LabelPosition::Left {
+                    let control = checkbox("", props.checkbox_checked)
+                        .size(props.checkbox_size)
+                        .spacing(0)
+                        .on_toggle(|_| Message::CheckboxToggled(widget.id, !props.checkbox_checked));
+                    left_labelled(&props.checkbox_label, props.checkbox_spacing, props.width, control.into())
+                } else {
+                    checkbox(&props.checkbox_label, props.checkbox_checked)
+                        .size(props.checkbox_size)
+                        .spacing(props.checkbox_spacing)
+                        .width(props.width)
+                        .on_toggle(|_| Message::CheckboxToggled(widget.id, !props.checkbox_checked))
+                        .into()
+                }
             }
 
             WidgetType::Radio => {
                 if !props.radio_options.is_empty() {
-                    column(
+                    let label: Option<Element<Message>> = (!props.radio_label.is_empty())
+                        .then(|| text(&props.radio_label).into());
+
+                    let options = label.into_iter().chain(
                         props.radio_options.iter().enumerate().map(|(i, option)| {
-                            radio(
-                                option,
+                            let left = props.label_position == LabelPosition::Left;
+                            let control = radio(
+                                if left { "" } else { option.as_str() },
                                 i,
                                 Some(props.radio_selected_index),
                                 move |selected_index| Message::RadioSelected(widget.id, selected_index)
                             )
-                            .size(props.radio_size)
-                            .spacing(props.radio_spacing)
-                            .into()
-                        }).collect::<Vec<Element<Message>>>()
-                    )
-                    .into()
+                            .size(props.radio_size);
+
+                            if left {
+                                left_labelled(option, props.radio_spacing, props.width, control.spacing(0).into())
+                            } else {
+                                control.spacing(props.radio_spacing).into()
+                            }
+                        })
+                    ).collect::<Vec<Element<Message>>>();
+
+                    match props.orientation {
+                        Orientation::Vertical => column(options).spacing(props.spacing).into(),
+                        Orientation::Horizontal => row(options).spacing(props.spacing).into(),
+                    }
                 } else {
                     text("No radio options").into()
                 }
@@ -2579,7 +6182,7 @@ impl WidgetVisualizer {
             }
 
             WidgetType::ProgressBar => {
-                let mut content = progress_bar(props.progress_min..=props.progress_max, props.progress_value)
+                let mut content = progress_bar(props.progress_min..=props.progress_max, props.shown_progress_value(self.show_preview_data))
                     .length(props.progress_length)
                     .girth(props.progress_girth);
 
@@ -2591,19 +6194,25 @@ impl WidgetVisualizer {
             }
 
             WidgetType::Toggler => {
-                toggler(props.toggler_active)
+                let control = toggler(props.toggler_active)
                     .on_toggle(|_| Message::TogglerToggled(widget.id, !props.toggler_active))
-                    .label(&props.toggler_label)
-                    .size(props.toggler_size)
-                    .spacing(props.toggler_spacing)
-                    .width(props.width)
-                    .into()
+                    .size(props.toggler_size);
+
+                if props.label_position == LabelPosition::Left {
+                    left_labelled(&props.toggler_label, props.toggler_spacing, props.width, control.into())
+                } else {
+                    control
+                        .label(&props.toggler_label)
+                        .spacing(props.toggler_spacing)
+                        .width(props.width)
+                        .into()
+                }
             }
 
             WidgetType::PickList => {
                 pick_list(
                     props.picklist_options.clone(),
-                    props.picklist_selected.clone(),
+                    props.shown_picklist_selected(self.show_preview_data),
                     |selected| Message::PickListSelected(widget.id, selected)
                 )
                 .placeholder(&props.picklist_placeholder)
@@ -2653,9 +6262,20 @@ impl WidgetVisualizer {
             }
 
             WidgetType::Rule => {
-                match props.orientation {
+                let rule: Element<Message> = match props.orientation {
                     Orientation::Horizontal => rule::horizontal(props.rule_thickness).into(),
                     Orientation::Vertical => rule::vertical(props.rule_thickness).into(),
+                };
+
+                // Same container the generated code wraps a sized rule in
+                if code_generator::rule_is_sized(props) {
+                    let explicit = |length: Length| if length == Length::Fill { Length::Shrink } else { length };
+                    container(rule)
+                        .width(explicit(props.width))
+                        .height(explicit(props.height))
+                        .into()
+                } else {
+                    rule
                 }
             }
 
@@ -2690,9 +6310,18 @@ impl WidgetVisualizer {
                         })
                         .into()
                 } else {
+                    let tint = props.svg_tint;
+                    let tint_color = props.svg_tint_color;
                     svg(svg::Handle::from_path(&props.svg_path))
                         .content_fit(props.svg_fit.into())
                         .width(props.width).height(props.height)
+                        .style(move |theme: &Theme, _status| svg::Style {
+                            color: match tint {
+                                SvgTint::None => None,
+                                SvgTint::ThemeText => Some(theme.palette().text),
+                                SvgTint::Custom => Some(tint_color),
+                            },
+                        })
                         .into()
                 };
                 el
@@ -2745,13 +6374,34 @@ impl WidgetVisualizer {
                 combo_box(
                     &props.combobox_state, 
                     &props.combobox_placeholder,
-                    props.combobox_selected.as_ref(), 
+                    props.shown_combobox_selected(self.show_preview_data),
                     on_selected
                 )
                 .on_close(Message::ComboBoxOnClose(id))
                 .on_input(move |search| Message::ComboBoxOnInput(id, search))
                 .on_open(Message::ComboBoxOnOpen(id))
                 .on_option_hovered(move |hovered| Message::ComboBoxOnOptionHovered(id, hovered))
+                .size(props.combobox_size)
+                .width(props.width)
+                .input_style({
+                    let background = props.combobox_input_background;
+                    let value = props.combobox_input_text_color;
+                    move |theme: &Theme, status| {
+                        let mut style = text_input::default(theme, status);
+                        if let Some(bg) = background { style.background = Background::Color(bg); }
+                        if let Some(c) = value { style.value = c; }
+                        style
+                    }
+                })
+                .menu_style({
+                    let background = props.combobox_menu_background;
+                    move |theme: &Theme| {
+                        let mut style = iced::overlay::menu::default(theme);
+                        if let Some(bg) = background { style.background = Background::Color(bg); }
+                        style
+                    }
+                })
+                .menu_height(props.combobox_menu_height.map_or(Length::Shrink, Length::Fixed))
                 .into()
             }
             
@@ -2904,13 +6554,28 @@ impl WidgetVisualizer {
                     );
                 } else {
                     for child in &widget.children {
-                        layers.push(self.build_widget_preview(child));
+                        let layer = self.build_widget_preview(child);
+                        let layer = match child.properties.float_anchor {
+                            Some(anchor) => {
+                                let (align_x, align_y) = anchor.alignment();
+                                container(layer)
+                                    .width(Length::Fill)
+                                    .height(Length::Fill)
+                                    .align_x(align_x)
+                                    .align_y(align_y)
+                                    .padding(anchor.padding(child.properties.float_offset_x, child.properties.float_offset_y))
+                                    .into()
+                            }
+                            None => layer,
+                        };
+                        layers.push(layer);
                     }
                 }
                 
                 stack(layers)
                     .width(props.width)
                     .height(props.height)
+                    .clip(props.clip)
                     .into()
             }
             
@@ -2934,57 +6599,374 @@ impl WidgetVisualizer {
                 }
             }
 
-            _ => {
-                text(format!("{:?} preview", widget.widget_type)).into()
+            _ => {
+                text(format!("{:?} preview", widget.widget_type)).into()
+            }
+        };
+
+        // Dev-tools style box model: padding in green, content box in blue
+        let content = if is_selected && self.show_padding_overlay && props.padding != Padding::ZERO {
+            let overlay = container(
+                container(Space::new().width(Length::Fill).height(Length::Fill))
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .style(|_| container::Style {
+                        background: Some(Background::Color(Color::from_rgba(0.2, 0.5, 1.0, 0.25))),
+                        ..Default::default()
+                    })
+            )
+            .padding(props.padding)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(|_| container::Style {
+                background: Some(Background::Color(Color::from_rgba(0.3, 0.8, 0.3, 0.35))),
+                ..Default::default()
+            });
+
+            overlay_on(content, overlay)
+        } else {
+            content
+        };
+
+        if is_selected && self.highlight_selected {
+            content.explain(self.theme.extended_palette().primary.strong.color)
+                .into()
+        } else {
+            content
+        }
+    }
+    
+    /// Properties panel for `widget`, with its groups in two columns when `wide`
+    /// Properties panel of one widget. A panic building it shows the recovery view instead.
+    fn build_editor_for_widget<'a>(&'a self, widget: &Widget, widget_id: WidgetId, wide: bool) -> Element<'a, Message> {
+        self.panic_guards.view(
+            GuardedPanel::Properties,
+            || format!("the properties of {} ({:?}, id {})", widget.name, widget.widget_type, widget_id.0),
+            || self.build_unguarded_editor(widget, widget_id, wide),
+        )
+    }
+
+    fn build_unguarded_editor<'a>(&'a self, widget: &Widget, widget_id: WidgetId, wide: bool) -> Element<'a, Message> {
+        let controls_view = self.build_type_controls(widget, wide);
+
+        let is_pinned = self.pinned_inspectors.contains(&widget_id);
+        let pin_button = button(text(if is_pinned { "Pinned" } else { "Pin" }).size(12))
+            .style(button::secondary)
+            .on_press_maybe(
+                if !is_pinned && self.pinned_inspectors.len() < MAX_PINNED_INSPECTORS {
+                    Some(Message::PinInspector(widget_id))
+                } else {
+                    None
+                }
+            );
+
+        column![
+            row![
+                text(format!("Editing: {}", widget.name)).size(20),
+                space::horizontal(),
+                pin_button,
+            ].align_y(Alignment::Center),
+            rule::horizontal(5),
+            self.build_history_section(widget_id),
+            self.build_refactor_section(widget_id),
+            tag_controls(widget, &self.tag_draft, &self.hierarchy.stats().by_tag),
+            named_style_controls(
+                widget,
+                &self.named_styles,
+                self.style_promote.as_ref().filter(|(id, _)| *id == widget_id).map(|(_, name)| name.as_str()),
+            ),
+            self.build_stack_layer_section(widget_id),
+            self.build_column_child_section(widget_id),
+            self.build_breakpoint_section(widget_id),
+            scrollable(column![controls_view, self.build_selection_code()]),
+        ]
+        .spacing(10)
+        .padding(20)
+        .into()
+    }
+
+    /// The selection's code from the last background run, so it follows selection changes and
+    /// edits without generating on every frame of a drag. Several widgets show as one `let`
+    /// binding each, named as in the app code, ready to move into helper functions.
+    fn build_selection_code<'a>(&'a self) -> Element<'a, Message> {
+        let Some(code) = self.code_generation.latest().filter(|code| !code.selection.is_empty()) else {
+            return column![].into();
+        };
+        let title = if self.hierarchy.selected_ids().len() > 1 { "Selection as Bindings" } else { "Generated Code" };
+
+        column![
+            space::vertical().height(20),
+            rule::horizontal(2),
+            space::vertical().height(10),
+            row![
+                text(title).size(16),
+                // The previous selection's code stays up while the next run is owed
+                text(if self.code_generation.is_generating() { "generating…" } else { "" })
+                    .size(12)
+                    .style(text::secondary),
+                space::horizontal(),
+                button(text("Copy").size(12))
+                    .style(button::text)
+                    .on_press(Message::CopyCode(code_generator::source_of(&code.selection))),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            code_generator::build_code_view_with_height(&code.selection, 400.0, self.theme.clone()),
+        ]
+        .spacing(5)
+        .padding(10)
+        .into()
+    }
+
+    /// The controls specific to `widget`'s type
+    fn build_type_controls<'a>(&'a self, widget: &Widget, wide: bool) -> Element<'a, Message> {
+        let widget_id = widget.id;
+        match widget.widget_type {
+            WidgetType::Container       => container_controls(
+                &self.hierarchy,
+                widget_id,
+                self.theme.clone(),
+                self.dismissed_contrast.get(&widget_id) == Some(&widget.properties.background_color),
+                wide,
+            ),
+            WidgetType::Scrollable      => scrollable_controls(&self.hierarchy, widget_id),
+            WidgetType::Row             => row_controls(&self.hierarchy, widget_id),
+            WidgetType::Column          => column_controls(&self.hierarchy, widget_id),
+            WidgetType::Button          => button_controls(&self.hierarchy, widget_id, self.theme.clone(), wide),
+            WidgetType::Text            => text_controls(&self.hierarchy, widget_id),
+            WidgetType::TextInput       => text_input_controls(&self.hierarchy, widget_id),
+            WidgetType::Checkbox        => checkbox_controls(&self.hierarchy, widget_id),
+            WidgetType::Radio           => radio_controls(&self.hierarchy, widget_id, self.option_import.as_ref()),
+            WidgetType::Toggler         => toggler_controls(&self.hierarchy, widget_id),
+            WidgetType::PickList        => picklist_controls(&self.hierarchy, widget_id, self.option_import.as_ref()),
+            WidgetType::Slider          => slider_controls(&self.hierarchy, widget_id),
+            WidgetType::VerticalSlider  => vertical_slider_controls(&self.hierarchy, widget_id),
+            WidgetType::Rule            => rule_controls(&self.hierarchy, widget_id),
+            WidgetType::Space           => space_controls(&self.hierarchy, widget_id),
+            WidgetType::ProgressBar     => progress_controls(&self.hierarchy, widget_id),
+            WidgetType::Image           => image_controls(&self.hierarchy, widget_id, &self.asset_library),
+            WidgetType::Svg             => svg_controls(&self.hierarchy, widget_id, &self.asset_library),
+            WidgetType::Tooltip         => tooltip_controls(&self.hierarchy, widget_id),
+            WidgetType::ComboBox        => combobox_controls(&self.hierarchy, widget_id, &self.type_system, self.enum_form.as_ref(), self.option_import.as_ref()),
+            WidgetType::Markdown        => markdown_controls(&self.hierarchy, widget_id, self.theme.clone(), self.markdown_preview_open),
+            WidgetType::MouseArea       => mousearea_controls(&self.hierarchy, widget_id),
+            WidgetType::QRCode          => qrcode_controls(&self.hierarchy, widget_id),
+            WidgetType::Stack           => stack_controls(&self.hierarchy, widget_id),
+            WidgetType::Themer          => themer_controls(&self.hierarchy, widget_id, self.theme.clone()),
+            WidgetType::Pin             => pin_controls(&self.hierarchy, widget_id),
+            _ => column![text("Editor not implemented for this widget type")].into(),
+        }
+    }
+
+    /// "Wrap in…" buttons for the wrappers that fit where the widget stands, "Unwrap" for a single-child
+    /// layout, and "Duplicate", disabled with the reason when the parent has no room for a copy
+    fn build_refactor_section<'a>(&'a self, widget_id: WidgetId) -> Element<'a, Message> {
+        let choices = self.hierarchy.wrap_choices(widget_id);
+        let can_unwrap = self.hierarchy.get_widget_by_id(widget_id)
+            .is_some_and(|widget| can_have_children(&widget.widget_type) && widget.children.len() == 1);
+        let duplicate_refusal = self.hierarchy.check_duplicate(widget_id).err();
+        if choices.is_empty() && !can_unwrap && matches!(duplicate_refusal, Some(HierarchyError::RootLocked)) {
+            return column![].into();
+        }
+
+        let mut buttons = row![]
+            .spacing(5)
+            .align_y(Alignment::Center);
+        if !choices.is_empty() {
+            buttons = buttons.push(text("Wrap in:").size(12));
+        }
+        for wrapper_type in choices {
+            buttons = buttons.push(
+                button(text(format!("{:?}", wrapper_type)).size(12))
+                    .style(button::secondary)
+                    .on_press(Message::WrapWidget(widget_id, wrapper_type))
+            );
+        }
+        buttons = buttons.push(space::horizontal());
+        if can_unwrap {
+            buttons = buttons.push(
+                button(text("Unwrap").size(12))
+                    .style(button::secondary)
+                    .on_press(Message::UnwrapWidget(widget_id))
+            );
+        }
+        match duplicate_refusal {
+            None => {
+                buttons = buttons.push(
+                    button(text("Duplicate").size(12))
+                        .style(button::secondary)
+                        .on_press(Message::DuplicateWidget(widget_id))
+                );
+            }
+            Some(HierarchyError::RootLocked) => {}
+            Some(refusal) => {
+                buttons = buttons.push(tooltip(
+                    button(text("Duplicate").size(12)).style(button::secondary),
+                    container(text(refusal.to_string()).size(12)).padding(5).style(container::rounded_box),
+                    tooltip::Position::Top,
+                ));
+            }
+        }
+        buttons.into()
+    }
+
+    /// Float settings for a direct child of a Stack, empty for every other widget
+    fn build_stack_layer_section<'a>(&'a self, widget_id: WidgetId) -> Element<'a, Message> {
+        let in_stack = self.hierarchy.find_parent_id(widget_id)
+            .and_then(|parent_id| self.hierarchy.get_widget_by_id(parent_id))
+            .is_some_and(|parent| parent.widget_type == WidgetType::Stack);
+        match self.hierarchy.get_widget_by_id(widget_id) {
+            Some(widget) if in_stack => stack_layer_controls(&self.hierarchy, widget),
+            _ => column![].into(),
+        }
+    }
+
+    /// Section marker for a direct child of a Column, empty for every other widget
+    fn build_column_child_section<'a>(&'a self, widget_id: WidgetId) -> Element<'a, Message> {
+        let in_column = self.hierarchy.find_parent_id(widget_id)
+            .and_then(|parent_id| self.hierarchy.get_widget_by_id(parent_id))
+            .is_some_and(|parent| parent.widget_type == WidgetType::Column);
+        match self.hierarchy.get_widget_by_id(widget_id) {
+            Some(widget) if in_column => section_anchor_controls(widget),
+            _ => column![].into(),
+        }
+    }
+
+    /// Breakpoint width and preview slider on the root, the stacking flag on a Row
+    fn build_breakpoint_section<'a>(&'a self, widget_id: WidgetId) -> Element<'a, Message> {
+        let root = self.hierarchy.root();
+        match self.hierarchy.get_widget_by_id(widget_id) {
+            Some(widget) if widget.id == root.id => breakpoint_controls(widget, self.breakpoint_preview_width),
+            Some(widget) if widget.widget_type == WidgetType::Row => {
+                stack_below_breakpoint_controls(widget, root.properties.breakpoint_enabled)
             }
-        };
+            _ => column![].into(),
+        }
+    }
 
-        if is_selected && self.highlight_selected {
-            content.explain(self.theme.extended_palette().primary.strong.color)
-                .into()
+    /// Whether flagged rows show as columns at the preview width
+    fn preview_is_narrow(&self) -> bool {
+        let root = &self.hierarchy.root().properties;
+        root.breakpoint_enabled && self.breakpoint_preview_width < root.breakpoint_width
+    }
+
+    /// Each child's fill portion badge, none while the badges are off
+    fn fill_shares(&self, parent: &Widget) -> Vec<Option<fill_portions::Share>> {
+        if self.show_fill_portions {
+            fill_portions::shares(parent)
         } else {
-            content
+            vec![None; parent.children.len()]
         }
     }
-    
-    fn build_editor_for_widget<'a>(&'a self, widget: &Widget, widget_id: WidgetId) -> Element<'a, Message> {
-        let controls_view: Element<Message> = match widget.widget_type {
-            WidgetType::Container       => container_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Scrollable      => scrollable_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Row             => row_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Column          => column_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Button          => button_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Text            => text_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::TextInput       => text_input_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Checkbox        => checkbox_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Radio           => radio_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Toggler         => toggler_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::PickList        => picklist_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Slider          => slider_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::VerticalSlider  => vertical_slider_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Rule            => rule_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Space           => space_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::ProgressBar     => progress_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Image           => image_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Svg             => svg_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Tooltip         => tooltip_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::ComboBox        => combobox_controls(&self.hierarchy, widget_id, self.theme.clone(), &self.type_system),
-            WidgetType::Markdown        => markdown_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::MouseArea       => mousearea_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::QRCode          => qrcode_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Stack           => stack_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Themer          => themer_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            WidgetType::Pin             => pin_controls(&self.hierarchy, widget_id, self.theme.clone(), Some(&self.type_system)),
-            _ => column![text("Editor not implemented for this widget type")].into(),
+
+    /// Collapsible timeline of recent property changes, newest first
+    fn build_history_section<'a>(&'a self, widget_id: WidgetId) -> Element<'a, Message> {
+        let history = self.hierarchy.history();
+        let count = history.len(widget_id);
+
+        let header = button(
+            row![
+                if self.show_history { icon::expanded() } else { icon::collapsed() },
+                text(format!("History ({})", count)).size(14),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center)
+        )
+        .style(button::text)
+        .on_press(Message::HistoryToggled);
+
+        if !self.show_history {
+            return header.into();
+        }
+
+        let now = std::time::Instant::now();
+        let entries = column(history.recent(widget_id, HISTORY_SHOWN).map(|entry| {
+            row![
+                column![
+                    text(entry.key).size(12),
+                    text(format!("{} → {}", entry.old_value, entry.new_value))
+                        .size(11)
+                        .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                ]
+                .spacing(2)
+                .width(Length::Fill),
+                text(history::elapsed_label(entry.at, now)).size(11),
+                button(text("Revert").size(11))
+                    .style(button::secondary)
+                    .on_press(Message::RevertProperty(widget_id, entry.seq)),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into()
+        }))
+        .spacing(6);
+
+        let body: Element<'a, Message> = if count == 0 {
+            text("No changes yet").size(12).into()
+        } else {
+            container(scrollable(entries)).max_height(220.0).into()
+        };
+
+        column![header, body].spacing(5).into()
+    }
+
+    /// Undo or Redo, disabled with nothing to step to. The tooltip names the step and the shortcut.
+    fn build_undo_button<'a>(&'a self, action: KeyAction) -> Element<'a, Message> {
+        let (message, step) = match action {
+            KeyAction::Redo => (Message::Redo, self.undo.entries().get(self.undo.position() + 1)),
+            _ => (Message::Undo, self.undo.can_undo().then(|| self.undo.current())),
         };
+        let tip = match step {
+            Some(entry) => format!("{} {} ({})", action.description(), entry.label, self.keybindings.combo(action)),
+            None => format!("Nothing to {}", action.description().to_lowercase()),
+        };
+        tooltip(
+            button(text(action.description()).size(12))
+                .style(button::secondary)
+                .on_press_maybe(step.is_some().then_some(message)),
+            text(tip).size(12),
+            tooltip::Position::Bottom,
+        )
+        .into()
+    }
+
+    /// Undo steps, newest first. Clicking one jumps to it; hovering shows what the jump would change.
+    fn build_undo_history<'a>(&'a self) -> Element<'a, Message> {
+        let now = std::time::Instant::now();
+        let position = self.undo.position();
+
+        let entries = column(self.undo.entries().iter().enumerate().rev().map(|(index, entry)| {
+            let mut details = column![
+                text(&entry.label).size(13),
+                text(history::elapsed_label(entry.at, now))
+                    .size(11)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            ]
+            .spacing(2);
+            if self.hovered_undo == Some(index) && index != position {
+                details = details.push(
+                    text(undo::structure_diff(self.hierarchy.root(), &entry.root)).size(11)
+                );
+            }
+
+            mouse_area(
+                button(details)
+                    .width(Length::Fill)
+                    .style(if index == position { styles::button::selected_text } else { button::text })
+                    .on_press(Message::JumpToUndoStep(index))
+            )
+            .on_enter(Message::UndoStepHovered(Some(index)))
+            .on_exit(Message::UndoStepHovered(None))
+            .into()
+        }))
+        .spacing(4);
 
         column![
-            text(format!("Editing: {}", widget.name)).size(20),
-            rule::horizontal(5),
-            controls_view,
+            text("Click a step to jump back or forward to it").size(12),
+            scrollable(entries).height(Length::Fill),
         ]
         .spacing(10)
-        .padding(20)
+        .padding(10)
         .into()
     }
 
@@ -3082,6 +7064,26 @@ impl WidgetVisualizer {
             .overlay_width(500.0)
             .overlay_height(750.0)
             .style(button::primary),
+
+            overlay_button(
+                "Rename…",
+                format!("Renaming {} widgets", selected_count),
+                self.build_bulk_rename()
+            )
+            .overlay_width(500.0)
+            .overlay_height(600.0)
+            .style(button::secondary),
+
+            overlay_button(
+                "Code…",
+                format!("Code of {} widgets", selected_count),
+                scrollable(self.build_selection_code())
+            )
+            .overlay_width(600.0)
+            .overlay_height(600.0)
+            .style(button::secondary),
+
+            bulk_tag_controls(&self.hierarchy.get_selected_widgets(), &self.tag_draft, &self.hierarchy.stats().by_tag),
             
             // Show validation error if wrapping isn't possible
             if !can_wrap {
@@ -3096,80 +7098,987 @@ impl WidgetVisualizer {
             }
         ]
         .spacing(10)
-        .padding(10)
+        .padding(10)
+        .into()
+    }
+    
+    /// Builds the batch property editor overlay
+    fn build_batch_editor<'a>(&'a self) -> Element<'a, Message> {
+        batch_editor_controls(&self.hierarchy, self.theme.clone(), self.build_shared_type_controls())
+    }
+
+    /// Type controls for a selection of widgets all of one type. They show the first widget's
+    /// values, and every edit goes to the whole selection.
+    fn build_shared_type_controls<'a>(&'a self) -> Option<Element<'a, Message>> {
+        self.hierarchy.common_properties.as_ref()?.shared_type?;
+        let first = self.hierarchy.get_selected_widgets().into_iter().min_by_key(|widget| widget.id.0)?;
+        Some(self.build_type_controls(first, false).map(|message| match message {
+            Message::PropertyChanged(_, change) => Message::BatchPropertyChanged(change),
+            Message::NumericEdited(field, text) => Message::BatchNumericEdited(field, text),
+            Message::NumericCommitted(field) => Message::BatchNumericCommitted(field),
+            message => message,
+        }))
+    }
+
+    /// Pattern, numbering options and a live preview for renaming the selection
+    fn build_bulk_rename<'a>(&'a self) -> Element<'a, Message> {
+        let edit = |edit| Message::EditBulkRename(edit);
+        let preview = self.bulk_rename.preview(&self.hierarchy);
+
+        let rows: Element<'a, Message> = match &preview {
+            Ok(rows) => scrollable(
+                column(rows.iter().map(|row| {
+                    let new_name = if row.collides {
+                        text(format!("{} (name taken, numbered)", row.final_name)).size(12).style(text::warning)
+                    } else {
+                        text(row.final_name.clone()).size(12)
+                    };
+                    row![
+                        text(row.old_name.clone()).size(12).width(Length::FillPortion(1)),
+                        text("->").size(12),
+                        container(new_name).width(Length::FillPortion(1)),
+                    ]
+                    .spacing(8)
+                    .into()
+                }))
+                .spacing(4)
+            )
+            .height(Length::Fill)
+            .into(),
+            Err(e) => text(e.clone()).size(12).style(text::danger).into(),
+        };
+
+        column![
+            text("Pattern").size(12),
+            text_input("{type}_{n}", &self.bulk_rename.pattern)
+                .on_input(move |v| edit(BulkRenameEdit::Pattern(v)))
+                .on_submit_maybe(preview.is_ok().then_some(Message::ApplyBulkRename)),
+            text("{n} position in the tree, {type} widget type, {old} current name")
+                .size(11)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            row![
+                text("Start at").size(12),
+                text_input("1", &self.bulk_rename.start)
+                    .on_input(move |v| edit(BulkRenameEdit::Start(v)))
+                    .width(60),
+                text("Zero padding").size(12),
+                pick_list(
+                    (0..=MAX_ZERO_PAD).collect::<Vec<_>>(),
+                    Some(self.bulk_rename.zero_pad),
+                    move |digits| edit(BulkRenameEdit::ZeroPad(digits)),
+                ),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+            rule::horizontal(1),
+            row![
+                text("Current name").size(12).width(Length::FillPortion(1)),
+                text("New name").size(12).width(Length::FillPortion(1)),
+            ],
+            rows,
+            row![
+                button(text("Apply"))
+                    .style(button::primary)
+                    .on_press_maybe(preview.is_ok().then_some(Message::ApplyBulkRename)),
+                button(text("Undo last rename"))
+                    .style(button::secondary)
+                    .on_press_maybe(self.last_bulk_rename.is_some().then_some(Message::UndoBulkRename)),
+            ]
+            .spacing(8),
+        ]
+        .spacing(10)
+        .padding(10)
+        .into()
+    }
+
+    fn build_settings<'a>(&'a self) -> Element<'a, Message> {
+        container(
+            column![
+                // Header
+                column![
+                    text("Global Settings and Defaults").size(24).center(),
+                    rule::horizontal(5),
+                ].spacing(10).align_x(Alignment::Center),
+                Space::new().width(Length::Fill).height(10),
+
+                row![
+                    column![
+                        text("App Window Title"),
+                        text_input("Generated UI", &self.app_window_title)
+                            .on_input(Message::AppWindowTitleChanged)
+                    ]
+                    .spacing(5)
+                    .padding(5),
+                    column![
+                        text("App Struct Name"),
+                        text_input("App", &self.app_name)
+                            .on_input(Message::AppStructName2Changed)
+                    ]
+                    .spacing(5)
+                    .padding(5),
+                ]
+                .padding(5),
+
+                self.build_window_settings(),
+                self.build_file_header_settings(),
+                self.build_print_settings(),
+
+                column![
+                    checkbox("Multi-Windowed App", self.multiple_windows)
+                        .on_toggle(Message::MultipleWindowsToggled)
+                ],
+                column![
+                    checkbox("Highlight Selected Widgets", self.highlight_selected)
+                        .on_toggle(Message::OutlineSelectedWidgetsToggled)
+                ],
+                column![
+                    checkbox("Doc Comments on Messages and State", self.doc_comments)
+                        .on_toggle(Message::DocCommentsToggled)
+                ],
+                row![
+                    text("Code Layout"),
+                    pick_list(CodeStyle::ALL, Some(self.code_style), Message::CodeStyleChanged),
+                ].spacing(10).align_y(Alignment::Center),
+                row![
+                    text("Empty Handlers"),
+                    pick_list(StubStyle::ALL, Some(self.stub_style), Message::StubStyleChanged),
+                ].spacing(10).align_y(Alignment::Center),
+                column![
+                    checkbox("Derive Default for App State", self.derive_default)
+                        .on_toggle(Message::DeriveDefaultToggled)
+                ],
+                column![
+                    checkbox("Pure View Builder (for snapshot tests)", self.view_builders)
+                        .on_toggle(Message::ViewBuildersToggled)
+                ],
+                {
+                    let view_helpers = column![
+                        checkbox("Split View into Helper Functions", self.view_helpers)
+                            .on_toggle(Message::ViewHelpersToggled)
+                    ]
+                    .spacing(5);
+                    if self.view_helpers {
+                        view_helpers.push(
+                            row![
+                                text("For layouts with more than").size(12),
+                                text_input("Children", &self.view_helper_children.to_string())
+                                    .on_input(Message::ViewHelperChildrenChanged)
+                                    .size(12)
+                                    .width(50),
+                                text("children, and named widgets").size(12),
+                            ].spacing(5).align_y(Alignment::Center)
+                        )
+                    } else {
+                        view_helpers
+                    }
+                },
+                {
+                    let multi_window = column![
+                        checkbox("Multi-Window Scaffold (iced::daemon)", self.multi_window)
+                            .on_toggle(Message::MultiWindowToggled)
+                    ]
+                    .spacing(5);
+                    // The file dialogs run as Tasks, so update returns one either way
+                    if !self.multi_window && uses_file_dialogs(self.hierarchy.root()) {
+                        multi_window.push(
+                            text("update() returns a Task, since a press opens a file dialog")
+                                .size(12)
+                                .color(Color::from_rgb(0.5, 0.5, 0.5))
+                        )
+                    } else {
+                        multi_window
+                    }
+                },
+                column![
+                    checkbox("Layout Only (standalone view, no state)", self.layout_only)
+                        .on_toggle(Message::LayoutOnlyToggled)
+                ],
+                self.build_project_defaults(),
+                self.build_code_budget(),
+                self.build_code_font_settings(),
+                self.build_design_token_settings(),
+                self.build_hint_settings(),
+                self.build_keybinding_settings(),
+                if cfg!(debug_assertions) {
+                    column![
+                        button(text("Generate 1000-widget stress hierarchy"))
+                            .style(button::secondary)
+                            .on_press(Message::GenerateStressHierarchy)
+                    ]
+                } else {
+                    column![]
+                }
+            ]
+                .spacing(16)
+        )
+        .width(Length::Fixed(400.0))
+        .padding(
+            Padding {
+                top: 10.0,
+                right: 5.0,
+                left: 5.0,
+                bottom: 10.0,
+            }
+        )
+        .into()
+
+    }
+
+    fn build_design_token_settings<'a>(&'a self) -> Element<'a, Message> {
+        let summary = match &self.design_tokens {
+            Some(tokens) => format!(
+                "Last import: {} colors, {} spacing and {} radius tokens",
+                tokens.colors.len(), tokens.spacing.len(), tokens.radius.len()
+            ),
+            None => "Colors, spacing and radii from a JSON or TOML token file".to_string(),
+        };
+
+        let mut content = column![
+            text("Design Tokens").size(18),
+            rule::horizontal(2),
+            row![
+                button(text("Import design tokens…"))
+                    .style(button::secondary)
+                    .on_press(Message::ImportDesignTokens),
+                text(summary).size(12),
+            ].spacing(10).align_y(Alignment::Center),
+        ]
+        .spacing(10);
+
+        if let Some(import) = &self.token_import {
+            content = content.push(self.build_token_import(import));
+        }
+        content.into()
+    }
+
+    /// Mapping dialog for a token file that was just read
+    fn build_token_import<'a>(&'a self, import: &'a TokenImport) -> Element<'a, Message> {
+        let edit = |edit| Message::EditTokenImport(edit);
+        let color_keys: Vec<String> = import.tokens.colors.keys().cloned().collect();
+
+        let roles = column(PaletteRole::ALL.iter().map(|&role| {
+            let mapped = import.mapping.get(&role).cloned();
+            let note = if mapped.is_none() { "no matching token" } else { "" };
+            row![
+                text(role.to_string()).width(110),
+                pick_list(color_keys.clone(), mapped, move |key| edit(TokenImportEdit::Role(role, key)))
+                    .placeholder("Keep theme color")
+                    .width(200),
+                text(note).size(12).style(text::secondary),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into()
+        }))
+        .spacing(5);
+
+        let scale_row = |label: &'a str, apply: bool, keys: Vec<String>, selected: Option<String>,
+                         toggle: fn(bool) -> TokenImportEdit, pick: fn(String) -> TokenImportEdit| {
+            row![
+                checkbox(label, apply).on_toggle(move |on| edit(toggle(on))).width(150),
+                pick_list(keys, selected, move |key| edit(pick(key))).width(160),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+        };
+
+        let mut dialog = column![
+            text(format!("Import \"{}\" as a custom theme", import.name)).size(16),
+            roles,
+        ]
+        .spacing(10);
+
+        let unmapped = import.unmapped_colors();
+        if !unmapped.is_empty() {
+            dialog = dialog.push(
+                text(format!("Unused colors: {}", unmapped.iter().map(|k| k.as_str()).collect::<Vec<_>>().join(", "))).size(12)
+            );
+        }
+        if !import.tokens.spacing.is_empty() {
+            dialog = dialog.push(scale_row(
+                "Default spacing",
+                import.apply_spacing,
+                import.tokens.spacing.keys().cloned().collect(),
+                import.spacing_key.clone(),
+                TokenImportEdit::ApplySpacing,
+                TokenImportEdit::SpacingKey,
+            ));
+        }
+        if !import.tokens.radius.is_empty() {
+            dialog = dialog.push(scale_row(
+                "Default border radius",
+                import.apply_radius,
+                import.tokens.radius.keys().cloned().collect(),
+                import.radius_key.clone(),
+                TokenImportEdit::ApplyRadius,
+                TokenImportEdit::RadiusKey,
+            ));
+        }
+        if !import.tokens.unknown.is_empty() {
+            dialog = dialog.push(
+                text(format!("Ignored: {}", import.tokens.unknown.join(", "))).size(12).style(text::secondary)
+            );
+        }
+
+        match &import.changes {
+            Some(changes) if changes.is_empty() => {
+                dialog = dialog.push(text("No values changed since the last import").size(12));
+            }
+            Some(changes) => {
+                let shown = |value: &Option<String>| value.clone().unwrap_or_else(|| "(none)".to_string());
+                dialog = dialog.push(text("Changed since the last import").size(14));
+                dialog = dialog.push(column(changes.iter().map(|change| {
+                    text(format!("{}: {} → {}", change.key, shown(&change.before), shown(&change.after)))
+                        .size(12)
+                        .into()
+                })).spacing(2));
+            }
+            None => {}
+        }
+
+        dialog = dialog.push(row![
+            space::horizontal(),
+            button(text("Cancel")).style(button::text).on_press(Message::CancelDesignTokens),
+            button(text("Apply")).style(button::primary).on_press(Message::ApplyDesignTokens),
+        ].spacing(10));
+
+        container(dialog)
+            .padding(10)
+            .style(container::bordered_box)
+            .into()
+    }
+
+    fn build_hint_settings<'a>(&'a self) -> Element<'a, Message> {
+        column![
+            text("Structure Hints").size(18),
+            rule::horizontal(2),
+            column(HintRule::ALL.iter().map(|&rule| {
+                checkbox(rule.description(), !self.disabled_hints.contains(&rule))
+                    .on_toggle(move |enabled| Message::HintRuleToggled(rule, enabled))
+                    .into()
+            }))
+            .spacing(5),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn build_keybinding_settings<'a>(&'a self) -> Element<'a, Message> {
+        column![
+            text("Keyboard Shortcuts").size(18),
+            rule::horizontal(2),
+            column(KeyAction::ALL.iter().map(|&action| {
+                let binding = if self.capturing_key == Some(action) {
+                    button(text("Press new key…"))
+                        .style(button::primary)
+                        .on_press(Message::CancelKeyCapture)
+                } else {
+                    button(text(self.keybindings.combo(action).to_string()))
+                        .style(button::secondary)
+                        .on_press(Message::CaptureKeybinding(action))
+                };
+                let reset = button(text("Reset").size(12))
+                    .style(button::text)
+                    .on_press_maybe((!self.keybindings.is_default(action)).then_some(Message::ResetKeybinding(action)));
+                let conflict = self.keybinding_conflict.as_ref()
+                    .filter(|(conflicted, _)| *conflicted == action)
+                    .map(|(_, reason)| text(reason).size(12).style(text::danger));
+
+                column![
+                    row![
+                        text(action.description()).width(170),
+                        binding.width(150),
+                        reset,
+                    ].spacing(10).align_y(Alignment::Center),
+                    conflict,
+                ]
+                .spacing(4)
+                .into()
+            }))
+            .spacing(5),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    /// Preset picker and the window fields it fills, each still editable on its own
+    fn build_window_settings<'a>(&'a self) -> Element<'a, Message> {
+        let window = self.window_settings;
+        let estimate = self.estimated_min_size();
+
+        let min_size: Element<'a, Message> = match window.min_size {
+            Some((min_width, min_height)) => row![
+                text("Minimum").width(110),
+                slider(100.0..=1920.0, min_width, move |v| Message::WindowSettingsChanged(WindowSettings { min_size: Some((v, min_height)), ..window }))
+                    .step(10.0)
+                    .width(120),
+                slider(100.0..=1080.0, min_height, move |v| Message::WindowSettingsChanged(WindowSettings { min_size: Some((min_width, v)), ..window }))
+                    .step(10.0)
+                    .width(120),
+                text(format!("{:.0} × {:.0}", min_width, min_height)),
+            ].spacing(10).align_y(Alignment::Center).into(),
+            None => row![].into(),
+        };
+
+        column![
+            text("Window").size(18),
+            rule::horizontal(2),
+            row![
+                text("Preset").width(110),
+                pick_list(WindowPreset::ALL, window.preset(), Message::WindowPresetChosen)
+                    .placeholder("Custom"),
+            ].spacing(10).align_y(Alignment::Center),
+            row![
+                text("Size").width(110),
+                slider(200.0..=3840.0, window.width, move |v| Message::WindowSettingsChanged(WindowSettings { width: v, ..window }))
+                    .step(10.0)
+                    .width(120),
+                slider(150.0..=2160.0, window.height, move |v| Message::WindowSettingsChanged(WindowSettings { height: v, ..window }))
+                    .step(10.0)
+                    .width(120),
+                text(format!("{:.0} × {:.0}", window.width, window.height)),
+            ].spacing(10).align_y(Alignment::Center),
+            checkbox("Minimum size", window.min_size.is_some())
+                .on_toggle(move |on| Message::WindowSettingsChanged(WindowSettings {
+                    min_size: on.then_some((window.width.min(320.0), window.height.min(200.0))),
+                    ..window
+                })),
+            min_size,
+            row![
+                text(format!("Estimated minimum: {:.0} × {:.0}", estimate.0, estimate.1))
+                    .size(12)
+                    .style(text::secondary),
+                button(text("Set as min size").size(12))
+                    .style(button::secondary)
+                    .on_press_maybe((window.min_size != Some(estimate)).then_some(
+                        Message::WindowSettingsChanged(WindowSettings { min_size: Some(estimate), ..window })
+                    )),
+            ].spacing(10).align_y(Alignment::Center),
+            text("From fixed sizes, padding and spacing, with text measured roughly. Check it in the running app.")
+                .size(11)
+                .style(text::secondary),
+            row![
+                checkbox("Resizable", window.resizable)
+                    .on_toggle(move |v| Message::WindowSettingsChanged(WindowSettings { resizable: v, ..window })),
+                checkbox("Centered", window.centered)
+                    .on_toggle(move |v| Message::WindowSettingsChanged(WindowSettings { centered: v, ..window })),
+                checkbox("Decorations", window.decorations)
+                    .on_toggle(move |v| Message::WindowSettingsChanged(WindowSettings { decorations: v, ..window })),
+            ].spacing(15),
+            row![
+                checkbox("Always on top", window.always_on_top)
+                    .on_toggle(move |v| Message::WindowSettingsChanged(WindowSettings { always_on_top: v, ..window })),
+                checkbox("Fullscreen", window.fullscreen)
+                    .on_toggle(move |v| Message::WindowSettingsChanged(WindowSettings { fullscreen: v, ..window })),
+            ].spacing(15),
+        ]
+        .spacing(10)
+        .padding(5)
         .into()
     }
-    
-    /// Builds the batch property editor overlay
-    fn build_batch_editor<'a>(&'a self) -> Element<'a, Message> {
-        batch_editor_controls(&self.hierarchy, self.theme.clone())
+
+    /// Author, license and year for the header of generated files, and the template they fill
+    fn build_file_header_settings<'a>(&'a self) -> Element<'a, Message> {
+        let header = &self.file_header;
+        let picked = file_header::COMMON_LICENSES.iter().copied().find(|license| *license == header.license);
+        let preview = header.comment_lines(&self.app_name).join("\n");
+
+        let fields: Element<'a, Message> = if header.enabled {
+            column![
+                row![
+                    text("Author").width(110),
+                    text_input("Name or company", &header.author)
+                        .on_input(|v| Message::FileHeader(FileHeaderMessage::AuthorChanged(v)))
+                        .width(250),
+                ].spacing(10).align_y(Alignment::Center),
+                row![
+                    text("License").width(110),
+                    pick_list(file_header::COMMON_LICENSES, picked, |v| Message::FileHeader(FileHeaderMessage::LicensePicked(v)))
+                        .placeholder("Other")
+                        .width(170),
+                    text_input("SPDX identifier", &header.license)
+                        .on_input(|v| Message::FileHeader(FileHeaderMessage::LicenseChanged(v)))
+                        .width(170),
+                ].spacing(10).align_y(Alignment::Center),
+                row![
+                    text("Year").width(110),
+                    text_input("Year", &header.year.to_string())
+                        .on_input(|v| Message::FileHeader(FileHeaderMessage::YearChanged(v)))
+                        .width(80),
+                ].spacing(10).align_y(Alignment::Center),
+                row![
+                    text("Template").width(110),
+                    text("{author}, {year}, {license} and {app_name} are filled in").size(12).style(text::secondary),
+                    space::horizontal(),
+                    button(text("Reset").size(12))
+                        .style(button::text)
+                        .on_press_maybe((header.template != file_header::DEFAULT_TEMPLATE)
+                            .then_some(Message::FileHeader(FileHeaderMessage::ResetTemplate))),
+                ].spacing(10).align_y(Alignment::Center),
+                text_editor(&self.file_header_template)
+                    .on_action(|action| Message::FileHeader(FileHeaderMessage::TemplateEdited(action)))
+                    .height(80),
+                text(preview).size(12).font(Font::MONOSPACE).style(text::secondary),
+            ]
+            .spacing(10)
+            .into()
+        } else {
+            row![].into()
+        };
+
+        column![
+            text("File Header").size(18),
+            rule::horizontal(2),
+            checkbox("License header on generated files", header.enabled)
+                .on_toggle(|v| Message::FileHeader(FileHeaderMessage::Toggled(v))),
+            fields,
+        ]
+        .spacing(10)
+        .padding(5)
+        .into()
     }
 
-    fn build_settings<'a>(&'a self) -> Element<'a, Message> {
+    fn build_print_settings<'a>(&'a self) -> Element<'a, Message> {
+        let settings = self.print_settings;
+        let narrow = settings.line_width < printable::MIN_LINE_WIDTH;
+        column![
+            text("Printable Export").size(18),
+            rule::horizontal(2),
+            row![
+                text("Line width").width(110),
+                text_input("Characters", &settings.line_width.to_string())
+                    .on_input(Message::PrintLineWidthChanged)
+                    .width(80),
+                text(if narrow {
+                    format!("Printed at {} characters, the narrowest that fits the code", printable::MIN_LINE_WIDTH)
+                } else {
+                    format!("Longer lines continue after \"{}\"", printable::CONTINUATION.trim_end())
+                })
+                .size(12)
+                .style(text::secondary),
+            ].spacing(10).align_y(Alignment::Center),
+            row![
+                text("Format").width(110),
+                pick_list(PrintFormat::ALL, Some(settings.format), Message::PrintFormatChanged).width(170),
+                space::horizontal(),
+                button(text("Export printable…")).on_press(Message::ExportPrintable),
+            ].spacing(10).align_y(Alignment::Center),
+        ]
+        .spacing(10)
+        .padding(5)
+        .into()
+    }
+
+    fn build_asset_library<'a>(&'a self) -> Element<'a, Message> {
+        let library = &self.asset_library;
+
+        let entries: Element<'a, Message> = if library.is_empty() {
+            text("No assets yet. Added files can be picked from the properties of any Image or Svg widget.")
+                .size(12)
+                .into()
+        } else {
+            column(library.assets().iter().map(|asset| self.build_asset_row(asset)))
+                .spacing(10)
+                .into()
+        };
+
+        let missing = match library.missing_count() {
+            0 => None,
+            count => Some(text(format!("{} asset file(s) are missing", count)).size(12).style(text::danger)),
+        };
+
         container(
-            column![
-                // Header
+            scrollable(
                 column![
-                    text("Global Settings and Defaults").size(24).center(),
-                    rule::horizontal(5),
-                ].spacing(10).align_x(Alignment::Center),
-                Space::new().width(Length::Fill).height(10),
-
-                row![
-                    column![
-                        text("App Window Title"),
-                        text_input("Generated UI", &self.app_window_title)
-                            .on_input(Message::AppWindowTitleChanged)
-                    ]
-                    .spacing(5)
-                    .padding(5),
                     column![
-                        text("App Struct Name"),
-                        text_input("App", &self.app_name)
-                            .on_input(Message::AppStructName2Changed)
-                    ]
-                    .spacing(5)
-                    .padding(5),
+                        text("Asset Library").size(24).center(),
+                        rule::horizontal(5),
+                    ].spacing(10).align_x(Alignment::Center),
+                    row![
+                        button(text("Add files…").size(12))
+                            .on_press(Message::Assets(AssetMessage::AddFiles)),
+                        button(text("Add folder…").size(12))
+                            .style(button::secondary)
+                            .on_press(Message::Assets(AssetMessage::AddFolder)),
+                        button(text("Check files").size(12))
+                            .style(button::secondary)
+                            .on_press(Message::Assets(AssetMessage::CheckFiles)),
+                    ].spacing(10),
+                    missing,
+                    entries,
                 ]
-                .padding(5),
+                .spacing(15)
+            )
+            .height(Length::Fill)
+        )
+        .width(Length::Fixed(400.0))
+        .padding(10)
+        .into()
+    }
 
-                column![
-                    checkbox("Multi-Windowed App", self.multiple_windows)
-                        .on_toggle(Message::MultipleWindowsToggled)
-                ],
-                column![
-                    checkbox("Highlight Selected Widgets", self.highlight_selected)
-                        .on_toggle(Message::OutlineSelectedWidgetsToggled)
+    fn build_asset_row<'a>(&'a self, asset: &'a Asset) -> Element<'a, Message> {
+        let name: Element<'a, Message> = match &self.asset_rename {
+            Some(rename) if rename.id == asset.id => column![
+                row![
+                    text_input("Name", &rename.name)
+                        .on_input(|name| Message::Assets(AssetMessage::RenameEdited(name)))
+                        .on_submit(Message::Assets(AssetMessage::CommitRename))
+                        .size(12),
+                    button(text("Save").size(12)).on_press(Message::Assets(AssetMessage::CommitRename)),
+                    button(text("Cancel").size(12))
+                        .style(button::secondary)
+                        .on_press(Message::Assets(AssetMessage::CancelRename)),
+                ].spacing(5).align_y(Alignment::Center),
+                rename.error.as_ref().map(|error| text(error).size(12).style(text::danger)),
+            ].spacing(4).into(),
+            _ => button(text(&asset.name).size(14))
+                .style(button::text)
+                .padding(0)
+                .on_press(Message::Assets(AssetMessage::StartRename(asset.id)))
+                .into(),
+        };
+
+        let status = if self.asset_library.is_missing(asset.id) {
+            text("missing file").size(11).style(text::danger)
+        } else {
+            let users = asset_library::linked_widgets(self.hierarchy.root(), asset.id, &asset.path_property()).len();
+            text(format!("{} · used by {} widget(s)", asset.kind, users)).size(11)
+        };
+
+        row![
+            asset_thumbnail(asset, 32.0, self.asset_library.is_missing(asset.id)),
+            column![
+                name,
+                text(asset.path.display().to_string()).size(11),
+                status,
+            ].spacing(2).width(Length::Fill),
+            button(text("Move…").size(12))
+                .style(button::secondary)
+                .on_press(Message::Assets(AssetMessage::Relink(asset.id))),
+            button(icon::trash())
+                .style(button::danger)
+                .on_press(Message::Assets(AssetMessage::Remove(asset.id))),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn build_project_defaults<'a>(&'a self) -> Element<'a, Message> {
+        let defaults = &self.project_defaults;
+
+        let apply_button = |property| {
+            button(text("Apply to existing").size(12))
+                .style(button::secondary)
+                .on_press(Message::ApplyDefaultToExisting(property))
+        };
+
+        column![
+            text("Defaults for New Widgets").size(18),
+            rule::horizontal(2),
+            row![
+                text("Text Size").width(110),
+                slider(8.0..=48.0, defaults.text_size, Message::DefaultTextSizeChanged).step(1.0).width(120),
+                text(format!("{:.0}px", defaults.text_size)).width(45),
+                apply_button(DefaultProperty::TextSize),
+            ].spacing(10).align_y(Alignment::Center),
+            row![
+                text("Row/Column Spacing").width(110),
+                slider(0.0..=50.0, defaults.layout_spacing, Message::DefaultSpacingChanged).step(1.0).width(120),
+                text(format!("{:.0}px", defaults.layout_spacing)).width(45),
+                apply_button(DefaultProperty::Spacing),
+            ].spacing(10).align_y(Alignment::Center),
+            row![
+                text("Padding").width(110),
+                slider(0.0..=50.0, defaults.padding, Message::DefaultPaddingChanged).step(1.0).width(120),
+                text(format!("{:.0}px", defaults.padding)).width(45),
+                apply_button(DefaultProperty::Padding),
+            ].spacing(10).align_y(Alignment::Center),
+            row![
+                text("Border Radius").width(110),
+                slider(0.0..=30.0, defaults.border_radius, Message::DefaultBorderRadiusChanged).step(1.0).width(120),
+                text(format!("{:.0}px", defaults.border_radius)).width(45),
+                apply_button(DefaultProperty::BorderRadius),
+            ].spacing(10).align_y(Alignment::Center),
+            row![
+                text("Button Style").width(110),
+                pick_list(
+                    ButtonStyleType::ALL,
+                    Some(defaults.button_style),
+                    Message::DefaultButtonStyleChanged,
+                ).width(175),
+                apply_button(DefaultProperty::ButtonStyle),
+            ].spacing(10).align_y(Alignment::Center),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn build_code_budget<'a>(&'a self) -> Element<'a, Message> {
+        column![
+            row![
+                text("Code Size Warnings").size(18),
+                space::horizontal(),
+                button(text("Reset").size(12))
+                    .style(button::text)
+                    .on_press_maybe((self.code_budget != CodeBudget::default()).then_some(Message::ResetCodeBudget)),
+            ].align_y(Alignment::Center),
+            rule::horizontal(2),
+            column(CodeMetric::ALL.iter().map(|&metric| {
+                let limit = self.code_budget.limit(metric);
+                row![
+                    text(format!("Max {}", metric.label())).width(110),
+                    slider(metric.limit_range(), limit as f32, move |v| Message::CodeBudgetChanged(metric, v as usize))
+                        .step(1.0)
+                        .width(120),
+                    text(limit.to_string()).width(45),
                 ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .into()
+            }))
+            .spacing(10),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn build_code_font_settings<'a>(&'a self) -> Element<'a, Message> {
+        column![
+            row![
+                text("Code Font").size(18),
+                space::horizontal(),
+                button(text("Reset").size(12))
+                    .style(button::text)
+                    .on_press_maybe((self.code_font != CodeFontSettings::default()).then_some(Message::ResetCodeFont)),
+            ].align_y(Alignment::Center),
+            rule::horizontal(2),
+            column![
+                text("Families, first installed one wins").size(12),
+                text_input(code_font::DEFAULT_FAMILIES, &self.code_font.families)
+                    .on_input(Message::CodeFontFamiliesChanged),
+                text(match self.code_font_family {
+                    Some(family) => format!("Using {}", family),
+                    None => "Using the default monospace font".to_string(),
+                })
+                .size(12)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
             ]
-                .spacing(16)
-        )
-        .width(Length::Fixed(400.0))
-        .padding(
-            Padding {
-                top: 10.0,
-                right: 5.0,
-                left: 5.0,
-                bottom: 10.0,
+            .spacing(5),
+            row![
+                text("Size").width(110),
+                pick_list(CodeFontSize::ALL, Some(self.code_font.size), Message::CodeFontSizeChanged),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    /// Line, message, state and nesting counts for the code panel header, in the warning
+    /// color with a suggestion once they pass the budget
+    fn build_code_metrics(&self, metrics: &CodeMetrics) -> Element<'_, Message> {
+        row(CodeMetric::ALL.iter().map(|&metric| {
+            let count = text(format!("{}: {}", metric.label(), metric.value(metrics))).size(12);
+            if self.code_budget.exceeds(metrics, metric) {
+                tooltip(
+                    count.style(text::warning),
+                    text(format!(
+                        "Over the budget of {}. {}",
+                        self.code_budget.limit(metric),
+                        metric.advice(),
+                    ))
+                    .size(12),
+                    tooltip::Position::Bottom,
+                )
+                .into()
+            } else {
+                count.into()
             }
-        )
+        }))
+        .spacing(15)
+        .align_y(Alignment::Center)
         .into()
+    }
+
+    /// Tokens of the code panel, generated with the current settings
+    fn full_code_tokens(&self) -> Vec<Token> {
+        self.full_code().tokens
+    }
+
+    /// Code panel tokens together with their counts and any message wiring the passes disagree on,
+    /// generated right away for copies and exports
+    fn full_code(&self) -> GeneratedCode {
+        code_generation::generate(&self.hierarchy, &self.type_system, &self.generation_settings())
+    }
+
+    /// Moves the widgets still on an asset's previous path to where the entry points now
+    fn follow_asset(&mut self, asset_id: Uuid, previous: &str) {
+        let Some(change) = self.asset_library.get(asset_id).map(|asset| asset.property_change()) else {
+            return;
+        };
+        let users = asset_library::linked_widgets(self.hierarchy.root(), asset_id, previous);
+        for &widget_id in &users {
+            self.hierarchy.apply_property_change(widget_id, change.clone(), &self.type_system);
+        }
+        self.notification = Some(format!("Moved the asset, {} widget(s) follow it", users.len()));
+    }
+
+    /// Every tab and the settings they share, as a project file
+    fn project_file(&self) -> ProjectFile {
+        let mut file = ProjectFile::new(self.saved_tabs(), self.workspace.active());
+        file.app_name = self.app_name.clone();
+        file.window_title = self.app_window_title.clone();
+        file.theme = Some(self.theme.to_string());
+        file.type_system = self.type_system.clone();
+        file.project_defaults = self.project_defaults.clone();
+        file.window_settings = self.window_settings;
+        file.file_header = self.file_header.clone();
+        file.named_styles = self.named_styles.clone();
+        file.asset_library = self.asset_library.clone();
+        file
+    }
 
+    /// Writes every tab and the settings they share to `path` as a project file
+    pub fn save_project(&self, path: &std::path::Path) -> Result<(), String> {
+        let json = self.project_file().to_json()?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Opens the project file at `path` in place of the open tabs and shared settings
+    pub fn load_project(&mut self, path: &std::path::Path) -> Result<(), String> {
+        let project = project_file::load(path)?;
+        self.apply_project(project);
+        Ok(())
+    }
+
+    /// Replaces the open tabs and shared settings with a loaded project's
+    fn apply_project(&mut self, project: LoadedProject) {
+        let LoadedProject { file, tabs, .. } = project;
+        self.replace_tabs(tabs, file.active_tab);
+
+        self.app_name = file.app_name;
+        self.app_window_title = file.window_title;
+        if let Some(theme) = file.theme.and_then(|name| {
+            Theme::ALL.iter().chain(&self.imported_themes).find(|theme| theme.to_string() == name).cloned()
+        }) {
+            self.theme = theme;
+        }
+        self.type_system = file.type_system;
+        self.project_defaults = file.project_defaults;
+        self.window_settings = file.window_settings;
+        self.file_header_template = text_editor::Content::with_text(&file.file_header.template);
+        self.file_header = file.file_header;
+        self.named_styles = file.named_styles;
+        self.asset_library = file.asset_library;
+        self.asset_library.check_files();
+    }
+
+    /// The title setting, or with several tabs open and no title set, the shown tab's name
+    fn generated_window_title(&self) -> &str {
+        if self.app_window_title.trim().is_empty() && self.workspace.len() > 1 {
+            self.workspace.active_name()
+        } else {
+            &self.app_window_title
+        }
+    }
+
+    /// Child count past which the generated view splits a layout out, None while it stays flat
+    fn view_helper_threshold(&self) -> Option<usize> {
+        self.view_helpers.then_some(self.view_helper_children)
+    }
+
+    fn generation_settings(&self) -> GenerationSettings {
+        GenerationSettings {
+            theme: self.theme.clone(),
+            app_name: self.app_name.clone(),
+            window_title: self.generated_window_title().to_string(),
+            doc_comments: self.doc_comments,
+            code_style: self.code_style,
+            stub_style: self.stub_style,
+            derive_default: self.derive_default,
+            view_builders: self.view_builders,
+            view_helpers: self.view_helper_threshold(),
+            multi_window: self.multi_window,
+            layout_only: self.layout_only,
+            window: self.window_settings,
+            file_header: self.file_header.clone(),
+            named_styles: self.named_styles.clone(),
+        }
+    }
+
+    /// Generates the code panel off the update path, from a copy of the tree. A newer run
+    /// started before this one finishes replaces it.
+    fn start_code_generation(&mut self) -> iced::Task<Message> {
+        let copied_at = std::time::Instant::now();
+        let snapshot = GenerationSnapshot::new(&self.hierarchy, &self.type_system, self.generation_settings());
+        if cfg!(debug_assertions) {
+            println!("Copied the tree for code generation in {:?}", copied_at.elapsed());
+        }
+
+        let run = self.code_generation.start(self.revision);
+        let widgets = undo::subtree_size(self.hierarchy.root());
+        let (task, handle) = iced::Task::perform(
+            async move {
+                panic_guard::catch(
+                    GuardedPanel::CodeGeneration,
+                    || format!("run {} over {} widgets", run, widgets),
+                    || snapshot.generate(),
+                )
+                .map(std::sync::Arc::new)
+            },
+            move |code| Message::CodeGenerated(run, code),
+        )
+        .abortable();
+        self.code_generation.track(handle);
+        task
+    }
+
+    /// One warning line per variant the generation passes disagree on
+    fn build_wiring_problems(&self, problems: &[WiringProblem]) -> Element<'_, Message> {
+        column(problems.iter().map(|problem| {
+            text(format!("Generator bug: {}", problem)).size(12).style(text::warning).into()
+        }))
+        .spacing(2)
+        .into()
     }
 
     fn build_full_code_content(&self) -> Element<Message> {
-        let mut generator = CodeGenerator::new(&self.hierarchy, self.theme.clone(), Some(&self.type_system));
-        generator.set_app_name(self.app_name.clone());
-        generator.set_window_title(self.app_window_title.clone());
-        let tokens = generator.generate_app_code();
+        if let Some(caught) = self.panic_guards.tripped(GuardedPanel::CodeGeneration) {
+            return panic_guard::recovery_view(&caught);
+        }
+        let Some(GeneratedCode { tokens, metrics, problems, .. }) = self.code_generation.latest() else {
+            return container(text("Generating…").size(14).style(text::secondary))
+                .padding(20)
+                .into();
+        };
+        let last_export: Option<Element<Message>> = self.last_export.as_ref().map(|path| {
+            row![
+                text(format!("Last export: {}", path.display())).size(12),
+                button(text("Open").size(12))
+                    .style(button::text)
+                    .on_press(Message::OpenLastExport),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .padding(Padding { top: 0.0, right: 10.0, bottom: 0.0, left: 10.0 })
+            .into()
+        });
         
         // Create the full code string for copying
-        let code_string: String = tokens.iter().map(|t| t.text.clone()).collect();
+        let code_string = code_generator::source_of(&tokens);
+        let matches = code_search::find_matches(&code_string, &self.code_search.query);
+        let current = (!matches.is_empty()).then(|| self.code_search.current.min(matches.len() - 1));
         
         column![
             // Header with copy button
             row![
-                text("Complete Iced Application Code").size(20),
+                text(if self.layout_only { "Layout Skeleton" } else { "Complete Iced Application Code" }).size(20),
+                // The shown code stays up while a newer run is owed
+                text(if self.code_generation.is_generating() { "generating…" } else { "" })
+                    .size(12)
+                    .style(text::secondary),
                 space::horizontal(),
                 tooltip(
                     button(icon::copy())
@@ -3179,6 +8088,46 @@ impl WidgetVisualizer {
                         .size(12),
                         tooltip::Position::Left
                 ),
+                tooltip(
+                    button(icon::code())
+                        .style(button::text)
+                        .on_press(Message::ExportProject),
+                    text("Export Cargo project (Cargo.toml, src/main.rs, README.md)")
+                        .size(12),
+                        tooltip::Position::Left
+                ),
+                tooltip(
+                    button(icon::type_icon())
+                        .style(button::text)
+                        .on_press(Message::ExportMainRs),
+                    text("Save main.rs only")
+                        .size(12),
+                        tooltip::Position::Left
+                ),
+                tooltip(
+                    button(icon::save())
+                        .style(button::text)
+                        .on_press(Message::ExportLayoutReport),
+                    text("Export layout report (markdown)")
+                        .size(12),
+                        tooltip::Position::Left
+                ),
+                tooltip(
+                    button(icon::info())
+                        .style(button::text)
+                        .on_press(Message::CopyLayoutReport),
+                    text("Copy layout report as markdown")
+                        .size(12),
+                        tooltip::Position::Left
+                ),
+                tooltip(
+                    button(icon::preview())
+                        .style(button::text)
+                        .on_press(Message::ExportPrintable),
+                    text("Export printable tree and code")
+                        .size(12),
+                        tooltip::Position::Left
+                ),
             ]
             .align_y(Alignment::Center)
             .padding(
@@ -3191,14 +8140,35 @@ impl WidgetVisualizer {
             )
             .spacing(20),
             
+            container(self.build_code_metrics(&metrics))
+                .padding(Padding { top: 0.0, right: 10.0, bottom: 0.0, left: 10.0 }),
+
+            container(self.build_wiring_problems(&problems))
+                .padding(Padding { top: 0.0, right: 10.0, bottom: 0.0, left: 10.0 }),
+
+            last_export,
+
             rule::horizontal(5),
-            Space::new().width(Length::Fill).height(10),
+
+            // Search, Enter for the next match and Shift+Enter for the previous one
+            row![
+                text_input("Search code", &self.code_search.query)
+                    .on_input(Message::CodeSearchChanged)
+                    .on_submit(Message::CodeSearchSubmitted)
+                    .width(Length::Fill),
+                text(if self.code_search.query.is_empty() {
+                    String::new()
+                } else {
+                    self.code_search.counter(matches.len())
+                })
+                .size(12),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .padding(Padding { top: 0.0, right: 10.0, bottom: 0.0, left: 10.0 }),
             
             container(
-                scrollable(
-                    build_code_view_with_height(&tokens, 0.0, self.theme.clone()) // 0.0 height == Length::Fill
-                )
-                .width(Length::Fill)
+                build_searchable_code_view(&tokens, self.theme.clone(), &matches, current, self.code_view_font())
             )
             .width(Length::Fill)
             .height(Length::Fill),
@@ -3233,9 +8203,27 @@ pub enum Message {
 
     // Widget Operations
     SelectWidgets(HashSet<usize>),
+    /// Selection reported by the tree, combined with the held modifiers into ranges and toggles
+    TreeSelect(HashSet<usize>),
+    /// Moves the primary end of the selection by this many rows, keeping the anchor
+    ExtendSelection(isize),
+    ModifiersChanged(keyboard::Modifiers),
     DeleteWidget(WidgetId),
     AddChild(WidgetId, WidgetType),
     PropertyChanged(WidgetId, PropertyChange),
+    /// Text typed into a numeric property field, evaluated once committed
+    NumericEdited(NumericField, String),
+    NumericCommitted(NumericField),
+    /// A numeric field of the batch editor, applied to the whole selection
+    BatchNumericEdited(NumericField, String),
+    BatchNumericCommitted(NumericField),
+    /// Steps the numeric field being edited to an older value, or a newer one when false
+    NumericRecall(bool),
+    EditBulkRename(BulkRenameEdit),
+    /// Renames the selection as the bulk rename preview shows
+    ApplyBulkRename,
+    /// Puts back every name the last bulk rename changed
+    UndoBulkRename,
     SwapKind(WidgetId),
 
     // Interactive widget messages
@@ -3254,6 +8242,8 @@ pub enum Message {
     ComboBoxOnClose(WidgetId),
     ComboBoxOnOpen(WidgetId),
     LinkClicked(markdown::Url),
+    MarkdownPreviewToggled(bool),
+    ReparseMarkdown(std::time::Instant),
     Noop,
 
     // Theme, not sure I'm going to implement this with the theme builder in the same app
@@ -3261,7 +8251,16 @@ pub enum Message {
 
     // Code generation related messages
     GenerateFullCode,
+    /// Debounce tick while the code panel is out of date
+    GenerateCode(std::time::Instant),
+    /// A background run finished, numbered so stale runs can be ignored
+    CodeGenerated(u64, Result<std::sync::Arc<GeneratedCode>, CaughtPanic>),
+    /// Resets what a panicked panel showed and builds it again
+    RecoverPanel(GuardedPanel),
     CopyCode(String),
+    CodeSearchChanged(String),
+    /// Enter in the code search field, Shift+Enter goes backwards
+    CodeSearchSubmitted,
     AppNameChanged(String),
     ToggleRadioLayout,
 
@@ -3274,22 +8273,162 @@ pub enum Message {
 
     // Settings
     AppWindowTitleChanged(String),
+    WindowPresetChosen(WindowPreset),
+    /// Any single field edited, carrying the whole settings with it changed
+    WindowSettingsChanged(WindowSettings),
+    FileHeader(FileHeaderMessage),
     AppStructName2Changed(String),
     MultipleWindowsToggled(bool),
     OutlineSelectedWidgetsToggled(bool),
+    DocCommentsToggled(bool),
+    CodeStyleChanged(CodeStyle),
+    StubStyleChanged(StubStyle),
+    DeriveDefaultToggled(bool),
+    ViewBuildersToggled(bool),
+    ViewHelpersToggled(bool),
+    ViewHelperChildrenChanged(String),
+    MultiWindowToggled(bool),
+    LayoutOnlyToggled(bool),
+    PaddingOverlayToggled(bool),
+    FillPortionOverlayToggled(bool),
+    AddWidgetFilterChanged(String),
+    /// Puts the cursor in the add widget palette's filter
+    FocusWidgetFilter,
+    /// Preview values in the live preview, or the generated initial state when false
+    PreviewDataToggled(bool),
+    BreakpointPreviewWidth(f32),
+    PreviewViewport(ViewportMessage),
+
+    // Project defaults for new widgets
+    DefaultTextSizeChanged(f32),
+    DefaultSpacingChanged(f32),
+    DefaultPaddingChanged(f32),
+    DefaultBorderRadiusChanged(f32),
+    DefaultButtonStyleChanged(ButtonStyleType),
+    ApplyDefaultToExisting(DefaultProperty),
+
+    // Soft limits on the generated code
+    CodeBudgetChanged(CodeMetric, usize),
+    ResetCodeBudget,
+    CodeFontFamiliesChanged(String),
+    CodeFontSizeChanged(CodeFontSize),
+    ResetCodeFont,
 
     //Send Messages to Stylefn_Builder
     ForwardThemeMessages(stylefn_builders::Message),
 
+    // Design tokens
+    ImportDesignTokens,
+    DesignTokensLoaded(Result<(std::path::PathBuf, String), String>),
+    EditTokenImport(TokenImportEdit),
+    ApplyDesignTokens,
+    CancelDesignTokens,
+
     // Type system messages
     TypeEditor(type_editor::Message),
+    Assets(AssetMessage),
+    Styles(StyleMessage),
+    Workspace(WorkspaceMessage),
+    OptionImport(OptionImportMessage),
+    ViewImport(ViewImportMessage),
+    ReferenceImage(ReferenceMessage),
+    TagDraftChanged(String),
+    /// Show only widgets with this tag, and their ancestors
+    TagFilterChanged(Option<String>),
     OpenTypeEditor,
+    /// Open the type editor on this enum
+    EditEnum(Uuid),
+    EnumForm(EnumFormMessage),
 
     // Wrapping operations
     WrapSelectedInContainer(WidgetType),  // Wraps selection in Row/Column/MouseArea/Tooltip
+    /// Wraps one widget in place, keeping it selected
+    WrapWidget(WidgetId, WidgetType),
+    /// Copies the widget and its children in right after it
+    DuplicateWidget(WidgetId),
+    /// Moves the widget this many places among its siblings
+    MoveAmongSiblings(WidgetId, isize),
+    /// Selects the row this many rows away from the primary selection
+    TreeStep(isize),
+    /// Shows or hides a branch's children in the tree
+    TreeSetExpanded(WidgetId, bool),
+    /// Collapses the selected branch, or selects its parent once it is collapsed
+    TreeSelectParent,
+    /// Expands the selected branch, or selects its first child once it is expanded
+    TreeSelectFirstChild,
+    /// Replaces a single-child layout widget with its child
+    UnwrapWidget(WidgetId),
     
     // Batch editing operations  
     BatchPropertyChanged(PropertyChange), // Applies property to all selected widgets
+
+    // Command palette
+    OpenCommandPalette,
+    CloseCommandPalette,
+    CommandPaletteQueryChanged(String),
+    CommandPaletteMove(isize),
+    CommandPaletteRun(usize),
+
+    // Exports
+    ExportLayoutReport,
+    ExportProject,
+    ExportMainRs,
+    /// Writes every tab and the shared settings to a project file
+    SaveProject,
+    OpenProject,
+    ProjectChosen(Result<std::path::PathBuf, String>),
+    ProjectOpened(Result<std::sync::Arc<LoadedProject>, String>),
+    CopyLayoutReport,
+    /// Tree, key properties and numbered code as one document for reviews on paper
+    ExportPrintable,
+    PrintLineWidthChanged(String),
+    PrintFormatChanged(PrintFormat),
+    TakeComparisonSnapshot,
+    ShowComparison(bool),
+    /// Picks a saved project to compare the current tree against
+    CompareWithProject,
+    ComparisonProjectOpened(Result<(std::path::PathBuf, String), String>),
+    FileSaved(Result<std::path::PathBuf, String>),
+    /// Hands the last export to the platform's default handler
+    OpenLastExport,
+    ExportOpened(Result<(), String>),
+    DismissNotification,
+    Notification(NotificationMessage),
+
+    // Structure hints
+    ApplyHint(HintFix),
+    DismissHint,
+    ApplyContrastSuggestion(WidgetId),
+    DismissContrastSuggestion(WidgetId),
+    HintDontShowAgainToggled(bool),
+    HintRuleToggled(HintRule, bool),
+
+    // Property history
+    HistoryToggled,
+    RevertProperty(WidgetId, u64),
+
+    // Undo history
+    JumpToUndoStep(usize),
+    Undo,
+    Redo,
+    UndoStepHovered(Option<usize>),
+
+    // Keybindings
+    KeyPressed(keyboard::Key, keyboard::Modifiers),
+    CaptureKeybinding(KeyAction),
+    CancelKeyCapture,
+    ResetKeybinding(KeyAction),
+    DeleteSelection,
+
+    // Pinned property inspectors
+    PinInspector(WidgetId),
+    UnpinInspector(WidgetId),
+    /// Toggles a pinned inspector between its narrow and wide width
+    WidenInspector(WidgetId),
+
+    // Tree windowing
+    TreeScrolled(scrollable::Viewport),
+    GenerateStressHierarchy,
 }
 
 pub enum Action {
@@ -3301,7 +8440,7 @@ pub enum Action {
 // WIDGET STRUCTURES
 // ============================================================================
 
-#[derive(Debug, Clone,)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Widget {
     pub id: WidgetId,
     pub widget_type: WidgetType,
@@ -3322,7 +8461,7 @@ impl Widget {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum WidgetType {
     Container,
     Scrollable,
@@ -3358,6 +8497,7 @@ enum LeftPane {
     Settings,
     Themes,
     Types,
+    Assets,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -3390,12 +8530,112 @@ fn parse_length(value: &str) -> Length {
     }
 }
 
-pub fn length_to_string(length: Length) -> String {
-    match length {
-        Length::Fill => "Fill".to_string(),
-        Length::Shrink => "Shrink".to_string(),
-        Length::Fixed(pixels) => format!("{}", pixels),
-        Length::FillPortion(p) => format!("FillPortion({p})"),
+pub fn length_to_string(length: Length) -> String {
+    match length {
+        Length::Fill => "Fill".to_string(),
+        Length::Shrink => "Shrink".to_string(),
+        Length::Fixed(pixels) => format!("{}", pixels),
+        Length::FillPortion(p) => format!("FillPortion({p})"),
+    }
+}
+
+/// Notification shown once an export is written, also how the bar recognizes it
+fn saved_notice(path: &std::path::Path) -> String {
+    format!("Saved {}", path.display())
+}
+
+/// The tree of a saved project to compare a tab against: the tab of the same name, or the
+/// one the project was saved on
+fn comparison_root(mut file: ProjectFile, tab_name: &str) -> Widget {
+    let index = file.tabs.iter().position(|tab| tab.name == tab_name).unwrap_or(file.active_tab);
+    file.tabs.swap_remove(index).root
+}
+
+/// `layer` drawn over `content`, the stack sized as `content` asks. A Fill layer would
+/// otherwise stretch a Shrink widget and even out its siblings' fill portions.
+fn overlay_on<'a>(content: Element<'a, Message>, layer: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    use iced::advanced::Widget as _;
+    let size = content.as_widget().size_hint();
+    stack![content, layer.into()].width(size.width).height(size.height).into()
+}
+
+/// `content` with its fill portion in the top right corner, left alone without one
+fn with_fill_badge<'a>(content: Element<'a, Message>, share: Option<fill_portions::Share>) -> Element<'a, Message> {
+    let Some(share) = share else { return content };
+    let badge = container(text(share.label()).size(10))
+        .padding([1, 4])
+        .style(|theme: &Theme| container::Style {
+            background: Some(Background::Color(theme.extended_palette().primary.strong.color)),
+            text_color: Some(theme.extended_palette().primary.strong.text),
+            border: Border { radius: 3.0.into(), ..Border::default() },
+            ..Default::default()
+        });
+    overlay_on(content, container(badge).align_right(Length::Fill).align_top(Length::Fill))
+}
+
+/// Short form of a widget's preview data for its tree row
+fn preview_data_label(widget: &Widget) -> Option<String> {
+    const MAX_CHARS: usize = 16;
+    let props = &widget.properties;
+    let preview = match widget.widget_type {
+        WidgetType::TextInput => props.preview_text.clone()?,
+        WidgetType::PickList | WidgetType::ComboBox => props.preview_selection.clone()?,
+        _ => return None,
+    };
+    Some(if preview.chars().count() > MAX_CHARS {
+        format!("{}…", preview.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        preview
+    })
+}
+
+/// Name shown in the widget tree. Rules carry their orientation so the two kinds can be told apart.
+fn tree_label(widget: &Widget) -> String {
+    match widget.widget_type {
+        WidgetType::Rule => format!("{} ({})", widget.name, widget.properties.orientation),
+        _ => widget.name.clone(),
+    }
+}
+
+/// Preview counterpart of the generated `row![text(label), space::horizontal(), control]`
+fn left_labelled<'a>(label: &'a str, spacing: f32, width: Length, control: Element<'a, Message>) -> Element<'a, Message> {
+    row![text(label), space::horizontal(), control]
+        .spacing(spacing)
+        .width(width)
+        .align_y(Alignment::Center)
+        .into()
+}
+
+/// Ids of `widget` and everything below it
+fn collect_ids(widget: &Widget, ids: &mut Vec<WidgetId>) {
+    ids.push(widget.id);
+    for child in &widget.children {
+        collect_ids(child, ids);
+    }
+}
+
+/// Two-stop linear gradient a container draws as its background
+pub fn container_gradient(props: &Properties) -> iced::gradient::Linear {
+    iced::gradient::Linear::new(iced::Degrees(props.gradient_angle))
+        .add_stop(0.0, props.gradient_start)
+        .add_stop(1.0, props.gradient_end)
+}
+
+/// The Checkbox or Toggler a conditional style follows, None once it is deleted or retyped
+pub fn condition_source(hierarchy: &WidgetHierarchy, id: WidgetId) -> Option<&Widget> {
+    hierarchy.get_widget_by_id(id)
+        .filter(|source| matches!(source.widget_type, WidgetType::Checkbox | WidgetType::Toggler))
+}
+
+/// Whether the preview draws a widget's alternate colors. A Checkbox or Toggler condition
+/// follows that widget, a named flag its preview value.
+pub fn condition_holds(hierarchy: &WidgetHierarchy, props: &Properties) -> bool {
+    match &props.style_condition {
+        None => false,
+        Some(StyleCondition::Flag(_)) => props.preview_condition,
+        Some(StyleCondition::Widget(id)) => condition_source(hierarchy, *id).is_some_and(|source| {
+            source.properties.checkbox_checked || source.properties.toggler_active
+        }),
     }
 }
 
@@ -3408,14 +8648,29 @@ fn can_have_children(widget_type: &WidgetType) -> bool {
     )
 }
 
+/// Widgets that produce messages in the generated app
+fn is_interactive(widget_type: &WidgetType) -> bool {
+    matches!(
+        widget_type,
+        WidgetType::Button | WidgetType::TextInput | WidgetType::Checkbox |
+        WidgetType::Radio | WidgetType::Slider | WidgetType::VerticalSlider |
+        WidgetType::Toggler | WidgetType::PickList | WidgetType::ComboBox |
+        WidgetType::MouseArea
+    )
+}
+
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Properties {
+    #[serde(with = "project_file::length")]
     pub width: Length,
+    #[serde(with = "project_file::length")]
     pub height: Length,
     pub max_width: Option<f32>,
     pub max_height: Option<f32>,
     pub clip: bool, 
+    #[serde(with = "project_file::padding")]
     pub padding: Padding,
     pub widget_id: Option<String>,
 
@@ -3433,13 +8688,40 @@ pub struct Properties {
     pub align_y: ContainerAlignY,
     pub border_width: f32,
     pub border_radius: f32,
+    #[serde(with = "project_file::hex_color")]
     pub border_color: Color,
+    #[serde(with = "project_file::hex_color")]
     pub background_color: Color,
+    /// Linear gradient drawn instead of `background_color` while enabled
+    pub gradient_enabled: bool,
+    #[serde(with = "project_file::hex_color")]
+    pub gradient_start: Color,
+    #[serde(with = "project_file::hex_color")]
+    pub gradient_end: Color,
+    /// Degrees, as iced measures them: 0 runs bottom to top, 90 left to right
+    pub gradient_angle: f32,
+    /// Set on a Stack layer that floats over the others, pinned to this corner or the center
+    pub float_anchor: Option<FloatAnchor>,
+    /// Distance from the anchored edges, emitted as padding on the layer's wrapper
+    pub float_offset_x: f32,
+    pub float_offset_y: f32,
+    /// Set on a Column child that gets a button in the generated section nav
+    pub section_anchor: bool,
+    /// Nav button text, the widget's name when empty
+    pub section_name: String,
+    /// Set on the root: the generated app tracks its window width against `breakpoint_width`
+    pub breakpoint_enabled: bool,
+    pub breakpoint_width: f32,
+    /// Set on a Row that becomes a Column while the window is narrower than the breakpoint
+    pub stack_below_breakpoint: bool,
     pub has_shadow: bool,
+    #[serde(with = "project_file::vector")]
     pub shadow_offset: Vector,
     pub shadow_blur: f32,
+    #[serde(with = "project_file::hex_color")]
     pub shadow_color: Color,
     pub container_sizing_mode: ContainerSizingMode,
+    #[serde(with = "project_file::length")]
     pub container_center_length: Length,  // Used when center_x/y/both is active
 
     // Row wrapping
@@ -3449,17 +8731,24 @@ pub struct Properties {
     
     // Layout properties (Row/Column)
     pub spacing: f32,
+    #[serde(with = "project_file::alignment")]
     pub align_items: Alignment,
     
     // Text properties
     pub text_content: String,
     pub text_size: f32,
+    #[serde(with = "project_file::hex_color")]
     pub text_color: Color,
     pub font: FontType,
+    #[serde(with = "project_file::line_height")]
     pub line_height: text::LineHeight,
+    #[serde(with = "project_file::wrapping")]
     pub wrap: text::Wrapping,
+    #[serde(with = "project_file::shaping")]
     pub shaping: text::Shaping,
+    #[serde(with = "project_file::text_alignment")]
     pub text_align_x: text::Alignment,
+    #[serde(with = "project_file::vertical")]
     pub text_align_y: iced::alignment::Vertical,
     
     // Button properties
@@ -3477,6 +8766,7 @@ pub struct Properties {
     pub text_input_on_submit: bool,
     pub text_input_on_paste: bool,
     pub text_input_font: FontType,
+    #[serde(with = "project_file::line_height")]
     pub text_input_line_height: text::LineHeight,
     pub text_input_alignment: ContainerAlignX,
 //    pub text_input_icon: Option<Icon>,
@@ -3506,6 +8796,7 @@ pub struct Properties {
     pub progress_value: f32,
     pub progress_min: f32,
     pub progress_max: f32,
+    #[serde(with = "project_file::length")]
     pub progress_length: Length,
     pub progress_girth: f32,
     pub progress_vertical: bool,
@@ -3515,6 +8806,9 @@ pub struct Properties {
     pub toggler_label: String,
     pub toggler_size: f32,
     pub toggler_spacing: f32,
+
+    // Checkbox, Toggler and Radio label placement
+    pub label_position: LabelPosition,
     
     // PickList properties
     pub picklist_selected: Option<String>,
@@ -3522,8 +8816,11 @@ pub struct Properties {
     pub picklist_options: Vec<String>,
     
     // Scrollable properties
+    #[serde(with = "project_file::direction")]
     pub scroll_dir: iced::widget::scrollable::Direction,
+    #[serde(with = "project_file::anchor")]
     pub anchor_x: iced::widget::scrollable::Anchor,
+    #[serde(with = "project_file::anchor")]
     pub anchor_y: iced::widget::scrollable::Anchor,
 
     // Rule properties
@@ -3539,6 +8836,13 @@ pub struct Properties {
     // Svg prroperties
     pub svg_path: String,
     pub svg_fit: ContentFitChoice,
+    pub svg_tint: SvgTint,
+    #[serde(with = "project_file::hex_color")]
+    pub svg_tint_color: Color,
+
+    // Image + Svg properties
+    /// Library entry the path was picked from. Stale once the path is edited by hand.
+    pub library_asset: Option<Uuid>,
 
     // Tooltip properties
     pub tooltip_text: String,
@@ -3546,6 +8850,7 @@ pub struct Properties {
     pub tooltip_gap: f32,
 
     // ComboBox properties
+    #[serde(skip)]
     pub combobox_state: combo_box::State<String>,
     pub combobox_placeholder: String,
     pub combobox_selected: Option<String>,
@@ -3556,21 +8861,35 @@ pub struct Properties {
     pub combobox_use_on_option_hovered: bool,
     pub combobox_use_on_open: bool,
     pub combobox_use_on_close: bool,
+    #[serde(with = "project_file::option_hex_color")]
+    pub combobox_input_background: Option<Color>,
+    #[serde(with = "project_file::option_hex_color")]
+    pub combobox_input_text_color: Option<Color>,
+    #[serde(with = "project_file::option_hex_color")]
+    pub combobox_menu_background: Option<Color>,
+    pub combobox_menu_height: Option<f32>,
     pub referenced_enum: Option<Uuid>,
     
     // Markdown properties
+    #[serde(skip)]
     pub markdown_content: Vec<markdown::Item>,
+    #[serde(with = "project_file::editor_text")]
     pub markdown_source: text_editor::Content,
     pub markdown_text_size: f32,
+    /// Last edit of a long document that hasn't been parsed into `markdown_content` yet
+    #[serde(skip)]
+    pub markdown_edited_at: Option<std::time::Instant>,
 
     // QR Code properties
     pub qrcode_data: String,
     pub qrcode_cell_size: f32,
 
     // Themer properties
+    #[serde(with = "project_file::option_theme")]
     pub themer_theme: Option<Theme>,
 
     // Pin properties
+    #[serde(with = "project_file::point")]
     pub pin_point: Point,
     
     //Mouse_Area properties
@@ -3587,12 +8906,179 @@ pub struct Properties {
     pub mousearea_on_exit: bool,
     pub mousearea_interaction: Option<MouseInteraction>,
 
+    // File dialog capability, for Buttons and MouseArea presses
+    pub file_dialog: FileDialog,
+    /// Text that shows the last path chosen in the dialog
+    pub file_dialog_path_text: Option<WidgetId>,
+
+    // Conditional style, for Containers and TextInputs
+    /// Bool that switches the widget to its alternate colors while true
+    pub style_condition: Option<StyleCondition>,
+    #[serde(with = "project_file::hex_color")]
+    pub condition_border_color: Color,
+    /// Transparent keeps the base background
+    #[serde(with = "project_file::hex_color")]
+    pub condition_background_color: Color,
+    /// Preview value of a named flag. A Checkbox or Toggler condition follows that widget.
+    pub preview_condition: bool,
+
+    // Preview data: placeholder content for the live preview, never generated
+    /// TextInput text shown instead of its empty initial value
+    pub preview_text: Option<String>,
+    pub preview_progress: Option<f32>,
+    /// PickList or ComboBox option shown as selected
+    pub preview_selection: Option<String>,
+
+    /// Free-form labels for organizing the tree, never generated
+    pub tags: Vec<String>,
+
+    /// Container style from the project's registry. The look is mirrored in the fields above.
+    pub named_style: Option<Uuid>,
+
     pub show_widget_bounds: bool,
     pub widget_name: String,
+    #[serde(with = "project_file::option_length")]
     pub saved_height_before_scrollable: Option<Length>,
+    #[serde(with = "project_file::option_length")]
     pub saved_width_before_scrollable: Option<Length>,
 }
 
+/// Compares what the user set. State derived from it at runtime, like the parsed markdown
+/// or a combo box's filter, is left out.
+impl PartialEq for Properties {
+    fn eq(&self, other: &Self) -> bool {
+        // No `..`: a new field won't compile until it's compared or skipped here
+        let Properties {
+            width, height, max_width, max_height, clip, padding, widget_id, draft_fixed_width,
+            draft_fixed_height, draft_fill_portion_width, draft_fill_portion_height, padding_mode, align_x,
+            align_y, border_width, border_radius, border_color, background_color, gradient_enabled,
+            gradient_start, gradient_end, gradient_angle, float_anchor, float_offset_x, float_offset_y,
+            section_anchor, section_name, breakpoint_enabled, breakpoint_width, stack_below_breakpoint,
+            has_shadow, shadow_offset, shadow_blur, shadow_color, container_sizing_mode,
+            container_center_length, is_wrapping_row, wrapping_vertical_spacing, wrapping_align_x, spacing,
+            align_items, text_content, text_size, text_color, font, line_height, wrap, shaping, text_align_x,
+            text_align_y, button_style, button_on_press_maybe_enabled, button_on_press_with_enabled,
+            button_on_press_enabled, text_input_value, text_input_placeholder, text_input_size,
+            text_input_padding, is_secure, text_input_on_submit, text_input_on_paste, text_input_font,
+            text_input_line_height, text_input_alignment, checkbox_checked, checkbox_label, checkbox_size,
+            checkbox_spacing, radio_selected_index, radio_options, radio_label, radio_size, radio_spacing,
+            slider_value, slider_min, slider_max, slider_step, slider_width, slider_height, progress_value,
+            progress_min, progress_max, progress_length, progress_girth, progress_vertical, toggler_active,
+            toggler_label, toggler_size, toggler_spacing, label_position, picklist_selected,
+            picklist_placeholder, picklist_options, scroll_dir, anchor_x, anchor_y, rule_thickness,
+            orientation, image_path, image_fit, svg_path, svg_fit, svg_tint, svg_tint_color, library_asset,
+            tooltip_text, tooltip_position, tooltip_gap, combobox_state: _, combobox_placeholder,
+            combobox_selected, combobox_options, combobox_size, combobox_padding, combobox_use_on_input,
+            combobox_use_on_option_hovered, combobox_use_on_open, combobox_use_on_close,
+            combobox_input_background, combobox_input_text_color, combobox_menu_background,
+            combobox_menu_height, referenced_enum, markdown_content: _, markdown_source, markdown_text_size,
+            markdown_edited_at: _, qrcode_data, qrcode_cell_size, themer_theme, pin_point,
+            mousearea_on_press, mousearea_on_release, mousearea_on_double_click, mousearea_on_right_press,
+            mousearea_on_right_release, mousearea_on_middle_press, mousearea_on_middle_release,
+            mousearea_on_scroll, mousearea_on_enter, mousearea_on_move, mousearea_on_exit,
+            mousearea_interaction, file_dialog, file_dialog_path_text, style_condition,
+            condition_border_color, condition_background_color, preview_condition, preview_text,
+            preview_progress, preview_selection, tags, named_style, show_widget_bounds, widget_name,
+            saved_height_before_scrollable, saved_width_before_scrollable
+        } = self;
+        *width == other.width && *height == other.height && *max_width == other.max_width
+            && *max_height == other.max_height && *clip == other.clip && *padding == other.padding
+            && *widget_id == other.widget_id && *draft_fixed_width == other.draft_fixed_width
+            && *draft_fixed_height == other.draft_fixed_height
+            && *draft_fill_portion_width == other.draft_fill_portion_width
+            && *draft_fill_portion_height == other.draft_fill_portion_height
+            && *padding_mode == other.padding_mode && *align_x == other.align_x && *align_y == other.align_y
+            && *border_width == other.border_width && *border_radius == other.border_radius
+            && *border_color == other.border_color && *background_color == other.background_color
+            && *gradient_enabled == other.gradient_enabled && *gradient_start == other.gradient_start
+            && *gradient_end == other.gradient_end && *gradient_angle == other.gradient_angle
+            && *float_anchor == other.float_anchor && *float_offset_x == other.float_offset_x
+            && *float_offset_y == other.float_offset_y && *section_anchor == other.section_anchor
+            && *section_name == other.section_name && *breakpoint_enabled == other.breakpoint_enabled
+            && *breakpoint_width == other.breakpoint_width
+            && *stack_below_breakpoint == other.stack_below_breakpoint && *has_shadow == other.has_shadow
+            && *shadow_offset == other.shadow_offset && *shadow_blur == other.shadow_blur
+            && *shadow_color == other.shadow_color && *container_sizing_mode == other.container_sizing_mode
+            && *container_center_length == other.container_center_length
+            && *is_wrapping_row == other.is_wrapping_row
+            && *wrapping_vertical_spacing == other.wrapping_vertical_spacing
+            && *wrapping_align_x == other.wrapping_align_x && *spacing == other.spacing
+            && *align_items == other.align_items && *text_content == other.text_content
+            && *text_size == other.text_size && *text_color == other.text_color && *font == other.font
+            && *line_height == other.line_height && *wrap == other.wrap && *shaping == other.shaping
+            && *text_align_x == other.text_align_x && *text_align_y == other.text_align_y
+            && *button_style == other.button_style
+            && *button_on_press_maybe_enabled == other.button_on_press_maybe_enabled
+            && *button_on_press_with_enabled == other.button_on_press_with_enabled
+            && *button_on_press_enabled == other.button_on_press_enabled
+            && *text_input_value == other.text_input_value
+            && *text_input_placeholder == other.text_input_placeholder
+            && *text_input_size == other.text_input_size && *text_input_padding == other.text_input_padding
+            && *is_secure == other.is_secure && *text_input_on_submit == other.text_input_on_submit
+            && *text_input_on_paste == other.text_input_on_paste && *text_input_font == other.text_input_font
+            && *text_input_line_height == other.text_input_line_height
+            && *text_input_alignment == other.text_input_alignment
+            && *checkbox_checked == other.checkbox_checked && *checkbox_label == other.checkbox_label
+            && *checkbox_size == other.checkbox_size && *checkbox_spacing == other.checkbox_spacing
+            && *radio_selected_index == other.radio_selected_index && *radio_options == other.radio_options
+            && *radio_label == other.radio_label && *radio_size == other.radio_size
+            && *radio_spacing == other.radio_spacing && *slider_value == other.slider_value
+            && *slider_min == other.slider_min && *slider_max == other.slider_max
+            && *slider_step == other.slider_step && *slider_width == other.slider_width
+            && *slider_height == other.slider_height && *progress_value == other.progress_value
+            && *progress_min == other.progress_min && *progress_max == other.progress_max
+            && *progress_length == other.progress_length && *progress_girth == other.progress_girth
+            && *progress_vertical == other.progress_vertical && *toggler_active == other.toggler_active
+            && *toggler_label == other.toggler_label && *toggler_size == other.toggler_size
+            && *toggler_spacing == other.toggler_spacing && *label_position == other.label_position
+            && *picklist_selected == other.picklist_selected
+            && *picklist_placeholder == other.picklist_placeholder
+            && *picklist_options == other.picklist_options && *scroll_dir == other.scroll_dir
+            && *anchor_x == other.anchor_x && *anchor_y == other.anchor_y
+            && *rule_thickness == other.rule_thickness && *orientation == other.orientation
+            && *image_path == other.image_path && *image_fit == other.image_fit && *svg_path == other.svg_path
+            && *svg_fit == other.svg_fit && *svg_tint == other.svg_tint
+            && *svg_tint_color == other.svg_tint_color && *library_asset == other.library_asset
+            && *tooltip_text == other.tooltip_text && *tooltip_position == other.tooltip_position
+            && *tooltip_gap == other.tooltip_gap && *combobox_placeholder == other.combobox_placeholder
+            && *combobox_selected == other.combobox_selected && *combobox_options == other.combobox_options
+            && *combobox_size == other.combobox_size && *combobox_padding == other.combobox_padding
+            && *combobox_use_on_input == other.combobox_use_on_input
+            && *combobox_use_on_option_hovered == other.combobox_use_on_option_hovered
+            && *combobox_use_on_open == other.combobox_use_on_open
+            && *combobox_use_on_close == other.combobox_use_on_close
+            && *combobox_input_background == other.combobox_input_background
+            && *combobox_input_text_color == other.combobox_input_text_color
+            && *combobox_menu_background == other.combobox_menu_background
+            && *combobox_menu_height == other.combobox_menu_height
+            && *referenced_enum == other.referenced_enum
+            && markdown_source.text() == other.markdown_source.text()
+            && *markdown_text_size == other.markdown_text_size && *qrcode_data == other.qrcode_data
+            && *qrcode_cell_size == other.qrcode_cell_size && *themer_theme == other.themer_theme
+            && *pin_point == other.pin_point && *mousearea_on_press == other.mousearea_on_press
+            && *mousearea_on_release == other.mousearea_on_release
+            && *mousearea_on_double_click == other.mousearea_on_double_click
+            && *mousearea_on_right_press == other.mousearea_on_right_press
+            && *mousearea_on_right_release == other.mousearea_on_right_release
+            && *mousearea_on_middle_press == other.mousearea_on_middle_press
+            && *mousearea_on_middle_release == other.mousearea_on_middle_release
+            && *mousearea_on_scroll == other.mousearea_on_scroll
+            && *mousearea_on_enter == other.mousearea_on_enter
+            && *mousearea_on_move == other.mousearea_on_move && *mousearea_on_exit == other.mousearea_on_exit
+            && *mousearea_interaction == other.mousearea_interaction && *file_dialog == other.file_dialog
+            && *file_dialog_path_text == other.file_dialog_path_text
+            && *style_condition == other.style_condition
+            && *condition_border_color == other.condition_border_color
+            && *condition_background_color == other.condition_background_color
+            && *preview_condition == other.preview_condition && *preview_text == other.preview_text
+            && *preview_progress == other.preview_progress && *preview_selection == other.preview_selection
+            && *tags == other.tags && *named_style == other.named_style
+            && *show_widget_bounds == other.show_widget_bounds && *widget_name == other.widget_name
+            && *saved_height_before_scrollable == other.saved_height_before_scrollable
+            && *saved_width_before_scrollable == other.saved_width_before_scrollable
+    }
+}
+
 impl Default for Properties {
     fn default() -> Self {
         Self {
@@ -3617,6 +9103,18 @@ impl Default for Properties {
             border_radius: 5.0,
             border_color: Color::from_rgb(0.5, 0.5, 0.5),
             background_color: Color::from_rgba(0.0, 0.0, 0.0, 0.0),
+            gradient_enabled: false,
+            gradient_start: Color::from_rgb(0.33, 0.42, 0.95),
+            gradient_end: Color::from_rgb(0.62, 0.3, 0.85),
+            gradient_angle: 90.0,
+            float_anchor: None,
+            float_offset_x: 0.0,
+            float_offset_y: 0.0,
+            section_anchor: false,
+            section_name: String::new(),
+            breakpoint_enabled: false,
+            breakpoint_width: 900.0,
+            stack_below_breakpoint: false,
             has_shadow: false,
             shadow_offset: Vector::new(0.0, 2.0),
             shadow_blur: 5.0,
@@ -3677,7 +9175,7 @@ impl Default for Properties {
                 "Option 2".to_string(), 
                 "Option 3".to_string(),
             ],
-            radio_label: "Radio Option".to_string(),
+            radio_label: String::new(),
             radio_size: radio::Radio::<Theme>::DEFAULT_SIZE,
             radio_spacing: radio::Radio::<Theme>::DEFAULT_SPACING,
             
@@ -3703,6 +9201,8 @@ impl Default for Properties {
             toggler_label: "Toggle me".to_string(),
             toggler_size: toggler::Toggler::<Theme>::DEFAULT_SIZE,
             toggler_spacing: toggler::Toggler::<Theme>::DEFAULT_SIZE / 2.0,
+
+            label_position: LabelPosition::Right,
             
             // PickList defaults
             picklist_selected: None,
@@ -3731,6 +9231,10 @@ impl Default for Properties {
             // Svg defaults
             svg_path: String::new(),
             svg_fit: ContentFitChoice::Contain,
+            svg_tint: SvgTint::None,
+            svg_tint_color: Color::BLACK,
+
+            library_asset: None,
 
             // Tooltip defaults
             tooltip_text: "Tooltip".to_string(),
@@ -3757,12 +9261,17 @@ impl Default for Properties {
             combobox_use_on_close: false,
             combobox_size: 16.0,
             combobox_padding: 5.0,
+            combobox_input_background: None,
+            combobox_input_text_color: None,
+            combobox_menu_background: None,
+            combobox_menu_height: None,
             referenced_enum: None,
 
             // Markdown defaults
             markdown_content: Vec::new(),
             markdown_source: text_editor::Content::with_text(""),
             markdown_text_size: 16.0,
+            markdown_edited_at: None,
             
             // QR Code defaults
             qrcode_data: "https://example.com".to_string(),
@@ -3787,16 +9296,32 @@ impl Default for Properties {
             mousearea_on_move: false,
             mousearea_on_exit: false,
             mousearea_interaction: None,
+            file_dialog: FileDialog::None,
+            file_dialog_path_text: None,
+            style_condition: None,
+            condition_border_color: Color::from_rgb(0.9, 0.2, 0.2),
+            condition_background_color: Color::TRANSPARENT,
+            preview_condition: false,
 
             show_widget_bounds: false,
             widget_name: String::new(),
             saved_height_before_scrollable: None,
             saved_width_before_scrollable: None,
+            preview_text: None,
+            preview_progress: None,
+            preview_selection: None,
+            tags: Vec::new(),
+            named_style: None,
         }
     }
 }
 
 impl Properties {
+    fn reparse_markdown(&mut self) {
+        self.markdown_content = markdown::Content::parse(&self.markdown_source.text()).items().to_vec();
+        self.markdown_edited_at = None;
+    }
+
     pub fn for_widget_type(widget_type: WidgetType) -> Self {
         let mut props = Self::default();
         
@@ -3841,6 +9366,7 @@ impl Properties {
                     "Radio Option 2".to_string(),
                 ];
                 props.width = Length::Shrink;
+                props.orientation = Orientation::Vertical;
             }
             WidgetType::Toggler => {
                 props.toggler_label = "Toggle me".to_string();
@@ -3884,6 +9410,52 @@ impl Properties {
         
         props
     }
+
+    /// Whether the property behind `key` has been changed from the widget type's
+    /// default. Only covers keys the panel can flag as not yet generated.
+    /// Properties the live preview may show instead of the initial state; codegen never reads them
+    pub const PREVIEW_DATA_KEYS: &'static [&'static str] = &["preview_progress", "preview_selection", "preview_text"];
+    /// Properties that only organize the builder's tree; codegen never reads them
+    pub const ORGANIZING_KEYS: &'static [&'static str] = &["tags"];
+
+    /// TextInput value the live preview shows
+    pub fn shown_text_input_value(&self, preview_data: bool) -> &str {
+        match &self.preview_text {
+            Some(text) if preview_data => text,
+            _ => &self.text_input_value,
+        }
+    }
+
+    pub fn shown_progress_value(&self, preview_data: bool) -> f32 {
+        self.preview_progress.filter(|_| preview_data).unwrap_or(self.progress_value)
+    }
+
+    pub fn shown_picklist_selected(&self, preview_data: bool) -> Option<String> {
+        self.preview_selection.clone().filter(|_| preview_data).or_else(|| self.picklist_selected.clone())
+    }
+
+    pub fn shown_combobox_selected(&self, preview_data: bool) -> Option<&String> {
+        self.preview_selection.as_ref().filter(|_| preview_data).or(self.combobox_selected.as_ref())
+    }
+
+    pub fn differs_from_default(&self, widget_type: WidgetType, key: &str) -> bool {
+        let default = Self::for_widget_type(widget_type);
+        match key {
+            "text_color"         => self.text_color != default.text_color,
+            "font"               => self.font != default.font,
+            "line_height"        => self.line_height != default.line_height,
+            "wrap"               => self.wrap != default.wrap,
+            "shaping"            => self.shaping != default.shaping,
+            "text_align_x"       => self.text_align_x != default.text_align_x,
+            "text_align_y"       => self.text_align_y != default.text_align_y,
+            "anchor_x"           => self.anchor_x != default.anchor_x,
+            "anchor_y"           => self.anchor_y != default.anchor_y,
+            "markdown_text_size" => self.markdown_text_size != default.markdown_text_size,
+            "border_width"       => self.border_width != default.border_width,
+            "border_radius"      => self.border_radius != default.border_radius,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -3896,6 +9468,8 @@ pub struct CommonProperties {
     pub has_text_properties: bool,
     pub has_border: bool,
     pub has_background: bool,
+    /// Type of every selected widget, None for a mix of types
+    pub shared_type: Option<WidgetType>,
     
     // Store the actual values (if all widgets have same value)
     pub uniform_width: Option<Length>,
@@ -3967,6 +9541,7 @@ impl CommonProperties {
             has_text_properties,
             has_border: false,    // todo
             has_background: false, // todo
+            shared_type: Self::get_uniform_property(widgets, |w| w.widget_type),
             uniform_width,
             uniform_height,
             uniform_padding_mode,
@@ -4002,6 +9577,37 @@ impl CommonProperties {
 }
 
 // Display implementations
+impl WidgetType {
+    pub const ALL: [WidgetType; 26] = [
+        WidgetType::Container,
+        WidgetType::Scrollable,
+        WidgetType::Row,
+        WidgetType::Column,
+        WidgetType::Button,
+        WidgetType::Text,
+        WidgetType::TextInput,
+        WidgetType::Checkbox,
+        WidgetType::Radio,
+        WidgetType::Slider,
+        WidgetType::VerticalSlider,
+        WidgetType::ProgressBar,
+        WidgetType::Toggler,
+        WidgetType::PickList,
+        WidgetType::Space,
+        WidgetType::Rule,
+        WidgetType::Image,
+        WidgetType::Svg,
+        WidgetType::Tooltip,
+        WidgetType::ComboBox,
+        WidgetType::Markdown,
+        WidgetType::MouseArea,
+        WidgetType::QRCode,
+        WidgetType::Stack,
+        WidgetType::Themer,
+        WidgetType::Pin,
+    ];
+}
+
 impl std::fmt::Display for WidgetType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
@@ -4097,6 +9703,42 @@ impl std::fmt::Display for TextShaping {
     }
 }
 
+/// How a line height is given: a factor of the text size or a number of pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineHeightMode {
+    Relative,
+    Absolute,
+}
+
+impl LineHeightMode {
+    pub const ALL: [LineHeightMode; 2] = [LineHeightMode::Relative, LineHeightMode::Absolute];
+
+    pub fn of(line_height: text::LineHeight) -> Self {
+        match line_height {
+            text::LineHeight::Relative(_) => LineHeightMode::Relative,
+            text::LineHeight::Absolute(_) => LineHeightMode::Absolute,
+        }
+    }
+
+    /// `line_height` given this way, keeping the height it comes to at `text_size`
+    pub fn convert(self, line_height: text::LineHeight, text_size: f32) -> text::LineHeight {
+        let pixels = line_height.to_absolute(iced::Pixels(text_size)).0;
+        match self {
+            LineHeightMode::Relative => text::LineHeight::Relative(((pixels / text_size.max(1.0)) * 100.0).round() / 100.0),
+            LineHeightMode::Absolute => text::LineHeight::Absolute(iced::Pixels(pixels.round())),
+        }
+    }
+}
+
+impl std::fmt::Display for LineHeightMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineHeightMode::Relative => write!(f, "Relative"),
+            LineHeightMode::Absolute => write!(f, "Absolute"),
+        }
+    }
+}
+
 impl std::fmt::Display for Orientation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self { Orientation::Horizontal => write!(f, "Horizontal"),
@@ -4116,7 +9758,7 @@ impl std::fmt::Display for AlignText {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum AlignmentXOption {
     Start,
     Center,
@@ -4161,7 +9803,7 @@ impl From<AlignmentXOption> for Alignment {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum AlignmentYOption {
     Top,
     Center,
@@ -4244,7 +9886,7 @@ impl From<ContainerAlignY> for iced::alignment::Vertical {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum TextWrapping {
     None,
     Word,
@@ -4293,7 +9935,7 @@ impl From<TextWrapping> for text::Wrapping {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum TextShaping {
     Basic,
     Advanced,
@@ -4336,27 +9978,37 @@ impl From<TextShaping> for text::Shaping {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ContainerAlignX { Left, Center, Right }
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ContainerAlignY { Top, Center, Bottom }
 
 #[derive(Debug, Clone, Copy, PartialEq,)]
 pub enum RowColumnAlign { Start, Center, End }
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ButtonStyleType { Primary, Secondary, Success, Danger, Text }
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+impl ButtonStyleType {
+    pub const ALL: [ButtonStyleType; 5] = [
+        ButtonStyleType::Primary,
+        ButtonStyleType::Secondary,
+        ButtonStyleType::Success,
+        ButtonStyleType::Danger,
+        ButtonStyleType::Text,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum FontType { Default, Monospace }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Orientation { Horizontal, Vertical }
 
 
 
-#[derive(Debug, Clone, Copy, PartialEq,)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum AlignText {
     Default,
     Left,
@@ -4411,7 +10063,7 @@ impl From<AlignText> for iced::advanced::text::Alignment {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum DirChoice { Vertical, Horizontal, Both }
 impl std::fmt::Display for DirChoice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -4439,7 +10091,7 @@ impl DirChoice {
 }
 
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum AnchorChoice { Start, End }
 impl std::fmt::Display for AnchorChoice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -4481,7 +10133,19 @@ impl From<AnchorChoice> for iced::widget::scrollable::Anchor {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+/// How an svg is recolored. `ThemeText` follows the active theme's text color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SvgTint { None, ThemeText, Custom }
+impl SvgTint {
+    pub const ALL: [SvgTint; 3] = [SvgTint::None, SvgTint::ThemeText, SvgTint::Custom];
+}
+impl std::fmt::Display for SvgTint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self { SvgTint::None=>"None", SvgTint::ThemeText=>"Theme text color", SvgTint::Custom=>"Custom color" })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ContentFitChoice { Contain, Cover, Fill, ScaleDown, None }
 impl std::fmt::Display for ContentFitChoice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -4502,7 +10166,7 @@ impl From<ContentFitChoice> for ContentFit {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum TooltipPosition { Top, Bottom, Left, Right, FollowCursor }
 impl std::fmt::Display for TooltipPosition {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -4517,7 +10181,7 @@ impl From<TooltipPosition> for tooltip::Position {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ContainerSizingMode {
     Manual,     // User sets width/height separately
     CenterX,    // Use center_x(length)
@@ -4536,6 +10200,128 @@ impl std::fmt::Display for ContainerSizingMode {
     }
 }
 
+/// Which side of a checkbox, toggler or radio its label sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum LabelPosition {
+    Right,  // iced's own placement
+    Left,   // Label first, control pushed to the far end
+}
+
+impl LabelPosition {
+    pub const ALL: [LabelPosition; 2] = [LabelPosition::Right, LabelPosition::Left];
+}
+
+impl std::fmt::Display for LabelPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelPosition::Right => write!(f, "Right"),
+            LabelPosition::Left => write!(f, "Left"),
+        }
+    }
+}
+
+/// Where a floating Stack layer sits over the layers below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FloatAnchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+impl FloatAnchor {
+    pub const ALL: [FloatAnchor; 5] = [
+        FloatAnchor::TopLeft,
+        FloatAnchor::TopRight,
+        FloatAnchor::BottomLeft,
+        FloatAnchor::BottomRight,
+        FloatAnchor::Center,
+    ];
+
+    /// Horizontal and vertical alignment of the layer inside its full-size wrapper
+    pub fn alignment(self) -> (Alignment, Alignment) {
+        match self {
+            FloatAnchor::TopLeft => (Alignment::Start, Alignment::Start),
+            FloatAnchor::TopRight => (Alignment::End, Alignment::Start),
+            FloatAnchor::BottomLeft => (Alignment::Start, Alignment::End),
+            FloatAnchor::BottomRight => (Alignment::End, Alignment::End),
+            FloatAnchor::Center => (Alignment::Center, Alignment::Center),
+        }
+    }
+
+    /// Offsets as padding on the anchored edges. A centered layer has no edge to measure from.
+    pub fn padding(self, x: f32, y: f32) -> Padding {
+        let (align_x, align_y) = self.alignment();
+        let mut padding = Padding::ZERO;
+        match align_x {
+            Alignment::Start => padding.left = x,
+            Alignment::End => padding.right = x,
+            Alignment::Center => {}
+        }
+        match align_y {
+            Alignment::Start => padding.top = y,
+            Alignment::End => padding.bottom = y,
+            Alignment::Center => {}
+        }
+        padding
+    }
+
+    /// Short form for the tree row badge
+    pub fn badge(self) -> &'static str {
+        match self {
+            FloatAnchor::TopLeft => "TL",
+            FloatAnchor::TopRight => "TR",
+            FloatAnchor::BottomLeft => "BL",
+            FloatAnchor::BottomRight => "BR",
+            FloatAnchor::Center => "C",
+        }
+    }
+}
+
+impl std::fmt::Display for FloatAnchor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FloatAnchor::TopLeft => write!(f, "Top Left"),
+            FloatAnchor::TopRight => write!(f, "Top Right"),
+            FloatAnchor::BottomLeft => write!(f, "Bottom Left"),
+            FloatAnchor::BottomRight => write!(f, "Bottom Right"),
+            FloatAnchor::Center => write!(f, "Center"),
+        }
+    }
+}
+
+/// Native file dialog a Button or MouseArea press opens in the generated app
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FileDialog {
+    None,
+    Open,
+    Save,
+}
+
+impl FileDialog {
+    pub const ALL: [FileDialog; 3] = [FileDialog::None, FileDialog::Open, FileDialog::Save];
+}
+
+impl std::fmt::Display for FileDialog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileDialog::None => write!(f, "None"),
+            FileDialog::Open => write!(f, "Opens File Dialog"),
+            FileDialog::Save => write!(f, "Saves File Dialog"),
+        }
+    }
+}
+
+/// Bool a Container or TextInput's conditional style follows
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum StyleCondition {
+    /// Checked state of a Checkbox, or active state of a Toggler
+    Widget(WidgetId),
+    /// Named flag, generated as a bool state field of its own
+    Flag(String),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OnHandler {
     None,
@@ -4544,7 +10330,7 @@ pub enum OnHandler {
     OnActionMaybe,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq,)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum MouseInteraction {
     None,
     Idle,
@@ -4666,3 +10452,840 @@ impl MouseInteraction {
             Self::Help,
     ];
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_path_root() {
+        let hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        assert_eq!(hierarchy.find_path(WidgetId(0)), vec![WidgetId(0)]);
+    }
+
+    #[test]
+    fn test_find_path_nested() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let button_id = hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+
+        assert_eq!(
+            hierarchy.find_path(button_id),
+            vec![WidgetId(0), column_id, row_id, button_id]
+        );
+    }
+
+    #[test]
+    fn test_project_defaults_only_affect_new_widgets() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let mut defaults = ProjectDefaults::default();
+
+        let column_id = hierarchy.add_child_with_defaults(WidgetId(0), WidgetType::Column, &defaults).unwrap();
+        let before_id = hierarchy.add_child_with_defaults(column_id, WidgetType::Text, &defaults).unwrap();
+
+        defaults.text_size = 20.0;
+        defaults.layout_spacing = 8.0;
+        let after_id = hierarchy.add_child_with_defaults(column_id, WidgetType::Text, &defaults).unwrap();
+        let row_id = hierarchy.add_child_with_defaults(column_id, WidgetType::Row, &defaults).unwrap();
+
+        assert_eq!(hierarchy.get_widget_by_id(before_id).unwrap().properties.text_size, 16.0);
+        assert_eq!(hierarchy.get_widget_by_id(column_id).unwrap().properties.spacing, 0.0);
+        assert_eq!(hierarchy.get_widget_by_id(after_id).unwrap().properties.text_size, 20.0);
+        assert_eq!(hierarchy.get_widget_by_id(row_id).unwrap().properties.spacing, 8.0);
+    }
+
+    #[test]
+    fn test_padding_mode_transitions() {
+        let type_system = TypeSystem::new();
+        let mut props = Properties::default();
+
+        apply_property_change(&mut props, PropertyChange::PaddingMode(PaddingMode::Individual), &type_system);
+        apply_property_change(&mut props, PropertyChange::PaddingTop(4.0), &type_system);
+        apply_property_change(&mut props, PropertyChange::PaddingRight(8.0), &type_system);
+        apply_property_change(&mut props, PropertyChange::PaddingBottom(12.0), &type_system);
+        apply_property_change(&mut props, PropertyChange::PaddingLeft(16.0), &type_system);
+
+        // Individual -> Uniform collapses to the top value
+        apply_property_change(&mut props, PropertyChange::PaddingMode(next_padding_mode(PaddingMode::Individual)), &type_system);
+        assert_eq!(props.padding_mode, PaddingMode::Uniform);
+        assert_eq!(props.padding, Padding::new(4.0));
+
+        // Uniform -> Symmetric keeps top for vertical and left for horizontal
+        apply_property_change(&mut props, padding_side_change(PaddingMode::Symmetric, PaddingSide::Left, 10.0), &type_system);
+        assert_eq!(props.padding_mode, PaddingMode::Symmetric);
+        assert_eq!(props.padding, Padding { top: 4.0, right: 10.0, bottom: 4.0, left: 10.0 });
+
+        // Editing one side in Uniform mode sets all sides
+        apply_property_change(&mut props, padding_side_change(PaddingMode::Uniform, PaddingSide::Bottom, 6.0), &type_system);
+        assert_eq!(props.padding, Padding::new(6.0));
+    }
+
+    #[test]
+    fn test_long_markdown_parsed_after_pause() {
+        let type_system = TypeSystem::new();
+        let paste = |text: String| PropertyChange::MarkdownContent(text_editor::Action::Edit(
+            text_editor::Edit::Paste(std::sync::Arc::new(text))
+        ));
+
+        let mut short = Properties::default();
+        apply_property_change(&mut short, paste("# Title".to_string()), &type_system);
+        assert!(short.markdown_edited_at.is_none());
+        assert!(!short.markdown_content.is_empty());
+
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let markdown_id = hierarchy.add_child(column_id, WidgetType::Markdown).unwrap();
+        let long = "- item\n".repeat(MARKDOWN_DEBOUNCE_LINES + 1);
+        hierarchy.apply_property_change(markdown_id, paste(long), &type_system);
+        assert!(hierarchy.has_pending_markdown());
+
+        let edited_at = hierarchy.get_widget_by_id(markdown_id).unwrap().properties.markdown_edited_at.unwrap();
+        hierarchy.reparse_markdown(edited_at);
+        assert!(hierarchy.has_pending_markdown(), "parsed before the pause was over");
+
+        hierarchy.reparse_markdown(edited_at + MARKDOWN_DEBOUNCE);
+        assert!(!hierarchy.has_pending_markdown());
+        assert!(!hierarchy.get_widget_by_id(markdown_id).unwrap().properties.markdown_content.is_empty());
+    }
+
+    #[test]
+    fn test_find_path_missing() {
+        let hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        assert!(hierarchy.find_path(WidgetId(42)).is_empty());
+    }
+
+    #[test]
+    fn test_add_child_errors() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        assert_eq!(hierarchy.add_child(WidgetId(0), WidgetType::Button), Err(HierarchyError::RootConstraint));
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        assert_eq!(hierarchy.add_child(WidgetId(0), WidgetType::Row), Err(HierarchyError::RootConstraint));
+
+        let text_id = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        assert_eq!(
+            hierarchy.add_child(text_id, WidgetType::Button),
+            Err(HierarchyError::InvalidParent { parent: WidgetType::Text, child: WidgetType::Button })
+        );
+
+        let scroll_id = hierarchy.add_child(column_id, WidgetType::Scrollable).unwrap();
+        assert_eq!(
+            hierarchy.add_child(scroll_id, WidgetType::Button),
+            Err(HierarchyError::InvalidParent { parent: WidgetType::Scrollable, child: WidgetType::Button })
+        );
+
+        let tooltip_id = hierarchy.add_child(column_id, WidgetType::Tooltip).unwrap();
+        hierarchy.add_child(tooltip_id, WidgetType::Button).unwrap();
+        hierarchy.add_child(tooltip_id, WidgetType::Text).unwrap();
+        assert_eq!(
+            hierarchy.add_child(tooltip_id, WidgetType::Text),
+            Err(HierarchyError::ChildLimitExceeded { parent: WidgetType::Tooltip, max: 2 })
+        );
+
+        assert_eq!(hierarchy.add_child(WidgetId(99), WidgetType::Text), Err(HierarchyError::WidgetNotFound(WidgetId(99))));
+    }
+
+    #[test]
+    fn test_move_and_delete_errors() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let text_id = hierarchy.add_child(row_id, WidgetType::Text).unwrap();
+        let mouse_area_id = hierarchy.add_child(column_id, WidgetType::MouseArea).unwrap();
+        hierarchy.add_child(mouse_area_id, WidgetType::Button).unwrap();
+
+        assert_eq!(hierarchy.move_widget(column_id, row_id, 0), Err(HierarchyError::CycleDetected));
+        assert_eq!(hierarchy.move_widget(row_id, row_id, 0), Err(HierarchyError::CycleDetected));
+        assert_eq!(hierarchy.move_widget(WidgetId(0), column_id, 0), Err(HierarchyError::RootLocked));
+        assert_eq!(hierarchy.move_widget(text_id, WidgetId(0), 0), Err(HierarchyError::RootConstraint));
+        assert_eq!(
+            hierarchy.move_widget(text_id, mouse_area_id, 0),
+            Err(HierarchyError::ChildLimitExceeded { parent: WidgetType::MouseArea, max: 1 })
+        );
+        assert_eq!(hierarchy.move_widget(WidgetId(99), column_id, 0), Err(HierarchyError::WidgetNotFound(WidgetId(99))));
+        // A refused move leaves the tree as it was
+        assert_eq!(hierarchy.find_path(text_id), vec![WidgetId(0), column_id, row_id, text_id]);
+
+        assert_eq!(hierarchy.delete_widget(WidgetId(0)), Err(HierarchyError::RootLocked));
+        hierarchy.delete_widget(row_id).unwrap();
+        assert_eq!(hierarchy.delete_widget(text_id), Err(HierarchyError::WidgetNotFound(text_id)));
+    }
+
+    #[test]
+    fn test_wrap_errors() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let text_id = hierarchy.add_child(row_id, WidgetType::Text).unwrap();
+        let button_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+
+        hierarchy.set_selected_ids(HashSet::new());
+        assert_eq!(hierarchy.wrap_selected_in_container(WidgetType::Container), Err(HierarchyError::EmptySelection));
+        hierarchy.set_selected_ids(HashSet::from([WidgetId(0)]));
+        assert_eq!(hierarchy.wrap_selected_in_container(WidgetType::Container), Err(HierarchyError::RootLocked));
+        hierarchy.set_selected_ids(HashSet::from([text_id, button_id]));
+        assert_eq!(hierarchy.wrap_selected_in_container(WidgetType::Row), Err(HierarchyError::SelectionSpansParents));
+        hierarchy.set_selected_ids(HashSet::from([button_id]));
+        assert_eq!(
+            hierarchy.wrap_selected_in_container(WidgetType::Tooltip),
+            Err(HierarchyError::SelectionCount { container: WidgetType::Tooltip, needed: 2 })
+        );
+    }
+
+    #[test]
+    fn test_wrap_widget_in_place() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let first_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        let text_id = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        hierarchy.set_selected_ids(HashSet::from([text_id]));
+
+        assert_eq!(hierarchy.wrap_choices(text_id), [WidgetType::Container, WidgetType::Row, WidgetType::Column]);
+        let wrapper_id = hierarchy.wrap_widget(text_id, WidgetType::Container).unwrap();
+
+        let column = hierarchy.get_widget_by_id(column_id).unwrap();
+        assert_eq!(column.children.iter().map(|c| c.id).collect::<Vec<_>>(), [first_id, wrapper_id]);
+        assert_eq!(hierarchy.find_parent_id(text_id), Some(wrapper_id));
+        assert_eq!(hierarchy.selected_ids(), &HashSet::from([text_id]));
+        // The new id comes from the same counter add_child uses
+        assert_eq!(hierarchy.add_child(column_id, WidgetType::Text).unwrap(), WidgetId(wrapper_id.0 + 1));
+
+        assert_eq!(
+            hierarchy.wrap_widget(text_id, WidgetType::Scrollable),
+            Err(HierarchyError::InvalidParent { parent: WidgetType::Scrollable, child: WidgetType::Text })
+        );
+    }
+
+    #[test]
+    fn test_wrap_roots_only_child() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+
+        // The root keeps holding a single Column or Row
+        assert_eq!(hierarchy.wrap_choices(column_id), [WidgetType::Row, WidgetType::Column]);
+        assert_eq!(hierarchy.wrap_widget(column_id, WidgetType::Container), Err(HierarchyError::RootConstraint));
+        assert_eq!(hierarchy.wrap_widget(WidgetId(0), WidgetType::Row), Err(HierarchyError::RootLocked));
+
+        let row_id = hierarchy.wrap_widget(column_id, WidgetType::Row).unwrap();
+        assert_eq!(hierarchy.root().children.len(), 1);
+        assert_eq!(hierarchy.root().children[0].id, row_id);
+        assert_eq!(hierarchy.find_parent_id(column_id), Some(row_id));
+
+        assert_eq!(hierarchy.unwrap_widget(row_id), Ok(column_id));
+        assert_eq!(hierarchy.root().children[0].id, column_id);
+        assert!(!hierarchy.widget_exists(row_id));
+    }
+
+    #[test]
+    fn test_unwrap_widget() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let container_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        let text_id = hierarchy.add_child(container_id, WidgetType::Text).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        hierarchy.set_selected_ids(HashSet::from([container_id]));
+
+        assert_eq!(hierarchy.unwrap_widget(row_id), Err(HierarchyError::UnwrapChildCount { container: WidgetType::Row, count: 0 }));
+        assert_eq!(hierarchy.unwrap_widget(container_id), Ok(text_id));
+        let column = hierarchy.get_widget_by_id(column_id).unwrap();
+        assert_eq!(column.children.iter().map(|c| c.id).collect::<Vec<_>>(), [text_id, row_id]);
+        assert_eq!(hierarchy.selected_ids(), &HashSet::from([text_id]));
+
+        // Replacing the root's Column with a lone Text would break the root constraint
+        hierarchy.delete_widget(row_id).unwrap();
+        assert_eq!(hierarchy.unwrap_widget(column_id), Err(HierarchyError::RootConstraint));
+        assert!(hierarchy.widget_exists(column_id));
+    }
+
+    #[test]
+    fn test_duplicate_subtree_gets_fresh_ids() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let button_id = hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+        let label_id = hierarchy.add_child(row_id, WidgetType::Text).unwrap();
+        let combo_id = hierarchy.add_child(row_id, WidgetType::ComboBox).unwrap();
+        let after_id = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        hierarchy.get_widget_by_id_mut(button_id).unwrap().properties.file_dialog_path_text = Some(label_id);
+        hierarchy.get_widget_by_id_mut(combo_id).unwrap().properties.combobox_options = vec!["A".to_string(), "B".to_string()];
+
+        let copy_id = hierarchy.duplicate_subtree(row_id).unwrap();
+        let column = hierarchy.get_widget_by_id(column_id).unwrap();
+        assert_eq!(column.children.iter().map(|c| c.id).collect::<Vec<_>>(), [row_id, copy_id, after_id]);
+        assert_eq!(hierarchy.selected_ids(), &HashSet::from([copy_id]));
+
+        let copy = hierarchy.get_widget_by_id(copy_id).unwrap();
+        let copied: Vec<WidgetId> = copy.children.iter().map(|c| c.id).collect();
+        assert_eq!(copied.len(), 3);
+        assert!(copied.iter().all(|id| id.0 > after_id.0));
+        // The copied button shows its path in the copied text, not the original's
+        assert_eq!(copy.children[0].properties.file_dialog_path_text, Some(copied[1]));
+        assert_eq!(copy.children[2].properties.combobox_options, ["A", "B"]);
+        assert_eq!(
+            hierarchy.get_widget_by_id(button_id).unwrap().properties.file_dialog_path_text,
+            Some(label_id)
+        );
+    }
+
+    #[test]
+    fn test_duplicate_respects_child_limits() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let container_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        let text_id = hierarchy.add_child(container_id, WidgetType::Text).unwrap();
+
+        assert_eq!(
+            hierarchy.duplicate_subtree(text_id),
+            Err(HierarchyError::ChildLimitExceeded { parent: WidgetType::Container, max: 1 })
+        );
+        assert_eq!(hierarchy.duplicate_subtree(column_id), Err(HierarchyError::RootConstraint));
+        assert_eq!(hierarchy.duplicate_subtree(WidgetId(0)), Err(HierarchyError::RootLocked));
+        assert_eq!(hierarchy.get_widget_by_id(container_id).unwrap().children.len(), 1);
+    }
+
+    /// Root > [Column of 150 texts, Column of 250 texts]
+    fn long_tree() -> (WidgetHierarchy, WidgetId, WidgetId) {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let first = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let second = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        for (column_id, count) in [(first, 150), (second, 250)] {
+            for _ in 0..count {
+                hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+            }
+        }
+        (hierarchy, first, second)
+    }
+
+    #[test]
+    fn test_tree_window_covers_the_rows_in_view() {
+        let (hierarchy, first, second) = long_tree();
+        let mut rows = TreeRows::default();
+        rows.rebuild(hierarchy.root());
+        assert_eq!(rows.order.len(), 403);
+
+        // Ten rows tall, scrolled a hundred rows down
+        rows.viewport_height = 10.0 * TREE_ROW_HEIGHT;
+        rows.scroll_offset = 100.0 * TREE_ROW_HEIGHT;
+        let window = rows.window().unwrap();
+        assert_eq!(window.rows, 80..130);
+        // The root and the first column are built above the range to hold its rows
+        assert_eq!(window.top, 78.0 * TREE_ROW_HEIGHT);
+        assert_eq!(window.bottom, (403 - 130) as f32 * TREE_ROW_HEIGHT);
+        assert!(rows.reaches(&window, first));
+        assert!(!rows.reaches(&window, second));
+        let column = hierarchy.get_widget_by_id(first).unwrap();
+        assert!(!rows.reaches(&window, column.children[10].id));
+        assert!(rows.reaches(&window, column.children[100].id));
+
+        // Short trees are built whole
+        let (hierarchy, _) = selection_fixture();
+        let mut short = TreeRows::default();
+        short.rebuild(hierarchy.root());
+        assert_eq!(short.window(), None);
+    }
+
+    #[test]
+    fn test_collapsed_rows_leave_the_window() {
+        let (hierarchy, first, second) = long_tree();
+        let mut rows = TreeRows::default();
+        rows.collapsed.insert(first);
+        rows.rebuild(hierarchy.root());
+        assert_eq!(rows.order.len(), 253);
+        assert_eq!(rows.index_of[&second], 2);
+        let hidden = hierarchy.get_widget_by_id(first).unwrap().children[0].id;
+        assert!(!rows.index_of.contains_key(&hidden));
+
+        rows.viewport_height = 10.0 * TREE_ROW_HEIGHT;
+        let window = rows.window().unwrap();
+        assert_eq!(window.rows, 0..30);
+        assert_eq!(window.top, 0.0);
+        assert_eq!(window.bottom, (253 - 30) as f32 * TREE_ROW_HEIGHT);
+        let shown = hierarchy.get_widget_by_id(second).unwrap().children[20].id;
+        assert!(rows.reaches(&window, shown));
+    }
+
+    /// A column holding three texts, with the flattened tree order
+    fn selection_fixture() -> (WidgetHierarchy, Vec<WidgetId>) {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        for _ in 0..3 {
+            hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        }
+        let mut rows = TreeRows::default();
+        rows.rebuild(hierarchy.root());
+        (hierarchy, rows.order)
+    }
+
+    #[test]
+    fn test_shift_click_selects_range() {
+        let (_, order) = selection_fixture();
+        let (first, last) = (order[2], order[4]);
+        let mut focus = SelectionFocus::default();
+
+        let selected = focus.click(&HashSet::new(), HashSet::from([first]), &order, keyboard::Modifiers::empty());
+        assert_eq!(selected, HashSet::from([first]));
+
+        let selected = focus.click(&selected, HashSet::from([last]), &order, keyboard::Modifiers::SHIFT);
+        assert_eq!(selected, HashSet::from([order[2], order[3], order[4]]));
+        assert_eq!(focus.anchor, Some(first));
+        assert_eq!(focus.primary, Some(last));
+
+        // Shift-clicking back above the anchor shrinks the range towards it
+        let selected = focus.click(&selected, HashSet::from([order[1]]), &order, keyboard::Modifiers::SHIFT);
+        assert_eq!(selected, HashSet::from([order[1], order[2]]));
+    }
+
+    #[test]
+    fn test_ranges_skip_collapsed_rows_and_the_root() {
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = visualizer.hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let hidden: Vec<WidgetId> = (0..2)
+            .map(|_| visualizer.hierarchy.add_child(row_id, WidgetType::Text).unwrap())
+            .collect();
+        let button_id = visualizer.hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        visualizer.tree_rows.rebuild(visualizer.hierarchy.root());
+        visualizer.update(Message::TreeSetExpanded(row_id, false));
+        assert_eq!(visualizer.tree_rows.selectable(), [column_id, row_id, button_id]);
+
+        visualizer.update(Message::TreeSelect(HashSet::from([column_id.0])));
+        visualizer.modifiers = keyboard::Modifiers::SHIFT;
+        visualizer.update(Message::TreeSelect(HashSet::from([button_id.0])));
+        assert_eq!(*visualizer.hierarchy.selected_ids(), HashSet::from([column_id, row_id, button_id]));
+
+        // Shift+Up from the top row stops there rather than reaching the root
+        visualizer.modifiers = keyboard::Modifiers::empty();
+        visualizer.update(Message::TreeSelect(HashSet::from([column_id.0])));
+        visualizer.update(Message::ExtendSelection(-1));
+        assert_eq!(*visualizer.hierarchy.selected_ids(), HashSet::from([column_id]));
+
+        // The collapsed row joins a range without its hidden children
+        visualizer.update(Message::TreeSelect(HashSet::from([button_id.0])));
+        visualizer.update(Message::ExtendSelection(-1));
+        assert_eq!(*visualizer.hierarchy.selected_ids(), HashSet::from([row_id, button_id]));
+        assert!(hidden.iter().all(|id| !visualizer.hierarchy.selected_ids().contains(id)));
+    }
+
+    #[test]
+    fn test_ctrl_click_toggles() {
+        let (_, order) = selection_fixture();
+        let mut focus = SelectionFocus::default();
+        let previous = HashSet::from([order[2]]);
+
+        let selected = focus.click(&previous, HashSet::from([order[4]]), &order, keyboard::Modifiers::COMMAND);
+        assert_eq!(selected, HashSet::from([order[2], order[4]]));
+        assert_eq!(focus.primary, Some(order[4]));
+
+        // A tree that toggles on its own reports the set without the clicked row
+        let selected = focus.click(&selected, HashSet::from([order[2]]), &order, keyboard::Modifiers::COMMAND);
+        assert_eq!(selected, HashSet::from([order[2]]));
+        assert_eq!(focus.anchor, Some(order[4]));
+        assert_eq!(focus.primary, None);
+    }
+
+    #[test]
+    fn test_primary_selection_falls_back_to_tree_order() {
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let first = visualizer.hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        let second = visualizer.hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        visualizer.tree_rows.rebuild(visualizer.hierarchy.root());
+
+        visualizer.update(Message::SelectWidgets(HashSet::from([first.0, second.0])));
+        assert_eq!(visualizer.primary_selection(), Some(first));
+
+        visualizer.selection_focus.primary = Some(second);
+        assert_eq!(visualizer.primary_selection(), Some(second));
+
+        // A primary that is no longer selected is ignored
+        visualizer.update(Message::SelectWidgets(HashSet::from([first.0])));
+        visualizer.selection_focus.primary = Some(second);
+        assert_eq!(visualizer.primary_selection(), Some(first));
+    }
+
+    #[test]
+    fn test_arrow_keys_walk_the_tree() {
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = visualizer.hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let text_id = visualizer.hierarchy.add_child(row_id, WidgetType::Text).unwrap();
+        let button_id = visualizer.hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        visualizer.tree_rows.rebuild(visualizer.hierarchy.root());
+        visualizer.update(Message::SelectWidgets(HashSet::from([row_id.0])));
+
+        let press = |visualizer: &mut WidgetVisualizer, named, modifiers| {
+            visualizer.update(Message::KeyPressed(keyboard::Key::Named(named), modifiers));
+            visualizer.primary_selection()
+        };
+        use iced::keyboard::key::Named;
+        let none = keyboard::Modifiers::empty();
+        assert_eq!(press(&mut visualizer, Named::ArrowDown, none), Some(text_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowDown, none), Some(button_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowDown, none), Some(button_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowLeft, none), Some(column_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowRight, none), Some(row_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowUp, none), Some(column_id));
+
+        // Left collapses an expanded branch before going up, and the rows under it are skipped
+        press(&mut visualizer, Named::ArrowDown, none);
+        assert_eq!(press(&mut visualizer, Named::ArrowLeft, none), Some(row_id));
+        assert!(!visualizer.tree_rows.order.contains(&text_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowDown, none), Some(button_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowUp, none), Some(row_id));
+        // The top row stays selected rather than stepping onto the root
+        assert_eq!(press(&mut visualizer, Named::ArrowUp, none), Some(column_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowUp, none), Some(column_id));
+        // Right expands a collapsed branch before going down into it
+        press(&mut visualizer, Named::ArrowDown, none);
+        assert_eq!(press(&mut visualizer, Named::ArrowRight, none), Some(row_id));
+        assert!(visualizer.tree_rows.order.contains(&text_id));
+        assert_eq!(press(&mut visualizer, Named::ArrowRight, none), Some(text_id));
+
+        // Ctrl+Down reorders, and the generated code follows the new order
+        visualizer.update(Message::SelectWidgets(HashSet::from([row_id.0])));
+        press(&mut visualizer, Named::ArrowDown, keyboard::Modifiers::COMMAND);
+        let column = visualizer.hierarchy.get_widget_by_id(column_id).unwrap();
+        assert_eq!(column.children.iter().map(|c| c.id).collect::<Vec<_>>(), [button_id, row_id]);
+        let code = visualizer.full_code();
+        let source = code_generator::source_of(&code.tokens);
+        let view = &source[source.find("fn view").unwrap()..];
+        assert!(view.find("button(").unwrap() < view.find("row![").unwrap());
+    }
+
+    #[test]
+    fn test_moves_obey_the_new_parents_rules() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let container_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        hierarchy.add_child(container_id, WidgetType::Text).unwrap();
+        let scrollable_id = hierarchy.add_child(column_id, WidgetType::Scrollable).unwrap();
+        let text_id = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        let button_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+
+        assert_eq!(
+            hierarchy.move_widget(text_id, container_id, 1),
+            Err(HierarchyError::ChildLimitExceeded { parent: WidgetType::Container, max: 1 })
+        );
+        assert_eq!(
+            hierarchy.move_widget(button_id, scrollable_id, 0),
+            Err(HierarchyError::InvalidParent { parent: WidgetType::Scrollable, child: WidgetType::Button })
+        );
+        assert_eq!(hierarchy.find_parent_id(text_id), Some(column_id));
+        assert_eq!(hierarchy.find_parent_id(button_id), Some(column_id));
+
+        // Reordering under a full parent is still allowed
+        let tooltip_id = hierarchy.add_child(column_id, WidgetType::Tooltip).unwrap();
+        let first = hierarchy.add_child(tooltip_id, WidgetType::Text).unwrap();
+        let second = hierarchy.add_child(tooltip_id, WidgetType::Text).unwrap();
+        hierarchy.move_widget(second, tooltip_id, 0).unwrap();
+        let tooltip = hierarchy.get_widget_by_id(tooltip_id).unwrap();
+        assert_eq!(tooltip.children.iter().map(|c| c.id).collect::<Vec<_>>(), [second, first]);
+    }
+
+    #[test]
+    fn test_move_among_siblings_stops_at_the_ends() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let first = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        let second = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+
+        assert_eq!(hierarchy.move_among_siblings(first, -1), Ok(false));
+        assert_eq!(hierarchy.move_among_siblings(first, 1), Ok(true));
+        let column = hierarchy.get_widget_by_id(column_id).unwrap();
+        assert_eq!(column.children.iter().map(|c| c.id).collect::<Vec<_>>(), [second, first]);
+        assert_eq!(hierarchy.move_among_siblings(WidgetId(0), 1), Err(HierarchyError::RootLocked));
+    }
+
+    #[test]
+    fn test_extend_selection_keeps_anchor() {
+        let (_, order) = selection_fixture();
+        let mut focus = SelectionFocus::default();
+        focus.click(&HashSet::new(), HashSet::from([order[3]]), &order, keyboard::Modifiers::empty());
+
+        assert_eq!(focus.extend(1, &order), Some(HashSet::from([order[3], order[4]])));
+        assert_eq!(focus.extend(-1, &order), Some(HashSet::from([order[3]])));
+        assert_eq!(focus.extend(-1, &order), Some(HashSet::from([order[2], order[3]])));
+        assert_eq!(focus.anchor, Some(order[3]));
+
+        // Past the last row nothing changes
+        focus.click(&HashSet::new(), HashSet::from([order[4]]), &order, keyboard::Modifiers::empty());
+        assert_eq!(focus.extend(1, &order), None);
+        assert_eq!(focus.primary, Some(order[4]));
+    }
+
+    #[test]
+    fn test_batch_edit_is_one_undo_step() {
+        let mut visualizer = WidgetVisualizer::new();
+        // Built through update so the undo stack holds every step
+        visualizer.update(Message::AddChild(WidgetId(0), WidgetType::Column));
+        let column_id = visualizer.hierarchy.root().children[0].id;
+        for _ in 0..5 {
+            visualizer.update(Message::AddChild(column_id, WidgetType::Button));
+        }
+        let buttons: Vec<WidgetId> = visualizer.hierarchy.get_widget_by_id(column_id).unwrap().children.iter().map(|c| c.id).collect();
+        visualizer.update(Message::PropertyChanged(buttons[0], PropertyChange::Width(Length::Fixed(80.0))));
+        visualizer.update(Message::SelectWidgets(buttons.iter().map(|id| id.0).collect()));
+
+        let common = visualizer.hierarchy.common_properties.clone().unwrap();
+        assert_eq!(common.shared_type, Some(WidgetType::Button));
+        assert_eq!(common.uniform_width, None);
+
+        let steps = visualizer.undo.entries().len();
+        visualizer.update(Message::BatchPropertyChanged(PropertyChange::Width(Length::Fill)));
+        assert_eq!(visualizer.undo.entries().len(), steps + 1);
+        assert!(buttons.iter().all(|&id| visualizer.hierarchy.get_widget_by_id(id).unwrap().properties.width == Length::Fill));
+
+        visualizer.update(Message::Undo);
+        assert_eq!(visualizer.hierarchy.get_widget_by_id(buttons[0]).unwrap().properties.width, Length::Fixed(80.0));
+        assert_eq!(visualizer.hierarchy.get_widget_by_id(buttons[1]).unwrap().properties.width, Length::Shrink);
+    }
+
+    #[test]
+    fn test_padding_side_keeps_a_half_typed_number() {
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        visualizer.update(Message::PropertyChanged(column_id, PropertyChange::PaddingMode(PaddingMode::Individual)));
+        let before = visualizer.hierarchy.get_widget_by_id(column_id).unwrap().properties.padding;
+        let field = NumericField { widget: column_id, property: numeric_input::NumericProperty::Padding(PaddingSide::Left) };
+
+        // Nothing is applied while typing, so the trailing dot survives the next keystroke
+        visualizer.update(Message::NumericEdited(field, "1.".to_string()));
+        assert_eq!(visualizer.hierarchy.numeric_inputs().text(field, before.left), "1.");
+        assert_eq!(visualizer.hierarchy.get_widget_by_id(column_id).unwrap().properties.padding, before);
+
+        visualizer.update(Message::NumericEdited(field, "1.5".to_string()));
+        visualizer.update(Message::NumericCommitted(field));
+        let padding = visualizer.hierarchy.get_widget_by_id(column_id).unwrap().properties.padding;
+        assert_eq!((padding.left, padding.right), (1.5, before.right));
+    }
+
+    #[test]
+    fn test_batch_numeric_field_sets_every_selected_widget() {
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let sliders: Vec<WidgetId> = (0..3)
+            .map(|_| visualizer.hierarchy.add_child(column_id, WidgetType::Slider).unwrap())
+            .collect();
+        visualizer.update(Message::SelectWidgets(sliders.iter().map(|id| id.0).collect()));
+
+        let field = NumericField { widget: sliders[0], property: numeric_input::NumericProperty::SliderMax };
+        visualizer.update(Message::BatchNumericEdited(field, "250".to_string()));
+        visualizer.update(Message::BatchNumericCommitted(field));
+        assert!(sliders.iter().all(|&id| visualizer.hierarchy.get_widget_by_id(id).unwrap().properties.slider_max == 250.0));
+
+        // Mixed types get no type section
+        let text_id = visualizer.hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        visualizer.update(Message::SelectWidgets(HashSet::from([sliders[0].0, text_id.0])));
+        assert_eq!(visualizer.hierarchy.common_properties.as_ref().unwrap().shared_type, None);
+        assert!(visualizer.build_shared_type_controls().is_none());
+    }
+
+    #[test]
+    fn test_float_anchor_only_counts_inside_a_stack() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let stack_id = hierarchy.add_child(column_id, WidgetType::Stack).unwrap();
+        let layer_id = hierarchy.add_child(stack_id, WidgetType::Text).unwrap();
+        let loose_id = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        for id in [layer_id, loose_id] {
+            hierarchy.apply_property_change(id, PropertyChange::FloatAnchor(Some(FloatAnchor::BottomRight)), &type_system);
+        }
+
+        assert_eq!(hierarchy.float_anchor(layer_id), Some(FloatAnchor::BottomRight));
+        assert_eq!(hierarchy.float_anchor(loose_id), None);
+        assert_eq!(FloatAnchor::BottomRight.padding(4.0, 2.0), Padding { top: 0.0, right: 4.0, bottom: 2.0, left: 0.0 });
+    }
+
+    #[test]
+    fn test_code_panel_keeps_the_newest_of_two_rapid_runs() {
+        let mut visualizer = WidgetVisualizer::new();
+        let due = std::time::Instant::now() + code_generation::GENERATION_DEBOUNCE;
+        visualizer.update(Message::GenerateCode(due));
+        let first = std::sync::Arc::new(visualizer.full_code());
+
+        // A second edit starts a second run before the first one reports back
+        visualizer.update(Message::AppNameChanged("Renamed".to_string()));
+        visualizer.update(Message::GenerateCode(due + code_generation::GENERATION_DEBOUNCE));
+        let second = std::sync::Arc::new(visualizer.full_code());
+
+        visualizer.update(Message::CodeGenerated(2, Ok(second.clone())));
+        visualizer.update(Message::CodeGenerated(1, Ok(first)));
+        let shown = code_generator::source_of(&visualizer.code_generation.latest().unwrap().tokens);
+        let expected = code_generator::source_of(&second.tokens);
+        assert!(shown.contains("Renamed"));
+        assert_eq!(shown, expected);
+    }
+
+    #[test]
+    fn test_panicked_code_generation_waits_for_recovery() {
+        let mut visualizer = WidgetVisualizer::new();
+        let due = std::time::Instant::now() + code_generation::GENERATION_DEBOUNCE;
+        visualizer.update(Message::GenerateCode(due));
+        let caught = CaughtPanic {
+            panel: GuardedPanel::CodeGeneration,
+            message: "fixture".to_string(),
+            context: String::new(),
+        };
+        visualizer.update(Message::CodeGenerated(1, Err(caught)));
+        assert!(visualizer.panic_guards.tripped(GuardedPanel::CodeGeneration).is_some());
+        assert!(!visualizer.code_generation.is_generating());
+
+        // Edits don't start runs until the panel is recovered
+        visualizer.update(Message::AppNameChanged("Renamed".to_string()));
+        visualizer.update(Message::GenerateCode(due + code_generation::GENERATION_DEBOUNCE * 2));
+        assert!(visualizer.code_generation.has_pending());
+
+        visualizer.update(Message::RecoverPanel(GuardedPanel::CodeGeneration));
+        assert!(visualizer.panic_guards.tripped(GuardedPanel::CodeGeneration).is_none());
+    }
+
+    #[test]
+    fn test_property_edit_reaches_the_tree_and_code_without_further_input() {
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let rule_id = visualizer.hierarchy.add_child(column_id, WidgetType::Rule).unwrap();
+        visualizer.tree_rows.rebuild(visualizer.hierarchy.root());
+        let labels = |visualizer: &WidgetVisualizer| -> Vec<String> {
+            visualizer.tree_rows.order.iter()
+                .filter_map(|&id| visualizer.hierarchy.get_widget_by_id(id))
+                .map(|widget| visualizer.tree_row_label(widget))
+                .collect()
+        };
+        let before = visualizer.revision();
+
+        // The message a properties panel sends, whichever window it's drawn in
+        visualizer.update(Message::PropertyChanged(rule_id, PropertyChange::Orientation(Orientation::Vertical)));
+        assert!(visualizer.revision() > before);
+        assert!(labels(&visualizer).last().unwrap().ends_with("(Vertical)"));
+
+        // The debounce tick the subscription sends starts a run, which copies the tree as it is now
+        let due = std::time::Instant::now() + code_generation::GENERATION_DEBOUNCE;
+        assert!(matches!(visualizer.update(Message::GenerateCode(due)), Action::Run(_)));
+        let snapshot = GenerationSnapshot::new(&visualizer.hierarchy, &visualizer.type_system, visualizer.generation_settings());
+        visualizer.update(Message::CodeGenerated(1, Ok(std::sync::Arc::new(snapshot.generate()))));
+
+        let shown = code_generator::source_of(&visualizer.code_generation.latest().unwrap().tokens);
+        assert!(shown.contains("rule::vertical"), "{}", shown);
+        assert_eq!(visualizer.code_generation.shown_revision(), visualizer.revision());
+        assert!(!visualizer.code_generation.is_generating());
+    }
+
+    #[test]
+    fn test_comparing_with_a_project_file() {
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let contents = visualizer.project_file().to_json().unwrap();
+        visualizer.hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+
+        let path = std::path::PathBuf::from("saved.json");
+        visualizer.update(Message::ComparisonProjectOpened(Ok((path.clone(), contents))));
+        let comparison = visualizer.comparison_snapshot.as_ref().unwrap();
+        assert_eq!(comparison.source.as_ref(), Some(&path));
+        assert!(visualizer.show_comparison);
+        let entries = project_diff::diff(&comparison.root, visualizer.hierarchy.root());
+        assert!(!entries.is_empty());
+
+        // An unreadable file leaves the comparison as it was
+        visualizer.update(Message::ComparisonProjectOpened(Ok((std::path::PathBuf::from("bad.json"), "{".to_string()))));
+        assert_eq!(visualizer.comparison_snapshot.as_ref().unwrap().source.as_ref(), Some(&path));
+        assert!(visualizer.notification.as_ref().unwrap().starts_with("Couldn't compare with bad.json"));
+    }
+
+    #[test]
+    fn test_project_round_trips_through_a_file() {
+        let path = std::env::temp_dir().join(format!("theme_viewer_project_{}.json", std::process::id()));
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        visualizer.hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        visualizer.app_name = "Saved App".to_string();
+        visualizer.save_project(&path).unwrap();
+
+        let mut reopened = WidgetVisualizer::new();
+        reopened.load_project(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(reopened.app_name, "Saved App");
+        // Compared as saved, since colors are stored to 8 bits a channel
+        assert_eq!(
+            serde_json::to_value(reopened.hierarchy.root()).unwrap(),
+            serde_json::to_value(visualizer.hierarchy.root()).unwrap(),
+        );
+
+        // A missing file is reported and leaves the project open as it was
+        let error = reopened.load_project(&path).unwrap_err();
+        assert!(error.contains("Failed to read"), "{}", error);
+        assert_eq!(reopened.app_name, "Saved App");
+    }
+
+    #[test]
+    fn test_large_project_loads_off_the_update_thread() {
+        // Coarse, the update thread only copies or swaps in state while the tasks do the rest
+        const UPDATE_BUDGET: std::time::Duration = std::time::Duration::from_millis(500);
+        let path = std::env::temp_dir().join(format!("theme_viewer_stress_{}.json", std::process::id()));
+        let mut visualizer = WidgetVisualizer::new();
+        let column_id = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let leaf_types = [WidgetType::Text, WidgetType::Button, WidgetType::Checkbox, WidgetType::Markdown];
+        for _ in 0..100 {
+            let group_id = visualizer.hierarchy.add_child(column_id, WidgetType::Column).unwrap();
+            for i in 0..19 {
+                visualizer.hierarchy.add_child(group_id, leaf_types[i % leaf_types.len()]).unwrap();
+            }
+        }
+        assert!(undo::subtree_size(visualizer.hierarchy.root()) >= 2000);
+
+        let started = std::time::Instant::now();
+        assert!(matches!(visualizer.update(Message::SaveProject), Action::Run(_)));
+        assert!(started.elapsed() < UPDATE_BUDGET, "save held update for {:?}", started.elapsed());
+        // What the save task writes once a file is picked
+        std::fs::write(&path, visualizer.project_file().to_json().unwrap()).unwrap();
+
+        let mut reopened = WidgetVisualizer::new();
+        assert!(matches!(reopened.update(Message::ProjectChosen(Ok(path.clone()))), Action::Run(_)));
+        assert_eq!(reopened.loading_project.as_ref(), Some(&path));
+        // Edits wait for the project
+        reopened.update(Message::AddChild(WidgetId(0), WidgetType::Column));
+        assert!(reopened.hierarchy.root().children.is_empty());
+
+        // What the read task hands back
+        let loaded = project_file::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let started = std::time::Instant::now();
+        reopened.update(Message::ProjectOpened(Ok(std::sync::Arc::new(loaded))));
+        assert!(started.elapsed() < UPDATE_BUDGET, "load held update for {:?}", started.elapsed());
+        assert!(reopened.loading_project.is_none());
+        assert_eq!(
+            serde_json::to_value(reopened.hierarchy.root()).unwrap(),
+            serde_json::to_value(visualizer.hierarchy.root()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_notifications_are_kept_after_dismissal() {
+        let mut visualizer = WidgetVisualizer::new();
+        visualizer.update(Message::TakeComparisonSnapshot);
+        visualizer.update(Message::DismissNotification);
+        visualizer.update(Message::TakeComparisonSnapshot);
+        visualizer.update(Message::OpenHome);
+
+        // Shown twice with a dismissal between, so recorded twice; other updates add nothing
+        assert_eq!(visualizer.notification_history.len(), 2);
+        let entry = visualizer.notification_history.get(0).unwrap();
+        assert!(entry.copy_text().ends_with(" INFO Snapshot taken, later changes can be compared against it"));
+        assert!(matches!(
+            visualizer.update(Message::Notification(NotificationMessage::Copy(0))),
+            Action::Run(_)
+        ));
+    }
+
+    #[test]
+    fn test_hierarchy_error_wording() {
+        assert_eq!(
+            HierarchyError::ChildLimitExceeded { parent: WidgetType::Container, max: 1 }.to_string(),
+            "A Container can only hold one child"
+        );
+        assert_eq!(
+            HierarchyError::SelectionCount { container: WidgetType::Tooltip, needed: 2 }.to_string(),
+            "A Tooltip wraps exactly 2 widgets"
+        );
+    }
+}
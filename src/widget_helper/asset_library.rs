@@ -0,0 +1,361 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::widget_helper::{PropertyChange, Widget, WidgetId, WidgetType};
+
+pub const SVG_EXTENSIONS: &[&str] = &["svg"];
+pub const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp"];
+/// Every extension Add assets… accepts
+pub const ASSET_EXTENSIONS: &[&str] = &["svg", "png", "jpg", "jpeg", "gif", "bmp", "webp"];
+
+/// Folder of the exported project that library assets are copied into
+pub const EXPORT_DIR: &str = "assets";
+
+/// Which widget draws the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssetKind {
+    Svg,
+    Image,
+}
+
+impl AssetKind {
+    /// Kind of a file by its extension, None for files neither widget can draw
+    pub fn of_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        if SVG_EXTENSIONS.contains(&extension.as_str()) {
+            Some(Self::Svg)
+        } else if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+            Some(Self::Image)
+        } else {
+            None
+        }
+    }
+
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Svg => SVG_EXTENSIONS,
+            Self::Image => IMAGE_EXTENSIONS,
+        }
+    }
+}
+
+impl std::fmt::Display for AssetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Svg => "SVG",
+            Self::Image => "Image",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Asset {
+    pub id: Uuid,
+    /// Shown in the library and the pickers, the file stem until renamed
+    pub name: String,
+    /// Absolute, so the entry doesn't depend on the working directory
+    pub path: PathBuf,
+    pub kind: AssetKind,
+}
+
+impl Asset {
+    /// The path as an Image or Svg widget stores it
+    pub fn path_property(&self) -> String {
+        self.path.to_string_lossy().into_owned()
+    }
+
+    /// Points an Image or Svg widget at the entry
+    pub fn property_change(&self) -> PropertyChange {
+        match self.kind {
+            AssetKind::Svg => PropertyChange::SvgAsset(Some(self.id), self.path_property()),
+            AssetKind::Image => PropertyChange::ImageAsset(Some(self.id), self.path_property()),
+        }
+    }
+}
+
+/// Files picked once and reused by any number of Image and Svg widgets. Widgets keep the
+/// entry's id next to their path, so moving the entry moves every widget that uses it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssetLibrary {
+    assets: Vec<Asset>,
+    /// Entries whose file wasn't there at the last check
+    #[serde(skip)]
+    missing: HashSet<Uuid>,
+}
+
+impl AssetLibrary {
+    /// Entries in the order they were added
+    pub fn assets(&self) -> &[Asset] {
+        &self.assets
+    }
+
+    pub fn of_kind(&self, kind: AssetKind) -> impl Iterator<Item = &Asset> {
+        self.assets.iter().filter(move |asset| asset.kind == kind)
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.id == id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Adds an entry for each file a widget can draw, skipping files already in the library.
+    /// Returns how many were added.
+    pub fn add_files(&mut self, paths: impl IntoIterator<Item = PathBuf>) -> usize {
+        let mut added = 0;
+        for path in paths {
+            let Some(kind) = AssetKind::of_path(&path) else { continue };
+            let path = std::path::absolute(&path).unwrap_or(path);
+            if self.assets.iter().any(|asset| asset.path == path) {
+                continue;
+            }
+            let stem = path.file_stem().map_or_else(|| "asset".to_string(), |s| s.to_string_lossy().into_owned());
+            let asset = Asset {
+                id: Uuid::new_v4(),
+                name: self.free_name(&stem),
+                path,
+                kind,
+            };
+            if !asset.path.is_file() {
+                self.missing.insert(asset.id);
+            }
+            self.assets.push(asset);
+            added += 1;
+        }
+        added
+    }
+
+    /// `stem`, or `stem 2`, `stem 3`… when another entry already has that name
+    fn free_name(&self, stem: &str) -> String {
+        let taken = |name: &str| self.assets.iter().any(|asset| asset.name == name);
+        if !taken(stem) {
+            return stem.to_string();
+        }
+        (2..)
+            .map(|n| format!("{} {}", stem, n))
+            .find(|name| !taken(name))
+            .expect("some suffix is free")
+    }
+
+    pub fn rename(&mut self, id: Uuid, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("An asset needs a name".to_string());
+        }
+        if self.assets.iter().any(|asset| asset.id != id && asset.name == name) {
+            return Err(format!("Another asset is already called {}", name));
+        }
+        let asset = self.assets.iter_mut().find(|asset| asset.id == id).ok_or("Asset not found")?;
+        asset.name = name.to_string();
+        Ok(())
+    }
+
+    /// Widgets keep the path they were given, only the entry goes
+    pub fn remove(&mut self, id: Uuid) -> Option<Asset> {
+        let index = self.assets.iter().position(|asset| asset.id == id)?;
+        self.missing.remove(&id);
+        Some(self.assets.remove(index))
+    }
+
+    /// Points the entry at another file of the same kind. Returns the previous path, which
+    /// the widgets still using the entry are moved off.
+    pub fn relink(&mut self, id: Uuid, path: PathBuf) -> Result<PathBuf, String> {
+        let path = std::path::absolute(&path).unwrap_or(path);
+        if self.assets.iter().any(|asset| asset.id != id && asset.path == path) {
+            return Err(format!("{} is already in the library", path.display()));
+        }
+        let asset = self.assets.iter_mut().find(|asset| asset.id == id).ok_or("Asset not found")?;
+        if AssetKind::of_path(&path) != Some(asset.kind) {
+            return Err(format!("{} is not a {} file", path.display(), asset.kind));
+        }
+        let previous = std::mem::replace(&mut asset.path, path);
+        if asset.path.is_file() {
+            self.missing.remove(&id);
+        } else {
+            self.missing.insert(id);
+        }
+        Ok(previous)
+    }
+
+    /// Looks for every entry's file again
+    pub fn check_files(&mut self) {
+        self.missing = self.assets.iter()
+            .filter(|asset| !asset.path.is_file())
+            .map(|asset| asset.id)
+            .collect();
+    }
+
+    pub fn is_missing(&self, id: Uuid) -> bool {
+        self.missing.contains(&id)
+    }
+
+    pub fn missing_count(&self) -> usize {
+        self.missing.len()
+    }
+
+    /// The entry a widget's path came from. None when there is none, or once the path
+    /// was edited by hand and no longer matches it.
+    pub fn linked(&self, id: Option<Uuid>, path: &str) -> Option<&Asset> {
+        self.get(id?).filter(|asset| asset.path_property() == path)
+    }
+
+    /// Where the exported project keeps the entry's file: `assets/<file name>`, numbered
+    /// when an earlier entry has the same file name
+    pub fn export_path(&self, id: Uuid) -> Option<String> {
+        let index = self.assets.iter().position(|asset| asset.id == id)?;
+        let asset = &self.assets[index];
+        let file_name = asset.path.file_name()?;
+        let clashes = self.assets[..index].iter()
+            .filter(|earlier| earlier.path.file_name() == Some(file_name))
+            .count();
+        if clashes == 0 {
+            return Some(format!("{}/{}", EXPORT_DIR, file_name.to_string_lossy()));
+        }
+        let stem = asset.path.file_stem().unwrap_or(file_name).to_string_lossy();
+        Some(match asset.path.extension() {
+            Some(extension) => format!("{}/{}-{}.{}", EXPORT_DIR, stem, clashes + 1, extension.to_string_lossy()),
+            None => format!("{}/{}-{}", EXPORT_DIR, stem, clashes + 1),
+        })
+    }
+}
+
+/// Path a widget loads, if it's an Image or Svg
+pub fn widget_path(widget: &Widget) -> Option<&str> {
+    match widget.widget_type {
+        WidgetType::Image => Some(&widget.properties.image_path),
+        WidgetType::Svg => Some(&widget.properties.svg_path),
+        _ => None,
+    }
+}
+
+/// Widgets, in tree order, whose path is the entry's `path`
+pub fn linked_widgets(root: &Widget, id: Uuid, path: &str) -> Vec<WidgetId> {
+    fn walk(widget: &Widget, id: Uuid, path: &str, found: &mut Vec<WidgetId>) {
+        if widget.properties.library_asset == Some(id) && widget_path(widget) == Some(path) {
+            found.push(widget.id);
+        }
+        for child in &widget.children {
+            walk(child, id, path, found);
+        }
+    }
+
+    let mut found = Vec::new();
+    walk(root, id, path, &mut found);
+    found
+}
+
+/// Name being typed for an entry, kept apart until it's committed
+#[derive(Debug, Clone)]
+pub struct AssetRename {
+    pub id: Uuid,
+    pub name: String,
+    /// Why the last commit was refused
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum AssetMessage {
+    Open,
+    AddFiles,
+    AddFolder,
+    Added(Result<Vec<PathBuf>, String>),
+    StartRename(Uuid),
+    RenameEdited(String),
+    CommitRename,
+    CancelRename,
+    Remove(Uuid),
+    Relink(Uuid),
+    Relinked(Uuid, Result<PathBuf, String>),
+    CheckFiles,
+    /// Sets a widget's path to the entry
+    Use(WidgetId, Uuid),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::WidgetHierarchy;
+    use crate::widget_helper::type_system::TypeSystem;
+
+    fn library(paths: &[&str]) -> AssetLibrary {
+        let mut library = AssetLibrary::default();
+        library.add_files(paths.iter().map(PathBuf::from));
+        library
+    }
+
+    fn id_of(library: &AssetLibrary, name: &str) -> Uuid {
+        library.assets().iter().find(|asset| asset.name == name).unwrap().id
+    }
+
+    #[test]
+    fn test_adding_names_entries_by_stem_and_skips_what_it_cant_draw() {
+        let mut library = library(&["/icons/home.svg", "/photos/home.PNG", "/notes/readme.txt"]);
+        let kinds: Vec<(&str, AssetKind)> = library.assets().iter().map(|a| (a.name.as_str(), a.kind)).collect();
+        assert_eq!(kinds, [("home", AssetKind::Svg), ("home 2", AssetKind::Image)]);
+
+        // The same file twice is one entry
+        assert_eq!(library.add_files([PathBuf::from("/icons/home.svg")]), 0);
+        assert!(library.is_missing(id_of(&library, "home")));
+    }
+
+    #[test]
+    fn test_rename_refuses_blank_and_taken_names() {
+        let mut library = library(&["/icons/home.svg", "/icons/gear.svg"]);
+        let home = id_of(&library, "home");
+        assert!(library.rename(home, "  ").is_err());
+        assert!(library.rename(home, "gear").is_err());
+        library.rename(home, " House ").unwrap();
+        assert_eq!(library.get(home).unwrap().name, "House");
+
+        assert_eq!(library.remove(home).unwrap().name, "House");
+        assert!(library.get(home).is_none());
+    }
+
+    #[test]
+    fn test_relink_moves_the_widgets_still_on_the_entry() {
+        let type_system = TypeSystem::new();
+        let mut library = library(&["/icons/home.svg", "/photos/cat.png"]);
+        let home = id_of(&library, "home");
+        let old_path = library.get(home).unwrap().path_property();
+
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        let picked = hierarchy.add_child(WidgetId(0), WidgetType::Svg).unwrap();
+        let typed = hierarchy.add_child(WidgetId(0), WidgetType::Svg).unwrap();
+        for id in [picked, typed] {
+            hierarchy.apply_property_change(id, PropertyChange::SvgAsset(Some(home), old_path.clone()), &type_system);
+        }
+        // Editing the path by hand leaves the entry behind
+        hierarchy.apply_property_change(typed, PropertyChange::SvgPath("/other/home.svg".to_string()), &type_system);
+        assert_eq!(linked_widgets(hierarchy.root(), home, &old_path), [picked]);
+
+        assert!(library.relink(home, PathBuf::from("/photos/dog.png")).is_err());
+        assert_eq!(library.relink(home, PathBuf::from("/icons/house.svg")).unwrap(), PathBuf::from(&old_path));
+        assert!(library.linked(Some(home), &old_path).is_none());
+        let new_path = library.get(home).unwrap().path_property();
+        assert!(new_path.ends_with("house.svg"));
+    }
+
+    #[test]
+    fn test_export_paths_number_clashing_file_names() {
+        let library = library(&["/a/logo.svg", "/b/logo.svg", "/c/logo.png"]);
+        let paths: Vec<String> = library.assets().iter().map(|a| library.export_path(a.id).unwrap()).collect();
+        assert_eq!(paths, ["assets/logo.svg", "assets/logo-2.svg", "assets/logo.png"]);
+    }
+
+    #[test]
+    fn test_library_round_trips_through_json() {
+        let library = library(&["/icons/home.svg", "/photos/cat.png"]);
+        let json = serde_json::to_string(&library).unwrap();
+        let mut loaded: AssetLibrary = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.assets(), library.assets());
+
+        // Missing files are found again after loading, not saved
+        assert_eq!(loaded.missing_count(), 0);
+        loaded.check_files();
+        assert_eq!(loaded.missing_count(), 2);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::widget_helper::*;
+use crate::widget_helper::code_generator::{collect_tree_order, resolve_names, widget_base_name, widget_type_name};
+
+/// Widest zero padding offered for `{n}`
+pub const MAX_ZERO_PAD: usize = 4;
+
+/// How each renamed widget's new name is built. `{n}` is the widget's 1-based position among the
+/// renamed ones (offset so the first gets `start`), `{type}` its type and `{old}` its current name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePattern {
+    pub pattern: String,
+    pub start: usize,
+    /// Digits `{n}` is padded to with leading zeros, 0 for none
+    pub zero_pad: usize,
+}
+
+impl RenamePattern {
+    /// Name for the `index`-th renamed widget, counting from 0
+    pub fn expand(&self, index: usize, widget_type: WidgetType, old: &str) -> String {
+        let n = format!("{:0width$}", self.start + index, width = self.zero_pad);
+        self.pattern
+            .replace("{n}", &n)
+            .replace("{type}", &widget_type_name(widget_type))
+            .replace("{old}", old)
+    }
+}
+
+/// One row of the preview: what a widget is called now and will be called after Apply
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePreview {
+    pub id: WidgetId,
+    /// Name in the generated code before the rename
+    pub old_name: String,
+    /// Expanded pattern, stored as the widget's custom name
+    pub new_name: String,
+    /// Name in the generated code after the rename, sanitized and made unique
+    pub final_name: String,
+    /// Another widget already has the name, so `final_name` carries a number
+    pub collides: bool,
+}
+
+/// Previews renaming `targets` with `pattern`. `widgets` is the whole tree in order as
+/// id, type and custom name, since a new name can collide with any widget's.
+/// `{n}` counts the targets in tree order, whatever order they were selected in.
+pub fn preview(
+    widgets: &[(WidgetId, WidgetType, &str)],
+    targets: &[WidgetId],
+    pattern: &RenamePattern,
+) -> Result<Vec<RenamePreview>, String> {
+    if pattern.pattern.trim().is_empty() {
+        return Err("Enter a pattern".to_string());
+    }
+
+    let old_names = resolve_names(widgets.iter().map(|&(_, widget_type, name)| (widget_type, name)));
+
+    let mut index = 0;
+    let new_names: Vec<Option<String>> = widgets.iter().zip(&old_names)
+        .map(|(&(id, widget_type, _), old)| {
+            targets.contains(&id).then(|| {
+                let name = pattern.expand(index, widget_type, old);
+                index += 1;
+                name
+            })
+        })
+        .collect();
+
+    let final_names = resolve_names(widgets.iter().zip(&new_names).map(|(&(_, widget_type, name), new)| {
+        (widget_type, new.as_deref().unwrap_or(name))
+    }));
+
+    Ok(widgets.iter()
+        .zip(old_names)
+        .zip(new_names)
+        .zip(final_names)
+        .filter_map(|((((id, widget_type, _), old_name), new_name), final_name)| {
+            let new_name = new_name?;
+            Some(RenamePreview {
+                id: *id,
+                collides: final_name != widget_base_name(*widget_type, &new_name),
+                old_name,
+                new_name,
+                final_name,
+            })
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone)]
+pub enum BulkRenameEdit {
+    Pattern(String),
+    Start(String),
+    ZeroPad(usize),
+}
+
+/// Options of the bulk rename dialog, which renames the current multi-selection
+#[derive(Debug, Clone)]
+pub struct BulkRename {
+    pub pattern: String,
+    /// Kept as typed, so the field can be cleared and retyped
+    pub start: String,
+    pub zero_pad: usize,
+}
+
+impl Default for BulkRename {
+    fn default() -> Self {
+        Self {
+            pattern: "{type}_{n}".to_string(),
+            start: "1".to_string(),
+            zero_pad: 0,
+        }
+    }
+}
+
+impl BulkRename {
+    pub fn edit(&mut self, edit: BulkRenameEdit) {
+        match edit {
+            BulkRenameEdit::Pattern(pattern) => self.pattern = pattern,
+            BulkRenameEdit::Start(start) => self.start = start,
+            BulkRenameEdit::ZeroPad(zero_pad) => self.zero_pad = zero_pad.min(MAX_ZERO_PAD),
+        }
+    }
+
+    pub fn rename_pattern(&self) -> Result<RenamePattern, String> {
+        let start = self.start.trim().parse::<usize>()
+            .map_err(|_| "Start index must be a whole number".to_string())?;
+        Ok(RenamePattern { pattern: self.pattern.clone(), start, zero_pad: self.zero_pad })
+    }
+
+    /// Preview of renaming the hierarchy's selected widgets
+    pub fn preview(&self, hierarchy: &WidgetHierarchy) -> Result<Vec<RenamePreview>, String> {
+        let mut widgets = Vec::new();
+        collect_tree_order(hierarchy.root(), &mut widgets);
+        let targets: Vec<WidgetId> = hierarchy.selected_ids().iter().copied().collect();
+        let widgets: Vec<_> = widgets.iter()
+            .map(|w| (w.id, w.widget_type, w.properties.widget_name.as_str()))
+            .collect();
+        preview(&widgets, &targets, &self.rename_pattern()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(pattern: &str) -> RenamePattern {
+        RenamePattern { pattern: pattern.to_string(), start: 1, zero_pad: 0 }
+    }
+
+    /// A container and column, then the given widgets, all selected except the first two
+    fn tree(widgets: &[(WidgetType, &'static str)]) -> (Vec<(WidgetId, WidgetType, &'static str)>, Vec<WidgetId>) {
+        let mut all = vec![(WidgetId(0), WidgetType::Container, ""), (WidgetId(1), WidgetType::Column, "")];
+        all.extend(widgets.iter().enumerate().map(|(i, &(widget_type, name))| (WidgetId(i + 2), widget_type, name)));
+        let targets = (2..all.len()).map(WidgetId).collect();
+        (all, targets)
+    }
+
+    fn final_names(rows: &[RenamePreview]) -> Vec<&str> {
+        rows.iter().map(|row| row.final_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_placeholders_expand() {
+        let p = RenamePattern { pattern: "{type}_{old}_{n}".to_string(), start: 0, zero_pad: 3 };
+        assert_eq!(p.expand(7, WidgetType::TextInput, "email"), "text_input_email_007");
+        // Unknown placeholders and stray braces are kept as written
+        assert_eq!(pattern("{x}_{n").expand(0, WidgetType::Button, "a"), "{x}_{n");
+    }
+
+    #[test]
+    fn test_numbered_pattern_renames_duplicates() {
+        let (widgets, targets) = tree(&[(WidgetType::Button, ""); 3]);
+        let rows = preview(&widgets, &targets, &pattern("nav_{n}")).unwrap();
+        assert_eq!(rows.iter().map(|r| r.old_name.as_str()).collect::<Vec<_>>(), ["button", "button_2", "button_3"]);
+        assert_eq!(final_names(&rows), ["nav_1", "nav_2", "nav_3"]);
+        assert!(rows.iter().all(|row| !row.collides));
+    }
+
+    #[test]
+    fn test_empty_pattern_is_refused() {
+        let (widgets, targets) = tree(&[(WidgetType::Button, "save")]);
+        assert!(preview(&widgets, &targets, &pattern("")).is_err());
+        assert!(preview(&widgets, &targets, &pattern("   ")).is_err());
+    }
+
+    #[test]
+    fn test_pattern_without_n_collides() {
+        let (widgets, targets) = tree(&[(WidgetType::Button, ""), (WidgetType::Checkbox, ""), (WidgetType::Button, "")]);
+        let rows = preview(&widgets, &targets, &pattern("Nav")).unwrap();
+        assert_eq!(final_names(&rows), ["nav", "nav_2", "nav_3"]);
+        assert_eq!(rows.iter().map(|row| row.collides).collect::<Vec<_>>(), [false, true, true]);
+    }
+
+    #[test]
+    fn test_collision_with_unselected_widget() {
+        let (widgets, mut targets) = tree(&[(WidgetType::Text, "title"), (WidgetType::Button, "")]);
+        targets.remove(0);
+        let rows = preview(&widgets, &targets, &pattern("title")).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].final_name, "title_2");
+        assert!(rows[0].collides);
+    }
+
+    #[test]
+    fn test_unicode_names_are_sanitized() {
+        let (widgets, targets) = tree(&[(WidgetType::Text, "Größe"), (WidgetType::Text, "名前")]);
+        let rows = preview(&widgets, &targets, &pattern("{old} Ä{n}")).unwrap();
+        assert_eq!(rows[0].new_name, "größe Ä1");
+        assert_eq!(final_names(&rows), ["größe_ä1", "名前_ä2"]);
+    }
+
+    #[test]
+    fn test_start_index_must_be_a_number() {
+        let mut rename = BulkRename::default();
+        rename.edit(BulkRenameEdit::Start("x".to_string()));
+        assert!(rename.rename_pattern().is_err());
+        rename.edit(BulkRenameEdit::Start(" 5".to_string()));
+        rename.edit(BulkRenameEdit::ZeroPad(9));
+        assert_eq!(rename.rename_pattern().unwrap().start, 5);
+        assert_eq!(rename.zero_pad, MAX_ZERO_PAD);
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+
+use iced::Font;
+use serde::{Deserialize, Serialize};
+
+/// Families tried when nothing else is saved. `monospace` stands for `Font::MONOSPACE`.
+pub const DEFAULT_FAMILIES: &str = "JetBrains Mono, Fira Code, Consolas, Menlo, monospace";
+
+/// The generic entry that ends the search on `Font::MONOSPACE`
+const GENERIC_MONOSPACE: &str = "monospace";
+
+/// Font the code view is set in. `Font::MONOSPACE` alone can land on a proportional face on
+/// some Linux setups, which breaks the indentation, so families are tried by name first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodeFontSettings {
+    /// Families in order of preference, comma separated
+    pub families: String,
+    pub size: CodeFontSize,
+}
+
+impl Default for CodeFontSettings {
+    fn default() -> Self {
+        Self {
+            families: DEFAULT_FAMILIES.to_string(),
+            size: CodeFontSize::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CodeFontSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl CodeFontSize {
+    pub const ALL: [Self; 3] = [Self::Small, Self::Medium, Self::Large];
+
+    pub fn px(self) -> f32 {
+        match self {
+            Self::Small => 12.0,
+            Self::Medium => 14.0,
+            Self::Large => 16.0,
+        }
+    }
+}
+
+impl std::fmt::Display for CodeFontSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.px())
+    }
+}
+
+/// Font and size the code view text is drawn with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CodeViewFont {
+    pub font: Font,
+    pub size: f32,
+}
+
+impl Default for CodeViewFont {
+    fn default() -> Self {
+        Self { font: Font::MONOSPACE, size: CodeFontSize::default().px() }
+    }
+}
+
+impl CodeViewFont {
+    /// `family` from `pick_family`, or the default monospace font when there is none
+    pub fn new(family: Option<&'static str>, size: CodeFontSize) -> Self {
+        let font = match family {
+            Some(name) => Font::with_name(name),
+            None => Font::MONOSPACE,
+        };
+        Self { font, size: size.px() }
+    }
+}
+
+/// Entries of a comma separated family list, trimmed, without blanks
+pub fn family_list(families: &str) -> Vec<&str> {
+    families
+        .split(',')
+        .map(str::trim)
+        .filter(|family| !family.is_empty())
+        .collect()
+}
+
+/// First family of the list that is installed, spelled as installed. None means
+/// `Font::MONOSPACE`: the list reached `monospace` or ran out first.
+pub fn pick_family(families: &str, installed: &HashSet<String>) -> Option<String> {
+    for family in family_list(families) {
+        if family.eq_ignore_ascii_case(GENERIC_MONOSPACE) {
+            return None;
+        }
+        if let Some(name) = installed.iter().find(|name| name.eq_ignore_ascii_case(family)) {
+            return Some(name.clone());
+        }
+    }
+    None
+}
+
+/// Family names of every face iced can draw with: the system's plus the app's bundled fonts
+pub fn installed_families() -> HashSet<String> {
+    let mut font_system = iced::advanced::graphics::text::font_system()
+        .write()
+        .expect("font system lock");
+    font_system
+        .raw()
+        .db()
+        .faces()
+        .flat_map(|face| face.families.iter().map(|(name, _)| name.clone()))
+        .collect()
+}
+
+/// Font names have to be 'static. Each installed family is leaked at most once, however often
+/// the setting is edited.
+pub fn intern(name: String) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut names = NAMES.get_or_init(Default::default).lock().expect("font name lock");
+    if let Some(&known) = names.get(name.as_str()) {
+        return known;
+    }
+    let leaked: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(leaked);
+    leaked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_first_installed_family_wins() {
+        let fonts = installed(&["DejaVu Sans", "Fira Code", "Menlo"]);
+        assert_eq!(pick_family(DEFAULT_FAMILIES, &fonts).as_deref(), Some("Fira Code"));
+        assert_eq!(pick_family("Menlo, Fira Code", &fonts).as_deref(), Some("Menlo"));
+    }
+
+    #[test]
+    fn test_names_match_case_insensitively_and_keep_the_installed_spelling() {
+        let fonts = installed(&["JetBrains Mono"]);
+        assert_eq!(pick_family(" jetbrains mono ,Menlo", &fonts).as_deref(), Some("JetBrains Mono"));
+    }
+
+    #[test]
+    fn test_monospace_entry_or_no_match_falls_back() {
+        let fonts = installed(&["Consolas"]);
+        // Entries after the generic one are never tried
+        assert_eq!(pick_family("Fira Code, monospace, Consolas", &fonts), None);
+        assert_eq!(pick_family("Fira Code, Menlo", &fonts), None);
+        assert_eq!(pick_family(" , ,", &fonts), None);
+    }
+
+    #[test]
+    fn test_family_list_skips_blanks() {
+        assert_eq!(family_list("A,  B ,,C,"), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_intern_reuses_names() {
+        let a = intern("Test Mono".to_string());
+        let b = intern("Test Mono".to_string());
+        assert!(std::ptr::eq(a, b));
+    }
+}
@@ -0,0 +1,278 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use iced::Theme;
+use iced::task::Handle;
+use crate::widget_helper::{WidgetHierarchy, WidgetId};
+use crate::widget_helper::code_generator::{CodeGenerator, CodeStyle, StubStyle, Token};
+use crate::widget_helper::code_metrics::CodeMetrics;
+use crate::widget_helper::message_wiring::WiringProblem;
+use crate::widget_helper::type_system::TypeSystem;
+use crate::widget_helper::window_preset::WindowSettings;
+use crate::widget_helper::file_header::FileHeader;
+use crate::widget_helper::named_styles::StyleRegistry;
+
+/// How long edits gather before the code panel generates again. Continuous edits, like a
+/// slider drag, still get one run per window instead of waiting for the drag to end.
+pub const GENERATION_DEBOUNCE: Duration = Duration::from_millis(150);
+
+static NEXT_RUN: AtomicU64 = AtomicU64::new(1);
+
+/// Builder settings the generated code depends on, besides the tree and the types
+#[derive(Debug, Clone)]
+pub struct GenerationSettings {
+    pub theme: Theme,
+    pub app_name: String,
+    pub window_title: String,
+    pub doc_comments: bool,
+    pub code_style: CodeStyle,
+    pub stub_style: StubStyle,
+    pub derive_default: bool,
+    pub view_builders: bool,
+    /// Child count past which a layout gets its own view helper, None for one flat view
+    pub view_helpers: Option<usize>,
+    pub multi_window: bool,
+    pub layout_only: bool,
+    pub window: WindowSettings,
+    pub file_header: FileHeader,
+    pub named_styles: StyleRegistry,
+}
+
+/// Tokens of the code panel, with their counts and any message wiring the passes disagree on
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedCode {
+    pub tokens: Vec<Token>,
+    pub metrics: CodeMetrics,
+    pub problems: Vec<WiringProblem>,
+    /// Code of the selection as it was copied: the widget's expression for one widget, a
+    /// `let` binding per subtree for several, nothing for none
+    pub selection: Vec<Token>,
+}
+
+/// A generator set up with the builder's settings, shared by the code panel and exports
+pub fn configured_generator<'a>(
+    hierarchy: &'a WidgetHierarchy,
+    type_system: &'a TypeSystem,
+    settings: &GenerationSettings,
+) -> CodeGenerator<'a> {
+    let mut generator = CodeGenerator::new(hierarchy, settings.theme.clone(), Some(type_system));
+    generator.set_app_name(settings.app_name.clone());
+    generator.set_window_title(settings.window_title.clone());
+    generator.set_doc_comments(settings.doc_comments);
+    generator.set_code_style(settings.code_style);
+    generator.set_stub_style(settings.stub_style);
+    generator.set_derive_default(settings.derive_default);
+    generator.set_view_builders(settings.view_builders);
+    generator.set_view_helpers(settings.view_helpers);
+    generator.set_multi_window(settings.multi_window);
+    generator.set_window_settings(settings.window);
+    generator.set_file_header(settings.file_header.comment_lines(&settings.app_name));
+    generator.set_named_styles(settings.named_styles.clone());
+    generator
+}
+
+pub fn generate(hierarchy: &WidgetHierarchy, type_system: &TypeSystem, settings: &GenerationSettings) -> GeneratedCode {
+    let mut generator = configured_generator(hierarchy, type_system, settings);
+    let tokens = if settings.layout_only { generator.generate_layout_code() } else { generator.generate_app_code() };
+    let metrics = generator.metrics();
+    let problems = generator.wiring().problems();
+
+    let mut selected: Vec<WidgetId> = hierarchy.selected_ids().iter().copied().collect();
+    selected.sort_by_key(|id| id.0);
+    let selection = match selected[..] {
+        [] => Vec::new(),
+        [widget_id] => generator.generate_widget_code(widget_id),
+        _ => generator.generate_selection_code(&selected),
+    };
+    GeneratedCode { tokens, metrics, problems, selection }
+}
+
+/// Owned copy of everything a run reads, so it can leave the update path without
+/// borrowing the live tree
+pub struct GenerationSnapshot {
+    hierarchy: WidgetHierarchy,
+    type_system: TypeSystem,
+    settings: GenerationSettings,
+}
+
+impl GenerationSnapshot {
+    pub fn new(hierarchy: &WidgetHierarchy, type_system: &TypeSystem, settings: GenerationSettings) -> Self {
+        Self {
+            hierarchy: hierarchy.clone(),
+            type_system: type_system.clone(),
+            settings,
+        }
+    }
+
+    pub fn generate(&self) -> GeneratedCode {
+        generate(&self.hierarchy, &self.type_system, &self.settings)
+    }
+}
+
+/// Background runs of the code panel's generation. Every run gets a number and only the
+/// newest is shown, so an older run finishing late can't replace a newer result.
+#[derive(Default)]
+pub struct CodeGeneration {
+    /// First change since the last run started, while a run is owed
+    stale_since: Option<Instant>,
+    requested: u64,
+    shown: u64,
+    /// Builder revision the newest run was copied at, and the one the shown code is from
+    requested_revision: u64,
+    shown_revision: u64,
+    latest: Option<Arc<GeneratedCode>>,
+    /// Aborts the previous run if it hasn't started yet when a newer one replaces it
+    running: Option<Handle>,
+}
+
+impl CodeGeneration {
+    /// Marks the shown code out of date, starting the debounce window if none is open
+    pub fn invalidate(&mut self, now: Instant) {
+        self.stale_since.get_or_insert(now);
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.stale_since.is_some()
+    }
+
+    /// Whether the debounce window that `now` falls in is over
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.stale_since
+            .is_some_and(|since| now.saturating_duration_since(since) >= GENERATION_DEBOUNCE)
+    }
+
+    /// Number of a new run of the builder at `revision`, superseding any still in flight.
+    /// Numbers come from one counter for every workspace tab's generation, so a run finishing
+    /// after its tab was switched away can't pass for the shown tab's.
+    pub fn start(&mut self, revision: u64) -> u64 {
+        if let Some(previous) = self.running.take() {
+            previous.abort();
+        }
+        self.stale_since = None;
+        self.requested_revision = revision;
+        self.requested = NEXT_RUN.fetch_add(1, Ordering::Relaxed);
+        self.requested
+    }
+
+    /// Keeps the handle of the run just started, to abort it if it gets superseded
+    pub fn track(&mut self, handle: Handle) {
+        self.running = Some(handle);
+    }
+
+    /// Shows the run's result unless a newer run was started since. Returns whether it was shown.
+    pub fn finish(&mut self, run: u64, code: Arc<GeneratedCode>) -> bool {
+        if run != self.requested {
+            return false;
+        }
+        self.running = None;
+        self.shown = run;
+        self.shown_revision = self.requested_revision;
+        self.latest = Some(code);
+        true
+    }
+
+    /// Gives up on a run that failed, keeping the last result shown
+    pub fn abandon(&mut self, run: u64) {
+        if run == self.requested {
+            self.running = None;
+            self.shown = run;
+        }
+    }
+
+    /// A run is owed or in flight, so the shown code may be behind
+    pub fn is_generating(&self) -> bool {
+        self.has_pending() || self.shown != self.requested
+    }
+
+    /// Builder revision the shown code was generated from
+    pub fn shown_revision(&self) -> u64 {
+        self.shown_revision
+    }
+
+    pub fn latest(&self) -> Option<&GeneratedCode> {
+        self.latest.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::code_generator::TokenType;
+
+    fn code(text: &str) -> Arc<GeneratedCode> {
+        let tokens = vec![Token { text: text.to_string(), token_type: TokenType::Plain }];
+        Arc::new(GeneratedCode { tokens, ..Default::default() })
+    }
+
+    fn shown(generation: &CodeGeneration) -> &str {
+        &generation.latest().unwrap().tokens[0].text
+    }
+
+    #[test]
+    fn test_only_the_latest_run_is_applied() {
+        let mut generation = CodeGeneration::default();
+        let first = generation.start(1);
+        let second = generation.start(2);
+
+        assert!(generation.finish(second, code("second")));
+        // The first run finishing late doesn't replace the newer result
+        assert!(!generation.finish(first, code("first")));
+        assert_eq!(shown(&generation), "second");
+        assert_eq!(generation.shown_revision(), 2);
+        assert!(!generation.is_generating());
+    }
+
+    #[test]
+    fn test_superseded_run_leaves_generation_pending() {
+        let mut generation = CodeGeneration::default();
+        let first = generation.start(0);
+        assert!(generation.finish(first, code("first")));
+
+        let second = generation.start(0);
+        let third = generation.start(0);
+        assert!(!generation.finish(second, code("second")));
+        assert!(generation.is_generating());
+        assert_eq!(shown(&generation), "first");
+
+        assert!(generation.finish(third, code("third")));
+        assert_eq!(shown(&generation), "third");
+    }
+
+    #[test]
+    fn test_abandoned_run_keeps_the_last_result() {
+        let mut generation = CodeGeneration::default();
+        let first = generation.start(0);
+        assert!(generation.finish(first, code("first")));
+
+        let second = generation.start(0);
+        generation.abandon(second);
+        assert!(!generation.is_generating());
+        assert_eq!(shown(&generation), "first");
+    }
+
+    #[test]
+    fn test_runs_of_different_tabs_never_share_a_number() {
+        let mut shown_tab = CodeGeneration::default();
+        let mut parked_tab = CodeGeneration::default();
+        let late = parked_tab.start(0);
+        let current = shown_tab.start(0);
+        assert_ne!(late, current);
+        assert!(!shown_tab.finish(late, code("other tab")));
+        assert!(shown_tab.finish(current, code("this tab")));
+    }
+
+    #[test]
+    fn test_debounce_window_starts_at_the_first_change() {
+        let mut generation = CodeGeneration::default();
+        let start = Instant::now();
+        assert!(!generation.is_due(start + GENERATION_DEBOUNCE));
+
+        generation.invalidate(start);
+        generation.invalidate(start + GENERATION_DEBOUNCE / 2);
+        assert!(!generation.is_due(start + GENERATION_DEBOUNCE / 2));
+        assert!(generation.is_due(start + GENERATION_DEBOUNCE));
+
+        generation.start(0);
+        assert!(!generation.has_pending());
+    }
+}
@@ -1,7 +1,16 @@
 use iced::{Color, Element, Length, Padding, widget::{column, container, space::horizontal, row, scrollable, text}, Background, Border, Theme};
 use crate::widget_helper::*;
 use crate::widget_helper::type_system::EnumDef;
+use crate::widget_helper::styles::stylefn_builders::StyleColor;
+use crate::widget_helper::code_search::{CODE_VIEW_SCROLLABLE, Highlight, Segment, highlighted_lines};
+use crate::widget_helper::code_metrics::CodeMetrics;
+use crate::widget_helper::code_font::CodeViewFont;
+use crate::widget_helper::window_preset::WindowSettings;
+use crate::widget_helper::message_wiring::{MessageWiring, WiringPass};
+use crate::widget_helper::sections::{Section, SECTIONS_SCROLLABLE, sections};
+use crate::widget_helper::named_styles::{NamedStyle, StyleRegistry};
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::path::PathBuf;
 
 /// Token types for syntax highlighting
@@ -230,6 +239,23 @@ impl TokenBuilder {
         self.add_plain(")");
     }
 
+    /// A literal color, or the `theme.extended_palette()` expression for a palette slot
+    pub fn add_style_color(&mut self, color: StyleColor) {
+        match color {
+            StyleColor::Literal(color) => self.add_color(color),
+            StyleColor::Palette(slot) => {
+                self.add_identifier("theme");
+                self.add_operator(".");
+                self.add_function("extended_palette");
+                self.add_plain("()");
+                for field in slot.path().split('.') {
+                    self.add_operator(".");
+                    self.add_identifier(field);
+                }
+            }
+        }
+    }
+
     pub fn add_color_hex(&mut self, color: Color) {
         let r = (color.r * 255.0) as u8;
         let g = (color.g * 255.0) as u8;
@@ -286,6 +312,80 @@ impl TokenBuilder {
     }
 }
 
+/// Layout of the generated code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CodeStyle {
+    /// Every chained call on its own line
+    #[default]
+    Verbose,
+    /// Short chains on one line and single-child containers inlined
+    Compact,
+}
+
+impl CodeStyle {
+    pub const ALL: [CodeStyle; 2] = [CodeStyle::Verbose, CodeStyle::Compact];
+}
+
+impl std::fmt::Display for CodeStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodeStyle::Verbose => write!(f, "Verbose"),
+            CodeStyle::Compact => write!(f, "Compact"),
+        }
+    }
+}
+
+/// What the generated update arms do for events that aren't wired to state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StubStyle {
+    /// A `// Handle ...` comment
+    #[default]
+    Comment,
+    /// `todo!()`, so a forgotten handler panics when it fires
+    Todo,
+    /// A `println!` naming the widget and event. Arms that update state log too.
+    Log,
+}
+
+impl StubStyle {
+    pub const ALL: [StubStyle; 3] = [StubStyle::Comment, StubStyle::Todo, StubStyle::Log];
+}
+
+impl std::fmt::Display for StubStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StubStyle::Comment => write!(f, "Comment"),
+            StubStyle::Todo => write!(f, "todo!()"),
+            StubStyle::Log => write!(f, "Log"),
+        }
+    }
+}
+
+// Compact layout limits
+const COMPACT_LINE_WIDTH: usize = 100;
+const COMPACT_INLINE_CALLS: usize = 3;
+const COMPACT_SHORT_CALL: usize = 24;  // Longer calls always start their own line
+
+// Lists longer than either limit go one entry per line
+const LIST_INLINE_ENTRIES: usize = 4;
+const LIST_INLINE_WIDTH: usize = 80;
+
+/// How `emit_list` lays out its entries
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListLayout {
+    /// On one line while within the list limits
+    Fit,
+    /// One entry per line however short the list is
+    OnePerLine,
+}
+
+/// A chain break whose newline hasn't been decided yet
+#[derive(Debug, Clone, Copy)]
+struct PendingBreak {
+    at: usize,
+    indent_level: usize,
+}
+
 /// Code generator for creating Iced code from widget hierarchy
 pub struct CodeGenerator<'a> {
     hierarchy: &'a WidgetHierarchy,
@@ -293,11 +393,39 @@ pub struct CodeGenerator<'a> {
     tokens: Vec<Token>,
     app_name: String,
     app_window_title: String,
-    widget_counts: HashMap<String, usize>,  // Track duplicate widgets
     used_widgets: HashSet<&'static str>,  // Track which widgets are used for the impl code gen
     widget_names: HashMap<WidgetId, String>,
     type_system: Option<&'a TypeSystem>,
     theme: Theme,
+    doc_comments: bool,
+    code_style: CodeStyle,
+    stub_style: StubStyle,
+    pending_break: Option<PendingBreak>,
+    inline_calls: usize,  // Chained calls already inlined on the current line
+    layout_only: bool,  // Emitting a standalone view with no handlers
+    derive_default: bool,
+    view_builders: bool,
+    /// Set while emitting the free view builder, whose state is the `state` argument
+    in_view_builder: bool,
+    /// Layouts with more children than this, and widgets with a custom name, get a
+    /// `<name>_view` method the view calls. None keeps the view one expression.
+    helper_threshold: Option<usize>,
+    /// Set while emitting the view or a helper, so snippets and layout-only code stay inline
+    splitting: bool,
+    /// Helpers called so far, in call order, emitted after the view
+    called_helpers: Vec<WidgetId>,
+    /// Helper whose body is being emitted, which is built inline rather than calling itself
+    emitting_helper: Option<WidgetId>,
+    multi_window: bool,
+    window: WindowSettings,
+    /// Paths written instead of a widget's own, where an export bundles library assets
+    asset_paths: HashMap<WidgetId, String>,
+    /// Comment lines written above the imports, like a license header
+    file_header: Vec<String>,
+    /// Project styles that Containers point at, each written once as a shared function
+    named_styles: StyleRegistry,
+    metrics: CodeMetrics,
+    wiring: MessageWiring,
 }
 
 impl<'a> CodeGenerator<'a> {
@@ -308,14 +436,101 @@ impl<'a> CodeGenerator<'a> {
             tokens: Vec::new(),
             app_name: "App".to_string(),
             app_window_title: "App Window".to_string(),
-            widget_counts: HashMap::new(),
             used_widgets: HashSet::new(),
             widget_names: HashMap::new(),
             type_system: type_system,
             theme,
+            doc_comments: false,
+            code_style: CodeStyle::Verbose,
+            stub_style: StubStyle::Comment,
+            pending_break: None,
+            inline_calls: 0,
+            layout_only: false,
+            derive_default: false,
+            view_builders: false,
+            in_view_builder: false,
+            helper_threshold: None,
+            splitting: false,
+            called_helpers: Vec::new(),
+            emitting_helper: None,
+            multi_window: false,
+            window: WindowSettings::default(),
+            asset_paths: HashMap::new(),
+            file_header: Vec::new(),
+            named_styles: StyleRegistry::default(),
+            metrics: CodeMetrics::default(),
+            wiring: MessageWiring::default(),
         }
     }
 
+    /// Emit `///` comments describing the source widget above message variants and state fields
+    pub fn set_doc_comments(&mut self, enabled: bool) {
+        self.doc_comments = enabled;
+    }
+
+    /// Choose between one call per line and the denser compact layout
+    pub fn set_code_style(&mut self, style: CodeStyle) {
+        self.code_style = style;
+    }
+
+    /// Choose what update arms without state changes contain
+    pub fn set_stub_style(&mut self, style: StubStyle) {
+        self.stub_style = style;
+    }
+
+    /// Build the initial state through `Default`, leaving `new()` to return `Self::default()`
+    pub fn set_derive_default(&mut self, enabled: bool) {
+        self.derive_default = enabled;
+    }
+
+    /// Build the view in a free `build_view(state: &App)` function that `view()` delegates to,
+    /// so downstream tests can call it on hand-built state
+    pub fn set_view_builders(&mut self, enabled: bool) {
+        self.view_builders = enabled;
+    }
+
+    /// Split the view into `fn <name>_view(&self)` helpers, one per Container, Row or Column with
+    /// more than `threshold` children and per widget with a custom name. None keeps it flat.
+    pub fn set_view_helpers(&mut self, threshold: Option<usize>) {
+        self.helper_threshold = threshold;
+    }
+
+    /// Scaffold the app as an `iced::daemon` with a map of open windows, instead of a
+    /// single-window `iced::application`. Layout-only output ignores it.
+    pub fn set_multi_window(&mut self, enabled: bool) {
+        self.multi_window = enabled;
+    }
+
+    /// How the app's window opens. Anything but iced's defaults gets a `window_settings()` function.
+    pub fn set_window_settings(&mut self, settings: WindowSettings) {
+        self.window = settings;
+    }
+
+    /// Image and Svg paths to write in place of the widgets' own, keyed by widget
+    pub fn set_asset_paths(&mut self, paths: HashMap<WidgetId, String>) {
+        self.asset_paths = paths;
+    }
+
+    /// Comment lines, `//` included, to open the file with. Empty leaves the output as it was.
+    pub fn set_file_header(&mut self, lines: Vec<String>) {
+        self.file_header = lines;
+    }
+
+    /// Named styles the tree's Containers may use, emitted as `fn name(theme) -> container::Style`
+    pub fn set_named_styles(&mut self, styles: StyleRegistry) {
+        self.named_styles = styles;
+    }
+
+    /// Counts from the last `generate_app_code` or `generate_layout_code`
+    pub fn metrics(&self) -> CodeMetrics {
+        self.metrics
+    }
+
+    /// Message variants recorded by each pass of the last `generate_app_code`
+    pub fn wiring(&self) -> &MessageWiring {
+        &self.wiring
+    }
+
     /// Set App name for code generation
     pub fn set_app_name(&mut self, name: String) {
         self.app_name = if name.trim().is_empty() { 
@@ -344,14 +559,74 @@ impl<'a> CodeGenerator<'a> {
     pub fn generate_widget_code(&mut self, widget_id: WidgetId) -> Vec<Token> {
         self.tokens.clear();
         self.indent_level = 0;
+        self.pending_break = None;
+        self.inline_calls = 0;
+        // Fields and messages the widget refers to get the names the app code gives them
+        self.generate_all_widget_names();
         
         if let Some(widget) = self.hierarchy.get_widget_by_id(widget_id) {
             self.generate_widget_creation(widget, false);
         }
+        self.resolve_chain_break();
         
         self.tokens.clone()
     }
 
+    /// `generate_widget_code` as plain text
+    pub fn generate_widget_source(&mut self, widget_id: WidgetId) -> String {
+        source_of(&self.generate_widget_code(widget_id))
+    }
+
+    /// Each selected subtree as a `let <name> = ...;` binding, named as `generate_app_code`
+    /// names the widget, in tree order. A widget inside another selected one comes with it
+    /// rather than getting a binding of its own.
+    pub fn generate_selection_code(&mut self, widget_ids: &[WidgetId]) -> Vec<Token> {
+        self.tokens.clear();
+        self.indent_level = 0;
+        self.pending_break = None;
+        self.inline_calls = 0;
+        self.generate_all_widget_names();
+
+        let hierarchy = self.hierarchy;
+        let selected: HashSet<WidgetId> = widget_ids.iter().copied().collect();
+        let mut widgets = Vec::new();
+        collect_tree_order(hierarchy.root(), &mut widgets);
+        let outermost = widgets.into_iter().filter(|widget| {
+            selected.contains(&widget.id)
+                && !hierarchy.find_path(widget.id).iter().rev().skip(1).any(|id| selected.contains(id))
+        });
+
+        for (i, widget) in outermost.enumerate() {
+            if i > 0 {
+                self.add_newline();
+            }
+            let name = self.get_widget_name(widget.id);
+            self.add_keyword("let");
+            self.add_plain(" ");
+            self.add_identifier(&name);
+            self.add_plain(" ");
+            self.add_operator("=");
+            self.add_newline();
+            self.indent_level = 1;
+            self.generate_widget_creation(widget, false);
+            self.resolve_chain_break();
+            self.indent_level = 0;
+            // The widget ends its last line, the semicolon goes on that line
+            if self.tokens.last().is_some_and(|token| token.text == "\n") {
+                self.tokens.pop();
+            }
+            self.add_plain(";");
+            self.add_newline();
+        }
+
+        self.tokens.clone()
+    }
+
+    /// `generate_selection_code` as plain text
+    pub fn generate_selection_source(&mut self, widget_ids: &[WidgetId]) -> String {
+        source_of(&self.generate_selection_code(widget_ids))
+    }
+
     fn generate_enum_definitions(&mut self) {
         if self.type_system.is_none() { return }
         for enum_def in self.type_system.unwrap().enums.values() {
@@ -452,21 +727,15 @@ impl<'a> CodeGenerator<'a> {
         
         self.add_indent();
         self.add_keyword("pub const");
-        self.add_plain(" ALL: &'static [Self] = &[");
-        self.add_newline();
-        self.indent_level += 1;
-        
-        for variant in &enum_def.variants {
-            self.add_indent();
-            self.add_plain("Self::");
-            self.add_plain(&variant.name);
-            self.add_plain(",");
-            self.add_newline();
-        }
-        
-        self.indent_level -= 1;
-        self.add_indent();
-        self.add_plain("];");
+        self.add_plain(" ALL: &'static [Self] = ");
+        let entries = enum_def.variants.iter()
+            .map(|variant| self.emit_detached(|generator| {
+                generator.add_plain("Self::");
+                generator.add_plain(&variant.name);
+            }))
+            .collect();
+        self.emit_list("&[", entries, "]", ListLayout::OnePerLine);
+        self.add_plain(";");
         self.add_newline();
         
         self.indent_level -= 1;
@@ -477,7 +746,12 @@ impl<'a> CodeGenerator<'a> {
     pub fn generate_app_code(&mut self) -> Vec<Token> {
         self.tokens.clear();
         self.indent_level = 0;
+        self.pending_break = None;
+        self.inline_calls = 0;
+        self.metrics = CodeMetrics::default();
+        self.wiring = MessageWiring::default();
         self.used_widgets.clear();
+        self.called_helpers.clear();
         
         // CRITICAL: Generate all widget names ONCE at the beginning
         self.generate_all_widget_names();
@@ -485,14 +759,26 @@ impl<'a> CodeGenerator<'a> {
         // First pass: collect all used widgets
         self.collect_used_widgets(&self.hierarchy.root().clone());
         
+        self.generate_file_header();
+
         // Generate imports
-        self.generate_imports();
+        self.generate_imports(None);
         self.add_newline();
         self.add_newline();
 
         // Generate enum definitions
         self.generate_enum_definitions();
         self.add_newline();
+
+        let nav_sections = self.nav_sections();
+        if !nav_sections.is_empty() {
+            self.generate_section_constants(&nav_sections);
+            self.add_newline();
+        }
+        if let Some(width) = self.breakpoint() {
+            self.generate_breakpoint_constant(width);
+            self.add_newline();
+        }
         
         // Generate Message enum
         self.generate_message_enum();
@@ -503,54 +789,145 @@ impl<'a> CodeGenerator<'a> {
         self.generate_app_struct();
         self.add_newline();
         self.add_newline();
+
+        if self.multi_window {
+            self.generate_window_types();
+            self.add_newline();
+            self.add_newline();
+        }
+
+        if self.derive_default && self.needs_default_impl() {
+            self.generate_default_impl();
+            self.add_newline();
+            self.add_newline();
+        }
         
         // Generate impl block (now just impl App, not impl Application for App)
         self.generate_impl_block();
         self.add_newline();
         self.add_newline();
+
+        if self.view_builders {
+            self.generate_view_builder();
+            self.add_newline();
+            self.add_newline();
+        }
+
+        if !self.called_helpers.is_empty() {
+            self.generate_view_helpers();
+            self.add_newline();
+            self.add_newline();
+        }
         
+        if !self.used_named_styles().is_empty() {
+            self.generate_named_style_fns();
+            self.add_newline();
+        }
+
+        if self.custom_window() {
+            self.generate_window_settings_fn();
+            self.add_newline();
+            self.add_newline();
+        }
+
         // Generate main function with new iced API
         self.generate_main_function();
+        self.resolve_chain_break();
+        self.count_lines();
+
+        // A disagreement between the passes is a generator bug, so fixtures must not produce one
+        if cfg!(test) {
+            debug_assert!(self.wiring.problems().is_empty(), "{:?}", self.wiring.problems());
+        }
         
         self.tokens.clone()
     }
 
+    /// `generate_app_code` as plain text, for saving, copying and tests
+    pub fn generate_app_source(&mut self) -> String {
+        source_of(&self.generate_app_code())
+    }
 
-    // Generate unique name for duplicate widgets
-    fn get_unique_widget_name(&mut self, widget: &Widget) -> String {
-        // If widget has a custom name, use it
-        if !widget.properties.widget_name.trim().is_empty() {
-            return self.sanitize_name(&widget.properties.widget_name);
-        }
-        
-        // Otherwise, use widget type as base name
-        let base_name = match widget.widget_type {
-            WidgetType::Button => "button",
-            WidgetType::Text => "text",
-            WidgetType::TextInput => "text_input",
-            WidgetType::Checkbox => "checkbox",
-            WidgetType::Radio => "radio",
-            WidgetType::Slider => "slider",
-            WidgetType::VerticalSlider => "vertical_slider",
-            WidgetType::ProgressBar => "progress_bar",
-            WidgetType::Toggler => "toggler",
-            WidgetType::PickList => "pick_list",
-            _ => return format!("{:?}", widget.widget_type).to_lowercase(), // Fallback to type name
-        }.to_string();
-        
-        // Add number if there are duplicates
-        let type_key = format!("{:?}", widget.widget_type).to_lowercase();
-        let count = self.widget_counts.entry(type_key).or_insert(0);
-        *count += 1;
-        
-        if *count > 1 {
-            format!("{}_{}", base_name, count)
-        } else {
-            base_name
+    /// Generate a standalone `view` function with every widget in its static form,
+    /// for stubbing out a layout before any state exists
+    pub fn generate_layout_code(&mut self) -> Vec<Token> {
+        self.tokens.clear();
+        self.indent_level = 0;
+        self.pending_break = None;
+        self.inline_calls = 0;
+        self.metrics = CodeMetrics::default();
+        self.layout_only = true;
+
+        self.generate_all_widget_names();
+
+        // The body goes first so the imports can leave out what it never names
+        let body = self.emit_detached(Self::generate_layout_body);
+        let words = code_words(&body);
+
+        self.generate_file_header();
+        self.generate_imports(Some(&words));
+        self.add_newline();
+        self.add_newline();
+        self.tokens.extend(body);
+        self.resolve_chain_break();
+        self.count_lines();
+
+        self.layout_only = false;
+        self.tokens.clone()
+    }
+
+    /// The `Message` stand-in and the `view` function of the layout-only code
+    fn generate_layout_body(&mut self) {
+        self.add_plain("#[derive(Debug, Clone)]");
+        self.add_newline();
+        self.add_keyword("pub enum");
+        self.add_plain(" ");
+        self.add_type("Message");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_plain("Noop,");
+        self.add_newline();
+        self.metrics.message_variants = 1;
+        self.indent_level -= 1;
+        self.add_plain("}");
+        self.add_newline();
+        self.add_newline();
+
+        self.add_keyword("fn");
+        self.add_plain(" ");
+        self.add_function("view");
+        self.add_plain("<");
+        self.add_operator("'a");
+        self.add_plain(">() ");
+        self.add_operator("->");
+        self.add_plain(" ");
+        self.add_type("Element");
+        self.add_plain("<");
+        self.add_operator("'a");
+        self.add_plain(", ");
+        self.add_type("Message");
+        self.add_plain("> {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.generate_view_body(false);
+        self.indent_level -= 1;
+        self.add_plain("}");
+        self.add_newline();
+        if !self.used_named_styles().is_empty() {
+            self.add_newline();
+            self.generate_named_style_fns();
         }
     }
 
+
     fn generate_new_method(&mut self) {
+        if self.derive_default {
+            self.generate_default_new_method();
+            return;
+        }
+
         self.add_indent();
         self.add_keyword("fn");
         self.add_plain(" ");
@@ -581,8 +958,10 @@ impl<'a> CodeGenerator<'a> {
         self.indent_level += 1;
         
         // Initialize state fields
-        self.widget_counts.clear();
+        self.generate_windows_initializer();
+        self.generate_window_width_initializer();
         self.generate_state_initializers(&self.hierarchy.root().clone());
+        self.generate_condition_flag_initializers();
         
         self.indent_level -= 1;
         self.add_indent();
@@ -590,26 +969,180 @@ impl<'a> CodeGenerator<'a> {
         self.add_newline();
         
         self.add_indent();
+        self.add_boot_task();
+        self.add_newline();
+        
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain(")");
+        self.add_newline();
+        
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+    }
+
+    /// `new()` when the state comes from `Default`
+    fn generate_default_new_method(&mut self) {
+        self.add_indent();
+        self.add_keyword("fn");
+        self.add_plain(" ");
+        self.add_function("new");
+        self.add_plain("() ");
+        self.add_operator("->");
+        self.add_plain(" (");
+        self.add_keyword("Self");
+        self.add_plain(", ");
         self.add_number("iced");
         self.add_operator("::");
         self.add_type("Task");
+        self.add_plain("<");
+        self.add_type("Message");
+        self.add_plain(">) {");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_plain("(");
+        self.add_keyword("Self");
         self.add_operator("::");
-        self.add_plain("none()");
+        self.add_function("default");
+        self.add_plain("(), ");
+        self.add_boot_task();
+        self.add_plain(")");
         self.add_newline();
-        
+
         self.indent_level -= 1;
         self.add_indent();
-        self.add_plain(")");
+        self.add_plain("}");
+    }
+
+    /// Manual `impl Default` for state that starts somewhere other than each field's default
+    fn generate_default_impl(&mut self) {
+        self.add_keyword("impl");
+        self.add_plain(" ");
+        self.add_type("Default");
+        self.add_plain(" ");
+        self.add_keyword("for");
+        self.add_plain(" ");
+        self.add_type(&self.app_name.clone());
+        self.add_plain(" {");
         self.add_newline();
-        
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_keyword("fn");
+        self.add_plain(" ");
+        self.add_function("default");
+        self.add_plain("() ");
+        self.add_operator("->");
+        self.add_plain(" ");
+        self.add_keyword("Self");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_keyword("Self");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.generate_windows_initializer();
+        self.generate_window_width_initializer();
+        self.generate_state_initializers(&self.hierarchy.root().clone());
+        self.generate_condition_flag_initializers();
+
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+
         self.indent_level -= 1;
         self.add_indent();
         self.add_plain("}");
+        self.add_newline();
+
+        self.indent_level -= 1;
+        self.add_plain("}");
     }
 
-    fn generate_state_initializers(&mut self, widget: &Widget) {
+    /// The task `new()` starts with: nothing, or opening the main window when there is no window yet
+    fn add_boot_task(&mut self) {
+        self.add_number("iced");
+        self.add_operator("::");
+        self.add_type("Task");
+        self.add_operator("::");
+        if self.multi_window {
+            self.add_function("done");
+            self.add_plain("(");
+            self.add_type("Message");
+            self.add_operator("::");
+            self.add_plain("RequestOpenWindow(");
+            self.add_type("WindowEnum");
+            self.add_operator("::");
+            self.add_plain("Main))");
+        } else {
+            self.add_plain("none()");
+        }
+    }
+
+    fn generate_window_width_initializer(&mut self) {
+        if self.breakpoint().is_none() {
+            return;
+        }
+        self.add_indent();
+        self.add_comment("// Wide until the first resize reports the real width");
+        self.add_newline();
+        self.add_indent();
+        self.add_identifier("window_width");
+        self.add_operator(":");
+        self.add_plain(" ");
+        self.add_identifier("BREAKPOINT");
+        self.add_plain(",");
+        self.add_newline();
+    }
+
+    fn generate_windows_initializer(&mut self) {
+        if !self.multi_window {
+            return;
+        }
+        self.add_indent();
+        self.add_identifier("windows");
+        self.add_operator(":");
+        self.add_plain(" ");
+        self.add_type("BTreeMap");
+        self.add_operator("::");
+        self.add_function("new");
+        self.add_plain("(),");
+        self.add_newline();
+    }
+
+    fn generate_condition_flag_initializers(&mut self) {
+        let mut flags = Vec::new();
+        condition_flags(self.hierarchy.root(), &mut flags);
+        for flag in flags {
+            self.add_indent();
+            self.add_identifier(&flag);
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_keyword("false");
+            self.add_plain(",");
+            self.add_newline();
+        }
+    }
+
+    fn generate_state_initializers(&mut self, widget: &Widget) {
         let name = self.get_widget_name(widget.id);
         let props = &widget.properties;
+
+        if file_dialog_of(widget) != FileDialog::None {
+            self.add_indent();
+            self.add_identifier(&format!("{}_path", to_snake_case(&name)));
+            self.add_operator(":");
+            self.add_plain(" None,");
+            self.add_newline();
+        }
         
         match widget.widget_type {
             WidgetType::TextInput => {
@@ -662,6 +1195,19 @@ impl<'a> CodeGenerator<'a> {
                 self.add_plain(" None,");
                 self.add_newline();
             }
+            WidgetType::Markdown => {
+                self.add_indent();
+                self.add_identifier(&format!("{}_content", to_snake_case(&name)));
+                self.add_operator(":");
+                self.add_plain(" ");
+                self.add_type("markdown::Content");
+                self.add_operator("::");
+                self.add_function("parse");
+                self.add_plain("(");
+                self.add_string(&raw_string_literal(&props.markdown_source.text()));
+                self.add_plain("),");
+                self.add_newline();
+            }
             WidgetType::ComboBox => {
                 if self.type_system.is_none() { return }
                 // Get the enum definition and initialize properly
@@ -706,17 +1252,9 @@ impl<'a> CodeGenerator<'a> {
                     self.add_type("combo_box::State");
                     self.add_operator("::");
                     self.add_function("new");
-                    self.add_plain("(vec![");
-                    for (i, option) in props.combobox_options.iter().enumerate() {
-                        self.add_string(&format!("\"{}\"", option));
-                        self.add_operator(".");
-                        self.add_function("to_string");
-                        self.add_plain("()");
-                        if i < props.combobox_options.len() - 1 {
-                            self.add_plain(", ");
-                        }
-                    }
-                    self.add_plain("]),");
+                    self.add_plain("(");
+                    self.emit_string_list(&props.combobox_options);
+                    self.add_plain("),");
                     self.add_newline();
                 }
             }
@@ -729,6 +1267,11 @@ impl<'a> CodeGenerator<'a> {
     }
 
     fn generate_title_method(&mut self) {
+        if self.multi_window {
+            self.generate_window_title_method();
+            return;
+        }
+
         self.add_indent();
         self.add_keyword("fn");
         self.add_plain(" ");
@@ -766,6 +1309,13 @@ impl<'a> CodeGenerator<'a> {
         self.add_plain("(");
         self.add_operator("&");
         self.add_keyword("self");
+        if self.multi_window {
+            self.add_plain(", ");
+            self.add_identifier("_window_id");
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_type("window::Id");
+        }
         self.add_plain(") ");
         self.add_operator("->");
         self.add_plain(" ");
@@ -775,39 +1325,62 @@ impl<'a> CodeGenerator<'a> {
         self.indent_level += 1;
         
         self.add_indent();
+        self.add_theme(&theme);
+        self.add_newline();
+        
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+    }
+
+    /// `Theme::Variant` for a built-in theme. A custom theme is spelled out as `Theme::custom`
+    /// with its palette, since the generated app has no other way to find it.
+    fn add_theme(&mut self, theme: &Theme) {
         self.add_type("Theme");
         self.add_operator("::");
-        // Use the actual theme passed in
-        match theme {
-            Theme::Light => self.add_plain("Light"),
-            Theme::Dark => self.add_plain("Dark"),
-            Theme::Dracula => self.add_plain("Dracula"),
-            Theme::Nord => self.add_plain("Nord"),
-            Theme::SolarizedLight => self.add_plain("SolarizedLight"),
-            Theme::SolarizedDark => self.add_plain("SolarizedDark"),
-            Theme::GruvboxLight => self.add_plain("GruvboxLight"),
-            Theme::GruvboxDark => self.add_plain("GruvboxDark"),
-            Theme::CatppuccinLatte => self.add_plain("CatppuccinLatte"),
-            Theme::CatppuccinFrappe => self.add_plain("CatppuccinFrappe"),
-            Theme::CatppuccinMacchiato => self.add_plain("CatppuccinMacchiato"),
-            Theme::CatppuccinMocha => self.add_plain("CatppuccinMocha"),
-            Theme::TokyoNight => self.add_plain("TokyoNight"),
-            Theme::TokyoNightStorm => self.add_plain("TokyoNightStorm"),
-            Theme::TokyoNightLight => self.add_plain("TokyoNightLight"),
-            Theme::KanagawaWave => self.add_plain("KanagawaWave"),
-            Theme::KanagawaDragon => self.add_plain("KanagawaDragon"),
-            Theme::KanagawaLotus => self.add_plain("KanagawaLotus"),
-            Theme::Moonfly => self.add_plain("Moonfly"),
-            Theme::Nightfly => self.add_plain("Nightfly"),
-            Theme::Oxocarbon => self.add_plain("Oxocarbon"),
-            Theme::Ferra => self.add_plain("Ferra"),
-            _ => self.add_plain("Dark"), // Default fallback for custom themes
+        if let Some(variant) = builtin_theme_variant(theme) {
+            self.add_plain(variant);
+            return;
         }
+        let palette = theme.palette();
+        self.add_function("custom");
+        self.add_plain("(");
         self.add_newline();
-        
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_string(&format!("{:?}", theme.to_string()));
+        self.add_operator(".");
+        self.add_function("to_string");
+        self.add_plain("(),");
+        self.add_newline();
+        self.add_indent();
+        self.add_type("iced::theme::Palette");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        for (field, color) in [
+            ("background", palette.background),
+            ("text", palette.text),
+            ("primary", palette.primary),
+            ("success", palette.success),
+            ("warning", palette.warning),
+            ("danger", palette.danger),
+        ] {
+            self.add_indent();
+            self.add_identifier(field);
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_color(color);
+            self.add_plain(",");
+            self.add_newline();
+        }
         self.indent_level -= 1;
         self.add_indent();
-        self.add_plain("}");
+        self.add_plain("},");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain(")");
     }
 
     // Updated impl block generation
@@ -838,6 +1411,18 @@ impl<'a> CodeGenerator<'a> {
         self.generate_update_method();
         self.add_newline();
         self.add_newline();
+
+        if self.multi_window || self.breakpoint().is_some() {
+            self.generate_subscription_method();
+            self.add_newline();
+            self.add_newline();
+        }
+
+        if self.multi_window {
+            self.generate_window_view_method();
+            self.add_newline();
+            self.add_newline();
+        }
         
         // Generate view method
         self.generate_view_method();
@@ -847,7 +1432,36 @@ impl<'a> CodeGenerator<'a> {
         self.add_plain("}");
     }
 
+    /// The file dialogs and section jumps run as Tasks, so they need the Task-returning update even in a single window app
+    fn update_returns_task(&self) -> bool {
+        self.multi_window || uses_file_dialogs(self.hierarchy.root()) || !self.nav_sections().is_empty()
+    }
+
+    /// Width the flagged rows stack below. The layout stub has no state to track the window in.
+    fn breakpoint(&self) -> Option<f32> {
+        if self.layout_only { None } else { active_breakpoint(self.hierarchy.root()) }
+    }
+
+    /// Whether `derive_default` still needs a hand-written Default for state that starts elsewhere
+    fn needs_default_impl(&self) -> bool {
+        needs_custom_default(self.hierarchy.root()) || self.breakpoint().is_some()
+    }
+
+    /// Sections the generated view gets nav buttons for. The layout stub has no update to scroll from.
+    fn nav_sections(&self) -> Vec<Section> {
+        if self.layout_only { Vec::new() } else { sections(self.hierarchy.root()) }
+    }
+
     fn generate_update_method(&mut self) {
+        if !self.multi_window && uses_file_dialogs(self.hierarchy.root()) {
+            self.add_indent();
+            self.add_comment("// Returns a Task so the file dialog buttons can run their dialogs");
+            self.add_newline();
+        } else if !self.multi_window && !self.nav_sections().is_empty() {
+            self.add_indent();
+            self.add_comment("// Returns a Task so the section buttons can scroll");
+            self.add_newline();
+        }
         self.add_indent();
         self.add_keyword("fn");
         self.add_plain(" ");
@@ -862,7 +1476,18 @@ impl<'a> CodeGenerator<'a> {
         self.add_operator(":");
         self.add_plain(" ");
         self.add_type("Message");
-        self.add_plain(") {");
+        let returns_task = self.update_returns_task();
+        if returns_task {
+            self.add_plain(") ");
+            self.add_operator("->");
+            self.add_plain(" ");
+            self.add_type("Task");
+            self.add_plain("<");
+            self.add_type("Message");
+            self.add_plain("> {");
+        } else {
+            self.add_plain(") {");
+        }
         self.add_newline();
         self.indent_level += 1;
         
@@ -876,11 +1501,28 @@ impl<'a> CodeGenerator<'a> {
         
         // Generate match arms for each message
         self.generate_update_match_arms(&self.hierarchy.root().clone());
+        if self.multi_window {
+            self.generate_window_match_arms();
+        }
+        if !self.nav_sections().is_empty() {
+            self.generate_section_match_arm();
+        }
+        if self.breakpoint().is_some() {
+            self.generate_window_resized_arm();
+        }
         
         self.indent_level -= 1;
         self.add_indent();
         self.add_plain("}");
         self.add_newline();
+
+        if returns_task {
+            self.add_indent();
+            self.add_type("Task");
+            self.add_operator("::");
+            self.add_plain("none()");
+            self.add_newline();
+        }
         
         self.indent_level -= 1;
         self.add_indent();
@@ -898,7 +1540,7 @@ impl<'a> CodeGenerator<'a> {
         self.indent_level += 1;
         
         self.add_indent();
-        self.add_plain("iced::application(");
+        self.add_plain(if self.multi_window { "iced::daemon(" } else { "iced::application(" });
         self.add_type(&self.app_name.clone());
         self.add_operator("::");
         self.add_plain("new, ");
@@ -928,6 +1570,28 @@ impl<'a> CodeGenerator<'a> {
         self.add_operator("::");
         self.add_plain("title)");
         self.add_newline();
+
+        // A daemon passes the settings to window::open instead
+        if self.custom_window() && !self.multi_window {
+            self.add_indent();
+            self.add_operator(".");
+            self.add_function("window");
+            self.add_plain("(");
+            self.add_function("window_settings");
+            self.add_plain("())");
+            self.add_newline();
+        }
+
+        if self.multi_window || self.breakpoint().is_some() {
+            self.add_indent();
+            self.add_operator(".");
+            self.add_function("subscription");
+            self.add_plain("(");
+            self.add_type(&self.app_name.clone());
+            self.add_operator("::");
+            self.add_plain("subscription)");
+            self.add_newline();
+        }
         
         self.add_indent();
         self.add_operator(".");
@@ -939,420 +1603,1310 @@ impl<'a> CodeGenerator<'a> {
         self.add_plain("}");
     }
 
-    fn generate_imports(&mut self) {
-        // Scan the entire hierarchy
-        let mut tracker = ImportTracker::new();
-        tracker.scan_widget(&self.hierarchy.root().clone());
-        
-        self.add_keyword("use");
-        self.add_number(" iced::");
-        self.add_plain("{");
+    /// `WindowEnum` naming the kinds of window the app opens, and the `Window` kept per open one
+    fn generate_window_types(&mut self) {
+        self.add_comment("// Kinds of window the app can open");
+        self.add_newline();
+        self.add_plain("#[derive(Debug, Clone, Copy, PartialEq)]");
+        self.add_newline();
+        self.add_keyword("enum");
+        self.add_plain(" ");
+        self.add_type("WindowEnum");
+        self.add_plain(" {");
         self.add_newline();
         self.indent_level += 1;
-        
-        // Core types - build list
-        let mut core_imports = Vec::new();
-        
-        if tracker.uses_length {
-            core_imports.push("Length");
-        }
-        if tracker.uses_alignment {
-            core_imports.push("Alignment");
-        }
-        if tracker.uses_color {
-            core_imports.push("Color");
-        }
-        if tracker.uses_padding {
-            core_imports.push("Padding");
-        }
-        if tracker.uses_font {
-            core_imports.push("Font");
-        }
-        if tracker.uses_border {
-            core_imports.push("Border");
-        }
-        if tracker.uses_shadow {
-            core_imports.push("Shadow");
+        self.add_indent();
+        self.add_plain("Main,");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_plain("}");
+        self.add_newline();
+        self.add_newline();
+
+        self.add_comment("// An open window and what it shows");
+        self.add_newline();
+        self.add_plain("#[derive(Debug, Clone)]");
+        self.add_newline();
+        self.add_keyword("struct");
+        self.add_plain(" ");
+        self.add_type("Window");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        for (field, field_type) in [("title", "String"), ("windowtype", "WindowEnum")] {
+            self.add_indent();
+            self.add_identifier(field);
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_type(field_type);
+            self.add_plain(",");
+            self.add_newline();
         }
-        if tracker.uses_background {
-            core_imports.push("Background");
+        self.indent_level -= 1;
+        self.add_plain("}");
+    }
+
+    /// Whether the window opens with anything but iced's defaults
+    fn custom_window(&self) -> bool {
+        !self.layout_only && self.window != WindowSettings::default()
+    }
+
+    /// The named style a Container's `.style()` calls, None when it has no style, its style
+    /// is gone, or a gradient or condition writes its own style
+    fn named_style_of(&self, widget: &Widget) -> Option<&NamedStyle> {
+        let props = &widget.properties;
+        if widget.widget_type != WidgetType::Container || props.gradient_enabled || self.condition_field(widget).is_some() {
+            return None;
         }
-        if tracker.uses_vector {
-            core_imports.push("Vector");
+        self.named_styles.get(props.named_style?)
+    }
+
+    /// Named styles at least one widget calls, in the registry's order
+    fn used_named_styles(&self) -> Vec<NamedStyle> {
+        fn walk(generator: &CodeGenerator, widget: &Widget, used: &mut HashSet<Uuid>) {
+            if let Some(style) = generator.named_style_of(widget) {
+                used.insert(style.id);
+            }
+            for child in &widget.children {
+                walk(generator, child, used);
+            }
         }
-        if tracker.uses_point {
-            core_imports.push("Point");
+        let mut used = HashSet::new();
+        walk(self, self.hierarchy.root(), &mut used);
+        self.named_styles.styles().iter().filter(|style| used.contains(&style.id)).cloned().collect()
+    }
+
+    /// One `fn name(_theme: &Theme) -> container::Style` per used named style, blank lines between
+    fn generate_named_style_fns(&mut self) {
+        for (i, style) in self.used_named_styles().into_iter().enumerate() {
+            if i > 0 {
+                self.add_newline();
+            }
+            let look = style.look;
+            self.add_keyword("fn");
+            self.add_plain(" ");
+            self.add_function(&style.name);
+            self.add_plain("(_theme: &");
+            self.add_type("Theme");
+            self.add_plain(") ");
+            self.add_operator("->");
+            self.add_plain(" ");
+            self.add_type("container::Style");
+            self.add_plain(" {");
+            self.indent_level += 1;
+            let shadow = look.has_shadow.then_some((look.shadow_color, look.shadow_offset, look.shadow_blur));
+            self.add_container_style_literal(look.background, look.border_color, look.border_width, look.border_radius, shadow);
+            self.indent_level -= 1;
+            self.add_newline();
+            self.add_plain("}");
+            self.add_newline();
         }
-        
-        // Element, Theme, and Task are always needed
-        core_imports.push("Element");
-        core_imports.push("Theme");
-        core_imports.push("Task");
+    }
+
+    /// fn window_settings() -> window::Settings, setting only the fields that differ from the defaults
+    fn generate_window_settings_fn(&mut self) {
+        let window = self.window;
+        let standard = WindowSettings::default();
 
+        self.add_keyword("fn");
+        self.add_plain(" ");
+        self.add_function("window_settings");
+        self.add_plain("() ");
+        self.add_operator("->");
+        self.add_plain(" ");
+        self.add_type("window::Settings");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
         self.add_indent();
-        core_imports.into_iter().for_each(|import| {
-            self.add_type(import);
-            self.add_plain(",");
-        });
+        self.add_type("window::Settings");
+        self.add_plain(" {");
         self.add_newline();
-        
-/*         if !core_imports.is_empty() {
+        self.indent_level += 1;
+
+        let size = |generator: &mut Self, (width, height): (f32, f32)| {
+            generator.add_type("Size");
+            generator.add_operator("::");
+            generator.add_function("new");
+            generator.add_plain("(");
+            generator.add_number(&format!("{:.1}", width));
+            generator.add_plain(", ");
+            generator.add_number(&format!("{:.1}", height));
+            generator.add_plain(")");
+        };
+        if (window.width, window.height) != (standard.width, standard.height) {
             self.add_indent();
-            self.add_plain(&core_imports.join(", "));
+            self.add_identifier("size");
+            self.add_plain(": ");
+            size(self, (window.width, window.height));
             self.add_plain(",");
             self.add_newline();
-        } */
-        
-        // Widget imports
-        if !tracker.used_widgets.is_empty() {
+        }
+        if let Some(min_size) = window.min_size {
             self.add_indent();
-            self.add_number("widget");
-            self.add_operator("::");
-            self.add_plain("{");
-            let mut widgets: Vec<_> = tracker.used_widgets.iter().map(|s| *s).collect();
-            widgets.sort();
-            self.add_plain(&widgets.join(", "));
-            self.add_plain("},");
+            self.add_identifier("min_size");
+            self.add_plain(": ");
+            self.add_type("Some");
+            self.add_plain("(");
+            size(self, min_size);
+            self.add_plain("),");
             self.add_newline();
         }
-        
-        // Mouse module - only if MouseArea is used
-        if tracker.uses_mouse {
+        if window.centered {
             self.add_indent();
-            self.add_plain("mouse");
-            
-            let mut mouse_items = Vec::new();
-            if tracker.uses_mouse_interaction {
-                mouse_items.push("Interaction");
-            }
-            if tracker.uses_mouse_scroll_delta {
-                mouse_items.push("ScrollDelta");
-            }
-            
-            if !mouse_items.is_empty() {
-                self.add_plain("::{");
-                self.add_plain(&mouse_items.join(", "));
-                self.add_plain("}");
-            }
-            self.add_plain(",");
+            self.add_identifier("position");
+            self.add_plain(": ");
+            self.add_type("window::Position");
+            self.add_operator("::");
+            self.add_plain("Centered,");
             self.add_newline();
         }
-        
-        // Text module - only if text properties are used
-        if tracker.uses_text_line_height || tracker.uses_text_wrapping || 
-        tracker.uses_text_shaping || tracker.uses_text_alignment {
-            self.add_indent();
-            self.add_plain("widget::text");
-            
-            let mut text_items = Vec::new();
-            if tracker.uses_text_line_height {
-                text_items.push("LineHeight");
-            }
-            if tracker.uses_text_wrapping {
-                text_items.push("Wrapping");
-            }
-            if tracker.uses_text_shaping {
-                text_items.push("Shaping");
-            }
-            if tracker.uses_text_alignment {
-                text_items.push("Alignment as TextAlignment");
-            }
-            
-            if !text_items.is_empty() {
-                self.add_plain("::{");
-                self.add_plain(&text_items.join(", "));
-                self.add_plain("}");
+        for (field, value, default) in [
+            ("resizable", window.resizable, standard.resizable),
+            ("decorations", window.decorations, standard.decorations),
+            ("fullscreen", window.fullscreen, standard.fullscreen),
+        ] {
+            if value != default {
+                self.add_indent();
+                self.add_identifier(field);
+                self.add_plain(": ");
+                self.add_keyword(if value { "true" } else { "false" });
+                self.add_plain(",");
+                self.add_newline();
             }
-            self.add_plain(",");
+        }
+        if window.always_on_top {
+            self.add_indent();
+            self.add_identifier("level");
+            self.add_plain(": ");
+            self.add_type("window::Level");
+            self.add_operator("::");
+            self.add_plain("AlwaysOnTop,");
             self.add_newline();
         }
-        
+
+        self.add_indent();
+        self.add_operator("..");
+        self.add_type("window::Settings");
+        self.add_operator("::");
+        self.add_function("default");
+        self.add_plain("()");
+        self.add_newline();
         self.indent_level -= 1;
-        self.add_plain("};");
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
         self.add_newline();
     }
 
-    // Collect which widgets are actually used
-    fn collect_used_widgets(&mut self, widget: &Widget) {
-        match widget.widget_type {
-            WidgetType::Container => self.used_widgets.insert("container"),
-            WidgetType::Row => self.used_widgets.insert("row"),
-            WidgetType::Column => self.used_widgets.insert("column"),
-            WidgetType::Button => self.used_widgets.insert("button"),
-            WidgetType::Text => self.used_widgets.insert("text"),
-            WidgetType::TextInput => self.used_widgets.insert("text_input"),
-            WidgetType::Checkbox => self.used_widgets.insert("checkbox"),
-            WidgetType::Radio => self.used_widgets.insert("radio"),
-            WidgetType::Slider => self.used_widgets.insert("slider"),
-            WidgetType::VerticalSlider => self.used_widgets.insert("vertical_slider"),
-            WidgetType::ProgressBar => self.used_widgets.insert("progress_bar"),
-            WidgetType::Toggler => self.used_widgets.insert("toggler"),
-            WidgetType::PickList => self.used_widgets.insert("pick_list"),
-            WidgetType::Scrollable => self.used_widgets.insert("scrollable"),
-            WidgetType::Space => self.used_widgets.insert("space"),
-            WidgetType::Rule => self.used_widgets.insert("rule"),
-            WidgetType::Image => self.used_widgets.insert("image"),
-            WidgetType::Svg => self.used_widgets.insert("svg"),
-            WidgetType::Tooltip => self.used_widgets.insert("tooltip"),
-            WidgetType::ComboBox => self.used_widgets.insert("combo_box"),
-            WidgetType::Markdown => self.used_widgets.insert("markdown"),
-            WidgetType::MouseArea => self.used_widgets.insert("mouse_area"),
-            WidgetType::QRCode => self.used_widgets.insert("qr_code"),
-            WidgetType::Stack => self.used_widgets.insert("stack"),
-            WidgetType::Themer => self.used_widgets.insert("themer"),
-            WidgetType::Pin => self.used_widgets.insert("pin"),
-        };
-        
-        for child in &widget.children {
-            self.collect_used_widgets(child);
-        }
+    fn generate_breakpoint_constant(&mut self, width: f32) {
+        self.add_comment("// Rows flagged in the builder stack into columns below this window width");
+        self.add_newline();
+        self.add_keyword("const");
+        self.add_plain(" ");
+        self.add_identifier("BREAKPOINT");
+        self.add_operator(":");
+        self.add_plain(" ");
+        self.add_type("f32");
+        self.add_plain(" ");
+        self.add_operator("=");
+        self.add_plain(" ");
+        self.add_number(&format!("{:.1}", width));
+        self.add_plain(";");
+        self.add_newline();
     }
 
-    fn generate_message_enum(&mut self) {
-        self.add_comment("// Application messages");
+    /// Message::WindowResized(width) => remember it for the flagged rows
+    fn generate_window_resized_arm(&mut self) {
+        self.add_indent();
+        self.add_type("Message");
+        self.add_operator("::");
+        self.add_plain("WindowResized(");
+        self.add_identifier("width");
+        self.add_plain(") ");
+        self.add_operator("=>");
+        self.add_plain(" {");
         self.add_newline();
-        self.add_plain("#[derive(Debug, Clone)]");
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_keyword("self");
+        self.add_operator(".");
+        self.add_identifier("window_width");
+        self.add_plain(" ");
+        self.add_operator("=");
+        self.add_plain(" ");
+        self.add_identifier("width");
+        self.add_plain(";");
         self.add_newline();
-        self.add_keyword("pub enum");
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+    }
+
+    /// A flagged Row as a block that builds its children once, then lays them out
+    /// in a `row` at or above the breakpoint and a `column` below it
+    fn generate_breakpoint_row(&mut self, widget: &Widget, use_self: bool) {
+        let props = &widget.properties;
+        self.add_indent();
+        self.add_plain("{");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_keyword("let");
+        self.add_plain(" ");
+        self.add_identifier("children");
+        self.add_operator(":");
         self.add_plain(" ");
+        self.add_type("Vec");
+        self.add_plain("<");
+        self.add_type("Element");
+        self.add_plain("<");
+        self.add_operator("'_");
+        self.add_plain(", ");
         self.add_type("Message");
-        self.add_plain(" {");
+        self.add_plain(">> ");
+        self.add_operator("=");
+        self.add_plain(" ");
+        self.add_macro("vec!");
+        self.add_plain("[");
         self.add_newline();
         self.indent_level += 1;
-        
-        // Collect all interactive widgets and generate message variants
-        self.generate_message_variants(&self.hierarchy.root().clone());
-        
+        if widget.children.is_empty() {
+            self.add_indent();
+            self.add_function("text");
+            self.add_plain("(");
+            self.add_string("\"Row Item\"");
+            self.add_plain(").into(),");
+            self.add_newline();
+        } else {
+            for child in &widget.children {
+                self.generate_widget_creation(child, use_self);
+                self.add_operator(".");
+                self.add_function("into");
+                self.add_plain("(),");
+                self.add_newline();
+            }
+        }
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("];");
+        self.add_newline();
+
+        self.add_indent();
+        self.add_keyword("if");
+        self.add_plain(" ");
+        self.add_state_receiver();
+        self.add_operator(".");
+        self.add_identifier("window_width");
+        self.add_plain(" ");
+        self.add_operator(">=");
+        self.add_plain(" ");
+        self.add_identifier("BREAKPOINT");
+        self.add_plain(" {");
+        self.add_newline();
+        for (function, is_row) in [("row", true), ("column", false)] {
+            self.indent_level += 1;
+            self.add_indent();
+            self.add_function(function);
+            self.add_plain("(");
+            self.add_identifier("children");
+            self.add_plain(")");
+            self.generate_layout_properties(props, is_row);
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("into");
+            self.add_plain("()");
+            self.add_newline();
+            self.indent_level -= 1;
+            self.add_indent();
+            self.add_plain("}");
+            if is_row {
+                self.add_plain(" ");
+                self.add_keyword("else");
+                self.add_plain(" {");
+            }
+            self.add_newline();
+        }
+
         self.indent_level -= 1;
+        self.add_indent();
         self.add_plain("}");
     }
 
-    fn generate_app_struct(&mut self) {
-        self.add_comment("// Application state");
+    /// The section ids and offsets the nav buttons scroll to
+    fn generate_section_constants(&mut self, sections: &[Section]) {
+        self.add_comment("// Section offsets, estimated from the builder's layout: adjust them if the real heights differ");
         self.add_newline();
-        self.add_keyword("struct");
+        self.add_keyword("const");
         self.add_plain(" ");
-        self.add_type(&self.app_name.clone());
+        self.add_identifier("SECTIONS_SCROLLABLE");
+        self.add_operator(":");
+        self.add_plain(" ");
+        self.add_operator("&");
+        self.add_type("str");
+        self.add_plain(" ");
+        self.add_operator("=");
+        self.add_plain(" ");
+        self.add_string(&format!("{:?}", SECTIONS_SCROLLABLE));
+        self.add_plain(";");
+        self.add_newline();
+        for section in sections {
+            self.add_keyword("const");
+            self.add_plain(" ");
+            self.add_identifier(&section.constant);
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_type("f32");
+            self.add_plain(" ");
+            self.add_operator("=");
+            self.add_plain(" ");
+            self.add_number(&format!("{:.1}", section.offset));
+            self.add_plain(";");
+            self.add_newline();
+        }
+    }
+
+    /// Message::ScrollToSection(offset) => scroll the sections scrollable to it
+    fn generate_section_match_arm(&mut self) {
+        self.add_indent();
+        self.add_type("Message");
+        self.add_operator("::");
+        self.add_plain("ScrollToSection(");
+        self.add_identifier("offset");
+        self.add_plain(") ");
+        self.add_operator("=>");
         self.add_plain(" {");
         self.add_newline();
         self.indent_level += 1;
-        
-        // Generate state fields for interactive widgets
-        self.generate_state_fields(&self.hierarchy.root().clone());
-        
+        self.add_indent();
+        self.add_keyword("return");
+        self.add_plain(" ");
+        self.add_number("iced");
+        self.add_operator("::");
+        self.add_plain("widget");
+        self.add_operator("::");
+        self.add_plain("operation");
+        self.add_operator("::");
+        self.add_function("scroll_to");
+        self.add_plain("(");
+        self.add_identifier("SECTIONS_SCROLLABLE");
+        self.add_plain(", ");
+        self.add_plain("scrollable");
+        self.add_operator("::");
+        self.add_type("AbsoluteOffset");
+        self.add_plain(" { x: ");
+        self.add_number("0.0");
+        self.add_plain(", y: ");
+        self.add_identifier("offset");
+        self.add_plain(" });");
+        self.add_newline();
         self.indent_level -= 1;
+        self.add_indent();
         self.add_plain("}");
+        self.add_newline();
     }
 
-    fn generate_message_variants(&mut self, widget: &Widget) {
-        let name = self.get_widget_name(widget.id);
-        
-        match widget.widget_type {
-            WidgetType::Button => {
-                self.add_indent();
-                self.add_plain(&format!("{}Pressed", to_pascal_case(&name)));
-                self.add_plain(",");
-                self.add_newline();
-            }
-            WidgetType::TextInput => {
-                let name = self.get_widget_name(widget.id);
-                let props = &widget.properties;
-                
-                self.add_indent();
-                self.add_plain(&format!("{}Changed", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_type("String");
-                self.add_plain("),");
-                self.add_newline();
-                
-                if props.text_input_on_submit {
-                    self.add_indent();
-                    self.add_plain(&format!("{}Submitted", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                
-                if props.text_input_on_paste {
-                    self.add_indent();
-                    self.add_plain(&format!("{}Pasted", to_pascal_case(&name)));
-                    self.add_plain("(");
-                    self.add_type("String");
-                    self.add_plain("),");
-                    self.add_newline();
-                }
-            }
-            WidgetType::Checkbox => {
-                self.add_indent();
-                self.add_plain(&format!("{}Toggled", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_type("bool");
-                self.add_plain("),");
-                self.add_newline();
-            }
-            WidgetType::Radio => {
-                self.add_indent();
-                self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_type("usize");
-                self.add_plain("),");
-                self.add_newline();
-            }
-            WidgetType::Slider | WidgetType::VerticalSlider => {
-                self.add_indent();
-                self.add_plain(&format!("{}Changed", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_type("f32");
-                self.add_plain("),");
-                self.add_newline();
-            }
-            WidgetType::Toggler => {
-                self.add_indent();
-                self.add_plain(&format!("{}Toggled", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_type("bool");
-                self.add_plain("),");
-                self.add_newline();
+    /// Read off every Column child for the nav row, whatever the child's own type
+    const SECTION_EMITS: &'static [&'static str] = &["section_anchor", "section_name"];
+    /// A row of section buttons kept above the scrollable the content moves in
+    fn generate_section_nav(&mut self, root: &Widget, use_self: bool, sections: &[Section]) {
+        self.add_indent();
+        self.add_macro("column!");
+        self.add_plain("[");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_macro("row!");
+        self.add_plain("[");
+        self.add_newline();
+        self.indent_level += 1;
+        for section in sections {
+            self.add_indent();
+            self.add_function("button");
+            self.add_plain("(");
+            self.add_string(&format!("{:?}", section.name));
+            self.add_plain(")");
+            self.add_operator(".");
+            self.add_function("on_press");
+            self.add_plain("(");
+            self.add_type("Message");
+            self.add_operator("::");
+            self.add_plain("ScrollToSection(");
+            self.add_identifier(&section.constant);
+            self.add_plain(")),");
+            self.add_newline();
+        }
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("]");
+        self.indent_level += 1;
+        self.chain_break();
+        self.add_operator(".");
+        self.add_function("spacing");
+        self.add_plain("(");
+        self.add_number("10");
+        self.add_plain(")");
+        self.chain_break();
+        self.add_operator(".");
+        self.add_function("padding");
+        self.add_plain("(");
+        self.add_number("10");
+        self.add_plain(")");
+        self.indent_level -= 1;
+        self.add_plain(",");
+        self.add_newline();
+
+        self.add_indent();
+        self.add_function("scrollable");
+        self.add_plain("(");
+        self.add_newline();
+        self.indent_level += 1;
+        self.generate_widget_creation(root, use_self);
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain(")");
+        self.indent_level += 1;
+        self.chain_break();
+        self.add_operator(".");
+        self.add_function("id");
+        self.add_plain("(");
+        self.add_identifier("SECTIONS_SCROLLABLE");
+        self.add_plain(")");
+        self.indent_level -= 1;
+        self.add_plain(",");
+        self.add_newline();
+
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("]");
+        self.add_newline();
+    }
+
+    fn generate_window_message_variants(&mut self) {
+        self.add_indent();
+        self.add_plain("WindowOpened(");
+        self.add_type("window::Id");
+        self.add_plain(", ");
+        self.add_type("WindowEnum");
+        self.add_plain("),");
+        self.add_newline();
+
+        self.add_indent();
+        self.add_plain("WindowClosed(");
+        self.add_type("window::Id");
+        self.add_plain("),");
+        self.add_newline();
+
+        self.add_indent();
+        self.add_plain("RequestOpenWindow(");
+        self.add_type("WindowEnum");
+        self.add_plain("),");
+        self.add_newline();
+
+        self.metrics.message_variants += 3;
+    }
+
+    /// Opening, recording and forgetting windows. The last window closing exits the app.
+    fn generate_window_match_arms(&mut self) {
+        // Message::RequestOpenWindow(window_type) => { open, then report the id }
+        self.add_indent();
+        self.add_type("Message");
+        self.add_operator("::");
+        self.add_plain("RequestOpenWindow(");
+        self.add_identifier("window_type");
+        self.add_plain(") ");
+        self.add_operator("=>");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_keyword("let");
+        self.add_plain(" (");
+        self.add_identifier("_id");
+        self.add_plain(", ");
+        self.add_identifier("open");
+        self.add_plain(") ");
+        self.add_operator("=");
+        self.add_plain(" ");
+        self.add_number("window");
+        self.add_operator("::");
+        self.add_function("open");
+        self.add_plain("(");
+        if self.custom_window() {
+            self.add_function("window_settings");
+            self.add_plain("());");
+        } else {
+            self.add_type("window::Settings");
+            self.add_operator("::");
+            self.add_function("default");
+            self.add_plain("());");
+        }
+        self.add_newline();
+        self.add_indent();
+        self.add_keyword("return");
+        self.add_plain(" ");
+        self.add_identifier("open");
+        self.add_operator(".");
+        self.add_function("map");
+        self.add_plain("(");
+        self.add_keyword("move");
+        self.add_plain(" |");
+        self.add_identifier("id");
+        self.add_plain("| ");
+        self.add_type("Message");
+        self.add_operator("::");
+        self.add_plain("WindowOpened(");
+        self.add_identifier("id");
+        self.add_plain(", ");
+        self.add_identifier("window_type");
+        self.add_plain("));");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+
+        // Message::WindowOpened(window_id, window_type) => { title it and keep it }
+        self.add_indent();
+        self.add_type("Message");
+        self.add_operator("::");
+        self.add_plain("WindowOpened(");
+        self.add_identifier("window_id");
+        self.add_plain(", ");
+        self.add_identifier("window_type");
+        self.add_plain(") ");
+        self.add_operator("=>");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_keyword("let");
+        self.add_plain(" ");
+        self.add_identifier("title");
+        self.add_plain(" ");
+        self.add_operator("=");
+        self.add_plain(" ");
+        self.add_keyword("match");
+        self.add_plain(" ");
+        self.add_identifier("window_type");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_type("WindowEnum");
+        self.add_operator("::");
+        self.add_plain("Main ");
+        self.add_operator("=>");
+        self.add_plain(" ");
+        self.add_type("String");
+        self.add_operator("::");
+        self.add_function("from");
+        self.add_plain("(");
+        self.add_string(&format!("\"{}\"", self.app_window_title));
+        self.add_plain("),");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("};");
+        self.add_newline();
+        self.add_indent();
+        self.add_keyword("self");
+        self.add_operator(".");
+        self.add_identifier("windows");
+        self.add_operator(".");
+        self.add_function("insert");
+        self.add_plain("(");
+        self.add_identifier("window_id");
+        self.add_plain(", ");
+        self.add_type("Window");
+        self.add_plain(" { ");
+        self.add_identifier("title");
+        self.add_plain(", ");
+        self.add_identifier("windowtype");
+        self.add_operator(":");
+        self.add_plain(" ");
+        self.add_identifier("window_type");
+        self.add_plain(" });");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+
+        // Message::WindowClosed(window_id) => { forget it, exit after the last one }
+        self.add_indent();
+        self.add_type("Message");
+        self.add_operator("::");
+        self.add_plain("WindowClosed(");
+        self.add_identifier("window_id");
+        self.add_plain(") ");
+        self.add_operator("=>");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_keyword("self");
+        self.add_operator(".");
+        self.add_identifier("windows");
+        self.add_operator(".");
+        self.add_function("remove");
+        self.add_plain("(");
+        self.add_operator("&");
+        self.add_identifier("window_id");
+        self.add_plain(");");
+        self.add_newline();
+        self.add_indent();
+        self.add_keyword("if");
+        self.add_plain(" ");
+        self.add_keyword("self");
+        self.add_operator(".");
+        self.add_identifier("windows");
+        self.add_operator(".");
+        self.add_function("is_empty");
+        self.add_plain("() {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_keyword("return");
+        self.add_plain(" ");
+        self.add_number("iced");
+        self.add_operator("::");
+        self.add_function("exit");
+        self.add_plain("();");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+    }
+
+    /// `title(window_id)`, read from the open windows
+    fn generate_window_title_method(&mut self) {
+        self.add_indent();
+        self.add_keyword("fn");
+        self.add_plain(" ");
+        self.add_function("title");
+        self.add_plain("(");
+        self.add_operator("&");
+        self.add_keyword("self");
+        self.add_plain(", ");
+        self.add_identifier("window_id");
+        self.add_operator(":");
+        self.add_plain(" ");
+        self.add_type("window::Id");
+        self.add_plain(") ");
+        self.add_operator("->");
+        self.add_plain(" ");
+        self.add_type("String");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_keyword("self");
+        self.add_operator(".");
+        self.add_identifier("windows");
+        self.add_operator(".");
+        self.add_function("get");
+        self.add_plain("(");
+        self.add_operator("&");
+        self.add_identifier("window_id");
+        self.add_plain(")");
+        self.add_operator(".");
+        self.add_function("map");
+        self.add_plain("(|");
+        self.add_identifier("window");
+        self.add_plain("| ");
+        self.add_identifier("window");
+        self.add_operator(".");
+        self.add_identifier("title");
+        self.add_operator(".");
+        self.add_function("clone");
+        self.add_plain("())");
+        self.add_operator(".");
+        self.add_function("unwrap_or_default");
+        self.add_plain("()");
+        self.add_newline();
+
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+    }
+
+    /// Window close events, so the app can forget closed windows and exit after the last
+    fn generate_subscription_method(&mut self) {
+        self.add_indent();
+        self.add_keyword("fn");
+        self.add_plain(" ");
+        self.add_function("subscription");
+        self.add_plain("(");
+        self.add_operator("&");
+        self.add_keyword("self");
+        self.add_plain(") ");
+        self.add_operator("->");
+        self.add_plain(" ");
+        self.add_type("Subscription");
+        self.add_plain("<");
+        self.add_type("Message");
+        self.add_plain("> {");
+        self.add_newline();
+        self.indent_level += 1;
+
+        let batched = self.multi_window && self.breakpoint().is_some();
+        if batched {
+            self.add_indent();
+            self.add_type("Subscription");
+            self.add_operator("::");
+            self.add_function("batch");
+            self.add_plain("([");
+            self.add_newline();
+            self.indent_level += 1;
+        }
+        if self.multi_window {
+            self.add_indent();
+            self.add_number("window");
+            self.add_operator("::");
+            self.add_function("close_events");
+            self.add_plain("()");
+            self.add_operator(".");
+            self.add_function("map");
+            self.add_plain("(");
+            self.add_type("Message");
+            self.add_operator("::");
+            self.add_plain("WindowClosed)");
+            if batched {
+                self.add_plain(",");
+            }
+            self.add_newline();
+        }
+        if self.breakpoint().is_some() {
+            self.add_indent();
+            self.add_number("window");
+            self.add_operator("::");
+            self.add_function("resize_events");
+            self.add_plain("()");
+            self.add_operator(".");
+            self.add_function("map");
+            self.add_plain("(");
+            self.add_operator("|");
+            self.add_plain("(");
+            self.add_identifier("_id");
+            self.add_plain(", ");
+            self.add_identifier("size");
+            self.add_plain(")");
+            self.add_operator("|");
+            self.add_plain(" ");
+            self.add_type("Message");
+            self.add_operator("::");
+            self.add_plain("WindowResized(");
+            self.add_identifier("size");
+            self.add_operator(".");
+            self.add_identifier("width");
+            self.add_plain("))");
+            if batched {
+                self.add_plain(",");
+            }
+            self.add_newline();
+        }
+        if batched {
+            self.indent_level -= 1;
+            self.add_indent();
+            self.add_plain("])");
+            self.add_newline();
+        }
+
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+    }
+
+    /// `view(window_id)` picking the layout by kind of window, with an error view for unknown ids
+    fn generate_window_view_method(&mut self) {
+        self.add_indent();
+        self.add_keyword("fn");
+        self.add_plain(" ");
+        self.add_function("view");
+        self.add_plain("<");
+        self.add_operator("'a");
+        self.add_plain(">(");
+        self.add_operator("&'a ");
+        self.add_type("self");
+        self.add_plain(", ");
+        self.add_identifier("window_id");
+        self.add_operator(":");
+        self.add_plain(" ");
+        self.add_type("window::Id");
+        self.add_plain(")");
+        self.add_operator(" -> ");
+        self.add_type("Element");
+        self.add_plain("<");
+        self.add_operator("'a");
+        self.add_plain(", ");
+        self.add_operator("Message");
+        self.add_plain("> {");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_keyword("match");
+        self.add_plain(" ");
+        self.add_keyword("self");
+        self.add_operator(".");
+        self.add_identifier("windows");
+        self.add_operator(".");
+        self.add_function("get");
+        self.add_plain("(");
+        self.add_operator("&");
+        self.add_identifier("window_id");
+        self.add_plain(")");
+        self.add_operator(".");
+        self.add_function("map");
+        self.add_plain("(|");
+        self.add_identifier("window");
+        self.add_plain("| ");
+        self.add_identifier("window");
+        self.add_operator(".");
+        self.add_identifier("windowtype");
+        self.add_plain(") {");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_type("Some");
+        self.add_plain("(");
+        self.add_type("WindowEnum");
+        self.add_operator("::");
+        self.add_plain("Main) ");
+        self.add_operator("=>");
+        self.add_plain(" ");
+        self.add_keyword("self");
+        self.add_operator(".");
+        self.add_function("view_main");
+        self.add_plain("(),");
+        self.add_newline();
+
+        self.add_indent();
+        self.add_type("None");
+        self.add_plain(" ");
+        self.add_operator("=>");
+        self.add_plain(" ");
+        self.add_number("iced");
+        self.add_operator("::");
+        self.add_number("widget");
+        self.add_operator("::");
+        self.add_function("text");
+        self.add_plain("(");
+        self.add_macro("format!");
+        self.add_plain("(");
+        self.add_string("\"Something has gone terribly wrong. Window Id: {:?}\"");
+        self.add_plain(", ");
+        self.add_identifier("window_id");
+        self.add_plain("))");
+        self.add_operator(".");
+        self.add_function("into");
+        self.add_plain("(),");
+        self.add_newline();
+
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+    }
+
+    /// With `used`, only what the code names out of it is imported
+    fn generate_imports(&mut self, used: Option<&HashSet<&str>>) {
+        // Scan the entire hierarchy
+        let mut tracker = ImportTracker::new();
+        tracker.scan_widget(&self.hierarchy.root().clone());
+        if self.layout_only {
+            tracker.use_layout_stand_ins();
+        }
+        if !self.nav_sections().is_empty() {
+            tracker.used_widgets.extend(["button", "column", "row", "scrollable"]);
+        }
+        // Flagged rows pick `row(children)` or `column(children)` at runtime
+        if self.breakpoint().is_some() {
+            tracker.used_widgets.extend(["column", "row"]);
+        }
+        // Named style functions spell out the border, the rest is tracked from the widgets' own fields
+        if !self.used_named_styles().is_empty() {
+            tracker.uses_border = true;
+            tracker.uses_color = true;
+        }
+        // A custom theme's palette is written out as colors
+        if builtin_theme_variant(&self.theme).is_none() {
+            tracker.uses_color = true;
+        }
+        
+        self.add_keyword("use");
+        self.add_number(" iced::");
+        self.add_plain("{");
+        self.add_newline();
+        self.indent_level += 1;
+        
+        // Core types - build list
+        let mut core_imports = Vec::new();
+        
+        if tracker.uses_length {
+            core_imports.push("Length");
+        }
+        if tracker.uses_alignment {
+            core_imports.push("Alignment");
+        }
+        if tracker.uses_color {
+            core_imports.push("Color");
+        }
+        if tracker.uses_padding {
+            core_imports.push("Padding");
+        }
+        if tracker.uses_font {
+            core_imports.push("Font");
+        }
+        if tracker.uses_border {
+            core_imports.push("Border");
+        }
+        if tracker.uses_shadow {
+            core_imports.push("Shadow");
+        }
+        if tracker.uses_background {
+            core_imports.push("Background");
+        }
+        if tracker.uses_vector {
+            core_imports.push("Vector");
+        }
+        if tracker.uses_point {
+            core_imports.push("Point");
+        }
+        if tracker.uses_pixels {
+            core_imports.push("Pixels");
+        }
+        if tracker.uses_gradient {
+            core_imports.push("Radians");
+        }
+        
+        // Element, Theme, and Task are always needed
+        core_imports.push("Element");
+        core_imports.push("Theme");
+        if !self.layout_only {
+            core_imports.push("Task");
+        }
+        let multi_window = self.multi_window && !self.layout_only;
+        let window_events = multi_window || self.breakpoint().is_some();
+        if window_events {
+            core_imports.push("Subscription");
+        }
+        let custom_window = self.custom_window();
+        if custom_window && self.window.names_a_size() {
+            core_imports.push("Size");
+        }
+        // `Alignment as TextAlignment` is named by its alias
+        let named = |import: &&str| used.is_none_or(|words| words.contains(import.rsplit(' ').next().unwrap_or(import)));
+        core_imports.retain(named);
+
+        self.add_indent();
+        core_imports.into_iter().for_each(|import| {
+            self.add_type(import);
+            self.add_plain(",");
+        });
+        self.add_newline();
+        
+/*         if !core_imports.is_empty() {
+            self.add_indent();
+            self.add_plain(&core_imports.join(", "));
+            self.add_plain(",");
+            self.add_newline();
+        } */
+        
+        // Widget imports
+        let mut widgets: Vec<_> = tracker.used_widgets.iter().copied().filter(named).collect();
+        if !widgets.is_empty() {
+            self.add_indent();
+            self.add_number("widget");
+            self.add_operator("::");
+            self.add_plain("{");
+            widgets.sort();
+            self.add_plain(&widgets.join(", "));
+            self.add_plain("},");
+            self.add_newline();
+        }
+        
+        // Window module - only for the multi-window scaffold, breakpoint tracking and window settings
+        if window_events || custom_window {
+            self.add_indent();
+            self.add_plain("window,");
+            self.add_newline();
+        }
+
+        // Gradient module - only if a container draws a gradient
+        if tracker.uses_gradient && named(&"gradient") {
+            self.add_indent();
+            self.add_plain("gradient,");
+            self.add_newline();
+        }
+
+        // Menu styles - only if a combo box overrides its menu
+        if tracker.uses_overlay_menu && named(&"menu") {
+            self.add_indent();
+            self.add_plain("overlay::menu,");
+            self.add_newline();
+        }
+
+        // Mouse module - only if MouseArea is used
+        if tracker.uses_mouse && named(&"mouse") {
+            self.add_indent();
+            self.add_plain("mouse");
+            
+            let mut mouse_items = Vec::new();
+            if tracker.uses_mouse_interaction {
+                mouse_items.push("Interaction");
             }
-            WidgetType::PickList => {
-                self.add_indent();
-                self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_type("String");
-                self.add_plain("),");
-                self.add_newline();
+            if tracker.uses_mouse_scroll_delta {
+                mouse_items.push("ScrollDelta");
             }
-            WidgetType::ComboBox => {
-                let props = &widget.properties;
-                
-                // Determine the type parameter based on whether enum is used
-                let type_name = if let Some(ref enum_id) = props.referenced_enum {
-                    if let Some(enum_def) = self.type_system.unwrap().get_enum(enum_id.clone()) {
-                        enum_def.name.clone()
-                    } else {
-                        "String".to_string()
-                    }
-                } else {
-                    "String".to_string()
-                };
-                
-                // Always generate Selected message
-                self.add_indent();
-                self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_type(&type_name);
-                self.add_plain("),");
-                self.add_newline();
-                
-                // Conditionally generate on_input
-                if props.combobox_use_on_input {
-                    self.add_indent();
-                    self.add_plain(&format!("{}OnInput", to_pascal_case(&name)));
-                    self.add_plain("(");
-                    self.add_type("String");
-                    self.add_plain("),");
-                    self.add_newline();
-                }
-                
-                // Conditionally generate on_option_hovered
-                if props.combobox_use_on_option_hovered {
-                    self.add_indent();
-                    self.add_plain(&format!("{}OnOptionHovered", to_pascal_case(&name)));
-                    self.add_plain("(");
-                    self.add_type(&type_name);
-                    self.add_plain("),");
-                    self.add_newline();
-                }
-                
-                // Conditionally generate on_open
-                if props.combobox_use_on_open {
-                    self.add_indent();
-                    self.add_plain(&format!("{}OnOpen", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                
-                // Conditionally generate on_close
-                if props.combobox_use_on_close {
-                    self.add_indent();
-                    self.add_plain(&format!("{}OnClose", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
+            mouse_items.retain(named);
+            
+            if !mouse_items.is_empty() {
+                self.add_plain("::{");
+                self.add_plain(&mouse_items.join(", "));
+                self.add_plain("}");
             }
-            WidgetType::MouseArea => {
-                let name = self.get_widget_name(widget.id);
-                let props = &widget.properties;
-                
-                if props.mousearea_on_press {
-                    self.add_indent();
-                    self.add_plain(&format!("{}Pressed", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                if props.mousearea_on_release {
-                    self.add_indent();
-                    self.add_plain(&format!("{}Released", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                if props.mousearea_on_double_click {
-                    self.add_indent();
-                    self.add_plain(&format!("{}DoubleClicked", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                if props.mousearea_on_right_press {
-                    self.add_indent();
-                    self.add_plain(&format!("{}RightPressed", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                if props.mousearea_on_right_release {
-                    self.add_indent();
-                    self.add_plain(&format!("{}RightReleased", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                if props.mousearea_on_middle_press {
-                    self.add_indent();
-                    self.add_plain(&format!("{}MiddlePressed", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                if props.mousearea_on_middle_release {
-                    self.add_indent();
-                    self.add_plain(&format!("{}MiddleReleased", to_pascal_case(&name)));
-                    self.add_plain(",");
-                    self.add_newline();
-                }
-                if props.mousearea_on_scroll {
-                    self.add_indent();
-                    self.add_plain(&format!("{}Scrolled", to_pascal_case(&name)));
-                    self.add_plain("(mouse::ScrollDelta),");
-                    self.add_newline();
-                }
-                if props.mousearea_on_enter {
-                    self.add_indent();
-                    self.add_plain(&format!("{}Entered", to_pascal_case(&name)));
-                    self.add_plain("(Point),");
-                    self.add_newline();
-                }
-                if props.mousearea_on_move {
-                    self.add_indent();
-                    self.add_plain(&format!("{}Moved", to_pascal_case(&name)));
-                    self.add_plain("(Point),");
-                    self.add_newline();
+            self.add_plain(",");
+            self.add_newline();
+        }
+        
+        // Text module - only if text properties are used
+        let mut text_items = Vec::new();
+        if tracker.uses_text_line_height {
+            text_items.push("LineHeight");
+        }
+        if tracker.uses_text_wrapping {
+            text_items.push("Wrapping");
+        }
+        if tracker.uses_text_shaping {
+            text_items.push("Shaping");
+        }
+        if tracker.uses_text_alignment {
+            text_items.push("Alignment as TextAlignment");
+        }
+        text_items.retain(named);
+        if !text_items.is_empty() {
+            self.add_indent();
+            self.add_plain("widget::text");
+            self.add_plain("::{");
+            self.add_plain(&text_items.join(", "));
+            self.add_plain("}");
+            self.add_plain(",");
+            self.add_newline();
+        }
+        
+        self.indent_level -= 1;
+        self.add_plain("};");
+        self.add_newline();
+
+        if multi_window {
+            self.add_keyword("use");
+            self.add_plain(" std::collections::");
+            self.add_type("BTreeMap");
+            self.add_plain(";");
+            self.add_newline();
+        }
+        if !self.layout_only && uses_file_dialogs(self.hierarchy.root()) {
+            self.add_keyword("use");
+            self.add_plain(" std::path::");
+            self.add_type("PathBuf");
+            self.add_plain(";");
+            self.add_newline();
+        }
+    }
+
+    // Collect which widgets are actually used
+    fn collect_used_widgets(&mut self, widget: &Widget) {
+        match widget.widget_type {
+            WidgetType::Container => self.used_widgets.insert("container"),
+            WidgetType::Row => self.used_widgets.insert("row"),
+            WidgetType::Column => self.used_widgets.insert("column"),
+            WidgetType::Button => self.used_widgets.insert("button"),
+            WidgetType::Text => self.used_widgets.insert("text"),
+            WidgetType::TextInput => self.used_widgets.insert("text_input"),
+            WidgetType::Checkbox => self.used_widgets.insert("checkbox"),
+            WidgetType::Radio => self.used_widgets.insert("radio"),
+            WidgetType::Slider => self.used_widgets.insert("slider"),
+            WidgetType::VerticalSlider => self.used_widgets.insert("vertical_slider"),
+            WidgetType::ProgressBar => self.used_widgets.insert("progress_bar"),
+            WidgetType::Toggler => self.used_widgets.insert("toggler"),
+            WidgetType::PickList => self.used_widgets.insert("pick_list"),
+            WidgetType::Scrollable => self.used_widgets.insert("scrollable"),
+            WidgetType::Space => self.used_widgets.insert("space"),
+            WidgetType::Rule => self.used_widgets.insert("rule"),
+            WidgetType::Image => self.used_widgets.insert("image"),
+            WidgetType::Svg => self.used_widgets.insert("svg"),
+            WidgetType::Tooltip => self.used_widgets.insert("tooltip"),
+            WidgetType::ComboBox => self.used_widgets.insert("combo_box"),
+            WidgetType::Markdown => self.used_widgets.insert("markdown"),
+            WidgetType::MouseArea => self.used_widgets.insert("mouse_area"),
+            WidgetType::QRCode => self.used_widgets.insert("qr_code"),
+            WidgetType::Stack => self.used_widgets.insert("stack"),
+            WidgetType::Themer => self.used_widgets.insert("themer"),
+            WidgetType::Pin => self.used_widgets.insert("pin"),
+        };
+        
+        for child in &widget.children {
+            self.collect_used_widgets(child);
+        }
+    }
+
+    fn generate_message_enum(&mut self) {
+        self.add_comment("// Application messages");
+        self.add_newline();
+        self.add_plain("#[derive(Debug, Clone)]");
+        self.add_newline();
+        self.add_keyword("pub enum");
+        self.add_plain(" ");
+        self.add_type("Message");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        
+        // Collect all interactive widgets and generate message variants
+        self.generate_message_variants(&self.hierarchy.root().clone());
+        if self.multi_window {
+            self.generate_window_message_variants();
+        }
+        if !self.nav_sections().is_empty() {
+            self.add_indent();
+            self.add_plain("ScrollToSection(");
+            self.add_type("f32");
+            self.add_plain("),");
+            self.add_newline();
+            self.metrics.message_variants += 1;
+        }
+        if self.breakpoint().is_some() {
+            self.add_indent();
+            self.add_plain("WindowResized(");
+            self.add_type("f32");
+            self.add_plain("),");
+            self.add_newline();
+            self.metrics.message_variants += 1;
+        }
+        
+        self.indent_level -= 1;
+        self.add_plain("}");
+    }
+
+    fn generate_app_struct(&mut self) {
+        self.add_comment("// Application state");
+        self.add_newline();
+        if self.derive_default && !self.needs_default_impl() {
+            self.add_plain("#[derive(Default)]");
+            self.add_newline();
+        }
+        self.add_keyword("struct");
+        self.add_plain(" ");
+        self.add_type(&self.app_name.clone());
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        
+        if self.multi_window {
+            self.add_indent();
+            self.add_identifier("windows");
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_type("BTreeMap");
+            self.add_operator("<");
+            self.add_type("window::Id");
+            self.add_plain(", ");
+            self.add_type("Window");
+            self.add_operator(">");
+            self.add_plain(",");
+            self.add_newline();
+            self.metrics.state_fields += 1;
+        }
+        if self.breakpoint().is_some() {
+            self.add_indent();
+            self.add_comment("// Last reported window width, compared against BREAKPOINT in view()");
+            self.add_newline();
+            self.add_indent();
+            self.add_identifier("window_width");
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_type("f32");
+            self.add_plain(",");
+            self.add_newline();
+            self.metrics.state_fields += 1;
+        }
+
+        // Generate state fields for interactive widgets
+        self.generate_state_fields(&self.hierarchy.root().clone());
+
+        let mut flags = Vec::new();
+        condition_flags(self.hierarchy.root(), &mut flags);
+        if !flags.is_empty() {
+            self.add_indent();
+            self.add_comment("// Conditional style flags, set them from update()");
+            self.add_newline();
+        }
+        for flag in flags {
+            self.add_indent();
+            self.add_identifier(&flag);
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_type("bool");
+            self.add_plain(",");
+            self.add_newline();
+            self.metrics.state_fields += 1;
+        }
+        
+        self.indent_level -= 1;
+        self.add_plain("}");
+    }
+
+    fn generate_message_variants(&mut self, widget: &Widget) {
+        let name = self.get_widget_name(widget.id);
+        let variants = message_variants(widget, &name, self.type_system);
+        self.metrics.message_variants += variants.len();
+
+        if self.doc_comments && !variants.is_empty() {
+            self.generate_widget_doc_comment(widget);
+        }
+
+        for variant in variants {
+            self.add_indent();
+            self.add_variant(WiringPass::Declared, &variant.name);
+            match variant.payload {
+                VariantPayload::None => self.add_plain(","),
+                VariantPayload::Type(type_name) => {
+                    self.add_plain("(");
+                    self.add_type(&type_name);
+                    self.add_plain("),");
                 }
-                if props.mousearea_on_exit {
-                    self.add_indent();
-                    self.add_plain(&format!("{}Exited", to_pascal_case(&name)));
-                    self.add_plain("(Point),");
-                    self.add_newline();
+                VariantPayload::Plain(payload) => self.add_plain(&format!("({}),", payload)),
+                VariantPayload::ChosenPath => {
+                    self.add_plain("(");
+                    self.add_type("Option");
+                    self.add_operator("<");
+                    self.add_type("PathBuf");
+                    self.add_operator(">");
+                    self.add_plain("),");
                 }
             }
-            _ => {}
+            self.add_newline();
         }
-        
+
         for child in &widget.children {
             self.generate_message_variants(child);
         }
@@ -1361,6 +2915,35 @@ impl<'a> CodeGenerator<'a> {
     fn generate_state_fields(&mut self, widget: &Widget) {
         let name = self.get_widget_name(widget.id);
         let props = &widget.properties;
+
+        let has_state = matches!(
+            widget.widget_type,
+            WidgetType::TextInput | WidgetType::Checkbox | WidgetType::Radio | WidgetType::Slider |
+            WidgetType::VerticalSlider | WidgetType::Toggler | WidgetType::PickList | WidgetType::ComboBox |
+            WidgetType::Markdown
+        );
+        let has_path = file_dialog_of(widget) != FileDialog::None;
+        if self.doc_comments && (has_state || has_path) {
+            self.generate_widget_doc_comment(widget);
+        }
+        // Combo boxes keep their `combo_box::State` next to the value
+        self.metrics.state_fields += match widget.widget_type {
+            WidgetType::ComboBox => 2,
+            _ => has_state as usize,
+        } + has_path as usize;
+
+        if has_path {
+            self.add_indent();
+            self.add_identifier(&format!("{}_path", to_snake_case(&name)));
+            self.add_operator(":");
+            self.add_plain(" ");
+            self.add_type("Option");
+            self.add_operator("<");
+            self.add_type("PathBuf");
+            self.add_operator(">");
+            self.add_plain(",");
+            self.add_newline();
+        }
         
         match widget.widget_type {
             WidgetType::TextInput => {
@@ -1420,6 +3003,15 @@ impl<'a> CodeGenerator<'a> {
                 self.add_plain(",");
                 self.add_newline();
             }
+            WidgetType::Markdown => {
+                self.add_indent();
+                self.add_identifier(&format!("{}_content", to_snake_case(&name)));
+                self.add_operator(":");
+                self.add_plain(" ");
+                self.add_type("markdown::Content");
+                self.add_plain(",");
+                self.add_newline();
+            }
             WidgetType::ComboBox => {
                 if let Some(ref enum_id) = props.referenced_enum {
                     if let Some(enum_def) = self.type_system.unwrap().get_enum(enum_id.clone()) {
@@ -1466,46 +3058,280 @@ impl<'a> CodeGenerator<'a> {
                 self.add_plain(",");
                 self.add_newline();
             }
-            _ => {}
-        }
-        
-        for child in &widget.children {
-            self.generate_state_fields(child);
-        }
-    }
-
-    fn generate_update_match_arms(&mut self, widget: &Widget) {
-        let name = self.get_widget_name(widget.id);
-        let props = &widget.properties;
-        
-        match widget.widget_type {
-            WidgetType::Button => {
+            _ => {}
+        }
+        
+        for child in &widget.children {
+            self.generate_state_fields(child);
+        }
+    }
+
+    fn generate_update_match_arms(&mut self, widget: &Widget) {
+        let name = self.get_widget_name(widget.id);
+        let props = &widget.properties;
+        
+        match widget.widget_type {
+            WidgetType::Button => {
+                self.add_indent();
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Matched, &format!("{}Pressed", to_pascal_case(&name)));
+                self.add_plain(" ");
+                self.add_operator("=>");
+                self.add_plain(" {");
+                self.add_newline();
+                self.indent_level += 1;
+                match file_dialog_of(widget) {
+                    FileDialog::None => self.add_handler_stub("// Handle button press", &name, "press", &[]),
+                    dialog => self.add_file_dialog_task(&name, dialog),
+                }
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("}");
+                self.add_newline();
+                self.generate_file_chosen_arm(widget, &name);
+            }
+            WidgetType::TextInput => {
+                let name = self.get_widget_name(widget.id);
+                let props = &widget.properties;
+                
+                // Always generate the Changed handler
+                self.add_indent();
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Matched, &format!("{}Changed", to_pascal_case(&name)));
+                self.add_plain("(");
+                self.add_identifier("value");
+                self.add_plain(") ");
+                self.add_operator("=>");
+                self.add_plain(" {");
+                self.add_newline();
+                self.indent_level += 1;
+                self.add_indent();
+                self.add_keyword("self");
+                self.add_operator(".");
+                self.add_identifier(&format!("{}_value", to_snake_case(&name)));
+                self.add_plain(" ");
+                self.add_operator("=");
+                self.add_plain(" ");
+                self.add_identifier("value");
+                self.add_plain(";");
+                self.add_newline();
+                self.add_event_log(&name, "changed", &[format!("self.{}_value", to_snake_case(&name)).as_str()]);
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("}");
+                self.add_newline();
+                
+                // Conditionally generate on_submit handler
+                if props.text_input_on_submit {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}Submitted", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle text input submission (Enter key pressed)", &name, "submit", &[format!("self.{}_value", to_snake_case(&name)).as_str()]);
+                    self.add_indent();
+                    self.add_comment(&format!("// Current value: self.{}_value", to_snake_case(&name)));
+                    self.add_newline();
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+                
+                // Conditionally generate on_paste handler
+                if props.text_input_on_paste {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}Pasted", to_pascal_case(&name)));
+                    self.add_plain("(");
+                    self.add_identifier("pasted_text");
+                    self.add_plain(") ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_indent();
+                    self.add_comment("// Handle text being pasted");
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_comment("// pasted_text contains the pasted string");
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_comment("// Note: on_input will also fire with the new combined value");
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_keyword("self");
+                    self.add_operator(".");
+                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=");
+                    self.add_plain(" ");
+                    self.add_identifier("pasted_text");
+                    self.add_plain(";");
+                    self.add_newline();
+                    self.add_event_log(&name, "pasted", &[format!("self.{}_value", to_snake_case(&name)).as_str()]);
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+            }
+            WidgetType::Checkbox => {
+                self.add_indent();
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Matched, &format!("{}Toggled", to_pascal_case(&name)));
+                self.add_plain("(");
+                self.add_identifier("checked");
+                self.add_plain(") ");
+                self.add_operator("=>");
+                self.add_plain(" {");
+                self.add_newline();
+                self.indent_level += 1;
+                self.add_indent();
+                self.add_keyword("self");
+                self.add_operator(".");
+                self.add_identifier(&format!("{}_checked", to_snake_case(&name)));
+                self.add_plain(" ");
+                self.add_operator("=");
+                self.add_plain(" ");
+                self.add_identifier("checked");
+                self.add_plain(";");
+                self.add_newline();
+                self.add_event_log(&name, "toggled", &[format!("self.{}_checked", to_snake_case(&name)).as_str()]);
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("}");
+                self.add_newline();
+            }
+            WidgetType::Radio => {
+                self.add_indent();
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Matched, &format!("{}Selected", to_pascal_case(&name)));
+                self.add_plain("(");
+                self.add_identifier("index");
+                self.add_plain(") ");
+                self.add_operator("=>");
+                self.add_plain(" {");
+                self.add_newline();
+                self.indent_level += 1;
+                self.add_indent();
+                self.add_keyword("self");
+                self.add_operator(".");
+                self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
+                self.add_plain(" ");
+                self.add_operator("=");
+                self.add_plain(" ");
+                self.add_identifier("index");
+                self.add_plain(";");
+                self.add_newline();
+                self.add_event_log(&name, "selected", &[format!("self.{}_selected", to_snake_case(&name)).as_str()]);
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("}");
+                self.add_newline();
+            }
+            WidgetType::Slider | WidgetType::VerticalSlider => {
+                self.add_indent();
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Matched, &format!("{}Changed", to_pascal_case(&name)));
+                self.add_plain("(");
+                self.add_identifier("value");
+                self.add_plain(") ");
+                self.add_operator("=>");
+                self.add_plain(" {");
+                self.add_newline();
+                self.indent_level += 1;
+                self.add_indent();
+                self.add_keyword("self");
+                self.add_operator(".");
+                self.add_identifier(&format!("{}_value", to_snake_case(&name)));
+                self.add_plain(" ");
+                self.add_operator("=");
+                self.add_plain(" ");
+                self.add_identifier("value");
+                self.add_plain(";");
+                self.add_newline();
+                self.add_event_log(&name, "changed", &[format!("self.{}_value", to_snake_case(&name)).as_str()]);
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("}");
+                self.add_newline();
+            }
+            WidgetType::Toggler => {
+                self.add_indent();
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Matched, &format!("{}Toggled", to_pascal_case(&name)));
+                self.add_plain("(");
+                self.add_identifier("active");
+                self.add_plain(") ");
+                self.add_operator("=>");
+                self.add_plain(" {");
+                self.add_newline();
+                self.indent_level += 1;
+                self.add_indent();
+                self.add_keyword("self");
+                self.add_operator(".");
+                self.add_identifier(&format!("{}_active", to_snake_case(&name)));
+                self.add_plain(" ");
+                self.add_operator("=");
+                self.add_plain(" ");
+                self.add_identifier("active");
+                self.add_plain(";");
+                self.add_newline();
+                self.add_event_log(&name, "toggled", &[format!("self.{}_active", to_snake_case(&name)).as_str()]);
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("}");
+                self.add_newline();
+            }
+            WidgetType::PickList => {
                 self.add_indent();
                 self.add_type("Message");
                 self.add_operator("::");
-                self.add_plain(&format!("{}Pressed", to_pascal_case(&name)));
-                self.add_plain(" ");
+                self.add_variant(WiringPass::Matched, &format!("{}Selected", to_pascal_case(&name)));
+                self.add_plain("(");
+                self.add_identifier("value");
+                self.add_plain(") ");
                 self.add_operator("=>");
                 self.add_plain(" {");
                 self.add_newline();
                 self.indent_level += 1;
                 self.add_indent();
-                self.add_comment("// Handle button press");
+                self.add_keyword("self");
+                self.add_operator(".");
+                self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
+                self.add_plain(" ");
+                self.add_operator("=");
+                self.add_plain(" Some(");
+                self.add_identifier("value");
+                self.add_plain(");");
                 self.add_newline();
+                self.add_event_log(&name, "selected", &[format!("self.{}_selected", to_snake_case(&name)).as_str()]);
                 self.indent_level -= 1;
                 self.add_indent();
                 self.add_plain("}");
                 self.add_newline();
             }
-            WidgetType::TextInput => {
+            WidgetType::ComboBox => {
                 let name = self.get_widget_name(widget.id);
                 let props = &widget.properties;
                 
-                // Always generate the Changed handler
+                // Always generate Selected handler with helpful example
                 self.add_indent();
                 self.add_type("Message");
                 self.add_operator("::");
-                self.add_plain(&format!("{}Changed", to_pascal_case(&name)));
+                self.add_variant(WiringPass::Matched, &format!("{}Selected", to_pascal_case(&name)));
                 self.add_plain("(");
                 self.add_identifier("value");
                 self.add_plain(") ");
@@ -1513,6 +3339,18 @@ impl<'a> CodeGenerator<'a> {
                 self.add_plain(" {");
                 self.add_newline();
                 self.indent_level += 1;
+                
+                // Add helpful println
+                self.add_indent();
+                self.add_macro("println!");
+                self.add_plain("(");
+                self.add_string(&format!("\"{} selected: {{:?}}\"", name));
+                self.add_plain(", ");
+                self.add_identifier("value");
+                self.add_plain(");");
+                self.add_newline();
+                
+                // Update state
                 self.add_indent();
                 self.add_keyword("self");
                 self.add_operator(".");
@@ -1523,1076 +3361,1551 @@ impl<'a> CodeGenerator<'a> {
                 self.add_identifier("value");
                 self.add_plain(";");
                 self.add_newline();
+                
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("}");
+                self.add_newline();
+                
+                // Conditionally generate on_input handler with example
+                if props.combobox_use_on_input {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}OnInput", to_pascal_case(&name)));
+                    self.add_plain("(");
+                    self.add_identifier("text");
+                    self.add_plain(") ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    
+                    self.add_indent();
+                    self.add_macro("println!");
+                    self.add_plain("(");
+                    self.add_string(&format!("\"{} input text: {{}}\"", name));
+                    self.add_plain(", ");
+                    self.add_identifier("text");
+                    self.add_plain(");");
+                    self.add_newline();
+                    
+                    self.add_indent();
+                    self.add_comment("// You can filter options, update state, etc.");
+                    self.add_newline();
+                    
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+                
+                // Conditionally generate on_option_hovered handler with example
+                if props.combobox_use_on_option_hovered {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}OnOptionHovered", to_pascal_case(&name)));
+                    self.add_plain("(");
+                    self.add_identifier("option");
+                    self.add_plain(") ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    
+                    self.add_indent();
+                    self.add_macro("println!");
+                    self.add_plain("(");
+                    self.add_string(&format!("\"{} option hovered: {{:?}}\"", name));
+                    self.add_plain(", ");
+                    self.add_identifier("option");
+                    self.add_plain(");");
+                    self.add_newline();
+                    
+                    self.add_indent();
+                    self.add_comment("// Preview the hovered option, update UI, etc.");
+                    self.add_newline();
+                    
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+                
+                // Conditionally generate on_open handler with example
+                if props.combobox_use_on_open {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}OnOpen", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    
+                    if self.stub_style == StubStyle::Todo {
+                        self.add_handler_stub("", &name, "open", &[]);
+                    } else {
+                        self.add_indent();
+                        self.add_macro("println!");
+                        self.add_plain("(");
+                        self.add_string(&format!("\"{} opened!\"", name));
+                        self.add_plain(");");
+                        self.add_newline();
+                    }
+                    
+                    self.add_indent();
+                    self.add_comment("// Refresh data, log analytics, etc.");
+                    self.add_newline();
+                    
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+                
+                // Conditionally generate on_close handler with example
+                if props.combobox_use_on_close {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}OnClose", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    
+                    if self.stub_style == StubStyle::Todo {
+                        self.add_handler_stub("", &name, "close", &[]);
+                    } else {
+                        self.add_indent();
+                        self.add_macro("println!");
+                        self.add_plain("(");
+                        self.add_string(&format!("\"{} closed!\"", name));
+                        self.add_plain(");");
+                        self.add_newline();
+                    }
+                    
+                    self.add_indent();
+                    self.add_comment("// Save user choice, validate selection, etc.");
+                    self.add_newline();
+                    
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+            }
+            WidgetType::Markdown => {
+                let name = self.get_widget_name(widget.id);
+                self.add_indent();
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Matched, &format!("{}LinkClicked", to_pascal_case(&name)));
+                self.add_plain("(");
+                self.add_identifier("url");
+                self.add_plain(") ");
+                self.add_operator("=>");
+                self.add_plain(" {");
+                self.add_newline();
+                self.indent_level += 1;
+                self.add_indent();
+                self.add_comment("// Open the link in a browser here, e.g. with the `open` crate");
+                self.add_newline();
+                self.add_indent();
+                self.add_event_println(&name, "link clicked", &["url"]);
+                self.add_newline();
                 self.indent_level -= 1;
                 self.add_indent();
                 self.add_plain("}");
                 self.add_newline();
+            }
+            WidgetType::MouseArea => {
+                let name = self.get_widget_name(widget.id);
+                let props = &widget.properties;
+                
+                // Left button press
+                if props.mousearea_on_press {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}Pressed", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    match file_dialog_of(widget) {
+                        FileDialog::None => self.add_handler_stub("// Handle left mouse button press", &name, "press", &[]),
+                        dialog => self.add_file_dialog_task(&name, dialog),
+                    }
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                    self.generate_file_chosen_arm(widget, &name);
+                }
+                
+                // Left button release
+                if props.mousearea_on_release {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}Released", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle left mouse button release", &name, "release", &[]);
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+                
+                // Double click
+                if props.mousearea_on_double_click {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}DoubleClicked", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle double click", &name, "double click", &[]);
+                    self.add_indent();
+                    self.add_comment("// Note: on_press and on_release will also fire");
+                    self.add_newline();
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+                
+                // Right button press
+                if props.mousearea_on_right_press {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}RightPressed", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle right mouse button press", &name, "right press", &[]);
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
                 
-                // Conditionally generate on_submit handler
-                if props.text_input_on_submit {
+                // Right button release
+                if props.mousearea_on_right_release {
                     self.add_indent();
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}Submitted", to_pascal_case(&name)));
+                    self.add_variant(WiringPass::Matched, &format!("{}RightReleased", to_pascal_case(&name)));
                     self.add_plain(" ");
                     self.add_operator("=>");
                     self.add_plain(" {");
                     self.add_newline();
                     self.indent_level += 1;
+                    self.add_handler_stub("// Handle right mouse button release", &name, "right release", &[]);
+                    self.indent_level -= 1;
                     self.add_indent();
-                    self.add_comment("// Handle text input submission (Enter key pressed)");
+                    self.add_plain("}");
                     self.add_newline();
+                }
+                
+                // Middle button press
+                if props.mousearea_on_middle_press {
                     self.add_indent();
-                    self.add_comment(&format!("// Current value: self.{}_value", to_snake_case(&name)));
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}MiddlePressed", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
                     self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle middle mouse button press", &name, "middle press", &[]);
                     self.indent_level -= 1;
                     self.add_indent();
                     self.add_plain("}");
                     self.add_newline();
                 }
                 
-                // Conditionally generate on_paste handler
-                if props.text_input_on_paste {
+                // Middle button release
+                if props.mousearea_on_middle_release {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}MiddleReleased", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle middle mouse button release", &name, "middle release", &[]);
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+                
+                // Scroll with delta parameter
+                if props.mousearea_on_scroll {
                     self.add_indent();
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}Pasted", to_pascal_case(&name)));
+                    self.add_variant(WiringPass::Matched, &format!("{}Scrolled", to_pascal_case(&name)));
                     self.add_plain("(");
-                    self.add_identifier("pasted_text");
+                    self.add_identifier("delta");
                     self.add_plain(") ");
                     self.add_operator("=>");
                     self.add_plain(" {");
                     self.add_newline();
                     self.indent_level += 1;
                     self.add_indent();
-                    self.add_comment("// Handle text being pasted");
+                    self.add_comment("// Handle scroll event");
                     self.add_newline();
                     self.add_indent();
-                    self.add_comment("// pasted_text contains the pasted string");
+                    self.add_comment("// delta is mouse::ScrollDelta enum:");
                     self.add_newline();
                     self.add_indent();
-                    self.add_comment("// Note: on_input will also fire with the new combined value");
+                    self.add_comment("//   Lines { x: f32, y: f32 } - scroll in lines");
                     self.add_newline();
                     self.add_indent();
-                    self.add_keyword("self");
-                    self.add_operator(".");
-                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=");
+                    self.add_comment("//   Pixels { x: f32, y: f32 } - scroll in pixels");
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_keyword("match");
                     self.add_plain(" ");
-                    self.add_identifier("pasted_text");
-                    self.add_plain(";");
+                    self.add_identifier("delta");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_indent();
+                    self.add_plain("mouse::ScrollDelta::Lines { ");
+                    self.add_identifier("x");
+                    self.add_plain(", ");
+                    self.add_identifier("y");
+                    self.add_plain(" } ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
                     self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle line-based scrolling", &name, "line scroll", &["x", "y"]);
                     self.indent_level -= 1;
                     self.add_indent();
                     self.add_plain("}");
                     self.add_newline();
-                }
-            }
-            WidgetType::Checkbox => {
-                self.add_indent();
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain(&format!("{}Toggled", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_identifier("checked");
-                self.add_plain(") ");
-                self.add_operator("=>");
-                self.add_plain(" {");
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_keyword("self");
-                self.add_operator(".");
-                self.add_identifier(&format!("{}_checked", to_snake_case(&name)));
-                self.add_plain(" ");
-                self.add_operator("=");
-                self.add_plain(" ");
-                self.add_identifier("checked");
-                self.add_plain(";");
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("}");
-                self.add_newline();
-            }
-            WidgetType::Radio => {
-                self.add_indent();
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_identifier("index");
-                self.add_plain(") ");
-                self.add_operator("=>");
-                self.add_plain(" {");
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_keyword("self");
-                self.add_operator(".");
-                self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
-                self.add_plain(" ");
-                self.add_operator("=");
-                self.add_plain(" ");
-                self.add_identifier("index");
-                self.add_plain(";");
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("}");
-                self.add_newline();
-            }
-            WidgetType::Slider | WidgetType::VerticalSlider => {
-                self.add_indent();
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain(&format!("{}Changed", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_identifier("value");
-                self.add_plain(") ");
-                self.add_operator("=>");
-                self.add_plain(" {");
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_keyword("self");
-                self.add_operator(".");
-                self.add_identifier(&format!("{}_value", to_snake_case(&name)));
-                self.add_plain(" ");
-                self.add_operator("=");
-                self.add_plain(" ");
-                self.add_identifier("value");
-                self.add_plain(";");
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("}");
-                self.add_newline();
-            }
-            WidgetType::Toggler => {
-                self.add_indent();
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain(&format!("{}Toggled", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_identifier("active");
-                self.add_plain(") ");
-                self.add_operator("=>");
-                self.add_plain(" {");
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_keyword("self");
-                self.add_operator(".");
-                self.add_identifier(&format!("{}_active", to_snake_case(&name)));
-                self.add_plain(" ");
-                self.add_operator("=");
-                self.add_plain(" ");
-                self.add_identifier("active");
-                self.add_plain(";");
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("}");
-                self.add_newline();
-            }
-            WidgetType::PickList => {
-                self.add_indent();
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_identifier("value");
-                self.add_plain(") ");
-                self.add_operator("=>");
-                self.add_plain(" {");
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_keyword("self");
-                self.add_operator(".");
-                self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
-                self.add_plain(" ");
-                self.add_operator("=");
-                self.add_plain(" Some(");
-                self.add_identifier("value");
-                self.add_plain(");");
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("}");
-                self.add_newline();
-            }
-            WidgetType::ComboBox => {
-                let name = self.get_widget_name(widget.id);
-                let props = &widget.properties;
-                
-                // Always generate Selected handler with helpful example
-                self.add_indent();
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-                self.add_plain("(");
-                self.add_identifier("value");
-                self.add_plain(") ");
-                self.add_operator("=>");
-                self.add_plain(" {");
-                self.add_newline();
-                self.indent_level += 1;
-                
-                // Add helpful println
-                self.add_indent();
-                self.add_macro("println!");
-                self.add_plain("(");
-                self.add_string(&format!("\"{} selected: {{:?}}\"", name));
-                self.add_plain(", ");
-                self.add_identifier("value");
-                self.add_plain(");");
-                self.add_newline();
-                
-                // Update state
-                self.add_indent();
-                self.add_keyword("self");
-                self.add_operator(".");
-                self.add_identifier(&format!("{}_value", to_snake_case(&name)));
-                self.add_plain(" ");
-                self.add_operator("=");
-                self.add_plain(" ");
-                self.add_identifier("value");
-                self.add_plain(";");
-                self.add_newline();
+                    self.add_indent();
+                    self.add_plain("mouse::ScrollDelta::Pixels { ");
+                    self.add_identifier("x");
+                    self.add_plain(", ");
+                    self.add_identifier("y");
+                    self.add_plain(" } ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle pixel-based scrolling", &name, "pixel scroll", &["x", "y"]);
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
                 
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("}");
-                self.add_newline();
+                // Mouse enter
+                if props.mousearea_on_enter {
+                    self.add_indent();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Matched, &format!("{}Entered", to_pascal_case(&name)));
+                    self.add_plain(" ");
+                    self.add_operator("=>");
+                    self.add_plain(" {");
+                    self.add_newline();
+                    self.indent_level += 1;
+                    self.add_handler_stub("// Handle mouse entering the area", &name, "enter", &[]);
+                    self.indent_level -= 1;
+                    self.add_indent();
+                    self.add_plain("}");
+                    self.add_newline();
+                }
                 
-                // Conditionally generate on_input handler with example
-                if props.combobox_use_on_input {
+                // Mouse move with point parameter
+                if props.mousearea_on_move {
                     self.add_indent();
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}OnInput", to_pascal_case(&name)));
+                    self.add_variant(WiringPass::Matched, &format!("{}Moved", to_pascal_case(&name)));
                     self.add_plain("(");
-                    self.add_identifier("text");
+                    self.add_identifier("point");
                     self.add_plain(") ");
                     self.add_operator("=>");
                     self.add_plain(" {");
                     self.add_newline();
                     self.indent_level += 1;
-                    
+                    if self.stub_style == StubStyle::Comment {
+                        self.add_indent();
+                        self.add_comment("// Handle mouse movement within the area");
+                        self.add_newline();
+                    }
                     self.add_indent();
-                    self.add_macro("println!");
-                    self.add_plain("(");
-                    self.add_string(&format!("\"{} input text: {{}}\"", name));
-                    self.add_plain(", ");
-                    self.add_identifier("text");
-                    self.add_plain(");");
+                    self.add_comment("// point is Point { x: f32, y: f32 } relative to the widget's bounds");
                     self.add_newline();
-                    
                     self.add_indent();
-                    self.add_comment("// You can filter options, update state, etc.");
+                    self.add_keyword("let");
+                    self.add_plain(" ");
+                    self.add_identifier("x");
+                    self.add_plain(" ");
+                    self.add_operator("=");
+                    self.add_plain(" ");
+                    self.add_identifier("point");
+                    self.add_operator(".");
+                    self.add_identifier("x");
+                    self.add_plain(";");
                     self.add_newline();
-                    
+                    self.add_indent();
+                    self.add_keyword("let");
+                    self.add_plain(" ");
+                    self.add_identifier("y");
+                    self.add_plain(" ");
+                    self.add_operator("=");
+                    self.add_plain(" ");
+                    self.add_identifier("point");
+                    self.add_operator(".");
+                    self.add_identifier("y");
+                    self.add_plain(";");
+                    self.add_newline();
+                    // todo!() first would leave the lets unreachable
+                    if self.stub_style != StubStyle::Comment {
+                        self.add_handler_stub("", &name, "move", &["x", "y"]);
+                    }
                     self.indent_level -= 1;
                     self.add_indent();
                     self.add_plain("}");
                     self.add_newline();
                 }
                 
-                // Conditionally generate on_option_hovered handler with example
-                if props.combobox_use_on_option_hovered {
+                // Mouse exit
+                if props.mousearea_on_exit {
                     self.add_indent();
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}OnOptionHovered", to_pascal_case(&name)));
-                    self.add_plain("(");
-                    self.add_identifier("option");
-                    self.add_plain(") ");
+                    self.add_variant(WiringPass::Matched, &format!("{}Exited", to_pascal_case(&name)));
+                    self.add_plain(" ");
                     self.add_operator("=>");
                     self.add_plain(" {");
                     self.add_newline();
                     self.indent_level += 1;
-                    
+                    self.add_handler_stub("// Handle mouse leaving the area", &name, "exit", &[]);
+                    self.indent_level -= 1;
                     self.add_indent();
-                    self.add_macro("println!");
-                    self.add_plain("(");
-                    self.add_string(&format!("\"{} option hovered: {{:?}}\"", name));
-                    self.add_plain(", ");
-                    self.add_identifier("option");
-                    self.add_plain(");");
+                    self.add_plain("}");
+                    self.add_newline();
+                }
+            }
+            _ => {}
+        }
+        
+        for child in &widget.children {
+            self.generate_update_match_arms(child);
+        }
+    }
+
+    fn generate_view_method(&mut self) {
+        self.add_indent();
+        self.add_keyword("fn");
+        self.add_plain(" ");
+        // With several windows, `view` dispatches here for the main one
+        self.add_function(if self.multi_window { "view_main" } else { "view" });
+        self.add_plain("<");
+        self.add_operator("'a");
+        self.add_plain(">");
+        self.add_plain("(");
+        self.add_operator("&'a ");
+        self.add_type("self");
+        self.add_plain(")");
+        self.add_operator(" -> ");
+        self.add_type("Element");
+        self.add_plain("<");
+        self.add_operator("'a");
+        self.add_plain(", ");
+        self.add_operator("Message");
+        self.add_plain("> {");
+        self.add_newline();
+        self.indent_level += 1;
+        if self.view_builders {
+            self.add_indent();
+            self.add_function("build_view");
+            self.add_plain("(");
+            self.add_keyword("self");
+            self.add_plain(")");
+            self.add_newline();
+        } else {
+            self.generate_view_body(true);
+        }
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+    }
+
+    /// Free function building the view from the state alone, preceded by what the state must satisfy
+    fn generate_view_builder(&mut self) {
+        let mut invariants = Vec::new();
+        self.collect_view_invariants(&self.hierarchy.root().clone(), &mut invariants);
+        self.add_comment("// Pure view builder: no &self, so tests can build the state by hand and call it");
+        self.add_newline();
+        if invariants.is_empty() {
+            self.add_comment("// Any state works, no field has to agree with another");
+            self.add_newline();
+        } else {
+            self.add_comment("// The state must satisfy:");
+            self.add_newline();
+            for invariant in invariants {
+                self.add_comment(&format!("// - {}", invariant));
+                self.add_newline();
+            }
+        }
+
+        self.add_keyword("pub fn");
+        self.add_plain(" ");
+        self.add_function("build_view");
+        self.add_plain("<");
+        self.add_operator("'a");
+        self.add_plain(">(");
+        self.add_identifier("state");
+        self.add_operator(":");
+        self.add_plain(" ");
+        self.add_operator("&'a ");
+        self.add_type(&self.app_name.clone());
+        self.add_plain(")");
+        self.add_operator(" -> ");
+        self.add_type("Element");
+        self.add_plain("<");
+        self.add_operator("'a");
+        self.add_plain(", ");
+        self.add_operator("Message");
+        self.add_plain("> {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.in_view_builder = true;
+        self.generate_view_body(true);
+        self.in_view_builder = false;
+        self.indent_level -= 1;
+        self.add_plain("}");
+    }
+
+    /// Field relationships the view relies on, one sentence per stateful widget that has one
+    fn collect_view_invariants(&self, widget: &Widget, invariants: &mut Vec<String>) {
+        let field = to_snake_case(&self.get_widget_name(widget.id));
+        let props = &widget.properties;
+        match widget.widget_type {
+            WidgetType::ComboBox => {
+                let options = props.referenced_enum.as_ref()
+                    .and_then(|id| self.type_system?.get_enum(id.clone()))
+                    .map(|enum_def| format!("`{}::ALL`", enum_def.name))
+                    .unwrap_or_else(|| "the combo box's option list".to_string());
+                invariants.push(format!("`{}_state` is built from {}, the options the combo box offers", field, options));
+            }
+            WidgetType::Radio if props.referenced_enum.is_none() && !props.radio_options.is_empty() => {
+                invariants.push(format!("`{}_selected` is below {}, the number of radio options", field, props.radio_options.len()));
+            }
+            WidgetType::Slider | WidgetType::VerticalSlider => {
+                invariants.push(format!("`{}_value` lies within {:.1}..={:.1}", field, props.slider_min, props.slider_max));
+            }
+            WidgetType::PickList => {
+                invariants.push(format!("`{}_selected` is None or one of the pick list's options", field));
+            }
+            _ => {}
+        }
+        for child in &widget.children {
+            self.collect_view_invariants(child, invariants);
+        }
+    }
+
+    /// `self.` in the view method, `state.` in the view builder
+    fn add_state_receiver(&mut self) {
+        if self.in_view_builder {
+            self.add_identifier("state");
+        } else {
+            self.add_keyword("self");
+        }
+    }
+
+    /// The root widget tree followed by `.into()`
+    fn generate_view_body(&mut self, use_self: bool) {
+        let root = self.hierarchy.root();
+        self.splitting = use_self && !self.layout_only && self.helper_threshold.is_some();
+
+        // Style closures outlive the view call, so they get copies of the flags they branch on
+        let mut condition_fields = Vec::new();
+        if use_self {
+            self.collect_condition_fields(root, &mut condition_fields);
+        }
+        self.generate_condition_copies(&condition_fields);
+
+        if root.children.is_empty() {
+            self.add_indent();
+            self.add_function("container");
+            self.add_plain("(");
+            self.add_function("text");
+            self.add_plain("(");
+            self.add_string("\"Empty\"");
+            self.add_plain("))");
+        } else {
+            let nav_sections = self.nav_sections();
+            if nav_sections.is_empty() {
+                self.generate_widget_creation(root, use_self);
+            } else {
+                self.generate_section_nav(root, use_self, &nav_sections);
+            }
+        }
+
+        self.add_indent();
+        self.add_operator(".");
+        self.add_function("into");
+        self.add_plain("()");
+        self.add_newline();
+        self.splitting = false;
+    }
+
+    /// `let flag = self.flag;` for each field a style closure branches on
+    fn generate_condition_copies(&mut self, fields: &[String]) {
+        for field in fields {
+            self.add_indent();
+            self.add_keyword("let");
+            self.add_plain(" ");
+            self.add_identifier(field);
+            self.add_plain(" ");
+            self.add_operator("=");
+            self.add_plain(" ");
+            self.add_state_receiver();
+            self.add_operator(".");
+            self.add_identifier(field);
+            self.add_plain(";");
+            self.add_newline();
+        }
+    }
+
+    /// Whether `widget` is built by a helper method, so the code building its parent calls it
+    fn calls_helper(&self, widget: &Widget) -> bool {
+        let Some(threshold) = self.helper_threshold else { return false };
+        self.splitting
+            && widget.id != self.hierarchy.root().id
+            && self.emitting_helper != Some(widget.id)
+            && ((matches!(widget.widget_type, WidgetType::Container | WidgetType::Row | WidgetType::Column)
+                && widget.children.len() > threshold)
+                || sanitize_name(&widget.properties.widget_name).is_some())
+    }
+
+    fn helper_name(&self, widget_id: WidgetId) -> String {
+        format!("{}_view", to_snake_case(&self.get_widget_name(widget_id)))
+    }
+
+    /// `self.<name>_view()` in place of the widget, queueing the helper to be emitted
+    fn generate_helper_call(&mut self, widget: &Widget) {
+        self.add_indent();
+        self.add_state_receiver();
+        self.add_operator(".");
+        self.add_function(&self.helper_name(widget.id));
+        self.add_plain("()");
+        if !self.called_helpers.contains(&widget.id) {
+            self.called_helpers.push(widget.id);
+        }
+    }
+
+    /// An `impl` block with the helpers the view called, each built like the view builds it.
+    /// Helpers calling helpers of their own queue them, so every helper emitted is called.
+    fn generate_view_helpers(&mut self) {
+        self.add_keyword("impl");
+        self.add_plain(" ");
+        self.add_type(&self.app_name.clone());
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+
+        let hierarchy = self.hierarchy;
+        let mut next = 0;
+        while let Some(&widget_id) = self.called_helpers.get(next) {
+            next += 1;
+            let Some(widget) = hierarchy.get_widget_by_id(widget_id) else { continue };
+            if next > 1 {
+                self.add_newline();
+            }
+            self.add_indent();
+            self.add_keyword("fn");
+            self.add_plain(" ");
+            self.add_function(&self.helper_name(widget_id));
+            self.add_plain("(");
+            self.add_operator("&");
+            self.add_keyword("self");
+            self.add_plain(")");
+            self.add_operator(" -> ");
+            self.add_type("Element");
+            self.add_plain("<");
+            self.add_operator("'_");
+            self.add_plain(", ");
+            self.add_type("Message");
+            self.add_plain("> {");
+            self.add_newline();
+            self.indent_level += 1;
+
+            self.splitting = true;
+            self.emitting_helper = Some(widget_id);
+            let mut condition_fields = Vec::new();
+            self.collect_condition_fields(widget, &mut condition_fields);
+            self.generate_condition_copies(&condition_fields);
+            self.generate_widget_creation(widget, true);
+            self.emitting_helper = None;
+            self.splitting = false;
+
+            self.add_indent();
+            self.add_operator(".");
+            self.add_function("into");
+            self.add_plain("()");
+            self.add_newline();
+            self.indent_level -= 1;
+            self.add_indent();
+            self.add_plain("}");
+            self.add_newline();
+        }
+
+        self.indent_level -= 1;
+        self.add_plain("}");
+    }
+
+    // Property keys emitted inline by generate_widget_creation rather than a properties fn
+    const RADIO_EMITS: &'static [&'static str] = &["label_position", "orientation", "radio_label", "radio_options", "radio_selected_index", "radio_size", "radio_spacing", "referenced_enum", "spacing", "width"];
+    const RULE_EMITS: &'static [&'static str] = &["height", "orientation", "rule_thickness", "width"];
+    const TOOLTIP_EMITS: &'static [&'static str] = &["tooltip_position", "tooltip_text"];
+    const MOUSE_AREA_EMITS: &'static [&'static str] = &[
+        "file_dialog", "file_dialog_path_text", "mousearea_interaction", "mousearea_on_double_click", "mousearea_on_enter", "mousearea_on_exit",
+        "mousearea_on_middle_press", "mousearea_on_middle_release", "mousearea_on_move", "mousearea_on_press",
+        "mousearea_on_release", "mousearea_on_right_press", "mousearea_on_right_release", "mousearea_on_scroll",
+    ];
+    const PIN_EMITS: &'static [&'static str] = &[];
+
+    fn generate_widget_creation(&mut self, widget: &Widget, use_self: bool) {
+        if self.calls_helper(widget) {
+            self.generate_helper_call(widget);
+            return;
+        }
+        let props = &widget.properties;
+        
+        match widget.widget_type {
+            WidgetType::Container => {
+                self.add_indent();
+                self.add_function("container");
+                self.add_plain("(");
+                self.indent_level += 1;
+                
+                let child_tokens = self.emit_detached(|generator| {
+                    if widget.children.is_empty() {
+                        generator.add_indent();
+                        generator.add_function("text");
+                        generator.add_plain("(");
+                        generator.add_string("\"Container Content\"");
+                        generator.add_plain(")");
+                    } else {
+                        for child in &widget.children {
+                            generator.generate_widget_creation(child, use_self);
+                        }
+                    }
+                });
+                
+                self.indent_level -= 1;
+                if !self.push_inline(&child_tokens) {
                     self.add_newline();
-                    
-                    self.add_indent();
-                    self.add_comment("// Preview the hovered option, update UI, etc.");
+                    self.tokens.extend(child_tokens);
                     self.add_newline();
-                    
-                    self.indent_level -= 1;
                     self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
                 }
-                
-                // Conditionally generate on_open handler with example
-                if props.combobox_use_on_open {
-                    self.add_indent();
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}OnOpen", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    
-                    self.add_indent();
-                    self.add_macro("println!");
+                self.add_plain(")");
+                self.generate_container_properties(props);
+                if let Some(field) = self.condition_field(widget) && !props.gradient_enabled {
+                    self.generate_container_condition_style(props, &field);
+                } else if let Some(style) = self.named_style_of(widget) {
+                    let name = style.name.clone();
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("style");
                     self.add_plain("(");
-                    self.add_string(&format!("\"{} opened!\"", name));
-                    self.add_plain(");");
-                    self.add_newline();
-                    
-                    self.add_indent();
-                    self.add_comment("// Refresh data, log analytics, etc.");
-                    self.add_newline();
-                    
-                    self.indent_level -= 1;
+                    self.add_function(&name);
+                    self.add_plain(")");
+                }
+                self.add_newline();
+            }
+            WidgetType::Row if use_self && props.stack_below_breakpoint && self.breakpoint().is_some() => {
+                self.generate_breakpoint_row(widget, use_self);
+            }
+            WidgetType::Row => {
+                if !hints::stretching_spaces(widget).is_empty() {
                     self.add_indent();
-                    self.add_plain("}");
+                    self.add_comment("// Fill spaces stretch each wrapped line separately, so gaps differ per line: prefer fixed spaces or .spacing()");
                     self.add_newline();
                 }
+                self.add_indent();
+                self.add_macro("row!");
+                self.add_plain("[");
+                self.add_newline();
+                self.indent_level += 1;
                 
-                // Conditionally generate on_close handler with example
-                if props.combobox_use_on_close {
-                    self.add_indent();
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}OnClose", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    
+                if widget.children.is_empty() {
                     self.add_indent();
-                    self.add_macro("println!");
+                    self.add_function("text");
                     self.add_plain("(");
-                    self.add_string(&format!("\"{} closed!\"", name));
-                    self.add_plain(");");
-                    self.add_newline();
+                    self.add_string("\"Row Item\"");
+                    self.add_plain(")");
+                } else {
+                    for (i, child) in widget.children.iter().enumerate() {
+                        self.generate_widget_creation(child, use_self);
+                        if i < widget.children.len() - 1 {
+                            self.add_plain(",");
+                        }
+                        self.add_newline();
+                    }
+                }
+
+                self.add_newline();
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("]");
+                
+                // Generate row properties
+                self.generate_layout_properties(props, true);
+                
+                // NEW: If wrapping, add .wrap() and wrapping properties
+                if props.is_wrapping_row {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("wrap");
+                    self.add_plain("()");
                     
-                    self.add_indent();
-                    self.add_comment("// Save user choice, validate selection, etc.");
-                    self.add_newline();
+                    // Vertical spacing
+                    if let Some(v_spacing) = props.wrapping_vertical_spacing {
+                        self.chain_break();
+                        self.add_operator(".");
+                        self.add_function("vertical_spacing");
+                        self.add_plain("(");
+                        self.add_number(&format!("{:.1}", v_spacing));
+                        self.add_plain(")");
+                    }
                     
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                    // Horizontal alignment (only if not Left/default)
+                    if !matches!(props.wrapping_align_x, ContainerAlignX::Left) {
+                        self.chain_break();
+                        self.add_operator(".");
+                        self.add_function("align_x");
+                        self.add_plain("(");
+                        match props.wrapping_align_x {
+                            ContainerAlignX::Left => self.add_type("Alignment::Start"),
+                            ContainerAlignX::Center => self.add_type("Alignment::Center"),
+                            ContainerAlignX::Right => self.add_type("Alignment::End"),
+                        }
+                        self.add_plain(")");
+                    }
                 }
             }
-            WidgetType::MouseArea => {
-                let name = self.get_widget_name(widget.id);
-                let props = &widget.properties;
-                
-                // Left button press
-                if props.mousearea_on_press {
-                    self.add_indent();
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Pressed", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle left mouse button press");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
-                }
+            WidgetType::Column => {
+                self.add_indent();
+                self.add_macro("column!");
+                self.add_plain("[");
+                self.add_newline();
+                self.indent_level += 1;
                 
-                // Left button release
-                if props.mousearea_on_release {
-                    self.add_indent();
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Released", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle left mouse button release");
-                    self.add_newline();
-                    self.indent_level -= 1;
+                if widget.children.is_empty() {
                     self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                    self.add_function("text");
+                    self.add_plain("(");
+                    self.add_string("\"Column Item\"");
+                    self.add_plain(")");
+                } else {
+                    for (i, child) in widget.children.iter().enumerate() {
+                        self.generate_widget_creation(child, use_self);
+                        if i < widget.children.len() - 1 {
+                            self.add_plain(",");
+                        }
+                        self.add_newline();
+                    }
                 }
                 
-                // Double click
-                if props.mousearea_on_double_click {
-                    self.add_indent();
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}DoubleClicked", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle double click");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_comment("// Note: on_press and on_release will also fire");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                self.add_newline();
+                self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain("]");
+                self.generate_layout_properties(props, false);
+            }
+            WidgetType::Button => {
+                let name = self.get_widget_name(widget.id);
+                self.add_indent();
+                self.add_function("button");
+                self.add_plain("(");
+//                self.add_function("text");
+//                self.add_plain("(");
+                self.add_string(&format!("\"{}\"", props.text_content));
+//                self.add_plain(")");
+                self.add_plain(")");
+                self.generate_button_properties(widget, props);
+            }
+            WidgetType::Text => {
+                self.add_indent();
+                self.add_function("text");
+                self.add_plain("(");
+                let path_source = path_source(self.hierarchy.root(), widget.id)
+                    .filter(|_| use_self)
+                    .map(|source| self.get_widget_name(source.id));
+                match path_source {
+                    // Shows the last chosen path, and its own content until one is chosen
+                    Some(source_name) => {
+                        self.add_state_receiver();
+                        self.add_operator(".");
+                        self.add_identifier(&format!("{}_path", to_snake_case(&source_name)));
+                        self.add_operator(".");
+                        self.add_function("as_ref");
+                        self.add_plain("()");
+                        self.add_operator(".");
+                        self.add_function("map_or");
+                        self.add_plain("(");
+                        self.add_type("String");
+                        self.add_operator("::");
+                        self.add_function("from");
+                        self.add_plain("(");
+                        self.add_string(&format!("\"{}\"", props.text_content));
+                        self.add_plain("), ");
+                        self.add_operator("|");
+                        self.add_identifier("path");
+                        self.add_operator("|");
+                        self.add_plain(" ");
+                        self.add_identifier("path");
+                        self.add_operator(".");
+                        self.add_function("display");
+                        self.add_plain("()");
+                        self.add_operator(".");
+                        self.add_function("to_string");
+                        self.add_plain("())");
+                    }
+                    None => self.add_string(&format!("\"{}\"", props.text_content)),
                 }
+                self.add_plain(")");
+                self.generate_text_properties(props);
+            }
+            WidgetType::TextInput => {
+                let name = self.get_widget_name(widget.id);
+                let props = &widget.properties;
                 
-                // Right button press
-                if props.mousearea_on_right_press {
-                    self.add_indent();
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}RightPressed", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle right mouse button press");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                self.add_indent();
+                self.add_function("text_input");
+                self.add_plain("(");
+                self.add_string(&format!("\"{}\"", props.text_input_placeholder));
+                self.add_plain(", ");
+                if use_self {
+                    self.add_operator("&");
+                    self.add_state_receiver();
+                    self.add_operator(".");
+                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
+                } else {
+                    self.add_string("\"\"");
                 }
+                self.add_plain(")");
+                self.indent_level += 1;
                 
-                // Right button release
-                if props.mousearea_on_right_release {
-                    self.add_indent();
+                // Always add on_input, so the input isn't drawn disabled
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("on_input");
+                self.add_plain("(");
+                if self.layout_only {
+                    self.add_operator("|");
+                    self.add_identifier("_");
+                    self.add_operator("|");
+                    self.add_plain(" ");
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}RightReleased", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle right mouse button release");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                    self.add_plain("Noop");
+                } else {
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Changed", to_pascal_case(&name)));
                 }
+                self.add_plain(")");
                 
-                // Middle button press
-                if props.mousearea_on_middle_press {
-                    self.add_indent();
+                // Conditionally add on_submit
+                if props.text_input_on_submit && !self.layout_only {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_submit");
+                    self.add_plain("(");
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}MiddlePressed", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle middle mouse button press");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                    self.add_variant(WiringPass::Sent, &format!("{}Submitted", to_pascal_case(&name)));
+                    self.add_plain(")");
                 }
                 
-                // Middle button release
-                if props.mousearea_on_middle_release {
-                    self.add_indent();
+                // Conditionally add on_paste
+                if props.text_input_on_paste && !self.layout_only {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_paste");
+                    self.add_plain("(");
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}MiddleReleased", to_pascal_case(&name)));
-                    self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle middle mouse button release");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                    self.add_variant(WiringPass::Sent, &format!("{}Pasted", to_pascal_case(&name)));
+                    self.add_plain(")");
                 }
                 
-                // Scroll with delta parameter
-                if props.mousearea_on_scroll {
-                    self.add_indent();
+                // Add secure if enabled
+                if props.is_secure {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("secure");
+                    self.add_plain("(");
+                    self.add_plain("true");
+                    self.add_plain(")");
+                }
+                
+                // Add font if not default
+                if props.text_input_font != FontType::Default {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("font");
+                    self.add_plain("(");
+                    match props.text_input_font {
+                        FontType::Monospace => {
+                            self.add_type("Font");
+                            self.add_operator("::");
+                            self.add_plain("MONOSPACE");
+                        }
+                        _ => {
+                            self.add_type("Font");
+                            self.add_operator("::");
+                            self.add_plain("default()");
+                        }
+                    }
+                    self.add_plain(")");
+                }
+                
+                // Add size
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("size");
+                self.add_plain("(");
+                self.add_plain(&format!("{}", props.text_input_size));
+                self.add_plain(")");
+                
+                // Add padding
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("padding");
+                self.add_plain("(");
+                self.add_plain(&format!("{}", props.text_input_padding));
+                self.add_plain(")");
+                
+                // Add line_height if not default
+                if props.text_input_line_height != text::LineHeight::default() {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("line_height");
+                    self.add_plain("(");
+                    self.add_line_height(props.text_input_line_height);
+                    self.add_plain(")");
+                }
+                
+                // Add alignment if not left
+                if props.text_input_alignment != ContainerAlignX::Left {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("align_x");
+                    self.add_plain("(");
+                    //self.add_plain("alignment::Horizontal::");
+                    self.add_plain("Alignment::");
+                    match props.text_input_alignment {
+                        ContainerAlignX::Left => self.add_plain("Start"),
+                        ContainerAlignX::Center => self.add_plain("Center"),
+                        ContainerAlignX::Right => self.add_plain("End"),
+                    }
+                    self.add_plain(")");
+                }
+                
+                // Add width
+                if !matches!(props.width, Length::Fill) {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("width");
+                    self.add_plain("(");
+                    self.add_length(props.width);
+                    self.add_plain(")");
+                }
+
+                if let Some(field) = self.condition_field(widget) {
+                    self.generate_text_input_condition_style(props, &field);
+                }
+                
+                self.indent_level -= 1;
+            }
+            WidgetType::Checkbox | WidgetType::Toggler if props.label_position == LabelPosition::Left => {
+                let (label, spacing) = match widget.widget_type {
+                    WidgetType::Checkbox => (props.checkbox_label.clone(), props.checkbox_spacing),
+                    _ => (props.toggler_label.clone(), props.toggler_spacing),
+                };
+
+                // The control keeps its own styling but loses the label it would draw itself
+                let mut control = widget.clone();
+                control.properties.label_position = LabelPosition::Right;
+                control.properties.width = Length::Shrink;
+                control.properties.checkbox_label.clear();
+                control.properties.checkbox_spacing = 0.0;
+                control.properties.toggler_label.clear();
+                control.properties.toggler_spacing = iced::widget::toggler::Toggler::<Theme>::DEFAULT_SIZE / 2.0;
+
+                self.open_left_label_row(&label);
+                self.generate_widget_creation(&control, use_self);
+                self.close_left_label_row(spacing, props.width);
+            }
+            WidgetType::Checkbox => {
+                let name = self.get_widget_name(widget.id);
+                self.add_indent();
+                self.add_function("checkbox");
+                self.add_plain("(");
+                self.add_string(&format!("\"{}\"", props.checkbox_label));
+                self.add_plain(", ");
+                if use_self {
+                    self.add_state_receiver();
+                    self.add_operator(".");
+                    self.add_identifier(&format!("{}_checked", to_snake_case(&name)));
+                } else {
+                    self.add_keyword(if props.checkbox_checked { "true" } else { "false" });
+                }
+                self.add_plain(")");
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("on_toggle");
+                self.add_plain("(");
+                if use_self {
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}Scrolled", to_pascal_case(&name)));
-                    self.add_plain("(");
-                    self.add_identifier("delta");
-                    self.add_plain(") ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle scroll event");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_comment("// delta is mouse::ScrollDelta enum:");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_comment("//   Lines { x: f32, y: f32 } - scroll in lines");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_comment("//   Pixels { x: f32, y: f32 } - scroll in pixels");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_keyword("match");
+                    self.add_variant(WiringPass::Sent, &format!("{}Toggled", to_pascal_case(&name)));
+                } else {
+                    self.add_operator("|");
+                    self.add_identifier("_");
+                    self.add_operator("|");
                     self.add_plain(" ");
-                    self.add_identifier("delta");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_plain("mouse::ScrollDelta::Lines { ");
-                    self.add_identifier("x");
-                    self.add_plain(", ");
-                    self.add_identifier("y");
-                    self.add_plain(" } ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle line-based scrolling");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_plain("mouse::ScrollDelta::Pixels { ");
-                    self.add_identifier("x");
-                    self.add_plain(", ");
-                    self.add_identifier("y");
-                    self.add_plain(" } ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle pixel-based scrolling");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_plain("Noop");
+                }
+                self.add_plain(")");
+                self.generate_checkbox_properties(props);
+                self.indent_level -= 1;
+            }
+            WidgetType::Radio => {
+                self.generate_radio_widget(widget, use_self, props.orientation == Orientation::Vertical);
+            }
+            WidgetType::Slider => {
+                let name = self.get_widget_name(widget.id);
+                self.add_indent();
+                self.add_function("slider");
+                self.add_plain("(");
+                self.add_number(&format!("{:.1}", props.slider_min));
+                self.add_operator("..=");
+                self.add_number(&format!("{:.1}", props.slider_max));
+                self.add_plain(", ");
+                if use_self {
+                    self.add_state_receiver();
+                    self.add_operator(".");
+                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
+                } else {
+                    self.add_number(&format!("{}", props.slider_value));
                 }
-                
-                // Mouse enter
-                if props.mousearea_on_enter {
-                    self.add_indent();
+                self.add_plain(", ");
+                if use_self {
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}Entered", to_pascal_case(&name)));
+                    self.add_variant(WiringPass::Sent, &format!("{}Changed", to_pascal_case(&name)));
+                } else {
+                    self.add_operator("|");
+                    self.add_identifier("_");
+                    self.add_operator("|");
                     self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle mouse entering the area");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
-                }
-                
-                // Mouse move with point parameter
-                if props.mousearea_on_move {
-                    self.add_indent();
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}Moved", to_pascal_case(&name)));
-                    self.add_plain("(");
-                    self.add_identifier("point");
-                    self.add_plain(") ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle mouse movement within the area");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_comment("// point is Point { x: f32, y: f32 } relative to the widget's bounds");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_keyword("let");
-                    self.add_plain(" ");
-                    self.add_identifier("x");
-                    self.add_plain(" ");
-                    self.add_operator("=");
-                    self.add_plain(" ");
-                    self.add_identifier("point");
+                    self.add_plain("Noop");
+                }
+                self.add_plain(")");
+                self.generate_slider_properties(props);
+            }
+            WidgetType::VerticalSlider => {
+                let name = self.get_widget_name(widget.id);
+                self.add_indent();
+                self.add_function("vertical_slider");
+                self.add_plain("(");
+                self.add_number(&format!("{:.1}", props.slider_min));
+                self.add_operator("..=");
+                self.add_number(&format!("{:.1}", props.slider_max));
+                self.add_plain(", ");
+                if use_self {
+                    self.add_state_receiver();
                     self.add_operator(".");
-                    self.add_identifier("x");
-                    self.add_plain(";");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_keyword("let");
-                    self.add_plain(" ");
-                    self.add_identifier("y");
-                    self.add_plain(" ");
-                    self.add_operator("=");
+                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
+                } else {
+                    self.add_number(&format!("{}", props.slider_value));
+                }
+                self.add_plain(", ");
+                if use_self {
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Changed", to_pascal_case(&name)));
+                } else {
+                    self.add_operator("|");
+                    self.add_identifier("_");
+                    self.add_operator("|");
                     self.add_plain(" ");
-                    self.add_identifier("point");
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_plain("Noop");
+                }
+                self.add_plain(")");
+                self.generate_vertical_slider_properties(props);
+            }
+            WidgetType::ProgressBar => {
+                self.add_indent();
+                self.add_function("progress_bar");
+                self.add_plain("(");
+                self.add_number(&format!("{:.1}", props.progress_min));
+                self.add_operator("..=");
+                self.add_number(&format!("{:.1}", props.progress_max));
+                self.add_plain(", ");
+                self.add_number(&format!("{:.2}", props.progress_value));
+                self.add_plain(")");
+                self.generate_progress_properties(props);
+            }
+            WidgetType::Toggler => {
+                let name = self.get_widget_name(widget.id);
+                self.add_indent();
+                self.add_function("toggler");
+                self.add_plain("(");
+                if use_self {
+                    self.add_state_receiver();
                     self.add_operator(".");
-                    self.add_identifier("y");
-                    self.add_plain(";");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                    self.add_identifier(&format!("{}_active", to_snake_case(&name)));
+                } else {
+                    self.add_keyword(if props.toggler_active { "true" } else { "false" });
                 }
-                
-                // Mouse exit
-                if props.mousearea_on_exit {
-                    self.add_indent();
+                self.add_plain(")");
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("on_toggle");
+                self.add_plain("(");
+                if use_self {
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}Exited", to_pascal_case(&name)));
+                    self.add_variant(WiringPass::Sent, &format!("{}Toggled", to_pascal_case(&name)));
+                } else {
+                    self.add_operator("|");
+                    self.add_identifier("_");
+                    self.add_operator("|");
                     self.add_plain(" ");
-                    self.add_operator("=>");
-                    self.add_plain(" {");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_comment("// Handle mouse leaving the area");
-                    self.add_newline();
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("}");
-                    self.add_newline();
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_plain("Noop");
                 }
-
-                self.add_plain(",");
-
+                self.add_plain(")");
+                self.generate_toggler_properties(props);
+                self.indent_level -= 1;
             }
-            _ => {}
-        }
-        
-        for child in &widget.children {
-            self.generate_update_match_arms(child);
-        }
-    }
-
-    fn generate_view_method(&mut self) {
-        self.widget_counts.clear();
-
-        self.add_indent();
-        self.add_keyword("fn");
-        self.add_plain(" ");
-        self.add_function("view");
-        self.add_plain("<");
-        self.add_operator("'a");
-        self.add_plain(">");
-        self.add_plain("(");
-        self.add_operator("&'a ");
-        self.add_type("self");
-        self.add_plain(")");
-        self.add_operator(" -> ");
-        self.add_type("Element");
-        self.add_plain("<");
-        self.add_operator("'a");
-        self.add_plain(", ");
-        self.add_operator("Message");
-        self.add_plain("> {");
-        self.add_newline();
-        self.indent_level += 1;
-
-        let root = self.hierarchy.root();
-
-        if root.children.is_empty() {
-            self.add_indent();
-            self.add_function("container");
-            self.add_plain("(");
-            self.add_function("text");
-            self.add_plain("(");
-            self.add_string("\"Empty\"");
-            self.add_plain("))");
-        } else {
-            self.generate_widget_creation(root, true);
-        }
-
-        self.add_indent();
-        self.add_operator(".");
-        self.add_function("into");
-        self.add_plain("()");
-        self.add_newline();
-
-        self.indent_level -= 1;
-        self.add_indent();
-        self.add_plain("}");
-    }
-
-    fn generate_widget_creation(&mut self, widget: &Widget, use_self: bool) {
-        let props = &widget.properties;
-        
-        match widget.widget_type {
-            WidgetType::Container => {
+            WidgetType::PickList => {
+                let name = self.get_widget_name(widget.id);
                 self.add_indent();
-                self.add_function("container");
+                self.add_function("pick_list");
                 self.add_plain("(");
                 self.add_newline();
                 self.indent_level += 1;
-                
-                if widget.children.is_empty() {
-                    self.add_indent();
-                    self.add_function("text");
-                    self.add_plain("(");
-                    self.add_string("\"Container Content\"");
-                    self.add_plain(")");
+                self.add_indent();
+                self.emit_string_list(&props.picklist_options);
+                self.add_plain(",");
+                self.add_newline();
+                self.add_indent();
+                if use_self {
+                    self.add_state_receiver();
+                    self.add_operator(".");
+                    self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
+                    self.add_operator(".");
+                    self.add_function("clone");
+                    self.add_plain("()");
+                } else if let Some(ref selected) = props.picklist_selected {
+                    self.add_plain("Some(");
+                    self.add_string(&format!("\"{}\"", selected));
+                    self.add_operator(".");
+                    self.add_function("to_string");
+                    self.add_plain("())");
+                } else {
+                    self.add_plain("None");
+                }
+                self.add_plain(",");
+                self.add_newline();
+                self.add_indent();
+                if use_self {
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Selected", to_pascal_case(&name)));
                 } else {
-                    for child in &widget.children {
-                        self.generate_widget_creation(child, use_self);
-                    }
+                    self.add_operator("|");
+                    self.add_identifier("_");
+                    self.add_operator("|");
+                    self.add_plain(" ");
+                    self.add_type("Message");
+                    self.add_operator("::");
+                    self.add_plain("Noop");
                 }
-                
                 self.add_newline();
                 self.indent_level -= 1;
                 self.add_indent();
                 self.add_plain(")");
-                self.generate_container_properties(props);
-                self.add_newline();
+                self.generate_picklist_properties(props);
             }
-            WidgetType::Row => {
+            WidgetType::Scrollable => {
                 self.add_indent();
-                self.add_macro("row!");
-                self.add_plain("[");
+                self.add_function("scrollable");
+                self.add_plain("(");
                 self.add_newline();
                 self.indent_level += 1;
                 
                 if widget.children.is_empty() {
                     self.add_indent();
-                    self.add_function("text");
-                    self.add_plain("(");
-                    self.add_string("\"Row Item\"");
-                    self.add_plain(")");
-                } else {
-                    for (i, child) in widget.children.iter().enumerate() {
-                        self.generate_widget_creation(child, use_self);
-                        if i < widget.children.len() - 1 {
-                            self.add_plain(",");
-                        }
-                        self.add_newline();
-                    }
-                }
-
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("]");
-                
-                // Generate row properties
-                self.generate_layout_properties(props, true);
-                
-                // NEW: If wrapping, add .wrap() and wrapping properties
-                if props.is_wrapping_row {
+                    self.add_macro("column!");
+                    self.add_plain("[");
                     self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("wrap");
-                    self.add_plain("()");
-                    
-                    // Vertical spacing
-                    if let Some(v_spacing) = props.wrapping_vertical_spacing {
-                        self.add_newline();
+                    self.indent_level += 1;
+                    for i in 1..=10 {
                         self.add_indent();
-                        self.add_operator(".");
-                        self.add_function("vertical_spacing");
+                        self.add_function("text");
                         self.add_plain("(");
-                        self.add_number(&format!("{:.1}", v_spacing));
+                        self.add_string(&format!("\"Scrollable Item {}\"", i));
                         self.add_plain(")");
-                    }
-                    
-                    // Horizontal alignment (only if not Left/default)
-                    if !matches!(props.wrapping_align_x, ContainerAlignX::Left) {
-                        self.add_newline();
-                        self.add_indent();
-                        self.add_operator(".");
-                        self.add_function("align_x");
-                        self.add_plain("(");
-                        match props.wrapping_align_x {
-                            ContainerAlignX::Left => self.add_type("Alignment::Start"),
-                            ContainerAlignX::Center => self.add_type("Alignment::Center"),
-                            ContainerAlignX::Right => self.add_type("Alignment::End"),
+                        if i < 10 {
+                            self.add_plain(",");
                         }
-                        self.add_plain(")");
+                        self.add_newline();
                     }
-                }
-            }
-            WidgetType::Column => {
-                self.add_indent();
-                self.add_macro("column!");
-                self.add_plain("[");
-                self.add_newline();
-                self.indent_level += 1;
-                
-                if widget.children.is_empty() {
+                    self.indent_level -= 1;
                     self.add_indent();
-                    self.add_function("text");
-                    self.add_plain("(");
-                    self.add_string("\"Column Item\"");
-                    self.add_plain(")");
+                    self.add_plain("]");
                 } else {
-                    for (i, child) in widget.children.iter().enumerate() {
+                    for child in &widget.children {
                         self.generate_widget_creation(child, use_self);
-                        if i < widget.children.len() - 1 {
-                            self.add_plain(",");
-                        }
-                        self.add_newline();
                     }
                 }
                 
                 self.add_newline();
                 self.indent_level -= 1;
                 self.add_indent();
-                self.add_plain("]");
-                self.generate_layout_properties(props, false);
-            }
-            WidgetType::Button => {
-                let name = self.get_widget_name(widget.id);
-                self.add_indent();
-                self.add_function("button");
-                self.add_plain("(");
-//                self.add_function("text");
-//                self.add_plain("(");
-                self.add_string(&format!("\"{}\"", props.text_content));
-//                self.add_plain(")");
-                self.add_plain(")");
-                self.generate_button_properties(widget, props);
-            }
-            WidgetType::Text => {
-                self.add_indent();
-                self.add_function("text");
-                self.add_plain("(");
-                self.add_string(&format!("\"{}\"", props.text_content));
                 self.add_plain(")");
-                self.generate_text_properties(props);
+                self.generate_scrollable_properties(props);
             }
-            WidgetType::TextInput => {
-                let name = self.get_widget_name(widget.id);
-                let props = &widget.properties;
-                
+            WidgetType::Space => {
                 self.add_indent();
-                self.add_function("text_input");
-                self.add_plain("(");
-                self.add_string(&format!("\"{}\"", props.text_input_placeholder));
-                self.add_plain(", ");
-                if use_self {
-                    self.add_operator("&");
-                    self.add_keyword("self");
-                    self.add_operator(".");
-                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
-                } else {
-                    self.add_string("\"\"");
+                match props.orientation {
+                    Orientation::Horizontal => {
+                        self.add_function("space::horizontal");
+                    }
+                    Orientation::Vertical => {
+                        self.add_function("space::vertical");
+                    }
                 }
-                self.add_plain(")");
-                self.indent_level += 1;
-                
-                // Always add on_input
-                self.add_newline();
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("on_input");
-                self.add_plain("(");
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain(&format!("{}Changed", to_pascal_case(&name)));
-                self.add_plain(")");
-                
-                // Conditionally add on_submit
-                if props.text_input_on_submit {
-                    self.add_newline();
+                self.add_plain("()");
+                self.generate_space_properties(props);
+            }
+            WidgetType::Rule => {
+                if let Some(note) = self.rule_length_note(widget) {
                     self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_submit");
-                    self.add_plain("(");
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Submitted", to_pascal_case(&name)));
-                    self.add_plain(")");
-                }
-                
-                // Conditionally add on_paste
-                if props.text_input_on_paste {
+                    self.add_comment(note);
                     self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_paste");
-                    self.add_plain("(");
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Pasted", to_pascal_case(&name)));
-                    self.add_plain(")");
                 }
-                
-                // Add secure if enabled
-                if props.is_secure {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("secure");
+
+                // Rules have no size methods of their own, so explicit sizes go on a container
+                let sized = rule_is_sized(props);
+                self.add_indent();
+                if sized {
+                    self.add_function("container");
                     self.add_plain("(");
-                    self.add_plain("true");
-                    self.add_plain(")");
                 }
-                
-                // Add font if not default
-                if props.text_input_font != FontType::Default {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("font");
-                    self.add_plain("(");
-                    match props.text_input_font {
-                        FontType::Monospace => {
-                            self.add_type("Font");
-                            self.add_operator("::");
-                            self.add_plain("MONOSPACE");
-                        }
-                        _ => {
-                            self.add_type("Font");
-                            self.add_operator("::");
-                            self.add_plain("default()");
-                        }
+                match props.orientation {
+                    Orientation::Horizontal => {
+                        self.add_function("rule::horizontal");
+                    }
+                    Orientation::Vertical => {
+                        self.add_function("rule::vertical");
                     }
+                }
+                self.add_plain("(");
+                self.add_number(&format!("{}", props.rule_thickness));
+                self.add_plain(")");
+                if sized {
                     self.add_plain(")");
+                    self.generate_rule_properties(props);
                 }
-                
-                // Add size
-                self.add_newline();
+            }
+            WidgetType::Image => {
                 self.add_indent();
-                self.add_operator(".");
-                self.add_function("size");
+                self.add_function("image");
                 self.add_plain("(");
-                self.add_plain(&format!("{}", props.text_input_size));
+                let path = self.asset_paths.get(&widget.id).unwrap_or(&props.image_path).clone();
+                if path.is_empty() {
+                    self.add_string("\"path/to/image.png\"");
+                } else {
+                    self.add_plain("r");
+                    self.add_string(&format!("\"{}\"", path));
+                }
                 self.add_plain(")");
-                
-                // Add padding
-                self.add_newline();
+                self.generate_image_properties(props);
+            }
+            WidgetType::Svg => {
+                self.add_indent();
+                self.add_function("svg");
+                self.add_plain("(svg::Handle::from_path(");
+                let path = self.asset_paths.get(&widget.id).unwrap_or(&props.svg_path).clone();
+                if path.is_empty() {
+                    self.add_string("\"path/to/icon.svg\"");
+                } else {
+                    self.add_plain("r");
+                    self.add_string(&format!("\"{}\"", path));
+                }
+                self.add_plain("))");
+                self.generate_svg_properties(props);
+            }
+            WidgetType::Tooltip => {
                 self.add_indent();
-                self.add_operator(".");
-                self.add_function("padding");
+                self.add_function("tooltip");
                 self.add_plain("(");
-                self.add_plain(&format!("{}", props.text_input_padding));
-                self.add_plain(")");
+                self.add_newline();
+                self.indent_level += 1;
                 
-                // Add line_height if not default
-                if props.text_input_line_height != text::LineHeight::default() {
-                    self.add_newline();
+                // First child (host)
+                if let Some(host) = widget.children.get(0) {
+                    self.generate_widget_creation(host, use_self);
+                } else {
                     self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("line_height");
+                    self.add_function("text");
                     self.add_plain("(");
-                    // Generate line_height value based on type
-                    match props.text_input_line_height {
-                        text::LineHeight::Absolute(pixels) => {
-                            self.add_plain(&format!("{}", pixels.0));
-                        }
-                        text::LineHeight::Relative(factor) => {
-                            self.add_plain("text::LineHeight::Relative(");
-                            self.add_plain(&format!("{}", factor));
-                            self.add_plain(")");
-                        }
-                    }
+                    self.add_string("\"Hover me\"");
                     self.add_plain(")");
                 }
+                self.add_plain(",");
+                self.add_newline();
                 
-                // Add alignment if not left
-                if props.text_input_alignment != ContainerAlignX::Left {
-                    self.add_newline();
+                // Second child (tooltip content) or text
+                if let Some(content) = widget.children.get(1) {
+                    self.generate_widget_creation(content, use_self);
+                } else {
                     self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("align_x");
+                    self.add_function("text");
                     self.add_plain("(");
-                    //self.add_plain("alignment::Horizontal::");
-                    self.add_plain("Alignment::");
-                    match props.text_input_alignment {
-                        ContainerAlignX::Left => self.add_plain("Start"),
-                        ContainerAlignX::Center => self.add_plain("Center"),
-                        ContainerAlignX::Right => self.add_plain("End"),
-                    }
+                    self.add_string(&format!("\"{}\"", props.tooltip_text));
                     self.add_plain(")");
                 }
+                self.add_plain(",");
+                self.add_newline();
                 
-                // Add width
-                if !matches!(props.width, Length::Fill) {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("width");
-                    self.add_plain("(");
-                    self.add_length(props.width);
-                    self.add_plain(")");
+                // Position
+                self.add_indent();
+                self.add_plain("tooltip::Position::");
+                match props.tooltip_position {
+                    TooltipPosition::Top => self.add_plain("Top"),
+                    TooltipPosition::Bottom => self.add_plain("Bottom"),
+                    TooltipPosition::Left => self.add_plain("Left"),
+                    TooltipPosition::Right => self.add_plain("Right"),
+                    TooltipPosition::FollowCursor => self.add_plain("FollowCursor"),
                 }
                 
+                self.add_newline();
                 self.indent_level -= 1;
+                self.add_indent();
+                self.add_plain(")");
             }
-            WidgetType::Checkbox => {
+            WidgetType::ComboBox if !use_self => {
+                // combo_box borrows a combo_box::State the caller would have to own,
+                // so without state it is shown as the pick_list it opens into
+                let mut stand_in = widget.clone();
+                stand_in.widget_type = WidgetType::PickList;
+                stand_in.properties.picklist_options = props.combobox_options.clone();
+                stand_in.properties.picklist_selected = props.combobox_selected.clone();
+                stand_in.properties.picklist_placeholder = props.combobox_placeholder.clone();
+                self.generate_widget_creation(&stand_in, false);
+            }
+            WidgetType::ComboBox => {
                 let name = self.get_widget_name(widget.id);
                 self.add_indent();
-                self.add_function("checkbox");
+                self.add_function("combo_box");
                 self.add_plain("(");
-                self.add_string(&format!("\"{}\"", props.checkbox_label));
-                self.add_plain(", ");
+                self.add_newline();
+                self.indent_level += 1;
+                
+                // State reference
+                self.add_indent();
+                self.add_operator("&");
                 if use_self {
-                    self.add_keyword("self");
+                    self.add_state_receiver();
                     self.add_operator(".");
-                    self.add_identifier(&format!("{}_checked", to_snake_case(&name)));
+                    self.add_identifier(&format!("{}_state", to_snake_case(&name)));
                 } else {
-                    self.add_keyword(if props.checkbox_checked { "true" } else { "false" });
+                    self.add_plain("state");
                 }
-                self.add_plain(")");
+                self.add_plain(",");
                 self.add_newline();
-                self.indent_level += 1;
+                
+                // Placeholder
+                self.add_indent();
+                self.add_string(&format!("\"{}\"", props.combobox_placeholder));
+                self.add_plain(",");
+                self.add_newline();
+                
+                // Current value
+                self.add_indent();
+                if use_self {
+                    self.add_plain("Some(");
+                    self.add_operator("&");
+                    self.add_state_receiver();
+                    self.add_operator(".");
+                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
+                    self.add_plain(")");
+                } else {
+                    if let Some(ref val) = props.combobox_selected {
+                        self.add_plain("Some(");
+                        self.add_string(&format!("\"{}\"", val));
+                        self.add_plain(")");
+                    } else {
+                        self.add_plain("None");
+                    }
+                }
+                self.add_plain(",");
+                self.add_newline();
+                
+                // On option selected (always present)
                 self.add_indent();
-                self.add_operator(".");
-                self.add_function("on_toggle");
-                self.add_plain("(");
                 if use_self {
                     self.add_type("Message");
                     self.add_operator("::");
-                    self.add_plain(&format!("{}Toggled", to_pascal_case(&name)));
+                    self.add_variant(WiringPass::Sent, &format!("{}Selected", to_pascal_case(&name)));
                 } else {
                     self.add_operator("|");
                     self.add_identifier("_");
@@ -2602,42 +4915,43 @@ impl<'a> CodeGenerator<'a> {
                     self.add_operator("::");
                     self.add_plain("Noop");
                 }
-                self.add_plain(")");
-                self.generate_checkbox_properties(props);
+                self.add_newline();
+                
                 self.indent_level -= 1;
-            }
-            WidgetType::Radio => {
-                let name = self.get_widget_name(widget.id);
                 self.add_indent();
-                self.add_macro("column!");
-                self.add_plain("[");
-                self.add_newline();
-                self.indent_level += 1;
+                self.add_plain(")");
                 
-                for (i, option) in props.radio_options.iter().enumerate() {
-                    self.add_indent();
-                    self.add_function("radio");
+                // Now add optional methods
+                if props.combobox_use_on_input {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_input");
                     self.add_plain("(");
-                    self.add_string(&format!("\"{}\"", option));
-                    self.add_plain(", ");
-                    self.add_number(&format!("{}", i));
-                    self.add_plain(", ");
                     if use_self {
-                        self.add_plain("Some(");
-                        self.add_keyword("self");
-                        self.add_operator(".");
-                        self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
-                        self.add_plain(")");
+                        self.add_type("Message");
+                        self.add_operator("::");
+                        self.add_variant(WiringPass::Sent, &format!("{}OnInput", to_pascal_case(&name)));
                     } else {
-                        self.add_plain("Some(");
-                        self.add_number(&format!("{}", props.radio_selected_index));
-                        self.add_plain(")");
+                        self.add_operator("|");
+                        self.add_identifier("_");
+                        self.add_operator("|");
+                        self.add_plain(" ");
+                        self.add_type("Message");
+                        self.add_operator("::");
+                        self.add_plain("Noop");
                     }
-                    self.add_plain(", ");
+                    self.add_plain(")");
+                }
+                
+                if props.combobox_use_on_option_hovered {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_option_hovered");
+                    self.add_plain("(");
                     if use_self {
                         self.add_type("Message");
                         self.add_operator("::");
-                        self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
+                        self.add_variant(WiringPass::Sent, &format!("{}OnOptionHovered", to_pascal_case(&name)));
                     } else {
                         self.add_operator("|");
                         self.add_identifier("_");
@@ -2648,2609 +4962,5339 @@ impl<'a> CodeGenerator<'a> {
                         self.add_plain("Noop");
                     }
                     self.add_plain(")");
-                    if props.radio_size != 16.0 {
-                        self.add_newline();
-                        self.indent_level += 1;
-                        self.add_indent();
-                        self.add_operator(".");
-                        self.add_function("size");
-                        self.add_plain("(");
-                        self.add_number(&format!("{}", props.radio_size));
-                        self.add_plain(")");
-                        self.indent_level -= 1;
-                    }
-                    if props.width != Length::Shrink {
-                        self.add_newline();
-                        self.indent_level += 1;
-                        self.add_indent();
-                        self.add_operator(".");
-                        self.add_function("width");
-                        self.add_plain("(");
-                        self.add_length(props.width);
-                        self.add_plain(")");
-                        self.indent_level -= 1;
-                    }
-                    if i < props.radio_options.len() - 1 {
-                        self.add_plain(",");
-                    }
-                    self.add_newline();
                 }
                 
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("]");
-            }
-            WidgetType::Slider => {
-                let name = self.get_widget_name(widget.id);
-                self.add_indent();
-                self.add_function("slider");
-                self.add_plain("(");
-                self.add_number(&format!("{:.1}", props.slider_min));
-                self.add_operator("..=");
-                self.add_number(&format!("{:.1}", props.slider_max));
-                self.add_plain(", ");
-                if use_self {
-                    self.add_keyword("self");
+                if props.combobox_use_on_open {
+                    self.chain_break();
                     self.add_operator(".");
-                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
-                } else {
-                    self.add_number(&format!("{}", props.slider_value));
-                }
-                self.add_plain(", ");
-                if use_self {
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Changed", to_pascal_case(&name)));
-                } else {
-                    self.add_operator("|");
-                    self.add_identifier("_");
-                    self.add_operator("|");
-                    self.add_plain(" ");
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain("Noop");
+                    self.add_function("on_open");
+                    self.add_plain("(");
+                    if use_self {
+                        self.add_type("Message");
+                        self.add_operator("::");
+                        self.add_variant(WiringPass::Sent, &format!("{}OnOpen", to_pascal_case(&name)));
+                    } else {
+                        self.add_type("Message");
+                        self.add_operator("::");
+                        self.add_plain("Noop");
+                    }
+                    self.add_plain(")");
                 }
-                self.add_plain(")");
-                self.generate_slider_properties(props);
-            }
-            WidgetType::VerticalSlider => {
-                let name = self.get_widget_name(widget.id);
-                self.add_indent();
-                self.add_function("vertical_slider");
-                self.add_plain("(");
-                self.add_number(&format!("{:.1}", props.slider_min));
-                self.add_operator("..=");
-                self.add_number(&format!("{:.1}", props.slider_max));
-                self.add_plain(", ");
-                if use_self {
-                    self.add_keyword("self");
+                
+                if props.combobox_use_on_close {
+                    self.chain_break();
                     self.add_operator(".");
-                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
-                } else {
-                    self.add_number(&format!("{}", props.slider_value));
-                }
-                self.add_plain(", ");
-                if use_self {
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Changed", to_pascal_case(&name)));
-                } else {
-                    self.add_operator("|");
-                    self.add_identifier("_");
-                    self.add_operator("|");
-                    self.add_plain(" ");
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain("Noop");
+                    self.add_function("on_close");
+                    self.add_plain("(");
+                    if use_self {
+                        self.add_type("Message");
+                        self.add_operator("::");
+                        self.add_variant(WiringPass::Sent, &format!("{}OnClose", to_pascal_case(&name)));
+                    } else {
+                        self.add_type("Message");
+                        self.add_operator("::");
+                        self.add_plain("Noop");
+                    }
+                    self.add_plain(")");
                 }
-                self.add_plain(")");
-                self.generate_vertical_slider_properties(props);
+                
+                self.generate_combobox_properties(props);
             }
-            WidgetType::ProgressBar => {
+            
+            WidgetType::Markdown if self.layout_only => {
+                let source = props.markdown_source.text();
+                let first_line = source.lines()
+                    .map(|line| line.trim_start_matches('#').trim())
+                    .find(|line| !line.is_empty())
+                    .unwrap_or("Markdown");
                 self.add_indent();
-                self.add_function("progress_bar");
+                self.add_function("text");
                 self.add_plain("(");
-                self.add_number(&format!("{:.1}", props.progress_min));
-                self.add_operator("..=");
-                self.add_number(&format!("{:.1}", props.progress_max));
-                self.add_plain(", ");
-                self.add_number(&format!("{:.2}", props.progress_value));
+                self.add_string(&format!("{:?}", first_line));
                 self.add_plain(")");
-                self.generate_progress_properties(props);
             }
-            WidgetType::Toggler => {
+            WidgetType::Markdown => {
                 let name = self.get_widget_name(widget.id);
+                let sized = props.width != Length::Fill || props.height != Length::Shrink;
                 self.add_indent();
-                self.add_function("toggler");
-                self.add_plain("(");
-                if use_self {
-                    self.add_keyword("self");
-                    self.add_operator(".");
-                    self.add_identifier(&format!("{}_active", to_snake_case(&name)));
-                } else {
-                    self.add_keyword(if props.toggler_active { "true" } else { "false" });
+                if sized {
+                    // The markdown view is a plain Element, so a container carries the size
+                    self.add_function("container");
+                    self.add_plain("(");
                 }
-                self.add_plain(")");
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
+                self.add_function("markdown::view");
+                self.add_plain("(");
+                self.add_state_receiver();
                 self.add_operator(".");
-                self.add_function("on_toggle");
+                self.add_identifier(&format!("{}_content", to_snake_case(&name)));
+                self.add_operator(".");
+                self.add_function("items");
+                self.add_plain("(), ");
+                self.add_type("markdown::Settings");
+                self.add_operator("::");
+                self.add_function("with_text_size");
                 self.add_plain("(");
-                if use_self {
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Toggled", to_pascal_case(&name)));
-                } else {
-                    self.add_operator("|");
-                    self.add_identifier("_");
-                    self.add_operator("|");
-                    self.add_plain(" ");
-                    self.add_type("Message");
+                self.add_number(&format!("{:.1}", props.markdown_text_size));
+                self.add_plain(", ");
+                // The app's own theme, so the document follows it if the app changes theme
+                self.add_state_receiver();
+                self.add_operator(".");
+                self.add_function("theme");
+                self.add_plain("(");
+                if self.multi_window {
+                    // Every window gets the same theme, so any id will do
+                    self.add_type("window::Id");
                     self.add_operator("::");
-                    self.add_plain("Noop");
+                    self.add_function("unique");
+                    self.add_plain("()");
                 }
+                self.add_plain(")))");
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("map");
+                self.add_plain("(");
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Sent, &format!("{}LinkClicked", to_pascal_case(&name)));
                 self.add_plain(")");
-                self.generate_toggler_properties(props);
                 self.indent_level -= 1;
+                if sized {
+                    self.add_plain(")");
+                    self.generate_markdown_properties(props);
+                }
             }
-            WidgetType::PickList => {
-                let name = self.get_widget_name(widget.id);
+            
+            WidgetType::MouseArea => {
                 self.add_indent();
-                self.add_function("pick_list");
+                self.add_function("mouse_area");
                 self.add_plain("(");
                 self.add_newline();
+                
+                // Generate child
+                if !widget.children.is_empty() {
+                    self.generate_widget_creation(&widget.children[0], use_self);
+                }
+                
+                self.add_plain(")");
                 self.indent_level += 1;
-                self.add_indent();
-                self.add_plain("vec![");
-                for (i, option) in props.picklist_options.iter().enumerate() {
-                    self.add_string(&format!("\"{}\"", option));
+                
+                let name = self.get_widget_name(widget.id);
+                
+                // Conditionally add event handlers
+                let handlers = !self.layout_only;
+                if handlers && props.mousearea_on_press {
+                    self.chain_break();
                     self.add_operator(".");
-                    self.add_function("to_string");
-                    self.add_plain("()");
-                    if i < props.picklist_options.len() - 1 {
-                        self.add_plain(", ");
-                    }
+                    self.add_function("on_press");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Pressed", to_pascal_case(&name)));
+                    self.add_plain(")");
                 }
-                self.add_plain("],");
-                self.add_newline();
-                self.add_indent();
-                if use_self {
-                    self.add_keyword("self");
+
+                if handlers && props.mousearea_on_release {
+                    self.chain_break();
                     self.add_operator(".");
-                    self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
+                    self.add_function("on_release");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Released", to_pascal_case(&name)));
+                    self.add_plain(")");
+                }
+
+                if handlers && props.mousearea_on_double_click {
+                    self.chain_break();
                     self.add_operator(".");
-                    self.add_function("clone");
-                    self.add_plain("()");
-                } else if let Some(ref selected) = props.picklist_selected {
-                    self.add_plain("Some(");
-                    self.add_string(&format!("\"{}\"", selected));
+                    self.add_function("on_double_click");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}DoubleClicked", to_pascal_case(&name)));
+                    self.add_plain(")");
+                }
+
+                if handlers && props.mousearea_on_right_press {
+                    self.chain_break();
                     self.add_operator(".");
-                    self.add_function("to_string");
-                    self.add_plain("())");
-                } else {
-                    self.add_plain("None");
+                    self.add_function("on_right_press");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}RightPressed", to_pascal_case(&name)));
+                    self.add_plain(")");
                 }
-                self.add_plain(",");
-                self.add_newline();
-                self.add_indent();
-                if use_self {
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-                } else {
-                    self.add_operator("|");
-                    self.add_identifier("_");
-                    self.add_operator("|");
-                    self.add_plain(" ");
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain("Noop");
+
+                if handlers && props.mousearea_on_right_release {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_right_release");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}RightReleased", to_pascal_case(&name)));
+                    self.add_plain(")");
+                }
+
+                if handlers && props.mousearea_on_middle_press {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_middle_press");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}MiddlePressed", to_pascal_case(&name)));
+                    self.add_plain(")");
+                }
+
+                if handlers && props.mousearea_on_middle_release {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_middle_release");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}MiddleReleased", to_pascal_case(&name)));
+                    self.add_plain(")");
                 }
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain(")");
-                self.generate_picklist_properties(props);
-            }
-            WidgetType::Scrollable => {
-                self.add_indent();
-                self.add_function("scrollable");
-                self.add_plain("(");
-                self.add_newline();
-                self.indent_level += 1;
                 
-                if widget.children.is_empty() {
-                    self.add_indent();
-                    self.add_macro("column!");
-                    self.add_plain("[");
-                    self.add_newline();
-                    self.indent_level += 1;
-                    for i in 1..=10 {
-                        self.add_indent();
-                        self.add_function("text");
-                        self.add_plain("(");
-                        self.add_string(&format!("\"Scrollable Item {}\"", i));
-                        self.add_plain(")");
-                        if i < 10 {
-                            self.add_plain(",");
-                        }
-                        self.add_newline();
-                    }
-                    self.indent_level -= 1;
-                    self.add_indent();
-                    self.add_plain("]");
-                } else {
-                    for child in &widget.children {
-                        self.generate_widget_creation(child, use_self);
-                    }
+                if handlers && props.mousearea_on_scroll {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_scroll");
+                    self.add_plain("(|delta| Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Scrolled", to_pascal_case(&name)));
+                    self.add_plain("(delta))");
+                }
+
+                if handlers && props.mousearea_on_enter {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_enter");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Entered", to_pascal_case(&name)));
+                    self.add_plain(")");
                 }
                 
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain(")");
-                self.generate_scrollable_properties(props);
-            }
-            WidgetType::Space => {
-                self.add_indent();
-                match props.orientation {
-                    Orientation::Horizontal => {
-                        self.add_function("space::horizontal");
-                    }
-                    Orientation::Vertical => {
-                        self.add_function("space::vertical");
-                    }
+                if handlers && props.mousearea_on_move {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_move");
+                    self.add_plain("(|point| Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Moved", to_pascal_case(&name)));
+                    self.add_plain("(point))");
                 }
-                self.add_plain("()");
-                self.generate_space_properties(props);
+
+                if handlers && props.mousearea_on_exit {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("on_exit");
+                    self.add_plain("(Message::");
+                    self.add_variant(WiringPass::Sent, &format!("{}Exited", to_pascal_case(&name)));
+                    self.add_plain(")");
+                }
+                
+                if let Some(interaction) = props.mousearea_interaction {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("interaction");
+                    self.add_plain("(Interaction::");
+                    self.add_plain(&format!("{:?}", interaction));
+                    self.add_plain(")");
+                }
+                
+                // Add layout properties
+                //self.generate_layout_properties(props, false);
+                self.indent_level -= 1;
             }
-            WidgetType::Rule => {
+            
+            WidgetType::QRCode => {
                 self.add_indent();
-                match props.orientation {
-                    Orientation::Horizontal => {
-                        self.add_function("rule::horizontal");
-                    }
-                    Orientation::Vertical => {
-                        self.add_function("rule::vertical");
-                    }
-                }
+                self.add_type("qr_code::QRCode");
+                self.add_operator("::");
+                self.add_function("new");
                 self.add_plain("(");
-                self.add_number(&format!("{}", props.rule_thickness));
+                self.add_string(&format!("\"{}\"", props.qrcode_data));
                 self.add_plain(")");
-            }
-            WidgetType::Image => {
-                self.add_indent();
-                self.add_function("image");
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("cell_size");
                 self.add_plain("(");
-                if props.image_path.is_empty() {
-                    self.add_string("\"path/to/image.png\"");
-                } else {
-                    self.add_plain("r");
-                    self.add_string(&format!("\"{}\"", props.image_path));
-                }
+                self.add_number(&format!("{}", props.qrcode_cell_size));
                 self.add_plain(")");
-                self.generate_image_properties(props);
-            }
-            WidgetType::Svg => {
-                self.add_indent();
-                self.add_function("svg");
-                self.add_plain("(svg::Handle::from_path(");
-                if props.svg_path.is_empty() {
-                    self.add_string("\"path/to/icon.svg\"");
-                } else {
-                    self.add_plain("r");
-                    self.add_string(&format!("\"{}\"", props.svg_path));
-                }
-                self.add_plain("))");
-                self.generate_svg_properties(props);
+                self.generate_qrcode_properties(props);
+                self.indent_level -= 1;
             }
-            WidgetType::Tooltip => {
+            
+            WidgetType::Stack => {
                 self.add_indent();
-                self.add_function("tooltip");
-                self.add_plain("(");
+                self.add_function("stack");
+                self.add_plain("(vec![");
                 self.add_newline();
                 self.indent_level += 1;
                 
-                // First child (host)
-                if let Some(host) = widget.children.get(0) {
-                    self.generate_widget_creation(host, use_self);
-                } else {
+                if widget.children.is_empty() {
                     self.add_indent();
                     self.add_function("text");
                     self.add_plain("(");
-                    self.add_string("\"Hover me\"");
-                    self.add_plain(")");
-                }
-                self.add_plain(",");
-                self.add_newline();
-                
-                // Second child (tooltip content) or text
-                if let Some(content) = widget.children.get(1) {
-                    self.generate_widget_creation(content, use_self);
-                } else {
+                    self.add_string("\"Layer 1\"");
+                    self.add_plain(").into(),");
+                    self.add_newline();
                     self.add_indent();
                     self.add_function("text");
                     self.add_plain("(");
-                    self.add_string(&format!("\"{}\"", props.tooltip_text));
-                    self.add_plain(")");
-                }
-                self.add_plain(",");
-                self.add_newline();
-                
-                // Position
-                self.add_indent();
-                self.add_plain("tooltip::Position::");
-                match props.tooltip_position {
-                    TooltipPosition::Top => self.add_plain("Top"),
-                    TooltipPosition::Bottom => self.add_plain("Bottom"),
-                    TooltipPosition::Left => self.add_plain("Left"),
-                    TooltipPosition::Right => self.add_plain("Right"),
-                    TooltipPosition::FollowCursor => self.add_plain("FollowCursor"),
+                    self.add_string("\"Layer 2\"");
+                    self.add_plain(").into(),");
+                } else {
+                    for (i, child) in widget.children.iter().enumerate() {
+                        match child.properties.float_anchor {
+                            Some(anchor) => self.generate_float_layer(child, anchor, use_self),
+                            None => self.generate_widget_creation(child, use_self),
+                        }
+                        self.add_operator(".");
+                        self.add_function("into");
+                        self.add_plain("()");
+                        if i < widget.children.len() - 1 {
+                            self.add_plain(",");
+                        }
+                        self.add_newline();
+                    }
                 }
                 
-                self.add_newline();
                 self.indent_level -= 1;
                 self.add_indent();
-                self.add_plain(")");
+                self.add_plain("])");
+                self.generate_stack_properties(props);
             }
-            WidgetType::ComboBox => {
-                let name = self.get_widget_name(widget.id);
+            
+            WidgetType::Themer => {
                 self.add_indent();
-                self.add_function("combo_box");
+                self.add_function("themer");
                 self.add_plain("(");
-                self.add_newline();
-                self.indent_level += 1;
-                
-                // State reference
-                self.add_indent();
-                self.add_operator("&");
-                if use_self {
-                    self.add_keyword("self");
-                    self.add_operator(".");
-                    self.add_identifier(&format!("{}_state", to_snake_case(&name)));
+                if let Some(theme) = &props.themer_theme {
+                    self.add_plain("Some(");
+                    self.add_theme(theme);
+                    self.add_plain(")");
                 } else {
-                    self.add_plain("state");
+                    self.add_plain("None");
                 }
-                self.add_plain(",");
-                self.add_newline();
-                
-                // Placeholder
-                self.add_indent();
-                self.add_string(&format!("\"{}\"", props.combobox_placeholder));
-                self.add_plain(",");
+                self.add_plain(", ");
                 self.add_newline();
+                self.indent_level += 1;
                 
-                // Current value
-                self.add_indent();
-                if use_self {
-                    self.add_plain("Some(");
-                    self.add_operator("&");
-                    self.add_keyword("self");
-                    self.add_operator(".");
-                    self.add_identifier(&format!("{}_value", to_snake_case(&name)));
-                    self.add_plain(")");
+                if widget.children.is_empty() {
+                    self.add_indent();
+                    self.add_function("container");
+                    self.add_plain("(");
+                    self.add_function("text");
+                    self.add_plain("(");
+                    self.add_string("\"Themed content\"");
+                    self.add_plain("))");
                 } else {
-                    if let Some(ref val) = props.combobox_selected {
-                        self.add_plain("Some(");
-                        self.add_string(&format!("\"{}\"", val));
-                        self.add_plain(")");
-                    } else {
-                        self.add_plain("None");
+                    for child in &widget.children {
+                        self.generate_widget_creation(child, use_self);
                     }
                 }
-                self.add_plain(",");
-                self.add_newline();
                 
-                // On option selected (always present)
+                self.add_newline();
+                self.indent_level -= 1;
                 self.add_indent();
-                if use_self {
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-                } else {
-                    self.add_operator("|");
-                    self.add_identifier("_");
-                    self.add_operator("|");
-                    self.add_plain(" ");
-                    self.add_type("Message");
-                    self.add_operator("::");
-                    self.add_plain("Noop");
+                self.add_plain(")");
+                self.generate_themer_properties(props);
+            } 
+            WidgetType::Pin => {
+            //    todo!("implement code gen for pin");
+            }
+        }
+    }
+
+    fn collect_widget_names(&mut self, root: &Widget) {
+        let mut widgets = Vec::new();
+        collect_tree_order(root, &mut widgets);
+        let names = resolve_names(widgets.iter().map(|w| (w.widget_type, w.properties.widget_name.as_str())));
+        self.widget_names.extend(widgets.iter().map(|w| w.id).zip(names));
+    }
+
+    /// `/// Button \`save\` at container > column > row_2`, plus options for list widgets
+    fn generate_widget_doc_comment(&mut self, widget: &Widget) {
+        let name = self.get_widget_name(widget.id);
+        let path = self.hierarchy.find_path(widget.id)
+            .into_iter()
+            .map(|id| self.get_widget_name(id))
+            .collect::<Vec<_>>()
+            .join(" > ");
+
+        self.add_indent();
+        self.add_comment(&format!("/// {} `{}` at {}", widget.widget_type, name, path));
+        self.add_newline();
+
+        let props = &widget.properties;
+        let options = match widget.widget_type {
+            WidgetType::Radio => Some(props.radio_options.join(", ")),
+            WidgetType::PickList => Some(props.picklist_options.join(", ")),
+            WidgetType::ComboBox => {
+                let enum_name = props.referenced_enum
+                    .and_then(|id| self.type_system.and_then(|ts| ts.get_enum(id)))
+                    .map(|enum_def| enum_def.name.clone());
+                match enum_name {
+                    Some(enum_name) => Some(format!("enum {}", enum_name)),
+                    None => Some(props.combobox_options.join(", ")),
                 }
-                self.add_newline();
+            }
+            _ => None,
+        };
+
+        if let Some(options) = options {
+            self.add_indent();
+            self.add_comment(&format!("/// Options: {}", options));
+            self.add_newline();
+        }
+    }
+
+    /// Resolves the names every widget will have in the generated code
+    pub fn resolved_widget_names(&mut self) -> HashMap<WidgetId, String> {
+        self.generate_all_widget_names();
+        self.widget_names.clone()
+    }
+
+    fn get_widget_name(&self, widget_id: WidgetId) -> String {
+        self.widget_names.get(&widget_id)
+            .cloned()
+            .unwrap_or_else(|| "widget".to_string())
+    }
+
+    /// Bool state field the widget's conditional style branches on. None without a condition,
+    /// in layout-only output, or once the Checkbox or Toggler it followed is gone.
+    fn condition_field(&self, widget: &Widget) -> Option<String> {
+        if self.layout_only || !matches!(widget.widget_type, WidgetType::Container | WidgetType::TextInput) {
+            return None;
+        }
+        match widget.properties.style_condition.as_ref()? {
+            StyleCondition::Flag(flag) => condition_flag_field(flag),
+            StyleCondition::Widget(id) => {
+                let suffix = match condition_source(self.hierarchy, *id)?.widget_type {
+                    WidgetType::Checkbox => "checked",
+                    _ => "active",
+                };
+                Some(format!("{}_{}", to_snake_case(&self.get_widget_name(*id)), suffix))
+            }
+        }
+    }
+
+    /// Condition fields of every conditional style below `widget`, in tree order and each once
+    fn collect_condition_fields(&self, widget: &Widget, fields: &mut Vec<String>) {
+        if let Some(field) = self.condition_field(widget)
+            && !(widget.widget_type == WidgetType::Container && widget.properties.gradient_enabled)
+            && !fields.contains(&field)
+        {
+            fields.push(field);
+        }
+        // A helper copies the flags its own widgets branch on
+        for child in widget.children.iter().filter(|child| !self.calls_helper(child)) {
+            self.collect_condition_fields(child, fields);
+        }
+    }
+
+    /// The group as a `column!`, or a `row!` for a horizontal one, holding the label and one
+    /// `radio` per option
+    fn generate_radio_widget(&mut self, widget: &Widget, use_self: bool, use_column: bool) {
+        let props = &widget.properties;
+        let name = self.get_widget_name(widget.id);
+        self.add_indent();
+        self.add_macro(if use_column { "column!" } else { "row!" });
+        self.add_plain("[");
+        self.add_newline();
+        self.indent_level += 1;
+
+        // Group label
+        if !props.radio_label.is_empty() {
+            self.add_indent();
+            self.add_function("text");
+            self.add_plain("(");
+            self.add_string(&format!("\"{}\"", props.radio_label));
+            self.add_plain(")");
+            if !props.radio_options.is_empty() {
+                self.add_plain(",");
+            }
+            self.add_newline();
+        }
                 
+        let left = props.label_position == LabelPosition::Left;
+        for (i, option) in props.radio_options.iter().enumerate() {
+            if left {
+                self.open_left_label_row(option);
+            }
+            self.add_indent();
+            self.add_function("radio");
+            self.add_plain("(");
+            self.add_string(&format!("\"{}\"", if left { "" } else { option }));
+            self.add_plain(", ");
+            self.add_number(&format!("{}", i));
+            self.add_plain(", ");
+            if use_self {
+                self.add_plain("Some(");
+                self.add_state_receiver();
+                self.add_operator(".");
+                self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
+                self.add_plain(")");
+            } else {
+                self.add_plain("Some(");
+                self.add_number(&format!("{}", props.radio_selected_index));
+                self.add_plain(")");
+            }
+            self.add_plain(", ");
+            if use_self {
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_variant(WiringPass::Sent, &format!("{}Selected", to_pascal_case(&name)));
+            } else {
+                self.add_operator("|");
+                self.add_identifier("_");
+                self.add_operator("|");
+                self.add_plain(" ");
+                self.add_type("Message");
+                self.add_operator("::");
+                self.add_plain("Noop");
+            }
+            self.add_plain(")");
+            if props.radio_size != 16.0 {
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("size");
+                self.add_plain("(");
+                self.add_number(&format!("{}", props.radio_size));
+                self.add_plain(")");
                 self.indent_level -= 1;
-                self.add_indent();
+            }
+            if left {
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("spacing");
+                self.add_plain("(");
+                self.add_number("0");
+                self.add_plain(")");
+                self.indent_level -= 1;
+                self.close_left_label_row(props.radio_spacing, props.width);
+            } else if props.radio_spacing != iced::widget::radio::Radio::<Theme>::DEFAULT_SPACING {
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("spacing");
+                self.add_plain("(");
+                self.add_number(&format!("{}", props.radio_spacing));
+                self.add_plain(")");
+                self.indent_level -= 1;
+            }
+            if !left && props.width != Length::Shrink {
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("width");
+                self.add_plain("(");
+                self.add_length(props.width);
                 self.add_plain(")");
+                self.indent_level -= 1;
+            }
+            if i < props.radio_options.len() - 1 {
+                self.add_plain(",");
+            }
+            self.add_newline();
+        }
                 
-                // Now add optional methods
-                if props.combobox_use_on_input {
-                    self.add_newline();
-                    self.add_indent();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("]");
+        // Room between the options
+        if props.spacing != 0.0 {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("spacing");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.spacing));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+    }
+
+    const CONTAINER_EMITS: &'static [&'static str] = &[
+        "align_x", "align_y", "clip", "condition_background_color", "condition_border_color", "container_center_length",
+        "container_sizing_mode", "gradient_angle", "gradient_enabled", "gradient_end", "gradient_start",
+        "height", "max_height", "max_width", "named_style", "padding", "padding_mode", "style_condition", "widget_id", "width",
+    ];
+    fn generate_container_properties(&mut self, props: &Properties) {
+        // Widget ID
+        if let Some(ref id) = props.widget_id {
+            if !id.is_empty() {
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("id");
+                self.add_plain("(");
+                self.add_string(&format!("\"{}\"", id));
+                self.add_plain(")");
+            }
+        }
+
+        // Sizing
+        match props.container_sizing_mode {
+            ContainerSizingMode::Manual => {
+                // Width
+                if !matches!(props.width, Length::Fill) {
+                    self.chain_break();
                     self.add_operator(".");
-                    self.add_function("on_input");
+                    self.add_function("width");
                     self.add_plain("(");
-                    if use_self {
-                        self.add_type("Message");
-                        self.add_operator("::");
-                        self.add_plain(&format!("{}OnInput", to_pascal_case(&name)));
-                    } else {
-                        self.add_operator("|");
-                        self.add_identifier("_");
-                        self.add_operator("|");
-                        self.add_plain(" ");
-                        self.add_type("Message");
-                        self.add_operator("::");
-                        self.add_plain("Noop");
-                    }
+                    self.add_length(props.width);
                     self.add_plain(")");
                 }
                 
-                if props.combobox_use_on_option_hovered {
-                    self.add_newline();
-                    self.add_indent();
+                // Height
+                if !matches!(props.height, Length::Fill) {
+                    self.chain_break();
                     self.add_operator(".");
-                    self.add_function("on_option_hovered");
+                    self.add_function("height");
                     self.add_plain("(");
-                    if use_self {
-                        self.add_type("Message");
-                        self.add_operator("::");
-                        self.add_plain(&format!("{}OnOptionHovered", to_pascal_case(&name)));
-                    } else {
-                        self.add_operator("|");
-                        self.add_identifier("_");
-                        self.add_operator("|");
-                        self.add_plain(" ");
-                        self.add_type("Message");
-                        self.add_operator("::");
-                        self.add_plain("Noop");
-                    }
+                    self.add_length(props.height);
                     self.add_plain(")");
                 }
                 
-                if props.combobox_use_on_open {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_open");
-                    self.add_plain("(");
-                    if use_self {
-                        self.add_type("Message");
-                        self.add_operator("::");
-                        self.add_plain(&format!("{}OnOpen", to_pascal_case(&name)));
-                    } else {
-                        self.add_type("Message");
-                        self.add_operator("::");
-                        self.add_plain("Noop");
+                // Alignment
+                match props.align_x {
+                    ContainerAlignX::Left => {},
+                    ContainerAlignX::Center => {
+                        self.chain_break();
+                        self.add_operator(".");
+                        self.add_function("align_x");
+                        self.add_plain("(");
+                        self.add_type("Alignment::Center");
+                        self.add_plain(")");
                     }
-                    self.add_plain(")");
+                    ContainerAlignX::Right => {
+                        self.chain_break();
+                        self.add_operator(".");
+                        self.add_function("align_x");
+                        self.add_plain("(");
+                        self.add_type("Alignment::End");
+                        self.add_plain(")");
+                    }
+                }
+                match props.align_y {
+                    ContainerAlignY::Top => {},
+                    ContainerAlignY::Center => {
+                        self.chain_break();
+                        self.add_operator(".");
+                        self.add_function("align_y");
+                        self.add_plain("(");
+                        self.add_type("Alignment::Center");
+                        self.add_plain(")");
+                    }
+                    ContainerAlignY::Bottom => {
+                        self.chain_break();
+                        self.add_operator(".");
+                        self.add_function("align_y");
+                        self.add_plain("(");
+                        self.add_type("Alignment::End");
+                        self.add_plain(")");
+                    }
+                }
+            }
+            ContainerSizingMode::CenterX => {
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("center_x");
+                self.add_plain("(");
+                self.add_length(props.container_center_length);
+                self.add_plain(")");
+            }
+            ContainerSizingMode::CenterY => {
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("center_y");
+                self.add_plain("(");
+                self.add_length(props.container_center_length);
+                self.add_plain(")");
+            }
+            ContainerSizingMode::Center => {
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("center");
+                self.add_plain("(");
+                self.add_length(props.container_center_length);
+                self.add_plain(")");
+            }
+        }
+
+        // Max width
+        if let Some(max_w) = props.max_width {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("max_width");
+            self.add_plain("(");
+            self.add_number(&format!("{:.1}", max_w));
+            self.add_plain(")");
+        }
+
+        // Max Height
+        if let Some(max_h) = props.max_height {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("max_height");
+            self.add_plain("(");
+            self.add_number(&format!("{:.1}", max_h));
+            self.add_plain(")");
+        }
+        
+        // Padding
+        if props.padding != Padding::ZERO {
+            self.generate_padding(&props.padding, props.padding_mode);
+        }
+
+        // Clip
+        if props.clip {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("clip");
+            self.add_plain("(");
+            self.add_keyword("true");
+            self.add_plain(")");
+        }
+
+        // Gradient background
+        if props.gradient_enabled {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("style");
+            self.add_plain("(|_| ");
+            self.add_type("container::Style");
+            self.add_plain(" {");
+            self.indent_level += 1;
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("background: Some(");
+            self.add_type("Background");
+            self.add_operator("::");
+            self.add_type("Gradient");
+            self.add_plain("(");
+            self.indent_level += 1;
+            self.add_newline();
+            self.add_indent();
+            self.add_function("gradient::Linear::new");
+            self.add_plain("(");
+            self.add_type("Radians");
+            self.add_plain("(");
+            self.add_number(&format!("{:.1}_f32", props.gradient_angle));
+            self.add_operator(".");
+            self.add_function("to_radians");
+            self.add_plain("()))");
+            self.indent_level += 1;
+            for (offset, color) in [(0.0, props.gradient_start), (1.0, props.gradient_end)] {
+                self.add_newline();
+                self.add_indent();
+                self.add_operator(".");
+                self.add_function("add_stop");
+                self.add_plain("(");
+                self.add_number(&format!("{:.1}", offset));
+                self.add_plain(", ");
+                self.add_color(color);
+                self.add_plain(")");
+            }
+            self.add_newline();
+            self.add_indent();
+            self.add_operator(".");
+            self.add_function("into");
+            self.add_plain("(),");
+            self.indent_level -= 2;
+            self.add_newline();
+            self.add_indent();
+            self.add_plain(")),");
+            self.add_newline();
+            self.add_indent();
+            self.add_operator("..");
+            self.add_function("container::Style::default");
+            self.add_plain("()");
+            self.indent_level -= 1;
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("})");
+        }
+    }
+
+    /// `.style()` switching to the alternate colors while `field` is true. Both branches
+    /// draw the Container's own background and border, so the base look is kept.
+    fn generate_container_condition_style(&mut self, props: &Properties, field: &str) {
+        let alternate_background = if props.condition_background_color.a > 0.0 {
+            props.condition_background_color
+        } else {
+            props.background_color
+        };
+
+        self.chain_break();
+        self.add_operator(".");
+        self.add_function("style");
+        self.add_plain("(");
+        self.add_keyword("move");
+        self.add_plain(" |_| ");
+        self.add_keyword("if");
+        self.add_plain(" ");
+        self.add_identifier(field);
+        self.add_plain(" {");
+        self.indent_level += 1;
+        self.add_container_style_branch(alternate_background, props.condition_border_color, props.border_width.max(1.0), props.border_radius);
+        self.indent_level -= 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("} ");
+        self.add_keyword("else");
+        self.add_plain(" {");
+        self.indent_level += 1;
+        self.add_container_style_branch(props.background_color, props.border_color, props.border_width, props.border_radius);
+        self.indent_level -= 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("})");
+    }
+
+    /// A `container::Style` literal on lines of its own, leaving out a transparent background
+    fn add_container_style_branch(&mut self, background: Color, border_color: Color, border_width: f32, border_radius: f32) {
+        self.add_container_style_literal(background, border_color, border_width, border_radius, None);
+    }
+
+    /// `add_container_style_branch` with a shadow of color, offset and blur radius
+    fn add_container_style_literal(
+        &mut self,
+        background: Color,
+        border_color: Color,
+        border_width: f32,
+        border_radius: f32,
+        shadow: Option<(Color, (f32, f32), f32)>,
+    ) {
+        self.add_newline();
+        self.add_indent();
+        self.add_type("container::Style");
+        self.add_plain(" {");
+        self.indent_level += 1;
+        if background.a > 0.0 {
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("background: Some(");
+            self.add_type("Background");
+            self.add_operator("::");
+            self.add_type("Color");
+            self.add_plain("(");
+            self.add_color(background);
+            self.add_plain(")),");
+        }
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("border: ");
+        self.add_type("Border");
+        self.add_plain(" {");
+        self.indent_level += 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("color: ");
+        self.add_color(border_color);
+        self.add_plain(",");
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("width: ");
+        self.add_number(&format!("{:.1}", border_width));
+        self.add_plain(",");
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("radius: ");
+        self.add_number(&format!("{:.1}", border_radius));
+        self.add_operator(".");
+        self.add_function("into");
+        self.add_plain("(),");
+        self.indent_level -= 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("},");
+        if let Some((color, (x, y), blur_radius)) = shadow {
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("shadow: ");
+            self.add_type("Shadow");
+            self.add_plain(" {");
+            self.indent_level += 1;
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("color: ");
+            self.add_color(color);
+            self.add_plain(",");
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("offset: ");
+            self.add_type("Vector");
+            self.add_operator("::");
+            self.add_function("new");
+            self.add_plain("(");
+            self.add_number(&format!("{:.1}", x));
+            self.add_plain(", ");
+            self.add_number(&format!("{:.1}", y));
+            self.add_plain("),");
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("blur_radius: ");
+            self.add_number(&format!("{:.1}", blur_radius));
+            self.add_plain(",");
+            self.indent_level -= 1;
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("},");
+        }
+        self.add_newline();
+        self.add_indent();
+        self.add_operator("..");
+        self.add_function("container::Style::default");
+        self.add_plain("()");
+        self.indent_level -= 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("}");
+    }
+
+    /// `.style()` recoloring the default text input's border, and background if one is set,
+    /// while `field` is true
+    fn generate_text_input_condition_style(&mut self, props: &Properties, field: &str) {
+        self.chain_break();
+        self.add_operator(".");
+        self.add_function("style");
+        self.add_plain("(");
+        self.add_keyword("move");
+        self.add_plain(" |theme: &");
+        self.add_type("Theme");
+        self.add_plain(", status| {");
+        self.indent_level += 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_keyword("let");
+        self.add_plain(" style ");
+        self.add_operator("=");
+        self.add_plain(" ");
+        self.add_function("text_input::default");
+        self.add_plain("(theme, status);");
+        self.add_newline();
+        self.add_indent();
+        self.add_keyword("if");
+        self.add_plain(" ");
+        self.add_identifier(field);
+        self.add_plain(" {");
+        self.indent_level += 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_type("text_input::Style");
+        self.add_plain(" {");
+        self.indent_level += 1;
+        if props.condition_background_color.a > 0.0 {
+            self.add_newline();
+            self.add_indent();
+            self.add_plain("background: ");
+            self.add_type("Background");
+            self.add_operator("::");
+            self.add_type("Color");
+            self.add_plain("(");
+            self.add_color(props.condition_background_color);
+            self.add_plain("),");
+        }
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("border: ");
+        self.add_type("Border");
+        self.add_plain(" { color: ");
+        self.add_color(props.condition_border_color);
+        self.add_plain(", ");
+        self.add_operator("..");
+        self.add_plain("style.border },");
+        self.add_newline();
+        self.add_indent();
+        self.add_operator("..");
+        self.add_plain("style");
+        self.indent_level -= 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("}");
+        self.indent_level -= 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("} ");
+        self.add_keyword("else");
+        self.add_plain(" {");
+        self.indent_level += 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("style");
+        self.indent_level -= 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("}");
+        self.indent_level -= 1;
+        self.add_newline();
+        self.add_indent();
+        self.add_plain("})");
+    }
+
+    const LAYOUT_EMITS: &'static [&'static str] = &[
+        "align_items", "clip", "height", "is_wrapping_row", "max_width", "padding", "padding_mode",
+        "spacing", "width", "wrapping_align_x", "wrapping_vertical_spacing",
+    ];
+    fn generate_layout_properties(&mut self, props: &Properties, is_row: bool) {
+        // Spacing
+        if props.spacing != 0.0 {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("spacing");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.spacing));
+            self.add_plain(")");
+        }
+
+        // Alignment
+        if !matches!(props.align_items, Alignment::Start) {
+            self.chain_break();
+            self.add_operator(".");
+            
+            if is_row {
+                // Row aligns children vertically
+                self.add_function("align_y");
+                self.add_plain("(");
+                match props.align_items {
+                    Alignment::Start => self.add_type("Alignment::Start"),
+                    Alignment::Center => self.add_type("Alignment::Center"),
+                    Alignment::End => self.add_type("Alignment::End"),
                 }
-                
-                if props.combobox_use_on_close {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_close");
-                    self.add_plain("(");
-                    if use_self {
-                        self.add_type("Message");
-                        self.add_operator("::");
-                        self.add_plain(&format!("{}OnClose", to_pascal_case(&name)));
-                    } else {
-                        self.add_type("Message");
-                        self.add_operator("::");
-                        self.add_plain("Noop");
-                    }
-                    self.add_plain(")");
+            } else {
+                // Column aligns children horizontally
+                self.add_function("align_x");
+                self.add_plain("(");
+                match props.align_items {
+                    Alignment::Start => self.add_type("Alignment::Start"),
+                    Alignment::Center => self.add_type("Alignment::Center"),
+                    Alignment::End => self.add_type("Alignment::End"),
                 }
-                
-                self.generate_combobox_properties(props);
             }
-            
-            WidgetType::Markdown => {
-/*                self.add_indent();
-                self.add_keyword("let");
-                self.add_plain(" items = ");
-                self.add_function("markdown::parse");
-                self.add_plain("(");
-                self.add_string(&format!("\"{}\"", props.markdown_source.replace("\"", "\\\"")));
-                self.add_plain(");");
-                self.add_newline();
-                self.add_indent();
-                self.add_function("markdown::view");
+            self.add_plain(")");
+        }
+        
+        // Width
+        if !matches!(props.width, Length::Shrink) {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+        }
+
+        // Max_Width for Column only
+        if !is_row {
+            if let Some(max_w) = props.max_width {
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("max_width");
                 self.add_plain("(");
-                self.add_operator("&");
-                self.add_plain("items, ");
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_type("markdown::Settings");
-                self.add_plain(" {");
-                self.add_newline();
-                 self.indent_level += 1;
-                
-                self.add_indent();
-                self.add_plain("link_color: Some(");
-                self.add_color(props.markdown_link_color);
-                self.add_plain("),");
-                self.add_newline();
-                
-                self.add_indent();
-                self.add_plain("link_underline: ");
-                self.add_keyword(if props.markdown_link_underline { "true" } else { "false" });
-                self.add_plain(",");
-                self.add_newline();
-                
-                self.add_indent();
-                self.add_plain("code_color: Some(");
-                self.add_color(props.markdown_code_color);
-                self.add_plain("),");
-                self.add_newline();
-                
-                self.add_indent();
-                self.add_plain("block_spacing: ");
-                self.add_number(&format!("{}", props.markdown_block_spacing));
-                self.add_plain(",");
-                self.add_newline();
-                
+                self.add_number(&format!("{:.1}", max_w));
+                self.add_plain(")");
+            }
+        }
+        
+        // Height
+        if !matches!(props.height, Length::Shrink) {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.height);
+            self.add_plain(")");
+        }
+
+        // Padding
+        if props.padding != Padding::ZERO {
+            self.generate_padding(&props.padding, props.padding_mode);
+        }
+
+        // Clip
+        if props.clip {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("clip");
+            self.add_plain("(");
+            self.add_keyword("true");
+            self.add_plain(")");
+        }
+    }
+
+    const BUTTON_EMITS: &'static [&'static str] = &[
+        "button_on_press_enabled", "button_on_press_maybe_enabled", "button_on_press_with_enabled",
+        "button_style", "clip", "file_dialog", "file_dialog_path_text", "height", "padding", "padding_mode", "text_content", "width",
+    ];
+    fn generate_button_properties(&mut self, widget: &Widget, props: &Properties) {
+        let name = self.get_widget_name(widget.id);
+        let handlers = !self.layout_only;
+
+        if handlers && props.button_on_press_enabled {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("on_press");
+            self.add_plain("(");
+            self.add_type("Message");
+            self.add_operator("::");
+            self.add_variant(WiringPass::Sent, &format!("{}Pressed", to_pascal_case(&name)));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+
+        if handlers && props.button_on_press_with_enabled {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("on_press_with");
+            self.add_plain("(|| ");
+            self.add_type("Message");
+            self.add_operator("::");
+            self.add_variant(WiringPass::Sent, &format!("{}Pressed", to_pascal_case(&name)));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+
+        if handlers && props.button_on_press_maybe_enabled {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("on_press_maybe");
+            self.add_plain("(Some(");
+            self.add_type("Message");
+            self.add_operator("::");
+            self.add_variant(WiringPass::Sent, &format!("{}Pressed", to_pascal_case(&name)));
+            self.add_plain("))");
+            self.indent_level -= 1;
+        }
+
+
+        // Clip
+        if props.clip {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("clip");
+            self.add_plain("(");
+            self.add_keyword("true");
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+
+        // Style - only add if not Primary (default)
+        match props.button_style {
+            ButtonStyleType::Secondary => {
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("style");
+                self.add_plain("(button::secondary)");
                 self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("}");
-                self.add_newline();
+            }
+            ButtonStyleType::Success => {
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("style");
+                self.add_plain("(button::success)");
                 self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain(")");
-                self.generate_markdown_properties(props);
-*/
             }
-            
-            WidgetType::MouseArea => {
-                self.add_indent();
-                self.add_function("mouse_area");
-                self.add_plain("(");
-                self.add_newline();
-                
-                // Generate child
-                if !widget.children.is_empty() {
-                    self.generate_widget_creation(&widget.children[0], use_self);
-                }
-                
-                self.add_plain(")");
+            ButtonStyleType::Danger => {
                 self.indent_level += 1;
-                
-                let name = self.get_widget_name(widget.id);
-                
-                // Conditionally add event handlers
-                if props.mousearea_on_press {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_press");
-                    self.add_plain("(Message::");
-                    self.add_plain(&format!("{}Pressed", to_pascal_case(&name)));
-                    self.add_plain(")");
-                }
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("style");
+                self.add_plain("(button::danger)");
+                self.indent_level -= 1;
+            }
+            ButtonStyleType::Text => {
+                self.indent_level += 1;
+                self.chain_break();
+                self.add_operator(".");
+                self.add_function("style");
+                self.add_plain("(button::text)");
+                self.indent_level -= 1;
+            }
+            ButtonStyleType::Primary => {} // Default, don't add
+        }
+        
+        if !matches!(props.width, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
 
-                if props.mousearea_on_release {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_press");
-                    self.add_plain("(Message::");
-                    self.add_plain(&format!("{}Released", to_pascal_case(&name)));
-                    self.add_plain(")");
-                }
+        if !matches!(props.height, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.height);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
 
-                if props.mousearea_on_double_click {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_double_click");
-                    self.add_plain("(Message::");
-                    self.add_plain(&format!("{}DoubleClicked", to_pascal_case(&name)));
-                    self.add_plain(")");
-                }
+        // Padding
+        if props.padding != (Padding { top: 5.0, bottom: 5.0, right: 10.0, left: 10.0 }) {
+            self.generate_padding(&props.padding, props.padding_mode);
+        }
+    }
 
-                if props.mousearea_on_right_press {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_right_press");
-                    self.add_plain("(Message::");
-                    self.add_plain(&format!("{}RightPressed", to_pascal_case(&name)));
-                    self.add_plain(")");
-                }
+    /// Opens `row![text(label), space::horizontal(), ` for a control whose label goes on the left.
+    /// The caller emits the control, then closes the row with `close_left_label_row`.
+    fn open_left_label_row(&mut self, label: &str) {
+        self.add_indent();
+        self.add_macro("row!");
+        self.add_plain("[");
+        self.add_newline();
+        self.indent_level += 1;
 
-                if props.mousearea_on_right_release {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_right_release");
-                    self.add_plain("(Message::");
-                    self.add_plain(&format!("{}RightReleased", to_pascal_case(&name)));
-                    self.add_plain(")");
-                }
+        self.add_indent();
+        self.add_function("text");
+        self.add_plain("(");
+        self.add_string(&format!("\"{}\"", label));
+        self.add_plain("),");
+        self.add_newline();
 
-                if props.mousearea_on_middle_press {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_middle_press");
-                    self.add_plain("(Message::");
-                    self.add_plain(&format!("{}MiddlePressed", to_pascal_case(&name)));
-                    self.add_plain(")");
-                }
+        self.add_indent();
+        self.add_function("space::horizontal");
+        self.add_plain("(),");
+        self.add_newline();
+    }
 
-                if props.mousearea_on_middle_release {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_middle_release");
-                    self.add_plain("(Message::");
-                    self.add_plain(&format!("{}MiddleReleased", to_pascal_case(&name)));
-                    self.add_plain(")");
-                }
-                
-                if props.mousearea_on_scroll {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_scroll");
-                    self.add_plain("(|delta| Message::");
-                    self.add_plain(&format!("{}Scrolled", to_pascal_case(&name)));
-                    self.add_plain("(delta))");
-                }
+    fn close_left_label_row(&mut self, spacing: f32, width: Length) {
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("]");
 
-                if props.mousearea_on_enter {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_enter");
-                    self.add_plain("(|point| Message::");
-                    self.add_plain(&format!("{}Entered", to_pascal_case(&name)));
-                    self.add_plain("(point))");
-                }
-                
-                if props.mousearea_on_move {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_move");
-                    self.add_plain("(|point| Message::");
-                    self.add_plain(&format!("{}Moved", to_pascal_case(&name)));
-                    self.add_plain("(point))");
-                }
+        self.chain_break();
+        self.add_operator(".");
+        self.add_function("spacing");
+        self.add_plain("(");
+        self.add_number(&format!("{}", spacing));
+        self.add_plain(")");
 
-                if props.mousearea_on_exit {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("on_exit");
-                    self.add_plain("(|point| Message::");
-                    self.add_plain(&format!("{}Exited", to_pascal_case(&name)));
-                    self.add_plain("(point))");
-                }
-                
-                if let Some(interaction) = props.mousearea_interaction {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("interaction");
-                    self.add_plain("(Interaction::");
-                    self.add_plain(&format!("{:?}", interaction));
-                    self.add_plain(")");
-                }
-                
-                // Add layout properties
-                //self.generate_layout_properties(props, false);
-                self.indent_level -= 1;
-            }
-            
-            WidgetType::QRCode => {
-                self.add_indent();
-                self.add_type("qr_code::QRCode");
-                self.add_operator("::");
-                self.add_function("new");
-                self.add_plain("(");
-                self.add_string(&format!("\"{}\"", props.qrcode_data));
+        self.chain_break();
+        self.add_operator(".");
+        self.add_function("align_y");
+        self.add_plain("(");
+        self.add_type("Alignment::Center");
+        self.add_plain(")");
+
+        if !matches!(width, Length::Shrink) {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(width);
+            self.add_plain(")");
+        }
+    }
+
+    const TEXT_EMITS: &'static [&'static str] = &["height", "line_height", "text_content", "text_size", "width"];
+    fn generate_text_properties(&mut self, props: &Properties) {
+        if props.text_size != 16.0 {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("size");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.text_size));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+
+        if props.line_height != text::LineHeight::default() {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("line_height");
+            self.add_plain("(");
+            self.add_line_height(props.line_height);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if !matches!(props.width, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+
+        if !matches!(props.height, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.height);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+    }
+
+    /// `LineHeight::Relative(1.5)` or `LineHeight::Absolute(Pixels(24.0))`, spelled out so a
+    /// pixel height can't be taken for a factor. `LineHeight` and `Pixels` come from the imports.
+    fn add_line_height(&mut self, line_height: text::LineHeight) {
+        self.add_type("LineHeight");
+        self.add_operator("::");
+        match line_height {
+            text::LineHeight::Relative(factor) => {
+                self.add_plain("Relative(");
+                self.add_number(&format!("{:?}", factor));
                 self.add_plain(")");
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("cell_size");
+            }
+            text::LineHeight::Absolute(pixels) => {
+                self.add_plain("Absolute(");
+                self.add_type("Pixels");
                 self.add_plain("(");
-                self.add_number(&format!("{}", props.qrcode_cell_size));
-                self.add_plain(")");
-                self.generate_qrcode_properties(props);
-                self.indent_level -= 1;
+                self.add_number(&format!("{:?}", pixels.0));
+                self.add_plain("))");
             }
-            
-            WidgetType::Stack => {
-                self.add_indent();
-                self.add_function("stack");
-                self.add_plain("(vec![");
-                self.add_newline();
-                self.indent_level += 1;
-                
-                if widget.children.is_empty() {
-                    self.add_indent();
-                    self.add_function("text");
-                    self.add_plain("(");
-                    self.add_string("\"Layer 1\"");
-                    self.add_plain(").into(),");
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_function("text");
-                    self.add_plain("(");
-                    self.add_string("\"Layer 2\"");
-                    self.add_plain(").into(),");
-                } else {
-                    for (i, child) in widget.children.iter().enumerate() {
-                        self.generate_widget_creation(child, use_self);
-                        self.add_operator(".");
-                        self.add_function("into");
-                        self.add_plain("()");
-                        if i < widget.children.len() - 1 {
-                            self.add_plain(",");
-                        }
-                        self.add_newline();
-                    }
+        }
+    }
+
+    const TEXT_INPUT_EMITS: &'static [&'static str] = &[
+        "condition_background_color", "condition_border_color", "is_secure", "style_condition", "text_input_alignment",
+        "text_input_font", "text_input_line_height", "text_input_on_paste", "text_input_on_submit",
+        "text_input_padding", "text_input_placeholder", "text_input_size", "width",
+    ];
+    fn generate_text_input_properties(&mut self, props: &Properties) {
+        if props.is_secure {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("secure");
+            self.add_plain("(");
+            self.add_keyword("true");
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if props.text_input_size != 16.0 {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("size");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.text_input_size));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if !matches!(props.width, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+    }
+    
+    const CHECKBOX_EMITS: &'static [&'static str] = &["checkbox_checked", "checkbox_label", "checkbox_size", "checkbox_spacing", "label_position", "width"];
+    fn generate_checkbox_properties(&mut self, props: &Properties) {
+        if props.checkbox_size != 16.0 {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("size");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.checkbox_size));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if props.checkbox_spacing != 8.0 {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("spacing");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.checkbox_spacing));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if !matches!(props.width, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+    }
+    
+    const SLIDER_EMITS: &'static [&'static str] = &["slider_height", "slider_max", "slider_min", "slider_step", "slider_value"];
+    fn generate_slider_properties(&mut self, props: &Properties) {
+        if props.slider_step != 1.0 {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("step");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.slider_step));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        // For horizontal slider, only height can be set
+        if !matches!(props.slider_height, iced::widget::slider::Slider::<f32, Theme>::DEFAULT_HEIGHT) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.slider_height.into());
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+    }
+
+    const VERTICAL_SLIDER_EMITS: &'static [&'static str] = &["slider_max", "slider_min", "slider_step", "slider_value", "slider_width"];
+    fn generate_vertical_slider_properties(&mut self, props: &Properties) {
+        if props.slider_step != 1.0 {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("step");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.slider_step));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        // For vertical slider, width can be set as a Length
+        if !matches!(props.slider_width, iced::widget::vertical_slider::VerticalSlider::<f32, Theme>::DEFAULT_WIDTH) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.slider_width.into());
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+    }
+    
+    const PROGRESS_EMITS: &'static [&'static str] = &["progress_girth", "progress_length", "progress_max", "progress_min", "progress_value", "progress_vertical"];
+    fn generate_progress_properties(&mut self, props: &Properties) {
+        if !matches!(props.progress_length, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("length");
+            self.add_plain("(");
+            self.add_length(props.progress_length);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if props.progress_girth != iced::widget::progress_bar::ProgressBar::<Theme>::DEFAULT_GIRTH {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("girth");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.progress_girth));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if props.progress_vertical {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("vertical");
+            self.add_plain("()");
+            self.indent_level -= 1;
+        }
+    }
+    
+    const TOGGLER_EMITS: &'static [&'static str] = &["label_position", "toggler_active", "toggler_label", "toggler_size", "toggler_spacing", "width"];
+    fn generate_toggler_properties(&mut self, props: &Properties) {
+        if props.toggler_size != iced::widget::toggler::Toggler::<Theme>::DEFAULT_SIZE {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("size");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.toggler_size));
+            self.add_plain(")");
+        }
+        
+        if props.toggler_spacing != iced::widget::toggler::Toggler::<Theme>::DEFAULT_SIZE / 2.0 {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("spacing");
+            self.add_plain("(");
+            self.add_number(&format!("{}", props.toggler_spacing));
+            self.add_plain(")");
+        }
+        
+        if !props.toggler_label.is_empty() {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("label");
+            self.add_plain("(");
+            self.add_string(&format!("\"{}\"", props.toggler_label));
+            self.add_plain(")");
+        }
+
+        if !matches!(props.width, Length::Shrink) {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+        }
+    }
+    
+    const PICKLIST_EMITS: &'static [&'static str] = &["padding", "padding_mode", "picklist_options", "picklist_placeholder", "picklist_selected", "referenced_enum", "width"];
+    fn generate_picklist_properties(&mut self, props: &Properties) {
+        if !props.picklist_placeholder.is_empty() && props.picklist_placeholder != "Choose an option..." {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("placeholder");
+            self.add_plain("(");
+            self.add_string(&format!("\"{}\"", props.picklist_placeholder));
+            self.add_plain(")");
+        }
+        
+        if !matches!(props.width, Length::Shrink) {
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+        }
+
+        if props.padding != (Padding { top: 5.0, bottom: 5.0, right: 10.0, left: 10.0 }) {
+            self.generate_padding(&props.padding, props.padding_mode);
+        }
+    }
+    
+    const SCROLLABLE_EMITS: &'static [&'static str] = &["height", "scroll_dir", "width"];
+    fn generate_scrollable_properties(&mut self, props: &Properties) {
+        // Direction
+        let is_default_dir = matches!(
+            props.scroll_dir,
+            iced::widget::scrollable::Direction::Vertical(_)
+        );
+        
+        if !is_default_dir {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("direction");
+            self.add_plain("(");
+            match props.scroll_dir {
+                iced::widget::scrollable::Direction::Horizontal(_) => {
+                    self.add_plain("scrollable::Direction::Horizontal(scrollable::Scrollbar::default())");
                 }
-                
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain("])");
-                self.generate_stack_properties(props);
+                iced::widget::scrollable::Direction::Both { .. } => {
+                    self.add_plain("scrollable::Direction::Both { vertical: scrollable::Scrollbar::default(), horizontal: scrollable::Scrollbar::default() }");
+                }
+                _ => {}
             }
-            
-            WidgetType::Themer => {
-                self.add_indent();
-                self.add_function("themer");
-                self.add_plain("(");
-                if let Some(theme) = &props.themer_theme {
-                    self.add_plain("Some(");
-                    self.add_type("Theme");
-                    self.add_operator("::");
-                    match theme {
-                        Theme::Light => self.add_plain("Light"),
-                        Theme::Dark => self.add_plain("Dark"),
-                        Theme::Dracula => self.add_plain("Dracula"),
-                        Theme::Nord => self.add_plain("Nord"),
-                        _ => self.add_plain("Dark"),
-                    }
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        // Width
+        if !matches!(props.width, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        // Height
+        if !matches!(props.height, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.height);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+    }
+    
+    const SPACE_EMITS: &'static [&'static str] = &["height", "orientation", "width"];
+    fn generate_space_properties(&mut self, props: &Properties) {
+        match props.orientation {
+            Orientation::Horizontal => {
+                if !matches!(props.width, Length::Fill) {
+                    self.indent_level += 1;
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("width");
+                    self.add_plain("(");
+                    self.add_length(props.width);
                     self.add_plain(")");
-                } else {
-                    self.add_plain("None");
+                    self.indent_level -= 1;
                 }
-                self.add_plain(", ");
-                self.add_newline();
-                self.indent_level += 1;
                 
-                if widget.children.is_empty() {
-                    self.add_indent();
-                    self.add_function("container");
+                if !matches!(props.height, Length::Shrink) {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("height");
                     self.add_plain("(");
-                    self.add_function("text");
+                    self.add_length(props.height);
+                    self.add_plain(")");
+                }
+            }
+            Orientation::Vertical => {
+                if !matches!(props.width, Length::Shrink) {
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("width");
                     self.add_plain("(");
-                    self.add_string("\"Themed content\"");
-                    self.add_plain("))");
-                } else {
-                    for child in &widget.children {
-                        self.generate_widget_creation(child, use_self);
-                    }
+                    self.add_length(props.width);
+                    self.add_plain(")");
                 }
                 
-                self.add_newline();
-                self.indent_level -= 1;
-                self.add_indent();
-                self.add_plain(")");
-                self.generate_themer_properties(props);
-            } 
-            WidgetType::Pin => {
-            //    todo!("implement code gen for pin");
+                if !matches!(props.height, Length::Fill) {
+                    self.indent_level += 1;
+                    self.chain_break();
+                    self.add_operator(".");
+                    self.add_function("height");
+                    self.add_plain("(");
+                    self.add_length(props.height);
+                    self.add_plain(")");
+                    self.indent_level -= 1;
+                }
             }
         }
-    }
-
-    fn collect_widget_names(&mut self, widget: &Widget) {
-        // Generate unique name for this widget
-        let mut derp = String::new();
-        let base_name = if !widget.properties.widget_name.trim().is_empty() {
-            self.sanitize_name(&widget.properties.widget_name)
-        } else {
-            match widget.widget_type {
-                WidgetType::Button => "button",
-                WidgetType::Text => "text",
-                WidgetType::TextInput => "text_input",
-                WidgetType::Checkbox => "checkbox",
-                WidgetType::Radio => "radio",
-                WidgetType::Slider => "slider",
-                WidgetType::VerticalSlider => "vertical_slider",
-                WidgetType::ProgressBar => "progress_bar",
-                WidgetType::Toggler => "toggler",
-                WidgetType::PickList => "pick_list",
-                _ => {
-                    derp = format!("{:?}", widget.widget_type).to_lowercase();
-
-                    &derp
-                }
-            }.to_string()
-        };
-
-        // Get count for this widget type
-        let type_key = format!("{:?}", widget.widget_type).to_lowercase();
-        let count = self.widget_counts.entry(type_key).or_insert(0);
-        *count += 1;
-
-        let final_name = if *count > 1 {
-            format!("{}_{}", base_name, count)
-        } else {
-            base_name
-        };
 
-        self.widget_names.insert(widget.id, final_name);
-
-        // Process children
-        for child in &widget.children {
-            self.collect_widget_names(child);
-        }
     }
+    
+    fn generate_rule_properties(&mut self, props: &Properties) {
+        if !matches!(props.width, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
 
-    fn get_widget_name(&self, widget_id: WidgetId) -> String {
-        self.widget_names.get(&widget_id)
-            .cloned()
-            .unwrap_or_else(|| "widget".to_string())
+        if !matches!(props.height, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.height);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
     }
 
-    fn generate_radio_widget(&mut self, widget: &Widget, use_self: bool, use_column: bool) {
+    /// A rule that fills along its axis inside a parent that shrinks on that axis ends up zero-length
+    fn rule_length_note(&self, widget: &Widget) -> Option<&'static str> {
+        let parent = self.hierarchy.get_widget_by_id(self.hierarchy.find_parent_id(widget.id)?)?;
         let props = &widget.properties;
-        let name = self.get_unique_widget_name(widget);
+
+        match props.orientation {
+            Orientation::Horizontal if matches!(props.width, Length::Fill) && matches!(parent.properties.width, Length::Shrink) => {
+                Some("// Parent shrinks to fit, so this rule has no width: give it or its parent a width")
+            }
+            Orientation::Vertical if matches!(props.height, Length::Fill) && matches!(parent.properties.height, Length::Shrink) => {
+                Some("// Parent shrinks to fit, so this rule has no height: give it or its parent a height")
+            }
+            _ => None,
+        }
+    }
+    
+    const IMAGE_EMITS: &'static [&'static str] = &["height", "image_fit", "image_path", "width"];
+    fn generate_image_properties(&mut self, props: &Properties) {
+        // Content fit
+        if !matches!(props.image_fit, ContentFitChoice::Contain) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("content_fit");
+            self.add_plain("(ContentFit::");
+            match props.image_fit {
+                ContentFitChoice::Cover => self.add_plain("Cover"),
+                ContentFitChoice::Fill => self.add_plain("Fill"),
+                ContentFitChoice::ScaleDown => self.add_plain("ScaleDown"),
+                ContentFitChoice::None => self.add_plain("None"),
+                _ => self.add_plain("Contain"),
+            }
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
         
-        self.add_indent();
-        if use_column {
-            self.add_macro("column!");
-        } else {
-            self.add_macro("row!");
+        // Width
+        if !matches!(props.width, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
         }
-        self.add_plain("[");
-        self.add_newline();
-        self.indent_level += 1;
         
-        for (i, option) in props.radio_options.iter().enumerate() {
-            self.add_indent();
-            self.add_function("radio");
+        // Height
+        if !matches!(props.height, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
             self.add_plain("(");
-            self.add_newline();
+            self.add_length(props.height);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+    }
+    
+    const SVG_EMITS: &'static [&'static str] = &["height", "svg_fit", "svg_path", "svg_tint", "svg_tint_color", "width"];
+    fn generate_svg_properties(&mut self, props: &Properties) {
+        // Tint
+        if props.svg_tint != SvgTint::None {
             self.indent_level += 1;
-            
-            // Each parameter on its own line for readability
-            self.add_indent();
-            self.add_string(&format!("\"{}\"", option));
-            self.add_plain(",");
-            self.add_newline();
-            
-            self.add_indent();
-            self.add_number(&format!("{}", i));
-            self.add_plain(",");
-            self.add_newline();
-            
-            self.add_indent();
-            if use_self {
-                self.add_plain("Some(");
-                self.add_keyword("self");
-                self.add_operator(".");
-                self.add_identifier(&format!("{}_selected", to_snake_case(&name)));
-                self.add_plain(")");
-            } else {
-                self.add_plain("Some(");
-                self.add_number(&format!("{}", props.radio_selected_index));
-                self.add_plain(")");
-            }
-            self.add_plain(",");
-            self.add_newline();
-            
-            self.add_indent();
-            if use_self {
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain(&format!("{}Selected", to_pascal_case(&name)));
-            } else {
-                self.add_operator("|");
-                self.add_identifier("_");
-                self.add_operator("|");
-                self.add_plain(" ");
-                self.add_type("Message");
-                self.add_operator("::");
-                self.add_plain("Noop");
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("style");
+            match props.svg_tint {
+                SvgTint::ThemeText => self.add_plain("(|theme: &"),
+                _ => self.add_plain("(|_theme: &"),
             }
+            self.add_type("Theme");
+            self.add_plain(", _status| ");
+            self.add_type("svg::Style");
+            self.add_plain(" {");
+            self.indent_level += 1;
             self.add_newline();
-            
-            self.indent_level -= 1;
             self.add_indent();
-            self.add_plain(")");
-            
-            if props.radio_size != 20.0 {
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("size");
-                self.add_plain("(");
-                self.add_number(&format!("{}", props.radio_size));
-                self.add_plain(")");
-                self.indent_level -= 1;
-            }
-            
-            if i < props.radio_options.len() - 1 {
-                self.add_plain(",");
+            self.add_plain("color: ");
+            self.add_type("Some");
+            self.add_plain("(");
+            match props.svg_tint {
+                SvgTint::Custom => self.add_color(props.svg_tint_color),
+                _ => {
+                    self.add_plain("theme");
+                    self.add_operator(".");
+                    self.add_function("palette");
+                    self.add_plain("()");
+                    self.add_operator(".");
+                    self.add_plain("text");
+                }
             }
+            self.add_plain("),");
+            self.indent_level -= 1;
             self.add_newline();
+            self.add_indent();
+            self.add_plain("})");
+            self.indent_level -= 1;
         }
-        
-        self.indent_level -= 1;
-        self.add_indent();
-        self.add_plain("]");
-    }
 
-    fn generate_container_properties(&mut self, props: &Properties) {
-        // Widget ID
-        if let Some(ref id) = props.widget_id {
-            if !id.is_empty() {
-                self.add_newline();
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("id");
-                self.add_plain("(");
-                self.add_string(&format!("\"{}\"", id));
-                self.add_plain(")");
+        // Content fit
+        if !matches!(props.svg_fit, ContentFitChoice::Contain) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("content_fit");
+            self.add_plain("(ContentFit::");
+            match props.svg_fit {
+                ContentFitChoice::Cover => self.add_plain("Cover"),
+                ContentFitChoice::Fill => self.add_plain("Fill"),
+                ContentFitChoice::ScaleDown => self.add_plain("ScaleDown"),
+                ContentFitChoice::None => self.add_plain("None"),
+                _ => self.add_plain("Contain"),
             }
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        // Width
+        if !matches!(props.width, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        // Height
+        if !matches!(props.height, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.height);
+            self.add_plain(")");
+            self.indent_level -= 1;
         }
+    }
 
-        // Sizing
-        match props.container_sizing_mode {
-            ContainerSizingMode::Manual => {
-                // Width
-                if !matches!(props.width, Length::Fill) {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("width");
-                    self.add_plain("(");
-                    self.add_length(props.width);
-                    self.add_plain(")");
-                }
-                
-                // Height
-                if !matches!(props.height, Length::Fill) {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("height");
-                    self.add_plain("(");
-                    self.add_length(props.height);
-                    self.add_plain(")");
-                }
-                
-                // Alignment
-                match props.align_x {
-                    ContainerAlignX::Left => {},
-                    ContainerAlignX::Center => {
+    const COMBOBOX_EMITS: &'static [&'static str] = &[
+        "combobox_input_background", "combobox_input_text_color", "combobox_menu_background", "combobox_menu_height",
+        "combobox_options", "combobox_placeholder", "combobox_selected", "combobox_size", "combobox_use_on_close",
+        "combobox_use_on_input", "combobox_use_on_open", "combobox_use_on_option_hovered", "referenced_enum", "width",
+    ];
+    fn generate_combobox_properties(&mut self, props: &Properties) {
+        for call in combobox_style_calls(props) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            match call {
+                ComboBoxStyleCall::InputStyle { background, value } => {
+                    self.add_function("input_style");
+                    self.add_plain("(|theme: &");
+                    self.add_type("Theme");
+                    self.add_plain(", status| ");
+                    self.add_type("text_input::Style");
+                    self.add_plain(" {");
+                    self.indent_level += 1;
+                    if let Some(bg) = background {
                         self.add_newline();
                         self.add_indent();
-                        self.add_operator(".");
-                        self.add_function("align_x");
+                        self.add_plain("background: ");
+                        self.add_type("Background");
+                        self.add_operator("::");
+                        self.add_type("Color");
                         self.add_plain("(");
-                        self.add_type("Alignment::Center");
-                        self.add_plain(")");
+                        self.add_color(bg);
+                        self.add_plain("),");
                     }
-                    ContainerAlignX::Right => {
+                    if let Some(c) = value {
                         self.add_newline();
                         self.add_indent();
-                        self.add_operator(".");
-                        self.add_function("align_x");
-                        self.add_plain("(");
-                        self.add_type("Alignment::End");
-                        self.add_plain(")");
+                        self.add_plain("value: ");
+                        self.add_color(c);
+                        self.add_plain(",");
                     }
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_operator("..");
+                    self.add_function("text_input::default");
+                    self.add_plain("(theme, status)");
+                    self.indent_level -= 1;
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_plain("})");
                 }
-                match props.align_y {
-                    ContainerAlignY::Top => {},
-                    ContainerAlignY::Center => {
-                        self.add_newline();
-                        self.add_indent();
-                        self.add_operator(".");
-                        self.add_function("align_y");
-                        self.add_plain("(");
-                        self.add_type("Alignment::Center");
-                        self.add_plain(")");
-                    }
-                    ContainerAlignY::Bottom => {
-                        self.add_newline();
-                        self.add_indent();
-                        self.add_operator(".");
-                        self.add_function("align_y");
-                        self.add_plain("(");
-                        self.add_type("Alignment::End");
-                        self.add_plain(")");
-                    }
+                ComboBoxStyleCall::MenuStyle { background } => {
+                    self.add_function("menu_style");
+                    self.add_plain("(|theme: &");
+                    self.add_type("Theme");
+                    self.add_plain("| ");
+                    self.add_type("menu::Style");
+                    self.add_plain(" {");
+                    self.indent_level += 1;
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_plain("background: ");
+                    self.add_type("Background");
+                    self.add_operator("::");
+                    self.add_type("Color");
+                    self.add_plain("(");
+                    self.add_color(background);
+                    self.add_plain("),");
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_operator("..");
+                    self.add_function("menu::default");
+                    self.add_plain("(theme)");
+                    self.indent_level -= 1;
+                    self.add_newline();
+                    self.add_indent();
+                    self.add_plain("})");
+                }
+                ComboBoxStyleCall::MenuHeight(height) => {
+                    self.add_function("menu_height");
+                    self.add_plain("(");
+                    self.add_number(&format!("{}", height));
+                    self.add_plain(")");
                 }
             }
-            ContainerSizingMode::CenterX => {
-                self.add_newline();
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("center_x");
-                self.add_plain("(");
-                self.add_length(props.container_center_length);
-                self.add_plain(")");
-            }
-            ContainerSizingMode::CenterY => {
-                self.add_newline();
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("center_y");
-                self.add_plain("(");
-                self.add_length(props.container_center_length);
-                self.add_plain(")");
-            }
-            ContainerSizingMode::Center => {
-                self.add_newline();
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("center");
-                self.add_plain("(");
-                self.add_length(props.container_center_length);
-                self.add_plain(")");
-            }
+            self.indent_level -= 1;
         }
 
-        // Max width
-        if let Some(max_w) = props.max_width {
-            self.add_newline();
-            self.add_indent();
+        if props.combobox_size != 16.0 {
+            self.indent_level += 1;
+            self.chain_break();
             self.add_operator(".");
-            self.add_function("max_width");
+            self.add_function("size");
             self.add_plain("(");
-            self.add_number(&format!("{:.1}", max_w));
+            self.add_number(&format!("{}", props.combobox_size));
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if !matches!(props.width, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
             self.add_plain(")");
+            self.indent_level -= 1;
         }
+    }
 
-        // Max Height
-        if let Some(max_h) = props.max_height {
-            self.add_newline();
-            self.add_indent();
+    const MARKDOWN_EMITS: &'static [&'static str] = &["height", "markdown_source", "markdown_text_size", "width"];
+    fn generate_markdown_properties(&mut self, props: &Properties) {
+        if !matches!(props.width, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
             self.add_operator(".");
-            self.add_function("max_height");
+            self.add_function("width");
             self.add_plain("(");
-            self.add_number(&format!("{:.1}", max_h));
+            self.add_length(props.width);
             self.add_plain(")");
+            self.indent_level -= 1;
         }
         
-        // Padding
-        if props.padding != Padding::ZERO {
-            self.generate_padding(&props.padding, props.padding_mode);
+        if !matches!(props.height, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.height);
+            self.add_plain(")");
+            self.indent_level -= 1;
         }
+    }
 
-        // Clip
-        if props.clip {
-            self.add_newline();
-            self.add_indent();
+    const QRCODE_EMITS: &'static [&'static str] = &["height", "qrcode_cell_size", "qrcode_data", "width"];
+    fn generate_qrcode_properties(&mut self, props: &Properties) {
+        if !matches!(props.width, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
             self.add_operator(".");
-            self.add_function("clip");
+            self.add_function("width");
             self.add_plain("(");
-            self.add_keyword("true");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
+        }
+        
+        if !matches!(props.height, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("height");
+            self.add_plain("(");
+            self.add_length(props.height);
             self.add_plain(")");
+            self.indent_level -= 1;
         }
     }
 
-    fn generate_layout_properties(&mut self, props: &Properties, is_row: bool) {
-        // Spacing
-        if props.spacing != 0.0 {
+    /// Carried by the wrapper around a floating layer, whatever the layer's own type
+    const STACK_LAYER_EMITS: &'static [&'static str] = &["float_anchor", "float_offset_x", "float_offset_y"];
+    /// A full-size container aligning `layer` to `anchor`, so it floats over the layers before it
+    fn generate_float_layer(&mut self, layer: &Widget, anchor: FloatAnchor, use_self: bool) {
+        let props = &layer.properties;
+        self.add_indent();
+        self.add_function("container");
+        self.add_plain("(");
+        self.indent_level += 1;
+
+        let layer_tokens = self.emit_detached(|generator| generator.generate_widget_creation(layer, use_self));
+
+        self.indent_level -= 1;
+        if !self.push_inline(&layer_tokens) {
+            self.add_newline();
+            self.tokens.extend(layer_tokens);
             self.add_newline();
             self.add_indent();
+        }
+        self.add_plain(")");
+
+        for function in ["width", "height"] {
+            self.chain_break();
             self.add_operator(".");
-            self.add_function("spacing");
+            self.add_function(function);
             self.add_plain("(");
-            self.add_number(&format!("{}", props.spacing));
+            self.add_length(Length::Fill);
             self.add_plain(")");
         }
 
-        // Alignment
-        if !matches!(props.align_items, Alignment::Start) {
-            self.add_newline();
-            self.add_indent();
+        let (align_x, align_y) = anchor.alignment();
+        for (function, alignment) in [("align_x", align_x), ("align_y", align_y)] {
+            let variant = match alignment {
+                Alignment::Start => continue,
+                Alignment::Center => "Alignment::Center",
+                Alignment::End => "Alignment::End",
+            };
+            self.chain_break();
             self.add_operator(".");
-            
-            if is_row {
-                // Row aligns children vertically
-                self.add_function("align_y");
-                self.add_plain("(");
-                match props.align_items {
-                    Alignment::Start => self.add_type("Alignment::Start"),
-                    Alignment::Center => self.add_type("Alignment::Center"),
-                    Alignment::End => self.add_type("Alignment::End"),
-                }
-            } else {
-                // Column aligns children horizontally
-                self.add_function("align_x");
-                self.add_plain("(");
-                match props.align_items {
-                    Alignment::Start => self.add_type("Alignment::Start"),
-                    Alignment::Center => self.add_type("Alignment::Center"),
-                    Alignment::End => self.add_type("Alignment::End"),
-                }
-            }
+            self.add_function(function);
+            self.add_plain("(");
+            self.add_type(variant);
             self.add_plain(")");
         }
-        
-        // Width
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.add_indent();
+
+        let padding = anchor.padding(props.float_offset_x, props.float_offset_y);
+        if padding != Padding::ZERO {
+            self.generate_padding(&padding, PaddingMode::Individual);
+        }
+    }
+
+    const STACK_EMITS: &'static [&'static str] = &["clip", "height", "width"];
+    fn generate_stack_properties(&mut self, props: &Properties) {
+        if !matches!(props.width, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
             self.add_operator(".");
             self.add_function("width");
             self.add_plain("(");
             self.add_length(props.width);
             self.add_plain(")");
-        }
-
-        // Max_Width for Column only
-        if !is_row {
-            if let Some(max_w) = props.max_width {
-                self.add_newline();
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("max_width");
-                self.add_plain("(");
-                self.add_number(&format!("{:.1}", max_w));
-                self.add_plain(")");
-            }
+            self.indent_level -= 1;
         }
         
-        // Height
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.add_indent();
+        if !matches!(props.height, Length::Fill) {
+            self.indent_level += 1;
+            self.chain_break();
             self.add_operator(".");
             self.add_function("height");
             self.add_plain("(");
             self.add_length(props.height);
             self.add_plain(")");
+            self.indent_level -= 1;
         }
 
-        // Padding
-        if props.padding != Padding::ZERO {
-            self.generate_padding(&props.padding, props.padding_mode);
-        }
-
-        // Clip
         if props.clip {
-            self.add_newline();
-            self.add_indent();
+            self.indent_level += 1;
+            self.chain_break();
             self.add_operator(".");
             self.add_function("clip");
             self.add_plain("(");
             self.add_keyword("true");
             self.add_plain(")");
+            self.indent_level -= 1;
         }
     }
 
-    fn generate_button_properties(&mut self, widget: &Widget, props: &Properties) {
-        let mut name = props.widget_name.clone();
-        if name.len() == 0 {
-            name = self.get_widget_name(widget.id);
+    const THEMER_EMITS: &'static [&'static str] = &["height", "themer_theme", "width"];
+    fn generate_themer_properties(&mut self, props: &Properties) {
+        if !matches!(props.width, Length::Shrink) {
+            self.indent_level += 1;
+            self.chain_break();
+            self.add_operator(".");
+            self.add_function("width");
+            self.add_plain("(");
+            self.add_length(props.width);
+            self.add_plain(")");
+            self.indent_level -= 1;
         }
-
-        if props.button_on_press_enabled {
-            self.add_newline();
+        
+        if !matches!(props.height, Length::Shrink) {
             self.indent_level += 1;
-            self.add_indent();
+            self.chain_break();
             self.add_operator(".");
-            self.add_function("on_press");
+            self.add_function("height");
             self.add_plain("(");
-            self.add_type("Message");
-            self.add_operator("::");
-            self.add_plain(&format!("{}Pressed", to_pascal_case(&name)));
+            self.add_length(props.height);
             self.add_plain(")");
             self.indent_level -= 1;
         }
+    }
+
+    fn generate_padding(&mut self, padding: &Padding, padding_mode: PaddingMode) {
+        self.chain_break();
+        self.add_operator(".");
+        self.add_function("padding");
+        self.add_plain("(");
+        
+        match padding_mode {
+            PaddingMode::Uniform => {
+                // .padding(10.0)
+                self.add_number(&format!("{:.1}", padding.top));
+            }
+            PaddingMode::Symmetric => {
+                // .padding([vertical, horizontal])
+                self.add_plain("[");
+                self.add_number(&format!("{:.1}", padding.top));
+                self.add_plain(", ");
+                self.add_number(&format!("{:.1}", padding.left));
+                self.add_plain("]");
+            }
+            PaddingMode::Individual => {
+                // .padding(Padding { top, right, bottom, left })
+                self.add_type("Padding");
+                self.add_plain(" { ");
+                self.add_plain("top: ");
+                self.add_number(&format!("{:.1}", padding.top));
+                self.add_plain(", ");
+                self.add_plain("right: ");
+                self.add_number(&format!("{:.1}", padding.right));
+                self.add_plain(", ");
+                self.add_plain("bottom: ");
+                self.add_number(&format!("{:.1}", padding.bottom));
+                self.add_plain(", ");
+                self.add_plain("left: ");
+                self.add_number(&format!("{:.1}", padding.left));
+                self.add_plain(" }");
+            }
+        }
+        
+        self.add_plain(")");
+    }
+
+    fn add_length(&mut self, length: Length) {
+        match length {
+            Length::Fill => {
+                self.add_type("Length");
+                self.add_operator("::");
+                self.add_plain("Fill");
+            }
+            Length::Shrink => {
+                self.add_type("Length");
+                self.add_operator("::");
+                self.add_plain("Shrink");
+            }
+            Length::Fixed(px) => {
+                self.add_type("Length");
+                self.add_operator("::");
+                self.add_plain("Fixed");
+                self.add_plain("(");
+                self.add_number(&format!("{:.1}", px));
+                self.add_plain(")");
+            }
+            Length::FillPortion(p) => {
+                self.add_type("Length");
+                self.add_operator("::");
+                self.add_plain("FillPortion");
+                self.add_plain("(");
+                self.add_number(&format!("{}", p));
+                self.add_plain(")");
+            }
+            _ => {
+                self.add_type("Length");
+                self.add_operator("::");
+                self.add_plain("Shrink");
+            }
+        }
+    }
+
+    fn generate_all_widget_names(&mut self) {
+        self.widget_names.clear();
+        self.collect_widget_names(&self.hierarchy.root().clone());
+    }
+
+    fn add_color(&mut self, color: Color) {
+        self.add_type("Color");
+        self.add_operator("::");
+        self.add_function("from_rgba");
+        self.add_plain("(");
+        self.add_number(&format!("{:.3}", color.r));
+        self.add_plain(", ");
+        self.add_number(&format!("{:.3}", color.g));
+        self.add_plain(", ");
+        self.add_number(&format!("{:.3}", color.b));
+        self.add_plain(", ");
+        self.add_number(&format!("{:.3}", color.a));
+        self.add_plain(")");
+    }
+
+    // Token helper methods
+    fn add_keyword(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Keyword,
+        });
+    }
+
+    fn add_type(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Type,
+        });
+    }
+
+    fn add_function(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Function,
+        });
+    }
+
+    fn add_string(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::String,
+        });
+    }
+
+    fn add_number(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Number,
+        });
+    }
+
+    fn add_comment(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Comment,
+        });
+    }
+
+    fn add_operator(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Operator,
+        });
+    }
+
+    fn add_identifier(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Identifier,
+        });
+    }
+
+    fn add_macro(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Macro,
+        });
+    }
+
+    fn add_plain(&mut self, text: &str) {
+        self.tokens.push(Token {
+            text: text.to_string(),
+            token_type: TokenType::Plain,
+        });
+    }
 
-        if props.button_on_press_with_enabled {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("on_press_with");
-            self.add_plain("(|| ");
-            self.add_type("Message");
-            self.add_operator("::");
-            self.add_plain(&format!("{}Pressed", to_pascal_case(&name)));
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
+    /// A widget's Message variant name, recorded for the pass that emits it
+    fn add_variant(&mut self, pass: WiringPass, variant: &str) {
+        self.wiring.record(pass, variant);
+        self.add_plain(variant);
+    }
 
-        if props.button_on_press_maybe_enabled {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("on_press_maybe");
-            self.add_plain("(Some(");
-            self.add_type("Message");
-            self.add_operator("::");
-            self.add_plain(&format!("{}Pressed", to_pascal_case(&name)));
-            self.add_plain("))");
-            self.indent_level -= 1;
-        }
+    fn add_newline(&mut self) {
+        self.resolve_chain_break();
+        self.tokens.push(Token {
+            text: "\n".to_string(),
+            token_type: TokenType::Plain,
+        });
+        self.inline_calls = 0;
+    }
 
+    fn add_indent(&mut self) {
+        self.metrics.max_nesting = self.metrics.max_nesting.max(self.indent_level);
+        self.tokens.push(Token {
+            text: "    ".repeat(self.indent_level),
+            token_type: TokenType::Plain,
+        });
+    }
 
-        // Clip
-        if props.clip {
+    /// The header's comment lines and one blank line after them, before anything else
+    fn generate_file_header(&mut self) {
+        if self.file_header.is_empty() {
+            return;
+        }
+        for line in self.file_header.clone() {
+            self.add_comment(&line);
             self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("clip");
-            self.add_plain("(");
-            self.add_keyword("true");
-            self.add_plain(")");
-            self.indent_level -= 1;
         }
+        self.add_newline();
+    }
 
-        // Style - only add if not Primary (default)
-        match props.button_style {
-            ButtonStyleType::Secondary => {
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("style");
-                self.add_plain("(button::secondary)");
-                self.indent_level -= 1;
-            }
-            ButtonStyleType::Success => {
+    /// Lines of the finished code. Counted once at the end, since compact layout drops some newlines again.
+    fn count_lines(&mut self) {
+        let newlines: usize = self.tokens.iter().map(|t| t.text.matches('\n').count()).sum();
+        let open_line = self.tokens.iter().rev()
+            .find(|t| !t.text.is_empty())
+            .is_some_and(|t| !t.text.ends_with('\n'));
+        self.metrics.lines = newlines + open_line as usize;
+    }
+
+    /// Start the next call of a method chain. Verbose puts every call on its own line; compact
+    /// defers the choice until the call has been emitted and its length is known.
+    fn chain_break(&mut self) {
+        match self.code_style {
+            CodeStyle::Verbose => {
                 self.add_newline();
-                self.indent_level += 1;
                 self.add_indent();
-                self.add_operator(".");
-                self.add_function("style");
-                self.add_plain("(button::success)");
-                self.indent_level -= 1;
             }
-            ButtonStyleType::Danger => {
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("style");
-                self.add_plain("(button::danger)");
-                self.indent_level -= 1;
+            CodeStyle::Compact => {
+                self.resolve_chain_break();
+                self.pending_break = Some(PendingBreak { at: self.tokens.len(), indent_level: self.indent_level });
             }
-            ButtonStyleType::Text => {
-                self.add_newline();
-                self.indent_level += 1;
-                self.add_indent();
-                self.add_operator(".");
-                self.add_function("style");
-                self.add_plain("(button::text)");
-                self.indent_level -= 1;
+        }
+    }
+
+    /// Settle a deferred chain break by inserting its newline and indent, or leaving the call inline
+    fn resolve_chain_break(&mut self) {
+        let Some(pending) = self.pending_break.take() else { return };
+        let call = &self.tokens[pending.at..];
+        let call_len: usize = call.iter().map(|t| t.text.chars().count()).sum();
+
+        // A call whose arguments continue on later lines has to start its own line
+        let mut depth = 0i32;
+        for token in call.iter().filter(|t| !matches!(t.token_type, TokenType::String | TokenType::Comment)) {
+            for c in token.text.chars() {
+                match c {
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' | '}' => depth -= 1,
+                    _ => {}
+                }
             }
-            ButtonStyleType::Primary => {} // Default, don't add
         }
-        
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+
+        if depth != 0 || self.should_break(self.line_len(pending.at), call_len) {
+            self.metrics.max_nesting = self.metrics.max_nesting.max(pending.indent_level);
+            let indent = Token { text: "    ".repeat(pending.indent_level), token_type: TokenType::Plain };
+            let newline = Token { text: "\n".to_string(), token_type: TokenType::Plain };
+            self.tokens.splice(pending.at..pending.at, [newline, indent]);
+            self.inline_calls = 1;
+        } else {
+            self.inline_calls += 1;
         }
+    }
 
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
+    /// Whether a chained call of `next_len` characters goes on a new line
+    fn should_break(&self, line_len: usize, next_len: usize) -> bool {
+        match self.code_style {
+            CodeStyle::Verbose => true,
+            CodeStyle::Compact => {
+                next_len > COMPACT_SHORT_CALL
+                    || self.inline_calls >= COMPACT_INLINE_CALLS
+                    || line_len + next_len > COMPACT_LINE_WIDTH
+            }
         }
+    }
 
-        // Padding
-        if props.padding != (Padding { top: 5.0, bottom: 5.0, right: 10.0, left: 10.0 }) {
-            self.generate_padding(&props.padding, props.padding_mode);
+    /// Characters on the line ending at token `end`
+    fn line_len(&self, end: usize) -> usize {
+        let mut len = 0;
+        for token in self.tokens[..end].iter().rev() {
+            match token.text.rfind('\n') {
+                Some(i) => return len + token.text[i + 1..].chars().count(),
+                None => len += token.text.chars().count(),
+            }
         }
+        len
     }
 
-    fn generate_text_properties(&mut self, props: &Properties) {
-        if props.text_size != 16.0 {
+    /// Run `emit` against an empty token stream and hand back what it produced
+    fn emit_detached(&mut self, emit: impl FnOnce(&mut Self)) -> Vec<Token> {
+        self.resolve_chain_break();
+        let outer = std::mem::take(&mut self.tokens);
+        let inline_calls = std::mem::replace(&mut self.inline_calls, 0);
+
+        emit(self);
+        self.resolve_chain_break();
+
+        self.inline_calls = inline_calls;
+        std::mem::replace(&mut self.tokens, outer)
+    }
+
+    /// Emit `open`, the entries and `close` on one line when the list is short and `layout`
+    /// allows it, otherwise one entry per line with trailing commas and `close` back at the
+    /// current indent
+    fn emit_list(&mut self, open: &str, entries: Vec<Vec<Token>>, close: &str, layout: ListLayout) {
+        let width = entries.iter().flatten().map(|t| t.text.chars().count()).sum::<usize>()
+            + entries.len().saturating_sub(1) * 2;
+
+        self.add_plain(open);
+        if layout == ListLayout::Fit && entries.len() <= LIST_INLINE_ENTRIES && width <= LIST_INLINE_WIDTH {
+            for (i, entry) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    self.add_plain(", ");
+                }
+                self.tokens.extend(entry);
+            }
+        } else {
             self.add_newline();
             self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("size");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.text_size));
-            self.add_plain(")");
+            for entry in entries {
+                self.add_indent();
+                self.tokens.extend(entry);
+                self.add_plain(",");
+                self.add_newline();
+            }
             self.indent_level -= 1;
-        }
-        
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
             self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
         }
+        self.add_plain(close);
+    }
 
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
+    /// `vec!["a".to_string(), ...]` for an option list, wrapped by `emit_list`
+    fn emit_string_list(&mut self, options: &[String]) {
+        let entries = options.iter()
+            .map(|option| self.emit_detached(|generator| {
+                generator.add_string(&format!("\"{}\"", option));
+                generator.add_operator(".");
+                generator.add_function("to_string");
+                generator.add_plain("()");
+            }))
+            .collect();
+        self.emit_list("vec![", entries, "]", ListLayout::Fit);
     }
 
-    fn generate_text_input_properties(&mut self, props: &Properties) {
-        if props.is_secure {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("secure");
-            self.add_plain("(");
-            self.add_keyword("true");
-            self.add_plain(")");
-            self.indent_level -= 1;
+    /// In compact mode, append a single-line child directly after the opening paren.
+    /// Returns false when the child has to stay on its own lines.
+    fn push_inline(&mut self, child: &[Token]) -> bool {
+        if self.code_style == CodeStyle::Verbose {
+            return false;
         }
-        
-        if props.text_input_size != 16.0 {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("size");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.text_input_size));
-            self.add_plain(")");
-            self.indent_level -= 1;
+
+        let start = child.iter().position(|t| !t.text.trim().is_empty()).unwrap_or(child.len());
+        let end = child.iter().rposition(|t| !t.text.trim().is_empty()).map_or(start, |i| i + 1);
+        let body = &child[start..end];
+        let body_len: usize = body.iter().map(|t| t.text.chars().count()).sum();
+
+        // +1 for the closing paren
+        if body.iter().any(|t| t.text.contains('\n')) || self.line_len(self.tokens.len()) + body_len + 1 > COMPACT_LINE_WIDTH {
+            return false;
         }
-        
-        if !matches!(props.width, Length::Fill) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+
+        self.tokens.extend_from_slice(body);
+        true
+    }
+
+    /// Returns the Task that runs the dialog. Cancelling it sends `<Name>FileChosen(None)`.
+    fn add_file_dialog_task(&mut self, name: &str, dialog: FileDialog) {
+        self.add_indent();
+        self.add_keyword("return");
+        self.add_plain(" ");
+        self.add_type("Task");
+        self.add_operator("::");
+        self.add_function("perform");
+        self.add_plain("(");
+        self.add_newline();
+        self.indent_level += 1;
+
+        self.add_indent();
+        self.add_keyword("async");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_number("rfd");
+        self.add_operator("::");
+        self.add_type("AsyncFileDialog");
+        self.add_operator("::");
+        self.add_function("new");
+        self.add_plain("()");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_operator(".");
+        self.add_function(if dialog == FileDialog::Save { "save_file" } else { "pick_file" });
+        self.add_plain("()");
+        self.add_newline();
+        self.add_indent();
+        self.add_operator(".");
+        self.add_keyword("await");
+        self.add_newline();
+        self.add_indent();
+        self.add_operator(".");
+        self.add_function("map");
+        self.add_plain("(");
+        self.add_operator("|");
+        self.add_identifier("file");
+        self.add_operator("|");
+        self.add_plain(" ");
+        self.add_identifier("file");
+        self.add_operator(".");
+        self.add_function("path");
+        self.add_plain("()");
+        self.add_operator(".");
+        self.add_function("to_path_buf");
+        self.add_plain("())");
+        self.add_newline();
+        self.indent_level -= 2;
+        self.add_indent();
+        self.add_plain("},");
+        self.add_newline();
+
+        self.add_indent();
+        self.add_type("Message");
+        self.add_operator("::");
+        self.add_variant(WiringPass::Sent, &format!("{}FileChosen", to_pascal_case(name)));
+        self.add_plain(",");
+        self.add_newline();
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain(");");
+        self.add_newline();
+    }
+
+    /// Stores the path the dialog returned, None when it was cancelled
+    fn generate_file_chosen_arm(&mut self, widget: &Widget, name: &str) {
+        if file_dialog_of(widget) == FileDialog::None {
+            return;
+        }
+        self.add_indent();
+        self.add_type("Message");
+        self.add_operator("::");
+        self.add_variant(WiringPass::Matched, &format!("{}FileChosen", to_pascal_case(name)));
+        self.add_plain("(");
+        self.add_identifier("path");
+        self.add_plain(") ");
+        self.add_operator("=>");
+        self.add_plain(" {");
+        self.add_newline();
+        self.indent_level += 1;
+        self.add_indent();
+        self.add_keyword("self");
+        self.add_operator(".");
+        self.add_identifier(&format!("{}_path", to_snake_case(name)));
+        self.add_plain(" ");
+        self.add_operator("=");
+        self.add_plain(" ");
+        self.add_identifier("path");
+        self.add_plain(";");
+        self.add_newline();
+        self.add_event_log(name, "file chosen", &[format!("self.{}_path", to_snake_case(name)).as_str()]);
+        self.indent_level -= 1;
+        self.add_indent();
+        self.add_plain("}");
+        self.add_newline();
+    }
+
+    /// The line of an update arm that has nothing else to do, in the chosen stub style
+    fn add_handler_stub(&mut self, comment: &str, widget_name: &str, event: &str, args: &[&str]) {
+        self.add_indent();
+        match self.stub_style {
+            StubStyle::Comment => self.add_comment(comment),
+            StubStyle::Todo => {
+                self.add_macro("todo!");
+                self.add_plain("(");
+                self.add_string(&format!("\"handle {} {}\"", widget_name, event));
+                self.add_plain(");");
+            }
+            StubStyle::Log => self.add_event_println(widget_name, event, args),
         }
+        self.add_newline();
     }
-    
-    fn generate_checkbox_properties(&mut self, props: &Properties) {
-        if props.checkbox_size != 16.0 {
-            self.add_newline();
-            self.indent_level += 1;
+
+    /// Log line after an arm's own statements, only emitted in the Log stub style
+    fn add_event_log(&mut self, widget_name: &str, event: &str, args: &[&str]) {
+        if self.stub_style == StubStyle::Log {
             self.add_indent();
-            self.add_operator(".");
-            self.add_function("size");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.checkbox_size));
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        if props.checkbox_spacing != 8.0 {
+            self.add_event_println(widget_name, event, args);
             self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("spacing");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.checkbox_spacing));
-            self.add_plain(")");
-            self.indent_level -= 1;
         }
-        
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+    }
+
+    fn add_event_println(&mut self, widget_name: &str, event: &str, args: &[&str]) {
+        let placeholders = if args.is_empty() { String::new() } else { format!(": {}", vec!["{:?}"; args.len()].join(", ")) };
+        self.add_macro("println!");
+        self.add_plain("(");
+        self.add_string(&format!("\"{} {}{}\"", widget_name, event, placeholders));
+        for arg in args {
+            self.add_plain(", ");
+            self.add_identifier(arg);
         }
+        self.add_plain(");");
+    }
+}
+
+/// Rust keywords plus the methods every generated App has. A widget named after one would
+/// read as the method (`new_value` next to `fn new`) or stop compiling once used bare.
+const RESERVED_NAMES: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    "new", "title", "theme", "update", "view", "subscription",
+];
+
+/// `text` as a raw string literal, with one more `#` than the longest run after a quote
+/// inside it, so quotes, backslashes and newlines all come through as typed
+pub fn raw_string_literal(text: &str) -> String {
+    let mut longest = None;
+    for (i, _) in text.match_indices('"') {
+        let run = text[i + 1..].chars().take_while(|&c| c == '#').count();
+        longest = longest.max(Some(run));
+    }
+    let hashes = "#".repeat(longest.map_or(0, |run| run + 1));
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+/// The code the tokens spell, which is the generated code with the highlighting dropped
+pub fn source_of(tokens: &[Token]) -> String {
+    tokens.iter().map(|t| t.text.as_str()).collect()
+}
+
+/// The `Theme` variant a built-in theme is written as, None for a custom theme
+pub fn builtin_theme_variant(theme: &Theme) -> Option<&'static str> {
+    Some(match theme {
+        Theme::Light => "Light",
+        Theme::Dark => "Dark",
+        Theme::Dracula => "Dracula",
+        Theme::Nord => "Nord",
+        Theme::SolarizedLight => "SolarizedLight",
+        Theme::SolarizedDark => "SolarizedDark",
+        Theme::GruvboxLight => "GruvboxLight",
+        Theme::GruvboxDark => "GruvboxDark",
+        Theme::CatppuccinLatte => "CatppuccinLatte",
+        Theme::CatppuccinFrappe => "CatppuccinFrappe",
+        Theme::CatppuccinMacchiato => "CatppuccinMacchiato",
+        Theme::CatppuccinMocha => "CatppuccinMocha",
+        Theme::TokyoNight => "TokyoNight",
+        Theme::TokyoNightStorm => "TokyoNightStorm",
+        Theme::TokyoNightLight => "TokyoNightLight",
+        Theme::KanagawaWave => "KanagawaWave",
+        Theme::KanagawaDragon => "KanagawaDragon",
+        Theme::KanagawaLotus => "KanagawaLotus",
+        Theme::Moonfly => "Moonfly",
+        Theme::Nightfly => "Nightfly",
+        Theme::Oxocarbon => "Oxocarbon",
+        Theme::Ferra => "Ferra",
+        _ => return None,
+    })
+}
+
+/// Lowercase name of a widget type, which widgets without a custom name are called by
+pub fn widget_type_name(widget_type: WidgetType) -> String {
+    match widget_type {
+        WidgetType::Button => "button".to_string(),
+        WidgetType::Text => "text".to_string(),
+        WidgetType::TextInput => "text_input".to_string(),
+        WidgetType::Checkbox => "checkbox".to_string(),
+        WidgetType::Radio => "radio".to_string(),
+        WidgetType::Slider => "slider".to_string(),
+        WidgetType::VerticalSlider => "vertical_slider".to_string(),
+        WidgetType::ProgressBar => "progress_bar".to_string(),
+        WidgetType::Toggler => "toggler".to_string(),
+        WidgetType::PickList => "pick_list".to_string(),
+        _ => format!("{:?}", widget_type).to_lowercase(), // Fallback to type name
     }
+}
+
+/// Name a widget's fields and variants start from, before duplicates are numbered
+pub fn widget_base_name(widget_type: WidgetType, custom_name: &str) -> String {
+    let type_name = widget_type_name(widget_type);
+
+    let Some(sanitized) = sanitize_name(custom_name) else {
+        return type_name;
+    };
     
-    fn generate_slider_properties(&mut self, props: &Properties) {
-        if props.slider_step != 1.0 {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("step");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.slider_step));
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        // For horizontal slider, only height can be set
-        if !matches!(props.slider_height, iced::widget::slider::Slider::<f32, Theme>::DEFAULT_HEIGHT) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.slider_height.into());
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
+    if sanitized.chars().next().is_some_and(|c| c.is_numeric()) {
+        // `_1st` would lose its underscore in `1stPressed`, so lead with the type instead
+        format!("{}_{}", type_name, sanitized)
+    } else if RESERVED_NAMES.contains(&sanitized.as_str()) {
+        // The name only ever starts an identifier (`type_value`, `TypePressed`), so a
+        // suffix reads better than `r#` and also covers `self`, which can't be raw
+        format!("{}_{}", sanitized, type_name)
+    } else {
+        sanitized
     }
+}
 
-    fn generate_vertical_slider_properties(&mut self, props: &Properties) {
-        if props.slider_step != 1.0 {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("step");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.slider_step));
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        // For vertical slider, width can be set as a Length
-        if !matches!(props.slider_width, iced::widget::vertical_slider::VerticalSlider::<f32, Theme>::DEFAULT_WIDTH) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.slider_width.into());
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
+/// Function name for a named style: the sanitized name, led by `style_` if it starts with a
+/// digit and followed by `_style` if the generated app already uses it for something else
+pub fn style_fn_name(name: &str) -> Option<String> {
+    const APP_FUNCTIONS: &[&str] = &["main", "window_settings", "build_view", "style"];
+    let name = sanitize_name(name)?;
+    let taken = RESERVED_NAMES.contains(&name.as_str())
+        || APP_FUNCTIONS.contains(&name.as_str())
+        || WidgetType::ALL.iter().any(|&widget_type| widget_type_name(widget_type) == name);
+    Some(if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("style_{}", name)
+    } else if taken {
+        format!("{}_style", name)
+    } else {
+        name
+    })
+}
+
+/// Lowercase snake_case form of a custom name, None when it has no letters or digits to keep
+fn sanitize_name(name: &str) -> Option<String> {
+    let trimmed = name.trim();
+    if !trimmed.chars().any(|c| c.is_alphanumeric()) {
+        return None;
     }
     
-    fn generate_progress_properties(&mut self, props: &Properties) {
-        if !matches!(props.progress_length, Length::Fill) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("length");
-            self.add_plain("(");
-            self.add_length(props.progress_length);
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        if props.progress_girth != iced::widget::progress_bar::ProgressBar::<Theme>::DEFAULT_GIRTH {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("girth");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.progress_girth));
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        if props.progress_vertical {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("vertical");
-            self.add_plain("()");
-            self.indent_level -= 1;
+    // Replace spaces and special characters with underscores
+    Some(trimmed
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+        .collect::<String>()
+        .to_lowercase())
+}
+
+/// Names for widgets given in tree order as type and custom name. Widgets left on their type
+/// name are numbered by type, while a custom name only gets a number when it is already taken,
+/// so an earlier widget keeps the plain name.
+pub fn resolve_names<'a>(widgets: impl IntoIterator<Item = (WidgetType, &'a str)>) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut taken: HashSet<String> = HashSet::new();
+    let mut names = Vec::new();
+
+    for (widget_type, custom_name) in widgets {
+        let base_name = widget_base_name(widget_type, custom_name);
+        let mut suffix = if sanitize_name(custom_name).is_some() {
+            1
+        } else {
+            let count = counts.entry(format!("{:?}", widget_type)).or_insert(0);
+            *count += 1;
+            *count
+        };
+
+        let mut final_name = if suffix > 1 {
+            format!("{}_{}", base_name, suffix)
+        } else {
+            base_name.clone()
+        };
+
+        // Names that only differed by case, or that match another widget's numbered name,
+        // keep counting up so every widget gets its own fields and variants. Variants drop
+        // the underscores, so `save2` and `save_2` are checked in that form.
+        while taken.contains(&to_pascal_case(&final_name)) {
+            suffix += 1;
+            final_name = format!("{}_{}", base_name, suffix);
         }
+
+        taken.insert(to_pascal_case(&final_name));
+        names.push(final_name);
     }
-    
-    fn generate_toggler_properties(&mut self, props: &Properties) {
-        if props.toggler_size != iced::widget::toggler::Toggler::<Theme>::DEFAULT_SIZE {
-            self.add_newline();
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("size");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.toggler_size));
-            self.add_plain(")");
+
+    names
+}
+
+/// `widget` and its descendants, parents before children
+pub fn collect_tree_order<'a>(widget: &'a Widget, widgets: &mut Vec<&'a Widget>) {
+    widgets.push(widget);
+    for child in &widget.children {
+        collect_tree_order(child, widgets);
+    }
+}
+
+/// Name `widget_id` gets in the generated code, for showing next to the name field
+pub fn generated_widget_name(hierarchy: &WidgetHierarchy, widget_id: WidgetId) -> String {
+    let mut generator = CodeGenerator::new(hierarchy, Theme::Light, None);
+    generator.resolved_widget_names()
+        .remove(&widget_id)
+        .unwrap_or_else(|| "widget".to_string())
+}
+
+// Helper functions
+pub fn to_pascal_case(s: &str) -> String {
+    s.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        })
+        .collect()
+}
+
+pub fn to_snake_case(s: &str) -> String {
+    s.to_lowercase().replace(' ', "_")
+}
+
+/// What a message variant carries, written the way the generated enum spells it
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantPayload {
+    None,
+    Type(String),
+    /// Written as is, like `mouse::ScrollDelta`
+    Plain(&'static str),
+    /// `Option<PathBuf>` from a file dialog
+    ChosenPath,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageVariant {
+    pub name: String,
+    pub payload: VariantPayload,
+}
+
+/// Message variants a widget declares, in the order the generated enum lists them.
+/// Combo boxes bound to an enum carry it when `type_system` knows the enum, a String otherwise.
+pub fn message_variants(widget: &Widget, name: &str, type_system: Option<&TypeSystem>) -> Vec<MessageVariant> {
+    let base = to_pascal_case(name);
+    let props = &widget.properties;
+    let mut variants = Vec::new();
+    let mut push = |suffix: &str, payload: VariantPayload| {
+        variants.push(MessageVariant { name: format!("{}{}", base, suffix), payload });
+    };
+    let typed = |type_name: &str| VariantPayload::Type(type_name.to_string());
+    let file_dialog = file_dialog_of(widget) != FileDialog::None;
+
+    match widget.widget_type {
+        WidgetType::Button => {
+            push("Pressed", VariantPayload::None);
+            if file_dialog { push("FileChosen", VariantPayload::ChosenPath); }
         }
-        
-        if props.toggler_spacing != iced::widget::toggler::Toggler::<Theme>::DEFAULT_SIZE / 2.0 {
-            self.add_newline();
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("spacing");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.toggler_spacing));
-            self.add_plain(")");
+        WidgetType::TextInput => {
+            push("Changed", typed("String"));
+            if props.text_input_on_submit { push("Submitted", VariantPayload::None); }
+            if props.text_input_on_paste { push("Pasted", typed("String")); }
         }
-        
-        if !props.toggler_label.is_empty() {
-            self.add_newline();
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("label");
-            self.add_plain("(");
-            self.add_string(&format!("\"{}\"", props.toggler_label));
-            self.add_plain(")");
+        WidgetType::Checkbox | WidgetType::Toggler => push("Toggled", typed("bool")),
+        WidgetType::Radio => push("Selected", typed("usize")),
+        WidgetType::Slider | WidgetType::VerticalSlider => push("Changed", typed("f32")),
+        WidgetType::PickList => push("Selected", typed("String")),
+        WidgetType::ComboBox => {
+            let type_name = props.referenced_enum.as_ref()
+                .and_then(|enum_id| type_system?.get_enum(enum_id.clone()))
+                .map(|enum_def| enum_def.name.clone())
+                .unwrap_or_else(|| "String".to_string());
+            push("Selected", typed(&type_name));
+            if props.combobox_use_on_input { push("OnInput", typed("String")); }
+            if props.combobox_use_on_option_hovered { push("OnOptionHovered", typed(&type_name)); }
+            if props.combobox_use_on_open { push("OnOpen", VariantPayload::None); }
+            if props.combobox_use_on_close { push("OnClose", VariantPayload::None); }
         }
-
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
+        WidgetType::Markdown => push("LinkClicked", typed("markdown::Url")),
+        WidgetType::MouseArea => {
+            if props.mousearea_on_press {
+                push("Pressed", VariantPayload::None);
+                if file_dialog { push("FileChosen", VariantPayload::ChosenPath); }
+            }
+            let handlers = [
+                (props.mousearea_on_release, "Released", VariantPayload::None),
+                (props.mousearea_on_double_click, "DoubleClicked", VariantPayload::None),
+                (props.mousearea_on_right_press, "RightPressed", VariantPayload::None),
+                (props.mousearea_on_right_release, "RightReleased", VariantPayload::None),
+                (props.mousearea_on_middle_press, "MiddlePressed", VariantPayload::None),
+                (props.mousearea_on_middle_release, "MiddleReleased", VariantPayload::None),
+                (props.mousearea_on_scroll, "Scrolled", VariantPayload::Plain("mouse::ScrollDelta")),
+                (props.mousearea_on_enter, "Entered", VariantPayload::None),
+                (props.mousearea_on_move, "Moved", VariantPayload::Plain("Point")),
+                (props.mousearea_on_exit, "Exited", VariantPayload::None),
+            ];
+            for (enabled, suffix, payload) in handlers {
+                if enabled {
+                    push(suffix, payload);
+                }
+            }
         }
+        _ => {}
+    }
+
+    variants
+}
+
+/// Names of the message variants a widget declares, as `message_variants` lists them
+pub fn message_variant_names(widget: &Widget, name: &str) -> Vec<String> {
+    message_variants(widget, name, None).into_iter().map(|variant| variant.name).collect()
+}
+
+/// Names the code refers to, leaving out strings and comments
+fn code_words(tokens: &[Token]) -> HashSet<&str> {
+    tokens.iter()
+        .filter(|token| !matches!(token.token_type, TokenType::String | TokenType::Comment))
+        .flat_map(|token| token.text.split(|c: char| !(c.is_alphanumeric() || c == '_')))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// File dialog a widget's press opens. A MouseArea only opens one while it handles presses.
+pub fn file_dialog_of(widget: &Widget) -> FileDialog {
+    match widget.widget_type {
+        WidgetType::Button => widget.properties.file_dialog,
+        WidgetType::MouseArea if widget.properties.mousearea_on_press => widget.properties.file_dialog,
+        _ => FileDialog::None,
+    }
+}
+
+/// Window width the flagged rows stack below, when the root enables a breakpoint and some Row is flagged
+pub fn active_breakpoint(root: &Widget) -> Option<f32> {
+    fn flagged(widget: &Widget) -> bool {
+        (widget.widget_type == WidgetType::Row && widget.properties.stack_below_breakpoint)
+            || widget.children.iter().any(flagged)
+    }
+    let props = &root.properties;
+    (props.breakpoint_enabled && flagged(root)).then_some(props.breakpoint_width)
+}
+
+/// Whether any press opens a file dialog, which needs `rfd` and an update returning Task
+pub fn uses_file_dialogs(widget: &Widget) -> bool {
+    file_dialog_of(widget) != FileDialog::None || widget.children.iter().any(uses_file_dialogs)
+}
+
+/// Widget whose chosen path the Text `text_id` shows, the first in tree order
+fn path_source(widget: &Widget, text_id: WidgetId) -> Option<&Widget> {
+    if file_dialog_of(widget) != FileDialog::None && widget.properties.file_dialog_path_text == Some(text_id) {
+        return Some(widget);
+    }
+    widget.children.iter().find_map(|child| path_source(child, text_id))
+}
+
+/// State field a named condition flag becomes, None when the name has nothing to keep
+pub fn condition_flag_field(flag: &str) -> Option<String> {
+    let name = sanitize_name(flag)?;
+    // A leading digit or a keyword can't start a field name
+    if name.starts_with(|c: char| c.is_numeric()) || RESERVED_NAMES.contains(&name.as_str()) {
+        Some(format!("is_{}", name))
+    } else {
+        Some(name)
+    }
+}
+
+/// Named condition flags of the conditional styles in tree order, each once, as field names
+fn condition_flags(widget: &Widget, flags: &mut Vec<String>) {
+    if matches!(widget.widget_type, WidgetType::Container | WidgetType::TextInput)
+        && let Some(StyleCondition::Flag(flag)) = &widget.properties.style_condition
+        && let Some(field) = condition_flag_field(flag)
+        && !flags.contains(&field)
+    {
+        flags.push(field);
+    }
+    for child in &widget.children {
+        condition_flags(child, flags);
     }
+}
+
+pub fn build_code_view_with_height<'a>(
+    tokens: &[Token], 
+    height: f32,
+    theme: Theme
+) -> Element<'a, crate::widget_helper::Message> {
+    let view = code_view(highlighted_lines(tokens, &[], None), &theme, CodeViewFont::default());
+    container(
+        scrollable(view)
+            .width(Length::Fill)
+            .height(
+                if height == 0.0 {
+                    Length::Fill
+                }
+                else {
+                    Length::Fixed(height)
+                }
+            )
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+/// Full-height code view with the search matches highlighted, scrollable to a match via `CODE_VIEW_SCROLLABLE`
+pub fn build_searchable_code_view<'a>(
+    tokens: &[Token],
+    theme: Theme,
+    matches: &[Range<usize>],
+    current: Option<usize>,
+    font: CodeViewFont,
+) -> Element<'a, crate::widget_helper::Message> {
+    let view = code_view(highlighted_lines(tokens, matches, current), &theme, font);
+    scrollable(view)
+        .id(CODE_VIEW_SCROLLABLE)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+fn code_view<'a>(lines: Vec<Vec<Segment>>, theme: &Theme, font: CodeViewFont) -> Element<'a, crate::widget_helper::Message> {
+    let bg_color = match theme {
+        Theme::Light => Color::from_rgb8(248, 248, 248),  // Very light gray
+        Theme::Dark => Color::from_rgb8(30, 30, 30),       // Dark gray
+        _ => Color::from_rgb8(40, 40, 40),                 // Default dark
+    };
+
+    let border_color = match theme {
+        Theme::Light => Color::from_rgb8(200, 200, 200),   // Light gray border
+        Theme::Dark => Color::from_rgb8(60, 60, 60),        // Dark gray border
+        _ => Color::from_rgb8(80, 80, 80),
+    };
+
+    let warning = theme.extended_palette().warning.base.color;
     
-    fn generate_picklist_properties(&mut self, props: &Properties) {
-        if !props.picklist_placeholder.is_empty() && props.picklist_placeholder != "Choose an option..." {
-            self.add_newline();
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("placeholder");
-            self.add_plain("(");
-            self.add_string(&format!("\"{}\"", props.picklist_placeholder));
-            self.add_plain(")");
-        }
-        
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-        }
+    // Build the content as a column of rows
+    let content = column(
+        lines.into_iter().map(|line| {
+            if line.is_empty() {
+                row![text(" ").size(font.size).font(font.font)].into()
+            } else {
+                row(
+                    line.into_iter().map(|segment| {
+                        let label = text(segment.text)
+                            .size(font.size)
+                            .font(font.font)
+                            .color(segment.token_type.color_for_theme(theme));
+                        let alpha = match segment.highlight {
+                            Highlight::None => return label.into(),
+                            Highlight::Match => 0.35,
+                            Highlight::Current => 0.8,
+                        };
+                        container(label)
+                            .style(move |_| container::Style {
+                                background: Some(Background::Color(Color { a: alpha, ..warning })),
+                                ..Default::default()
+                            })
+                            .into()
+                    }).collect::<Vec<Element<'a, crate::widget_helper::Message>>>()
+                ).into()
+            }
+        }).collect::<Vec<Element<'a, crate::widget_helper::Message>>>()
+    )
+    .spacing(2);
+    
+    container(content)
+        .width(Length::Fill)
+        .padding(15)
+        .style(move |_| container::Style {
+            background: Some(Background::Color(bg_color)),
+            border: Border {
+                color: border_color,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+}
 
-        if props.padding != (Padding { top: 5.0, bottom: 5.0, right: 10.0, left: 10.0 }) {
-            self.generate_padding(&props.padding, props.padding_mode);
-        }
-    }
+/// Build a syntax-highlighted code view
+pub fn build_code_view<'a>(tokens: &[Token], theme: Theme) -> Element<'a, crate::widget_helper::Message> {
+    build_code_view_with_height(tokens, 300.0, theme)
+}
+
+/// Build a syntax-highlighted code view - generic so I can use it outside of widget_helper::Messages
+pub fn build_code_view_with_height_generic<'a, Message: 'a>(
+    tokens: &[Token], 
+    height: f32,
+    theme: Theme
+) -> Element<'a, Message> {
+    // Group tokens by lines
+    let mut lines: Vec<Vec<Token>> = vec![vec![]];
     
-    fn generate_scrollable_properties(&mut self, props: &Properties) {
-        // Direction
-        let is_default_dir = matches!(
-            props.scroll_dir,
-            iced::widget::scrollable::Direction::Vertical(_)
-        );
-        
-        if !is_default_dir {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("direction");
-            self.add_plain("(");
-            match props.scroll_dir {
-                iced::widget::scrollable::Direction::Horizontal(_) => {
-                    self.add_plain("scrollable::Direction::Horizontal(scrollable::Scrollbar::default())");
+    for token in tokens {
+        if token.text.contains('\n') {
+            let parts: Vec<&str> = token.text.split('\n').collect();
+            for (i, part) in parts.iter().enumerate() {
+                if !part.is_empty() {
+                    lines.last_mut().unwrap().push(Token {
+                        text: part.to_string(),
+                        token_type: token.token_type,
+                    });
                 }
-                iced::widget::scrollable::Direction::Both { .. } => {
-                    self.add_plain("scrollable::Direction::Both { vertical: scrollable::Scrollbar::default(), horizontal: scrollable::Scrollbar::default() }");
+                if i < parts.len() - 1 {
+                    lines.push(vec![]);
                 }
-                _ => {}
             }
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        // Width
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        } else {
+            lines.last_mut().unwrap().push(token.clone());
         }
-        
-        // Height
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
+    }
+
+    let bg_color = match theme {
+        Theme::Light => Color::from_rgb8(248, 248, 248),
+        Theme::Dark => Color::from_rgb8(30, 30, 30),
+        _ => Color::from_rgb8(40, 40, 40),
+    };
+
+    let border_color = match theme {
+        Theme::Light => Color::from_rgb8(200, 200, 200),
+        Theme::Dark => Color::from_rgb8(60, 60, 60),
+        _ => Color::from_rgb8(80, 80, 80),
+    };
+    
+    let content = column(
+        lines.into_iter().map(|line| {
+            if line.is_empty() {
+                row![text(" ").size(14).font(iced::Font::MONOSPACE)].into()
+            } else {
+                row(
+                    line.into_iter().map(|token| {
+                        text(token.text)
+                            .size(14)
+                            .font(iced::Font::MONOSPACE)
+                            .color(token.token_type.color_for_theme(&theme))
+                            .into()
+                    }).collect::<Vec<Element<'a, Message>>>()
+                ).into()
+            }
+        }).collect::<Vec<Element<'a, Message>>>()
+    )
+    .spacing(2);
+    
+    container(
+        scrollable(
+            container(content)
+                .width(Length::Fill)
+                .padding(15)
+                .style(move |_| container::Style {
+                    background: Some(Background::Color(bg_color)),
+                    border: Border {
+                        color: border_color,
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    ..Default::default()
+                })
+        )
+        .width(Length::Fill)
+        .height(
+            if height == 0.0 {
+                Length::Fill
+            } else {
+                Length::Fixed(height)
+            }
+        )
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+
+
+/// Whether any state field starts at something other than its type's `Default`,
+/// so a derived `Default` wouldn't reproduce the initializers in `new()`
+fn needs_custom_default(widget: &Widget) -> bool {
+    let props = &widget.properties;
+    let custom = match widget.widget_type {
+        WidgetType::Checkbox => props.checkbox_checked,
+        WidgetType::Radio => props.radio_selected_index != 0,
+        WidgetType::Slider | WidgetType::VerticalSlider => props.slider_value != 0.0,
+        WidgetType::Toggler => props.toggler_active,
+        // Carries its options in the state, and an enum value has no default variant
+        WidgetType::ComboBox => true,
+        WidgetType::Markdown => !props.markdown_source.text().trim().is_empty(),
+        _ => false,
+    };
+    custom || widget.children.iter().any(needs_custom_default)
+}
+
+/// StyleFn Generators
+
+/// Generate tokens for container style code
+pub fn generate_container_style_tokens(
+    text_color: StyleColor,
+    background_color: StyleColor,
+    border_color: StyleColor,
+    border_width: f32,
+    border_radius_top_left: f32,
+    border_radius_top_right: f32,
+    border_radius_bottom_right: f32,
+    border_radius_bottom_left: f32,
+    shadow_enabled: bool,
+    shadow_color: StyleColor,
+    shadow_offset_x: f32,
+    shadow_offset_y: f32,
+    shadow_blur_radius: f32,
+    snap: bool,
+) -> Vec<Token> {
+    let mut builder = TokenBuilder::new();
+
+    // Palette references read the theme, so the style becomes the closure that receives it
+    let colors = [text_color, background_color, border_color, shadow_color];
+    if colors.iter().any(|color| matches!(color, StyleColor::Palette(_))) {
+        builder.add_operator("|");
+        builder.add_identifier("theme");
+        builder.add_operator(":");
+        builder.add_space();
+        builder.add_operator("&");
+        builder.add_type("Theme");
+        builder.add_operator("|");
+        builder.add_space();
+    }
+
+    builder.add_plain("container");
+    builder.add_operator("::");
+    builder.add_type("Style");
+    builder.add_space();
+    builder.add_plain("{");
+    builder.add_newline();
+    builder.increase_indent();
+
+    // text_color field
+    builder.add_field("text_color", |b| {
+        b.add_plain("Some(");
+        b.add_style_color(text_color);
+        b.add_plain(")");
+    });
+
+    // background field
+    builder.add_field("background", |b| {
+        b.add_plain("Some(");
+        b.add_type("Background");
+        b.add_operator("::");
+        b.add_type("Color");
+        b.add_plain("(");
+        b.add_style_color(background_color);
+        b.add_plain("))");
+    });
+
+    // border field
+    builder.add_field("border", |b| {
+        b.add_struct("Border", |b| {
+            b.add_field("color", |b| b.add_style_color(border_color));
+            b.add_field("width", |b| b.add_number(&format!("{:.1}", border_width)));
+            b.add_field("radius", |b| {
+                b.add_struct("Radius", |b| {
+                    b.add_field("top_left", |b| b.add_number(&format!("{:.1}", border_radius_top_left)));
+                    b.add_field("top_right", |b| b.add_number(&format!("{:.1}", border_radius_top_right)));
+                    b.add_field("bottom_right", |b| b.add_number(&format!("{:.1}", border_radius_bottom_right)));
+                    b.add_field("bottom_left", |b| b.add_number(&format!("{:.1}", border_radius_bottom_left)));
+                });
+            });
+        });
+    });
+
+    // shadow field
+    builder.add_field("shadow", |b| {
+        if shadow_enabled {
+            b.add_struct("Shadow", |b| {
+                b.add_field("color", |b| b.add_style_color(shadow_color));
+                b.add_field("offset", |b| {
+                    b.add_struct("Vector", |b| {
+                        b.add_field("x", |b| b.add_number(&format!("{:.1}", shadow_offset_x)));
+                        b.add_field("y", |b| b.add_number(&format!("{:.1}", shadow_offset_y)));
+                    });
+                });
+                b.add_field("blur_radius", |b| b.add_number(&format!("{:.1}", shadow_blur_radius)));
+            });
+        } else {
+            b.add_type("Shadow");
+            b.add_operator("::");
+            b.add_function("default");
+            b.add_plain("()");
         }
+    });
+
+    // snap field
+    builder.add_field("snap", |b| {
+        b.add_keyword(if snap { "true" } else { "false" });
+    });
+
+    builder.decrease_indent();
+    builder.add_plain("}");
+
+    builder.into_tokens()
+}
+
+/// Appearance calls emitted for a combo box, in emission order.
+/// Keeping the property -> method mapping here means targeting a different
+/// iced API only has to change this function.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComboBoxStyleCall {
+    InputStyle { background: Option<Color>, value: Option<Color> },
+    MenuStyle { background: Color },
+    MenuHeight(f32),
+}
+
+pub fn combobox_style_calls(props: &Properties) -> Vec<ComboBoxStyleCall> {
+    let mut calls = Vec::new();
+
+    if props.combobox_input_background.is_some() || props.combobox_input_text_color.is_some() {
+        calls.push(ComboBoxStyleCall::InputStyle {
+            background: props.combobox_input_background,
+            value: props.combobox_input_text_color,
+        });
     }
-    
-    fn generate_space_properties(&mut self, props: &Properties) {
-        match props.orientation {
-            Orientation::Horizontal => {
-                if !matches!(props.width, Length::Fill) {
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("width");
-                    self.add_plain("(");
-                    self.add_length(props.width);
-                    self.add_plain(")");
-                    self.indent_level -= 1;
-                }
-                
-                if !matches!(props.height, Length::Shrink) {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("height");
-                    self.add_plain("(");
-                    self.add_length(props.height);
-                    self.add_plain(")");
-                }
-            }
-            Orientation::Vertical => {
-                if !matches!(props.width, Length::Shrink) {
-                    self.add_newline();
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("width");
-                    self.add_plain("(");
-                    self.add_length(props.width);
-                    self.add_plain(")");
-                }
-                
-                if !matches!(props.height, Length::Fill) {
-                    self.add_newline();
-                    self.indent_level += 1;
-                    self.add_indent();
-                    self.add_operator(".");
-                    self.add_function("height");
-                    self.add_plain("(");
-                    self.add_length(props.height);
-                    self.add_plain(")");
-                    self.indent_level -= 1;
-                }
-            }
-        }
+    if let Some(background) = props.combobox_menu_background {
+        calls.push(ComboBoxStyleCall::MenuStyle { background });
+    }
+    if let Some(height) = props.combobox_menu_height {
+        calls.push(ComboBoxStyleCall::MenuHeight(height));
+    }
+
+    calls
+}
+
+/// Whether a rule has a width or height other than the Fill it gets by default
+pub fn rule_is_sized(props: &Properties) -> bool {
+    !matches!(props.width, Length::Fill) || !matches!(props.height, Length::Fill)
+}
+
+/// Widgets with a `.clip(bool)` in iced. The rest either always clip, like Scrollable,
+/// or draw within their own bounds, so the panel hides the clip control for them.
+pub fn supports_clip(widget_type: WidgetType) -> bool {
+    matches!(
+        widget_type,
+        WidgetType::Container | WidgetType::Row | WidgetType::Column | WidgetType::Button | WidgetType::Stack
+    )
+}
 
+/// Property keys the generator will actually emit for a widget. Each list sits next
+/// to the function that does the emitting; when a gap is closed, add the key there.
+pub fn emitted_property_keys(widget_type: WidgetType, props: &Properties) -> HashSet<&'static str> {
+    let keys = match widget_type {
+        WidgetType::Container      => CodeGenerator::CONTAINER_EMITS,
+        WidgetType::Row
+        | WidgetType::Column       => CodeGenerator::LAYOUT_EMITS,
+        WidgetType::Button         => CodeGenerator::BUTTON_EMITS,
+        WidgetType::Text           => CodeGenerator::TEXT_EMITS,
+        WidgetType::TextInput      => CodeGenerator::TEXT_INPUT_EMITS,
+        WidgetType::Checkbox       => CodeGenerator::CHECKBOX_EMITS,
+        WidgetType::Radio          => CodeGenerator::RADIO_EMITS,
+        WidgetType::Slider         => CodeGenerator::SLIDER_EMITS,
+        WidgetType::VerticalSlider => CodeGenerator::VERTICAL_SLIDER_EMITS,
+        WidgetType::ProgressBar    => CodeGenerator::PROGRESS_EMITS,
+        WidgetType::Toggler        => CodeGenerator::TOGGLER_EMITS,
+        WidgetType::PickList       => CodeGenerator::PICKLIST_EMITS,
+        WidgetType::Scrollable     => CodeGenerator::SCROLLABLE_EMITS,
+        WidgetType::Space          => CodeGenerator::SPACE_EMITS,
+        WidgetType::Rule           => CodeGenerator::RULE_EMITS,
+        WidgetType::Image          => CodeGenerator::IMAGE_EMITS,
+        WidgetType::Svg            => CodeGenerator::SVG_EMITS,
+        WidgetType::Tooltip        => CodeGenerator::TOOLTIP_EMITS,
+        WidgetType::ComboBox       => CodeGenerator::COMBOBOX_EMITS,
+        WidgetType::Markdown       => CodeGenerator::MARKDOWN_EMITS,
+        WidgetType::MouseArea      => CodeGenerator::MOUSE_AREA_EMITS,
+        WidgetType::QRCode         => CodeGenerator::QRCODE_EMITS,
+        WidgetType::Stack          => CodeGenerator::STACK_EMITS,
+        WidgetType::Themer         => CodeGenerator::THEMER_EMITS,
+        WidgetType::Pin            => CodeGenerator::PIN_EMITS,
+    };
+
+    let mut keys: HashSet<&'static str> = keys.iter().copied().collect();
+    // Any widget can be a Stack layer. Outside a Stack the float settings are ignored, like the builder does.
+    keys.extend(CodeGenerator::STACK_LAYER_EMITS);
+    keys.extend(CodeGenerator::SECTION_EMITS);
+    // Set on the root, which can be any type
+    keys.extend(["breakpoint_enabled", "breakpoint_width"]);
+    if widget_type == WidgetType::Row {
+        keys.insert("stack_below_breakpoint");
     }
-    
-    fn generate_image_properties(&mut self, props: &Properties) {
-        // Content fit
-        if !matches!(props.image_fit, ContentFitChoice::Contain) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("content_fit");
-            self.add_plain("(ContentFit::");
-            match props.image_fit {
-                ContentFitChoice::Cover => self.add_plain("Cover"),
-                ContentFitChoice::Fill => self.add_plain("Fill"),
-                ContentFitChoice::ScaleDown => self.add_plain("ScaleDown"),
-                ContentFitChoice::None => self.add_plain("None"),
-                _ => self.add_plain("Contain"),
-            }
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        // Width
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        // Height
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
+    // The wrapping settings only reach the output through `.wrap()`
+    if widget_type != WidgetType::Row || !props.is_wrapping_row {
+        keys.remove("wrapping_vertical_spacing");
+        keys.remove("wrapping_align_x");
     }
+    keys
+}
+
+/// Widget modules the generated code imports, sorted
+pub fn used_widget_modules(hierarchy: &WidgetHierarchy) -> Vec<&'static str> {
+    let mut tracker = ImportTracker::new();
+    tracker.scan_widget(hierarchy.root());
+    let mut widgets: Vec<_> = tracker.used_widgets.into_iter().collect();
+    widgets.sort();
+    widgets
+}
+
+struct ImportTracker {
+    used_widgets: HashSet<&'static str>,
     
-    fn generate_svg_properties(&mut self, props: &Properties) {
-        // Content fit
-        if !matches!(props.svg_fit, ContentFitChoice::Contain) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("content_fit");
-            self.add_plain("(ContentFit::");
-            match props.svg_fit {
-                ContentFitChoice::Cover => self.add_plain("Cover"),
-                ContentFitChoice::Fill => self.add_plain("Fill"),
-                ContentFitChoice::ScaleDown => self.add_plain("ScaleDown"),
-                ContentFitChoice::None => self.add_plain("None"),
-                _ => self.add_plain("Contain"),
-            }
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
-        
-        // Width
-        if !matches!(props.width, Length::Fill) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+    uses_length: bool,
+    uses_alignment: bool,
+    uses_padding: bool,
+    uses_color: bool,
+    
+    // Text properties
+    uses_text_line_height: bool,
+    uses_text_wrapping: bool,
+    uses_text_shaping: bool,
+    uses_text_alignment: bool,
+    
+    // Mouse
+    uses_mouse: bool,
+    uses_mouse_interaction: bool,
+    uses_mouse_scroll_delta: bool,
+    
+    // Other
+    uses_point: bool,
+    uses_font: bool,
+    uses_border: bool,
+    uses_shadow: bool,
+    uses_background: bool,
+    uses_vector: bool,
+    uses_pixels: bool,
+    uses_overlay_menu: bool,
+    uses_gradient: bool,
+}
+
+impl ImportTracker {
+    fn new() -> Self {
+        Self {
+            used_widgets: HashSet::new(),
+            uses_length: false,
+            uses_alignment: false,
+            uses_padding: false,
+            uses_color: false,
+            uses_text_line_height: false,
+            uses_text_wrapping: false,
+            uses_text_shaping: false,
+            uses_text_alignment: false,
+            uses_mouse: false,
+            uses_mouse_interaction: false,
+            uses_mouse_scroll_delta: false,
+            uses_point: false,
+            uses_font: false,
+            uses_border: false,
+            uses_shadow: false,
+            uses_background: false,
+            uses_vector: false,
+            uses_pixels: false,
+            uses_overlay_menu: false,
+            uses_gradient: false,
         }
-        
-        // Height
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
+    }
+    
+    /// Swap in what the layout-only view emits for widgets it can't show as-is
+    fn use_layout_stand_ins(&mut self) {
+        if self.used_widgets.remove("combo_box") {
+            self.used_widgets.insert("pick_list");
+        }
+        if self.used_widgets.remove("markdown") {
+            self.used_widgets.insert("text");
         }
+        self.uses_mouse_scroll_delta = false;
     }
 
-    fn generate_combobox_properties(&mut self, props: &Properties) {
-        if props.combobox_size != 16.0 {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("size");
-            self.add_plain("(");
-            self.add_number(&format!("{}", props.combobox_size));
-            self.add_plain(")");
-            self.indent_level -= 1;
-        }
+    fn scan_widget(&mut self, widget: &Widget) {
+        let props = &widget.properties;
         
-        if !matches!(props.width, Length::Fill) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        // Track widget type
+        match widget.widget_type {
+            WidgetType::Container => { self.used_widgets.insert("container"); }
+            WidgetType::Row => { self.used_widgets.insert("row"); }
+            WidgetType::Column => { self.used_widgets.insert("column"); }
+            WidgetType::Button => { self.used_widgets.insert("button"); }
+            WidgetType::Text => { self.used_widgets.insert("text"); }
+            WidgetType::TextInput => { self.used_widgets.insert("text_input"); }
+            WidgetType::Checkbox => { self.used_widgets.insert("checkbox"); }
+            WidgetType::Radio => { self.used_widgets.insert("radio"); }
+            WidgetType::Slider => { self.used_widgets.insert("slider"); }
+            WidgetType::VerticalSlider => { self.used_widgets.insert("vertical_slider"); }
+            WidgetType::ProgressBar => { self.used_widgets.insert("progress_bar"); }
+            WidgetType::Toggler => { self.used_widgets.insert("toggler"); }
+            WidgetType::PickList => { self.used_widgets.insert("pick_list"); }
+            WidgetType::Scrollable => { self.used_widgets.insert("scrollable"); }
+            WidgetType::Space => { self.used_widgets.insert("space"); }
+            WidgetType::Rule => { self.used_widgets.insert("rule"); }
+            WidgetType::Image => { self.used_widgets.insert("image"); }
+            WidgetType::Svg => { self.used_widgets.insert("svg"); }
+            WidgetType::Tooltip => { self.used_widgets.insert("tooltip"); }
+            WidgetType::ComboBox => { self.used_widgets.insert("combo_box"); }
+            WidgetType::Markdown => { self.used_widgets.insert("markdown"); }
+            WidgetType::MouseArea => { 
+                self.used_widgets.insert("mouse_area");
+                self.uses_mouse = true;
+            }
+            WidgetType::QRCode => { self.used_widgets.insert("qr_code"); }
+            WidgetType::Stack => { self.used_widgets.insert("stack"); }
+            WidgetType::Themer => {
+                self.used_widgets.insert("themer");
+                if widget.properties.themer_theme.as_ref().is_some_and(|theme| builtin_theme_variant(theme).is_none()) {
+                    self.uses_color = true;
+                }
+            }
+            WidgetType::Pin => { self.used_widgets.insert("pin"); }
         }
-    }
 
-    fn generate_markdown_properties(&mut self, props: &Properties) {
-        if !matches!(props.width, Length::Fill) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        // Floating layers are wrapped in an aligned container
+        if widget.widget_type == WidgetType::Stack {
+            for child in &widget.children {
+                let Some(anchor) = child.properties.float_anchor else { continue };
+                self.used_widgets.insert("container");
+                if anchor != FloatAnchor::TopLeft {
+                    self.uses_alignment = true;
+                }
+                if anchor.padding(child.properties.float_offset_x, child.properties.float_offset_y) != Padding::ZERO {
+                    self.uses_padding = true;
+                }
+            }
         }
         
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        // Track if any Length is used (always true if widget exists)
+        self.uses_length = true;
+        
+        // Track Alignment if used in Row/Column
+        if matches!(widget.widget_type, WidgetType::Row | WidgetType::Column) {
+            if props.align_items != Alignment::Start {
+                self.uses_alignment = true;
+            }
         }
-    }
-
-    fn generate_qrcode_properties(&mut self, props: &Properties) {
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        
+        // Track Padding
+        if props.padding_mode == PaddingMode::Individual {
+            self.uses_padding = true;
         }
         
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        // Track Container-specific features
+        if widget.widget_type == WidgetType::Container {
+            if props.border_width > 0.0 {
+                self.uses_border = true;
+            }
+            if props.background_color.a > 0.0 {
+                self.uses_background = true;
+                self.uses_color = true;
+            }
+            if props.gradient_enabled {
+                self.uses_gradient = true;
+                self.uses_background = true;
+                self.uses_color = true;
+            }
+            if props.has_shadow {
+                self.uses_shadow = true;
+                self.uses_vector = true;
+            }
+            if props.align_x != ContainerAlignX::Left || props.align_y != ContainerAlignY::Top {
+                self.uses_alignment = true;
+            }
+            if props.style_condition.is_some() && !props.gradient_enabled {
+                self.uses_border = true;
+                self.uses_color = true;
+                if props.background_color.a > 0.0 || props.condition_background_color.a > 0.0 {
+                    self.uses_background = true;
+                }
+            }
         }
-    }
-
-    fn generate_stack_properties(&mut self, props: &Properties) {
-        if !matches!(props.width, Length::Fill) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        
+        // Track Text properties
+        if widget.widget_type == WidgetType::Text {
+            if props.text_color.a > 0.0 {
+                self.uses_color = true;
+            }
+            if props.font != FontType::Default {
+                self.uses_font = true;
+            }
+            if props.line_height != text::LineHeight::default() {
+                self.uses_text_line_height = true;
+                self.uses_pixels |= matches!(props.line_height, text::LineHeight::Absolute(_));
+            }
+            if props.wrap != text::Wrapping::default() {
+                self.uses_text_wrapping = true;
+            }
+            if props.shaping != text::Shaping::default() {
+                self.uses_text_shaping = true;
+            }
+            if props.text_align_x != text::Alignment::default() || 
+               props.text_align_y != iced::alignment::Vertical::Top {
+                self.uses_text_alignment = true;
+                self.uses_alignment = true;
+            }
         }
         
-        if !matches!(props.height, Length::Fill) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        // Track TextInput properties
+        if widget.widget_type == WidgetType::TextInput {
+            if props.text_input_font != FontType::Default {
+                self.uses_font = true;
+            }
+            if props.text_input_line_height != text::LineHeight::default() {
+                self.uses_text_line_height = true;
+                self.uses_pixels |= matches!(props.text_input_line_height, text::LineHeight::Absolute(_));
+            }
+            if props.text_input_alignment != ContainerAlignX::Left {
+                self.uses_alignment = true;
+            }
+            if props.style_condition.is_some() {
+                self.uses_border = true;
+                self.uses_color = true;
+                if props.condition_background_color.a > 0.0 {
+                    self.uses_background = true;
+                }
+            }
+        }
+        
+        // Sized rules are wrapped in a container
+        if widget.widget_type == WidgetType::Rule && rule_is_sized(props) {
+            self.used_widgets.insert("container");
         }
-    }
 
-    fn generate_themer_properties(&mut self, props: &Properties) {
-        if !matches!(props.width, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("width");
-            self.add_plain("(");
-            self.add_length(props.width);
-            self.add_plain(")");
-            self.indent_level -= 1;
+        // Left-placed labels wrap the control in a row
+        if matches!(widget.widget_type, WidgetType::Checkbox | WidgetType::Toggler | WidgetType::Radio)
+            && props.label_position == LabelPosition::Left
+        {
+            self.used_widgets.extend(["row", "text", "space"]);
+            self.uses_alignment = true;
         }
-        
-        if !matches!(props.height, Length::Shrink) {
-            self.add_newline();
-            self.indent_level += 1;
-            self.add_indent();
-            self.add_operator(".");
-            self.add_function("height");
-            self.add_plain("(");
-            self.add_length(props.height);
-            self.add_plain(")");
-            self.indent_level -= 1;
+
+        // Track Svg tint
+        if widget.widget_type == WidgetType::Svg && props.svg_tint == SvgTint::Custom {
+            self.uses_color = true;
         }
-    }
 
-    fn generate_padding(&mut self, padding: &Padding, padding_mode: PaddingMode) {
-        self.add_newline();
-        self.add_indent();
-        self.add_operator(".");
-        self.add_function("padding");
-        self.add_plain("(");
-        
-        match padding_mode {
-            PaddingMode::Uniform => {
-                // .padding(10.0)
-                self.add_number(&format!("{:.1}", padding.top));
+        // Track ComboBox appearance overrides
+        if widget.widget_type == WidgetType::ComboBox {
+            for call in combobox_style_calls(props) {
+                match call {
+                    ComboBoxStyleCall::InputStyle { background, .. } => {
+                        self.used_widgets.insert("text_input");
+                        self.uses_color = true;
+                        if background.is_some() {
+                            self.uses_background = true;
+                        }
+                    }
+                    ComboBoxStyleCall::MenuStyle { .. } => {
+                        self.uses_overlay_menu = true;
+                        self.uses_background = true;
+                        self.uses_color = true;
+                    }
+                    ComboBoxStyleCall::MenuHeight(_) => {}
+                }
             }
-            PaddingMode::Symmetric => {
-                // .padding([vertical, horizontal])
-                self.add_plain("[");
-                self.add_number(&format!("{:.1}", padding.top));
-                self.add_plain(", ");
-                self.add_number(&format!("{:.1}", padding.left));
-                self.add_plain("]");
+        }
+
+        // Track MouseArea event handlers
+        if widget.widget_type == WidgetType::MouseArea {
+            if props.mousearea_on_scroll {
+                self.uses_mouse_scroll_delta = true;
             }
-            PaddingMode::Individual => {
-                // .padding(Padding { top, right, bottom, left })
-                self.add_type("Padding");
-                self.add_plain(" { ");
-                self.add_plain("top: ");
-                self.add_number(&format!("{:.1}", padding.top));
-                self.add_plain(", ");
-                self.add_plain("right: ");
-                self.add_number(&format!("{:.1}", padding.right));
-                self.add_plain(", ");
-                self.add_plain("bottom: ");
-                self.add_number(&format!("{:.1}", padding.bottom));
-                self.add_plain(", ");
-                self.add_plain("left: ");
-                self.add_number(&format!("{:.1}", padding.left));
-                self.add_plain(" }");
+            if props.mousearea_on_move {
+                self.uses_point = true;
+            }
+            if props.mousearea_interaction.is_some() {
+                self.uses_mouse_interaction = true;
             }
         }
         
-        self.add_plain(")");
+        // Recursively scan children
+        for child in &widget.children {
+            self.scan_widget(child);
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::styles::stylefn_builders::PaletteSlot;
+    use crate::widget_helper::message_wiring::WiringProblem;
+    use crate::widget_helper::window_preset::WindowPreset;
+    use crate::widget_helper::file_header::FileHeader;
+    use crate::widget_helper::named_styles::ContainerLook;
+
+    /// Root > Column > `widgets`, with the ids of `widgets`
+    fn column_with<const N: usize>(widgets: [WidgetType; N]) -> (WidgetHierarchy, [WidgetId; N]) {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let ids = widgets.map(|widget_type| hierarchy.add_child(column_id, widget_type).unwrap());
+        (hierarchy, ids)
     }
 
-    fn add_length(&mut self, length: Length) {
-        match length {
-            Length::Fill => {
-                self.add_type("Length");
-                self.add_operator("::");
-                self.add_plain("Fill");
-            }
-            Length::Shrink => {
-                self.add_type("Length");
-                self.add_operator("::");
-                self.add_plain("Shrink");
-            }
-            Length::Fixed(px) => {
-                self.add_type("Length");
-                self.add_operator("::");
-                self.add_plain("Fixed");
-                self.add_plain("(");
-                self.add_number(&format!("{:.1}", px));
-                self.add_plain(")");
+    fn generate(hierarchy: &WidgetHierarchy, type_system: &TypeSystem) -> String {
+        let mut generator = CodeGenerator::new(hierarchy, Theme::Light, Some(type_system));
+        generator.generate_app_source()
+    }
+
+    /// App source with the generator's settings changed by `configure` first
+    fn generate_with(hierarchy: &WidgetHierarchy, configure: impl FnOnce(&mut CodeGenerator)) -> String {
+        let type_system = TypeSystem::new();
+        let mut generator = CodeGenerator::new(hierarchy, Theme::Light, Some(&type_system));
+        configure(&mut generator);
+        generator.generate_app_source()
+    }
+
+    #[test]
+    fn test_emitted_keys_are_properties_fields() {
+        let source = include_str!("../widget_helper.rs");
+        let start = source.find("pub struct Properties {").expect("Properties struct");
+        let body = &source[start..start + source[start..].find("\n}").expect("end of Properties")];
+        let fields: HashSet<&str> = body.lines()
+            .filter_map(|line| line.trim().strip_prefix("pub "))
+            .filter_map(|rest| rest.split(':').next())
+            .map(str::trim)
+            .collect();
+
+        for widget_type in WidgetType::ALL {
+            let mut props = Properties::for_widget_type(widget_type);
+            props.is_wrapping_row = true;
+            for key in emitted_property_keys(widget_type, &props) {
+                assert!(fields.contains(key), "{:?} lists unknown property `{}`", widget_type, key);
             }
-            Length::FillPortion(p) => {
-                self.add_type("Length");
-                self.add_operator("::");
-                self.add_plain("FillPortion");
-                self.add_plain("(");
-                self.add_number(&format!("{}", p));
-                self.add_plain(")");
+        }
+    }
+
+    #[test]
+    fn test_preview_data_is_never_generated() {
+        for widget_type in WidgetType::ALL {
+            let emitted = emitted_property_keys(widget_type, &Properties::for_widget_type(widget_type));
+            for key in Properties::PREVIEW_DATA_KEYS {
+                assert!(!emitted.contains(key), "{:?} emits preview data `{}`", widget_type, key);
             }
-            _ => {
-                self.add_type("Length");
-                self.add_operator("::");
-                self.add_plain("Shrink");
+        }
+
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let input_id = hierarchy.add_child(column_id, WidgetType::TextInput).unwrap();
+        let progress_id = hierarchy.add_child(column_id, WidgetType::ProgressBar).unwrap();
+        let pick_list_id = hierarchy.add_child(column_id, WidgetType::PickList).unwrap();
+        let combo_box_id = hierarchy.add_child(column_id, WidgetType::ComboBox).unwrap();
+        let before = generate(&hierarchy, &type_system);
+
+        hierarchy.apply_property_change(input_id, PropertyChange::PreviewText(Some("jane@example.com".to_string())), &type_system);
+        hierarchy.apply_property_change(progress_id, PropertyChange::PreviewProgress(Some(0.8)), &type_system);
+        hierarchy.apply_property_change(pick_list_id, PropertyChange::PreviewSelection(Some("Option 2".to_string())), &type_system);
+        hierarchy.apply_property_change(combo_box_id, PropertyChange::PreviewSelection(Some("Option 3".to_string())), &type_system);
+        assert_eq!(generate(&hierarchy, &type_system), before);
+
+        let props = &hierarchy.get_widget_by_id(input_id).unwrap().properties;
+        assert_eq!(props.shown_text_input_value(true), "jane@example.com");
+        assert_eq!(props.shown_text_input_value(false), "");
+    }
+
+    #[test]
+    fn test_file_header_opens_the_file_once() {
+        let type_system = TypeSystem::new();
+        let (hierarchy, _) = column_with([WidgetType::Radio]);
+        let plain = generate(&hierarchy, &type_system);
+
+        let header = FileHeader { enabled: true, author: "Ada".to_string(), year: 2030, ..FileHeader::default() };
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        generator.set_file_header(header.comment_lines("App"));
+        let tokens = generator.generate_app_code();
+        let code = source_of(&tokens);
+
+        let expected = "// Copyright (c) 2030 Ada\n// SPDX-License-Identifier: MIT\n\n";
+        assert_eq!(code.strip_prefix(expected), Some(plain.as_str()));
+        assert_eq!(code.matches("SPDX-License-Identifier").count(), 1);
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Comment && t.text == "// Copyright (c) 2030 Ada"));
+
+        // Switched off, the output is the same as without a header
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        generator.set_file_header(FileHeader { enabled: false, ..header }.comment_lines("App"));
+        let code = generator.generate_app_source();
+        assert_eq!(code, plain);
+    }
+
+    #[test]
+    fn test_line_heights_are_spelled_out_for_text_and_text_input() {
+        let type_system = TypeSystem::new();
+        let changes: [(WidgetType, fn(text::LineHeight) -> PropertyChange); 2] = [
+            (WidgetType::Text, PropertyChange::TextLineHeight),
+            (WidgetType::TextInput, PropertyChange::TextInputLineHeight),
+        ];
+        for (widget_type, change) in changes {
+            let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+            let id = hierarchy.add_child(WidgetId(0), widget_type).unwrap();
+
+            hierarchy.apply_property_change(id, change(text::LineHeight::Absolute(iced::Pixels(24.0))), &type_system);
+            let code = generate(&hierarchy, &type_system);
+            assert!(code.contains(".line_height(LineHeight::Absolute(Pixels(24.0)))"), "{:?}: {}", widget_type, code);
+            assert!(code.contains("LineHeight}") || code.contains("LineHeight,"), "{:?}: {}", widget_type, code);
+            assert!(code.contains("Pixels,"), "{:?}: {}", widget_type, code);
+
+            hierarchy.apply_property_change(id, change(text::LineHeight::Relative(1.5)), &type_system);
+            let code = generate(&hierarchy, &type_system);
+            assert!(code.contains(".line_height(LineHeight::Relative(1.5))"), "{:?}: {}", widget_type, code);
+            assert!(!code.contains("Pixels"), "{:?}: {}", widget_type, code);
+        }
+    }
+
+    #[test]
+    fn test_shared_named_style_is_defined_once() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        let mut look = ContainerLook::of(&Properties::default());
+        look.background = Color::from_rgb8(0x20, 0x40, 0x60);
+        look.border_radius = 8.0;
+        let mut registry = StyleRegistry::default();
+        let style_id = registry.promote("Card Surface", look).unwrap().id;
+        for _ in 0..2 {
+            let id = hierarchy.add_child(WidgetId(0), WidgetType::Container).unwrap();
+            hierarchy.apply_property_change(id, PropertyChange::NamedStyle(Some(style_id), look), &type_system);
+        }
+
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        generator.set_named_styles(registry.clone());
+        let code = generator.generate_app_source();
+        assert_eq!(code.matches("fn card_surface(_theme: &Theme) -> container::Style").count(), 1, "{}", code);
+        assert_eq!(code.matches(".style(card_surface)").count(), 2, "{}", code);
+
+        // A deleted style leaves nothing behind to call
+        registry.remove(style_id);
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        generator.set_named_styles(registry);
+        let code = generator.generate_app_source();
+        assert!(!code.contains("card_surface"), "{}", code);
+    }
+
+    #[test]
+    fn test_tags_are_never_generated() {
+        for widget_type in WidgetType::ALL {
+            let mut props = Properties::for_widget_type(widget_type);
+            props.tags = vec!["todo".to_string()];
+            let emitted = emitted_property_keys(widget_type, &props);
+            for key in Properties::ORGANIZING_KEYS {
+                assert!(!emitted.contains(key), "{:?} emits `{}`", widget_type, key);
             }
         }
+
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let button_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        let before = generate(&hierarchy, &type_system);
+
+        for id in [WidgetId(0), column_id, button_id] {
+            hierarchy.apply_property_change(id, PropertyChange::AddTag("a11y-reviewed".to_string()), &type_system);
+        }
+        let code = generate(&hierarchy, &type_system);
+        assert_eq!(code, before);
+        assert!(!code.contains("a11y"));
     }
 
-    fn sanitize_name(&self, name: &str) -> String {
-        let trimmed = name.trim();
-        if trimmed.is_empty() {
-            return "widget".to_string();
+    #[test]
+    fn test_clip_is_emitted_exactly_where_iced_has_it() {
+        for widget_type in WidgetType::ALL {
+            let emitted = emitted_property_keys(widget_type, &Properties::for_widget_type(widget_type));
+            assert_eq!(emitted.contains("clip"), supports_clip(widget_type), "{:?}", widget_type);
         }
-        
-        // Replace spaces and special characters with underscores
-        let sanitized = trimmed
-            .chars()
-            .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
-            .collect::<String>()
-            .to_lowercase();
-        
-        // Ensure it starts with a letter or underscore
-        if sanitized.chars().next().map_or(false, |c| c.is_numeric()) {
-            format!("_{}", sanitized)
-        } else if sanitized.is_empty() {
-            "widget".to_string()
-        } else {
-            sanitized
+
+        let type_system = TypeSystem::new();
+        for widget_type in WidgetType::ALL {
+            let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+            let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+            let Ok(id) = hierarchy.add_child(column_id, widget_type) else { continue };
+            hierarchy.apply_property_change(id, PropertyChange::Clip(true), &type_system);
+            let code = generate(&hierarchy, &type_system);
+            assert_eq!(code.contains(".clip(true)"), supports_clip(widget_type), "{:?}:\n{}", widget_type, code);
         }
     }
 
-    fn generate_all_widget_names(&mut self) {
-        self.widget_counts.clear();
-        self.widget_names.clear();
-        self.collect_widget_names(&self.hierarchy.root().clone());
+    #[test]
+    fn test_clip_control_hides_for_text() {
+        assert!(!supports_clip(WidgetType::Text));
+        assert!(!supports_clip(WidgetType::Scrollable));
+        assert!(supports_clip(WidgetType::Stack));
     }
 
-    fn add_color(&mut self, color: Color) {
-        self.add_type("Color");
-        self.add_operator("::");
-        self.add_function("from_rgba");
-        self.add_plain("(");
-        self.add_number(&format!("{:.3}", color.r));
-        self.add_plain(", ");
-        self.add_number(&format!("{:.3}", color.g));
-        self.add_plain(", ");
-        self.add_number(&format!("{:.3}", color.b));
-        self.add_plain(", ");
-        self.add_number(&format!("{:.3}", color.a));
-        self.add_plain(")");
+    #[test]
+    fn test_text_color_not_yet_emitted() {
+        let emitted = emitted_property_keys(WidgetType::Text, &Properties::default());
+        assert!(!emitted.contains("text_color"));
+        assert!(emitted.contains("text_size"));
     }
 
-    // Token helper methods
-    fn add_keyword(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Keyword,
-        });
+    /// Root > Column > Container > Text, with the inner container as `WidgetId(2)`
+    fn sized_text_hierarchy(content: &str) -> WidgetHierarchy {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let container_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        let text_id = hierarchy.add_child(container_id, WidgetType::Text).unwrap();
+        hierarchy.apply_property_change(text_id, PropertyChange::TextContent(content.to_string()), &type_system);
+        hierarchy.apply_property_change(text_id, PropertyChange::TextSize(14.0), &type_system);
+        hierarchy.apply_property_change(text_id, PropertyChange::Width(Length::Fill), &type_system);
+        hierarchy
     }
 
-    fn add_type(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Type,
-        });
+    fn generate_widget(hierarchy: &WidgetHierarchy, id: WidgetId, style: CodeStyle) -> String {
+        let mut generator = CodeGenerator::new(hierarchy, Theme::Light, None);
+        generator.set_code_style(style);
+        generator.generate_widget_source(id)
     }
 
-    fn add_function(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Function,
-        });
+    #[test]
+    fn test_verbose_layout_golden() {
+        let hierarchy = sized_text_hierarchy("Sample Text");
+        assert_eq!(
+            generate_widget(&hierarchy, WidgetId(2), CodeStyle::Verbose),
+            "container(\n    text(\"Sample Text\")\n        .size(14)\n        .width(Length::Fill)\n)\n"
+        );
     }
 
-    fn add_string(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::String,
-        });
+    fn gradient_hierarchy(enabled: bool) -> (WidgetHierarchy, WidgetId) {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let container_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        hierarchy.apply_property_change(container_id, PropertyChange::GradientStart(Color::from_rgb(1.0, 0.0, 0.0)), &type_system);
+        hierarchy.apply_property_change(container_id, PropertyChange::GradientEnd(Color::from_rgb(0.0, 0.0, 1.0)), &type_system);
+        hierarchy.apply_property_change(container_id, PropertyChange::GradientAngle(45.0), &type_system);
+        hierarchy.apply_property_change(container_id, PropertyChange::GradientEnabled(enabled), &type_system);
+        (hierarchy, container_id)
     }
 
-    fn add_number(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Number,
-        });
+    #[test]
+    fn test_container_gradient_golden() {
+        let (hierarchy, container_id) = gradient_hierarchy(true);
+        assert_eq!(
+            generate_widget(&hierarchy, container_id, CodeStyle::Verbose),
+            "container(\n    text(\"Container Content\")\n)\n\
+             .style(|_| container::Style {\n    \
+                 background: Some(Background::Gradient(\n        \
+                     gradient::Linear::new(Radians(45.0_f32.to_radians()))\n            \
+                         .add_stop(0.0, Color::from_rgba(1.000, 0.000, 0.000, 1.000))\n            \
+                         .add_stop(1.0, Color::from_rgba(0.000, 0.000, 1.000, 1.000))\n            \
+                         .into(),\n    \
+                 )),\n    \
+                 ..container::Style::default()\n\
+             })\n"
+        );
+
+        let code = generate(&hierarchy, &TypeSystem::new());
+        assert!(code.contains("Radians,"));
+        assert!(code.contains("    gradient,\n"));
     }
 
-    fn add_comment(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Comment,
-        });
+    #[test]
+    fn test_disabled_gradient_keeps_solid_output() {
+        let (hierarchy, container_id) = gradient_hierarchy(false);
+        assert_eq!(
+            generate_widget(&hierarchy, container_id, CodeStyle::Verbose),
+            "container(\n    text(\"Container Content\")\n)\n"
+        );
+
+        let code = generate(&hierarchy, &TypeSystem::new());
+        assert!(!code.contains("Gradient"));
+        assert!(!code.contains("Radians"));
     }
 
-    fn add_operator(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Operator,
-        });
+    #[test]
+    fn test_wrapping_row_with_fill_space_gets_comment() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+        let space_id = hierarchy.add_child(row_id, WidgetType::Space).unwrap();
+        hierarchy.apply_property_change(space_id, PropertyChange::Width(Length::Fill), &type_system);
+
+        let note = "// Fill spaces stretch each wrapped line separately";
+        assert!(!generate(&hierarchy, &type_system).contains(note));
+
+        hierarchy.apply_property_change(row_id, PropertyChange::IsWrappingRow(true), &type_system);
+        let code = generate(&hierarchy, &type_system);
+        let note_at = code.find(note).expect("note emitted");
+        assert!(code[note_at..].lines().nth(1).unwrap().trim_start().starts_with("row!["));
+
+        hierarchy.apply_property_change(space_id, PropertyChange::Width(Length::Fixed(20.0)), &type_system);
+        assert!(!generate(&hierarchy, &type_system).contains(note));
     }
 
-    fn add_identifier(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Identifier,
-        });
+    fn generate_layout(hierarchy: &WidgetHierarchy) -> String {
+        let mut generator = CodeGenerator::new(hierarchy, Theme::Light, None);
+        source_of(&generator.generate_layout_code())
     }
 
-    fn add_macro(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Macro,
-        });
+    #[test]
+    fn test_layout_only_golden() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        assert_eq!(
+            generate_layout(&hierarchy),
+            "use iced::{\n    Element,\n    widget::{button, column, container},\n};\n\n\n\
+             #[derive(Debug, Clone)]\npub enum Message {\n    Noop,\n}\n\n\
+             fn view<'a>() -> Element<'a, Message> {\n    container(\n        column![\n            button(\"Click Me!\")\n\n        ]\n    )\n    .into()\n}\n"
+        );
     }
 
-    fn add_plain(&mut self, text: &str) {
-        self.tokens.push(Token {
-            text: text.to_string(),
-            token_type: TokenType::Plain,
-        });
+    #[test]
+    fn test_layout_only_imports_what_the_view_names() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        hierarchy.apply_property_change(column_id, PropertyChange::Width(Length::Fill), &type_system);
+
+        let code = generate_layout(&hierarchy);
+        assert!(code.starts_with("use iced::{\n    Length,Element,\n    widget::{column, container, text},\n};\n"), "{}", code);
+        assert!(code.contains(".width(Length::Fill)"), "{}", code);
     }
 
-    fn add_newline(&mut self) {
-        self.tokens.push(Token {
-            text: "\n".to_string(),
-            token_type: TokenType::Plain,
-        });
+    #[test]
+    fn test_layout_only_uses_no_named_messages() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let input_id = hierarchy.add_child(column_id, WidgetType::TextInput).unwrap();
+        hierarchy.apply_property_change(input_id, PropertyChange::TextInputOnSubmit(true), &type_system);
+        hierarchy.add_child(column_id, WidgetType::ComboBox).unwrap();
+
+        let code = generate_layout(&hierarchy);
+        assert!(code.contains(".on_input(|_| Message::Noop)"), "{}", code);
+        assert!(!code.contains("on_submit"), "{}", code);
+        assert!(!code.contains("combo_box") && code.contains("pick_list("), "{}", code);
+        assert!(!code.replace("Message::Noop", "").contains("Message::"), "{}", code);
     }
 
-    fn add_indent(&mut self) {
-        self.tokens.push(Token {
-            text: "    ".repeat(self.indent_level),
-            token_type: TokenType::Plain,
-        });
+    #[test]
+    fn test_selection_bindings_use_the_app_code_names() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let save_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let inner_id = hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+        hierarchy.apply_property_change(save_id, PropertyChange::WidgetName("save".to_string()), &type_system);
+
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, None);
+        let names = generator.resolved_widget_names();
+        let code = generator.generate_selection_source(&[inner_id, row_id, save_id]);
+
+        // Tree order, whatever order the ids came in
+        assert!(code.starts_with(&format!("let {} =\n    button(", names[&save_id])), "{}", code);
+        assert!(code.contains(&format!("\n\nlet {} =\n    row![", names[&row_id])), "{}", code);
+        // The button inside the selected row comes with it
+        assert!(!code.contains(&format!("let {} =", names[&inner_id])), "{}", code);
+        assert!(code.trim_end().ends_with(';'), "{}", code);
     }
-}
 
-// Helper functions
-fn to_pascal_case(s: &str) -> String {
-    s.split('_')
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                None => String::new(),
-                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-            }
-        })
-        .collect()
-}
+    #[test]
+    fn test_combobox_snippet_has_no_state_reference() {
+        let (hierarchy, combobox_id) = combobox_hierarchy();
+        let code = generate_widget(&hierarchy, combobox_id, CodeStyle::Verbose);
+        assert!(code.contains("pick_list("), "{}", code);
+        assert!(!code.contains("state"), "{}", code);
+    }
 
-fn to_snake_case(s: &str) -> String {
-    s.to_lowercase().replace(' ', "_")
-}
+    #[test]
+    fn test_compact_layout_golden() {
+        let hierarchy = sized_text_hierarchy("Sample Text");
+        assert_eq!(
+            generate_widget(&hierarchy, WidgetId(2), CodeStyle::Compact),
+            "container(text(\"Sample Text\").size(14).width(Length::Fill))\n"
+        );
+    }
 
-pub fn build_code_view_with_height<'a>(
-    tokens: &[Token], 
-    height: f32,
-    theme: Theme
-) -> Element<'a, crate::widget_helper::Message> {
-    // Group tokens by lines
-    let mut lines: Vec<Vec<Token>> = vec![vec![]];
-    
-    for token in tokens {
-        if token.text.contains('\n') {
-            // Handle tokens that contain newlines
-            let parts: Vec<&str> = token.text.split('\n').collect();
-            for (i, part) in parts.iter().enumerate() {
-                if !part.is_empty() {
-                    lines.last_mut().unwrap().push(Token {
-                        text: part.to_string(),
-                        token_type: token.token_type,
-                    });
-                }
-                // Add new line for all but the last part
-                if i < parts.len() - 1 {
-                    lines.push(vec![]);
-                }
-            }
-        } else {
-            lines.last_mut().unwrap().push(token.clone());
+    #[test]
+    fn test_compact_breaks_long_lines() {
+        let long = "x".repeat(90);
+        let hierarchy = sized_text_hierarchy(&long);
+        assert_eq!(
+            generate_widget(&hierarchy, WidgetId(2), CodeStyle::Compact),
+            format!("container(\n    text(\"{}\")\n        .size(14).width(Length::Fill)\n)\n", long)
+        );
+    }
+
+    #[test]
+    fn test_default_style_is_verbose() {
+        let type_system = TypeSystem::new();
+        let hierarchy = sized_text_hierarchy("Sample Text");
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        generator.set_code_style(CodeStyle::Verbose);
+        let verbose = generator.generate_app_source();
+
+        assert_eq!(generate(&hierarchy, &type_system), verbose);
+        assert!(!verbose.contains("container(text("));
+    }
+
+    /// Root > Column > `widget_type`, with the column as `WidgetId(1)`
+    fn labelled_hierarchy(widget_type: WidgetType, position: LabelPosition) -> WidgetHierarchy {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let id = hierarchy.add_child(column_id, widget_type).unwrap();
+        hierarchy.apply_property_change(id, PropertyChange::LabelPosition(position), &type_system);
+        hierarchy
+    }
+
+    #[test]
+    fn test_left_label_wraps_checkbox() {
+        let code = generate_widget(&labelled_hierarchy(WidgetType::Checkbox, LabelPosition::Left), WidgetId(1), CodeStyle::Verbose);
+        assert!(code.contains(
+            "    row![\n        text(\"Check me\"),\n        space::horizontal(),\n        checkbox(\"\", false)"
+        ), "{}", code);
+        assert!(code.contains("    ]\n    .spacing(8)\n    .align_y(Alignment::Center)"), "{}", code);
+    }
+
+    #[test]
+    fn test_right_label_not_wrapped() {
+        for widget_type in [WidgetType::Checkbox, WidgetType::Toggler, WidgetType::Radio] {
+            let code = generate_widget(&labelled_hierarchy(widget_type, LabelPosition::Right), WidgetId(1), CodeStyle::Verbose);
+            assert!(!code.contains("row!["), "{:?}: {}", widget_type, code);
+            assert!(!code.contains("space::horizontal()"), "{:?}: {}", widget_type, code);
+        }
+        let code = generate_widget(&labelled_hierarchy(WidgetType::Checkbox, LabelPosition::Right), WidgetId(1), CodeStyle::Verbose);
+        assert!(code.contains("checkbox(\"Check me\", false)"));
+    }
+
+    #[test]
+    fn test_left_label_toggler_and_radio() {
+        let code = generate_widget(&labelled_hierarchy(WidgetType::Toggler, LabelPosition::Left), WidgetId(1), CodeStyle::Verbose);
+        assert!(code.contains("text(\"Toggle me\"),"));
+        assert!(!code.contains(".label("));
+
+        let code = generate_widget(&labelled_hierarchy(WidgetType::Radio, LabelPosition::Left), WidgetId(1), CodeStyle::Verbose);
+        assert_eq!(code.matches("space::horizontal()").count(), 2);
+        assert!(code.contains("text(\"Radio Option 1\"),"));
+        assert!(code.contains("radio(\"\", 0,"));
+    }
+
+    /// Root > `parent` > Rule, with the parent as `WidgetId(1)`
+    fn rule_hierarchy(parent: WidgetType, orientation: Orientation, size: Option<Length>) -> WidgetHierarchy {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let parent_id = hierarchy.add_child(WidgetId(0), parent).unwrap();
+        let rule_id = hierarchy.add_child(parent_id, WidgetType::Rule).unwrap();
+        hierarchy.apply_property_change(rule_id, PropertyChange::Orientation(orientation), &type_system);
+        if let Some(length) = size {
+            let change = match orientation {
+                Orientation::Horizontal => PropertyChange::Width(length),
+                Orientation::Vertical => PropertyChange::Height(length),
+            };
+            hierarchy.apply_property_change(rule_id, change, &type_system);
         }
+        hierarchy
+    }
+
+    #[test]
+    fn test_horizontal_rule_unsized() {
+        let hierarchy = rule_hierarchy(WidgetType::Row, Orientation::Horizontal, None);
+        let code = generate_widget(&hierarchy, WidgetId(1), CodeStyle::Verbose);
+        assert!(code.contains("    rule::horizontal(5)\n"), "{}", code);
+        assert!(!code.contains("container("));
+        assert!(code.contains("// Parent shrinks to fit, so this rule has no width"));
+    }
+
+    #[test]
+    fn test_horizontal_rule_sized() {
+        let hierarchy = rule_hierarchy(WidgetType::Row, Orientation::Horizontal, Some(Length::Fixed(200.0)));
+        let code = generate_widget(&hierarchy, WidgetId(1), CodeStyle::Verbose);
+        assert!(code.contains("    container(rule::horizontal(5))\n        .width(Length::Fixed(200.0))"), "{}", code);
+        assert!(!code.contains(".height("));
+        assert!(!code.contains("// Parent shrinks"));
+    }
+
+    #[test]
+    fn test_vertical_rule_unsized() {
+        let hierarchy = rule_hierarchy(WidgetType::Row, Orientation::Vertical, None);
+        let code = generate_widget(&hierarchy, WidgetId(1), CodeStyle::Verbose);
+        assert!(code.contains("    rule::vertical(5)\n"), "{}", code);
+        assert!(code.contains("// Parent shrinks to fit, so this rule has no height"));
+    }
+
+    #[test]
+    fn test_vertical_rule_sized() {
+        let hierarchy = rule_hierarchy(WidgetType::Column, Orientation::Vertical, Some(Length::Fixed(120.0)));
+        let code = generate_widget(&hierarchy, WidgetId(1), CodeStyle::Verbose);
+        assert!(code.contains("    container(rule::vertical(5))\n        .height(Length::Fixed(120.0))"), "{}", code);
+        assert!(!code.contains(".width("));
+        assert!(!code.contains("// Parent shrinks"));
+    }
+
+    #[test]
+    fn test_vertical_rule_in_filled_parent_has_no_note() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = rule_hierarchy(WidgetType::Row, Orientation::Vertical, None);
+        hierarchy.apply_property_change(WidgetId(1), PropertyChange::Height(Length::Fill), &type_system);
+        let code = generate_widget(&hierarchy, WidgetId(1), CodeStyle::Verbose);
+        assert!(!code.contains("// Parent shrinks"), "{}", code);
+    }
+
+    fn combobox_hierarchy() -> (WidgetHierarchy, WidgetId) {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let combobox_id = hierarchy.add_child(column_id, WidgetType::ComboBox).unwrap();
+        (hierarchy, combobox_id)
+    }
+
+    #[test]
+    fn test_combobox_style_only_when_customized() {
+        let type_system = TypeSystem::new();
+        let (hierarchy, _) = combobox_hierarchy();
+
+        let code = generate(&hierarchy, &type_system);
+        assert!(!code.contains(".input_style("));
+        assert!(!code.contains(".menu_style("));
+        assert!(!code.contains(".menu_height("));
+        assert!(!code.contains("overlay::menu"));
+    }
+
+    #[test]
+    fn test_combobox_style_calls_emitted() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, combobox_id) = combobox_hierarchy();
+
+        hierarchy.apply_property_change(combobox_id, PropertyChange::ComboBoxInputTextColor(Some(Color::WHITE)), &type_system);
+        hierarchy.apply_property_change(combobox_id, PropertyChange::ComboBoxMenuBackground(Some(Color::BLACK)), &type_system);
+        hierarchy.apply_property_change(combobox_id, PropertyChange::ComboBoxMenuHeight(Some(150.0)), &type_system);
+
+        let code = generate(&hierarchy, &type_system);
+        assert!(code.contains(".input_style(|theme: &Theme, status| text_input::Style {"));
+        assert!(code.contains("value: Color::from_rgba(1.000, 1.000, 1.000, 1.000),"));
+        assert!(code.contains("..text_input::default(theme, status)"));
+        assert!(code.contains(".menu_style(|theme: &Theme| menu::Style {"));
+        assert!(code.contains("background: Background::Color(Color::from_rgba(0.000, 0.000, 0.000, 1.000)),"));
+        assert!(code.contains(".menu_height(150)"));
+        assert!(code.contains("overlay::menu,"));
+        assert!(code.contains("text_input"));
+    }
+
+    fn picklist_hierarchy(options: &[&str]) -> (WidgetHierarchy, WidgetId) {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, [picklist_id]) = column_with([WidgetType::PickList]);
+        let options = options.iter().map(|o| o.to_string()).collect();
+        hierarchy.apply_property_change(picklist_id, PropertyChange::PickListOptions(options), &type_system);
+        (hierarchy, picklist_id)
+    }
+
+    #[test]
+    fn test_short_option_list_stays_inline() {
+        let (hierarchy, picklist_id) = picklist_hierarchy(&["Option 1", "Option 2", "Option 3"]);
+        let code = generate_widget(&hierarchy, picklist_id, CodeStyle::Verbose);
+        assert!(code.contains("\n    vec![\"Option 1\".to_string(), \"Option 2\".to_string(), \"Option 3\".to_string()],\n"), "{}", code);
+    }
+
+    #[test]
+    fn test_long_option_list_wraps() {
+        let (hierarchy, picklist_id) = picklist_hierarchy(&["a", "b", "c", "d", "e"]);
+        let code = generate_widget(&hierarchy, picklist_id, CodeStyle::Verbose);
+        assert!(code.contains(
+            "\n    vec![\n        \"a\".to_string(),\n        \"b\".to_string(),\n        \"c\".to_string(),\n        \"d\".to_string(),\n        \"e\".to_string(),\n    ],\n"
+        ), "{}", code);
+    }
+
+    #[test]
+    fn test_wide_combobox_state_wraps() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, combobox_id) = combobox_hierarchy();
+        let options = vec!["A rather long first option".to_string(), "And an even longer second option".to_string()];
+        hierarchy.get_widget_by_id_mut(combobox_id).unwrap().properties.combobox_options = options;
+
+        let code = generate(&hierarchy, &type_system);
+        assert!(code.contains(
+            ": combo_box::State::new(vec![\n                    \"A rather long first option\".to_string(),\n                    \"And an even longer second option\".to_string(),\n                ]),\n"
+        ), "{}", code);
+    }
+
+    #[test]
+    fn test_enum_all_golden() {
+        let mut type_system = TypeSystem::new();
+        let (hierarchy, _) = column_with([WidgetType::Radio]);
+        type_system.add_enum("Size".to_string(), vec!["Small".to_string(), "Large".to_string()]).unwrap();
+        let code = generate(&hierarchy, &type_system);
+        assert!(code.contains(
+            "impl Size {\n    pub const ALL: &'static [Self] = &[\n        Self::Small,\n        Self::Large,\n    ];\n}"
+        ), "{}", code);
+    }
+
+    #[test]
+    fn test_svg_tint_none() {
+        let type_system = TypeSystem::new();
+        let (hierarchy, _) = column_with([WidgetType::Svg]);
+
+        let code = generate(&hierarchy, &type_system);
+        assert!(!code.contains("svg::Style"));
     }
 
-    let bg_color = match theme {
-        Theme::Light => Color::from_rgb8(248, 248, 248),  // Very light gray
-        Theme::Dark => Color::from_rgb8(30, 30, 30),       // Dark gray
-        _ => Color::from_rgb8(40, 40, 40),                 // Default dark
-    };
+    #[test]
+    fn test_svg_tint_theme_text() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, [svg_id]) = column_with([WidgetType::Svg]);
+        hierarchy.apply_property_change(svg_id, PropertyChange::SvgTint(SvgTint::ThemeText), &type_system);
 
-    let border_color = match theme {
-        Theme::Light => Color::from_rgb8(200, 200, 200),   // Light gray border
-        Theme::Dark => Color::from_rgb8(60, 60, 60),        // Dark gray border
-        _ => Color::from_rgb8(80, 80, 80),
-    };
-    
-    // Build the content as a column of rows
-    let content = column(
-        lines.into_iter().map(|line| {
-            if line.is_empty() {
-                row![text(" ").size(14).font(iced::Font::MONOSPACE)].into()
-            } else {
-                row(
-                    line.into_iter().map(|token| {
-                        text(token.text)
-                            .size(14)
-                            .font(iced::Font::MONOSPACE)
-                            .color(token.token_type.color_for_theme(&theme))
-                            .into()
-                    }).collect::<Vec<Element<'a, crate::widget_helper::Message>>>()
-                ).into()
-            }
-        }).collect::<Vec<Element<'a, crate::widget_helper::Message>>>()
-    )
-    .spacing(2);
-    
-    container(
-        scrollable(
-            container(content)
-                .width(Length::Fill)
-                .padding(15)
-                .style(move |_| container::Style {
-                    background: Some(Background::Color(bg_color)),
-                    border: Border {
-                        color: border_color,
-                        width: 1.0,
-                        radius: 4.0.into(),
-                    },
-                    ..Default::default()
-                })
-        )
-        .width(Length::Fill)
-        .height(
-            if height == 0.0 {
-                Length::Fill
-            }
-            else {
-                Length::Fixed(height)
-            }
-        )
-    )
-    .width(Length::Fill)
-    .height(Length::Fill)
-    .into()
-}
+        let code = generate(&hierarchy, &type_system);
+        assert!(code.contains(".style(|theme: &Theme, _status| svg::Style {"));
+        assert!(code.contains("color: Some(theme.palette().text),"));
+    }
 
-/// Build a syntax-highlighted code view
-pub fn build_code_view<'a>(tokens: &[Token], theme: Theme) -> Element<'a, crate::widget_helper::Message> {
-    build_code_view_with_height(tokens, 300.0, theme)
-}
+    #[test]
+    fn test_svg_tint_custom() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, [svg_id]) = column_with([WidgetType::Svg]);
+        hierarchy.apply_property_change(svg_id, PropertyChange::SvgTint(SvgTint::Custom), &type_system);
+        hierarchy.apply_property_change(svg_id, PropertyChange::SvgTintColor(Color::from_rgb(1.0, 0.0, 0.0)), &type_system);
+
+        let code = generate(&hierarchy, &type_system);
+        assert!(code.contains(".style(|_theme: &Theme, _status| svg::Style {"));
+        assert!(code.contains("color: Some(Color::from_rgba(1.000, 0.000, 0.000, 1.000)),"));
+        assert!(code.contains("Color,"));
+    }
 
-/// Build a syntax-highlighted code view - generic so I can use it outside of widget_helper::Messages
-pub fn build_code_view_with_height_generic<'a, Message: 'a>(
-    tokens: &[Token], 
-    height: f32,
-    theme: Theme
-) -> Element<'a, Message> {
-    // Group tokens by lines
-    let mut lines: Vec<Vec<Token>> = vec![vec![]];
-    
-    for token in tokens {
-        if token.text.contains('\n') {
-            let parts: Vec<&str> = token.text.split('\n').collect();
-            for (i, part) in parts.iter().enumerate() {
-                if !part.is_empty() {
-                    lines.last_mut().unwrap().push(Token {
-                        text: part.to_string(),
-                        token_type: token.token_type,
-                    });
-                }
-                if i < parts.len() - 1 {
-                    lines.push(vec![]);
-                }
-            }
-        } else {
-            lines.last_mut().unwrap().push(token.clone());
-        }
+    #[test]
+    fn test_radio_spacing_emitted_when_changed() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, [radio_id]) = column_with([WidgetType::Radio]);
+
+        let code = generate(&hierarchy, &type_system);
+        assert!(!code.contains(".spacing(12)"));
+
+        hierarchy.apply_property_change(radio_id, PropertyChange::RadioSpacing(12.0), &type_system);
+        let code = generate(&hierarchy, &type_system);
+        assert_eq!(code.matches(".spacing(12)").count(), 2);
     }
 
-    let bg_color = match theme {
-        Theme::Light => Color::from_rgb8(248, 248, 248),
-        Theme::Dark => Color::from_rgb8(30, 30, 30),
-        _ => Color::from_rgb8(40, 40, 40),
-    };
+    #[test]
+    fn test_radio_orientation_and_option_spacing() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, [radio_id]) = column_with([WidgetType::Radio]);
+
+        // The parent column and the group
+        let code = generate(&hierarchy, &type_system);
+        assert_eq!(code.matches("column![").count(), 2, "{}", code);
+        assert!(!code.contains("row!["), "{}", code);
+
+        hierarchy.apply_property_change(radio_id, PropertyChange::Orientation(Orientation::Horizontal), &type_system);
+        hierarchy.apply_property_change(radio_id, PropertyChange::Spacing(14.0), &type_system);
+        let code = generate(&hierarchy, &type_system);
+        assert_eq!(code.matches("column![").count(), 1, "{}", code);
+        assert!(code.contains("row!["), "{}", code);
+        assert_eq!(code.matches(".spacing(14)").count(), 1, "{}", code);
+        assert_parses(&code);
+    }
 
-    let border_color = match theme {
-        Theme::Light => Color::from_rgb8(200, 200, 200),
-        Theme::Dark => Color::from_rgb8(60, 60, 60),
-        _ => Color::from_rgb8(80, 80, 80),
-    };
-    
-    let content = column(
-        lines.into_iter().map(|line| {
-            if line.is_empty() {
-                row![text(" ").size(14).font(iced::Font::MONOSPACE)].into()
-            } else {
-                row(
-                    line.into_iter().map(|token| {
-                        text(token.text)
-                            .size(14)
-                            .font(iced::Font::MONOSPACE)
-                            .color(token.token_type.color_for_theme(&theme))
-                            .into()
-                    }).collect::<Vec<Element<'a, Message>>>()
-                ).into()
-            }
-        }).collect::<Vec<Element<'a, Message>>>()
-    )
-    .spacing(2);
-    
-    container(
-        scrollable(
-            container(content)
-                .width(Length::Fill)
-                .padding(15)
-                .style(move |_| container::Style {
-                    background: Some(Background::Color(bg_color)),
-                    border: Border {
-                        color: border_color,
-                        width: 1.0,
-                        radius: 4.0.into(),
-                    },
-                    ..Default::default()
-                })
-        )
-        .width(Length::Fill)
-        .height(
-            if height == 0.0 {
-                Length::Fill
-            } else {
-                Length::Fixed(height)
-            }
-        )
-    )
-    .width(Length::Fill)
-    .height(Length::Fill)
-    .into()
-}
+    #[test]
+    fn test_radio_reordered_options() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, [radio_id]) = column_with([WidgetType::Radio]);
 
+        hierarchy.apply_property_change(radio_id, PropertyChange::RadioSelectedIndex(0), &type_system);
+        hierarchy.apply_property_change(
+            radio_id,
+            PropertyChange::RadioOptionsEdit(OptionListEdit::MoveDown(0)),
+            &type_system,
+        );
 
+        let props = &hierarchy.get_widget_by_id(radio_id).unwrap().properties;
+        assert_eq!(props.radio_options, vec!["Radio Option 2".to_string(), "Radio Option 1".to_string()]);
+        assert_eq!(props.radio_selected_index, 1);
 
-/// StyleFn Generators
+        let code = generate(&hierarchy, &type_system);
+        let first = code.find("\"Radio Option 2\"").unwrap();
+        let second = code.find("\"Radio Option 1\"").unwrap();
+        assert!(first < second);
+        assert!(code.contains("radio_selected: 1,"));
+    }
 
-/// Generate tokens for container style code
-pub fn generate_container_style_tokens(
-    text_color: Color,
-    background_color: Color,
-    border_color: Color,
-    border_width: f32,
-    border_radius_top_left: f32,
-    border_radius_top_right: f32,
-    border_radius_bottom_right: f32,
-    border_radius_bottom_left: f32,
-    shadow_enabled: bool,
-    shadow_color: Color,
-    shadow_offset_x: f32,
-    shadow_offset_y: f32,
-    shadow_blur_radius: f32,
-    snap: bool,
-) -> Vec<Token> {
-    let mut builder = TokenBuilder::new();
+    /// Fails with the parse error and the code when `code` isn't a valid Rust file
+    fn assert_parses(code: &str) {
+        if let Err(e) = syn::parse_file(code) {
+            panic!("generated code doesn't parse: {}\n{}", e, code);
+        }
+    }
 
-    builder.add_plain("container");
-    builder.add_operator("::");
-    builder.add_type("Style");
-    builder.add_space();
-    builder.add_plain("{");
-    builder.add_newline();
-    builder.increase_indent();
+    #[test]
+    fn test_source_is_what_the_tokens_spell() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let type_system = TypeSystem::new();
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        let tokens = generator.generate_app_code();
+        assert_eq!(generator.generate_app_source(), source_of(&tokens));
+
+        let button_id = hierarchy.root().children[0].children[0].id;
+        let tokens = generator.generate_widget_code(button_id);
+        assert_eq!(generator.generate_widget_source(button_id), source_of(&tokens));
+    }
 
-    // text_color field
-    builder.add_field("text_color", |b| {
-        b.add_plain("Some(");
-        b.add_color(text_color);
-        b.add_plain(")");
-    });
+    #[test]
+    fn test_generated_app_parses() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        for widget_type in [WidgetType::Text, WidgetType::Button, WidgetType::TextInput, WidgetType::Checkbox] {
+            hierarchy.add_child(row_id, widget_type).unwrap();
+        }
+        for widget_type in [WidgetType::Slider, WidgetType::Toggler, WidgetType::PickList, WidgetType::Radio] {
+            hierarchy.add_child(column_id, widget_type).unwrap();
+        }
+        let type_system = TypeSystem::new();
+        for style in StubStyle::ALL {
+            let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+            generator.set_stub_style(style);
+            assert_parses(&generator.generate_app_source());
+        }
+    }
 
-    // background field
-    builder.add_field("background", |b| {
-        b.add_plain("Some(");
-        b.add_type("Background");
-        b.add_operator("::");
-        b.add_type("Color");
-        b.add_plain("(");
-        b.add_color(background_color);
-        b.add_plain("))");
-    });
+    #[test]
+    fn test_split_view_parses_and_calls_every_helper() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let toolbar_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        hierarchy.apply_property_change(toolbar_id, PropertyChange::WidgetName("toolbar".to_string()), &type_system);
+        let save_id = hierarchy.add_child(toolbar_id, WidgetType::Button).unwrap();
+        hierarchy.apply_property_change(save_id, PropertyChange::WidgetName("save".to_string()), &type_system);
+        let form_id = hierarchy.add_child(column_id, WidgetType::Column).unwrap();
+        for widget_type in [WidgetType::Text, WidgetType::TextInput, WidgetType::Checkbox, WidgetType::Slider, WidgetType::Toggler] {
+            hierarchy.add_child(form_id, widget_type).unwrap();
+        }
 
-    // border field
-    builder.add_field("border", |b| {
-        b.add_struct("Border", |b| {
-            b.add_field("color", |b| b.add_color(border_color));
-            b.add_field("width", |b| b.add_number(&format!("{:.1}", border_width)));
-            b.add_field("radius", |b| {
-                b.add_struct("Radius", |b| {
-                    b.add_field("top_left", |b| b.add_number(&format!("{:.1}", border_radius_top_left)));
-                    b.add_field("top_right", |b| b.add_number(&format!("{:.1}", border_radius_top_right)));
-                    b.add_field("bottom_right", |b| b.add_number(&format!("{:.1}", border_radius_bottom_right)));
-                    b.add_field("bottom_left", |b| b.add_number(&format!("{:.1}", border_radius_bottom_left)));
-                });
-            });
-        });
-    });
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        assert!(!generator.generate_app_source().contains("_view(&self)"));
+
+        generator.set_view_helpers(Some(4));
+        let code = generator.generate_app_source();
+        assert_parses(&code);
+
+        let helpers: Vec<&str> = code
+            .split("fn ")
+            .skip(1)
+            .filter_map(|rest| rest.split_once("(&self) -> Element").map(|(name, _)| name))
+            .filter(|name| name.ends_with("_view"))
+            .collect();
+        // The named row, the button named inside it, and the column of five
+        assert_eq!(helpers.len(), 3, "{}", code);
+        assert!(helpers.contains(&"toolbar_view") && helpers.contains(&"save_view"), "{}", code);
+        for helper in helpers {
+            assert!(code.contains(&format!("self.{}()", helper)), "{} is never called\n{}", helper, code);
+        }
+    }
 
-    // shadow field
-    builder.add_field("shadow", |b| {
-        if shadow_enabled {
-            b.add_struct("Shadow", |b| {
-                b.add_field("color", |b| b.add_color(shadow_color));
-                b.add_field("offset", |b| {
-                    b.add_struct("Vector", |b| {
-                        b.add_field("x", |b| b.add_number(&format!("{:.1}", shadow_offset_x)));
-                        b.add_field("y", |b| b.add_number(&format!("{:.1}", shadow_offset_y)));
-                    });
-                });
-                b.add_field("blur_radius", |b| b.add_number(&format!("{:.1}", shadow_blur_radius)));
-            });
-        } else {
-            b.add_type("Shadow");
-            b.add_operator("::");
-            b.add_function("default");
-            b.add_plain("()");
+    #[test]
+    fn test_duplicate_unnamed_widgets_agree_on_names() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        for widget_type in [WidgetType::Slider, WidgetType::Button, WidgetType::Slider, WidgetType::Button, WidgetType::Slider] {
+            hierarchy.add_child(WidgetId(0), widget_type).unwrap();
         }
-    });
+        let type_system = TypeSystem::new();
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        let code = generator.generate_app_source();
+        assert_parses(&code);
+        assert!(generator.wiring().problems().is_empty(), "{:?}\n{}", generator.wiring().problems(), code);
+
+        for (field, variant) in [("slider", "Slider"), ("slider_2", "Slider2"), ("slider_3", "Slider3")] {
+            assert!(code.contains(&format!("{}Changed(f32)", variant)), "{}", code);
+            assert!(code.contains(&format!("{}_value: f32,", field)), "{}", code);
+            assert!(code.contains(&format!("{}_value: 50.0,", field)), "{}", code);
+            assert!(code.contains(&format!("self.{}_value, Message::{}Changed", field, variant)), "{}", code);
+            assert!(code.contains(&format!("Message::{}Changed(value) => {{", variant)), "{}", code);
+            assert!(code.contains(&format!("self.{}_value = value;", field)), "{}", code);
+        }
+        assert!(!code.contains("slider_4"), "{}", code);
+        for variant in ["ButtonPressed", "Button2Pressed"] {
+            assert!(code.contains(&format!("{},", variant)), "{}", code);
+            assert!(code.contains(&format!("on_press(Message::{})", variant)), "{}", code);
+            assert!(code.contains(&format!("Message::{} => {{", variant)), "{}", code);
+        }
+        assert!(!code.contains("Button3"), "{}", code);
+    }
+
+    /// Whether `expected` appears as consecutive lines of `code`, ignoring indentation
+    fn has_lines(code: &str, expected: &[&str]) -> bool {
+        let lines: Vec<&str> = code.lines().map(str::trim).collect();
+        lines.windows(expected.len()).any(|window| window == expected)
+    }
+
+    #[test]
+    fn test_metrics_count_the_generated_code() {
+        let (mut hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let column_id = hierarchy.root().children[0].id;
+        hierarchy.add_child(column_id, WidgetType::ComboBox).unwrap();
+        let type_system = TypeSystem::new();
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        let code = generator.generate_app_source();
+        let metrics = generator.metrics();
+
+        // ButtonPressed, CheckboxToggled and ComboBoxSelected; the checked flag plus the combo box value and state
+        assert_eq!(metrics.message_variants, 3);
+        assert_eq!(metrics.state_fields, 3);
+        assert_eq!(metrics.lines, code.lines().count());
+        let deepest = code.lines().map(|line| (line.len() - line.trim_start().len()) / 4).max().unwrap();
+        assert_eq!(metrics.max_nesting, deepest);
+
+        generator.generate_layout_code();
+        assert_eq!(generator.metrics().message_variants, 1);
+        assert_eq!(generator.metrics().state_fields, 0);
+    }
+
+    #[test]
+    fn test_mouse_area_with_every_handler_parses() {
+        let (mut hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let column_id = hierarchy.root().children[0].id;
+        let mouse_area_id = hierarchy.add_child(column_id, WidgetType::MouseArea).unwrap();
+        hierarchy.add_child(mouse_area_id, WidgetType::Text).unwrap();
+        let props = &mut hierarchy.get_widget_by_id_mut(mouse_area_id).unwrap().properties;
+        props.mousearea_on_press = true;
+        props.mousearea_on_release = true;
+        props.mousearea_on_double_click = true;
+        props.mousearea_on_right_press = true;
+        props.mousearea_on_right_release = true;
+        props.mousearea_on_middle_press = true;
+        props.mousearea_on_middle_release = true;
+        props.mousearea_on_scroll = true;
+        props.mousearea_on_enter = true;
+        props.mousearea_on_move = true;
+        props.mousearea_on_exit = true;
+        let type_system = TypeSystem::new();
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        let code = generator.generate_app_source();
+
+        assert_parses(&code);
+        // iced's on_enter and on_exit take a message, not a closure over the cursor position
+        assert!(has_lines(&code, &["MouseareaEntered,"]), "{}", code);
+        assert!(has_lines(&code, &["MouseareaExited,"]), "{}", code);
+        assert!(code.contains(".on_enter(Message::MouseareaEntered)"), "{}", code);
+        assert!(code.contains(".on_exit(Message::MouseareaExited)"), "{}", code);
+        assert!(code.contains(".on_move(|point| Message::MouseareaMoved(point))"), "{}", code);
+        assert!(has_lines(&code, &["Message::MouseareaEntered => {"]), "{}", code);
+        assert!(has_lines(&code, &["Message::MouseareaExited => {"]), "{}", code);
+    }
+
+    #[test]
+    fn test_declared_variants_are_the_listed_ones() {
+        let (mut hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let column_id = hierarchy.root().children[0].id;
+        let mouse_area_id = hierarchy.add_child(column_id, WidgetType::MouseArea).unwrap();
+        hierarchy.add_child(mouse_area_id, WidgetType::Text).unwrap();
+        let type_system = TypeSystem::new();
+        hierarchy.apply_property_change(mouse_area_id, PropertyChange::WidgetName("picker".to_string()), &type_system);
+        let props = &mut hierarchy.get_widget_by_id_mut(mouse_area_id).unwrap().properties;
+        props.mousearea_on_press = true;
+        props.mousearea_on_scroll = true;
+        props.mousearea_on_move = true;
+        props.file_dialog = FileDialog::Open;
+
+        let widget = hierarchy.get_widget_by_id(mouse_area_id).unwrap();
+        assert_eq!(
+            message_variant_names(widget, "picker"),
+            ["PickerPressed", "PickerFileChosen", "PickerScrolled", "PickerMoved"]
+        );
+        let code = generate(&hierarchy, &type_system);
+        assert!(has_lines(&code, &[
+            "PickerPressed,",
+            "PickerFileChosen(Option<PathBuf>),",
+            "PickerScrolled(mouse::ScrollDelta),",
+            "PickerMoved(Point),",
+        ]), "{}", code);
+    }
+
+    #[test]
+    fn test_each_mouse_area_handler_calls_its_own_method() {
+        let handlers: [(fn(&mut Properties), &str); 11] = [
+            (|props| props.mousearea_on_press = true, ".on_press("),
+            (|props| props.mousearea_on_release = true, ".on_release("),
+            (|props| props.mousearea_on_double_click = true, ".on_double_click("),
+            (|props| props.mousearea_on_right_press = true, ".on_right_press("),
+            (|props| props.mousearea_on_right_release = true, ".on_right_release("),
+            (|props| props.mousearea_on_middle_press = true, ".on_middle_press("),
+            (|props| props.mousearea_on_middle_release = true, ".on_middle_release("),
+            (|props| props.mousearea_on_scroll = true, ".on_scroll("),
+            (|props| props.mousearea_on_enter = true, ".on_enter("),
+            (|props| props.mousearea_on_move = true, ".on_move("),
+            (|props| props.mousearea_on_exit = true, ".on_exit("),
+        ];
+        let methods: Vec<&str> = handlers.iter().map(|(_, method)| *method).collect();
+        let type_system = TypeSystem::new();
+        for (enable, method) in handlers {
+            let mut hierarchy = WidgetHierarchy::new(WidgetType::MouseArea);
+            hierarchy.add_child(WidgetId(0), WidgetType::Text).unwrap();
+            enable(&mut hierarchy.get_widget_by_id_mut(WidgetId(0)).unwrap().properties);
+            let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+            let code = generator.generate_widget_source(WidgetId(0));
+
+            assert_eq!(code.matches(method).count(), 1, "{}\n{}", method, code);
+            for other in methods.iter().filter(|other| **other != method) {
+                assert!(!code.contains(other), "{} also emitted {}\n{}", method, other, code);
+            }
+        }
+    }
+
+    #[test]
+    fn test_raw_string_literal_outlasts_its_text() {
+        assert_eq!(raw_string_literal("plain"), "r\"plain\"");
+        assert_eq!(raw_string_literal("say \"hi\"\\n"), "r#\"say \"hi\"\\n\"#");
+        assert_eq!(raw_string_literal("a \"# b \"## c"), "r###\"a \"# b \"## c\"###");
+        assert_eq!(raw_string_literal("line\nbreak"), "r\"line\nbreak\"");
+    }
+
+    #[test]
+    fn test_markdown_keeps_its_parsed_content_in_state() {
+        let (mut hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let column_id = hierarchy.root().children[0].id;
+        let markdown_id = hierarchy.add_child(column_id, WidgetType::Markdown).unwrap();
+        let source = "# Notes\n\nA \"quoted\" path: C:\\temp and a \"#tag\n\n[Docs](https://iced.rs)";
+        hierarchy.get_widget_by_id_mut(markdown_id).unwrap().properties.markdown_source =
+            iced::widget::text_editor::Content::with_text(source);
+        let type_system = TypeSystem::new();
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        let code = generator.generate_app_source();
+
+        assert_parses(&code);
+        // `"#` in the text needs two hashes
+        assert!(code.contains("markdown_content: markdown::Content::parse(r##\"# Notes\n"), "{}", code);
+        assert!(code.contains("C:\\temp and a \"#tag"), "{}", code);
+        assert!(has_lines(&code, &["markdown_content: markdown::Content,"]), "{}", code);
+        assert!(has_lines(&code, &["MarkdownLinkClicked(markdown::Url),"]), "{}", code);
+        assert!(has_lines(&code, &["Message::MarkdownLinkClicked(url) => {"]), "{}", code);
+        assert!(code.contains("markdown::view(self.markdown_content.items(), markdown::Settings::with_text_size(16.0, self.theme()))"), "{}", code);
+        assert!(code.contains(".map(Message::MarkdownLinkClicked)"), "{}", code);
+
+        let code = generate_with(&hierarchy, |generator| generator.set_multi_window(true));
+        assert!(code.contains("markdown::Settings::with_text_size(16.0, self.theme(window::Id::unique())))"), "{}", code);
+    }
+
+    #[test]
+    fn test_wiring_detects_a_desynced_pass() {
+        let (mut hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let column_id = hierarchy.root().children[0].id;
+        let mouse_area_id = hierarchy.add_child(column_id, WidgetType::MouseArea).unwrap();
+        hierarchy.get_widget_by_id_mut(mouse_area_id).unwrap().properties.mousearea_on_enter = true;
+        let type_system = TypeSystem::new();
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        generator.generate_app_code();
+        assert!(generator.wiring().problems().is_empty());
+
+        // As if the view had dropped the on_enter call while the other passes kept the message
+        let mut wiring = generator.wiring().clone();
+        wiring.forget(WiringPass::Sent, "MouseareaEntered");
+        assert_eq!(wiring.problems(), [WiringProblem::NeverSent("MouseareaEntered".to_string())]);
+
+        let mut wiring = generator.wiring().clone();
+        wiring.record(WiringPass::Declared, "ButtonPressed");
+        assert_eq!(wiring.problems(), [WiringProblem::Duplicated("ButtonPressed".to_string())]);
+    }
+
+    #[test]
+    fn test_comment_stubs_are_default() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let type_system = TypeSystem::new();
+        let code = generate(&hierarchy, &type_system);
+        assert_eq!(code, generate_with(&hierarchy, |generator| generator.set_stub_style(StubStyle::Comment)));
+        assert!(has_lines(&code, &["Message::ButtonPressed => {", "// Handle button press", "}"]), "{}", code);
+        assert!(!code.contains("todo!"));
+    }
 
-    // snap field
-    builder.add_field("snap", |b| {
-        b.add_keyword(if snap { "true" } else { "false" });
-    });
+    #[test]
+    fn test_todo_stubs_golden() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate_with(&hierarchy, |generator| generator.set_stub_style(StubStyle::Todo));
+        assert!(has_lines(&code, &["Message::ButtonPressed => {", "todo!(\"handle button press\");", "}"]), "{}", code);
+        assert!(has_lines(&code, &["Message::CheckboxToggled(checked) => {", "self.checkbox_checked = checked;", "}"]), "{}", code);
+    }
 
-    builder.decrease_indent();
-    builder.add_plain("}");
+    #[test]
+    fn test_log_stubs_golden() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate_with(&hierarchy, |generator| generator.set_stub_style(StubStyle::Log));
+        assert!(has_lines(&code, &["Message::ButtonPressed => {", "println!(\"button press\");", "}"]), "{}", code);
+        assert!(has_lines(&code, &[
+            "Message::CheckboxToggled(checked) => {",
+            "self.checkbox_checked = checked;",
+            "println!(\"checkbox toggled: {:?}\", self.checkbox_checked);",
+            "}",
+        ]), "{}", code);
+    }
 
-    builder.into_tokens()
-}
+    #[test]
+    fn test_derive_default_golden() {
+        // An unchecked checkbox starts at bool's default, so the derive covers it
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate_with(&hierarchy, |generator| generator.set_derive_default(true));
+        assert!(has_lines(&code, &["// Application state", "#[derive(Default)]", "struct App {", "checkbox_checked: bool,", "}"]), "{}", code);
+        assert!(has_lines(&code, &["fn new() -> (Self, iced::Task<Message>) {", "(Self::default(), iced::Task::none())", "}"]), "{}", code);
+        assert!(!code.contains("impl Default for"), "{}", code);
+    }
 
-struct ImportTracker {
-    used_widgets: HashSet<&'static str>,
-    
-    uses_length: bool,
-    uses_alignment: bool,
-    uses_padding: bool,
-    uses_color: bool,
-    
-    // Text properties
-    uses_text_line_height: bool,
-    uses_text_wrapping: bool,
-    uses_text_shaping: bool,
-    uses_text_alignment: bool,
-    
-    // Mouse
-    uses_mouse: bool,
-    uses_mouse_interaction: bool,
-    uses_mouse_scroll_delta: bool,
-    
-    // Other
-    uses_point: bool,
-    uses_font: bool,
-    uses_border: bool,
-    uses_shadow: bool,
-    uses_background: bool,
-    uses_vector: bool,
-}
+    #[test]
+    fn test_derive_default_writes_impl_for_custom_state() {
+        let (hierarchy, _) = column_with([WidgetType::Slider]);
+
+        let code = generate_with(&hierarchy, |generator| generator.set_derive_default(true));
+        assert!(has_lines(&code, &[
+            "impl Default for App {",
+            "fn default() -> Self {",
+            "Self {",
+            "slider_value: 50.0,",
+            "}",
+            "}",
+            "}",
+        ]), "{}", code);
+        assert!(has_lines(&code, &["fn new() -> (Self, iced::Task<Message>) {", "(Self::default(), iced::Task::none())", "}"]), "{}", code);
+        assert!(!code.contains("#[derive(Default)]"), "{}", code);
+    }
 
-impl ImportTracker {
-    fn new() -> Self {
-        Self {
-            used_widgets: HashSet::new(),
-            uses_length: false,
-            uses_alignment: false,
-            uses_padding: false,
-            uses_color: false,
-            uses_text_line_height: false,
-            uses_text_wrapping: false,
-            uses_text_shaping: false,
-            uses_text_alignment: false,
-            uses_mouse: false,
-            uses_mouse_interaction: false,
-            uses_mouse_scroll_delta: false,
-            uses_point: false,
-            uses_font: false,
-            uses_border: false,
-            uses_shadow: false,
-            uses_background: false,
-            uses_vector: false,
+    #[test]
+    fn test_derive_default_is_off_by_default() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate(&hierarchy, &TypeSystem::new());
+        assert!(!code.contains("#[derive(Default)]"));
+        assert!(!code.contains("Self::default()"));
+    }
+
+    #[test]
+    fn test_multi_window_golden() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate_with(&hierarchy, |generator| generator.set_multi_window(true));
+        assert!(code.contains("Task,Subscription,"), "{}", code);
+        assert!(code.contains("\n    window,\n"), "{}", code);
+        assert!(has_lines(&code, &["};", "use std::collections::BTreeMap;"]), "{}", code);
+        assert!(has_lines(&code, &[
+            "WindowOpened(window::Id, WindowEnum),",
+            "WindowClosed(window::Id),",
+            "RequestOpenWindow(WindowEnum),",
+            "}",
+        ]), "{}", code);
+        assert!(has_lines(&code, &["struct App {", "windows: BTreeMap<window::Id, Window>,", "checkbox_checked: bool,", "}"]), "{}", code);
+        assert!(has_lines(&code, &["enum WindowEnum {", "Main,", "}"]), "{}", code);
+        assert!(has_lines(&code, &["struct Window {", "title: String,", "windowtype: WindowEnum,", "}"]), "{}", code);
+        assert!(has_lines(&code, &["Self {", "windows: BTreeMap::new(),"]), "{}", code);
+        assert!(has_lines(&code, &["},", "iced::Task::done(Message::RequestOpenWindow(WindowEnum::Main))", ")"]), "{}", code);
+        assert!(has_lines(&code, &[
+            "fn title(&self, window_id: window::Id) -> String {",
+            "self.windows.get(&window_id).map(|window| window.title.clone()).unwrap_or_default()",
+            "}",
+        ]), "{}", code);
+        assert!(code.contains("fn theme(&self, _window_id: window::Id) -> Theme {"), "{}", code);
+        assert!(has_lines(&code, &[
+            "Message::RequestOpenWindow(window_type) => {",
+            "let (_id, open) = window::open(window::Settings::default());",
+            "return open.map(move |id| Message::WindowOpened(id, window_type));",
+            "}",
+            "Message::WindowOpened(window_id, window_type) => {",
+            "let title = match window_type {",
+            "WindowEnum::Main => String::from(\"App Window\"),",
+            "};",
+            "self.windows.insert(window_id, Window { title, windowtype: window_type });",
+            "}",
+            "Message::WindowClosed(window_id) => {",
+            "self.windows.remove(&window_id);",
+            "if self.windows.is_empty() {",
+            "return iced::exit();",
+            "}",
+            "}",
+            "}",
+            "Task::none()",
+            "}",
+        ]), "{}", code);
+        assert!(code.contains("fn update(&mut self, message: Message) -> Task<Message> {"), "{}", code);
+        assert!(has_lines(&code, &[
+            "fn subscription(&self) -> Subscription<Message> {",
+            "window::close_events().map(Message::WindowClosed)",
+            "}",
+        ]), "{}", code);
+        assert!(has_lines(&code, &[
+            "fn view<'a>(&'a self, window_id: window::Id) -> Element<'a, Message> {",
+            "match self.windows.get(&window_id).map(|window| window.windowtype) {",
+            "Some(WindowEnum::Main) => self.view_main(),",
+            "None => iced::widget::text(format!(\"Something has gone terribly wrong. Window Id: {:?}\", window_id)).into(),",
+            "}",
+            "}",
+        ]), "{}", code);
+        assert!(code.contains("fn view_main<'a>(&'a self) -> Element<'a, Message> {"), "{}", code);
+        assert!(has_lines(&code, &[
+            "iced::daemon(App::new, App::update, App::view)",
+            ".theme(App::theme)",
+            ".title(App::title)",
+            ".subscription(App::subscription)",
+            ".run()",
+        ]), "{}", code);
+    }
+
+    #[test]
+    fn test_multi_window_with_derived_default_opens_main_window() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate_with(&hierarchy, |generator| {
+            generator.set_multi_window(true);
+            generator.set_derive_default(true);
+        });
+        assert!(has_lines(&code, &["#[derive(Default)]", "struct App {", "windows: BTreeMap<window::Id, Window>,"]), "{}", code);
+        assert!(code.contains("(Self::default(), iced::Task::done(Message::RequestOpenWindow(WindowEnum::Main)))"), "{}", code);
+    }
+
+    /// Button "open" opening `dialog`, with its chosen path shown in Text "path_label"
+    fn file_dialog_fixture(dialog: FileDialog) -> WidgetHierarchy {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, [button_id, text_id]) = column_with([WidgetType::Button, WidgetType::Text]);
+        for (id, name) in [(button_id, "open"), (text_id, "path_label")] {
+            hierarchy.apply_property_change(id, PropertyChange::WidgetName(name.to_string()), &type_system);
         }
+        hierarchy.apply_property_change(text_id, PropertyChange::TextContent("No file".to_string()), &type_system);
+        hierarchy.apply_property_change(button_id, PropertyChange::FileDialog(dialog), &type_system);
+        hierarchy.apply_property_change(button_id, PropertyChange::FileDialogPathText(Some(text_id)), &type_system);
+        hierarchy
     }
-    
-    fn scan_widget(&mut self, widget: &Widget) {
-        let props = &widget.properties;
-        
-        // Track widget type
-        match widget.widget_type {
-            WidgetType::Container => { self.used_widgets.insert("container"); }
-            WidgetType::Row => { self.used_widgets.insert("row"); }
-            WidgetType::Column => { self.used_widgets.insert("column"); }
-            WidgetType::Button => { self.used_widgets.insert("button"); }
-            WidgetType::Text => { self.used_widgets.insert("text"); }
-            WidgetType::TextInput => { self.used_widgets.insert("text_input"); }
-            WidgetType::Checkbox => { self.used_widgets.insert("checkbox"); }
-            WidgetType::Radio => { self.used_widgets.insert("radio"); }
-            WidgetType::Slider => { self.used_widgets.insert("slider"); }
-            WidgetType::VerticalSlider => { self.used_widgets.insert("vertical_slider"); }
-            WidgetType::ProgressBar => { self.used_widgets.insert("progress_bar"); }
-            WidgetType::Toggler => { self.used_widgets.insert("toggler"); }
-            WidgetType::PickList => { self.used_widgets.insert("pick_list"); }
-            WidgetType::Scrollable => { self.used_widgets.insert("scrollable"); }
-            WidgetType::Space => { self.used_widgets.insert("space"); }
-            WidgetType::Rule => { self.used_widgets.insert("rule"); }
-            WidgetType::Image => { self.used_widgets.insert("image"); }
-            WidgetType::Svg => { self.used_widgets.insert("svg"); }
-            WidgetType::Tooltip => { self.used_widgets.insert("tooltip"); }
-            WidgetType::ComboBox => { self.used_widgets.insert("combo_box"); }
-            WidgetType::Markdown => { self.used_widgets.insert("markdown"); }
-            WidgetType::MouseArea => { 
-                self.used_widgets.insert("mouse_area");
-                self.uses_mouse = true;
-            }
-            WidgetType::QRCode => { self.used_widgets.insert("qr_code"); }
-            WidgetType::Stack => { self.used_widgets.insert("stack"); }
-            WidgetType::Themer => { self.used_widgets.insert("themer"); }
-            WidgetType::Pin => { self.used_widgets.insert("pin"); }
+
+    #[test]
+    fn test_open_file_dialog_golden() {
+        let code = generate(&file_dialog_fixture(FileDialog::Open), &TypeSystem::new());
+        assert!(has_lines(&code, &["};", "use std::path::PathBuf;"]), "{}", code);
+        assert!(has_lines(&code, &["OpenPressed,", "OpenFileChosen(Option<PathBuf>),"]), "{}", code);
+        assert!(has_lines(&code, &["struct App {", "open_path: Option<PathBuf>,", "}"]), "{}", code);
+        assert!(has_lines(&code, &["Self {", "open_path: None,", "}"]), "{}", code);
+        assert!(has_lines(&code, &[
+            "// Returns a Task so the file dialog buttons can run their dialogs",
+            "fn update(&mut self, message: Message) -> Task<Message> {",
+        ]), "{}", code);
+        assert!(has_lines(&code, &[
+            "Message::OpenPressed => {",
+            "return Task::perform(",
+            "async {",
+            "rfd::AsyncFileDialog::new()",
+            ".pick_file()",
+            ".await",
+            ".map(|file| file.path().to_path_buf())",
+            "},",
+            "Message::OpenFileChosen,",
+            ");",
+            "}",
+            "Message::OpenFileChosen(path) => {",
+            "self.open_path = path;",
+            "}",
+            "}",
+            "Task::none()",
+            "}",
+        ]), "{}", code);
+        assert!(code.contains("text(self.open_path.as_ref().map_or(String::from(\"No file\"), |path| path.display().to_string()))"), "{}", code);
+    }
+
+    #[test]
+    fn test_save_dialog_and_plain_buttons() {
+        let code = generate(&file_dialog_fixture(FileDialog::Save), &TypeSystem::new());
+        assert!(code.contains(".save_file()"), "{}", code);
+
+        // Without a capability the update keeps its plain signature and the Text stays static
+        let code = generate(&file_dialog_fixture(FileDialog::None), &TypeSystem::new());
+        assert!(code.contains("fn update(&mut self, message: Message) {"), "{}", code);
+        assert!(!code.contains("PathBuf") && !code.contains("rfd"), "{}", code);
+        assert!(code.contains("text(\"No file\")"), "{}", code);
+    }
+
+    /// Checkbox "invalid", TextInput "email" following it, and Container "card" following flag "is error"
+    fn conditional_style_fixture() -> (WidgetHierarchy, WidgetId) {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let checkbox_id = hierarchy.add_child(column_id, WidgetType::Checkbox).unwrap();
+        let input_id = hierarchy.add_child(column_id, WidgetType::TextInput).unwrap();
+        let card_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        for (id, name) in [(checkbox_id, "invalid"), (input_id, "email"), (card_id, "card")] {
+            hierarchy.apply_property_change(id, PropertyChange::WidgetName(name.to_string()), &type_system);
         }
-        
-        // Track if any Length is used (always true if widget exists)
-        self.uses_length = true;
-        
-        // Track Alignment if used in Row/Column
-        if matches!(widget.widget_type, WidgetType::Row | WidgetType::Column) {
-            if props.align_items != Alignment::Start {
-                self.uses_alignment = true;
-            }
+        hierarchy.apply_property_change(input_id, PropertyChange::StyleCondition(Some(StyleCondition::Widget(checkbox_id))), &type_system);
+        hierarchy.apply_property_change(card_id, PropertyChange::StyleCondition(Some(StyleCondition::Flag("is error".to_string()))), &type_system);
+        (hierarchy, checkbox_id)
+    }
+
+    #[test]
+    fn test_conditional_style_golden() {
+        let (hierarchy, _) = conditional_style_fixture();
+        let code = generate(&hierarchy, &TypeSystem::new());
+        assert!(has_lines(&code, &["// Conditional style flags, set them from update()", "is_error: bool,", "}"]), "{}", code);
+        assert!(has_lines(&code, &["let invalid_checked = self.invalid_checked;", "let is_error = self.is_error;"]), "{}", code);
+        assert!(has_lines(&code, &[
+            ".style(move |theme: &Theme, status| {",
+            "let style = text_input::default(theme, status);",
+            "if invalid_checked {",
+            "text_input::Style {",
+            "border: Border { color: Color::from_rgba(0.900, 0.200, 0.200, 1.000), ..style.border },",
+            "..style",
+            "}",
+            "} else {",
+            "style",
+            "}",
+        ]), "{}", code);
+        assert!(has_lines(&code, &[
+            ".style(move |_| if is_error {",
+            "container::Style {",
+            "border: Border {",
+            "color: Color::from_rgba(0.900, 0.200, 0.200, 1.000),",
+            "width: 1.0,",
+            "radius: 5.0.into(),",
+            "},",
+            "..container::Style::default()",
+            "}",
+            "} else {",
+            "container::Style {",
+            "border: Border {",
+            "color: Color::from_rgba(0.500, 0.500, 0.500, 1.000),",
+        ]), "{}", code);
+    }
+
+    #[test]
+    fn test_conditional_style_drops_with_its_source() {
+        let (mut hierarchy, checkbox_id) = conditional_style_fixture();
+        hierarchy.delete_widget(checkbox_id).unwrap();
+        let code = generate(&hierarchy, &TypeSystem::new());
+        assert!(!code.contains("invalid_checked") && !code.contains("text_input::default"), "{}", code);
+        // The flag doesn't depend on another widget, so the Container keeps its style
+        assert!(code.contains("let is_error = self.is_error;"), "{}", code);
+    }
+
+    #[test]
+    fn test_single_window_is_default() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate(&hierarchy, &TypeSystem::new());
+        assert!(code.contains("iced::application(App::new, App::update, App::view)"), "{}", code);
+        assert!(code.contains("fn title(&self) -> String {"), "{}", code);
+        for scaffold in ["daemon", "WindowEnum", "BTreeMap", "window::Id", "subscription"] {
+            assert!(!code.contains(scaffold), "{}:\n{}", scaffold, code);
         }
-        
-        // Track Padding
-        if props.padding_mode == PaddingMode::Individual {
-            self.uses_padding = true;
+    }
+
+    /// Root > Column > Stack > [Text, Text], with the stack and its top layer
+    fn float_hierarchy(anchor: Option<FloatAnchor>) -> (WidgetHierarchy, WidgetId) {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let stack_id = hierarchy.add_child(column_id, WidgetType::Stack).unwrap();
+        hierarchy.add_child(stack_id, WidgetType::Text).unwrap();
+        let layer_id = hierarchy.add_child(stack_id, WidgetType::Text).unwrap();
+        hierarchy.apply_property_change(layer_id, PropertyChange::FloatAnchor(anchor), &type_system);
+        hierarchy.apply_property_change(layer_id, PropertyChange::FloatOffsetX(16.0), &type_system);
+        hierarchy.apply_property_change(layer_id, PropertyChange::FloatOffsetY(8.0), &type_system);
+        (hierarchy, stack_id)
+    }
+
+    #[test]
+    fn test_float_layer_wrapper_per_anchor() {
+        let cases = [
+            (FloatAnchor::TopLeft, vec![".padding(Padding { top: 8.0, right: 0.0, bottom: 0.0, left: 16.0 })"]),
+            (FloatAnchor::TopRight, vec![".align_x(Alignment::End)", ".padding(Padding { top: 8.0, right: 16.0, bottom: 0.0, left: 0.0 })"]),
+            (FloatAnchor::BottomLeft, vec![".align_y(Alignment::End)", ".padding(Padding { top: 0.0, right: 0.0, bottom: 8.0, left: 16.0 })"]),
+            (
+                FloatAnchor::BottomRight,
+                vec![".align_x(Alignment::End)", ".align_y(Alignment::End)", ".padding(Padding { top: 0.0, right: 16.0, bottom: 8.0, left: 0.0 })"],
+            ),
+            // No edge to measure the offsets from
+            (FloatAnchor::Center, vec![".align_x(Alignment::Center)", ".align_y(Alignment::Center)"]),
+        ];
+
+        for (anchor, calls) in cases {
+            let (hierarchy, stack_id) = float_hierarchy(Some(anchor));
+            let code = generate_widget(&hierarchy, stack_id, CodeStyle::Verbose);
+            let last = format!("{}.into()", calls.last().unwrap());
+            let mut expected = vec![")", ".width(Length::Fill)", ".height(Length::Fill)"];
+            expected.extend(&calls[..calls.len() - 1]);
+            expected.push(&last);
+            assert!(has_lines(&code, &expected), "{:?}:\n{}", anchor, code);
+            assert_eq!(code.matches("container(").count(), 1, "{:?}:\n{}", anchor, code);
         }
-        
-        // Track Container-specific features
-        if widget.widget_type == WidgetType::Container {
-            if props.border_width > 0.0 {
-                self.uses_border = true;
-            }
-            if props.background_color.a > 0.0 {
-                self.uses_background = true;
-                self.uses_color = true;
-            }
-            if props.has_shadow {
-                self.uses_shadow = true;
-                self.uses_vector = true;
-            }
-            if props.align_x != ContainerAlignX::Left || props.align_y != ContainerAlignY::Top {
-                self.uses_alignment = true;
-            }
+    }
+
+    #[test]
+    fn test_non_floating_layers_are_not_wrapped() {
+        let (hierarchy, stack_id) = float_hierarchy(None);
+        let code = generate_widget(&hierarchy, stack_id, CodeStyle::Verbose);
+        assert!(!code.contains("container("), "{}", code);
+        assert!(!code.contains("Padding"), "{}", code);
+    }
+
+    /// Root > Column > [Text "Intro", Text "Usage"], with both texts marked as sections
+    fn sections_fixture() -> WidgetHierarchy {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        for name in ["Intro", "Usage"] {
+            let id = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+            hierarchy.apply_property_change(id, PropertyChange::TextContent(name.to_string()), &type_system);
+            hierarchy.apply_property_change(id, PropertyChange::SectionAnchor(true), &type_system);
+            hierarchy.apply_property_change(id, PropertyChange::SectionName(name.to_string()), &type_system);
         }
-        
-        // Track Text properties
-        if widget.widget_type == WidgetType::Text {
-            if props.text_color.a > 0.0 {
-                self.uses_color = true;
-            }
-            if props.font != FontType::Default {
-                self.uses_font = true;
-            }
-            if props.line_height != text::LineHeight::default() {
-                self.uses_text_line_height = true;
-            }
-            if props.wrap != text::Wrapping::default() {
-                self.uses_text_wrapping = true;
-            }
-            if props.shaping != text::Shaping::default() {
-                self.uses_text_shaping = true;
-            }
-            if props.text_align_x != text::Alignment::default() || 
-               props.text_align_y != iced::alignment::Vertical::Top {
-                self.uses_text_alignment = true;
-                self.uses_alignment = true;
-            }
+        hierarchy
+    }
+
+    #[test]
+    fn test_section_nav_golden() {
+        let hierarchy = sections_fixture();
+        let code = generate(&hierarchy, &TypeSystem::new());
+        let offsets = sections(hierarchy.root());
+        assert!(has_lines(&code, &[
+            "// Section offsets, estimated from the builder's layout: adjust them if the real heights differ",
+            "const SECTIONS_SCROLLABLE: &str = \"sections\";",
+            &format!("const SECTION_INTRO: f32 = {:.1};", offsets[0].offset),
+            &format!("const SECTION_USAGE: f32 = {:.1};", offsets[1].offset),
+        ]), "{}", code);
+        assert!(has_lines(&code, &["ScrollToSection(f32),", "}"]), "{}", code);
+        assert!(has_lines(&code, &[
+            "// Returns a Task so the section buttons can scroll",
+            "fn update(&mut self, message: Message) -> Task<Message> {",
+        ]), "{}", code);
+        assert!(has_lines(&code, &[
+            "Message::ScrollToSection(offset) => {",
+            "return iced::widget::operation::scroll_to(SECTIONS_SCROLLABLE, scrollable::AbsoluteOffset { x: 0.0, y: offset });",
+            "}",
+        ]), "{}", code);
+        assert!(has_lines(&code, &[
+            "column![",
+            "row![",
+            "button(\"Intro\").on_press(Message::ScrollToSection(SECTION_INTRO)),",
+            "button(\"Usage\").on_press(Message::ScrollToSection(SECTION_USAGE)),",
+            "]",
+            ".spacing(10)",
+            ".padding(10),",
+            "scrollable(",
+        ]), "{}", code);
+        assert!(has_lines(&code, &[")", ".id(SECTIONS_SCROLLABLE),", "]", ".into()"]), "{}", code);
+        assert!(code.contains("widget::{button, column, container, row, scrollable, text},"), "{}", code);
+    }
+
+    #[test]
+    fn test_unmarked_tree_has_no_section_nav() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = sections_fixture();
+        let marked: Vec<WidgetId> = sections(hierarchy.root()).iter().map(|section| section.id).collect();
+        for id in marked {
+            hierarchy.apply_property_change(id, PropertyChange::SectionAnchor(false), &type_system);
         }
-        
-        // Track TextInput properties
-        if widget.widget_type == WidgetType::TextInput {
-            if props.text_input_font != FontType::Default {
-                self.uses_font = true;
-            }
-            if props.text_input_line_height != text::LineHeight::default() {
-                self.uses_text_line_height = true;
-            }
-            if props.text_input_alignment != ContainerAlignX::Left {
-                self.uses_alignment = true;
-            }
+        let code = generate(&hierarchy, &type_system);
+        for nav in ["SECTION", "ScrollToSection", "scrollable"] {
+            assert!(!code.contains(nav), "{}:\n{}", nav, code);
         }
-        
-        // Track MouseArea event handlers
-        if widget.widget_type == WidgetType::MouseArea {
-            if props.mousearea_on_scroll {
-                self.uses_mouse_scroll_delta = true;
-            }
-            if props.mousearea_on_move {
-                self.uses_point = true;
-            }
-            if props.mousearea_interaction.is_some() {
-                self.uses_mouse_interaction = true;
-            }
+        assert!(code.contains("fn update(&mut self, message: Message) {"), "{}", code);
+
+        // The layout stub has no update to scroll from, so it leaves marked sections alone
+        let hierarchy = sections_fixture();
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        let code = source_of(&generator.generate_layout_code());
+        assert!(!code.contains("SECTION") && !code.contains("scrollable"), "{}", code);
+    }
+
+    /// Root (breakpoint 800) > Column > [Row "toolbar" (flagged) > [Text, Text], Row "footer" > Text]
+    fn breakpoint_fixture() -> WidgetHierarchy {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        hierarchy.apply_property_change(WidgetId(0), PropertyChange::BreakpointEnabled(true), &type_system);
+        hierarchy.apply_property_change(WidgetId(0), PropertyChange::BreakpointWidth(800.0), &type_system);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let toolbar_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let footer_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        hierarchy.apply_property_change(toolbar_id, PropertyChange::StackBelowBreakpoint(true), &type_system);
+        hierarchy.apply_property_change(toolbar_id, PropertyChange::Spacing(10.0), &type_system);
+        for (parent, content) in [(toolbar_id, "Left"), (toolbar_id, "Right"), (footer_id, "Footer")] {
+            let id = hierarchy.add_child(parent, WidgetType::Text).unwrap();
+            hierarchy.apply_property_change(id, PropertyChange::TextContent(content.to_string()), &type_system);
         }
-        
-        // Recursively scan children
-        for child in &widget.children {
-            self.scan_widget(child);
+        hierarchy
+    }
+
+    #[test]
+    fn test_breakpoint_golden() {
+        let code = generate(&breakpoint_fixture(), &TypeSystem::new());
+        assert!(has_lines(&code, &[
+            "// Rows flagged in the builder stack into columns below this window width",
+            "const BREAKPOINT: f32 = 800.0;",
+        ]), "{}", code);
+        assert!(has_lines(&code, &["WindowResized(f32),", "}"]), "{}", code);
+        assert!(has_lines(&code, &[
+            "// Last reported window width, compared against BREAKPOINT in view()",
+            "window_width: f32,",
+        ]), "{}", code);
+        assert!(has_lines(&code, &[
+            "// Wide until the first resize reports the real width",
+            "window_width: BREAKPOINT,",
+        ]), "{}", code);
+        assert!(has_lines(&code, &["Message::WindowResized(width) => {", "self.window_width = width;", "}"]), "{}", code);
+        assert!(has_lines(&code, &[
+            "fn subscription(&self) -> Subscription<Message> {",
+            "window::resize_events().map(|(_id, size)| Message::WindowResized(size.width))",
+            "}",
+        ]), "{}", code);
+        assert!(code.contains(".subscription(App::subscription)"), "{}", code);
+
+        // The flagged row builds its children once and picks the layout at runtime
+        assert!(has_lines(&code, &[
+            "{",
+            "let children: Vec<Element<'_, Message>> = vec![",
+            "text(\"Left\").into(),",
+            "text(\"Right\").into(),",
+            "];",
+            "if self.window_width >= BREAKPOINT {",
+            "row(children)",
+            ".spacing(10)",
+            ".into()",
+            "} else {",
+            "column(children)",
+            ".spacing(10)",
+            ".into()",
+            "}",
+            "},",
+        ]), "{}", code);
+        assert_eq!(code.matches("text(\"Left\")").count(), 1, "{}", code);
+
+        // The unflagged row stays a plain row! macro
+        assert!(has_lines(&code, &["row![", "text(\"Footer\")"]), "{}", code);
+    }
+
+    #[test]
+    fn test_breakpoint_needs_a_flagged_row_and_the_root_switch() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = breakpoint_fixture();
+        hierarchy.apply_property_change(WidgetId(0), PropertyChange::BreakpointEnabled(false), &type_system);
+        let code = generate(&hierarchy, &type_system);
+        for scaffold in ["BREAKPOINT", "window_width", "WindowResized", "subscription"] {
+            assert!(!code.contains(scaffold), "{}:\n{}", scaffold, code);
+        }
+        assert!(has_lines(&code, &["row![", "text(\"Left\"),"]), "{}", code);
+
+        let hierarchy = breakpoint_fixture();
+        let mut generator = CodeGenerator::new(&hierarchy, Theme::Light, Some(&type_system));
+        let code = source_of(&generator.generate_layout_code());
+        assert!(!code.contains("BREAKPOINT"), "{}", code);
+    }
+
+    #[test]
+    fn test_breakpoint_with_multi_window_batches_subscriptions() {
+        let code = generate_with(&breakpoint_fixture(), |generator| generator.set_multi_window(true));
+        assert!(has_lines(&code, &[
+            "Subscription::batch([",
+            "window::close_events().map(Message::WindowClosed),",
+            "window::resize_events().map(|(_id, size)| Message::WindowResized(size.width)),",
+            "])",
+        ]), "{}", code);
+    }
+
+    #[test]
+    fn test_view_builder_golden() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate_with(&hierarchy, |generator| generator.set_view_builders(true));
+        assert!(has_lines(&code, &["fn view<'a>(&'a self) -> Element<'a, Message> {", "build_view(self)", "}"]), "{}", code);
+        assert!(has_lines(&code, &[
+            "// Pure view builder: no &self, so tests can build the state by hand and call it",
+            "// Any state works, no field has to agree with another",
+            "pub fn build_view<'a>(state: &'a App) -> Element<'a, Message> {",
+        ]), "{}", code);
+
+        let builder = &code[code.find("pub fn build_view").unwrap()..code.find("fn main").unwrap()];
+        assert!(builder.contains("state.checkbox_checked"), "{}", builder);
+        assert!(!builder.contains("self"), "{}", builder);
+    }
+
+    #[test]
+    fn test_view_builder_lists_invariants_with_derive_default() {
+        let (hierarchy, _) = column_with([WidgetType::Slider]);
+
+        let code = generate_with(&hierarchy, |generator| {
+            generator.set_view_builders(true);
+            generator.set_derive_default(true);
+        });
+        assert!(has_lines(&code, &[
+            "// The state must satisfy:",
+            "// - `slider_value` lies within 0.0..=100.0",
+            "pub fn build_view<'a>(state: &'a App) -> Element<'a, Message> {",
+        ]), "{}", code);
+        assert!(has_lines(&code, &["(Self::default(), iced::Task::none())"]), "{}", code);
+    }
+
+    #[test]
+    fn test_view_builders_off_keeps_view_inline() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate(&hierarchy, &TypeSystem::new());
+        assert!(!code.contains("build_view"));
+        assert!(code.contains("self.checkbox_checked"));
+    }
+
+    /// Resolved names for widgets of the given types and custom names under one column
+    fn names_for(widgets: &[(WidgetType, &str)]) -> Vec<String> {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let ids: Vec<WidgetId> = widgets.iter().map(|(widget_type, name)| {
+            let id = hierarchy.add_child(column_id, *widget_type).unwrap();
+            hierarchy.apply_property_change(id, PropertyChange::WidgetName(name.to_string()), &type_system);
+            id
+        }).collect();
+        ids.into_iter().map(|id| generated_widget_name(&hierarchy, id)).collect()
+    }
+
+    #[test]
+    fn test_keyword_names_get_type_suffix() {
+        let names = names_for(&[
+            (WidgetType::TextInput, "type"),
+            (WidgetType::Checkbox, "Self"),
+            (WidgetType::Slider, "loop"),
+        ]);
+        assert_eq!(names, ["type_text_input", "self_checkbox", "loop_slider"]);
+    }
+
+    #[test]
+    fn test_method_names_get_type_suffix() {
+        let names = names_for(&[
+            (WidgetType::Button, "new"),
+            (WidgetType::Toggler, "Update"),
+            (WidgetType::TextInput, "view"),
+        ]);
+        assert_eq!(names, ["new_button", "update_toggler", "view_text_input"]);
+    }
+
+    #[test]
+    fn test_names_differing_by_case_are_numbered() {
+        let names = names_for(&[
+            (WidgetType::Button, "Save"),
+            (WidgetType::Checkbox, "save"),
+            (WidgetType::TextInput, "SAVE"),
+        ]);
+        assert_eq!(names, ["save", "save_2", "save_3"]);
+    }
+
+    #[test]
+    fn test_names_matching_as_variants_are_numbered() {
+        let names = names_for(&[
+            (WidgetType::Button, "save2"),
+            (WidgetType::Button, "save"),
+            (WidgetType::Checkbox, "save"),
+            (WidgetType::Button, "foo_bar"),
+            (WidgetType::Button, "foo__bar"),
+        ]);
+        assert_eq!(names, ["save2", "save", "save_3", "foo_bar", "foo__bar_2"]);
+
+        let names = names_for(&[
+            (WidgetType::Button, "save"),
+            (WidgetType::Checkbox, "save"),
+            (WidgetType::Button, "save2"),
+        ]);
+        assert_eq!(names, ["save", "save_2", "save2_2"]);
+    }
+
+    #[test]
+    fn test_custom_names_are_only_numbered_when_taken() {
+        let names = names_for(&[
+            (WidgetType::Button, "nav_home"),
+            (WidgetType::Button, "nav_about"),
+            (WidgetType::Button, ""),
+            (WidgetType::Button, "nav_home"),
+        ]);
+        assert_eq!(names, ["nav_home", "nav_about", "button", "nav_home_2"]);
+    }
+
+    #[test]
+    fn test_names_without_valid_characters_fall_back_to_type() {
+        let names = names_for(&[
+            (WidgetType::Button, "!!!"),
+            (WidgetType::Button, "---"),
+            (WidgetType::Slider, "2nd"),
+        ]);
+        assert_eq!(names, ["button", "button_2", "slider_2nd"]);
+
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let button_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        hierarchy.apply_property_change(button_id, PropertyChange::WidgetName("My Save!".to_string()), &TypeSystem::new());
+        let code = generate(&hierarchy, &TypeSystem::new());
+        assert!(code.contains("Message::MySavePressed"), "{}", code);
+        assert!(!code.contains("My Save!Pressed"), "{}", code);
+    }
+
+    #[test]
+    fn test_container_style_mixes_literal_and_palette_colors() {
+        let code = source_of(&generate_container_style_tokens(
+            StyleColor::Palette(PaletteSlot::Text),
+            StyleColor::Palette(PaletteSlot::PrimaryStrong),
+            StyleColor::Literal(Color::from_rgba(1.0, 0.0, 0.0, 1.0)),
+            2.0, 0.0, 0.0, 0.0, 0.0,
+            true, StyleColor::Palette(PaletteSlot::DangerBase), 0.0, 2.0, 4.0,
+            true,
+        ));
+
+        assert!(code.starts_with("|theme: &Theme| container::Style {"), "{}", code);
+        assert!(has_lines(&code, &[
+            "text_color: Some(theme.extended_palette().background.base.text),",
+            "background: Some(Background::Color(theme.extended_palette().primary.strong.color)),",
+            "border: Border {",
+            "color: Color::from_rgba(1.0, 0.0, 0.0, 1.0),",
+        ]), "{}", code);
+        assert!(has_lines(&code, &[
+            "shadow: Shadow {",
+            "color: theme.extended_palette().danger.base.color,",
+        ]), "{}", code);
+    }
+
+    #[test]
+    fn test_literal_container_style_needs_no_theme() {
+        let white = StyleColor::Literal(Color::WHITE);
+        let code = source_of(&generate_container_style_tokens(
+            white, white, white, 0.0, 0.0, 0.0, 0.0, 0.0, false, white, 0.0, 0.0, 0.0, true,
+        ));
+
+        assert!(code.starts_with("container::Style {"), "{}", code);
+        assert!(!code.contains("extended_palette"), "{}", code);
+    }
+
+    #[test]
+    fn test_custom_theme_is_written_with_its_palette() {
+        let palette = iced::theme::Palette {
+            background: Color::from_rgb(0.1, 0.1, 0.2),
+            text: Color::WHITE,
+            primary: Color::from_rgb(1.0, 0.5, 0.0),
+            success: Color::from_rgb(0.0, 1.0, 0.0),
+            warning: Color::from_rgb(1.0, 1.0, 0.0),
+            danger: Color::from_rgb(1.0, 0.0, 0.0),
+        };
+        let theme = Theme::custom("Brand \"Night\"".to_string(), palette);
+        let (mut hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let themer_id = hierarchy.add_child(hierarchy.root().children[0].id, WidgetType::Themer).unwrap();
+        hierarchy.get_widget_by_id_mut(themer_id).unwrap().properties.themer_theme = Some(theme.clone());
+        let type_system = TypeSystem::new();
+        let mut generator = CodeGenerator::new(&hierarchy, theme, Some(&type_system));
+        let code = generator.generate_app_source();
+
+        assert!(!code.contains("Theme::Dark"), "{}", code);
+        assert!(has_lines(&code, &[
+            "fn theme(&self) -> Theme {",
+            "Theme::custom(",
+            "\"Brand \\\"Night\\\"\".to_string(),",
+            "iced::theme::Palette {",
+            "background: Color::from_rgba(0.100, 0.100, 0.200, 1.000),",
+            "text: Color::from_rgba(1.000, 1.000, 1.000, 1.000),",
+            "primary: Color::from_rgba(1.000, 0.500, 0.000, 1.000),",
+            "success: Color::from_rgba(0.000, 1.000, 0.000, 1.000),",
+            "warning: Color::from_rgba(1.000, 1.000, 0.000, 1.000),",
+            "danger: Color::from_rgba(1.000, 0.000, 0.000, 1.000),",
+            "},",
+            ")",
+            "}",
+        ]), "{}", code);
+        assert!(code.contains("themer(Some(Theme::custom("), "{}", code);
+        assert!(code.contains("Color,"), "{}", code);
+    }
+
+    #[test]
+    fn test_standard_window_keeps_the_plain_main() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate_with(&hierarchy, |generator| generator.set_window_settings(WindowPreset::Standard.settings()));
+        assert!(!code.contains("window_settings"), "{}", code);
+        assert!(has_lines(&code, &[
+            "iced::application(App::new, App::update, App::view)",
+            ".theme(App::theme)",
+            ".title(App::title)",
+            ".run()",
+        ]), "{}", code);
+    }
+
+    #[test]
+    fn test_window_preset_goldens() {
+        let expected: [(WindowPreset, &[&str]); 3] = [
+            (WindowPreset::Dialog, &[
+                "size: Size::new(480.0, 320.0),",
+                "min_size: Some(Size::new(320.0, 200.0)),",
+                "position: window::Position::Centered,",
+                "resizable: false,",
+            ]),
+            (WindowPreset::ToolPalette, &[
+                "size: Size::new(280.0, 600.0),",
+                "min_size: Some(Size::new(200.0, 300.0)),",
+                "level: window::Level::AlwaysOnTop,",
+            ]),
+            (WindowPreset::Kiosk, &[
+                "resizable: false,",
+                "decorations: false,",
+                "fullscreen: true,",
+            ]),
+        ];
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        for (preset, fields) in expected {
+            let code = generate_with(&hierarchy, |generator| generator.set_window_settings(preset.settings()));
+            let mut lines = vec!["fn window_settings() -> window::Settings {", "window::Settings {"];
+            lines.extend_from_slice(fields);
+            lines.extend(["..window::Settings::default()", "}", "}"]);
+            assert!(has_lines(&code, &lines), "{:?}\n{}", preset, code);
+            assert!(has_lines(&code, &[
+                "fn main() -> iced::Result {",
+                "iced::application(App::new, App::update, App::view)",
+                ".theme(App::theme)",
+                ".title(App::title)",
+                ".window(window_settings())",
+                ".run()",
+            ]), "{:?}\n{}", preset, code);
+            assert!(code.contains("\n    window,\n"), "{:?}\n{}", preset, code);
+            assert_eq!(code.contains("Size,"), preset != WindowPreset::Kiosk, "{:?}\n{}", preset, code);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_window_preset_opens_the_daemon_window() {
+        let (hierarchy, _) = column_with([WidgetType::Button, WidgetType::Checkbox]);
+        let code = generate_with(&hierarchy, |generator| {
+            generator.set_window_settings(WindowPreset::Dialog.settings());
+            generator.set_multi_window(true);
+        });
+        assert!(code.contains("let (_id, open) = window::open(window_settings());"), "{}", code);
+        assert!(!code.contains(".window(window_settings())"), "{}", code);
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+/// Size of the generated code, counted while it is emitted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CodeMetrics {
+    pub lines: usize,
+    pub message_variants: usize,
+    pub state_fields: usize,
+    /// Deepest indent reached
+    pub max_nesting: usize,
+}
+
+/// One of the counted sizes, with its soft limit in a `CodeBudget`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeMetric {
+    Lines,
+    MessageVariants,
+    StateFields,
+    Nesting,
+}
+
+impl CodeMetric {
+    pub const ALL: &'static [Self] = &[
+        Self::Lines,
+        Self::MessageVariants,
+        Self::StateFields,
+        Self::Nesting,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Lines => "Lines",
+            Self::MessageVariants => "Messages",
+            Self::StateFields => "State Fields",
+            Self::Nesting => "Nesting",
+        }
+    }
+
+    pub fn value(self, metrics: &CodeMetrics) -> usize {
+        match self {
+            Self::Lines => metrics.lines,
+            Self::MessageVariants => metrics.message_variants,
+            Self::StateFields => metrics.state_fields,
+            Self::Nesting => metrics.max_nesting,
+        }
+    }
+
+    /// Range the settings slider offers for the limit
+    pub fn limit_range(self) -> std::ops::RangeInclusive<f32> {
+        match self {
+            Self::Lines => 100.0..=5000.0,
+            Self::MessageVariants => 10.0..=500.0,
+            Self::StateFields => 10.0..=500.0,
+            Self::Nesting => 4.0..=40.0,
+        }
+    }
+
+    /// What to try once the limit is passed
+    pub fn advice(self) -> &'static str {
+        match self {
+            Self::Lines => "Consider splitting the layout into smaller views in their own modules",
+            Self::MessageVariants => "Consider grouping related messages into nested enums, one per section of the UI",
+            Self::StateFields => "Consider moving related state into sub-structs owned by each section",
+            Self::Nesting => "Consider decomposing deep branches into helper functions returning an Element",
+        }
+    }
+}
+
+/// Soft limits on the generated code. Passing one only changes how the count is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodeBudget {
+    pub max_lines: usize,
+    pub max_message_variants: usize,
+    pub max_state_fields: usize,
+    pub max_nesting: usize,
+}
+
+impl Default for CodeBudget {
+    fn default() -> Self {
+        Self {
+            max_lines: 1500,
+            max_message_variants: 120,
+            max_state_fields: 120,
+            max_nesting: 12,
+        }
+    }
+}
+
+impl CodeBudget {
+    pub fn limit(&self, metric: CodeMetric) -> usize {
+        match metric {
+            CodeMetric::Lines => self.max_lines,
+            CodeMetric::MessageVariants => self.max_message_variants,
+            CodeMetric::StateFields => self.max_state_fields,
+            CodeMetric::Nesting => self.max_nesting,
+        }
+    }
+
+    pub fn set_limit(&mut self, metric: CodeMetric, limit: usize) {
+        match metric {
+            CodeMetric::Lines => self.max_lines = limit,
+            CodeMetric::MessageVariants => self.max_message_variants = limit,
+            CodeMetric::StateFields => self.max_state_fields = limit,
+            CodeMetric::Nesting => self.max_nesting = limit,
+        }
+    }
+
+    pub fn exceeds(&self, metrics: &CodeMetrics, metric: CodeMetric) -> bool {
+        metric.value(metrics) > self.limit(metric)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_counts_above_the_limit_exceed() {
+        let budget = CodeBudget { max_message_variants: 3, max_nesting: 2, ..CodeBudget::default() };
+        let metrics = CodeMetrics { lines: 40, message_variants: 3, state_fields: 1, max_nesting: 5 };
+
+        let exceeded: Vec<_> = CodeMetric::ALL.iter()
+            .copied()
+            .filter(|&metric| budget.exceeds(&metrics, metric))
+            .collect();
+        assert_eq!(exceeded, [CodeMetric::Nesting]);
+    }
+
+    #[test]
+    fn test_missing_limits_load_as_defaults() {
+        let budget: CodeBudget = toml::from_str("max_nesting = 8").unwrap();
+        assert_eq!(budget.max_nesting, 8);
+        assert_eq!(budget.max_message_variants, CodeBudget::default().max_message_variants);
+    }
+}
@@ -0,0 +1,211 @@
+use std::ops::Range;
+
+use iced::widget::scrollable::AbsoluteOffset;
+
+use crate::widget_helper::code_generator::{Token, TokenType};
+
+/// Id of the full code panel's scrollable, scrolled to the current match
+pub const CODE_VIEW_SCROLLABLE: &str = "code_view_scrollable";
+
+/// Height of one rendered code line: 14px monospace at iced's default 1.3 line height, plus the 2px row spacing
+const LINE_HEIGHT: f32 = 14.0 * 1.3 + 2.0;
+/// Padding above the first line inside the code view
+const TOP_PADDING: f32 = 15.0;
+/// Lines kept visible above a match jumped to, so it doesn't sit flush against the edge
+const CONTEXT_LINES: usize = 3;
+
+/// Search state of the code panel. `current` indexes the matches of the code as last rendered.
+#[derive(Debug, Clone, Default)]
+pub struct CodeSearch {
+    pub query: String,
+    pub current: usize,
+}
+
+impl CodeSearch {
+    /// Moves to the next match, or the previous one when `backwards`, wrapping at either end.
+    /// Returns the offset that brings the new match into view.
+    pub fn step(&mut self, text: &str, backwards: bool) -> Option<AbsoluteOffset> {
+        let matches = find_matches(text, &self.query);
+        if matches.is_empty() {
+            return None;
+        }
+        let current = self.current.min(matches.len() - 1);
+        self.current = if backwards {
+            (current + matches.len() - 1) % matches.len()
+        } else {
+            (current + 1) % matches.len()
+        };
+        Some(scroll_offset(line_of(text, matches[self.current].start)))
+    }
+
+    /// Replaces the query and starts over at the first match, returning where it is
+    pub fn set_query(&mut self, text: &str, query: String) -> Option<AbsoluteOffset> {
+        self.query = query;
+        self.current = 0;
+        find_matches(text, &self.query)
+            .first()
+            .map(|first| scroll_offset(line_of(text, first.start)))
+    }
+
+    /// "3 of 17", or "No matches" for a query that finds nothing
+    pub fn counter(&self, match_count: usize) -> String {
+        if match_count == 0 {
+            "No matches".to_string()
+        } else {
+            format!("{} of {}", self.current.min(match_count - 1) + 1, match_count)
+        }
+    }
+}
+
+/// Case-insensitive, non-overlapping matches of `query` in `text`, as byte ranges.
+/// Searching the flattened text finds matches that span several tokens.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    // ASCII lowercasing keeps byte offsets, so the ranges index the original text
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    haystack.match_indices(&needle)
+        .map(|(start, found)| start..start + found.len())
+        .collect()
+}
+
+/// 0-based line the byte `offset` is on
+pub fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset].matches('\n').count()
+}
+
+fn scroll_offset(line: usize) -> AbsoluteOffset {
+    AbsoluteOffset {
+        x: 0.0,
+        y: (TOP_PADDING + line.saturating_sub(CONTEXT_LINES) as f32 * LINE_HEIGHT).max(0.0),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Highlight {
+    None,
+    Match,
+    /// The match the counter points at
+    Current,
+}
+
+/// A run of one token's text with a single highlight
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub text: String,
+    pub token_type: TokenType,
+    pub highlight: Highlight,
+}
+
+/// Splits the tokens into lines, and further at match boundaries so only the matched text is highlighted
+pub fn highlighted_lines(tokens: &[Token], matches: &[Range<usize>], current: Option<usize>) -> Vec<Vec<Segment>> {
+    let mut lines: Vec<Vec<Segment>> = vec![vec![]];
+    let mut offset = 0;
+
+    for token in tokens {
+        let token_end = offset + token.text.len();
+        let mut position = offset;
+
+        while position < token_end {
+            let next = matches.partition_point(|m| m.end <= position);
+            let (highlight, end) = match matches.get(next) {
+                Some(m) if m.start <= position => {
+                    let highlight = if Some(next) == current { Highlight::Current } else { Highlight::Match };
+                    (highlight, m.end.min(token_end))
+                }
+                Some(m) => (Highlight::None, m.start.min(token_end)),
+                None => (Highlight::None, token_end),
+            };
+
+            let piece = &token.text[position - offset..end - offset];
+            for (i, part) in piece.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(vec![]);
+                }
+                if !part.is_empty() {
+                    lines.last_mut().unwrap().push(Segment {
+                        text: part.to_string(),
+                        token_type: token.token_type,
+                        highlight,
+                    });
+                }
+            }
+            position = end;
+        }
+        offset = token_end;
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(text: &str, token_type: TokenType) -> Token {
+        Token { text: text.to_string(), token_type }
+    }
+
+    fn flatten(tokens: &[Token]) -> String {
+        tokens.iter().map(|t| t.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_match_spans_token_boundaries() {
+        let tokens = [
+            token("Message", TokenType::Type),
+            token("::", TokenType::Operator),
+            token("SavePressed", TokenType::Plain),
+        ];
+        let matches = find_matches(&flatten(&tokens), "message::save");
+        assert_eq!(matches, [0..13]);
+
+        let lines = highlighted_lines(&tokens, &matches, Some(0));
+        let texts: Vec<(&str, Highlight)> = lines[0].iter().map(|s| (s.text.as_str(), s.highlight)).collect();
+        assert_eq!(texts, [
+            ("Message", Highlight::Current),
+            ("::", Highlight::Current),
+            ("Save", Highlight::Current),
+            ("Pressed", Highlight::None),
+        ]);
+    }
+
+    #[test]
+    fn test_only_matched_text_is_highlighted() {
+        let tokens = [token("let save = 1;\nsave();", TokenType::Plain)];
+        let text = flatten(&tokens);
+        let matches = find_matches(&text, "SAVE");
+        assert_eq!(matches, [4..8, 14..18]);
+
+        let lines = highlighted_lines(&tokens, &matches, Some(1));
+        assert_eq!(lines.len(), 2);
+        let first: Vec<(&str, Highlight)> = lines[0].iter().map(|s| (s.text.as_str(), s.highlight)).collect();
+        assert_eq!(first, [("let ", Highlight::None), ("save", Highlight::Match), (" = 1;", Highlight::None)]);
+        assert_eq!(lines[1][0].highlight, Highlight::Current);
+        assert_eq!(line_of(&text, matches[1].start), 1);
+    }
+
+    #[test]
+    fn test_step_wraps_both_ways() {
+        let text = "a\nb a\nc\nd a";
+        let mut search = CodeSearch::default();
+        assert!(search.set_query(text, "A".to_string()).is_some());
+        assert_eq!(search.counter(3), "1 of 3");
+
+        search.step(text, true);
+        assert_eq!(search.current, 2);
+        search.step(text, false);
+        assert_eq!(search.current, 0);
+        assert_eq!(search.counter(0), "No matches");
+    }
+
+    #[test]
+    fn test_cleared_query_renders_plain() {
+        let tokens = [token("fn view", TokenType::Keyword)];
+        assert!(find_matches(&flatten(&tokens), "").is_empty());
+        let lines = highlighted_lines(&tokens, &[], None);
+        assert_eq!(lines[0], [Segment { text: "fn view".to_string(), token_type: TokenType::Keyword, highlight: Highlight::None }]);
+    }
+}
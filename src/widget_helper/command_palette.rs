@@ -0,0 +1,142 @@
+use crate::widget_helper::Message;
+
+/// Id of the palette's search field, focused when the palette opens
+pub const COMMAND_PALETTE_INPUT: &str = "command_palette_input";
+
+/// A single palette entry. `message` is the same Message the matching button sends.
+#[derive(Debug, Clone)]
+pub struct PaletteAction {
+    pub label: String,
+    pub message: Message,
+}
+
+impl PaletteAction {
+    pub fn new(label: impl Into<String>, message: Message) -> Self {
+        Self { label: label.into(), message }
+    }
+}
+
+/// Open palette state. Actions are captured when it opens, since the
+/// selection can't change while the palette has focus.
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    pub query: String,
+    pub highlighted: usize,
+    actions: Vec<PaletteAction>,
+}
+
+impl CommandPalette {
+    pub fn new(actions: Vec<PaletteAction>) -> Self {
+        Self { query: String::new(), highlighted: 0, actions }
+    }
+
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.highlighted = 0;
+    }
+
+    /// Moves the highlight, wrapping at either end
+    pub fn move_highlight(&mut self, delta: isize) {
+        let len = self.filtered().len();
+        if len == 0 {
+            self.highlighted = 0;
+            return;
+        }
+        let current = self.highlighted.min(len - 1) as isize;
+        self.highlighted = (current + delta).rem_euclid(len as isize) as usize;
+    }
+
+    /// Actions matching the query, best match first. Ties keep their original order.
+    pub fn filtered(&self) -> Vec<&PaletteAction> {
+        let mut scored: Vec<(i32, &PaletteAction)> = self.actions
+            .iter()
+            .filter_map(|action| fuzzy_score(&self.query, &action.label).map(|score| (score, action)))
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, action)| action).collect()
+    }
+}
+
+/// Subsequence match score, case-insensitive. `None` when `query` isn't a
+/// subsequence of `candidate`. Consecutive matches and matches at the start of
+/// a word score higher; skipped characters cost a little.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut last_match: Option<usize> = None;
+
+    for (i, &c) in candidate.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if c != query[next] {
+            continue;
+        }
+
+        score += 1;
+        if last_match.is_some_and(|last| last + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || !candidate[i - 1].is_alphanumeric() {
+            score += 3;
+        }
+        if let Some(last) = last_match {
+            score -= (i - last - 1).min(3) as i32;
+        }
+
+        last_match = Some(i);
+        next += 1;
+    }
+
+    (next == query.len()).then_some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_subsequence() {
+        assert!(fuzzy_score("abt", "Add Button").is_some());
+        assert!(fuzzy_score("ADD", "add button").is_some());
+        assert!(fuzzy_score("xyz", "Add Button").is_none());
+        assert!(fuzzy_score("tb", "Add Button").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_prefers_word_starts_and_runs() {
+        let word_starts = fuzzy_score("sp", "Select parent").unwrap();
+        let scattered = fuzzy_score("sp", "Press space").unwrap();
+        assert!(word_starts > scattered);
+
+        let run = fuzzy_score("copy", "Copy code").unwrap();
+        let split = fuzzy_score("copy", "Clear options press y").unwrap();
+        assert!(run > split);
+    }
+
+    #[test]
+    fn test_filtered_order_and_highlight_wrap() {
+        let mut palette = CommandPalette::new(vec![
+            PaletteAction::new("Select root", Message::Noop),
+            PaletteAction::new("Copy code", Message::Noop),
+            PaletteAction::new("Show code view", Message::Noop),
+        ]);
+
+        palette.set_query("code".to_string());
+        let labels: Vec<_> = palette.filtered().iter().map(|a| a.label.as_str()).collect();
+        // The contiguous match wins over the scattered one
+        assert_eq!(labels, vec!["Show code view", "Copy code"]);
+
+        palette.move_highlight(-1);
+        assert_eq!(palette.highlighted, 1);
+        palette.move_highlight(1);
+        assert_eq!(palette.highlighted, 0);
+    }
+}
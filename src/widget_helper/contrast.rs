@@ -0,0 +1,146 @@
+use iced::{Color, Theme};
+
+use crate::widget_helper::*;
+
+/// WCAG AA minimum for body text
+pub const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+/// Relative luminance of an sRGB color, as WCAG defines it
+pub fn relative_luminance(color: Color) -> f32 {
+    let linear = |c: f32| {
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+}
+
+/// Contrast ratio between two colors, from 1:1 up to 21:1
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Black or white, whichever reads better on `background`
+pub fn readable_text_color(background: Color) -> Color {
+    if contrast_ratio(Color::BLACK, background) >= contrast_ratio(Color::WHITE, background) {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
+
+/// `top` drawn over an opaque `bottom`
+fn composite(top: Color, bottom: Color) -> Color {
+    let mix = |t: f32, b: f32| t * top.a + b * (1.0 - top.a);
+    Color::from_rgb(mix(top.r, bottom.r), mix(top.g, bottom.g), mix(top.b, bottom.b))
+}
+
+/// Text on a Container's background that is hard to read, and the color that would fix it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastSuggestion {
+    /// Lowest ratio among the Text widgets drawn on the background
+    pub ratio: f32,
+    pub text_color: Color,
+    /// Direct Text children the suggested color is applied to
+    pub targets: Vec<WidgetId>,
+}
+
+/// Checks the Texts on `container_id`'s background color against it. None while no background
+/// color is drawn, no Text sits on it, or every Text already reads well enough.
+pub fn suggest(hierarchy: &WidgetHierarchy, container_id: WidgetId, theme: &Theme) -> Option<ContrastSuggestion> {
+    let container = hierarchy.get_widget_by_id(container_id)?;
+    let props = &container.properties;
+    if props.gradient_enabled || props.background_color.a == 0.0 {
+        return None;
+    }
+    let background = composite(props.background_color, theme.palette().background);
+
+    let mut text_colors = Vec::new();
+    for child in &container.children {
+        collect_text_colors(child, theme, &mut text_colors);
+    }
+    let ratio = text_colors.iter()
+        .map(|&color| contrast_ratio(composite(color, background), background))
+        .reduce(f32::min)?;
+    if ratio >= MIN_TEXT_CONTRAST {
+        return None;
+    }
+
+    Some(ContrastSuggestion {
+        ratio,
+        text_color: readable_text_color(background),
+        targets: container.children.iter()
+            .filter(|child| child.widget_type == WidgetType::Text)
+            .map(|child| child.id)
+            .collect(),
+    })
+}
+
+/// Effective colors of the Texts in `widget`, stopping at Containers that draw their own background
+fn collect_text_colors(widget: &Widget, theme: &Theme, colors: &mut Vec<Color>) {
+    let props = &widget.properties;
+    match widget.widget_type {
+        WidgetType::Text => {
+            // A transparent text color means none was set, so the theme's applies
+            colors.push(if props.text_color.a == 0.0 { theme.palette().text } else { props.text_color });
+        }
+        WidgetType::Container if props.gradient_enabled || props.background_color.a > 0.0 => return,
+        _ => {}
+    }
+    for child in &widget.children {
+        collect_text_colors(child, theme, colors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::type_system::TypeSystem;
+
+    fn container_with_text(background: Color) -> (WidgetHierarchy, WidgetId) {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let text_id = hierarchy.add_child(WidgetId(0), WidgetType::Text).unwrap();
+        hierarchy.apply_property_change(WidgetId(0), PropertyChange::BackgroundColor(background), &type_system);
+        (hierarchy, text_id)
+    }
+
+    #[test]
+    fn test_contrast_ratio_extremes() {
+        assert!((contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(Color::WHITE, Color::WHITE) - 1.0).abs() < 0.01);
+        assert_eq!(readable_text_color(Color::from_rgb(0.1, 0.1, 0.2)), Color::WHITE);
+        assert_eq!(readable_text_color(Color::from_rgb(0.9, 0.9, 0.7)), Color::BLACK);
+    }
+
+    #[test]
+    fn test_dark_background_suggests_white_for_direct_text() {
+        // Light's default text is near black
+        let (hierarchy, text_id) = container_with_text(Color::from_rgb(0.1, 0.1, 0.15));
+        let suggestion = suggest(&hierarchy, WidgetId(0), &Theme::Light).unwrap();
+        assert!(suggestion.ratio < MIN_TEXT_CONTRAST);
+        assert_eq!(suggestion.text_color, Color::WHITE);
+        assert_eq!(suggestion.targets, [text_id]);
+
+        // Dark's default text is already light
+        assert_eq!(suggest(&hierarchy, WidgetId(0), &Theme::Dark), None);
+    }
+
+    #[test]
+    fn test_explicit_text_color_is_checked() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, text_id) = container_with_text(Color::from_rgb(0.1, 0.1, 0.15));
+        hierarchy.apply_property_change(text_id, PropertyChange::TextColor(Color::WHITE), &type_system);
+        assert_eq!(suggest(&hierarchy, WidgetId(0), &Theme::Light), None);
+    }
+
+    #[test]
+    fn test_silent_without_text_or_background() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        hierarchy.add_child(WidgetId(0), WidgetType::Button).unwrap();
+        hierarchy.apply_property_change(WidgetId(0), PropertyChange::BackgroundColor(Color::BLACK), &TypeSystem::new());
+        assert_eq!(suggest(&hierarchy, WidgetId(0), &Theme::Light), None);
+
+        let (hierarchy, _) = container_with_text(Color::TRANSPARENT);
+        assert_eq!(suggest(&hierarchy, WidgetId(0), &Theme::Light), None);
+    }
+}
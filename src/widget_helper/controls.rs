@@ -1,9 +1,21 @@
 // controls.rs
 use iced::{ Alignment, Color, Element, Length, Padding, Theme, mouse::Interaction };
-use iced::widget::{ container, button, checkbox, column, pick_list, radio, row, rule, scrollable, slider, space, text, text_editor, text_input, Space};
+use iced::widget::{ container, button, checkbox, column, markdown, pick_list, radio, row, rule, scrollable, slider, space, text, text_editor, text_input, Space};
 use crate::widget_helper::*;
-use crate::widget_helper::code_generator::{CodeGenerator, build_code_view_with_height};
+use crate::widget_helper::code_generator::{CodeGenerator, collect_tree_order, emitted_property_keys, generated_widget_name, supports_clip};
 use crate::widget_helper::type_system::TypeSystem;
+use crate::widget_helper::contrast;
+use crate::widget_helper::color_names;
+use crate::widget_helper::numeric_input::{NumericField, NumericProperty};
+use crate::widget_helper::enum_form::{EnumFormMessage, NewEnumForm};
+use crate::widget_helper::option_import::{OptionImportForm, OptionImportMessage, OptionTarget};
+use crate::widget_helper::reference_image::ReferenceMessage;
+use crate::widget_helper::tags;
+use crate::widget_helper::named_styles::{self, ContainerLook, StyleMessage, StyleRegistry};
+use std::collections::BTreeMap;
+use crate::widget_helper::pane_width::split_columns;
+use crate::widget_helper::asset_library::{Asset, AssetKind, AssetLibrary, AssetMessage};
+use uuid::Uuid;
 use crate::widget_helper::styles::container::*;
 use crate::icon;
 
@@ -19,18 +31,13 @@ pub fn container_controls<'a>(
     h: &'a WidgetHierarchy,
     widget_id: WidgetId,
     theme: Theme,
-    type_system: Option<&'a TypeSystem>
+    contrast_dismissed: bool,
+    wide: bool,
 ) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
-    let content = column![
-        // Title
-        text("Container Properties").size(TITLE_SIZE),
-
-        // Widget Name
-        widget_name(widget_id, &props.widget_name),
-
+    let layout: Vec<Element<'a, Message>> = vec![
         column![
             text("Sizing Mode").size(SECTION_SIZE),
             pick_list(
@@ -55,7 +62,8 @@ pub fn container_controls<'a>(
             .size(LABEL_SIZE - 1.0)
             .color(Color::from_rgb(0.5, 0.5, 0.5)),
         ]
-        .spacing(LABEL_SPACING),
+        .spacing(LABEL_SPACING)
+        .into(),
 
         // Size Controls - conditional based on mode
         match props.container_sizing_mode {
@@ -146,27 +154,18 @@ pub fn container_controls<'a>(
                 .width(Length::Fill),
             ]
             .spacing(SECTION_SPACING)
+            .into()
         } else {
-            row![]
+            row![].into()
         },
 
         // Padding Controls
         padding_controls(
-            props.padding,
+            h,
             widget_id,
             props.padding_mode,
         ),
 
-        // Border Controls
-        border_controls(
-            props.border_width,
-            props.border_radius,
-            widget_id,
-        ),
-
-        // Set a Widget Id
-        widget_id_control(widget_id, props.widget_id.clone()),
-
         // Max Width control
         max_width_control(widget_id, props.max_width),
 
@@ -174,23 +173,54 @@ pub fn container_controls<'a>(
         max_height_control(widget_id, props.max_height),
         
         //Clip control
-        clip_control(widget_id, props.clip),
+        clip_control(widget),
+    ];
+
+    let style: Vec<Element<'a, Message>> = vec![
+        // Border Controls
+        border_controls(widget),
+
+        // Gradient Background
+        gradient_controls(h, widget),
+
+        // Alternate colors while a bool is true
+        conditional_style_controls(h, widget_id),
+
+        // Offer readable text for the background, unless dismissed for this color
+        if contrast_dismissed {
+            column![].into()
+        } else {
+            contrast_suggestion_chip(h, widget_id, &theme)
+        },
+    ];
+
+    let content = column![
+        // Title
+        text("Container Properties").size(TITLE_SIZE),
+
+        // Widget Name
+        widget_name(h, widget_id, &props.widget_name),
+
+        // Sizing on the left and looks on the right in a wide panel
+        split_columns(wide, layout, style, MAIN_SPACING),
 
+        // Set a Widget Id
+        widget_id_control(widget_id, props.widget_id.clone()),
     ]
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn row_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn row_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Row Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Layout Mode").size(SECTION_SIZE),
@@ -199,6 +229,17 @@ pub fn row_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Them
                 props.is_wrapping_row,
             )
             .on_toggle(move |v| Message::PropertyChanged(widget_id, PropertyChange::IsWrappingRow(v))),
+            match hints::stretching_spaces(widget).len() {
+                0 => column![],
+                count => column![
+                    text(format!(
+                        "{} Fill Space{} will stretch each wrapped line differently. Use fixed widths or the spacing below.",
+                        count, if count == 1 { "" } else { "s" }
+                    ))
+                    .size(LABEL_SIZE - 1.0)
+                    .style(text::warning),
+                ],
+            },
         ]
         .spacing(LABEL_SPACING),
 
@@ -314,27 +355,27 @@ pub fn row_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Them
         ),
 
         padding_controls(
-            props.padding,
+            h,
             widget_id,
             props.padding_mode,
         ),
 
-        clip_control(widget_id, props.clip),
+        clip_control(widget),
     ]
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn column_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn column_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Column Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Spacing between items").size(LABEL_SIZE),
@@ -371,7 +412,7 @@ pub fn column_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: T
         ),
 
         padding_controls(
-            props.padding,
+            h,
             widget_id,
             props.padding_mode,
         ),
@@ -380,15 +421,15 @@ pub fn column_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: T
         max_width_control(widget_id, props.max_width),
         
         //Clip control
-        clip_control(widget_id, props.clip),
+        clip_control(widget),
     ]
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn button_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn button_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, wide: bool) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
     let palette = theme.extended_palette();
@@ -404,18 +445,15 @@ pub fn button_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: T
         3 // None selected
     };
 
-    let content = column![
-        text("Button Properties").size(TITLE_SIZE),
-
-        widget_name(widget_id, &props.widget_name),
-
+    let behavior: Vec<Element<'a, Message>> = vec![
         column![
             text("Button Text").size(LABEL_SIZE),
             text_input("Text", &props.text_content)
                 .on_input(move |v| Message::PropertyChanged(widget_id, PropertyChange::TextContent(v)))
                 .width(250),
         ]
-        .spacing(LABEL_SPACING),
+        .spacing(LABEL_SPACING)
+        .into(),
 
         column![
             text("Button Style").size(LABEL_SIZE),
@@ -432,7 +470,8 @@ pub fn button_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: T
             )
             .width(250),
         ]
-        .spacing(LABEL_SPACING),
+        .spacing(LABEL_SPACING)
+        .into(),
 
         column![
             text("Event Handler").size(SECTION_SIZE),
@@ -505,8 +544,13 @@ pub fn button_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: T
             .spacing(5)
             .align_y(Alignment::Center),
         ]
-        .spacing(SECTION_SPACING),
+        .spacing(SECTION_SPACING)
+        .into(),
+
+        file_dialog_controls(h, widget_id),
+    ];
 
+    let layout: Vec<Element<'a, Message>> = vec![
         size_controls_scrollable_aware(
             props.width,
             move |l| Message::PropertyChanged(widget_id, PropertyChange::Width(l)),
@@ -517,28 +561,36 @@ pub fn button_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: T
         ),
 
         padding_controls(
-            props.padding,
+            h,
             widget_id,
             props.padding_mode,
         ),
 
-        clip_control(widget_id, props.clip),
+        clip_control(widget),
+    ];
+
+    let content = column![
+        text("Button Properties").size(TITLE_SIZE),
+
+        widget_name(h, widget_id, &props.widget_name),
+
+        split_columns(wide, behavior, layout, MAIN_SPACING),
     ]
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
 
-pub fn text_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn text_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Text Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Text Content").size(LABEL_SIZE),
@@ -564,7 +616,7 @@ pub fn text_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: The
         .spacing(LABEL_SPACING),
 
         column![
-            text("Font").size(LABEL_SIZE),
+            badged_label("Font", widget, "font"),
             pick_list(
                 vec![FontType::Default, FontType::Monospace],
                 Some(props.font),
@@ -583,12 +635,16 @@ pub fn text_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: The
             widget_id,
         ),
 
-        color_hex_input("Text Color", props.text_color, move |c| {
-            Message::PropertyChanged(widget_id, PropertyChange::TextColor(c))
-        }),
+        column![
+            color_hex_input("Text Color", props.text_color, move |c| {
+                Message::PropertyChanged(widget_id, PropertyChange::TextColor(c))
+            }),
+            coverage_badge(widget, "text_color"),
+        ]
+        .spacing(LABEL_SPACING),
 
         column![
-            text("Wrapping").size(LABEL_SIZE),
+            badged_label("Wrapping", widget, "wrap"),
             pick_list(
                 vec![TextWrapping::None, TextWrapping::Word, TextWrapping::Glyph, TextWrapping::WordOrGlyph],
                 Some(TextWrapping::from(props.wrap)),
@@ -598,7 +654,7 @@ pub fn text_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: The
         .spacing(LABEL_SPACING),
 
         column![
-            text("Shaping").size(LABEL_SIZE),
+            badged_label("Shaping", widget, "shaping"),
             pick_list(
                 vec![TextShaping::Basic, TextShaping::Advanced, TextShaping::Auto],
                 Some(TextShaping::from(props.shaping)),
@@ -608,23 +664,16 @@ pub fn text_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: The
         .spacing(LABEL_SPACING),
 
         column![
-            text("Line Height").size(LABEL_SIZE),
-            row![
-                slider(0.8..=2.0, match props.line_height { text::LineHeight::Relative(v) => v, _ => 1.0 }, move |v| {
-                    Message::PropertyChanged(widget_id, PropertyChange::TextLineHeight(text::LineHeight::Relative((v*100.0).round()/100.0)))
-                })
-                .step(0.05)
-                .width(220),
-                text(match props.line_height { text::LineHeight::Relative(v) => format!("{:.2}", v), _ => "1.00".into() }).size(LABEL_SIZE)
-            ]
-            .spacing(SECTION_SPACING)
-            .align_y(Alignment::Center)
+            badged_label("Line Height", widget, "line_height"),
+            line_height_control(props.line_height, props.text_size, move |v| {
+                Message::PropertyChanged(widget_id, PropertyChange::TextLineHeight(v))
+            }),
         ]
         .spacing(LABEL_SPACING),
 
         row![
             column![
-                text("Align X").size(LABEL_SIZE),
+                badged_label("Align X", widget, "text_align_x"),
                 pick_list(
                     vec![AlignText::Default, AlignText::Left, AlignText::Center, AlignText::Right, AlignText::Justified],
                     Some(AlignText::from(props.text_align_x)),
@@ -635,7 +684,7 @@ pub fn text_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: The
             .width(Length::Fill),
             
             column![
-                text("Align Y").size(LABEL_SIZE),
+                badged_label("Align Y", widget, "text_align_y"),
                 pick_list(
                     vec![AlignmentYOption::Top, AlignmentYOption::Center, AlignmentYOption::Bottom],
                     Some(AlignmentYOption::from(props.text_align_y)),
@@ -650,17 +699,58 @@ pub fn text_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: The
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
+}
+
+/// Relative or Absolute mode and the value in it. Switching modes keeps the height the
+/// lines come to at `text_size`.
+fn line_height_control<'a>(
+    line_height: text::LineHeight,
+    text_size: f32,
+    on_change: impl Fn(text::LineHeight) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let mode = LineHeightMode::of(line_height);
+    let value: Element<'a, Message> = match line_height {
+        text::LineHeight::Relative(factor) => row![
+            slider(0.5..=3.0, factor, move |v| on_change(text::LineHeight::Relative((v * 100.0).round() / 100.0)))
+                .step(0.05)
+                .width(160),
+            text(format!("{:.2}×", factor)).size(LABEL_SIZE).width(50),
+        ]
+        .spacing(SECTION_SPACING)
+        .align_y(Alignment::Center)
+        .into(),
+        text::LineHeight::Absolute(pixels) => row![
+            slider(4.0..=96.0, pixels.0, move |v| on_change(text::LineHeight::Absolute(iced::Pixels(v.round()))))
+                .step(1.0)
+                .width(160),
+            text(format!("{:.0}px", pixels.0)).size(LABEL_SIZE).width(50),
+        ]
+        .spacing(SECTION_SPACING)
+        .align_y(Alignment::Center)
+        .into(),
+    };
+
+    row![
+        pick_list(LineHeightMode::ALL, Some(mode), move |mode: LineHeightMode| {
+            on_change(mode.convert(line_height, text_size))
+        })
+        .text_size(LABEL_SIZE),
+        value,
+    ]
+    .spacing(SECTION_SPACING)
+    .align_y(Alignment::Center)
+    .into()
 }
 
-pub fn text_input_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn text_input_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Text Input Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Placeholder Text").size(LABEL_SIZE),
@@ -670,6 +760,14 @@ pub fn text_input_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, them
         ]
         .spacing(LABEL_SPACING),
 
+        column![
+            preview_only_label("Preview Text"),
+            text_input("None, starts empty", props.preview_text.as_deref().unwrap_or(""))
+                .on_input(move |v| Message::PropertyChanged(widget_id, PropertyChange::PreviewText((!v.is_empty()).then_some(v))))
+                .width(250),
+        ]
+        .spacing(LABEL_SPACING),
+
         column![
             text("Font Size").size(LABEL_SIZE),
             row![
@@ -700,6 +798,14 @@ pub fn text_input_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, them
         ]
         .spacing(LABEL_SPACING),
 
+        column![
+            badged_label("Line Height", widget, "text_input_line_height"),
+            line_height_control(props.text_input_line_height, props.text_input_size, move |v| {
+                Message::PropertyChanged(widget_id, PropertyChange::TextInputLineHeight(v))
+            }),
+        ]
+        .spacing(LABEL_SPACING),
+
         column![
             text("Font").size(LABEL_SIZE),
             pick_list(
@@ -752,6 +858,8 @@ pub fn text_input_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, them
         ]
         .spacing(SECTION_SPACING),
 
+        conditional_style_controls(h, widget_id),
+
         size_controls_scrollable_aware(
             props.width,
             move |l| Message::PropertyChanged(widget_id, PropertyChange::Width(l)),
@@ -764,17 +872,32 @@ pub fn text_input_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, them
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn checkbox_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+/// Label side picker shared by checkbox, toggler and radio
+fn label_position_control<'a>(widget_id: WidgetId, position: LabelPosition) -> Element<'a, Message> {
+    column![
+        text("Label Position").size(LABEL_SIZE),
+        pick_list(
+            LabelPosition::ALL,
+            Some(position),
+            move |v| Message::PropertyChanged(widget_id, PropertyChange::LabelPosition(v)),
+        )
+        .width(120),
+    ]
+    .spacing(LABEL_SPACING)
+    .into()
+}
+
+pub fn checkbox_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Checkbox Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Label Text").size(LABEL_SIZE),
@@ -814,6 +937,8 @@ pub fn checkbox_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
         ]
         .spacing(LABEL_SPACING),
 
+        label_position_control(widget_id, props.label_position),
+
         checkbox("Default Checked State", props.checkbox_checked)
             .on_toggle(move |v| Message::PropertyChanged(widget_id, PropertyChange::CheckboxChecked(v))),
 
@@ -829,17 +954,17 @@ pub fn checkbox_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn toggler_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn toggler_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Toggler Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Label Text").size(LABEL_SIZE),
@@ -879,6 +1004,8 @@ pub fn toggler_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
         ]
         .spacing(LABEL_SPACING),
 
+        label_position_control(widget_id, props.label_position),
+
         checkbox("Default Active State", props.toggler_active)
             .on_toggle(move |v| Message::PropertyChanged(widget_id, PropertyChange::TogglerActive(v))),
 
@@ -894,17 +1021,21 @@ pub fn toggler_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn radio_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn radio_controls<'a>(
+    hierarchy: &'a WidgetHierarchy,
+    widget_id: WidgetId,
+    option_import: Option<&'a OptionImportForm>,
+) -> Element<'a, Message> {
     let widget = hierarchy.get_widget_by_id(widget_id).unwrap();
     let props = &widget.properties;
 
     let content = column![
         text("Radio Button Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(hierarchy, widget_id, &props.widget_name),
 
         column![
             text("Label Text").size(LABEL_SIZE),
@@ -947,50 +1078,43 @@ pub fn radio_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, t
         ]
         .spacing(SECTION_SPACING),
 
-        column![
-            text("Options").size(SECTION_SIZE),
-            column(
-                props.radio_options
-                    .iter()
-                    .enumerate()
-                    .map(|(i, option)| {
-                        let label = format!("Option {}", i + 1);
-                        row![
-                            text_input(&label, option)
-                                .on_input({
-                                    let index = i;
-                                    let existing = props.radio_options.clone();
-                                    move |v| {
-                                        let mut next = existing.clone();
-                                        if index < next.len() { next[index] = v; }
-                                        Message::PropertyChanged(widget_id, PropertyChange::RadioOptions(next))
-                                    }
-                                })
-                                .width(220),
-                            button("Remove")
-                                .on_press({
-                                    let index = i;
-                                    let mut next = props.radio_options.clone();
-                                    if index < next.len() && next.len() > 1 { next.remove(index); }
-                                    Message::PropertyChanged(widget_id, PropertyChange::RadioOptions(next))
-                                }),
-                        ]
-                        .spacing(SECTION_SPACING)
-                        .align_y(Alignment::Center)
-                        .into()
+        row![
+            column![
+                text("Orientation").size(LABEL_SIZE),
+                pick_list(
+                    vec![Orientation::Vertical, Orientation::Horizontal],
+                    Some(props.orientation),
+                    move |o| Message::PropertyChanged(widget_id, PropertyChange::Orientation(o))
+                )
+            ]
+            .spacing(LABEL_SPACING),
+
+            column![
+                text("Option Spacing").size(LABEL_SIZE),
+                row![
+                    slider(0.0..=50.0, props.spacing, move |v| {
+                        Message::PropertyChanged(widget_id, PropertyChange::Spacing(v))
                     })
-                    .collect::<Vec<Element<'a, Message>>>()
-            )
+                    .step(1.0)
+                    .width(200),
+                    text(format!("{:.0}px", props.spacing)).size(LABEL_SIZE).width(50),
+                ]
+                .spacing(SECTION_SPACING)
+                .align_y(Alignment::Center),
+            ]
             .spacing(LABEL_SPACING),
-            button("Add Option")
-                .on_press({
-                    let mut next = props.radio_options.clone();
-                    next.push(format!("Option {}", next.len() + 1));
-                    Message::PropertyChanged(widget_id, PropertyChange::RadioOptions(next))
-                })
         ]
         .spacing(SECTION_SPACING),
 
+        label_position_control(widget_id, props.label_position),
+
+        option_list_editor(
+            &props.radio_options,
+            move |edit| Message::PropertyChanged(widget_id, PropertyChange::RadioOptionsEdit(edit)),
+        ),
+
+        option_import_controls(widget_id, OptionTarget::Radio, props.radio_options.len(), option_import),
+
         column![
             text("Default Selection").size(LABEL_SIZE),
             pick_list(
@@ -1021,20 +1145,21 @@ pub fn radio_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, t
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, hierarchy, widget_id, theme, type_system))
-        .width(450)
-        .height(Length::Fixed(600.0))
-        .into()
+    content
 }
 
-pub fn picklist_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn picklist_controls<'a>(
+    h: &'a WidgetHierarchy,
+    widget_id: WidgetId,
+    option_import: Option<&'a OptionImportForm>,
+) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Pick List Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Placeholder Text").size(LABEL_SIZE),
@@ -1054,6 +1179,8 @@ pub fn picklist_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
         ]
         .spacing(LABEL_SPACING),
 
+        preview_selection_control(widget_id, props.picklist_options.clone(), props.preview_selection.clone()),
+
         column![
             text("Options").size(SECTION_SIZE),
             column(
@@ -1103,6 +1230,8 @@ pub fn picklist_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
                 })
                 .style(button::success)
                 .padding(Padding::new(5.0)),
+
+            option_import_controls(widget_id, OptionTarget::PickList, props.picklist_options.len(), option_import),
         ]
         .spacing(SECTION_SPACING),
 
@@ -1118,48 +1247,34 @@ pub fn picklist_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn slider_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn slider_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = hierarchy.get_widget_by_id(widget_id).unwrap();
     let props = &widget.properties;
 
-    let min_str = format!("{:.3}", props.slider_min);
-    let max_str = format!("{:.3}", props.slider_max);
-    let step_str = format!("{:.3}", props.slider_step);
-    let slider_height = format!("{:.0}", props.slider_height);
-
     let content = column![
         text("Slider Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(hierarchy, widget_id, &props.widget_name),
 
         row![
             column![
                 text("Min").size(LABEL_SIZE),
-                text_input("min", &min_str).on_input(move |s| {
-                    let v = parse_f32(&s, props.slider_min);
-                    Message::PropertyChanged(widget_id, PropertyChange::SliderMin(v))
-                }).width(120),
+                numeric_input(hierarchy, widget_id, NumericProperty::SliderMin, "min").width(120),
             ]
             .spacing(LABEL_SPACING),
             
             column![
                 text("Max").size(LABEL_SIZE),
-                text_input("max", &max_str).on_input(move |s| {
-                    let v = parse_f32(&s, props.slider_max);
-                    Message::PropertyChanged(widget_id, PropertyChange::SliderMax(v))
-                }).width(120),
+                numeric_input(hierarchy, widget_id, NumericProperty::SliderMax, "max").width(120),
             ]
             .spacing(LABEL_SPACING),
             
             column![
                 text("Step").size(LABEL_SIZE),
-                text_input("step", &step_str).on_input(move |s| {
-                    let v = parse_f32(&s, props.slider_step.max(0.000_001));
-                    Message::PropertyChanged(widget_id, PropertyChange::SliderStep(v.max(0.000_001)))
-                }).width(120),
+                numeric_input(hierarchy, widget_id, NumericProperty::SliderStep, "step").width(120),
             ]
             .spacing(LABEL_SPACING),
         ]
@@ -1191,9 +1306,7 @@ pub fn slider_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId,
             ),
             column![
                 text("Height (Thickness)").size(LABEL_SIZE),
-                text_input("px", &slider_height).on_input(move |s| {
-                    Message::PropertyChanged(widget_id, PropertyChange::SliderHeight(parse_f32(&s, props.slider_height)))
-                }).width(120)
+                numeric_input(hierarchy, widget_id, NumericProperty::SliderHeight, "px").width(120)
             ]
             .spacing(LABEL_SPACING),
         ]
@@ -1202,48 +1315,34 @@ pub fn slider_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId,
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, hierarchy, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn vertical_slider_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn vertical_slider_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = hierarchy.get_widget_by_id(widget_id).unwrap();
     let props = &widget.properties;
 
-    let min_str = format!("{:.3}", props.slider_min);
-    let max_str = format!("{:.3}", props.slider_max);
-    let step_str = format!("{:.3}", props.slider_step);
-    let slider_width = format!("{:.0}", props.slider_width);
-
     let content = column![
         text("Vertical Slider Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(hierarchy, widget_id, &props.widget_name),
 
         row![
             column![
                 text("Min").size(LABEL_SIZE),
-                text_input("min", &min_str).on_input(move |s| {
-                    let v = parse_f32(&s, props.slider_min);
-                    Message::PropertyChanged(widget_id, PropertyChange::SliderMin(v))
-                }).width(120),
+                numeric_input(hierarchy, widget_id, NumericProperty::SliderMin, "min").width(120),
             ]
             .spacing(LABEL_SPACING),
             
             column![
                 text("Max").size(LABEL_SIZE),
-                text_input("max", &max_str).on_input(move |s| {
-                    let v = parse_f32(&s, props.slider_max);
-                    Message::PropertyChanged(widget_id, PropertyChange::SliderMax(v))
-                }).width(120),
+                numeric_input(hierarchy, widget_id, NumericProperty::SliderMax, "max").width(120),
             ]
             .spacing(LABEL_SPACING),
             
             column![
                 text("Step").size(LABEL_SIZE),
-                text_input("step", &step_str).on_input(move |s| {
-                    let v = parse_f32(&s, props.slider_step.max(0.000_001));
-                    Message::PropertyChanged(widget_id, PropertyChange::SliderStep(v.max(0.000_001)))
-                }).width(120),
+                numeric_input(hierarchy, widget_id, NumericProperty::SliderStep, "step").width(120),
             ]
             .spacing(LABEL_SPACING),
         ]
@@ -1275,9 +1374,7 @@ pub fn vertical_slider_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: W
             ),
             column![
                 text("Width (Thickness)").size(LABEL_SIZE),
-                text_input("px", &slider_width).on_input(move |s| {
-                    Message::PropertyChanged(widget_id, PropertyChange::SliderWidth(parse_f32(&s, props.slider_width)))
-                }).width(120)
+                numeric_input(hierarchy, widget_id, NumericProperty::SliderWidth, "px").width(120)
             ]
             .spacing(LABEL_SPACING),
         ]
@@ -1286,10 +1383,10 @@ pub fn vertical_slider_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: W
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, hierarchy, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn rule_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn rule_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).unwrap();
     let p = &widget.properties;
 
@@ -1332,21 +1429,30 @@ pub fn rule_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: The
             .spacing(LABEL_SPACING)
         ]
         .spacing(LABEL_SPACING),
+
+        size_controls_scrollable_aware(
+            p.width,
+            move |l| Message::PropertyChanged(widget_id, PropertyChange::Width(l)),
+            p.height,
+            move |l| Message::PropertyChanged(widget_id, PropertyChange::Height(l)),
+            h,
+            widget_id,
+        ),
     ]
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn scrollable_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn scrollable_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = hierarchy.get_widget_by_id(widget_id).unwrap();
     let props = &widget.properties;
 
     let content = column![
         text("Scrollable Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(hierarchy, widget_id, &props.widget_name),
 
         size_controls_scrollable_aware(
             props.width,
@@ -1369,7 +1475,7 @@ pub fn scrollable_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: Widget
 
         row![
             column![
-                text("Anchor X").size(LABEL_SIZE),
+                badged_label("Anchor X", widget, "anchor_x"),
                 pick_list(
                     vec![AnchorChoice::Start, AnchorChoice::End],
                     Some(AnchorChoice::from(props.anchor_x)),
@@ -1380,7 +1486,7 @@ pub fn scrollable_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: Widget
             .width(Length::Fill),
             
             column![
-                text("Anchor Y").size(LABEL_SIZE),
+                badged_label("Anchor Y", widget, "anchor_y"),
                 pick_list(
                     vec![AnchorChoice::Start, AnchorChoice::End],
                     Some(AnchorChoice::from(props.anchor_y)),
@@ -1395,17 +1501,17 @@ pub fn scrollable_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: Widget
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, hierarchy, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn space_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn space_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = hierarchy.get_widget_by_id(widget_id).unwrap();
     let props = &widget.properties;
 
     let content = column![
         text("Space Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(hierarchy, widget_id, &props.widget_name),
 
         column![
             text("Orientation").size(LABEL_SIZE),
@@ -1429,20 +1535,19 @@ pub fn space_controls<'a>(hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, t
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, hierarchy, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn progress_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn progress_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let w = h.get_widget_by_id(widget_id).unwrap();
     let p = &w.properties;
-    let girth_str = format!("{:.0}", p.progress_girth);
 
     let clamp_step = ((p.progress_max - p.progress_min) / 100.0).abs().max(0.001);
 
     let content = column![
         text("Progress Bar Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &p.widget_name),
+        widget_name(h, widget_id, &p.widget_name),
 
         row![
             text("Orientation").size(LABEL_SIZE).width(Length::Fixed(80.0)),
@@ -1468,9 +1573,7 @@ pub fn progress_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
                 ),
                 column![
                     text("Girth (Width)").size(LABEL_SIZE),
-                    text_input("px", &girth_str).on_input(move |s| {
-                        Message::PropertyChanged(widget_id, PropertyChange::ProgressGirth(parse_f32(&s, p.progress_girth)))
-                    }).width(120)
+                    numeric_input(h, widget_id, NumericProperty::ProgressGirth, "px").width(120)
                 ]
                 .spacing(LABEL_SPACING),
             ]
@@ -1487,9 +1590,7 @@ pub fn progress_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
                 ),
                 column![
                     text("Girth (Height)").size(LABEL_SIZE),
-                    text_input("px", &girth_str).on_input(move |s| {
-                        Message::PropertyChanged(widget_id, PropertyChange::ProgressGirth(parse_f32(&s, p.progress_girth)))
-                    }).width(120)
+                    numeric_input(h, widget_id, NumericProperty::ProgressGirth, "px").width(120)
                 ]
                 .spacing(LABEL_SPACING),
             ]
@@ -1538,21 +1639,49 @@ pub fn progress_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
             .align_y(Alignment::Center),
         ]
         .spacing(LABEL_SPACING),
+
+        column![
+            row![
+                checkbox("", p.preview_progress.is_some()).on_toggle(move |on| {
+                    Message::PropertyChanged(widget_id, PropertyChange::PreviewProgress(on.then_some(p.progress_value)))
+                }),
+                preview_only_label("Preview Value"),
+            ]
+            .align_y(Alignment::Center),
+            match p.preview_progress {
+                Some(preview) => row![
+                    slider(p.progress_min..=p.progress_max, preview, move |v| {
+                        Message::PropertyChanged(widget_id, PropertyChange::PreviewProgress(Some(v)))
+                    })
+                    .step(clamp_step)
+                    .width(250),
+                    text(format!("{:.02}", preview)).size(LABEL_SIZE).width(60),
+                ]
+                .spacing(SECTION_SPACING)
+                .align_y(Alignment::Center),
+                None => row![],
+            },
+        ]
+        .spacing(LABEL_SPACING),
     ]
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn image_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn image_controls<'a>(
+    h: &'a WidgetHierarchy,
+    widget_id: WidgetId,
+    library: &'a AssetLibrary,
+) -> Element<'a, Message> {
     let w = h.get_widget_by_id(widget_id).unwrap();
     let props = &w.properties;
 
     let content = column![
         text("Image Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
         
         row![
             text("Path").size(LABEL_SIZE).width(Length::Fixed(80.0)),
@@ -1561,6 +1690,8 @@ pub fn image_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Th
                 .width(Length::Fill),
         ]
         .spacing(SECTION_SPACING),
+
+        library_picker(library, AssetKind::Image, widget_id, props.library_asset, &props.image_path),
         
         row![
             text("Fit").size(LABEL_SIZE).width(Length::Fixed(80.0)),
@@ -1590,17 +1721,94 @@ pub fn image_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Th
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
+}
+
+/// Library entries of the widget's kind, with small previews. Picking one sets the path and
+/// keeps the entry, so the widget follows it when the entry is moved.
+fn library_picker<'a>(
+    library: &'a AssetLibrary,
+    kind: AssetKind,
+    widget_id: WidgetId,
+    asset_id: Option<Uuid>,
+    path: &str,
+) -> Element<'a, Message> {
+    let linked = library.linked(asset_id, path).map(|asset| asset.id);
+    let open_library = button(text("Manage…").size(LABEL_SIZE))
+        .style(button::text)
+        .on_press(Message::Assets(AssetMessage::Open));
+
+    let header = row![
+        text("From library").size(LABEL_SIZE).width(Length::Fixed(80.0)),
+        match linked {
+            Some(id) if library.is_missing(id) => text("file missing").size(LABEL_SIZE).style(text::danger),
+            Some(_) => text("linked").size(LABEL_SIZE).style(text::success),
+            None => text(""),
+        },
+        space::horizontal(),
+        open_library,
+    ]
+    .spacing(SECTION_SPACING)
+    .align_y(Alignment::Center);
+
+    let entries: Vec<Element<'a, Message>> = library.of_kind(kind)
+        .map(|asset| {
+            let missing = library.is_missing(asset.id);
+            button(
+                column![
+                    asset_thumbnail(asset, 24.0, missing),
+                    text(&asset.name).size(LABEL_SIZE),
+                ]
+                .spacing(2)
+                .align_x(Alignment::Center)
+            )
+            .style(if linked == Some(asset.id) { button::primary } else { button::secondary })
+            .padding(4)
+            .on_press(Message::Assets(AssetMessage::Use(widget_id, asset.id)))
+            .into()
+        })
+        .collect();
+
+    let entries: Element<'a, Message> = if entries.is_empty() {
+        text(format!("No {} assets in the library yet", kind)).size(LABEL_SIZE).into()
+    } else {
+        row(entries).spacing(LABEL_SPACING).wrap().into()
+    };
+
+    column![header, entries].spacing(LABEL_SPACING).into()
+}
+
+/// Small preview of a library entry, a placeholder while its file is missing
+pub fn asset_thumbnail<'a>(asset: &Asset, size: f32, missing: bool) -> Element<'a, Message> {
+    if missing {
+        return container(text("?").size(size * 0.6).style(text::danger))
+            .center(Length::Fixed(size))
+            .into();
+    }
+    match asset.kind {
+        AssetKind::Svg => iced::widget::svg(iced::widget::svg::Handle::from_path(&asset.path))
+            .width(size)
+            .height(size)
+            .into(),
+        AssetKind::Image => iced::widget::image(iced::widget::image::Handle::from_path(&asset.path))
+            .width(size)
+            .height(size)
+            .into(),
+    }
 }
 
-pub fn svg_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn svg_controls<'a>(
+    h: &'a WidgetHierarchy,
+    widget_id: WidgetId,
+    library: &'a AssetLibrary,
+) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).unwrap();
     let props = &widget.properties;
 
     let content = column![
         text("SVG Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
         
         row![
             text("Path").size(LABEL_SIZE).width(Length::Fixed(80.0)),
@@ -1609,6 +1817,8 @@ pub fn svg_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Them
                 .width(Length::Fill),
         ]
         .spacing(SECTION_SPACING),
+
+        library_picker(library, AssetKind::Svg, widget_id, props.library_asset, &props.svg_path),
         
         row![
             text("Fit").size(LABEL_SIZE).width(Length::Fixed(80.0)),
@@ -1626,6 +1836,24 @@ pub fn svg_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Them
         ]
         .spacing(SECTION_SPACING),
 
+        row![
+            text("Tint").size(LABEL_SIZE).width(Length::Fixed(80.0)),
+            pick_list(
+                SvgTint::ALL,
+                Some(props.svg_tint),
+                move |v| Message::PropertyChanged(widget_id, PropertyChange::SvgTint(v))
+            )
+        ]
+        .spacing(SECTION_SPACING),
+
+        if props.svg_tint == SvgTint::Custom {
+            color_hex_input("Tint Color", props.svg_tint_color, move |c| {
+                Message::PropertyChanged(widget_id, PropertyChange::SvgTintColor(c))
+            })
+        } else {
+            column![].into()
+        },
+
         size_controls_scrollable_aware(
             props.width,
             move |l| Message::PropertyChanged(widget_id, PropertyChange::Width(l)),
@@ -1638,17 +1866,17 @@ pub fn svg_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Them
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn tooltip_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn tooltip_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let w = h.get_widget_by_id(widget_id).unwrap();
     let p = &w.properties;
 
     let content = column![
         text("Tooltip Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &p.widget_name),
+        widget_name(h, widget_id, &p.widget_name),
         
         row![
             text("Text").size(LABEL_SIZE).width(Length::Fixed(80.0)),
@@ -1684,14 +1912,15 @@ pub fn tooltip_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
 pub fn combobox_controls<'a>(
     h: &'a WidgetHierarchy, 
     widget_id: WidgetId, 
-    theme: Theme,
-    type_system: &'a TypeSystem
+    type_system: &'a TypeSystem,
+    enum_form: Option<&'a NewEnumForm>,
+    option_import: Option<&'a OptionImportForm>,
 ) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
@@ -1709,7 +1938,7 @@ pub fn combobox_controls<'a>(
     let content = column![
         text("ComboBox Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Placeholder Text").size(LABEL_SIZE),
@@ -1719,6 +1948,8 @@ pub fn combobox_controls<'a>(
         ]
         .spacing(LABEL_SPACING),
 
+        preview_selection_control(widget_id, props.combobox_options.clone(), props.preview_selection.clone()),
+
         column![
             text("Font Size").size(LABEL_SIZE),
             row![
@@ -1764,9 +1995,6 @@ pub fn combobox_controls<'a>(
                             text("No enums defined yet")
                                 .size(LABEL_SIZE)
                                 .style(text::warning),
-                            button("Create Enum")
-                                .on_press(Message::OpenTypeEditor)
-                                .style(button::primary)
                         ]
                         .spacing(LABEL_SPACING)
                     } else {
@@ -1785,10 +2013,26 @@ pub fn combobox_controls<'a>(
                             .placeholder("Choose an enum...")
                             .width(200)
                         ]
-                    }
+                    },
+                    props.referenced_enum
+                        .filter(|enum_id| type_system.get_enum(*enum_id).is_some())
+                        .map(|enum_id| -> Element<'a, Message> {
+                            button(text("Edit variants").size(LABEL_SIZE))
+                                .on_press(Message::EditEnum(enum_id))
+                                .style(button::secondary)
+                                .into()
+                        }),
                 ]
                 .spacing(SECTION_SPACING)
                 .align_y(Alignment::Center),
+
+                match enum_form.filter(|form| form.widget_id == widget_id) {
+                    Some(form) => new_enum_form(form),
+                    None => button(text("Create new enum…").size(LABEL_SIZE))
+                        .on_press(Message::EnumForm(EnumFormMessage::Open(widget_id)))
+                        .style(button::primary)
+                        .into(),
+                },
                 
                 if let Some(ref enum_name) = props.referenced_enum {
                     if let Some(enum_def) = type_system.get_enum(enum_name.clone()) {
@@ -1871,10 +2115,50 @@ pub fn combobox_controls<'a>(
                         Message::PropertyChanged(widget_id, PropertyChange::ComboBoxState(new_options))
                     })
                     .style(button::success),
+
+                option_import_controls(widget_id, OptionTarget::ComboBox, props.combobox_options.len(), option_import),
             ]
             .spacing(SECTION_SPACING)
         },
 
+        column![
+            text("Appearance").size(SECTION_SIZE),
+            text("Overrides for themes whose default contrast doesn't work")
+                .size(LABEL_SIZE)
+                .color(Color::from_rgb(0.6, 0.6, 0.6)),
+
+            optional_color_input("Input background", props.combobox_input_background, Color::WHITE, move |c| {
+                Message::PropertyChanged(widget_id, PropertyChange::ComboBoxInputBackground(c))
+            }),
+            optional_color_input("Input text color", props.combobox_input_text_color, Color::BLACK, move |c| {
+                Message::PropertyChanged(widget_id, PropertyChange::ComboBoxInputTextColor(c))
+            }),
+            optional_color_input("Menu background", props.combobox_menu_background, Color::WHITE, move |c| {
+                Message::PropertyChanged(widget_id, PropertyChange::ComboBoxMenuBackground(c))
+            }),
+
+            checkbox("Limit menu height", props.combobox_menu_height.is_some())
+                .on_toggle(move |v| Message::PropertyChanged(
+                    widget_id,
+                    PropertyChange::ComboBoxMenuHeight(v.then_some(150.0))
+                )),
+            if let Some(menu_height) = props.combobox_menu_height {
+                row![
+                    slider(50.0..=400.0, menu_height, move |v| {
+                        Message::PropertyChanged(widget_id, PropertyChange::ComboBoxMenuHeight(Some(v)))
+                    })
+                    .step(10.0)
+                    .width(200),
+                    text(format!("{:.0}px", menu_height)).size(LABEL_SIZE).width(50),
+                ]
+                .spacing(SECTION_SPACING)
+                .align_y(Alignment::Center)
+            } else {
+                row![]
+            },
+        ]
+        .spacing(LABEL_SPACING),
+
         column![
             text("ComboBox Event Handlers").size(SECTION_SIZE),
             text("Enable optional event handlers for advanced interactions")
@@ -1922,30 +2206,69 @@ pub fn combobox_controls<'a>(
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, Some(type_system))).into()
+    content
 }
 
-pub fn markdown_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn markdown_controls<'a>(
+    h: &'a WidgetHierarchy,
+    widget_id: WidgetId,
+    theme: Theme,
+    preview_open: bool,
+) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
-    let content = column![
-        text("Markdown Properties").size(TITLE_SIZE),
+    let editor = text_editor(&props.markdown_source)
+        .placeholder("Markdown text here")
+        .on_action(move |act| Message::PropertyChanged(widget_id, PropertyChange::MarkdownContent(act)))
+        .height(Length::Fixed(180.0));
 
-        widget_name(widget_id, &props.widget_name),
+    // Renders the items parsed on edit, so nothing is parsed per frame. Links go nowhere.
+    let source: Element<'a, Message> = if preview_open {
+        let preview = markdown::view(
+            &props.markdown_content,
+            markdown::Settings::with_text_size(props.markdown_text_size, theme.clone()),
+        )
+        .map(|_| Message::Noop);
 
-        column![
-            text("Markdown Content").size(LABEL_SIZE),
-            text_editor(&props.markdown_source)
-                .placeholder("Markdown text here")
-                .on_action(move |act| Message::PropertyChanged(widget_id, PropertyChange::MarkdownContent(act)))
+        row![
+            editor.width(Length::FillPortion(1)),
+            container(scrollable(preview).height(Length::Fill))
+                .padding(5)
+                .width(Length::FillPortion(1))
                 .height(Length::Fixed(180.0))
-                .width(350.0),
+                .style(container::bordered_box),
         ]
-        .spacing(LABEL_SPACING),
-
+        .spacing(SECTION_SPACING)
+        .into()
+    } else {
+        editor.width(350.0).into()
+    };
+
+    let preview_status = if props.markdown_edited_at.is_some() { "Updating preview…" } else { "" };
+
+    let content = column![
+        text("Markdown Properties").size(TITLE_SIZE),
+
+        widget_name(h, widget_id, &props.widget_name),
+
+        column![
+            row![
+                text("Markdown Content").size(LABEL_SIZE),
+                space::horizontal(),
+                text(preview_status).size(LABEL_SIZE),
+                button(text(if preview_open { "Hide Preview" } else { "Show Preview" }).size(LABEL_SIZE))
+                    .style(button::text)
+                    .on_press(Message::MarkdownPreviewToggled(!preview_open)),
+            ]
+            .spacing(SECTION_SPACING)
+            .align_y(Alignment::Center),
+            source,
+        ]
+        .spacing(LABEL_SPACING),
+
         column![
-            text("Text Size").size(LABEL_SIZE),
+            badged_label("Text Size", widget, "markdown_text_size"),
             row![
                 slider(8.0..=32.0, props.markdown_text_size, move |v| {
                     Message::PropertyChanged(widget_id, PropertyChange::MarkdownTextSize(v))
@@ -1971,17 +2294,17 @@ pub fn markdown_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme:
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn qrcode_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn qrcode_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("QR Code Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Data to Encode").size(LABEL_SIZE),
@@ -2018,17 +2341,17 @@ pub fn qrcode_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: T
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn stack_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn stack_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Stack Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         text("Stack overlays its children on top of each other.")
             .size(LABEL_SIZE)
@@ -2046,21 +2369,23 @@ pub fn stack_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Th
             h,
             widget_id,
         ),
+
+        clip_control(widget),
     ]
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn mousearea_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn mousearea_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Mouse Area Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         text("Mouse Area captures mouse events over its child widget.")
             .size(LABEL_SIZE)
@@ -2124,6 +2449,15 @@ pub fn mousearea_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme
             ].spacing(LABEL_SPACING),
         ].spacing(SECTION_SPACING),
 
+        if props.mousearea_on_press {
+            file_dialog_controls(h, widget_id)
+        } else {
+            text("Enable on_press to open a file dialog from it")
+                .size(LABEL_SIZE)
+                .color(Color::from_rgb(0.5, 0.5, 0.5))
+                .into()
+        },
+
         size_controls_scrollable_aware(
             props.width,
             move |l| Message::PropertyChanged(widget_id, PropertyChange::Width(l)),
@@ -2136,17 +2470,17 @@ pub fn mousearea_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn themer_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn themer_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme) -> Element<'a, Message> {
     let widget = h.get_widget_by_id(widget_id).expect("widget exists");
     let props = &widget.properties;
 
     let content = column![
         text("Themer Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
 
         column![
             text("Theme").size(LABEL_SIZE),
@@ -2175,17 +2509,17 @@ pub fn themer_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: T
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
 }
 
-pub fn pin_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
+pub fn pin_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
     let w = h.get_widget_by_id(widget_id).unwrap();
     let props = &w.properties;
 
     let content = column![
         text("Pin Properties").size(TITLE_SIZE),
 
-        widget_name(widget_id, &props.widget_name),
+        widget_name(h, widget_id, &props.widget_name),
         
         row![
             text("Position").size(LABEL_SIZE).width(Length::Fixed(80.0)),
@@ -2205,11 +2539,161 @@ pub fn pin_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId, theme: Them
     .spacing(MAIN_SPACING)
     .into();
 
-    scrollable(add_code_preview(content, h, widget_id, theme, type_system)).into()
+    content
+}
+
+/// Add / remove / reorder editor for a list of option labels
+pub fn option_list_editor<'a, F>(options: &'a [String], on_edit: F) -> Element<'a, Message>
+where
+    F: Fn(OptionListEdit) -> Message + Copy + 'a,
+{
+    let invalid = invalid_option_labels(options);
+    let last = options.len().saturating_sub(1);
+
+    column![
+        text("Options").size(SECTION_SIZE),
+        column(
+            options
+                .iter()
+                .enumerate()
+                .map(|(i, option)| {
+                    row![
+                        text_input(&format!("Option {}", i + 1), option)
+                            .on_input(move |v| on_edit(OptionListEdit::Rename(i, v)))
+                            .width(180),
+                        button(text("↑").size(LABEL_SIZE))
+                            .on_press_maybe((i > 0).then(|| on_edit(OptionListEdit::MoveUp(i))))
+                            .style(button::secondary),
+                        button(text("↓").size(LABEL_SIZE))
+                            .on_press_maybe((i < last).then(|| on_edit(OptionListEdit::MoveDown(i))))
+                            .style(button::secondary),
+                        button("Remove")
+                            .on_press_maybe((options.len() > 1).then(|| on_edit(OptionListEdit::Remove(i))))
+                            .style(button::danger)
+                            .padding(Padding::new(5.0)),
+                    ]
+                    .spacing(LABEL_SPACING)
+                    .align_y(Alignment::Center)
+                    .into()
+                })
+                .collect::<Vec<Element<'a, Message>>>()
+        )
+        .spacing(LABEL_SPACING),
+
+        if invalid.is_empty() {
+            column![]
+        } else {
+            column![
+                text("Option labels must be unique and not empty")
+                    .size(LABEL_SIZE)
+                    .color(Color::from_rgb(0.8, 0.3, 0.3))
+            ]
+        },
+
+        button("Add Option")
+            .on_press(on_edit(OptionListEdit::Add))
+            .style(button::success)
+            .padding(Padding::new(5.0)),
+    ]
+    .spacing(SECTION_SPACING)
+    .into()
+}
+
+/// Import and export buttons under an option list, with the import form when it's open for it
+fn option_import_controls<'a>(
+    widget_id: WidgetId,
+    target: OptionTarget,
+    len: usize,
+    form: Option<&'a OptionImportForm>,
+) -> Element<'a, Message> {
+    let mut content = column![
+        row![
+            button(text("Import…").size(LABEL_SIZE))
+                .on_press(Message::OptionImport(OptionImportMessage::Open(widget_id, target)))
+                .style(button::secondary),
+            button(text("Export options").size(LABEL_SIZE))
+                .on_press(Message::OptionImport(OptionImportMessage::Export(widget_id, target)))
+                .style(button::secondary),
+        ]
+        .spacing(SECTION_SPACING),
+    ]
+    .spacing(LABEL_SPACING);
+
+    if target.is_too_long(len) {
+        content = content.push(
+            text(format!("{} options may be slow to draw here, a ComboBox filters them as you type", len))
+                .size(LABEL_SIZE)
+                .style(text::warning),
+        );
+    }
+
+    match form.filter(|form| form.widget_id == widget_id && form.target == target) {
+        Some(form) => content.push(option_import_form(form)).into(),
+        None => content.into(),
+    }
+}
+
+fn option_import_form<'a>(form: &'a OptionImportForm) -> Element<'a, Message> {
+    let mut content = column![
+        text("Import options").size(SECTION_SIZE),
+        text("Values separated by commas or new lines. Quote a value to keep a comma in it.").size(LABEL_SIZE),
+        text_editor(&form.text)
+            .on_action(|action| Message::OptionImport(OptionImportMessage::TextEdited(action)))
+            .height(120),
+        row![
+            button(text("Load .csv or .txt…").size(LABEL_SIZE))
+                .on_press(Message::OptionImport(OptionImportMessage::ChooseFile))
+                .style(button::secondary),
+            checkbox("Append to the current options", form.append)
+                .on_toggle(|append| Message::OptionImport(OptionImportMessage::AppendToggled(append)))
+                .size(14)
+                .text_size(LABEL_SIZE),
+        ]
+        .spacing(SECTION_SPACING)
+        .align_y(Alignment::Center),
+    ]
+    .spacing(LABEL_SPACING);
+
+    if let Some(source) = &form.source {
+        content = content.push(text(format!("Loaded from {}", source.display())).size(LABEL_SIZE));
+    }
+    if let Some(error) = &form.error {
+        content = content.push(text(error).size(LABEL_SIZE).style(text::danger));
+    }
+
+    content.push(
+        row![
+            button(text(if form.append { "Append" } else { "Replace options" }).size(LABEL_SIZE))
+                .on_press(Message::OptionImport(OptionImportMessage::Apply))
+                .style(button::primary),
+            button(text("Cancel").size(LABEL_SIZE))
+                .on_press(Message::OptionImport(OptionImportMessage::Cancel))
+                .style(button::secondary),
+        ]
+        .spacing(SECTION_SPACING),
+    )
+    .into()
 }
 
-fn parse_f32(s: &str, default: f32) -> f32 {
-    s.trim().parse::<f32>().unwrap_or(default)
+/// Text field for a numeric property. Takes arithmetic such as `16*2`, applied on Enter or once
+/// another numeric field is edited; an expression that doesn't evaluate is kept and outlined.
+fn numeric_input<'a>(h: &WidgetHierarchy, widget_id: WidgetId, property: NumericProperty, placeholder: &str) -> text_input::TextInput<'a, Message> {
+    let field = NumericField { widget: widget_id, property };
+    let current = h.get_widget_by_id(widget_id)
+        .map(|widget| property.current(&widget.properties))
+        .unwrap_or_default();
+    let invalid = h.numeric_inputs().is_invalid(field);
+
+    text_input(placeholder, &h.numeric_inputs().text(field, current))
+        .on_input(move |s| Message::NumericEdited(field, s))
+        .on_submit(Message::NumericCommitted(field))
+        .style(move |theme: &Theme, status| {
+            let mut style = text_input::default(theme, status);
+            if invalid {
+                style.border.color = theme.palette().danger;
+            }
+            style
+        })
 }
 
 fn parse_color_hex(s: &str, default: Color) -> Color {
@@ -2232,7 +2716,7 @@ fn parse_color_hex(s: &str, default: Color) -> Color {
     }
 }
 
-fn color_to_hex(c: Color) -> String {
+pub fn color_to_hex(c: Color) -> String {
     let [r,g,b,a] = [
         (c.r * 255.0).round().clamp(0.0,255.0) as u8,
         (c.g * 255.0).round().clamp(0.0,255.0) as u8,
@@ -2243,19 +2727,302 @@ fn color_to_hex(c: Color) -> String {
     else { format!("#{:02X}{:02X}{:02X}{:02X}", r,g,b,a) }
 }
 
+/// Small warning shown next to a control whose value is set but that codegen doesn't emit yet
+fn coverage_badge<'a>(widget: &Widget, key: &str) -> Element<'a, Message> {
+    let dropped = !emitted_property_keys(widget.widget_type, &widget.properties).contains(key)
+        && widget.properties.differs_from_default(widget.widget_type, key);
+
+    if dropped {
+        text("not yet generated").size(LABEL_SIZE - 2.0).style(text::warning).into()
+    } else {
+        row![].into()
+    }
+}
+
+/// Label for preview data, which the live preview shows but codegen never emits
+fn preview_only_label<'a>(label: &'a str) -> Element<'a, Message> {
+    row![
+        text(label).size(LABEL_SIZE),
+        text("preview only, not generated").size(LABEL_SIZE - 2.0).color(Color::from_rgb(0.5, 0.5, 0.5)),
+    ]
+    .spacing(LABEL_SPACING)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Option a PickList or ComboBox shows as selected in the live preview
+fn preview_selection_control<'a>(widget_id: WidgetId, options: Vec<String>, selected: Option<String>) -> Element<'a, Message> {
+    let has_selection = selected.is_some();
+    column![
+        preview_only_label("Preview Selection"),
+        row![
+            pick_list(options, selected, move |selection| {
+                Message::PropertyChanged(widget_id, PropertyChange::PreviewSelection(Some(selection)))
+            })
+            .placeholder("None"),
+            button(text("Clear").size(LABEL_SIZE))
+                .style(button::text)
+                .on_press_maybe(has_selection.then_some(Message::PropertyChanged(widget_id, PropertyChange::PreviewSelection(None)))),
+        ]
+        .spacing(LABEL_SPACING)
+        .align_y(Alignment::Center),
+    ]
+    .spacing(LABEL_SPACING)
+    .into()
+}
+
+/// A Text the chosen path can be shown in, listed by its generated name
+#[derive(Debug, Clone, PartialEq)]
+struct PathText {
+    id: WidgetId,
+    name: String,
+}
+
+impl std::fmt::Display for PathText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// File dialog a press opens in the generated app, and the Text showing the chosen path
+fn file_dialog_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
+    let props = &h.get_widget_by_id(widget_id).expect("widget exists").properties;
+
+    let mut content = column![
+        text("Capabilities").size(SECTION_SIZE),
+        pick_list(
+            FileDialog::ALL,
+            Some(props.file_dialog),
+            move |v| Message::PropertyChanged(widget_id, PropertyChange::FileDialog(v)),
+        )
+        .width(250),
+    ]
+    .spacing(LABEL_SPACING);
+
+    if props.file_dialog != FileDialog::None {
+        let names = CodeGenerator::new(h, Theme::Light, None).resolved_widget_names();
+        let mut widgets = Vec::new();
+        collect_tree_order(h.root(), &mut widgets);
+        let texts: Vec<PathText> = widgets.iter()
+            .filter(|w| w.widget_type == WidgetType::Text)
+            .map(|w| PathText { id: w.id, name: names.get(&w.id).cloned().unwrap_or_default() })
+            .collect();
+        let selected = texts.iter().find(|t| Some(t.id) == props.file_dialog_path_text).cloned();
+        let has_text = selected.is_some();
+
+        content = content
+            .push(text("Show Chosen Path In").size(LABEL_SIZE))
+            .push(
+                row![
+                    pick_list(texts, selected, move |t| {
+                        Message::PropertyChanged(widget_id, PropertyChange::FileDialogPathText(Some(t.id)))
+                    })
+                    .placeholder("No Text"),
+                    button(text("Clear").size(LABEL_SIZE))
+                        .style(button::text)
+                        .on_press_maybe(has_text.then_some(Message::PropertyChanged(widget_id, PropertyChange::FileDialogPathText(None)))),
+                ]
+                .spacing(LABEL_SPACING)
+                .align_y(Alignment::Center),
+            )
+            .push(
+                text("The generated update returns a Task to run the dialog, and the rfd crate is added to Cargo.toml")
+                    .size(LABEL_SIZE - 1.0)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            );
+    }
+
+    content.into()
+}
+
+/// What a conditional style can follow. Checkboxes and Togglers are listed by generated name.
+#[derive(Debug, Clone, PartialEq)]
+enum ConditionChoice {
+    None,
+    Widget { id: WidgetId, name: String },
+    Flag,
+}
+
+impl std::fmt::Display for ConditionChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionChoice::None => write!(f, "None"),
+            ConditionChoice::Widget { name, .. } => write!(f, "{}", name),
+            ConditionChoice::Flag => write!(f, "Named Flag"),
+        }
+    }
+}
+
+/// Bool that switches a Container or TextInput to alternate colors, the colors, and a toggle
+/// to see either variant in the preview
+fn conditional_style_controls<'a>(h: &'a WidgetHierarchy, widget_id: WidgetId) -> Element<'a, Message> {
+    let widget = h.get_widget_by_id(widget_id).expect("widget exists");
+    let props = &widget.properties;
+
+    let names = CodeGenerator::new(h, Theme::Light, None).resolved_widget_names();
+    let mut widgets = Vec::new();
+    collect_tree_order(h.root(), &mut widgets);
+    let mut choices = vec![ConditionChoice::None];
+    choices.extend(widgets.iter()
+        .filter(|w| matches!(w.widget_type, WidgetType::Checkbox | WidgetType::Toggler))
+        .map(|w| ConditionChoice::Widget { id: w.id, name: names.get(&w.id).cloned().unwrap_or_default() }));
+    choices.push(ConditionChoice::Flag);
+
+    let selected = match &props.style_condition {
+        None => Some(ConditionChoice::None),
+        Some(StyleCondition::Flag(_)) => Some(ConditionChoice::Flag),
+        Some(StyleCondition::Widget(id)) => choices.iter()
+            .find(|choice| matches!(choice, ConditionChoice::Widget { id: choice_id, .. } if choice_id == id))
+            .cloned(),
+    };
+    let current_flag = match &props.style_condition {
+        Some(StyleCondition::Flag(flag)) => flag.clone(),
+        _ => "is_error".to_string(),
+    };
+
+    let mut content = column![
+        badged_label("Conditional Style", widget, "style_condition"),
+        pick_list(choices, selected, move |choice| {
+            let condition = match choice {
+                ConditionChoice::None => None,
+                ConditionChoice::Widget { id, .. } => Some(StyleCondition::Widget(id)),
+                ConditionChoice::Flag => Some(StyleCondition::Flag(current_flag.clone())),
+            };
+            Message::PropertyChanged(widget_id, PropertyChange::StyleCondition(condition))
+        })
+        .placeholder("Deleted widget")
+        .width(250),
+    ]
+    .spacing(LABEL_SPACING);
+
+    let Some(condition) = &props.style_condition else {
+        return content
+            .push(
+                text("Follow a Checkbox, a Toggler or a flag of your own to switch colors, like a red border while invalid")
+                    .size(LABEL_SIZE - 1.0)
+                    .color(Color::from_rgb(0.5, 0.5, 0.5)),
+            )
+            .into();
+    };
+
+    // Showing the condition true flips the followed widget itself, so the preview stays in step with it
+    let preview: Option<(bool, WidgetId, fn(bool) -> PropertyChange)> = match condition {
+        StyleCondition::Flag(flag) => {
+            content = content.push(
+                text_input("Flag name", flag)
+                    .on_input(move |v| Message::PropertyChanged(widget_id, PropertyChange::StyleCondition(Some(StyleCondition::Flag(v)))))
+                    .width(250),
+            );
+            Some((props.preview_condition, widget_id, PropertyChange::PreviewCondition))
+        }
+        StyleCondition::Widget(id) => match condition_source(h, *id) {
+            Some(source) if source.widget_type == WidgetType::Checkbox => {
+                Some((source.properties.checkbox_checked, source.id, PropertyChange::CheckboxChecked))
+            }
+            Some(source) => Some((source.properties.toggler_active, source.id, PropertyChange::TogglerActive)),
+            None => {
+                content = content.push(
+                    text("The Checkbox or Toggler this followed is gone, so no conditional style is generated")
+                        .size(LABEL_SIZE - 1.0)
+                        .style(text::warning),
+                );
+                None
+            }
+        },
+    };
+
+    content = content.push(
+        row![
+            color_hex_input("Border When True", props.condition_border_color, move |c| {
+                Message::PropertyChanged(widget_id, PropertyChange::ConditionBorderColor(c))
+            }),
+            color_hex_input("Background When True", props.condition_background_color, move |c| {
+                Message::PropertyChanged(widget_id, PropertyChange::ConditionBackgroundColor(c))
+            }),
+        ]
+        .spacing(SECTION_SPACING),
+    )
+    .push(
+        text("A transparent background keeps the base one")
+            .size(LABEL_SIZE - 1.0)
+            .color(Color::from_rgb(0.5, 0.5, 0.5)),
+    );
+
+    if widget.widget_type == WidgetType::Container && props.gradient_enabled {
+        content = content.push(
+            text("Not drawn while the gradient background is on")
+                .size(LABEL_SIZE - 1.0)
+                .style(text::warning),
+        );
+    }
+
+    if let Some((holds, target, change)) = preview {
+        content = content.push(
+            checkbox("Preview condition true", holds)
+                .on_toggle(move |b| Message::PropertyChanged(target, change(b))),
+        );
+    }
+
+    content.into()
+}
+
+/// Label followed by its coverage badge
+fn badged_label<'a>(label: &'a str, widget: &Widget, key: &str) -> Element<'a, Message> {
+    row![
+        text(label).size(LABEL_SIZE),
+        coverage_badge(widget, key),
+    ]
+    .spacing(LABEL_SPACING)
+    .align_y(Alignment::Center)
+    .into()
+}
+
 fn color_hex_input<'a, F>(label: &'a str, current: Color, on_change: F) -> Element<'a, Message>
 where F: Fn(Color) -> Message + 'a + Copy {
     let cur = color_to_hex(current);
+    let name: Element<'a, Message> = match color_names::caption(current) {
+        Some(caption) => text(caption).size(LABEL_SIZE - 1.0).color(Color::from_rgb(0.5, 0.5, 0.5)).into(),
+        None => Space::new().into(),
+    };
+    // Every color input sends a PropertyChanged, which the reference panel refills on a click
+    let eyedropper = match on_change(current) {
+        Message::PropertyChanged(widget_id, change) => Some(Message::ReferenceImage(ReferenceMessage::Target(widget_id, change))),
+        _ => None,
+    };
     column![
         text(label),
-        text_input("#RRGGBB or #RRGGBBAA", &cur)
-            .on_input(move |s| on_change(parse_color_hex(&s, current)))
-            .width(160)
+        row![
+            text_input("#RRGGBB or #RRGGBBAA", &cur)
+                .on_input(move |s| on_change(parse_color_hex(&s, current)))
+                .width(160),
+            button(text("From image").size(LABEL_SIZE))
+                .style(button::secondary)
+                .on_press_maybe(eyedropper),
+        ]
+        .spacing(LABEL_SPACING)
+        .align_y(Alignment::Center),
+        name,
     ]
     .spacing(5)
     .into()
 }
 
+/// Color input that only exists while its override is switched on
+fn optional_color_input<'a, F>(label: &'a str, current: Option<Color>, default: Color, on_change: F) -> Element<'a, Message>
+where F: Fn(Option<Color>) -> Message + 'a + Copy {
+    column![
+        checkbox(label, current.is_some())
+            .on_toggle(move |enabled| on_change(enabled.then_some(default))),
+        if let Some(color) = current {
+            color_hex_input("", color, move |c| on_change(Some(c)))
+        } else {
+            column![].into()
+        },
+    ]
+    .spacing(LABEL_SPACING)
+    .into()
+}
+
 /// Helper for scrollable-aware size controls
 pub fn size_controls_scrollable_aware<'a>(
     width_now: Length,
@@ -2593,201 +3360,59 @@ pub fn length_picker_with_draft<'a>(
 }
 
 pub fn padding_controls<'a>(
-    current_padding: Padding,
+    h: &WidgetHierarchy,
     widget_id: WidgetId,
     padding_mode: PaddingMode,
 ) -> Element<'a, Message> {
-    column![
-        text("Padding").size(SECTION_SIZE),
-        
-        // Mode selection
+    // Box model: one editable number per side around a content box,
+    // with a link toggle that cycles Uniform -> Symmetric -> Individual
+    let side_input = |side: PaddingSide| {
+        numeric_input(h, widget_id, NumericProperty::Padding(side), "0")
+            .size(LABEL_SIZE)
+            .width(50)
+    };
+
+    let link_label = match padding_mode {
+        PaddingMode::Uniform    => "Linked: all sides",
+        PaddingMode::Symmetric  => "Linked: vertical / horizontal",
+        PaddingMode::Individual => "Unlinked",
+    };
+
+    let box_model = container(
         column![
-            text("Padding Mode").size(LABEL_SIZE),
-            column![
-                radio(
-                    "Uniform - All sides equal",
-                    PaddingMode::Uniform,
-                    Some(padding_mode),
-                    move |mode| Message::PropertyChanged(
-                        widget_id,
-                        PropertyChange::PaddingMode(mode)
-                    )
-                ),
-                radio(
-                    "Symmetric - Vertical/Horizontal pairs",
-                    PaddingMode::Symmetric,
-                    Some(padding_mode),
-                    move |mode| Message::PropertyChanged(
-                        widget_id,
-                        PropertyChange::PaddingMode(mode)
-                    )
-                ),
-                radio(
-                    "Individual - Each side separate",
-                    PaddingMode::Individual,
-                    Some(padding_mode),
-                    move |mode| Message::PropertyChanged(
-                        widget_id,
-                        PropertyChange::PaddingMode(mode)
-                    )
-                ),
+            row![space::horizontal(), side_input(PaddingSide::Top), space::horizontal()],
+            row![
+                side_input(PaddingSide::Left),
+                container(text("content").size(LABEL_SIZE))
+                    .center_x(Length::Fill)
+                    .center_y(40)
+                    .style(container::bordered_box),
+                side_input(PaddingSide::Right),
             ]
-            .spacing(LABEL_SPACING)
+            .spacing(SECTION_SPACING)
+            .align_y(Alignment::Center),
+            row![space::horizontal(), side_input(PaddingSide::Bottom), space::horizontal()],
         ]
-        .spacing(LABEL_SPACING),
-        
-        // Controls based on mode
-        match padding_mode {
-            PaddingMode::Uniform => {
-                // Single slider controls all sides
-                column![
-                    text("All Sides").size(LABEL_SIZE),
-                    row![
-                        slider(0.0..=50.0, current_padding.top, move |v| {
-                            Message::PropertyChanged(
-                                widget_id,
-                                PropertyChange::PaddingUniform(v)
-                            )
-                        })
-                        .step(1.0)
-                        .width(250),
-                        text(format!("{:.0}px", current_padding.top))
-                            .size(LABEL_SIZE)
-                            .width(50),
-                    ]
-                    .spacing(SECTION_SPACING)
-                    .align_y(Alignment::Center),
-                ]
-                .spacing(LABEL_SPACING)
-            }
-            
-            PaddingMode::Symmetric => {
-                // Two sliders: vertical and horizontal
-                column![
-                    row![
-                        column![
-                            text("Vertical (Top/Bottom)").size(LABEL_SIZE),
-                            row![
-                                slider(0.0..=50.0, current_padding.top, move |v| {
-                                    Message::PropertyChanged(
-                                        widget_id,
-                                        PropertyChange::PaddingVertical(v)
-                                    )
-                                })
-                                .step(1.0)
-                                .width(200),
-                                text(format!("{:.0}px", current_padding.top))
-                                    .size(LABEL_SIZE)
-                                    .width(50),
-                            ]
-                            .spacing(SECTION_SPACING)
-                            .align_y(Alignment::Center),
-                        ]
-                        .spacing(LABEL_SPACING)
-                        .width(Length::Fill),
-                    ],
-                    row![
-                        column![
-                            text("Horizontal (Left/Right)").size(LABEL_SIZE),
-                            row![
-                                slider(0.0..=50.0, current_padding.left, move |v| {
-                                    Message::PropertyChanged(
-                                        widget_id,
-                                        PropertyChange::PaddingHorizontal(v)
-                                    )
-                                })
-                                .step(1.0)
-                                .width(200),
-                                text(format!("{:.0}px", current_padding.left))
-                                    .size(LABEL_SIZE)
-                                    .width(50),
-                            ]
-                            .spacing(SECTION_SPACING)
-                            .align_y(Alignment::Center),
-                        ]
-                        .spacing(LABEL_SPACING)
-                        .width(Length::Fill),
-                    ],
-                ]
-                .spacing(SECTION_SPACING)
-            }
-            
-            PaddingMode::Individual => {
-                // Four separate sliders in a 2x2 grid
-                column![
-                    row![
-                        column![
-                            text("Top").size(LABEL_SIZE),
-                            slider(0.0..=50.0, current_padding.top, move |v| {
-                                Message::PropertyChanged(
-                                    widget_id,
-                                    PropertyChange::PaddingTop(v)
-                                )
-                            })
-                            .step(1.0),
-                            text(format!("{:.0}px", current_padding.top))
-                                .size(LABEL_SIZE)
-                                .center(),
-                        ]
-                        .spacing(LABEL_SPACING)
-                        .width(Length::Fill),
-                        
-                        column![
-                            text("Right").size(LABEL_SIZE),
-                            slider(0.0..=50.0, current_padding.right, move |v| {
-                                Message::PropertyChanged(
-                                    widget_id,
-                                    PropertyChange::PaddingRight(v)
-                                )
-                            })
-                            .step(1.0),
-                            text(format!("{:.0}px", current_padding.right))
-                                .size(LABEL_SIZE)
-                                .center(),
-                        ]
-                        .spacing(LABEL_SPACING)
-                        .width(Length::Fill),
-                    ]
-                    .spacing(MAIN_SPACING),
-                    
-                    row![
-                        column![
-                            text("Bottom").size(LABEL_SIZE),
-                            slider(0.0..=50.0, current_padding.bottom, move |v| {
-                                Message::PropertyChanged(
-                                    widget_id,
-                                    PropertyChange::PaddingBottom(v)
-                                )
-                            })
-                            .step(1.0),
-                            text(format!("{:.0}px", current_padding.bottom))
-                                .size(LABEL_SIZE)
-                                .center(),
-                        ]
-                        .spacing(LABEL_SPACING)
-                        .width(Length::Fill),
-                        
-                        column![
-                            text("Left").size(LABEL_SIZE),
-                            slider(0.0..=50.0, current_padding.left, move |v| {
-                                Message::PropertyChanged(
-                                    widget_id,
-                                    PropertyChange::PaddingLeft(v)
-                                )
-                            })
-                            .step(1.0),
-                            text(format!("{:.0}px", current_padding.left))
-                                .size(LABEL_SIZE)
-                                .center(),
-                        ]
-                        .spacing(LABEL_SPACING)
-                        .width(Length::Fill),
-                    ]
-                    .spacing(MAIN_SPACING),
-                ]
-                .spacing(SECTION_SPACING)
-            }
-        },
+        .spacing(LABEL_SPACING)
+    )
+    .padding(SECTION_SPACING)
+    .width(300)
+    .style(container::rounded_box);
+
+    column![
+        row![
+            text("Padding").size(SECTION_SIZE),
+            space::horizontal(),
+            button(text(link_label).size(LABEL_SIZE))
+                .style(button::secondary)
+                .on_press(Message::PropertyChanged(
+                    widget_id,
+                    PropertyChange::PaddingMode(next_padding_mode(padding_mode)),
+                )),
+        ]
+        .width(300)
+        .align_y(Alignment::Center),
+        box_model,
     ]
     .spacing(SECTION_SPACING)
     .into()
@@ -2805,16 +3430,16 @@ pub fn information<'a>(theme: Theme, info: &'a str) -> Element<'a, Message> {
     ).into()
 }
 
-pub fn border_controls<'a>(
-    border_width: f32,
-    border_radius: f32,
-    widget_id: WidgetId,
-) -> Element<'a, Message> {
+pub fn border_controls<'a>(widget: &Widget) -> Element<'a, Message> {
+    let widget_id = widget.id;
+    let border_width = widget.properties.border_width;
+    let border_radius = widget.properties.border_radius;
+
     column![
         text("Border").size(SECTION_SIZE),
         row![
             column![
-                text("Width").size(LABEL_SIZE),
+                badged_label("Width", widget, "border_width"),
                 slider(0.0..=10.0, border_width, move |v| {
                     Message::PropertyChanged(widget_id, PropertyChange::BorderWidth(v))
                 })
@@ -2827,7 +3452,7 @@ pub fn border_controls<'a>(
             .width(Length::Fill),
             
             column![
-                text("Radius").size(LABEL_SIZE),
+                badged_label("Radius", widget, "border_radius"),
                 slider(0.0..=30.0, border_radius, move |v| {
                     Message::PropertyChanged(widget_id, PropertyChange::BorderRadius(v))
                 })
@@ -2845,7 +3470,486 @@ pub fn border_controls<'a>(
     .into()
 }
 
-pub fn clip_control<'a>(widget_id: WidgetId, clipped: bool) -> Element<'a, Message>{
+/// Floating anchor and offsets for a direct child of a Stack
+pub fn stack_layer_controls<'a>(h: &WidgetHierarchy, widget: &Widget) -> Element<'a, Message> {
+    let widget_id = widget.id;
+    let props = &widget.properties;
+
+    let settings: Element<'a, Message> = match props.float_anchor {
+        Some(anchor) => {
+            let offsets: Element<'a, Message> = if anchor == FloatAnchor::Center {
+                text("A centered layer has no edge to offset from")
+                    .size(LABEL_SIZE)
+                    .color(Color::from_rgb(0.6, 0.6, 0.6))
+                    .into()
+            } else {
+                row![
+                    text("Offset X").size(LABEL_SIZE),
+                    numeric_input(h, widget_id, NumericProperty::FloatOffsetX, "0").width(60),
+                    text("Offset Y").size(LABEL_SIZE),
+                    numeric_input(h, widget_id, NumericProperty::FloatOffsetY, "0").width(60),
+                ]
+                .spacing(LABEL_SPACING)
+                .align_y(Alignment::Center)
+                .into()
+            };
+            column![
+                row![
+                    text("Anchor").size(LABEL_SIZE),
+                    pick_list(FloatAnchor::ALL, Some(anchor), move |a| {
+                        Message::PropertyChanged(widget_id, PropertyChange::FloatAnchor(Some(a)))
+                    }),
+                ]
+                .spacing(LABEL_SPACING)
+                .align_y(Alignment::Center),
+                offsets,
+            ]
+            .spacing(SECTION_SPACING)
+            .into()
+        }
+        None => column![].into(),
+    };
+
+    column![
+        text("Stack Layer").size(SECTION_SIZE),
+        checkbox("Float over the other layers", props.float_anchor.is_some())
+            .on_toggle(move |on| {
+                Message::PropertyChanged(widget_id, PropertyChange::FloatAnchor(on.then_some(FloatAnchor::TopRight)))
+            }),
+        settings,
+    ]
+    .spacing(SECTION_SPACING)
+    .into()
+}
+
+/// Section marker and nav button name for a direct child of a Column
+pub fn section_anchor_controls<'a>(widget: &Widget) -> Element<'a, Message> {
+    let widget_id = widget.id;
+    let props = &widget.properties;
+
+    let mut content = column![
+        text("Section").size(SECTION_SIZE),
+        checkbox("Mark as a section in the nav row", props.section_anchor)
+            .on_toggle(move |on| Message::PropertyChanged(widget_id, PropertyChange::SectionAnchor(on))),
+    ]
+    .spacing(SECTION_SPACING);
+
+    if props.section_anchor {
+        content = content.push(
+            row![
+                text("Name").size(LABEL_SIZE),
+                text_input(&widget.name, &props.section_name)
+                    .on_input(move |v| Message::PropertyChanged(widget_id, PropertyChange::SectionName(v)))
+                    .width(200),
+            ]
+            .spacing(LABEL_SPACING)
+            .align_y(Alignment::Center),
+        )
+        .push(
+            text("The nav scrolls to an offset estimated from the heights in the builder")
+                .size(LABEL_SIZE - 1.0)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    content.into()
+}
+
+/// Small colored label for a tag, with a remove button when `on_remove` is given
+pub fn tag_chip<'a>(tag: &str, on_remove: Option<Message>) -> Element<'a, Message> {
+    labelled_tag_chip(tag, tag.to_string(), on_remove)
+}
+
+/// A tag's chip showing something besides its name, in the tag's color
+fn labelled_tag_chip<'a>(tag: &str, label: String, on_remove: Option<Message>) -> Element<'a, Message> {
+    let color = tags::color(tag);
+    let mut content = row![text(label).size(LABEL_SIZE - 2.0).color(Color::WHITE)]
+        .spacing(4)
+        .align_y(Alignment::Center);
+    if let Some(message) = on_remove {
+        content = content.push(
+            button(text("×").size(LABEL_SIZE - 2.0).color(Color::WHITE))
+                .style(button::text)
+                .padding(0)
+                .on_press(message),
+        );
+    }
+    container(content)
+        .padding(Padding::from([1, 6]))
+        .style(move |_: &Theme| container::Style {
+            background: Some(color.into()),
+            border: iced::Border { radius: 8.0.into(), ..Default::default() },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// A widget's tags as chips, for its tree row
+pub fn tag_chips<'a>(tags: &[String]) -> Element<'a, Message> {
+    row(tags.iter().map(|tag| tag_chip(tag, None))).spacing(3).into()
+}
+
+/// Tags of one widget, each removable, and an input to add more
+pub fn tag_controls<'a>(widget: &Widget, draft: &'a str, known: &BTreeMap<String, usize>) -> Element<'a, Message> {
+    let widget_id = widget.id;
+    let tags = &widget.properties.tags;
+    let chips = tags.iter().map(|tag| {
+        tag_chip(tag, Some(Message::PropertyChanged(widget_id, PropertyChange::RemoveTag(tag.clone()))))
+    });
+
+    column![
+        text("Tags").size(SECTION_SIZE),
+        row(chips).spacing(4).wrap(),
+        tag_input(draft, tags::suggestions(known, draft, tags), move |tag| {
+            Message::PropertyChanged(widget_id, PropertyChange::AddTag(tag))
+        }),
+    ]
+    .spacing(SECTION_SPACING)
+    .into()
+}
+
+/// Style picker of a Container, with saving its look as a new style. `promote` is the
+/// name being typed when the save form is open for this widget.
+pub fn named_style_controls<'a>(widget: &Widget, registry: &'a StyleRegistry, promote: Option<&'a str>) -> Element<'a, Message> {
+    if !named_styles::takes_named_style(widget.widget_type) {
+        return column![].into();
+    }
+    let widget_id = widget.id;
+    let props = &widget.properties;
+    let own = ContainerLook::of(props);
+    let choices = registry.choices();
+    let selected = choices.iter().find(|choice| choice.id == props.named_style).cloned();
+    let picker = pick_list(choices, selected, move |choice| {
+        let look = choice.id.and_then(|id| registry.get(id)).map_or(own, |style| style.look);
+        Message::PropertyChanged(widget_id, PropertyChange::NamedStyle(choice.id, look))
+    })
+    .text_size(LABEL_SIZE);
+    let mut pick_row = row![picker].spacing(LABEL_SPACING).align_y(Alignment::Center);
+    if let Some(id) = props.named_style {
+        pick_row = pick_row.push(
+            button(text("Delete style").size(LABEL_SIZE))
+                .style(button::danger)
+                .on_press(Message::Styles(StyleMessage::Delete(id))),
+        );
+    }
+
+    let save: Element<'a, Message> = match promote {
+        Some(name) => row![
+            text_input("Style name", name)
+                .size(LABEL_SIZE)
+                .on_input(|v| Message::Styles(StyleMessage::NameChanged(v)))
+                .on_submit(Message::Styles(StyleMessage::Promote)),
+            button(text("Save").size(LABEL_SIZE))
+                .on_press_maybe((!name.trim().is_empty()).then_some(Message::Styles(StyleMessage::Promote))),
+            button(text("Cancel").size(LABEL_SIZE))
+                .style(button::secondary)
+                .on_press(Message::Styles(StyleMessage::CancelPromote)),
+        ]
+        .spacing(LABEL_SPACING)
+        .align_y(Alignment::Center)
+        .into(),
+        None => button(text("Save look as style").size(LABEL_SIZE))
+            .style(button::secondary)
+            .on_press(Message::Styles(StyleMessage::StartPromote(widget_id)))
+            .into(),
+    };
+
+    let mut section = column![
+        text("Named Style").size(SECTION_SIZE),
+        pick_row,
+        save,
+    ]
+    .spacing(LABEL_SPACING);
+    if props.named_style.is_some() {
+        let note = if props.gradient_enabled || props.style_condition.is_some() {
+            "The gradient or condition style is generated instead of this style"
+        } else {
+            "Background, border and shadow edits change every container using this style"
+        };
+        section = section.push(text(note).size(LABEL_SIZE - 1.0).style(text::secondary));
+    }
+    section.into()
+}
+
+/// Tags across a multi-selection with how many of the selected widgets carry each.
+/// Adding tags every selected widget, removing untags every one.
+pub fn bulk_tag_controls<'a>(selected: &[&Widget], draft: &'a str, known: &BTreeMap<String, usize>) -> Element<'a, Message> {
+    let mut on_selection: BTreeMap<&str, usize> = BTreeMap::new();
+    for widget in selected {
+        for tag in &widget.properties.tags {
+            *on_selection.entry(tag.as_str()).or_insert(0) += 1;
+        }
+    }
+    let everywhere: Vec<String> = on_selection.iter()
+        .filter(|&(_, &count)| count == selected.len())
+        .map(|(tag, _)| tag.to_string())
+        .collect();
+    let chips = on_selection.iter().map(|(&tag, &count)| {
+        let label = if count == selected.len() { tag.to_string() } else { format!("{} {}/{}", tag, count, selected.len()) };
+        tooltip(
+            labelled_tag_chip(tag, label, Some(Message::BatchPropertyChanged(PropertyChange::RemoveTag(tag.to_string())))),
+            text("Remove from every selected widget").size(LABEL_SIZE),
+            tooltip::Position::Top,
+        )
+        .into()
+    });
+
+    column![
+        text("Tags").size(14),
+        row(chips).spacing(4).wrap(),
+        tag_input(draft, tags::suggestions(known, draft, &everywhere), |tag| {
+            Message::BatchPropertyChanged(PropertyChange::AddTag(tag))
+        }),
+    ]
+    .spacing(5)
+    .into()
+}
+
+/// Text input for a new tag, with the project's matching tags under it as one-click adds
+fn tag_input<'a>(draft: &'a str, suggestions: Vec<String>, on_add: impl Fn(String) -> Message + 'a) -> Element<'a, Message> {
+    let valid = tags::normalize(draft).is_some();
+    let mut content = column![
+        row![
+            text_input("Add a tag, like todo or a11y-reviewed", draft)
+                .on_input(Message::TagDraftChanged)
+                .on_submit_maybe(valid.then(|| on_add(draft.to_string())))
+                .size(LABEL_SIZE)
+                .width(Length::Fill),
+            button(text("Add").size(LABEL_SIZE))
+                .style(button::secondary)
+                .on_press_maybe(valid.then(|| on_add(draft.to_string()))),
+        ]
+        .spacing(LABEL_SPACING)
+        .align_y(Alignment::Center),
+    ]
+    .spacing(LABEL_SPACING);
+
+    if !draft.trim().is_empty() && !suggestions.is_empty() {
+        content = content.push(
+            row(suggestions.into_iter().map(|tag| {
+                button(text(tag.clone()).size(LABEL_SIZE - 1.0))
+                    .style(button::text)
+                    .padding(Padding::from([1, 4]))
+                    .on_press(on_add(tag))
+                    .into()
+            }))
+            .spacing(4)
+            .wrap(),
+        );
+    }
+    content.into()
+}
+
+/// Name and one-variant-per-line fields that create an enum and bind the widget to it
+fn new_enum_form<'a>(form: &'a NewEnumForm) -> Element<'a, Message> {
+    let mut content = column![
+        text("New enum").size(SECTION_SIZE),
+        row![
+            text("Name").size(LABEL_SIZE).width(100),
+            text_input("MyEnum", &form.name)
+                .on_input(|v| Message::EnumForm(EnumFormMessage::NameChanged(v)))
+                .on_submit(Message::EnumForm(EnumFormMessage::Create))
+                .width(200),
+        ]
+        .spacing(SECTION_SPACING)
+        .align_y(Alignment::Center),
+        text("Variants, one per line").size(LABEL_SIZE),
+        text_editor(&form.variants)
+            .on_action(|action| Message::EnumForm(EnumFormMessage::VariantsEdited(action)))
+            .height(100),
+    ]
+    .spacing(LABEL_SPACING);
+
+    if let Some(error) = &form.error {
+        content = content.push(text(error).size(LABEL_SIZE).style(text::danger));
+    }
+
+    content.push(
+        row![
+            button(text("Create").size(LABEL_SIZE))
+                .on_press(Message::EnumForm(EnumFormMessage::Create))
+                .style(button::primary),
+            button(text("Cancel").size(LABEL_SIZE))
+                .on_press(Message::EnumForm(EnumFormMessage::Cancel))
+                .style(button::secondary),
+        ]
+        .spacing(SECTION_SPACING),
+    )
+    .into()
+}
+
+/// Breakpoint for the generated app on the root, with a window width to preview the flagged rows at
+pub fn breakpoint_controls<'a>(widget: &Widget, preview_width: f32) -> Element<'a, Message> {
+    let widget_id = widget.id;
+    let props = &widget.properties;
+
+    let mut content = column![
+        text("Breakpoint").size(SECTION_SIZE),
+        checkbox("Stack flagged rows below a window width", props.breakpoint_enabled)
+            .on_toggle(move |on| Message::PropertyChanged(widget_id, PropertyChange::BreakpointEnabled(on))),
+    ]
+    .spacing(SECTION_SPACING);
+
+    if props.breakpoint_enabled {
+        let branch = if preview_width < props.breakpoint_width { "column" } else { "row" };
+        content = content.push(
+            row![
+                text("Breakpoint").size(LABEL_SIZE),
+                slider(320.0..=1920.0, props.breakpoint_width, move |v| {
+                    Message::PropertyChanged(widget_id, PropertyChange::BreakpointWidth(v))
+                })
+                .step(10.0)
+                .width(200),
+                text(format!("{:.0}px", props.breakpoint_width)).size(LABEL_SIZE).width(60),
+            ]
+            .spacing(LABEL_SPACING)
+            .align_y(Alignment::Center),
+        )
+        .push(
+            row![
+                text("Preview at").size(LABEL_SIZE),
+                slider(320.0..=1920.0, preview_width, Message::BreakpointPreviewWidth)
+                    .step(10.0)
+                    .width(200),
+                text(format!("{:.0}px", preview_width)).size(LABEL_SIZE).width(60),
+            ]
+            .spacing(LABEL_SPACING)
+            .align_y(Alignment::Center),
+        )
+        .push(
+            text(format!("Flagged rows show as a {} at this width", branch))
+                .size(LABEL_SIZE - 1.0)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    content.into()
+}
+
+/// Flag for a Row that stacks into a column below the root's breakpoint
+pub fn stack_below_breakpoint_controls<'a>(widget: &Widget, breakpoint_enabled: bool) -> Element<'a, Message> {
+    let widget_id = widget.id;
+
+    let mut content = column![
+        checkbox("Stack below breakpoint", widget.properties.stack_below_breakpoint)
+            .on_toggle(move |on| Message::PropertyChanged(widget_id, PropertyChange::StackBelowBreakpoint(on))),
+    ]
+    .spacing(SECTION_SPACING);
+
+    if widget.properties.stack_below_breakpoint && !breakpoint_enabled {
+        content = content.push(
+            text("Enable the breakpoint on the root widget for this to take effect")
+                .size(LABEL_SIZE - 1.0)
+                .style(text::warning),
+        );
+    }
+
+    content.into()
+}
+
+/// Toggle, stops and angle for a container's linear gradient background
+pub fn gradient_controls<'a>(h: &WidgetHierarchy, widget: &Widget) -> Element<'a, Message> {
+    let widget_id = widget.id;
+    let props = &widget.properties;
+
+    let settings: Element<'a, Message> = if props.gradient_enabled {
+        let angle = props.gradient_angle;
+        let gradient = container_gradient(props);
+        column![
+            row![
+                color_hex_input("Start", props.gradient_start, move |c| {
+                    Message::PropertyChanged(widget_id, PropertyChange::GradientStart(c))
+                }),
+                color_hex_input("End", props.gradient_end, move |c| {
+                    Message::PropertyChanged(widget_id, PropertyChange::GradientEnd(c))
+                }),
+            ]
+            .spacing(SECTION_SPACING),
+            column![
+                text("Angle").size(LABEL_SIZE),
+                row![
+                    slider(0.0..=345.0, angle.min(345.0), move |v| {
+                        Message::PropertyChanged(widget_id, PropertyChange::GradientAngle(v))
+                    })
+                    .step(15.0),
+                    numeric_input(h, widget_id, NumericProperty::GradientAngle, "deg").width(60),
+                ]
+                .spacing(SECTION_SPACING)
+                .align_y(Alignment::Center),
+            ]
+            .spacing(LABEL_SPACING),
+            // Swatch of the gradient as the preview draws it
+            container(Space::new())
+                .width(Length::Fill)
+                .height(24)
+                .style(move |theme: &Theme| container::Style {
+                    background: Some(iced::Background::Gradient(gradient.into())),
+                    border: iced::Border {
+                        color: theme.extended_palette().background.strong.color,
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    ..container::Style::default()
+                }),
+        ]
+        .spacing(SECTION_SPACING)
+        .into()
+    } else {
+        color_hex_input("Background Color", props.background_color, move |c| {
+            Message::PropertyChanged(widget_id, PropertyChange::BackgroundColor(c))
+        })
+    };
+
+    column![
+        text("Background").size(SECTION_SIZE),
+        checkbox("Linear gradient", props.gradient_enabled)
+            .on_toggle(move |enabled| Message::PropertyChanged(widget_id, PropertyChange::GradientEnabled(enabled))),
+        settings,
+    ]
+    .spacing(SECTION_SPACING)
+    .into()
+}
+
+/// "Low contrast" chip for a Container whose background makes its text hard to read
+fn contrast_suggestion_chip<'a>(h: &WidgetHierarchy, widget_id: WidgetId, theme: &Theme) -> Element<'a, Message> {
+    let Some(suggestion) = contrast::suggest(h, widget_id, theme) else {
+        return column![].into();
+    };
+    let color_name = if suggestion.text_color == Color::WHITE { "white" } else { "black" };
+
+    let label = if suggestion.targets.is_empty() {
+        format!("Low contrast ({:.1}:1) for the text inside", suggestion.ratio)
+    } else {
+        format!("Low contrast ({:.1}:1) \u{2014} use {} text?", suggestion.ratio, color_name)
+    };
+
+    container(
+        row![
+            text(label).size(LABEL_SIZE).style(text::warning).width(Length::Fill),
+            (!suggestion.targets.is_empty()).then(|| {
+                button(text("Apply").size(LABEL_SIZE))
+                    .style(button::secondary)
+                    .on_press(Message::ApplyContrastSuggestion(widget_id))
+            }),
+            button(text("Dismiss").size(LABEL_SIZE))
+                .style(button::text)
+                .on_press(Message::DismissContrastSuggestion(widget_id)),
+        ]
+        .spacing(SECTION_SPACING)
+        .align_y(Alignment::Center)
+    )
+    .padding(8)
+    .style(container::bordered_box)
+    .into()
+}
+
+/// Clip checkbox, shown only for the widgets that have a `.clip()` to generate
+pub fn clip_control<'a>(widget: &Widget) -> Element<'a, Message>{
+        if !supports_clip(widget.widget_type) {
+            return column![].into();
+        }
+        let widget_id = widget.id;
+        let clipped = widget.properties.clip;
         column![
             text("Clipping").size(SECTION_SIZE),
             checkbox(
@@ -2953,43 +4057,20 @@ pub fn widget_id_control<'a>(widget_id: WidgetId, id: Option<String> ) -> Elemen
     .into()    
 }
 
-pub fn widget_name<'a>(widget_id: WidgetId, name: &'a str) -> Element<'a, Message> {
+pub fn widget_name<'a>(hierarchy: &WidgetHierarchy, widget_id: WidgetId, name: &'a str) -> Element<'a, Message> {
         column![
             text("Widget Name").size(LABEL_SIZE),
             text_input("Name", name)
                 .on_input(move |v| Message::PropertyChanged(widget_id, PropertyChange::WidgetName(v)))
                 .width(250),
+            text(format!("Generated as: {}", generated_widget_name(hierarchy, widget_id)))
+                .size(LABEL_SIZE - 1.0)
+                .color(Color::from_rgb(0.5, 0.5, 0.5)),
         ]
         .spacing(LABEL_SPACING)
         .into()
 }
 
-pub fn add_code_preview<'a>(content: Element<'a, Message>, hierarchy: &'a WidgetHierarchy, widget_id: WidgetId, theme: Theme, type_system: Option<&'a TypeSystem>) -> Element<'a, Message> {
-    let mut generator = CodeGenerator::new(hierarchy, theme.clone(), type_system);
-    let tokens = generator.generate_widget_code(widget_id);
-    
-    // Check if we have code to display
-    if tokens.is_empty() {
-        return content;
-    }
-    
-    column![
-        content,
-        
-        // Code preview section
-        column![
-            space::vertical().height(20),
-            rule::horizontal(2),
-            space::vertical().height(10),
-            text("Generated Code").size(16),
-            // Use a reasonable height for widget-specific code
-            build_code_view_with_height(&tokens, 400.0, theme),
-        ].spacing(5).padding(10)
-    ]
-    .padding(10)
-    .into()
-}
-
 /// What the user is choosing for a Length
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LengthChoice {
@@ -3021,7 +4102,7 @@ impl LengthChoice {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum PaddingMode {
     /// All four sides have the same value
     Uniform,
@@ -3047,6 +4128,7 @@ impl std::fmt::Display for PaddingMode {
 pub fn batch_editor_controls<'a>(
     hierarchy: &'a WidgetHierarchy,
     _theme: Theme,
+    shared_type_controls: Option<Element<'a, Message>>,
 ) -> Element<'a, Message> {
     let selected = hierarchy.get_selected_widgets();
     let selected_count = selected.len();
@@ -3093,6 +4175,18 @@ pub fn batch_editor_controls<'a>(
         );
     }
     
+    // Everything a single widget of the type offers, when all of them share it
+    if let (Some(controls), Some(widget_type)) = (shared_type_controls, common.shared_type) {
+        content = content.push(rule::horizontal(2));
+        content = content.push(text(format!("{} Properties", widget_type)).size(SECTION_SIZE));
+        content = content.push(
+            text("Values shown are the first widget's")
+                .size(LABEL_SIZE)
+                .color(Color::from_rgb(0.6, 0.6, 0.6))
+        );
+        content = content.push(controls);
+    }
+
     // List the widgets being edited (for clarity)
     content = content.push(rule::horizontal(2));
     content = content.push(text("Editing:").size(SECTION_SIZE));
@@ -3218,9 +4312,8 @@ fn batch_length_picker<'a>(
         }
     });
     
-    // Determine current choice
-    let choice_now = current.map(|len| LengthChoice::from_length(len))
-        .unwrap_or(LengthChoice::Fill);
+    // None while the widgets differ, so the picker reads "Mixed" rather than claiming a value
+    let choice_now = current.map(LengthChoice::from_length);
     
     // Select the appropriate static slice based on constraints
     let available_choices = if any_scrollable_conflicts {
@@ -3231,7 +4324,7 @@ fn batch_length_picker<'a>(
     
     // Show warning if current choice is incompatible
     let warning = if any_scrollable_conflicts && 
-                     (choice_now == LengthChoice::Fill || choice_now == LengthChoice::FillPortion) {
+                     matches!(choice_now, Some(LengthChoice::Fill | LengthChoice::FillPortion)) {
         Some(
             container(
                 text("⚠ Some widgets are in scrollables and cannot use Fill")
@@ -3249,7 +4342,7 @@ fn batch_length_picker<'a>(
         text(label).size(LABEL_SIZE),
         pick_list(
             available_choices,
-            Some(choice_now),
+            choice_now,
             move |choice| {
                 // When changing choice, use default values
                 let new_len = match choice {
@@ -3266,13 +4359,14 @@ fn batch_length_picker<'a>(
                 }
             }
         )
+        .placeholder("Mixed")
         .width(250)
     ]
     .spacing(LABEL_SPACING);
     
     // Add extra input fields based on current choice
     let extra: Element<'a, Message> = match choice_now {
-        LengthChoice::Fixed => {
+        Some(LengthChoice::Fixed) => {
             
             column![
                 text("Pixels").size(LABEL_SIZE),
@@ -3290,7 +4384,7 @@ fn batch_length_picker<'a>(
             .spacing(LABEL_SPACING)
             .into()
         }
-        LengthChoice::FillPortion => {
+        Some(LengthChoice::FillPortion) => {
             
             column![
                 text("Portion").size(LABEL_SIZE),
@@ -3332,6 +4426,19 @@ fn batch_padding_controls<'a>(
     column![
         text("Padding").size(SECTION_SIZE),
 
+        if common.uniform_padding.is_some() && common.uniform_padding_mode.is_some() {
+            text(format!(
+                "Current: {:.0} {:.0} {:.0} {:.0}px",
+                current_padding.top, current_padding.right, current_padding.bottom, current_padding.left,
+            ))
+                .size(LABEL_SIZE)
+                .color(Color::from_rgb(0.5, 0.5, 0.5))
+        } else {
+            text("(mixed values)")
+                .size(LABEL_SIZE)
+                .color(Color::from_rgb(0.8, 0.6, 0.3))
+        },
+
 
         // Mode selection
         column![
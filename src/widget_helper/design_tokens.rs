@@ -0,0 +1,363 @@
+use std::collections::BTreeMap;
+
+use iced::theme::Palette;
+use iced::Color;
+use serde_json::Value;
+
+use crate::palette_file::{color_to_hex, parse_hex};
+
+/// Design tokens read from a JSON or TOML file. Only a small subset is understood:
+///
+/// - `color.*`: hex strings, `#RRGGBB` or `#RRGGBBAA`
+/// - `spacing.*`: numbers, in pixels
+/// - `radius.*`: numbers, in pixels
+///
+/// Groups may nest (`color.brand.primary`). Anything else is listed in `unknown`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TokenSet {
+    pub colors: BTreeMap<String, Color>,
+    pub spacing: BTreeMap<String, f32>,
+    pub radius: BTreeMap<String, f32>,
+    pub unknown: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFormat {
+    Json,
+    Toml,
+}
+
+impl TokenFormat {
+    /// JSON for `.json`, TOML otherwise
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => TokenFormat::Json,
+            _ => TokenFormat::Toml,
+        }
+    }
+}
+
+pub fn parse_tokens(contents: &str, format: TokenFormat) -> Result<TokenSet, String> {
+    let root: Value = match format {
+        TokenFormat::Json => serde_json::from_str(contents).map_err(|e| format!("Invalid JSON: {}", e))?,
+        TokenFormat::Toml => toml::from_str(contents).map_err(|e| format!("Invalid TOML: {}", e))?,
+    };
+    let Value::Object(_) = root else {
+        return Err("Expected a table of token groups at the top level".to_string());
+    };
+
+    let mut leaves = Vec::new();
+    flatten("", &root, &mut leaves);
+
+    let mut tokens = TokenSet::default();
+    for (key, value) in leaves {
+        match key.split('.').next().unwrap_or_default() {
+            "color" => {
+                let hex = value.as_str().ok_or_else(|| format!("`{}`: expected a hex color string, found {}", key, value))?;
+                let color = parse_hex(hex).map_err(|e| format!("`{}`: {}", key, e))?;
+                tokens.colors.insert(key, color);
+            }
+            "spacing" => {
+                let number = number(&key, value)?;
+                tokens.spacing.insert(key, number);
+            }
+            "radius" => {
+                let number = number(&key, value)?;
+                tokens.radius.insert(key, number);
+            }
+            _ => tokens.unknown.push(key),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Dotted keys for every non-table value under `value`
+fn flatten<'a>(prefix: &str, value: &'a Value, out: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&path, child, out);
+            }
+        }
+        leaf => out.push((prefix.to_string(), leaf)),
+    }
+}
+
+fn number(key: &str, value: &Value) -> Result<f32, String> {
+    value.as_f64()
+        .map(|n| n as f32)
+        .ok_or_else(|| format!("`{}`: expected a number, found {}", key, value))
+}
+
+/// Palette slot a color token can fill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PaletteRole {
+    Background,
+    Text,
+    Primary,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl PaletteRole {
+    pub const ALL: [PaletteRole; 6] = [
+        PaletteRole::Background,
+        PaletteRole::Text,
+        PaletteRole::Primary,
+        PaletteRole::Success,
+        PaletteRole::Warning,
+        PaletteRole::Danger,
+    ];
+
+    /// Last key segments that map onto this role without asking
+    fn conventional_names(self) -> &'static [&'static str] {
+        match self {
+            PaletteRole::Background => &["background", "bg", "surface"],
+            PaletteRole::Text       => &["text", "foreground", "fg", "on-background"],
+            PaletteRole::Primary    => &["primary", "brand", "accent"],
+            PaletteRole::Success    => &["success", "positive"],
+            PaletteRole::Warning    => &["warning", "caution"],
+            PaletteRole::Danger     => &["danger", "error", "negative"],
+        }
+    }
+
+    fn slot(self, palette: &mut Palette) -> &mut Color {
+        match self {
+            PaletteRole::Background => &mut palette.background,
+            PaletteRole::Text       => &mut palette.text,
+            PaletteRole::Primary    => &mut palette.primary,
+            PaletteRole::Success    => &mut palette.success,
+            PaletteRole::Warning    => &mut palette.warning,
+            PaletteRole::Danger     => &mut palette.danger,
+        }
+    }
+}
+
+impl std::fmt::Display for PaletteRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Color key for each role whose conventional name appears in the tokens
+pub fn suggested_mapping(tokens: &TokenSet) -> BTreeMap<PaletteRole, String> {
+    PaletteRole::ALL.iter()
+        .filter_map(|&role| {
+            tokens.colors.keys()
+                .find(|key| {
+                    let last = key.rsplit('.').next().unwrap_or_default().to_ascii_lowercase();
+                    role.conventional_names().contains(&last.as_str())
+                })
+                .map(|key| (role, key.clone()))
+        })
+        .collect()
+}
+
+/// `base` with each mapped role replaced by its token color
+pub fn palette(tokens: &TokenSet, mapping: &BTreeMap<PaletteRole, String>, base: Palette) -> Palette {
+    let mut palette = base;
+    for (&role, key) in mapping {
+        if let Some(&color) = tokens.colors.get(key) {
+            *role.slot(&mut palette) = color;
+        }
+    }
+    palette
+}
+
+/// The step of a spacing or radius scale used as the project default: a medium-named
+/// key if there is one, else the middle value
+pub fn suggested_scale_key(scale: &BTreeMap<String, f32>) -> Option<String> {
+    const MEDIUM: [&str; 5] = ["md", "medium", "base", "default", "m"];
+    if let Some(key) = scale.keys().find(|key| MEDIUM.contains(&key.rsplit('.').next().unwrap_or_default())) {
+        return Some(key.clone());
+    }
+
+    let mut by_value: Vec<(&String, f32)> = scale.iter().map(|(key, &value)| (key, value)).collect();
+    by_value.sort_by(|a, b| a.1.total_cmp(&b.1));
+    by_value.get(by_value.len() / 2).map(|(key, _)| (*key).clone())
+}
+
+/// A token whose value differs between two imports. `None` when it's missing on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenChange {
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+pub fn diff(old: &TokenSet, new: &TokenSet) -> Vec<TokenChange> {
+    let values = |tokens: &TokenSet| -> BTreeMap<String, String> {
+        tokens.colors.iter().map(|(key, &color)| (key.clone(), color_to_hex(color)))
+            .chain(tokens.spacing.iter().chain(&tokens.radius).map(|(key, value)| (key.clone(), value.to_string())))
+            .collect()
+    };
+    let (before, after) = (values(old), values(new));
+
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| TokenChange {
+            key: key.clone(),
+            before: before.get(key).cloned(),
+            after: after.get(key).cloned(),
+        })
+        .collect()
+}
+
+/// An import waiting for the user to confirm the mapping
+#[derive(Debug, Clone)]
+pub struct TokenImport {
+    /// Name of the custom theme, from the file stem
+    pub name: String,
+    pub tokens: TokenSet,
+    pub mapping: BTreeMap<PaletteRole, String>,
+    pub spacing_key: Option<String>,
+    pub radius_key: Option<String>,
+    pub apply_spacing: bool,
+    pub apply_radius: bool,
+    /// Changes since the last applied import, None on the first one
+    pub changes: Option<Vec<TokenChange>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum TokenImportEdit {
+    Role(PaletteRole, String),
+    SpacingKey(String),
+    RadiusKey(String),
+    ApplySpacing(bool),
+    ApplyRadius(bool),
+}
+
+impl TokenImport {
+    pub fn new(name: String, tokens: TokenSet, previous: Option<&TokenSet>) -> Self {
+        let spacing_key = suggested_scale_key(&tokens.spacing);
+        let radius_key = suggested_scale_key(&tokens.radius);
+        Self {
+            name,
+            mapping: suggested_mapping(&tokens),
+            apply_spacing: spacing_key.is_some(),
+            apply_radius: radius_key.is_some(),
+            spacing_key,
+            radius_key,
+            changes: previous.map(|previous| diff(previous, &tokens)),
+            tokens,
+        }
+    }
+
+    pub fn edit(&mut self, edit: TokenImportEdit) {
+        match edit {
+            TokenImportEdit::Role(role, key) => { self.mapping.insert(role, key); }
+            TokenImportEdit::SpacingKey(key) => self.spacing_key = Some(key),
+            TokenImportEdit::RadiusKey(key) => self.radius_key = Some(key),
+            TokenImportEdit::ApplySpacing(apply) => self.apply_spacing = apply,
+            TokenImportEdit::ApplyRadius(apply) => self.apply_radius = apply,
+        }
+    }
+
+    /// Color keys no role uses yet
+    pub fn unmapped_colors(&self) -> Vec<&String> {
+        self.tokens.colors.keys().filter(|key| !self.mapping.values().any(|used| used == *key)).collect()
+    }
+
+    /// Spacing to use as the project default, if the user kept that option on
+    pub fn spacing(&self) -> Option<f32> {
+        if !self.apply_spacing {
+            return None;
+        }
+        self.spacing_key.as_ref().and_then(|key| self.tokens.spacing.get(key).copied())
+    }
+
+    /// Border radius to use as the project default, if the user kept that option on
+    pub fn radius(&self) -> Option<f32> {
+        if !self.apply_radius {
+            return None;
+        }
+        self.radius_key.as_ref().and_then(|key| self.tokens.radius.get(key).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r##"{
+        "color": {
+            "background": "#FFFFFF",
+            "text": "#1A1A1A",
+            "brand": { "primary": "#5E7CE2" },
+            "error": "#C3423F",
+            "highlight": "#FFC14E"
+        },
+        "spacing": { "sm": 4, "md": 8, "lg": 16 },
+        "radius": { "sm": 2, "lg": 6, "xl": 12 },
+        "font": { "body": "Inter" }
+    }"##;
+
+    #[test]
+    fn test_parse_json_subset() {
+        let tokens = parse_tokens(JSON, TokenFormat::Json).unwrap();
+        assert_eq!(tokens.colors["color.brand.primary"], Color::from_rgb8(0x5E, 0x7C, 0xE2));
+        assert_eq!(tokens.spacing["spacing.md"], 8.0);
+        assert_eq!(tokens.radius.len(), 3);
+        assert_eq!(tokens.unknown, vec!["font.body".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_toml_matches_json() {
+        let toml = r##"
+            [color]
+            primary = "#5E7CE2"
+
+            [spacing]
+            md = 8
+        "##;
+        let tokens = parse_tokens(toml, TokenFormat::Toml).unwrap();
+        assert_eq!(tokens.colors["color.primary"], Color::from_rgb8(0x5E, 0x7C, 0xE2));
+        assert_eq!(tokens.spacing["spacing.md"], 8.0);
+    }
+
+    #[test]
+    fn test_errors_name_the_key() {
+        let err = parse_tokens(r#"{ "color": { "primary": "#5E7" } }"#, TokenFormat::Json).unwrap_err();
+        assert!(err.starts_with("`color.primary`:"), "{}", err);
+
+        let err = parse_tokens(r#"{ "spacing": { "md": "8px" } }"#, TokenFormat::Json).unwrap_err();
+        assert_eq!(err, "`spacing.md`: expected a number, found \"8px\"");
+    }
+
+    #[test]
+    fn test_conventional_names_mapped() {
+        let tokens = parse_tokens(JSON, TokenFormat::Json).unwrap();
+        let mapping = suggested_mapping(&tokens);
+        assert_eq!(mapping[&PaletteRole::Primary], "color.brand.primary");
+        assert_eq!(mapping[&PaletteRole::Danger], "color.error");
+        assert!(!mapping.contains_key(&PaletteRole::Warning));
+
+        let import = TokenImport::new("Brand".to_string(), tokens, None);
+        assert_eq!(import.unmapped_colors(), vec!["color.highlight"]);
+        assert_eq!(import.spacing(), Some(8.0));
+        // No medium radius, so the middle step is used
+        assert_eq!(import.radius(), Some(6.0));
+
+        let palette = palette(&import.tokens, &import.mapping, Palette::DARK);
+        assert_eq!(palette.background, Color::WHITE);
+        assert_eq!(palette.warning, Palette::DARK.warning);
+    }
+
+    #[test]
+    fn test_reimport_diff() {
+        let old = parse_tokens(JSON, TokenFormat::Json).unwrap();
+        let new = parse_tokens(&JSON.replace("\"md\": 8", "\"md\": 10").replace("\"sm\": 2, ", ""), TokenFormat::Json).unwrap();
+
+        assert_eq!(diff(&old, &new), vec![
+            TokenChange { key: "radius.sm".to_string(), before: Some("2".to_string()), after: None },
+            TokenChange { key: "spacing.md".to_string(), before: Some("8".to_string()), after: Some("10".to_string()) },
+        ]);
+        assert!(diff(&new, &new).is_empty());
+    }
+}
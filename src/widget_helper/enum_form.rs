@@ -0,0 +1,86 @@
+use iced::widget::text_editor;
+use uuid::Uuid;
+use crate::widget_helper::WidgetId;
+use crate::widget_helper::type_system::TypeSystem;
+
+/// Inline form in a ComboBox's properties that creates the enum the widget binds to,
+/// without a trip to the type editor
+pub struct NewEnumForm {
+    /// Widget the new enum gets bound to
+    pub widget_id: WidgetId,
+    pub name: String,
+    /// Initial variants, one per line
+    pub variants: text_editor::Content,
+    /// Why the last Create was refused
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum EnumFormMessage {
+    Open(WidgetId),
+    NameChanged(String),
+    VariantsEdited(text_editor::Action),
+    Create,
+    Cancel,
+}
+
+impl NewEnumForm {
+    pub fn new(widget_id: WidgetId) -> Self {
+        Self {
+            widget_id,
+            name: String::new(),
+            variants: text_editor::Content::new(),
+            error: None,
+        }
+    }
+}
+
+/// Trimmed, non-blank lines of the variants field
+pub fn variant_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Adds the enum through the same validation as the type editor: identifier names,
+/// at least one variant, and no name taken by another enum or variant
+pub fn create_enum(type_system: &mut TypeSystem, name: &str, variants: &str) -> Result<Uuid, String> {
+    type_system.add_enum(name.trim().to_string(), variant_lines(variants))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_lines_skip_blanks_and_trim() {
+        assert_eq!(variant_lines("  Small\n\nMedium \n\t\nLarge"), ["Small", "Medium", "Large"]);
+        assert!(variant_lines("\n  \n").is_empty());
+    }
+
+    #[test]
+    fn test_create_enum_adds_it_with_its_variants() {
+        let mut type_system = TypeSystem::new();
+        let id = create_enum(&mut type_system, " Size ", "Small\nLarge\n").unwrap();
+        let enum_def = type_system.get_enum(id).unwrap();
+        assert_eq!(enum_def.name, "Size");
+        let names: Vec<&str> = enum_def.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["Small", "Large"]);
+    }
+
+    #[test]
+    fn test_create_enum_refuses_what_the_type_editor_refuses() {
+        let mut type_system = TypeSystem::new();
+        create_enum(&mut type_system, "Size", "Small").unwrap();
+        let count = type_system.enum_count();
+
+        assert!(create_enum(&mut type_system, "Size", "Tiny").is_err());
+        assert!(create_enum(&mut type_system, "", "Tiny").is_err());
+        assert!(create_enum(&mut type_system, "Shape", "").is_err());
+        assert!(create_enum(&mut type_system, "Shape", "Round\nC++").is_err());
+        assert!(create_enum(&mut type_system, "Shape", "Round\nRound").is_err());
+        assert_eq!(type_system.enum_count(), count);
+    }
+}
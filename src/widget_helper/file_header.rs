@@ -0,0 +1,147 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+/// SPDX identifiers offered in the license picker. Anything else can still be typed in.
+pub const COMMON_LICENSES: &[&str] = &[
+    "MIT",
+    "Apache-2.0",
+    "MIT OR Apache-2.0",
+    "BSD-3-Clause",
+    "MPL-2.0",
+    "GPL-3.0-only",
+    "LGPL-3.0-only",
+    "Unlicense",
+];
+
+pub const DEFAULT_TEMPLATE: &str = "Copyright (c) {year} {author}\nSPDX-License-Identifier: {license}";
+
+/// License header and metadata written at the top of every generated file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileHeader {
+    pub enabled: bool,
+    pub author: String,
+    /// SPDX identifier, also written as the exported Cargo.toml's `license`
+    pub license: String,
+    pub year: i32,
+    /// Header text with `{author}`, `{year}`, `{license}` and `{app_name}` placeholders
+    pub template: String,
+}
+
+impl Default for FileHeader {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            author: String::new(),
+            license: "MIT".to_string(),
+            year: current_year(),
+            template: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl FileHeader {
+    /// Comment lines to emit above the imports, none while the header is off
+    pub fn comment_lines(&self, app_name: &str) -> Vec<String> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        comment_lines(&render(&self.template, &self.author, self.year, &self.license, app_name))
+    }
+
+    /// License for Cargo.toml, None while the header is off or the field is blank
+    pub fn cargo_license(&self) -> Option<&str> {
+        let license = self.license.trim();
+        (self.enabled && !license.is_empty()).then_some(license)
+    }
+}
+
+/// `template` with each placeholder filled in. Unknown `{names}` are left as typed.
+pub fn render(template: &str, author: &str, year: i32, license: &str, app_name: &str) -> String {
+    template
+        .replace("{author}", author.trim())
+        .replace("{year}", &year.to_string())
+        .replace("{license}", license.trim())
+        .replace("{app_name}", app_name.trim())
+}
+
+/// `// ` lines of rendered header text. Blank lines around it are dropped, so the generator's
+/// own blank line after the header is the only one and reformatting can't stack more.
+pub fn comment_lines(rendered: &str) -> Vec<String> {
+    let lines: Vec<&str> = rendered.lines().map(str::trim_end).collect();
+    let start = lines.iter().position(|line| !line.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|line| !line.is_empty()).map_or(start, |last| last + 1);
+    lines[start..end]
+        .iter()
+        .map(|line| if line.is_empty() { "//".to_string() } else { format!("// {}", line) })
+        .collect()
+}
+
+/// Calendar year in UTC, for the header's default copyright year
+pub fn current_year() -> i32 {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    year_of_day((seconds / 86_400) as i64)
+}
+
+/// Year of a day counted from 1970-01-01, by the civil-from-days algorithm
+fn year_of_day(days: i64) -> i32 {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    // The era counts years from March, so January and February belong to the next year
+    let year = year_of_era + era * 400 + if month_index >= 10 { 1 } else { 0 };
+    year as i32
+}
+
+#[derive(Debug, Clone)]
+pub enum FileHeaderMessage {
+    Toggled(bool),
+    AuthorChanged(String),
+    LicensePicked(&'static str),
+    LicenseChanged(String),
+    YearChanged(String),
+    TemplateEdited(iced::widget::text_editor::Action),
+    ResetTemplate,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders_are_filled_and_unknown_ones_kept() {
+        let rendered = render("{app_name} (c) {year} {author}, {license}. {version}", " Ada ", 2031, "MIT", "Counter");
+        assert_eq!(rendered, "Counter (c) 2031 Ada, MIT. {version}");
+        assert_eq!(render("{year}-{year}", "", 2020, "", ""), "2020-2020");
+    }
+
+    #[test]
+    fn test_comment_lines_trim_surrounding_blank_lines() {
+        assert_eq!(comment_lines("\n\nFirst  \n\nSecond\n\n"), ["// First", "//", "// Second"]);
+        assert!(comment_lines("\n  \n").is_empty());
+    }
+
+    #[test]
+    fn test_disabled_header_emits_nothing() {
+        let header = FileHeader { author: "Ada".to_string(), ..FileHeader::default() };
+        assert!(header.comment_lines("App").is_empty());
+        assert_eq!(header.cargo_license(), None);
+
+        let header = FileHeader { enabled: true, year: 2030, ..header };
+        assert_eq!(header.comment_lines("App"), ["// Copyright (c) 2030 Ada", "// SPDX-License-Identifier: MIT"]);
+        assert_eq!(header.cargo_license(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_year_of_day() {
+        assert_eq!(year_of_day(0), 1970);
+        assert_eq!(year_of_day(364), 1970);
+        assert_eq!(year_of_day(365), 1971);
+        // 2024-12-31 and 2025-01-01
+        assert_eq!(year_of_day(20_088), 2024);
+        assert_eq!(year_of_day(20_089), 2025);
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::PathBuf;
+
+/// Ask the user where to save `contents`, then write it out.
+/// Returns the chosen path, or an error string for the notification bar.
+pub async fn save_text_file(
+    contents: String,
+    default_name: String,
+    filter_name: &'static str,
+    extensions: &'static [&'static str],
+) -> Result<PathBuf, String> {
+    let handle = rfd::AsyncFileDialog::new()
+        .set_file_name(&default_name)
+        .add_filter(filter_name, extensions)
+        .save_file()
+        .await
+        .ok_or_else(|| "Save cancelled".to_string())?;
+
+    let path = handle.path().to_path_buf();
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    Ok(path)
+}
+
+/// Ask the user for a file to open, then read it.
+/// Returns the chosen path along with its contents.
+pub async fn open_text_file(
+    filter_name: &'static str,
+    extensions: &'static [&'static str],
+) -> Result<(PathBuf, String), String> {
+    let handle = rfd::AsyncFileDialog::new()
+        .add_filter(filter_name, extensions)
+        .pick_file()
+        .await
+        .ok_or_else(|| "Open cancelled".to_string())?;
+
+    let path = handle.path().to_path_buf();
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    Ok((path, contents))
+}
+
+/// Ask the user for a file to open, leaving the reading to the caller
+pub async fn pick_open_file(
+    filter_name: &'static str,
+    extensions: &'static [&'static str],
+) -> Result<PathBuf, String> {
+    let handle = rfd::AsyncFileDialog::new()
+        .add_filter(filter_name, extensions)
+        .pick_file()
+        .await
+        .ok_or_else(|| "Open cancelled".to_string())?;
+
+    Ok(handle.path().to_path_buf())
+}
+
+/// Ask the user for a directory, then write each file (relative to it) and
+/// copy each asset that exists to its place (also relative to it).
+/// Returns the chosen directory.
+pub async fn save_project(
+    files: Vec<(PathBuf, String)>,
+    assets: Vec<(PathBuf, PathBuf)>,
+) -> Result<PathBuf, String> {
+    let handle = rfd::AsyncFileDialog::new()
+        .set_title("Choose a folder for the project")
+        .pick_folder()
+        .await
+        .ok_or_else(|| "Export cancelled".to_string())?;
+
+    let root = handle.path().to_path_buf();
+    for (relative, contents) in files {
+        let path = root.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    for (source, relative) in assets {
+        if !source.is_file() {
+            continue;
+        }
+        let target = root.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::copy(&source, &target)
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+    }
+
+    Ok(root)
+}
+
+/// Ask the user for any number of files to add to the asset library
+pub async fn pick_asset_files(extensions: &'static [&'static str]) -> Result<Vec<PathBuf>, String> {
+    let handles = rfd::AsyncFileDialog::new()
+        .set_title("Add assets")
+        .add_filter("Images and SVG", extensions)
+        .pick_files()
+        .await
+        .ok_or_else(|| "Add cancelled".to_string())?;
+
+    Ok(handles.iter().map(|handle| handle.path().to_path_buf()).collect())
+}
+
+/// Ask the user for a folder, then list the files directly in it with one of `extensions`,
+/// sorted by name. Subfolders aren't searched.
+pub async fn pick_asset_folder(extensions: &'static [&'static str]) -> Result<Vec<PathBuf>, String> {
+    let handle = rfd::AsyncFileDialog::new()
+        .set_title("Add a folder of assets")
+        .pick_folder()
+        .await
+        .ok_or_else(|| "Add cancelled".to_string())?;
+
+    let folder = handle.path().to_path_buf();
+    let entries = std::fs::read_dir(&folder)
+        .map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| extensions.contains(&extension.to_ascii_lowercase().as_str()))
+        })
+        .collect();
+    if paths.is_empty() {
+        return Err(format!("{} has no images or SVG files", folder.display()));
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Ask the user for the file an asset library entry should point at instead
+pub async fn pick_asset_file(extensions: &'static [&'static str]) -> Result<PathBuf, String> {
+    let handle = rfd::AsyncFileDialog::new()
+        .set_title("Choose the asset's new file")
+        .add_filter("Asset", extensions)
+        .pick_file()
+        .await
+        .ok_or_else(|| "Relink cancelled".to_string())?;
+
+    Ok(handle.path().to_path_buf())
+}
+
+/// Ask the user for an image to sample colors from
+pub async fn pick_reference_image(extensions: &'static [&'static str]) -> Result<PathBuf, String> {
+    let handle = rfd::AsyncFileDialog::new()
+        .set_title("Choose a reference image")
+        .add_filter("Image", extensions)
+        .pick_file()
+        .await
+        .ok_or_else(|| "Open cancelled".to_string())?;
+
+    Ok(handle.path().to_path_buf())
+}
@@ -0,0 +1,89 @@
+use iced::Length;
+
+use crate::widget_helper::{Widget, WidgetType};
+
+/// A child's part of the room its parent shares out along the main axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Share {
+    pub portion: u16,
+    /// Of the room left after the siblings that don't fill, 0..=1
+    pub fraction: f32,
+}
+
+impl Share {
+    /// Badge text, like `FP:3 · 60%`
+    pub fn label(self) -> String {
+        format!("FP:{} · {:.0}%", self.portion, self.fraction * 100.0)
+    }
+}
+
+/// `Fill` counts as one portion, anything that doesn't fill as none
+pub fn portion(length: Length) -> Option<u16> {
+    match length {
+        Length::Fill => Some(1),
+        Length::FillPortion(portion) => Some(portion),
+        _ => None,
+    }
+}
+
+/// Each child's share of a Row's width or a Column's height, None for children that don't
+/// fill along it. A wrapping row gives every filling child a line of its own, so nothing is
+/// shared there.
+pub fn shares(parent: &Widget) -> Vec<Option<Share>> {
+    let main_axis: fn(&Widget) -> Length = match parent.widget_type {
+        WidgetType::Row if !parent.properties.is_wrapping_row => |child| child.properties.width,
+        WidgetType::Column => |child| child.properties.height,
+        _ => return vec![None; parent.children.len()],
+    };
+    let portions: Vec<Option<u16>> = parent.children.iter().map(|child| portion(main_axis(child))).collect();
+    let total: u32 = portions.iter().flatten().map(|&portion| portion as u32).sum();
+    portions
+        .into_iter()
+        .map(|portion| portion.map(|portion| Share {
+            portion,
+            fraction: if total == 0 { 0.0 } else { portion as f32 / total as f32 },
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::{WidgetHierarchy, WidgetId};
+
+    fn sized(hierarchy: &mut WidgetHierarchy, parent: WidgetId, width: Length, height: Length) {
+        let id = hierarchy.add_child(parent, WidgetType::Text).unwrap();
+        let props = &mut hierarchy.get_widget_by_id_mut(id).unwrap().properties;
+        props.width = width;
+        props.height = height;
+    }
+
+    #[test]
+    fn test_row_shares_its_width() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Row);
+        sized(&mut hierarchy, WidgetId(0), Length::FillPortion(1), Length::Shrink);
+        sized(&mut hierarchy, WidgetId(0), Length::Fixed(80.0), Length::Fill);
+        sized(&mut hierarchy, WidgetId(0), Length::FillPortion(3), Length::Shrink);
+
+        let shares = shares(hierarchy.root());
+        assert_eq!(shares[0], Some(Share { portion: 1, fraction: 0.25 }));
+        // Height doesn't divide a row
+        assert_eq!(shares[1], None);
+        assert_eq!(shares[2].unwrap().label(), "FP:3 · 75%");
+    }
+
+    #[test]
+    fn test_column_shares_its_height_and_wrapping_rows_share_nothing() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        sized(&mut hierarchy, WidgetId(0), Length::FillPortion(5), Length::Fill);
+        sized(&mut hierarchy, WidgetId(0), Length::Shrink, Length::FillPortion(2));
+        let shares = shares(hierarchy.root());
+        assert_eq!(shares[0].unwrap().portion, 1);
+        assert_eq!(shares[1].unwrap().label(), "FP:2 · 67%");
+
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Row);
+        hierarchy.get_widget_by_id_mut(WidgetId(0)).unwrap().properties.is_wrapping_row = true;
+        sized(&mut hierarchy, WidgetId(0), Length::FillPortion(2), Length::Shrink);
+        assert_eq!(super::shares(hierarchy.root()), [None]);
+    }
+}
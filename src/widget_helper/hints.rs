@@ -0,0 +1,731 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::widget_helper::*;
+use crate::widget_helper::type_system::TypeSystem;
+
+/// Direct children a Column can hold before a Scrollable is suggested
+pub const LONG_COLUMN_CHILDREN: usize = 8;
+
+/// Advance of one glyph in em for the bundled monospace font
+const MONOSPACE_ADVANCE: f32 = 0.6;
+/// Average advance in em for proportional text, mixing narrow and wide glyphs
+const PROPORTIONAL_ADVANCE: f32 = 0.5;
+/// Size iced uses for button and checkbox labels
+pub const DEFAULT_LABEL_SIZE: f32 = 16.0;
+/// iced's default line height, relative to the text size
+const LINE_HEIGHT: f32 = 1.3;
+/// Gap a stretching Space becomes in a wrapping Row whose spacing is zero
+const WRAPPED_ROW_GAP: f32 = 10.0;
+
+/// Heuristics that can produce a hint. Stored in settings when switched off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HintRule {
+    ContainerToColumn,
+    RemoveSingleChildRow,
+    ScrollLongColumn,
+    TextOverflow,
+    WrappingRowSpace,
+    DanglingStyleCondition,
+}
+
+impl HintRule {
+    pub const ALL: [HintRule; 6] = [
+        HintRule::ContainerToColumn,
+        HintRule::RemoveSingleChildRow,
+        HintRule::ScrollLongColumn,
+        HintRule::TextOverflow,
+        HintRule::WrappingRowSpace,
+        HintRule::DanglingStyleCondition,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            HintRule::ContainerToColumn      => "Suggest a Column when a Container refuses a second child",
+            HintRule::RemoveSingleChildRow   => "Suggest removing Rows that hold a single widget",
+            HintRule::ScrollLongColumn       => "Suggest a Scrollable for long Columns",
+            HintRule::TextOverflow           => "Warn when text is wider than its fixed-width parent",
+            HintRule::WrappingRowSpace       => "Warn about Fill spaces inside wrapping Rows",
+            HintRule::DanglingStyleCondition => "Warn when a conditional style follows a deleted widget",
+        }
+    }
+}
+
+/// A Container turned down an added child
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefusedAdd {
+    pub container: WidgetId,
+    pub widget_type: WidgetType,
+}
+
+/// Change a hint's apply button performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintFix {
+    /// Wrap the Container's only child in a Column, then add the refused widget to it
+    WrapInColumn { child: WidgetId, then_add: WidgetType },
+    /// Move the Row's only child up into the Row's place and delete the Row
+    RemoveRow { row: WidgetId },
+    /// Move the Column's children into a Scrollable > Column inside it
+    ScrollColumn { column: WidgetId },
+    /// Set the Text's wrapping to Word
+    WrapText { text: WidgetId },
+    /// Switch the fixed-width ancestor to Shrink so it grows with its content
+    ShrinkAncestor { ancestor: WidgetId },
+    /// Give the Space a fixed width matching its Row's spacing
+    FixSpace { space: WidgetId },
+    /// Delete the Space and let the Row's spacing make the gap
+    RemoveSpace { space: WidgetId },
+    /// Drop the conditional style whose Checkbox or Toggler is gone
+    ClearStyleCondition { widget: WidgetId },
+}
+
+impl HintFix {
+    pub fn rule(&self) -> HintRule {
+        match self {
+            HintFix::WrapInColumn { .. }        => HintRule::ContainerToColumn,
+            HintFix::RemoveRow { .. }           => HintRule::RemoveSingleChildRow,
+            HintFix::ScrollColumn { .. }        => HintRule::ScrollLongColumn,
+            HintFix::WrapText { .. } |
+            HintFix::ShrinkAncestor { .. }      => HintRule::TextOverflow,
+            HintFix::FixSpace { .. } |
+            HintFix::RemoveSpace { .. }         => HintRule::WrappingRowSpace,
+            HintFix::ClearStyleCondition { .. } => HintRule::DanglingStyleCondition,
+        }
+    }
+
+    /// Button text on the hint card
+    pub fn label(&self) -> &'static str {
+        match self {
+            HintFix::WrapInColumn { .. } |
+            HintFix::RemoveRow { .. } |
+            HintFix::ScrollColumn { .. }        => "Apply",
+            HintFix::WrapText { .. }            => "Wrap Text",
+            HintFix::ShrinkAncestor { .. }      => "Shrink Parent",
+            HintFix::FixSpace { .. }            => "Make Fixed",
+            HintFix::RemoveSpace { .. }         => "Use Row Spacing",
+            HintFix::ClearStyleCondition { .. } => "Remove Condition",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    /// Widget the hint is about, used to remember dismissals
+    pub target: WidgetId,
+    pub message: String,
+    pub fix: HintFix,
+    /// Second fix offered next to `fix`, if the hint has one
+    pub alternative: Option<HintFix>,
+}
+
+impl Hint {
+    pub fn rule(&self) -> HintRule {
+        self.fix.rule()
+    }
+}
+
+/// The single most relevant hint, if any. A refused add reacts to what the user
+/// just did, so it wins over the rules that scan the whole tree.
+pub fn suggest(
+    hierarchy: &WidgetHierarchy,
+    refused: Option<RefusedAdd>,
+    disabled: &HashSet<HintRule>,
+    dismissed: &HashSet<(HintRule, WidgetId)>,
+) -> Option<Hint> {
+    let allowed = |hint: &Hint| !disabled.contains(&hint.rule()) && !dismissed.contains(&(hint.rule(), hint.target));
+
+    let mut candidates = refused.and_then(|r| container_refused(hierarchy, r)).into_iter()
+        .chain(dangling_style_conditions(hierarchy))
+        .chain(wrapping_row_spaces(hierarchy))
+        .chain(single_child_rows(hierarchy))
+        .chain(long_columns(hierarchy))
+        .chain(overflowing_text(hierarchy));
+
+    candidates.find(|hint| allowed(hint))
+}
+
+fn container_refused(hierarchy: &WidgetHierarchy, refused: RefusedAdd) -> Option<Hint> {
+    let container = hierarchy.get_widget_by_id(refused.container)?;
+    if container.widget_type != WidgetType::Container || container.id == hierarchy.root().id {
+        return None;
+    }
+    let [child] = container.children.as_slice() else { return None };
+    if matches!(child.widget_type, WidgetType::Column | WidgetType::Row) {
+        return None;
+    }
+
+    Some(Hint {
+        target: container.id,
+        message: format!(
+            "A Container holds a single child. Put its {:?} in a Column to add the {:?} alongside it.",
+            child.widget_type, refused.widget_type
+        ),
+        fix: HintFix::WrapInColumn { child: child.id, then_add: refused.widget_type },
+        alternative: None,
+    })
+}
+
+/// Conditional styles following a Checkbox or Toggler that was deleted or retyped since.
+/// The generated code leaves such a style out, which would otherwise go unnoticed.
+fn dangling_style_conditions(hierarchy: &WidgetHierarchy) -> Vec<Hint> {
+    fn walk(hierarchy: &WidgetHierarchy, widget: &Widget, hints: &mut Vec<Hint>) {
+        if let Some(StyleCondition::Widget(id)) = widget.properties.style_condition
+            && condition_source(hierarchy, id).is_none()
+        {
+            hints.push(Hint {
+                target: widget.id,
+                message: format!(
+                    "This {:?}'s conditional style followed a Checkbox or Toggler that no longer exists, so it is never generated. \
+                     Pick another one in its properties, or remove the condition.",
+                    widget.widget_type
+                ),
+                fix: HintFix::ClearStyleCondition { widget: widget.id },
+                alternative: None,
+            });
+        }
+        for child in &widget.children {
+            walk(hierarchy, child, hints);
+        }
+    }
+
+    let mut hints = Vec::new();
+    walk(hierarchy, hierarchy.root(), &mut hints);
+    hints
+}
+
+/// Space children of a wrapping Row that fill whichever line they land on. Each line
+/// hands its leftover width to its own spaces, so the gaps differ from line to line.
+pub fn stretching_spaces(row: &Widget) -> Vec<&Widget> {
+    if row.widget_type != WidgetType::Row || !row.properties.is_wrapping_row {
+        return Vec::new();
+    }
+    row.children.iter()
+        .filter(|child| child.widget_type == WidgetType::Space)
+        .filter(|child| matches!(child.properties.width, Length::Fill | Length::FillPortion(_)))
+        .collect()
+}
+
+fn wrapping_row_spaces(hierarchy: &WidgetHierarchy) -> Vec<Hint> {
+    fn walk(widget: &Widget, hints: &mut Vec<Hint>) {
+        for space in stretching_spaces(widget) {
+            hints.push(Hint {
+                target: space.id,
+                message: "This Space fills the width of a wrapping Row. Each wrapped line stretches its own spaces, \
+                          so the gaps won't match from line to line. Give it a fixed width, or remove it and use the Row's spacing."
+                    .to_string(),
+                fix: HintFix::FixSpace { space: space.id },
+                alternative: Some(HintFix::RemoveSpace { space: space.id }),
+            });
+        }
+        for child in &widget.children {
+            walk(child, hints);
+        }
+    }
+
+    let mut hints = Vec::new();
+    walk(hierarchy.root(), &mut hints);
+    hints
+}
+
+fn single_child_rows(hierarchy: &WidgetHierarchy) -> Vec<Hint> {
+    fn walk(widget: &Widget, parent: &Widget, hints: &mut Vec<Hint>) {
+        if let (WidgetType::Row, [child]) = (widget.widget_type, widget.children.as_slice()) {
+            if can_take_row_place(parent, child.widget_type) {
+                hints.push(Hint {
+                    target: widget.id,
+                    message: format!("This Row only holds a {:?}. Remove the Row and keep the {:?}?", child.widget_type, child.widget_type),
+                    fix: HintFix::RemoveRow { row: widget.id },
+                    alternative: None,
+                });
+            }
+        }
+        for child in &widget.children {
+            walk(child, widget, hints);
+        }
+    }
+
+    // The root only accepts a Column or Row, so its own child is never unwrapped
+    let mut hints = Vec::new();
+    for child in &hierarchy.root().children {
+        for grandchild in &child.children {
+            walk(grandchild, child, &mut hints);
+        }
+    }
+    hints
+}
+
+/// Whether moving the Row's child into the Row's slot leaves `parent` valid
+fn can_take_row_place(parent: &Widget, child_type: WidgetType) -> bool {
+    match parent.widget_type {
+        WidgetType::Column | WidgetType::Row | WidgetType::Stack | WidgetType::Container => true,
+        WidgetType::Scrollable => matches!(child_type, WidgetType::Column | WidgetType::Row | WidgetType::Container),
+        // Tooltip and MouseArea refuse moves in while full
+        _ => false,
+    }
+}
+
+fn long_columns(hierarchy: &WidgetHierarchy) -> Vec<Hint> {
+    fn walk(hierarchy: &WidgetHierarchy, widget: &Widget, hints: &mut Vec<Hint>) {
+        if widget.widget_type == WidgetType::Column
+            && widget.children.len() > LONG_COLUMN_CHILDREN
+            && hierarchy.get_scrollable_ancestor_info(widget.id).is_none()
+        {
+            hints.push(Hint {
+                target: widget.id,
+                message: format!(
+                    "This Column has {} widgets. Wrap them in a Scrollable so they don't get cut off.",
+                    widget.children.len()
+                ),
+                fix: HintFix::ScrollColumn { column: widget.id },
+                alternative: None,
+            });
+            // Columns further down are already covered by this one
+            return;
+        }
+        for child in &widget.children {
+            walk(hierarchy, child, hints);
+        }
+    }
+
+    let mut hints = Vec::new();
+    walk(hierarchy, hierarchy.root(), &mut hints);
+    hints
+}
+
+/// Rough rendered width of the longest line in `content`. Monospace glyphs all share
+/// one advance, so that case is exact; proportional text uses an average advance.
+pub fn estimate_text_width(content: &str, size: f32, monospace: bool) -> f32 {
+    let advance = if monospace { MONOSPACE_ADVANCE } else { PROPORTIONAL_ADVANCE };
+    let longest = content.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    longest as f32 * size * advance
+}
+
+/// Rough rendered height of `content` at its explicit line breaks, with iced's default line height
+pub fn estimate_text_height(content: &str, size: f32) -> f32 {
+    content.lines().count().max(1) as f32 * size * LINE_HEIGHT
+}
+
+/// Width the widget needs to show its label on one line, or None if it has no
+/// label that could overflow
+fn single_line_width(widget: &Widget) -> Option<f32> {
+    let props = &widget.properties;
+    match widget.widget_type {
+        // Text that already wraps breaks onto more lines instead of overflowing
+        WidgetType::Text if props.wrap == text::Wrapping::None => {
+            Some(estimate_text_width(&props.text_content, props.text_size, props.font == FontType::Monospace))
+        }
+        WidgetType::Button => {
+            Some(estimate_text_width(&props.text_content, DEFAULT_LABEL_SIZE, false) + props.padding.left + props.padding.right)
+        }
+        WidgetType::Checkbox => {
+            Some(estimate_text_width(&props.checkbox_label, DEFAULT_LABEL_SIZE, false) + props.checkbox_size + props.checkbox_spacing)
+        }
+        _ => None,
+    }
+}
+
+fn overflowing_text(hierarchy: &WidgetHierarchy) -> Vec<Hint> {
+    fn walk<'a>(widget: &'a Widget, ancestors: &mut Vec<&'a Widget>, hints: &mut Vec<Hint>) {
+        if let Some(needed) = single_line_width(widget)
+            && let Some((ancestor, available)) = narrowest_fixed_ancestor(ancestors)
+            && needed > available
+        {
+            let wraps = widget.widget_type == WidgetType::Text;
+            hints.push(Hint {
+                target: widget.id,
+                message: format!(
+                    "This {:?} is about {:.0}px wide on one line, but its {:?} only leaves {:.0}px. {}",
+                    widget.widget_type, needed, ancestor.widget_type, available,
+                    if wraps { "Let the text wrap, or let the parent size itself." } else { "Let the parent size itself." },
+                ),
+                fix: HintFix::ShrinkAncestor { ancestor: ancestor.id },
+                alternative: wraps.then_some(HintFix::WrapText { text: widget.id }),
+            });
+        }
+
+        ancestors.push(widget);
+        for child in &widget.children {
+            walk(child, ancestors, hints);
+        }
+        ancestors.pop();
+    }
+
+    let mut hints = Vec::new();
+    walk(hierarchy.root(), &mut Vec::new(), &mut hints);
+    hints
+}
+
+/// The fixed-width ancestor leaving the least room, after the horizontal padding of it
+/// and every ancestor between it and the widget. None when the chain is all Fill/Shrink.
+fn narrowest_fixed_ancestor<'a>(ancestors: &[&'a Widget]) -> Option<(&'a Widget, f32)> {
+    let mut padding = 0.0;
+    let mut narrowest: Option<(&Widget, f32)> = None;
+    for &ancestor in ancestors.iter().rev() {
+        padding += ancestor.properties.padding.left + ancestor.properties.padding.right;
+        if let Length::Fixed(width) = ancestor.properties.width {
+            let available = width - padding;
+            if narrowest.is_none_or(|(_, best)| available < best) {
+                narrowest = Some((ancestor, available));
+            }
+        }
+    }
+    narrowest
+}
+
+/// Performs the fix with the hierarchy's own add/move/wrap operations.
+/// Returns the widget to select afterwards.
+pub fn apply_fix(
+    hierarchy: &mut WidgetHierarchy,
+    fix: HintFix,
+    defaults: &ProjectDefaults,
+    type_system: &TypeSystem,
+) -> Result<WidgetId, HierarchyError> {
+    match fix {
+        HintFix::WrapInColumn { child, then_add } => {
+            hierarchy.set_selected_ids(HashSet::from([child]));
+            let column_id = hierarchy.wrap_selected_in_container(WidgetType::Column)?;
+            hierarchy.add_child_with_defaults(column_id, then_add, defaults)
+        }
+
+        HintFix::RemoveRow { row } => {
+            let row_widget = hierarchy.get_widget_by_id(row).ok_or(HierarchyError::WidgetNotFound(row))?;
+            if row_widget.children.len() != 1 {
+                return Err(HierarchyError::HintOutdated);
+            }
+
+            let child_id = hierarchy.unwrap_widget(row)?;
+            hierarchy.set_selected_ids(HashSet::from([child_id]));
+            Ok(child_id)
+        }
+
+        HintFix::ScrollColumn { column } => {
+            let outer = hierarchy.get_widget_by_id(column).ok_or(HierarchyError::WidgetNotFound(column))?;
+            let children: HashSet<WidgetId> = outer.children.iter().map(|c| c.id).collect();
+            let (spacing, align_items) = (outer.properties.spacing, outer.properties.align_items);
+
+            // The root can't hold a Scrollable, so the Column stays and the Scrollable goes inside it
+            hierarchy.set_selected_ids(children);
+            let inner = hierarchy.wrap_selected_in_container(WidgetType::Column)?;
+            hierarchy.apply_property_change(inner, PropertyChange::Spacing(spacing), type_system);
+            hierarchy.apply_property_change(inner, PropertyChange::AlignItems(align_items), type_system);
+
+            let scroll_id = hierarchy.wrap_selected_in_container(WidgetType::Scrollable)?;
+            hierarchy.apply_property_change(scroll_id, PropertyChange::Width(Length::Fill), type_system);
+            hierarchy.apply_property_change(scroll_id, PropertyChange::Height(Length::Fill), type_system);
+            hierarchy.sanitize_subtree_for_scrollable(scroll_id);
+            Ok(scroll_id)
+        }
+
+        HintFix::WrapText { text } => {
+            hierarchy.apply_property_change(text, PropertyChange::TextWrap(TextWrapping::Word), type_system);
+            Ok(text)
+        }
+
+        HintFix::ShrinkAncestor { ancestor } => {
+            hierarchy.apply_property_change(ancestor, PropertyChange::Width(Length::Shrink), type_system);
+            Ok(ancestor)
+        }
+
+        HintFix::FixSpace { space } => {
+            let row_id = hierarchy.find_parent_id(space).ok_or(HierarchyError::WidgetNotFound(space))?;
+            let spacing = hierarchy.get_widget_by_id(row_id).map_or(0.0, |row| row.properties.spacing);
+            let width = if spacing > 0.0 { spacing } else { WRAPPED_ROW_GAP };
+            hierarchy.apply_property_change(space, PropertyChange::Width(Length::Fixed(width)), type_system);
+            Ok(space)
+        }
+
+        HintFix::RemoveSpace { space } => {
+            let row_id = hierarchy.find_parent_id(space).ok_or(HierarchyError::WidgetNotFound(space))?;
+            hierarchy.delete_widget(space)?;
+            if hierarchy.get_widget_by_id(row_id).is_some_and(|row| row.properties.spacing == 0.0) {
+                hierarchy.apply_property_change(row_id, PropertyChange::Spacing(WRAPPED_ROW_GAP), type_system);
+            }
+            hierarchy.set_selected_ids(HashSet::from([row_id]));
+            Ok(row_id)
+        }
+
+        HintFix::ClearStyleCondition { widget } => {
+            hierarchy.apply_property_change(widget, PropertyChange::StyleCondition(None), type_system);
+            Ok(widget)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn none_disabled() -> (HashSet<HintRule>, HashSet<(HintRule, WidgetId)>) {
+        (HashSet::new(), HashSet::new())
+    }
+
+    /// Container > Column, returning the column id
+    fn base() -> (WidgetHierarchy, WidgetId) {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        (hierarchy, column_id)
+    }
+
+    #[test]
+    fn test_refused_container_add_suggests_column() {
+        let (mut hierarchy, column_id) = base();
+        let container_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        let button_id = hierarchy.add_child(container_id, WidgetType::Button).unwrap();
+        assert!(hierarchy.add_child(container_id, WidgetType::Button).is_err());
+
+        let refused = Some(RefusedAdd { container: container_id, widget_type: WidgetType::Button });
+        let (disabled, dismissed) = none_disabled();
+        let hint = suggest(&hierarchy, refused, &disabled, &dismissed).expect("hint fires");
+        assert_eq!(hint.rule(), HintRule::ContainerToColumn);
+        assert_eq!(hint.target, container_id);
+
+        apply_fix(&mut hierarchy, hint.fix, &ProjectDefaults::default(), &TypeSystem::new()).unwrap();
+        let container = hierarchy.get_widget_by_id(container_id).unwrap();
+        assert_eq!(container.children.len(), 1);
+        let column = &container.children[0];
+        assert_eq!(column.widget_type, WidgetType::Column);
+        assert_eq!(column.children.len(), 2);
+        assert_eq!(column.children[0].id, button_id);
+        assert!(suggest(&hierarchy, refused, &disabled, &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_no_hint_without_refusal() {
+        let (mut hierarchy, column_id) = base();
+        let container_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        hierarchy.add_child(container_id, WidgetType::Button).unwrap();
+
+        let (disabled, dismissed) = none_disabled();
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_single_child_row_removed() {
+        let (mut hierarchy, column_id) = base();
+        hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let button_id = hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+
+        let (disabled, dismissed) = none_disabled();
+        let hint = suggest(&hierarchy, None, &disabled, &dismissed).expect("hint fires");
+        assert_eq!(hint.fix, HintFix::RemoveRow { row: row_id });
+
+        apply_fix(&mut hierarchy, hint.fix, &ProjectDefaults::default(), &TypeSystem::new()).unwrap();
+        assert!(!hierarchy.widget_exists(row_id));
+        let column = hierarchy.get_widget_by_id(column_id).unwrap();
+        assert_eq!(column.children[1].id, button_id);
+    }
+
+    #[test]
+    fn test_root_row_is_left_alone() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let row_id = hierarchy.add_child(WidgetId(0), WidgetType::Row).unwrap();
+        hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+
+        let (disabled, dismissed) = none_disabled();
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_long_column_threshold() {
+        let (mut hierarchy, column_id) = base();
+        for _ in 0..LONG_COLUMN_CHILDREN {
+            hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        }
+        let (disabled, dismissed) = none_disabled();
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+
+        hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        let hint = suggest(&hierarchy, None, &disabled, &dismissed).expect("hint fires");
+        assert_eq!(hint.fix, HintFix::ScrollColumn { column: column_id });
+
+        let scroll_id = apply_fix(&mut hierarchy, hint.fix, &ProjectDefaults::default(), &TypeSystem::new()).unwrap();
+        let column = hierarchy.get_widget_by_id(column_id).unwrap();
+        assert_eq!(column.children.len(), 1);
+        assert_eq!(column.children[0].id, scroll_id);
+        assert_eq!(column.children[0].widget_type, WidgetType::Scrollable);
+        assert_eq!(column.children[0].children[0].children.len(), LONG_COLUMN_CHILDREN + 1);
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_disabled_and_dismissed_rules_are_skipped() {
+        let (mut hierarchy, column_id) = base();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+
+        let disabled = HashSet::from([HintRule::RemoveSingleChildRow]);
+        assert!(suggest(&hierarchy, None, &disabled, &HashSet::new()).is_none());
+
+        let dismissed = HashSet::from([(HintRule::RemoveSingleChildRow, row_id)]);
+        assert!(suggest(&hierarchy, None, &HashSet::new(), &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_refusal_takes_priority() {
+        let (mut hierarchy, column_id) = base();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        hierarchy.add_child(row_id, WidgetType::Text).unwrap();
+        let container_id = hierarchy.add_child(column_id, WidgetType::Container).unwrap();
+        hierarchy.add_child(container_id, WidgetType::Text).unwrap();
+
+        let refused = Some(RefusedAdd { container: container_id, widget_type: WidgetType::Text });
+        let (disabled, dismissed) = none_disabled();
+        let hint = suggest(&hierarchy, refused, &disabled, &dismissed).unwrap();
+        assert_eq!(hint.rule(), HintRule::ContainerToColumn);
+    }
+
+    /// base() with the Column at a fixed width
+    fn fixed_column(width: f32) -> (WidgetHierarchy, WidgetId) {
+        let (mut hierarchy, column_id) = base();
+        hierarchy.apply_property_change(column_id, PropertyChange::Width(Length::Fixed(width)), &TypeSystem::new());
+        (hierarchy, column_id)
+    }
+
+    fn add_unwrapped_text(hierarchy: &mut WidgetHierarchy, parent: WidgetId, content: &str) -> WidgetId {
+        let type_system = TypeSystem::new();
+        let text_id = hierarchy.add_child(parent, WidgetType::Text).unwrap();
+        hierarchy.apply_property_change(text_id, PropertyChange::TextContent(content.to_string()), &type_system);
+        hierarchy.apply_property_change(text_id, PropertyChange::TextWrap(TextWrapping::None), &type_system);
+        text_id
+    }
+
+    #[test]
+    fn test_text_width_estimate() {
+        assert_eq!(estimate_text_width("Hello", 20.0, false), 50.0);
+        assert_eq!(estimate_text_width("0123456789", 10.0, true), 60.0);
+        assert_eq!(estimate_text_width("iiii", 10.0, true), estimate_text_width("WWWW", 10.0, true));
+        assert_eq!(estimate_text_width("ab\nabcd", 10.0, false), 20.0);
+        assert_eq!(estimate_text_width("", 16.0, false), 0.0);
+        assert!((estimate_text_height("", 10.0) - 13.0).abs() < 0.01);
+        assert!((estimate_text_height("one\ntwo", 10.0) - 26.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_long_text_in_fixed_column_warns() {
+        let (mut hierarchy, column_id) = fixed_column(200.0);
+        let text_id = add_unwrapped_text(&mut hierarchy, column_id, &"x".repeat(40));
+
+        let (disabled, dismissed) = none_disabled();
+        let hint = suggest(&hierarchy, None, &disabled, &dismissed).expect("hint fires");
+        assert_eq!(hint.target, text_id);
+        assert_eq!(hint.fix, HintFix::ShrinkAncestor { ancestor: column_id });
+        assert_eq!(hint.alternative, Some(HintFix::WrapText { text: text_id }));
+
+        apply_fix(&mut hierarchy, hint.alternative.unwrap(), &ProjectDefaults::default(), &TypeSystem::new()).unwrap();
+        assert_eq!(hierarchy.get_widget_by_id(text_id).unwrap().properties.wrap, text::Wrapping::Word);
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_padding_narrows_available_width() {
+        let (mut hierarchy, column_id) = fixed_column(200.0);
+        // 20 chars at 16px is about 160px, which fits 200px but not 200px less 2 x 30px padding
+        add_unwrapped_text(&mut hierarchy, column_id, &"x".repeat(20));
+        let (disabled, dismissed) = none_disabled();
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+
+        hierarchy.apply_property_change(column_id, PropertyChange::PaddingUniform(30.0), &TypeSystem::new());
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_some());
+    }
+
+    #[test]
+    fn test_button_label_shrinks_parent() {
+        let (mut hierarchy, column_id) = fixed_column(100.0);
+        let button_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        hierarchy.apply_property_change(button_id, PropertyChange::TextContent("Save all changes and close".to_string()), &TypeSystem::new());
+
+        let (disabled, dismissed) = none_disabled();
+        let hint = suggest(&hierarchy, None, &disabled, &dismissed).expect("hint fires");
+        assert_eq!(hint.alternative, None);
+
+        apply_fix(&mut hierarchy, hint.fix, &ProjectDefaults::default(), &TypeSystem::new()).unwrap();
+        assert_eq!(hierarchy.get_widget_by_id(column_id).unwrap().properties.width, Length::Shrink);
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+
+    /// base() > wrapping Row holding Button, Space, Button, returning the row and space ids
+    fn wrapping_row_with_space(width: Length) -> (WidgetHierarchy, WidgetId, WidgetId) {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, column_id) = base();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+        let space_id = hierarchy.add_child(row_id, WidgetType::Space).unwrap();
+        hierarchy.add_child(row_id, WidgetType::Button).unwrap();
+        hierarchy.apply_property_change(space_id, PropertyChange::Width(width), &type_system);
+        hierarchy.apply_property_change(row_id, PropertyChange::IsWrappingRow(true), &type_system);
+        (hierarchy, row_id, space_id)
+    }
+
+    #[test]
+    fn test_fill_space_in_wrapping_row_warns() {
+        let (mut hierarchy, row_id, space_id) = wrapping_row_with_space(Length::Fill);
+        let (disabled, dismissed) = none_disabled();
+        let hint = suggest(&hierarchy, None, &disabled, &dismissed).expect("hint fires");
+        assert_eq!(hint.target, space_id);
+        assert_eq!(hint.fix, HintFix::FixSpace { space: space_id });
+        assert_eq!(hint.alternative, Some(HintFix::RemoveSpace { space: space_id }));
+
+        // The same row without wrapping is fine
+        hierarchy.apply_property_change(row_id, PropertyChange::IsWrappingRow(false), &TypeSystem::new());
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+        hierarchy.apply_property_change(row_id, PropertyChange::IsWrappingRow(true), &TypeSystem::new());
+
+        // Zero row spacing falls back to the default gap
+        apply_fix(&mut hierarchy, hint.fix, &ProjectDefaults::default(), &TypeSystem::new()).unwrap();
+        assert_eq!(hierarchy.get_widget_by_id(space_id).unwrap().properties.width, Length::Fixed(WRAPPED_ROW_GAP));
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_fixed_space_in_wrapping_row_is_fine() {
+        let (hierarchy, _, _) = wrapping_row_with_space(Length::Fixed(24.0));
+        let (disabled, dismissed) = none_disabled();
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_removing_space_falls_back_to_row_spacing() {
+        let (mut hierarchy, row_id, space_id) = wrapping_row_with_space(Length::FillPortion(2));
+        let (disabled, dismissed) = none_disabled();
+        let hint = suggest(&hierarchy, None, &disabled, &dismissed).expect("hint fires");
+
+        let selected = apply_fix(&mut hierarchy, hint.alternative.unwrap(), &ProjectDefaults::default(), &TypeSystem::new()).unwrap();
+        assert_eq!(selected, row_id);
+        assert!(!hierarchy.widget_exists(space_id));
+        let row = hierarchy.get_widget_by_id(row_id).unwrap();
+        assert_eq!(row.children.len(), 2);
+        assert_eq!(row.properties.spacing, WRAPPED_ROW_GAP);
+    }
+
+    #[test]
+    fn test_deleted_condition_source_warns() {
+        let type_system = TypeSystem::new();
+        let (mut hierarchy, column_id) = base();
+        let checkbox_id = hierarchy.add_child(column_id, WidgetType::Checkbox).unwrap();
+        let input_id = hierarchy.add_child(column_id, WidgetType::TextInput).unwrap();
+        hierarchy.apply_property_change(input_id, PropertyChange::StyleCondition(Some(StyleCondition::Widget(checkbox_id))), &type_system);
+        let (disabled, dismissed) = none_disabled();
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+
+        hierarchy.delete_widget(checkbox_id).unwrap();
+        let hint = suggest(&hierarchy, None, &disabled, &dismissed).expect("hint fires");
+        assert_eq!(hint.target, input_id);
+        assert_eq!(hint.rule(), HintRule::DanglingStyleCondition);
+
+        apply_fix(&mut hierarchy, hint.fix, &ProjectDefaults::default(), &type_system).unwrap();
+        assert_eq!(hierarchy.get_widget_by_id(input_id).unwrap().properties.style_condition, None);
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+
+    #[test]
+    fn test_fill_and_shrink_chains_never_warn() {
+        let (mut hierarchy, column_id) = base();
+        add_unwrapped_text(&mut hierarchy, column_id, &"x".repeat(500));
+        let (disabled, dismissed) = none_disabled();
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+
+        hierarchy.apply_property_change(column_id, PropertyChange::Width(Length::Shrink), &TypeSystem::new());
+        assert!(suggest(&hierarchy, None, &disabled, &dismissed).is_none());
+    }
+}
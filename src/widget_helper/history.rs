@@ -0,0 +1,467 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::palette_file::color_to_hex;
+use crate::widget_helper::*;
+use crate::widget_helper::named_styles::ContainerLook;
+
+/// Entries kept per widget; older ones are dropped first
+pub const MAX_ENTRIES_PER_WIDGET: usize = 50;
+/// Consecutive changes to the same key closer together than this become one entry
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(500);
+/// Longest rendered value before it gets cut off
+const MAX_VALUE_CHARS: usize = 32;
+
+/// The current value of the property a change targets, plus the changes that put it back
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub key: &'static str,
+    pub value: String,
+    pub restore: Vec<PropertyChange>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    /// Stable id for the revert button, unaffected by later entries
+    pub seq: u64,
+    pub key: &'static str,
+    pub old_value: String,
+    pub new_value: String,
+    pub at: Instant,
+    /// Changes that bring back `old_value`
+    pub revert: Vec<PropertyChange>,
+}
+
+/// Session-only log of property changes, per widget
+#[derive(Debug, Clone, Default)]
+pub struct PropertyHistory {
+    entries: HashMap<WidgetId, VecDeque<HistoryEntry>>,
+    next_seq: u64,
+}
+
+impl PropertyHistory {
+    /// Records a change from `before` to `new_value`. A change to the same key
+    /// within the coalescing window extends the previous entry instead.
+    pub fn record(&mut self, id: WidgetId, before: Snapshot, new_value: String, now: Instant) {
+        if before.value == new_value {
+            return;
+        }
+
+        let log = self.entries.entry(id).or_default();
+        if let Some(last) = log.back_mut() {
+            if last.key == before.key && now.saturating_duration_since(last.at) <= COALESCE_WINDOW {
+                last.new_value = new_value;
+                last.at = now;
+                // A drag that ends where it started didn't change anything
+                if last.new_value == last.old_value {
+                    log.pop_back();
+                }
+                return;
+            }
+        }
+
+        log.push_back(HistoryEntry {
+            seq: self.next_seq,
+            key: before.key,
+            old_value: before.value,
+            new_value,
+            at: now,
+            revert: before.restore,
+        });
+        self.next_seq += 1;
+
+        if log.len() > MAX_ENTRIES_PER_WIDGET {
+            log.pop_front();
+        }
+    }
+
+    /// Up to `limit` entries for the widget, newest first
+    pub fn recent(&self, id: WidgetId, limit: usize) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.get(&id).into_iter().flat_map(|log| log.iter().rev()).take(limit)
+    }
+
+    pub fn len(&self, id: WidgetId) -> usize {
+        self.entries.get(&id).map_or(0, VecDeque::len)
+    }
+
+    pub fn find(&self, id: WidgetId, seq: u64) -> Option<&HistoryEntry> {
+        self.entries.get(&id)?.iter().find(|entry| entry.seq == seq)
+    }
+
+    pub fn forget(&mut self, id: WidgetId) {
+        self.entries.remove(&id);
+    }
+}
+
+/// Snapshot of the property `change` is about to write, or `None` for changes
+/// that can't be replayed (drafts and text editor actions)
+pub fn snapshot(props: &Properties, change: &PropertyChange) -> Option<Snapshot> {
+    use PropertyChange as P;
+
+    let one = |key: &'static str, value: String, restore: PropertyChange| {
+        Some(Snapshot { key, value, restore: vec![restore] })
+    };
+
+    match change {
+        P::WidgetName(_)  => one("widget_name", quoted(&props.widget_name), P::WidgetName(props.widget_name.clone())),
+        P::Width(_)       => one("width", length_to_string(props.width), P::Width(props.width)),
+        P::Height(_)      => one("height", length_to_string(props.height), P::Height(props.height)),
+        P::MaxWidth(_)    => one("max_width", optional(props.max_width.map(num)), P::MaxWidth(props.max_width)),
+        P::MaxHeight(_)   => one("max_height", optional(props.max_height.map(num)), P::MaxHeight(props.max_height)),
+        P::Clip(_)        => one("clip", props.clip.to_string(), P::Clip(props.clip)),
+        P::WidgetId(_)    => one("widget_id", optional(props.widget_id.as_deref().map(quoted)), P::WidgetId(props.widget_id.clone())),
+
+        P::PaddingMode(_) | P::PaddingUniform(_) | P::PaddingVertical(_) | P::PaddingHorizontal(_) |
+        P::PaddingTop(_) | P::PaddingRight(_) | P::PaddingBottom(_) | P::PaddingLeft(_) => {
+            let p = props.padding;
+            Some(Snapshot {
+                key: "padding",
+                value: format!("{:?} {} {} {} {}", props.padding_mode, num(p.top), num(p.right), num(p.bottom), num(p.left)),
+                // Set every side individually, then switch back to the old mode, which keeps them
+                restore: vec![
+                    P::PaddingMode(PaddingMode::Individual),
+                    P::PaddingTop(p.top),
+                    P::PaddingRight(p.right),
+                    P::PaddingBottom(p.bottom),
+                    P::PaddingLeft(p.left),
+                    P::PaddingMode(props.padding_mode),
+                ],
+            })
+        }
+
+        P::AlignX(_)                => one("align_x", debug(props.align_x), P::AlignX(props.align_x)),
+        P::AlignY(_)                => one("align_y", debug(props.align_y), P::AlignY(props.align_y)),
+        P::BorderWidth(_)           => one("border_width", num(props.border_width), P::BorderWidth(props.border_width)),
+        P::BorderRadius(_)          => one("border_radius", num(props.border_radius), P::BorderRadius(props.border_radius)),
+        P::BorderColor(_)           => one("border_color", color_to_hex(props.border_color), P::BorderColor(props.border_color)),
+        P::BackgroundColor(_)       => one("background_color", color_to_hex(props.background_color), P::BackgroundColor(props.background_color)),
+        P::GradientEnabled(_)       => one("gradient_enabled", props.gradient_enabled.to_string(), P::GradientEnabled(props.gradient_enabled)),
+        P::GradientStart(_)         => one("gradient_start", color_to_hex(props.gradient_start), P::GradientStart(props.gradient_start)),
+        P::GradientEnd(_)           => one("gradient_end", color_to_hex(props.gradient_end), P::GradientEnd(props.gradient_end)),
+        P::GradientAngle(_)         => one("gradient_angle", num(props.gradient_angle), P::GradientAngle(props.gradient_angle)),
+        P::FloatAnchor(_)           => one("float_anchor", optional(props.float_anchor.map(debug)), P::FloatAnchor(props.float_anchor)),
+        P::FloatOffsetX(_)          => one("float_offset_x", num(props.float_offset_x), P::FloatOffsetX(props.float_offset_x)),
+        P::FloatOffsetY(_)          => one("float_offset_y", num(props.float_offset_y), P::FloatOffsetY(props.float_offset_y)),
+        P::SectionAnchor(_)         => one("section_anchor", props.section_anchor.to_string(), P::SectionAnchor(props.section_anchor)),
+        P::SectionName(_)           => one("section_name", quoted(&props.section_name), P::SectionName(props.section_name.clone())),
+        P::BreakpointEnabled(_)     => one("breakpoint_enabled", props.breakpoint_enabled.to_string(), P::BreakpointEnabled(props.breakpoint_enabled)),
+        P::BreakpointWidth(_)       => one("breakpoint_width", num(props.breakpoint_width), P::BreakpointWidth(props.breakpoint_width)),
+        P::StackBelowBreakpoint(_)  => one("stack_below_breakpoint", props.stack_below_breakpoint.to_string(), P::StackBelowBreakpoint(props.stack_below_breakpoint)),
+        P::ContainerSizingMode(_)   => one("container_sizing_mode", debug(props.container_sizing_mode), P::ContainerSizingMode(props.container_sizing_mode)),
+        P::ContainerCenterLength(_) => one("container_center_length", length_to_string(props.container_center_length), P::ContainerCenterLength(props.container_center_length)),
+
+        P::IsWrappingRow(_) => one("is_wrapping_row", props.is_wrapping_row.to_string(), P::IsWrappingRow(props.is_wrapping_row)),
+        P::WrappingVerticalSpacing(_) => {
+            let spacing = props.wrapping_vertical_spacing?;
+            one("wrapping_vertical_spacing", num(spacing), P::WrappingVerticalSpacing(spacing))
+        }
+        P::WrappingAlignX(_) => one("wrapping_align_x", debug(props.wrapping_align_x), P::WrappingAlignX(props.wrapping_align_x)),
+
+        P::Spacing(_)    => one("spacing", num(props.spacing), P::Spacing(props.spacing)),
+        P::AlignItems(_) => one("align_items", debug(props.align_items), P::AlignItems(props.align_items)),
+
+        P::TextContent(_)    => one("text_content", quoted(&props.text_content), P::TextContent(props.text_content.clone())),
+        P::TextSize(_)       => one("text_size", num(props.text_size), P::TextSize(props.text_size)),
+        P::TextColor(_)      => one("text_color", color_to_hex(props.text_color), P::TextColor(props.text_color)),
+        P::Font(_)           => one("font", debug(props.font), P::Font(props.font)),
+        P::TextLineHeight(_) => one("line_height", debug(props.line_height), P::TextLineHeight(props.line_height)),
+        P::TextWrap(_)       => one("wrap", debug(props.wrap), P::TextWrap(TextWrapping::from(props.wrap))),
+        P::TextShaping(_)    => one("shaping", debug(props.shaping), P::TextShaping(TextShaping::from(props.shaping))),
+        P::TextAlignX(_)     => one("text_align_x", debug(props.text_align_x), P::TextAlignX(AlignText::from(props.text_align_x))),
+        P::TextAlignY(_)     => one("text_align_y", debug(props.text_align_y), P::TextAlignY(AlignmentYOption::from(props.text_align_y))),
+
+        P::ButtonStyle(_) => one("button_style", debug(props.button_style), P::ButtonStyle(props.button_style)),
+        P::ButtonPressHandler(_) => {
+            let handler = if props.button_on_press_enabled {
+                OnHandler::OnAction
+            } else if props.button_on_press_with_enabled {
+                OnHandler::OnActionWith
+            } else if props.button_on_press_maybe_enabled {
+                OnHandler::OnActionMaybe
+            } else {
+                OnHandler::None
+            };
+            one("button_on_press", debug(handler), P::ButtonPressHandler(handler))
+        }
+
+        P::TextInputValue(_)       => one("text_input_value", quoted(&props.text_input_value), P::TextInputValue(props.text_input_value.clone())),
+        P::TextInputPlaceholder(_) => one("text_input_placeholder", quoted(&props.text_input_placeholder), P::TextInputPlaceholder(props.text_input_placeholder.clone())),
+        P::TextInputSize(_)        => one("text_input_size", num(props.text_input_size), P::TextInputSize(props.text_input_size)),
+        P::TextInputPadding(_)     => one("text_input_padding", num(props.text_input_padding), P::TextInputPadding(props.text_input_padding)),
+        P::IsSecure(_)             => one("is_secure", props.is_secure.to_string(), P::IsSecure(props.is_secure)),
+        P::TextInputOnSubmit(_)    => one("text_input_on_submit", props.text_input_on_submit.to_string(), P::TextInputOnSubmit(props.text_input_on_submit)),
+        P::TextInputOnPaste(_)     => one("text_input_on_paste", props.text_input_on_paste.to_string(), P::TextInputOnPaste(props.text_input_on_paste)),
+        P::TextInputFont(_)        => one("text_input_font", debug(props.text_input_font), P::TextInputFont(props.text_input_font)),
+        P::TextInputLineHeight(_)  => one("text_input_line_height", debug(props.text_input_line_height), P::TextInputLineHeight(props.text_input_line_height)),
+        P::TextInputAlignment(_)   => one("text_input_alignment", debug(props.text_input_alignment), P::TextInputAlignment(props.text_input_alignment)),
+
+        P::CheckboxChecked(_) => one("checkbox_checked", props.checkbox_checked.to_string(), P::CheckboxChecked(props.checkbox_checked)),
+        P::CheckboxLabel(_)   => one("checkbox_label", quoted(&props.checkbox_label), P::CheckboxLabel(props.checkbox_label.clone())),
+        P::CheckboxSize(_)    => one("checkbox_size", num(props.checkbox_size), P::CheckboxSize(props.checkbox_size)),
+        P::CheckboxSpacing(_) => one("checkbox_spacing", num(props.checkbox_spacing), P::CheckboxSpacing(props.checkbox_spacing)),
+
+        P::RadioSelectedIndex(_) => one("radio_selected_index", props.radio_selected_index.to_string(), P::RadioSelectedIndex(props.radio_selected_index)),
+        P::RadioOptions(_) | P::RadioOptionsEdit(_) => Some(Snapshot {
+            key: "radio_options",
+            value: list(&props.radio_options),
+            restore: vec![
+                P::RadioOptions(props.radio_options.clone()),
+                P::RadioSelectedIndex(props.radio_selected_index),
+            ],
+        }),
+        P::RadioLabel(_)   => one("radio_label", quoted(&props.radio_label), P::RadioLabel(props.radio_label.clone())),
+        P::RadioSize(_)    => one("radio_size", num(props.radio_size), P::RadioSize(props.radio_size)),
+        P::RadioSpacing(_) => one("radio_spacing", num(props.radio_spacing), P::RadioSpacing(props.radio_spacing)),
+
+        P::SliderValue(_)  => one("slider_value", num(props.slider_value), P::SliderValue(props.slider_value)),
+        P::SliderMin(_)    => one("slider_min", num(props.slider_min), P::SliderMin(props.slider_min)),
+        P::SliderMax(_)    => one("slider_max", num(props.slider_max), P::SliderMax(props.slider_max)),
+        P::SliderStep(_)   => one("slider_step", num(props.slider_step), P::SliderStep(props.slider_step)),
+        P::SliderHeight(_) => one("slider_height", num(props.slider_height), P::SliderHeight(props.slider_height)),
+        P::SliderWidth(_)  => one("slider_width", num(props.slider_width), P::SliderWidth(props.slider_width)),
+
+        P::ProgressValue(_)    => one("progress_value", num(props.progress_value), P::ProgressValue(props.progress_value)),
+        P::ProgressMin(_)      => one("progress_min", num(props.progress_min), P::ProgressMin(props.progress_min)),
+        P::ProgressMax(_)      => one("progress_max", num(props.progress_max), P::ProgressMax(props.progress_max)),
+        P::ProgressLength(_)   => one("progress_length", length_to_string(props.progress_length), P::ProgressLength(props.progress_length)),
+        P::ProgressGirth(_)    => one("progress_girth", num(props.progress_girth), P::ProgressGirth(props.progress_girth)),
+        P::ProgressVertical(_) => one("progress_vertical", props.progress_vertical.to_string(), P::ProgressVertical(props.progress_vertical)),
+
+        P::TogglerActive(_)  => one("toggler_active", props.toggler_active.to_string(), P::TogglerActive(props.toggler_active)),
+        P::TogglerLabel(_)   => one("toggler_label", quoted(&props.toggler_label), P::TogglerLabel(props.toggler_label.clone())),
+        P::TogglerSize(_)    => one("toggler_size", num(props.toggler_size), P::TogglerSize(props.toggler_size)),
+        P::TogglerSpacing(_) => one("toggler_spacing", num(props.toggler_spacing), P::TogglerSpacing(props.toggler_spacing)),
+
+        P::LabelPosition(_) => one("label_position", debug(props.label_position), P::LabelPosition(props.label_position)),
+
+        P::PickListSelected(_)    => one("picklist_selected", optional(props.picklist_selected.as_deref().map(quoted)), P::PickListSelected(props.picklist_selected.clone())),
+        P::PickListPlaceholder(_) => one("picklist_placeholder", quoted(&props.picklist_placeholder), P::PickListPlaceholder(props.picklist_placeholder.clone())),
+        P::PickListOptions(_)     => one("picklist_options", list(&props.picklist_options), P::PickListOptions(props.picklist_options.clone())),
+
+        P::Orientation(_)   => one("orientation", debug(props.orientation), P::Orientation(props.orientation)),
+        P::RuleThickness(_) => one("rule_thickness", num(props.rule_thickness), P::RuleThickness(props.rule_thickness)),
+
+        P::ScrollableDirection(_) => one("scroll_dir", debug(props.scroll_dir), P::ScrollableDirection(props.scroll_dir)),
+        P::ScrollableAnchorX(_)   => one("anchor_x", debug(props.anchor_x), P::ScrollableAnchorX(props.anchor_x)),
+        P::ScrollableAnchorY(_)   => one("anchor_y", debug(props.anchor_y), P::ScrollableAnchorY(props.anchor_y)),
+
+        P::ImagePath(_)    => one("image_path", quoted(&props.image_path), P::ImagePath(props.image_path.clone())),
+        P::ImageFit(_)     => one("image_fit", props.image_fit.to_string(), P::ImageFit(props.image_fit)),
+        P::SvgPath(_)      => one("svg_path", quoted(&props.svg_path), P::SvgPath(props.svg_path.clone())),
+        P::SvgFit(_)       => one("svg_fit", props.svg_fit.to_string(), P::SvgFit(props.svg_fit)),
+        P::SvgTint(_)      => one("svg_tint", props.svg_tint.to_string(), P::SvgTint(props.svg_tint)),
+        P::SvgTintColor(_) => one("svg_tint_color", color_to_hex(props.svg_tint_color), P::SvgTintColor(props.svg_tint_color)),
+        P::ImageAsset(..)  => one("image_path", quoted(&props.image_path), P::ImageAsset(props.library_asset, props.image_path.clone())),
+        P::SvgAsset(..)    => one("svg_path", quoted(&props.svg_path), P::SvgAsset(props.library_asset, props.svg_path.clone())),
+
+        P::TooltipText(_)     => one("tooltip_text", quoted(&props.tooltip_text), P::TooltipText(props.tooltip_text.clone())),
+        P::TooltipPosition(_) => one("tooltip_position", debug(props.tooltip_position), P::TooltipPosition(props.tooltip_position)),
+        P::TooltipGap(_)      => one("tooltip_gap", num(props.tooltip_gap), P::TooltipGap(props.tooltip_gap)),
+
+        P::ComboBoxPlaceholder(_)        => one("combobox_placeholder", quoted(&props.combobox_placeholder), P::ComboBoxPlaceholder(props.combobox_placeholder.clone())),
+        P::ComboBoxSelected(_)           => one("combobox_selected", optional(props.combobox_selected.as_deref().map(quoted)), P::ComboBoxSelected(props.combobox_selected.clone())),
+        P::ComboBoxState(_)              => one("combobox_options", list(&props.combobox_options), P::ComboBoxState(props.combobox_options.clone())),
+        P::ComboBoxUseOnInput(_)         => one("combobox_use_on_input", props.combobox_use_on_input.to_string(), P::ComboBoxUseOnInput(props.combobox_use_on_input)),
+        P::ComboBoxUseOnOptionHovered(_) => one("combobox_use_on_option_hovered", props.combobox_use_on_option_hovered.to_string(), P::ComboBoxUseOnOptionHovered(props.combobox_use_on_option_hovered)),
+        P::ComboBoxUseOnOpen(_)          => one("combobox_use_on_open", props.combobox_use_on_open.to_string(), P::ComboBoxUseOnOpen(props.combobox_use_on_open)),
+        P::ComboBoxUseOnClose(_)         => one("combobox_use_on_close", props.combobox_use_on_close.to_string(), P::ComboBoxUseOnClose(props.combobox_use_on_close)),
+        P::ComboBoxSize(_)               => one("combobox_size", num(props.combobox_size), P::ComboBoxSize(props.combobox_size)),
+        P::ComboBoxPadding(_)            => one("combobox_padding", num(props.combobox_padding), P::ComboBoxPadding(props.combobox_padding)),
+        P::ComboBoxEnumId(_)             => one("referenced_enum", optional(props.referenced_enum.map(|id| id.to_string())), P::ComboBoxEnumId(props.referenced_enum)),
+        P::ComboBoxInputBackground(_)    => one("combobox_input_background", optional(props.combobox_input_background.map(color_to_hex)), P::ComboBoxInputBackground(props.combobox_input_background)),
+        P::ComboBoxInputTextColor(_)     => one("combobox_input_text_color", optional(props.combobox_input_text_color.map(color_to_hex)), P::ComboBoxInputTextColor(props.combobox_input_text_color)),
+        P::ComboBoxMenuBackground(_)     => one("combobox_menu_background", optional(props.combobox_menu_background.map(color_to_hex)), P::ComboBoxMenuBackground(props.combobox_menu_background)),
+        P::ComboBoxMenuHeight(_)         => one("combobox_menu_height", optional(props.combobox_menu_height.map(num)), P::ComboBoxMenuHeight(props.combobox_menu_height)),
+
+        P::MarkdownTextSize(_) => one("markdown_text_size", num(props.markdown_text_size), P::MarkdownTextSize(props.markdown_text_size)),
+
+        P::QRCodeData(_)     => one("qrcode_data", quoted(&props.qrcode_data), P::QRCodeData(props.qrcode_data.clone())),
+        P::QRCodeCellSize(_) => one("qrcode_cell_size", num(props.qrcode_cell_size), P::QRCodeCellSize(props.qrcode_cell_size)),
+
+        P::ThemerTheme(_) => one("themer_theme", optional(props.themer_theme.as_ref().map(|t| t.to_string())), P::ThemerTheme(props.themer_theme.clone())),
+
+        P::MouseAreaOnPress(_)         => one("mousearea_on_press", props.mousearea_on_press.to_string(), P::MouseAreaOnPress(props.mousearea_on_press)),
+        P::MouseAreaOnRelease(_)       => one("mousearea_on_release", props.mousearea_on_release.to_string(), P::MouseAreaOnRelease(props.mousearea_on_release)),
+        P::MouseAreaOnDoubleClick(_)   => one("mousearea_on_double_click", props.mousearea_on_double_click.to_string(), P::MouseAreaOnDoubleClick(props.mousearea_on_double_click)),
+        P::MouseAreaOnRightPress(_)    => one("mousearea_on_right_press", props.mousearea_on_right_press.to_string(), P::MouseAreaOnRightPress(props.mousearea_on_right_press)),
+        P::MouseAreaOnRightRelease(_)  => one("mousearea_on_right_release", props.mousearea_on_right_release.to_string(), P::MouseAreaOnRightRelease(props.mousearea_on_right_release)),
+        P::MouseAreaOnMiddlePress(_)   => one("mousearea_on_middle_press", props.mousearea_on_middle_press.to_string(), P::MouseAreaOnMiddlePress(props.mousearea_on_middle_press)),
+        P::MouseAreaOnMiddleRelease(_) => one("mousearea_on_middle_release", props.mousearea_on_middle_release.to_string(), P::MouseAreaOnMiddleRelease(props.mousearea_on_middle_release)),
+        P::MouseAreaOnScroll(_)        => one("mousearea_on_scroll", props.mousearea_on_scroll.to_string(), P::MouseAreaOnScroll(props.mousearea_on_scroll)),
+        P::MouseAreaOnEnter(_)         => one("mousearea_on_enter", props.mousearea_on_enter.to_string(), P::MouseAreaOnEnter(props.mousearea_on_enter)),
+        P::MouseAreaOnMove(_)          => one("mousearea_on_move", props.mousearea_on_move.to_string(), P::MouseAreaOnMove(props.mousearea_on_move)),
+        P::MouseAreaOnExit(_)          => one("mousearea_on_exit", props.mousearea_on_exit.to_string(), P::MouseAreaOnExit(props.mousearea_on_exit)),
+        P::MouseAreaInteraction(_)     => one("mousearea_interaction", optional(props.mousearea_interaction.map(debug)), P::MouseAreaInteraction(props.mousearea_interaction)),
+
+        P::FileDialog(_)         => one("file_dialog", props.file_dialog.to_string(), P::FileDialog(props.file_dialog)),
+        P::FileDialogPathText(_) => one("file_dialog_path_text", optional(props.file_dialog_path_text.map(|id| id.0.to_string())), P::FileDialogPathText(props.file_dialog_path_text)),
+
+        P::StyleCondition(_)           => one("style_condition", optional(props.style_condition.as_ref().map(debug)), P::StyleCondition(props.style_condition.clone())),
+        P::ConditionBorderColor(_)     => one("condition_border_color", color_to_hex(props.condition_border_color), P::ConditionBorderColor(props.condition_border_color)),
+        P::ConditionBackgroundColor(_) => one("condition_background_color", color_to_hex(props.condition_background_color), P::ConditionBackgroundColor(props.condition_background_color)),
+        P::PreviewCondition(_)         => one("preview_condition", props.preview_condition.to_string(), P::PreviewCondition(props.preview_condition)),
+
+        P::PreviewText(_)      => one("preview_text", optional(props.preview_text.as_deref().map(quoted)), P::PreviewText(props.preview_text.clone())),
+        P::PreviewProgress(_)  => one("preview_progress", optional(props.preview_progress.map(num)), P::PreviewProgress(props.preview_progress)),
+        P::PreviewSelection(_) => one("preview_selection", optional(props.preview_selection.as_deref().map(quoted)), P::PreviewSelection(props.preview_selection.clone())),
+
+        P::Tags(_) | P::AddTag(_) | P::RemoveTag(_) => one("tags", list(&props.tags), P::Tags(props.tags.clone())),
+        P::NamedStyle(..) => one(
+            "named_style",
+            optional(props.named_style.map(|id| id.to_string())),
+            P::NamedStyle(props.named_style, ContainerLook::of(props)),
+        ),
+
+        // Drafts are echoed into width/height, which are logged when set directly.
+        // Editor actions and the unused shadow setters have nothing to restore.
+        P::DraftFixedWidth(_) | P::DraftFixedHeight(_) | P::DraftFillPortionWidth(_) | P::DraftFillPortionHeight(_) |
+        P::HasShadow(_) | P::ShadowOffsetX(_) | P::ShadowOffsetY(_) | P::ShadowBlur(_) | P::ShadowColor(_) |
+        P::MarkdownContent(_) | P::Noop => None,
+    }
+}
+
+/// Short "how long ago" label for the timeline
+pub fn elapsed_label(at: Instant, now: Instant) -> String {
+    let secs = now.saturating_duration_since(at).as_secs();
+    match secs {
+        0..=4     => "just now".to_string(),
+        5..=59    => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        _         => format!("{}h ago", secs / 3600),
+    }
+}
+
+fn num(v: f32) -> String {
+    if v.fract() == 0.0 { format!("{:.0}", v) } else { format!("{:.2}", v) }
+}
+
+fn debug(v: impl std::fmt::Debug) -> String {
+    shorten(format!("{:?}", v))
+}
+
+fn quoted(s: &str) -> String {
+    shorten(format!("\"{}\"", s))
+}
+
+fn optional(v: Option<String>) -> String {
+    v.unwrap_or_else(|| "none".to_string())
+}
+
+fn list(items: &[String]) -> String {
+    shorten(format!("[{}]", items.join(", ")))
+}
+
+fn shorten(s: String) -> String {
+    if s.chars().count() <= MAX_VALUE_CHARS {
+        return s;
+    }
+    let mut short: String = s.chars().take(MAX_VALUE_CHARS - 1).collect();
+    short.push('…');
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::type_system::TypeSystem;
+
+    fn text_hierarchy() -> (WidgetHierarchy, WidgetId) {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let text_id = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        (hierarchy, text_id)
+    }
+
+    fn size_snapshot(size: f32) -> Snapshot {
+        let mut props = Properties::default();
+        props.text_size = size;
+        snapshot(&props, &PropertyChange::TextSize(0.0)).unwrap()
+    }
+
+    #[test]
+    fn test_change_recorded_with_old_and_new() {
+        let (mut hierarchy, text_id) = text_hierarchy();
+        let type_system = TypeSystem::new();
+        hierarchy.apply_property_change(text_id, PropertyChange::TextSize(24.0), &type_system);
+
+        let entries: Vec<_> = hierarchy.history().recent(text_id, 20).collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "text_size");
+        assert_eq!(entries[0].old_value, "16");
+        assert_eq!(entries[0].new_value, "24");
+    }
+
+    #[test]
+    fn test_unchanged_value_not_recorded() {
+        let (mut hierarchy, text_id) = text_hierarchy();
+        let type_system = TypeSystem::new();
+        let size = hierarchy.get_widget_by_id(text_id).unwrap().properties.text_size;
+        hierarchy.apply_property_change(text_id, PropertyChange::TextSize(size), &type_system);
+        assert_eq!(hierarchy.history().len(text_id), 0);
+    }
+
+    #[test]
+    fn test_slider_stream_coalesces() {
+        let mut history = PropertyHistory::default();
+        let id = WidgetId(1);
+        let start = Instant::now();
+
+        history.record(id, size_snapshot(16.0), "17".into(), start);
+        history.record(id, size_snapshot(17.0), "18".into(), start + Duration::from_millis(100));
+        history.record(id, size_snapshot(18.0), "20".into(), start + Duration::from_millis(550));
+        assert_eq!(history.len(id), 1);
+        let entry = history.recent(id, 20).next().unwrap();
+        assert_eq!((entry.old_value.as_str(), entry.new_value.as_str()), ("16", "20"));
+
+        // A pause longer than the window starts a new entry
+        history.record(id, size_snapshot(20.0), "22".into(), start + Duration::from_millis(1200));
+        assert_eq!(history.len(id), 2);
+        assert_eq!(history.recent(id, 20).next().unwrap().old_value, "20");
+    }
+
+    #[test]
+    fn test_drag_back_to_start_drops_entry() {
+        let mut history = PropertyHistory::default();
+        let id = WidgetId(1);
+        let start = Instant::now();
+        history.record(id, size_snapshot(16.0), "20".into(), start);
+        history.record(id, size_snapshot(20.0), "16".into(), start + Duration::from_millis(200));
+        assert_eq!(history.len(id), 0);
+    }
+
+    #[test]
+    fn test_memory_capped_per_widget() {
+        let mut history = PropertyHistory::default();
+        let id = WidgetId(1);
+        let start = Instant::now();
+        for i in 0..(MAX_ENTRIES_PER_WIDGET + 10) {
+            let at = start + COALESCE_WINDOW * 2 * i as u32;
+            history.record(id, size_snapshot(i as f32), (i + 1).to_string(), at);
+        }
+        assert_eq!(history.len(id), MAX_ENTRIES_PER_WIDGET);
+        // Newest first, oldest ones dropped
+        assert_eq!(history.recent(id, 1).next().unwrap().old_value, (MAX_ENTRIES_PER_WIDGET + 9).to_string());
+    }
+
+    #[test]
+    fn test_revert_restores_old_value() {
+        let (mut hierarchy, text_id) = text_hierarchy();
+        let type_system = TypeSystem::new();
+        let before = hierarchy.get_widget_by_id(text_id).unwrap().properties.clone();
+
+        // Quick successive padding edits coalesce into one entry
+        hierarchy.apply_property_change(text_id, PropertyChange::PaddingUniform(12.0), &type_system);
+        hierarchy.apply_property_change(text_id, PropertyChange::PaddingMode(PaddingMode::Individual), &type_system);
+        hierarchy.apply_property_change(text_id, PropertyChange::PaddingLeft(3.0), &type_system);
+        assert_eq!(hierarchy.history().len(text_id), 1);
+
+        let revert = hierarchy.history().recent(text_id, 1).next().unwrap().revert.clone();
+        for change in revert {
+            hierarchy.apply_property_change(text_id, change, &type_system);
+        }
+
+        let props = &hierarchy.get_widget_by_id(text_id).unwrap().properties;
+        assert_eq!(props.padding_mode, before.padding_mode);
+        assert_eq!(props.padding, before.padding);
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+use serde::{Deserialize, Serialize};
+
+/// Builder actions that can be bound to a shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyAction {
+    DeleteSelection,
+    Duplicate,
+    Undo,
+    Redo,
+    OpenPalette,
+    /// Second palette shortcut, kept from before shortcuts could be rebound
+    OpenPaletteAlternate,
+    ZoomIn,
+    ZoomOut,
+    ExtendSelectionUp,
+    ExtendSelectionDown,
+    FocusWidgetFilter,
+    SelectPrevious,
+    SelectNext,
+    SelectParent,
+    SelectFirstChild,
+    MoveUp,
+    MoveDown,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 17] = [
+        KeyAction::DeleteSelection,
+        KeyAction::Duplicate,
+        KeyAction::Undo,
+        KeyAction::Redo,
+        KeyAction::OpenPalette,
+        KeyAction::OpenPaletteAlternate,
+        KeyAction::ZoomIn,
+        KeyAction::ZoomOut,
+        KeyAction::ExtendSelectionUp,
+        KeyAction::ExtendSelectionDown,
+        KeyAction::FocusWidgetFilter,
+        KeyAction::SelectPrevious,
+        KeyAction::SelectNext,
+        KeyAction::SelectParent,
+        KeyAction::SelectFirstChild,
+        KeyAction::MoveUp,
+        KeyAction::MoveDown,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            KeyAction::DeleteSelection => "Delete selection",
+            KeyAction::Duplicate => "Duplicate",
+            KeyAction::Undo => "Undo",
+            KeyAction::Redo => "Redo",
+            KeyAction::OpenPalette => "Open command palette",
+            KeyAction::OpenPaletteAlternate => "Open command palette (alternate)",
+            KeyAction::ZoomIn => "Zoom in",
+            KeyAction::ZoomOut => "Zoom out",
+            KeyAction::ExtendSelectionUp => "Extend selection up",
+            KeyAction::ExtendSelectionDown => "Extend selection down",
+            KeyAction::FocusWidgetFilter => "Filter widget palette",
+            KeyAction::SelectPrevious => "Select previous row",
+            KeyAction::SelectNext => "Select next row",
+            KeyAction::SelectParent => "Collapse, or select parent",
+            KeyAction::SelectFirstChild => "Expand, or select first child",
+            KeyAction::MoveUp => "Move up among siblings",
+            KeyAction::MoveDown => "Move down among siblings",
+        }
+    }
+
+    pub fn default_combo(&self) -> KeyCombo {
+        match self {
+            KeyAction::DeleteSelection => KeyCombo::new("Delete", false, false, false),
+            KeyAction::Duplicate => KeyCombo::new("d", true, false, false),
+            KeyAction::Undo => KeyCombo::new("z", true, false, false),
+            KeyAction::Redo => KeyCombo::new("z", true, true, false),
+            KeyAction::OpenPalette => KeyCombo::new("p", true, false, false),
+            KeyAction::OpenPaletteAlternate => KeyCombo::new("a", true, true, false),
+            KeyAction::ZoomIn => KeyCombo::new("=", true, false, false),
+            KeyAction::ZoomOut => KeyCombo::new("-", true, false, false),
+            KeyAction::ExtendSelectionUp => KeyCombo::new("ArrowUp", false, true, false),
+            KeyAction::ExtendSelectionDown => KeyCombo::new("ArrowDown", false, true, false),
+            KeyAction::FocusWidgetFilter => KeyCombo::new("/", false, false, false),
+            KeyAction::SelectPrevious => KeyCombo::new("ArrowUp", false, false, false),
+            KeyAction::SelectNext => KeyCombo::new("ArrowDown", false, false, false),
+            KeyAction::SelectParent => KeyCombo::new("ArrowLeft", false, false, false),
+            KeyAction::SelectFirstChild => KeyCombo::new("ArrowRight", false, false, false),
+            KeyAction::MoveUp => KeyCombo::new("ArrowUp", true, false, false),
+            KeyAction::MoveDown => KeyCombo::new("ArrowDown", true, false, false),
+        }
+    }
+}
+
+/// A key and its modifiers, normalized so equal presses hash equally.
+/// Character keys are lowercased, named keys use their iced name (`Delete`, `ArrowUp`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyCombo {
+    key: String,
+    command: bool,
+    shift: bool,
+    alt: bool,
+}
+
+impl KeyCombo {
+    pub fn new(key: &str, command: bool, shift: bool, alt: bool) -> Self {
+        Self { key: normalize_key(key), command, shift, alt }
+    }
+
+    /// None for a bare modifier press, which can't be bound on its own
+    pub fn from_event(key: &Key, modifiers: Modifiers) -> Option<Self> {
+        let name = match key.as_ref() {
+            Key::Character(c) => c.to_string(),
+            Key::Named(Named::Shift | Named::Control | Named::Alt | Named::Super | Named::Meta) => return None,
+            Key::Named(named) => format!("{:?}", named),
+            Key::Unidentified => return None,
+        };
+        Some(Self::new(&name, modifiers.command(), modifiers.shift(), modifiers.alt()))
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    if key.chars().count() == 1 { key.to_lowercase() } else { key.to_string() }
+}
+
+impl std::fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.command { write!(f, "Ctrl+")?; }
+        if self.shift { write!(f, "Shift+")?; }
+        if self.alt { write!(f, "Alt+")?; }
+        if self.key.chars().count() == 1 {
+            write!(f, "{}", self.key.to_uppercase())
+        } else {
+            write!(f, "{}", self.key)
+        }
+    }
+}
+
+impl std::str::FromStr for KeyCombo {
+    type Err = String;
+
+    /// Parses the `Display` form, e.g. `Ctrl+Shift+Z`. `+` itself is written as the last segment of `Ctrl++`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None if s == "+" => ("", "+"),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+        if key.is_empty() {
+            return Err(format!("Missing key in shortcut \"{}\"", s));
+        }
+
+        let (mut command, mut shift, mut alt) = (false, false, false);
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" => command = true,
+                "shift" => shift = true,
+                "alt" => alt = true,
+                other => return Err(format!("Unknown modifier \"{}\" in shortcut \"{}\"", other, s)),
+            }
+        }
+
+        Ok(Self::new(key, command, shift, alt))
+    }
+}
+
+impl TryFrom<String> for KeyCombo {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<KeyCombo> for String {
+    fn from(combo: KeyCombo) -> Self {
+        combo.to_string()
+    }
+}
+
+/// One rebound action as stored in the settings file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindingOverride {
+    pub action: KeyAction,
+    pub keys: KeyCombo,
+}
+
+/// Current shortcut for every action, plus the reverse map the key handler looks presses up in
+#[derive(Debug, Clone)]
+pub struct Keybindings {
+    bindings: HashMap<KeyAction, KeyCombo>,
+    lookup: HashMap<KeyCombo, KeyAction>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        let bindings: HashMap<_, _> = KeyAction::ALL.iter().map(|&action| (action, action.default_combo())).collect();
+        let lookup = bindings.iter().map(|(&action, combo)| (combo.clone(), action)).collect();
+        Self { bindings, lookup }
+    }
+}
+
+impl Keybindings {
+    /// Saved overrides, then the defaults of the other actions. Overrides go in first so one
+    /// can take a combo whose default owner was rebound too. An override clashing with an
+    /// earlier one is skipped, and one holding the default of an action with no override is
+    /// dropped so that action keeps its shortcut.
+    pub fn with_overrides(overrides: &[KeyBindingOverride]) -> Self {
+        let mut overrides = overrides.to_vec();
+        'retry: loop {
+            let mut keybindings = Self { bindings: HashMap::new(), lookup: HashMap::new() };
+            for entry in &overrides {
+                let _ = keybindings.set(entry.action, entry.keys.clone());
+            }
+            for action in KeyAction::ALL {
+                if keybindings.bindings.contains_key(&action) {
+                    continue;
+                }
+                let combo = action.default_combo();
+                if let Some(&holder) = keybindings.lookup.get(&combo) {
+                    overrides.retain(|entry| entry.action != holder);
+                    continue 'retry;
+                }
+                keybindings.bindings.insert(action, combo.clone());
+                keybindings.lookup.insert(combo, action);
+            }
+            return keybindings;
+        }
+    }
+
+    pub fn action_for(&self, combo: &KeyCombo) -> Option<KeyAction> {
+        self.lookup.get(combo).copied()
+    }
+
+    pub fn combo(&self, action: KeyAction) -> &KeyCombo {
+        &self.bindings[&action]
+    }
+
+    pub fn is_default(&self, action: KeyAction) -> bool {
+        *self.combo(action) == action.default_combo()
+    }
+
+    /// Rebind `action`, refusing combos another action already uses
+    pub fn set(&mut self, action: KeyAction, combo: KeyCombo) -> Result<(), String> {
+        match self.lookup.get(&combo) {
+            Some(&other) if other != action => {
+                return Err(format!("{} is already used by \"{}\"", combo, other.description()));
+            }
+            _ => {}
+        }
+
+        if let Some(old) = self.bindings.insert(action, combo.clone()) {
+            self.lookup.remove(&old);
+        }
+        self.lookup.insert(combo, action);
+        Ok(())
+    }
+
+    pub fn reset(&mut self, action: KeyAction) -> Result<(), String> {
+        self.set(action, action.default_combo())
+    }
+
+    /// Bindings that differ from the defaults, in `KeyAction::ALL` order
+    pub fn overrides(&self) -> Vec<KeyBindingOverride> {
+        KeyAction::ALL.iter()
+            .filter(|&&action| !self.is_default(action))
+            .map(|&action| KeyBindingOverride { action, keys: self.combo(action).clone() })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        let keybindings = Keybindings::default();
+        for action in KeyAction::ALL {
+            assert_eq!(keybindings.action_for(&action.default_combo()), Some(action));
+        }
+        assert!(keybindings.overrides().is_empty());
+    }
+
+    #[test]
+    fn test_event_normalized() {
+        let shifted = KeyCombo::from_event(&Key::Character("Z".into()), Modifiers::COMMAND | Modifiers::SHIFT);
+        assert_eq!(shifted, Some(KeyAction::Redo.default_combo()));
+        assert_eq!(KeyCombo::from_event(&Key::Named(Named::Delete), Modifiers::empty()), Some(KeyAction::DeleteSelection.default_combo()));
+        assert_eq!(KeyCombo::from_event(&Key::Named(Named::Shift), Modifiers::SHIFT), None);
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for combo in [
+            KeyCombo::new("z", true, true, false),
+            KeyCombo::new("ArrowUp", false, false, true),
+            KeyCombo::new("+", true, false, false),
+        ] {
+            assert_eq!(combo.to_string().parse::<KeyCombo>().unwrap(), combo);
+        }
+        assert_eq!(KeyCombo::new("z", true, true, false).to_string(), "Ctrl+Shift+Z");
+        assert!("Hyper+K".parse::<KeyCombo>().is_err());
+    }
+
+    #[test]
+    fn test_conflicting_binding_rejected() {
+        let mut keybindings = Keybindings::default();
+        let err = keybindings.set(KeyAction::Duplicate, KeyAction::Undo.default_combo()).unwrap_err();
+        assert_eq!(err, "Ctrl+Z is already used by \"Undo\"");
+        assert!(keybindings.is_default(KeyAction::Duplicate));
+    }
+
+    #[test]
+    fn test_rebinding_frees_old_combo() {
+        let mut keybindings = Keybindings::default();
+        let combo = KeyCombo::new("k", true, false, false);
+        keybindings.set(KeyAction::OpenPalette, combo.clone()).unwrap();
+
+        assert_eq!(keybindings.action_for(&combo), Some(KeyAction::OpenPalette));
+        assert_eq!(keybindings.action_for(&KeyAction::OpenPalette.default_combo()), None);
+        assert_eq!(keybindings.overrides(), vec![KeyBindingOverride { action: KeyAction::OpenPalette, keys: combo }]);
+
+        keybindings.reset(KeyAction::OpenPalette).unwrap();
+        assert!(keybindings.overrides().is_empty());
+    }
+
+    #[test]
+    fn test_swapped_shortcuts_survive_a_reload() {
+        let mut keybindings = Keybindings::default();
+        let ctrl_k = KeyCombo::new("k", true, false, false);
+        keybindings.set(KeyAction::OpenPalette, ctrl_k.clone()).unwrap();
+        keybindings.set(KeyAction::Duplicate, KeyAction::OpenPalette.default_combo()).unwrap();
+
+        let reloaded = Keybindings::with_overrides(&keybindings.overrides());
+        assert_eq!(reloaded.combo(KeyAction::OpenPalette), &ctrl_k);
+        assert_eq!(reloaded.action_for(&KeyAction::OpenPalette.default_combo()), Some(KeyAction::Duplicate));
+        assert_eq!(reloaded.overrides(), keybindings.overrides());
+        assert_eq!(reloaded.action_for(&KeyAction::Duplicate.default_combo()), None);
+    }
+
+    #[test]
+    fn test_override_taking_an_unmoved_default_is_dropped() {
+        // A hand-edited file can give Undo's shortcut away without rebinding Undo
+        let overrides = [KeyBindingOverride { action: KeyAction::Duplicate, keys: KeyAction::Undo.default_combo() }];
+        let keybindings = Keybindings::with_overrides(&overrides);
+        assert_eq!(keybindings.action_for(&KeyAction::Undo.default_combo()), Some(KeyAction::Undo));
+        assert!(keybindings.is_default(KeyAction::Duplicate));
+        for action in KeyAction::ALL {
+            assert_eq!(keybindings.action_for(keybindings.combo(action)), Some(action));
+        }
+    }
+
+    #[test]
+    fn test_overrides_round_trip_through_toml() {
+        #[derive(Serialize, Deserialize)]
+        struct File { keybindings: Vec<KeyBindingOverride> }
+
+        let overrides = vec![KeyBindingOverride { action: KeyAction::ZoomIn, keys: KeyCombo::new("+", true, false, false) }];
+        let toml = toml::to_string(&File { keybindings: overrides.clone() }).unwrap();
+        assert!(toml.contains("keys = \"Ctrl++\""), "{}", toml);
+
+        let parsed: File = toml::from_str(&toml).unwrap();
+        assert_eq!(Keybindings::with_overrides(&parsed.keybindings).overrides(), overrides);
+    }
+}
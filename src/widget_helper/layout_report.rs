@@ -0,0 +1,263 @@
+use iced::Theme;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::widget_helper::*;
+use crate::widget_helper::code_generator::{CodeGenerator, message_variant_names};
+use crate::widget_helper::type_system::TypeSystem;
+
+/// Builds a markdown description of the layout for people who don't read Rust
+pub fn generate_layout_report(
+    hierarchy: &WidgetHierarchy,
+    type_system: &TypeSystem,
+    app_name: &str,
+    window_title: &str,
+    theme: &Theme,
+) -> String {
+    let mut generator = CodeGenerator::new(hierarchy, theme.clone(), Some(type_system));
+    let names = generator.resolved_widget_names();
+    let stats = hierarchy.stats();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# Layout Report: {}", escape_markdown(app_name));
+    out.push('\n');
+
+    // Counts
+    let _ = writeln!(out, "## Summary");
+    out.push('\n');
+    let _ = writeln!(out, "- Total widgets: {}", stats.total);
+    let _ = writeln!(out, "- Interactive widgets: {}", stats.interactive);
+    let _ = writeln!(out, "- Maximum nesting depth: {}", stats.max_depth);
+    for (widget_type, count) in &stats.by_type {
+        let _ = writeln!(out, "  - {}: {}", widget_type, count);
+    }
+    if !stats.by_tag.is_empty() {
+        let _ = writeln!(out, "- Tags:");
+        for (tag, count) in &stats.by_tag {
+            let _ = writeln!(out, "  - {}: {}", escape_markdown(tag), count);
+        }
+    }
+    out.push('\n');
+
+    // App settings
+    let _ = writeln!(out, "## App Settings");
+    out.push('\n');
+    let _ = writeln!(out, "- App name: {}", escape_markdown(app_name));
+    let title = if window_title.trim().is_empty() { "(default)" } else { window_title };
+    let _ = writeln!(out, "- Window title: {}", escape_markdown(title));
+    let _ = writeln!(out, "- Target theme: {}", escape_markdown(&theme.to_string()));
+    out.push('\n');
+
+    // Hierarchy
+    let _ = writeln!(out, "## Hierarchy");
+    out.push('\n');
+    write_widget(&mut out, hierarchy.root(), &names, 0);
+    out.push('\n');
+
+    // Interactive widgets
+    let _ = writeln!(out, "## Interactive Widgets");
+    out.push('\n');
+    let mut rows = Vec::new();
+    collect_interactive_rows(hierarchy.root(), &names, &mut rows);
+    if rows.is_empty() {
+        let _ = writeln!(out, "_No interactive widgets._");
+    } else {
+        let _ = writeln!(out, "| Widget | Type | Messages |");
+        let _ = writeln!(out, "| --- | --- | --- |");
+        for (name, widget_type, messages) in rows {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                escape_markdown(&name),
+                widget_type,
+                escape_markdown(&messages.join(", ")),
+            );
+        }
+    }
+    out.push('\n');
+
+    // User defined types
+    let _ = writeln!(out, "## Enums");
+    out.push('\n');
+    let enums = type_system.all_enums();
+    if enums.is_empty() {
+        let _ = writeln!(out, "_No enums defined._");
+    } else {
+        for enum_def in enums {
+            let _ = writeln!(out, "- **{}**", escape_markdown(&enum_def.name));
+            for variant in &enum_def.variants {
+                let _ = writeln!(out, "  - {}", escape_markdown(&variant.name));
+            }
+        }
+    }
+
+    out
+}
+
+fn write_widget(out: &mut String, widget: &Widget, names: &HashMap<WidgetId, String>, depth: usize) {
+    let name = names.get(&widget.id).cloned().unwrap_or_else(|| widget.name.clone());
+    let details = key_properties(widget);
+
+    let _ = write!(out, "{}- **{}** {}", "  ".repeat(depth), widget.widget_type, escape_markdown(&name));
+    if !details.is_empty() {
+        let _ = write!(out, " ({})", escape_markdown(&details.join(", ")));
+    }
+    out.push('\n');
+
+    for child in &widget.children {
+        write_widget(out, child, names, depth + 1);
+    }
+}
+
+fn collect_interactive_rows(
+    widget: &Widget,
+    names: &HashMap<WidgetId, String>,
+    rows: &mut Vec<(String, WidgetType, Vec<String>)>,
+) {
+    let name = names.get(&widget.id).cloned().unwrap_or_else(|| widget.name.clone());
+    let messages = message_variant_names(widget, &name);
+    if !messages.is_empty() {
+        rows.push((name, widget.widget_type, messages));
+    }
+
+    for child in &widget.children {
+        collect_interactive_rows(child, names, rows);
+    }
+}
+
+/// The handful of properties a reviewer cares about for each widget type
+pub fn key_properties(widget: &Widget) -> Vec<String> {
+    let props = &widget.properties;
+    let mut details = vec![format!(
+        "{} x {}",
+        length_to_string(props.width),
+        length_to_string(props.height)
+    )];
+
+    match widget.widget_type {
+        WidgetType::Row | WidgetType::Column => {
+            if props.spacing != 0.0 {
+                details.push(format!("spacing {}", props.spacing));
+            }
+        }
+        WidgetType::Text => details.push(format!("\"{}\"", props.text_content)),
+        WidgetType::Button => {
+            details.push(format!("style {}", props.button_style));
+        }
+        WidgetType::TextInput => {
+            details.push(format!("placeholder \"{}\"", props.text_input_placeholder));
+            if props.is_secure {
+                details.push("secure".to_string());
+            }
+        }
+        WidgetType::Checkbox => details.push(format!("label \"{}\"", props.checkbox_label)),
+        WidgetType::Toggler => details.push(format!("label \"{}\"", props.toggler_label)),
+        WidgetType::Radio => {
+            details.push(format!("label \"{}\"", props.radio_label));
+            details.push(format!("options [{}]", props.radio_options.join(", ")));
+        }
+        WidgetType::PickList => {
+            details.push(format!("options [{}]", props.picklist_options.join(", ")));
+        }
+        WidgetType::ComboBox => {
+            details.push(format!("options [{}]", props.combobox_options.join(", ")));
+        }
+        WidgetType::Slider | WidgetType::VerticalSlider => {
+            details.push(format!("range {}..={}", props.slider_min, props.slider_max));
+        }
+        WidgetType::ProgressBar => {
+            details.push(format!("range {}..={}", props.progress_min, props.progress_max));
+        }
+        WidgetType::Image => details.push(format!("path \"{}\"", props.image_path)),
+        WidgetType::Svg => details.push(format!("path \"{}\"", props.svg_path)),
+        WidgetType::Tooltip => details.push(format!("tooltip \"{}\"", props.tooltip_text)),
+        WidgetType::QRCode => details.push(format!("data \"{}\"", props.qrcode_data)),
+        _ => {}
+    }
+
+    details
+}
+
+/// Escapes characters that would otherwise be read as markdown syntax
+pub fn escape_markdown(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '|' | '*' | '_' | '`' | '#' | '[' | ']' | '<' | '>' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(hierarchy: &WidgetHierarchy, app_name: &str) -> String {
+        generate_layout_report(hierarchy, &TypeSystem::new(), app_name, "", &Theme::Light)
+    }
+
+    #[test]
+    fn test_escapes_markdown_syntax() {
+        assert_eq!(escape_markdown("a|b *c* _d_ `e`"), "a\\|b \\*c\\* \\_d\\_ \\`e\\`");
+        assert_eq!(escape_markdown("first\nsecond"), "first second");
+        assert_eq!(escape_markdown("plain text"), "plain text");
+    }
+
+    #[test]
+    fn test_names_and_text_are_escaped_in_the_report() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let text_id = hierarchy.add_child(WidgetId(0), WidgetType::Text).unwrap();
+        hierarchy.apply_property_change(text_id, PropertyChange::TextContent("*bold* `code`\nnext|".to_string()), &type_system);
+
+        let report = report(&hierarchy, "my_app");
+        assert!(report.starts_with("# Layout Report: my\\_app\n"), "{}", report);
+        assert!(report.contains("\"\\*bold\\* \\`code\\` next\\|\""), "{}", report);
+        assert!(!report.contains("*bold*"), "{}", report);
+    }
+
+    #[test]
+    fn test_nested_hierarchy_report() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        hierarchy.apply_property_change(column_id, PropertyChange::Spacing(10.0), &type_system);
+        let text_id = hierarchy.add_child(column_id, WidgetType::Text).unwrap();
+        hierarchy.apply_property_change(text_id, PropertyChange::TextContent("Hello".to_string()), &type_system);
+        let button_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        hierarchy.apply_property_change(button_id, PropertyChange::WidgetName("save".to_string()), &type_system);
+
+        let report = report(&hierarchy, "Demo");
+        assert!(report.contains("- Total widgets: 4\n"), "{}", report);
+        assert!(report.contains("\n- **Container** "), "{}", report);
+        assert!(report.contains("\n  - **Column** column (Shrink x Shrink, spacing 10)\n"), "{}", report);
+        assert!(report.contains("\n    - **Text** text (Shrink x Shrink, \"Hello\")\n"), "{}", report);
+        assert!(report.contains("\n    - **Button** save (Shrink x Shrink, style "), "{}", report);
+        assert!(report.contains("| save | Button | SavePressed |"), "{}", report);
+        assert!(report.contains("_No enums defined._"), "{}", report);
+    }
+
+    #[test]
+    fn test_key_properties_leave_out_defaults() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let input_id = hierarchy.add_child(column_id, WidgetType::TextInput).unwrap();
+
+        assert_eq!(key_properties(hierarchy.get_widget_by_id(column_id).unwrap()), ["Shrink x Shrink"]);
+        let input = key_properties(hierarchy.get_widget_by_id(input_id).unwrap());
+        assert!(!input.contains(&"secure".to_string()), "{:?}", input);
+
+        hierarchy.apply_property_change(column_id, PropertyChange::Spacing(8.0), &type_system);
+        hierarchy.apply_property_change(input_id, PropertyChange::IsSecure(true), &type_system);
+        assert_eq!(key_properties(hierarchy.get_widget_by_id(column_id).unwrap()), ["Shrink x Shrink", "spacing 8"]);
+        let input = key_properties(hierarchy.get_widget_by_id(input_id).unwrap());
+        assert!(input.contains(&"secure".to_string()), "{:?}", input);
+    }
+}
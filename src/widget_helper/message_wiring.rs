@@ -0,0 +1,143 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Where in the generated code a Message variant name was emitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WiringPass {
+    /// A variant of the `Message` enum
+    Declared,
+    /// The pattern of an update arm
+    Matched,
+    /// A view callback or Task that produces the message
+    Sent,
+}
+
+/// The widget message variants each generation pass emitted. Every declared variant should
+/// be matched once in update and sent from somewhere; anything else means the passes disagree.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageWiring {
+    declared: BTreeSet<String>,
+    matched: BTreeSet<String>,
+    sent: BTreeSet<String>,
+    /// Variants declared, or matched, more than once
+    duplicated: BTreeSet<String>,
+}
+
+/// A variant the passes disagree about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WiringProblem {
+    /// Declared or matched twice, so the code won't compile
+    Duplicated(String),
+    /// Matched or sent, but not in the Message enum
+    Undeclared(String),
+    /// Declared without an update arm
+    NeverMatched(String),
+    /// Has an update arm, but nothing in the view sends it
+    NeverSent(String),
+}
+
+impl MessageWiring {
+    pub fn record(&mut self, pass: WiringPass, variant: &str) {
+        let set = match pass {
+            WiringPass::Declared => &mut self.declared,
+            WiringPass::Matched => &mut self.matched,
+            // Several callbacks may send the same message
+            WiringPass::Sent => {
+                self.sent.insert(variant.to_string());
+                return;
+            }
+        };
+        if !set.insert(variant.to_string()) {
+            self.duplicated.insert(variant.to_string());
+        }
+    }
+
+    /// Forgets a recorded variant, for simulating a pass that skipped it
+    #[cfg(test)]
+    pub fn forget(&mut self, pass: WiringPass, variant: &str) {
+        match pass {
+            WiringPass::Declared => self.declared.remove(variant),
+            WiringPass::Matched => self.matched.remove(variant),
+            WiringPass::Sent => self.sent.remove(variant),
+        };
+    }
+
+    /// Disagreements between the passes, in variant order per kind
+    pub fn problems(&self) -> Vec<WiringProblem> {
+        let mut problems: Vec<WiringProblem> = self.duplicated.iter()
+            .cloned()
+            .map(WiringProblem::Duplicated)
+            .collect();
+        problems.extend(
+            self.matched.union(&self.sent)
+                .filter(|variant| !self.declared.contains(*variant))
+                .cloned()
+                .map(WiringProblem::Undeclared),
+        );
+        problems.extend(
+            self.declared.difference(&self.matched)
+                .cloned()
+                .map(WiringProblem::NeverMatched),
+        );
+        problems.extend(
+            self.declared.intersection(&self.matched)
+                .filter(|variant| !self.sent.contains(*variant))
+                .cloned()
+                .map(WiringProblem::NeverSent),
+        );
+        problems
+    }
+}
+
+impl fmt::Display for WiringProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicated(variant) => write!(f, "Message::{} is generated more than once", variant),
+            Self::Undeclared(variant) => write!(f, "Message::{} is used but missing from the Message enum", variant),
+            Self::NeverMatched(variant) => write!(f, "Message::{} has no update arm", variant),
+            Self::NeverSent(variant) => write!(f, "Message::{} is handled in update but never sent by the view", variant),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wired(variants: &[&str]) -> MessageWiring {
+        let mut wiring = MessageWiring::default();
+        for variant in variants {
+            wiring.record(WiringPass::Declared, variant);
+            wiring.record(WiringPass::Matched, variant);
+            wiring.record(WiringPass::Sent, variant);
+        }
+        wiring
+    }
+
+    #[test]
+    fn test_consistent_passes_have_no_problems() {
+        let mut wiring = wired(&["SavePressed", "NameChanged"]);
+        wiring.record(WiringPass::Sent, "SavePressed");
+        assert!(wiring.problems().is_empty());
+    }
+
+    #[test]
+    fn test_each_missing_pass_is_reported() {
+        let mut wiring = wired(&["SavePressed", "NameChanged", "ListSelected"]);
+        wiring.forget(WiringPass::Sent, "SavePressed");
+        wiring.forget(WiringPass::Matched, "NameChanged");
+        wiring.forget(WiringPass::Declared, "ListSelected");
+        assert_eq!(wiring.problems(), [
+            WiringProblem::Undeclared("ListSelected".to_string()),
+            WiringProblem::NeverMatched("NameChanged".to_string()),
+            WiringProblem::NeverSent("SavePressed".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_duplicate_declaration_is_reported() {
+        let mut wiring = wired(&["SavePressed"]);
+        wiring.record(WiringPass::Declared, "SavePressed");
+        assert_eq!(wiring.problems(), [WiringProblem::Duplicated("SavePressed".to_string())]);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::widget_helper::*;
+use crate::widget_helper::hints::{estimate_text_width, DEFAULT_LABEL_SIZE};
+use crate::widget_helper::sections::estimate_height;
+
+/// Narrowest a control without a label can get and still be usable, like a slider or text input
+const CONTROL_WIDTH: f32 = 100.0;
+/// Images, SVGs and other media without a fixed width, as `estimate_height` takes them
+const MEDIA_WIDTH: f32 = 100.0;
+
+/// Smallest window the layout fits in without clipping, rounded up to whole pixels. Fixed
+/// sizes count as they are and Fill or Shrink widgets as their content, with padding and
+/// spacing along the chain of rows and columns that needs the most room on each axis.
+pub fn estimate_min_size(root: &Widget) -> (f32, f32) {
+    (estimate_width(root).ceil(), estimate_height(root).ceil())
+}
+
+/// Rough width a widget can't shrink below, the horizontal counterpart of `estimate_height`
+pub fn estimate_width(widget: &Widget) -> f32 {
+    let props = &widget.properties;
+    if let Length::Fixed(width) = props.width {
+        return width;
+    }
+    let padding = props.padding.left + props.padding.right;
+
+    match widget.widget_type {
+        WidgetType::Row => {
+            let children: f32 = widget.children.iter().map(estimate_width).sum();
+            let gaps = widget.children.len().saturating_sub(1) as f32 * props.spacing;
+            padding + children + gaps
+        }
+        WidgetType::Column | WidgetType::Container | WidgetType::Scrollable | WidgetType::Stack
+        | WidgetType::Tooltip | WidgetType::MouseArea | WidgetType::Themer | WidgetType::Pin => {
+            let widest = widget.children.iter().map(estimate_width).fold(0.0, f32::max);
+            padding + widest
+        }
+        WidgetType::Text => {
+            let monospace = props.font == FontType::Monospace;
+            if props.wrap == text::Wrapping::None {
+                estimate_text_width(&props.text_content, props.text_size, monospace)
+            } else {
+                // Wrapping text can go down to its longest word per line
+                props.text_content
+                    .split_whitespace()
+                    .map(|word| estimate_text_width(word, props.text_size, monospace))
+                    .fold(0.0, f32::max)
+            }
+        }
+        WidgetType::Button => estimate_text_width(&props.text_content, DEFAULT_LABEL_SIZE, false) + padding,
+        WidgetType::Checkbox => {
+            estimate_text_width(&props.checkbox_label, DEFAULT_LABEL_SIZE, false) + props.checkbox_size + props.checkbox_spacing
+        }
+        WidgetType::Toggler => {
+            // The switch is about twice as wide as it is tall
+            estimate_text_width(&props.toggler_label, DEFAULT_LABEL_SIZE, false) + props.toggler_size * 2.0 + props.toggler_spacing
+        }
+        WidgetType::VerticalSlider => props.slider_width,
+        WidgetType::ProgressBar if props.progress_vertical => props.progress_girth,
+        WidgetType::Rule | WidgetType::Space => 0.0,
+        WidgetType::Image | WidgetType::Svg | WidgetType::QRCode => MEDIA_WIDTH,
+        _ => CONTROL_WIDTH,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::type_system::TypeSystem;
+
+    fn set(hierarchy: &mut WidgetHierarchy, id: WidgetId, change: PropertyChange) {
+        hierarchy.apply_property_change(id, change, &TypeSystem::new());
+    }
+
+    fn fixed(hierarchy: &mut WidgetHierarchy, parent: WidgetId, width: f32, height: f32) -> WidgetId {
+        let id = hierarchy.add_child(parent, WidgetType::Space).unwrap();
+        set(hierarchy, id, PropertyChange::Width(Length::Fixed(width)));
+        set(hierarchy, id, PropertyChange::Height(Length::Fixed(height)));
+        id
+    }
+
+    fn assert_close(actual: (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (actual.0 - expected.0).abs() <= 1.0 && (actual.1 - expected.1).abs() <= 1.0,
+            "estimated {:?}, expected {:?}", actual, expected,
+        );
+    }
+
+    #[test]
+    fn test_row_of_columns_sums_across_and_takes_the_tallest_down() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Row);
+        let root = WidgetId(0);
+        set(&mut hierarchy, root, PropertyChange::Spacing(20.0));
+        set(&mut hierarchy, root, PropertyChange::PaddingUniform(10.0));
+
+        // A sidebar column: 200 wide, two 40 tall entries 5 apart
+        let sidebar = hierarchy.add_child(root, WidgetType::Column).unwrap();
+        set(&mut hierarchy, sidebar, PropertyChange::Spacing(5.0));
+        fixed(&mut hierarchy, sidebar, 200.0, 40.0);
+        fixed(&mut hierarchy, sidebar, 150.0, 40.0);
+
+        // A Fill content column holding a 300x400 block and a Fill slider
+        let content = hierarchy.add_child(root, WidgetType::Column).unwrap();
+        set(&mut hierarchy, content, PropertyChange::Width(Length::Fill));
+        fixed(&mut hierarchy, content, 300.0, 400.0);
+        hierarchy.add_child(content, WidgetType::Slider).unwrap();
+
+        // Across: 10 + 200 + 20 + 300 + 10. Down: 10 + (400 + 30 of slider) + 10.
+        assert_close(estimate_min_size(hierarchy.root()), (540.0, 450.0));
+    }
+
+    #[test]
+    fn test_nested_chain_takes_the_worst_case_path() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        let root = WidgetId(0);
+        set(&mut hierarchy, root, PropertyChange::Spacing(10.0));
+
+        // A header row of two 100x50 blocks 10 apart, inside a container padded by 20
+        let header = hierarchy.add_child(root, WidgetType::Container).unwrap();
+        set(&mut hierarchy, header, PropertyChange::PaddingUniform(20.0));
+        let header_row = hierarchy.add_child(header, WidgetType::Row).unwrap();
+        set(&mut hierarchy, header_row, PropertyChange::Spacing(10.0));
+        fixed(&mut hierarchy, header_row, 100.0, 50.0);
+        fixed(&mut hierarchy, header_row, 100.0, 50.0);
+
+        // A wide but short body row of a 400x30 block and a stretching space
+        let body = hierarchy.add_child(root, WidgetType::Row).unwrap();
+        fixed(&mut hierarchy, body, 400.0, 30.0);
+        let space = hierarchy.add_child(body, WidgetType::Space).unwrap();
+        set(&mut hierarchy, space, PropertyChange::Width(Length::Fill));
+
+        // Across, the body is widest: 400 against 20 + 210 + 20.
+        // Down: 20 + 50 + 20 of header, 10 gap, 30 of body.
+        assert_close(estimate_min_size(hierarchy.root()), (400.0, 130.0));
+    }
+
+    #[test]
+    fn test_fixed_size_wins_over_content() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let root = WidgetId(0);
+        set(&mut hierarchy, root, PropertyChange::Width(Length::Fixed(320.0)));
+        set(&mut hierarchy, root, PropertyChange::Height(Length::Fixed(240.0)));
+        fixed(&mut hierarchy, root, 1000.0, 1000.0);
+        assert_eq!(estimate_min_size(hierarchy.root()), (320.0, 240.0));
+    }
+}
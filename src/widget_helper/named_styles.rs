@@ -0,0 +1,213 @@
+use iced::{Color, Vector};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::widget_helper::{Properties, PropertyChange, Widget, WidgetId, WidgetType};
+use crate::widget_helper::code_generator::style_fn_name;
+use crate::widget_helper::project_file::hex_color;
+
+/// The part of a Container's properties a named style holds: its background, border and shadow
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContainerLook {
+    #[serde(with = "hex_color")]
+    pub background: Color,
+    #[serde(with = "hex_color")]
+    pub border_color: Color,
+    pub border_width: f32,
+    pub border_radius: f32,
+    pub has_shadow: bool,
+    #[serde(with = "hex_color")]
+    pub shadow_color: Color,
+    pub shadow_offset: (f32, f32),
+    pub shadow_blur: f32,
+}
+
+impl ContainerLook {
+    pub fn of(props: &Properties) -> Self {
+        Self {
+            background: props.background_color,
+            border_color: props.border_color,
+            border_width: props.border_width,
+            border_radius: props.border_radius,
+            has_shadow: props.has_shadow,
+            shadow_color: props.shadow_color,
+            shadow_offset: (props.shadow_offset.x, props.shadow_offset.y),
+            shadow_blur: props.shadow_blur,
+        }
+    }
+
+    /// Writes the look into a widget's own fields, which the preview draws from
+    pub fn apply(&self, props: &mut Properties) {
+        props.background_color = self.background;
+        props.border_color = self.border_color;
+        props.border_width = self.border_width;
+        props.border_radius = self.border_radius;
+        props.has_shadow = self.has_shadow;
+        props.shadow_color = self.shadow_color;
+        props.shadow_offset = Vector::new(self.shadow_offset.0, self.shadow_offset.1);
+        props.shadow_blur = self.shadow_blur;
+    }
+
+    /// Whether `change` edits a field the look covers, so a styled widget's edit reaches the style
+    pub fn is_changed_by(change: &PropertyChange) -> bool {
+        matches!(
+            change,
+            PropertyChange::BackgroundColor(_) | PropertyChange::BorderColor(_) | PropertyChange::BorderWidth(_) |
+            PropertyChange::BorderRadius(_) | PropertyChange::HasShadow(_) | PropertyChange::ShadowColor(_) |
+            PropertyChange::ShadowOffsetX(_) | PropertyChange::ShadowOffsetY(_) | PropertyChange::ShadowBlur(_)
+        )
+    }
+}
+
+/// Widgets a named style can be picked for
+pub fn takes_named_style(widget_type: WidgetType) -> bool {
+    widget_type == WidgetType::Container
+}
+
+/// A look promoted from a widget, shared by every widget that picks it. The name is the
+/// generated style function's, so it is kept a valid and unused identifier.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedStyle {
+    pub id: Uuid,
+    pub name: String,
+    pub look: ContainerLook,
+}
+
+/// The project's named styles. Widgets keep a style's id, so the look can change under them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StyleRegistry {
+    styles: Vec<NamedStyle>,
+}
+
+impl StyleRegistry {
+    /// Styles in the order they were added
+    pub fn styles(&self) -> &[NamedStyle] {
+        &self.styles
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&NamedStyle> {
+        self.styles.iter().find(|style| style.id == id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty()
+    }
+
+    /// Adds a style under the function name `name` makes, numbered if another style has it
+    pub fn promote(&mut self, name: &str, look: ContainerLook) -> Result<&NamedStyle, String> {
+        let base = style_fn_name(name).ok_or_else(|| "A style name needs a letter or digit".to_string())?;
+        let mut name = base.clone();
+        let mut n = 2;
+        while self.styles.iter().any(|style| style.name == name) {
+            name = format!("{}_{}", base, n);
+            n += 1;
+        }
+        self.styles.push(NamedStyle { id: Uuid::new_v4(), name, look });
+        Ok(self.styles.last().expect("style just pushed"))
+    }
+
+    pub fn set_look(&mut self, id: Uuid, look: ContainerLook) {
+        if let Some(style) = self.styles.iter_mut().find(|style| style.id == id) {
+            style.look = look;
+        }
+    }
+
+    /// What a styled widget's picker offers: keeping its own look, then each style
+    pub fn choices(&self) -> Vec<StyleChoice> {
+        std::iter::once(StyleChoice { id: None, name: "Inline".to_string() })
+            .chain(self.styles.iter().map(|style| StyleChoice { id: Some(style.id), name: style.name.clone() }))
+            .collect()
+    }
+
+    pub fn remove(&mut self, id: Uuid) -> Option<NamedStyle> {
+        let index = self.styles.iter().position(|style| style.id == id)?;
+        Some(self.styles.remove(index))
+    }
+
+    /// Takes each style's look from its first user in the tree, after an undo put the users
+    /// back to how they looked then
+    pub fn follow(&mut self, root: &Widget) {
+        for style in &mut self.styles {
+            if let Some(user) = users(root, style.id).first() {
+                style.look = ContainerLook::of(&user.properties);
+            }
+        }
+    }
+}
+
+/// An entry of the style picker, None for the widget's own look
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleChoice {
+    pub id: Option<Uuid>,
+    pub name: String,
+}
+
+impl std::fmt::Display for StyleChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Widgets using the style `id`, in tree order
+pub fn users(root: &Widget, id: Uuid) -> Vec<&Widget> {
+    fn walk<'a>(widget: &'a Widget, id: Uuid, users: &mut Vec<&'a Widget>) {
+        if widget.properties.named_style == Some(id) {
+            users.push(widget);
+        }
+        for child in &widget.children {
+            walk(child, id, users);
+        }
+    }
+    let mut found = Vec::new();
+    walk(root, id, &mut found);
+    found
+}
+
+#[derive(Debug, Clone)]
+pub enum StyleMessage {
+    /// Opens the name field for promoting the widget's look
+    StartPromote(WidgetId),
+    NameChanged(String),
+    Promote,
+    CancelPromote,
+    Delete(Uuid),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn look(background: Color) -> ContainerLook {
+        ContainerLook::of(&Properties { background_color: background, ..Properties::default() })
+    }
+
+    #[test]
+    fn test_names_are_sanitized_and_numbered() {
+        let mut registry = StyleRegistry::default();
+        let blue = look(Color::from_rgb(0.0, 0.0, 1.0));
+        assert_eq!(registry.promote("Card Surface", blue).unwrap().name, "card_surface");
+        assert_eq!(registry.promote("card surface", blue).unwrap().name, "card_surface_2");
+        // Names of the generated app's own functions get a suffix
+        assert_eq!(registry.promote("view", blue).unwrap().name, "view_style");
+        assert_eq!(registry.promote("container", blue).unwrap().name, "container_style");
+        assert_eq!(registry.promote("2 up", blue).unwrap().name, "style_2_up");
+        assert!(registry.promote(" -- ", blue).is_err());
+    }
+
+    #[test]
+    fn test_registry_round_trips_through_json() {
+        let mut registry = StyleRegistry::default();
+        let mut primary = look(Color::from_rgb8(0x33, 0x66, 0x99));
+        primary.has_shadow = true;
+        primary.shadow_offset = (0.0, 2.0);
+        registry.promote("primary_cta", primary).unwrap();
+
+        let json = serde_json::to_string(&registry).unwrap();
+        assert!(json.contains("\"#336699\""), "{}", json);
+        // Colors go through 8-bit hex, so compare what the loaded registry writes back
+        let back: StyleRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        assert_eq!(back.styles()[0].look.background, primary.background);
+    }
+}
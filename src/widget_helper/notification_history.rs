@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Notifications kept for the history panel, oldest dropped first
+pub const NOTIFICATION_HISTORY: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Error,
+}
+
+impl Severity {
+    /// Notifications are plain text, so failures are told apart by how their messages open
+    pub fn of(message: &str) -> Self {
+        const FAILURES: &[&str] = &["Couldn't", "Can't", "Failed", "Invalid", "Error"];
+        if FAILURES.iter().any(|start| message.starts_with(start)) {
+            Self::Error
+        } else {
+            Self::Info
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Info => "INFO",
+            Self::Error => "ERROR",
+        })
+    }
+}
+
+/// A notification as it was shown, with when
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationEntry {
+    /// Seconds since midnight UTC
+    pub time_of_day: u64,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl NotificationEntry {
+    pub fn new(message: String, at: SystemTime) -> Self {
+        let seconds = at.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        Self {
+            time_of_day: seconds % 86_400,
+            severity: Severity::of(&message),
+            message,
+        }
+    }
+
+    /// `14:03:22`, in UTC
+    pub fn timestamp(&self) -> String {
+        let (hours, minutes, seconds) = (self.time_of_day / 3600, self.time_of_day / 60 % 60, self.time_of_day % 60);
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    }
+
+    /// What the entry's copy button puts on the clipboard: `14:03:22 ERROR Couldn't move widget`
+    pub fn copy_text(&self) -> String {
+        format!("{} {} {}", self.timestamp(), self.severity, self.message)
+    }
+}
+
+/// Notifications this session, newest first
+#[derive(Debug, Default)]
+pub struct NotificationHistory {
+    entries: VecDeque<NotificationEntry>,
+    pub open: bool,
+}
+
+impl NotificationHistory {
+    pub fn push(&mut self, entry: NotificationEntry) {
+        self.entries.push_front(entry);
+        self.entries.truncate(NOTIFICATION_HISTORY);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &NotificationEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&NotificationEntry> {
+        self.entries.get(index)
+    }
+
+    /// Every entry's copy text, one per line, newest first like the panel lists them
+    pub fn copy_all(&self) -> String {
+        self.entries.iter().map(NotificationEntry::copy_text).collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum NotificationMessage {
+    ToggleHistory,
+    /// Copies the entry at this index of the history, newest first
+    Copy(usize),
+    CopyAll,
+    Clear,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_copy_text_has_timestamp_severity_and_message() {
+        // Two days in, at 14:03:22
+        let entry = NotificationEntry::new("Couldn't move widget: not a container".to_string(), at(2 * 86_400 + 50_602));
+        assert_eq!(entry.copy_text(), "14:03:22 ERROR Couldn't move widget: not a container");
+
+        let entry = NotificationEntry::new("Saved to /tmp/app.rs".to_string(), at(5));
+        assert_eq!(entry.copy_text(), "00:00:05 INFO Saved to /tmp/app.rs");
+    }
+
+    #[test]
+    fn test_history_keeps_the_newest_fifty() {
+        let mut history = NotificationHistory::default();
+        for i in 0..NOTIFICATION_HISTORY + 5 {
+            history.push(NotificationEntry::new(format!("note {}", i), at(i as u64)));
+        }
+        assert_eq!(history.len(), NOTIFICATION_HISTORY);
+        assert_eq!(history.get(0).unwrap().message, "note 54");
+        assert_eq!(history.entries().last().unwrap().message, "note 5");
+        assert!(history.copy_all().starts_with("00:00:54 INFO note 54\n00:00:53 INFO note 53\n"));
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::widget_helper::*;
+
+/// Committed values remembered per field for recalling with the arrow keys
+pub const HISTORY_LEN: usize = 5;
+/// Deepest nesting of parentheses and signs an expression may use
+const MAX_DEPTH: usize = 32;
+
+/// Evaluates `+ - * /` arithmetic with parentheses, as in `16*2` or `(100 - 8) / 3`.
+/// Anything outside that grammar, and results that aren't finite, are errors.
+pub fn evaluate(expression: &str) -> Result<f32, String> {
+    let mut parser = Parser { chars: expression.chars().collect(), at: 0, depth: 0 };
+    if parser.peek().is_none() {
+        return Err("Enter a number".to_string());
+    }
+    let value = parser.expression()?;
+    if let Some(c) = parser.peek() {
+        return Err(format!("Unexpected '{}'", c));
+    }
+    if !value.is_finite() {
+        return Err("The result isn't a finite number".to_string());
+    }
+    Ok(value)
+}
+
+/// Recursive descent over `expression := term (('+' | '-') term)*`,
+/// `term := factor (('*' | '/') factor)*`, `factor := ('+' | '-') factor | number | '(' expression ')'`
+struct Parser {
+    chars: Vec<char>,
+    at: usize,
+    depth: usize,
+}
+
+impl Parser {
+    /// Next character that isn't whitespace
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.get(self.at).is_some_and(|c| c.is_whitespace()) {
+            self.at += 1;
+        }
+        self.chars.get(self.at).copied()
+    }
+
+    fn expression(&mut self) -> Result<f32, String> {
+        let mut value = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.at += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f32, String> {
+        let mut value = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.at += 1;
+            let rhs = self.factor()?;
+            if op == '/' && rhs == 0.0 {
+                return Err("Division by zero".to_string());
+            }
+            value = if op == '*' { value * rhs } else { value / rhs };
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<f32, String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("Expression is nested too deeply".to_string());
+        }
+        let value = match self.peek() {
+            Some(sign @ ('+' | '-')) => {
+                self.at += 1;
+                let value = self.factor()?;
+                if sign == '-' { -value } else { value }
+            }
+            Some('(') => {
+                self.at += 1;
+                let value = self.expression()?;
+                if self.peek() != Some(')') {
+                    return Err("Missing ')'".to_string());
+                }
+                self.at += 1;
+                value
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number()?,
+            Some(c) => return Err(format!("Unexpected '{}'", c)),
+            None => return Err("Expression ends early".to_string()),
+        };
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<f32, String> {
+        let start = self.at;
+        // Read without skipping whitespace, so "1 2" isn't taken for 12
+        while self.chars.get(self.at).is_some_and(|c| c.is_ascii_digit() || *c == '.') {
+            self.at += 1;
+        }
+        let literal: String = self.chars[start..self.at].iter().collect();
+        literal.parse::<f32>().map_err(|_| format!("'{}' isn't a number", literal))
+    }
+}
+
+/// A number-valued property edited through a text field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericProperty {
+    SliderMin,
+    SliderMax,
+    SliderStep,
+    SliderHeight,
+    SliderWidth,
+    ProgressGirth,
+    Padding(PaddingSide),
+    FloatOffsetX,
+    FloatOffsetY,
+    GradientAngle,
+}
+
+impl NumericProperty {
+    pub fn current(self, props: &Properties) -> f32 {
+        match self {
+            Self::SliderMin => props.slider_min,
+            Self::SliderMax => props.slider_max,
+            Self::SliderStep => props.slider_step,
+            Self::SliderHeight => props.slider_height,
+            Self::SliderWidth => props.slider_width,
+            Self::ProgressGirth => props.progress_girth,
+            Self::Padding(PaddingSide::Top) => props.padding.top,
+            Self::Padding(PaddingSide::Right) => props.padding.right,
+            Self::Padding(PaddingSide::Bottom) => props.padding.bottom,
+            Self::Padding(PaddingSide::Left) => props.padding.left,
+            Self::FloatOffsetX => props.float_offset_x,
+            Self::FloatOffsetY => props.float_offset_y,
+            Self::GradientAngle => props.gradient_angle,
+        }
+    }
+
+    /// The change that sets the property to `value`, kept in the range the property allows
+    pub fn change(self, props: &Properties, value: f32) -> PropertyChange {
+        match self {
+            Self::SliderMin => PropertyChange::SliderMin(value),
+            Self::SliderMax => PropertyChange::SliderMax(value),
+            Self::SliderStep => PropertyChange::SliderStep(value.max(0.000_001)),
+            Self::SliderHeight => PropertyChange::SliderHeight(value),
+            Self::SliderWidth => PropertyChange::SliderWidth(value),
+            Self::ProgressGirth => PropertyChange::ProgressGirth(value),
+            Self::Padding(side) => padding_side_change(props.padding_mode, side, value.max(0.0)),
+            Self::FloatOffsetX => PropertyChange::FloatOffsetX(value),
+            Self::FloatOffsetY => PropertyChange::FloatOffsetY(value),
+            Self::GradientAngle => PropertyChange::GradientAngle(value),
+        }
+    }
+}
+
+/// One numeric text field: a property of a widget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumericField {
+    pub widget: WidgetId,
+    pub property: NumericProperty,
+}
+
+/// Text typed into a field and not committed yet
+#[derive(Debug, Clone)]
+struct Draft {
+    field: NumericField,
+    text: String,
+    /// The last commit didn't evaluate
+    invalid: bool,
+    /// Index into the field's history, newest first, while stepping through it
+    recalled: Option<usize>,
+}
+
+/// Drafts and recent values of the numeric property fields. Session-only.
+#[derive(Debug, Clone, Default)]
+pub struct NumericInputs {
+    draft: Option<Draft>,
+    history: HashMap<NumericField, VecDeque<f32>>,
+}
+
+impl NumericInputs {
+    /// What the field shows: the draft while one is being typed, otherwise `current`
+    pub fn text(&self, field: NumericField, current: f32) -> String {
+        match &self.draft {
+            Some(draft) if draft.field == field => draft.text.clone(),
+            _ => format!("{}", current),
+        }
+    }
+
+    pub fn is_invalid(&self, field: NumericField) -> bool {
+        self.draft.as_ref().is_some_and(|draft| draft.field == field && draft.invalid)
+    }
+
+    /// Field being typed into, if any
+    pub fn draft_field(&self) -> Option<NumericField> {
+        self.draft.as_ref().map(|draft| draft.field)
+    }
+
+    /// Replaces the draft. Typing into another field commits the one left behind, which
+    /// stands in for committing on blur; its value is returned when it evaluated.
+    pub fn edit(&mut self, field: NumericField, text: String) -> Option<(NumericField, f32)> {
+        let left = match self.draft.as_ref() {
+            Some(draft) if draft.field != field => {
+                let left = draft.field;
+                self.commit(left).map(|value| (left, value))
+            }
+            _ => None,
+        };
+        self.draft = Some(Draft { field, text, invalid: false, recalled: None });
+        left
+    }
+
+    /// Evaluates the field's draft. A valid one is remembered and cleared so the field shows
+    /// the resulting property; an invalid one stays, marked, and None leaves the property alone.
+    pub fn commit(&mut self, field: NumericField) -> Option<f32> {
+        let draft = self.draft.as_mut().filter(|draft| draft.field == field)?;
+        match evaluate(&draft.text) {
+            Ok(value) => {
+                self.draft = None;
+                let history = self.history.entry(field).or_default();
+                history.retain(|&v| v != value);
+                history.push_back(value);
+                if history.len() > HISTORY_LEN {
+                    history.pop_front();
+                }
+                Some(value)
+            }
+            Err(_) => {
+                draft.invalid = true;
+                None
+            }
+        }
+    }
+
+    /// Whether the arrow keys step through history: the field being edited is empty or already recalling
+    pub fn can_recall(&self) -> bool {
+        self.draft.as_ref().is_some_and(|draft| {
+            (draft.text.is_empty() || draft.recalled.is_some()) && self.history.contains_key(&draft.field)
+        })
+    }
+
+    /// Shows the next older value, or the next newer one, in the field being edited.
+    /// Stepping newer past the latest value empties the field again.
+    pub fn recall(&mut self, older: bool) {
+        if !self.can_recall() {
+            return;
+        }
+        let Some(draft) = self.draft.as_mut() else { return };
+        let Some(history) = self.history.get(&draft.field) else { return };
+
+        let next = match (draft.recalled, older) {
+            (None, true) => Some(0),
+            (None, false) => None,
+            (Some(i), true) => Some((i + 1).min(history.len() - 1)),
+            (Some(0), false) => None,
+            (Some(i), false) => Some(i - 1),
+        };
+        draft.recalled = next;
+        draft.invalid = false;
+        draft.text = next
+            .and_then(|i| history.iter().rev().nth(i))
+            .map(|value| format!("{}", value))
+            .unwrap_or_default();
+    }
+
+    /// Drops drafts and history that belong to a deleted widget
+    pub fn forget(&mut self, id: WidgetId) {
+        if self.draft.as_ref().is_some_and(|draft| draft.field.widget == id) {
+            self.draft = None;
+        }
+        self.history.retain(|field, _| field.widget != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn padding_top() -> NumericField {
+        NumericField { widget: WidgetId(1), property: NumericProperty::Padding(PaddingSide::Top) }
+    }
+
+    fn committed(inputs: &mut NumericInputs, field: NumericField, text: &str) -> Option<f32> {
+        inputs.edit(field, text.to_string());
+        inputs.commit(field)
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic() {
+        assert_eq!(evaluate("16*2"), Ok(32.0));
+        assert_eq!(evaluate("48+8"), Ok(56.0));
+        assert!((evaluate("100/3").unwrap() - 33.333_332).abs() < 1e-4);
+        assert_eq!(evaluate(" (2 + 3) * -4 "), Ok(-20.0));
+        assert_eq!(evaluate("1 - 2 - 3"), Ok(-4.0));
+        assert_eq!(evaluate("12"), Ok(12.0));
+        assert_eq!(evaluate(".5*4"), Ok(2.0));
+    }
+
+    #[test]
+    fn test_evaluate_rejects_outside_grammar() {
+        for input in ["", "16*", "(1+2", "1+2)", "2^3", "1e3", "1..2", "abc", "4/0", "16 * px", "()", "--", "1 2"] {
+            assert!(evaluate(input).is_err(), "{:?} should be rejected", input);
+        }
+        assert!(evaluate(&"(".repeat(1000)).is_err());
+        assert!(evaluate(&format!("{}1", "-".repeat(1000))).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_random_input_never_panics() {
+        const ALPHABET: &[char] = &['0', '1', '9', '.', '+', '-', '*', '/', '(', ')', ' ', 'x', 'é', '^'];
+        // Small LCG, so failures reproduce
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for _ in 0..5000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let len = (seed >> 58) as usize;
+            let input: String = (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    ALPHABET[(seed >> 33) as usize % ALPHABET.len()]
+                })
+                .collect();
+            if let Ok(value) = evaluate(&input) {
+                assert!(value.is_finite(), "{:?} evaluated to {}", input, value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_commit_keeps_draft() {
+        let mut inputs = NumericInputs::default();
+        let field = padding_top();
+        assert_eq!(committed(&mut inputs, field, "16*"), None);
+        assert!(inputs.is_invalid(field));
+        assert_eq!(inputs.text(field, 8.0), "16*");
+
+        assert_eq!(committed(&mut inputs, field, "16*2"), Some(32.0));
+        assert!(!inputs.is_invalid(field));
+        assert_eq!(inputs.text(field, 32.0), "32");
+    }
+
+    #[test]
+    fn test_recall_steps_through_last_values() {
+        let mut inputs = NumericInputs::default();
+        let field = padding_top();
+        for value in ["4", "8", "16", "8"] {
+            committed(&mut inputs, field, value);
+        }
+
+        // Only from an empty field
+        inputs.edit(field, "2".to_string());
+        assert!(!inputs.can_recall());
+        inputs.edit(field, String::new());
+        inputs.recall(true);
+        assert_eq!(inputs.text(field, 0.0), "8");
+        inputs.recall(true);
+        assert_eq!(inputs.text(field, 0.0), "16");
+        inputs.recall(true);
+        inputs.recall(true);
+        assert_eq!(inputs.text(field, 0.0), "4");
+        inputs.recall(false);
+        inputs.recall(false);
+        inputs.recall(false);
+        assert_eq!(inputs.text(field, 0.0), "");
+    }
+
+    #[test]
+    fn test_history_keeps_five() {
+        let mut inputs = NumericInputs::default();
+        let field = padding_top();
+        for value in 1..=7 {
+            committed(&mut inputs, field, &value.to_string());
+        }
+        assert_eq!(inputs.history[&field], [3.0, 4.0, 5.0, 6.0, 7.0]);
+    }
+
+    #[test]
+    fn test_editing_another_field_commits_the_first() {
+        let mut inputs = NumericInputs::default();
+        let other = NumericField { widget: WidgetId(1), property: NumericProperty::Padding(PaddingSide::Left) };
+        inputs.edit(padding_top(), "10+2".to_string());
+        assert_eq!(inputs.edit(other, "3".to_string()), Some((padding_top(), 12.0)));
+        assert_eq!(inputs.text(padding_top(), 12.0), "12");
+    }
+}
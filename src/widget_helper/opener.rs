@@ -0,0 +1,80 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Platforms that hand files to their default handler differently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Windows,
+    MacOs,
+    /// Linux and the BSDs, through xdg-open
+    Unix,
+}
+
+impl Platform {
+    #[cfg(target_os = "windows")]
+    pub fn current() -> Self {
+        Self::Windows
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn current() -> Self {
+        Self::MacOs
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    pub fn current() -> Self {
+        Self::Unix
+    }
+}
+
+/// Program and arguments that open `path` with the platform's default handler.
+/// A directory opens in the file manager.
+pub fn open_command(platform: Platform, path: &Path) -> (&'static str, Vec<OsString>) {
+    match platform {
+        // `start` takes the first quoted argument as the window title, so give it an empty one
+        Platform::Windows => ("cmd", vec!["/C".into(), "start".into(), "".into(), path.into()]),
+        Platform::MacOs => ("open", vec![path.into()]),
+        Platform::Unix => ("xdg-open", vec![path.into()]),
+    }
+}
+
+/// Opens an exported file in its default editor, or an exported folder in the file manager
+pub async fn open_path(path: PathBuf) -> Result<(), String> {
+    let (program, args) = open_command(Platform::current(), &path);
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|e| format!("Couldn't run {}: {}", program, e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Couldn't open {}: {} exited with {}", path.display(), program, status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[OsString]) -> Vec<&str> {
+        args.iter().map(|arg| arg.to_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_windows_uses_start_with_empty_title() {
+        let (program, args) = open_command(Platform::Windows, Path::new(r"C:\My App\src\main.rs"));
+        assert_eq!(program, "cmd");
+        assert_eq!(strings(&args), ["/C", "start", "", r"C:\My App\src\main.rs"]);
+    }
+
+    #[test]
+    fn test_macos_and_unix_pass_the_path_alone() {
+        let path = Path::new("/home/me/my app");
+        let (program, args) = open_command(Platform::MacOs, path);
+        assert_eq!((program, strings(&args)), ("open", vec!["/home/me/my app"]));
+        let (program, args) = open_command(Platform::Unix, path);
+        assert_eq!((program, strings(&args)), ("xdg-open", vec!["/home/me/my app"]));
+    }
+}
@@ -0,0 +1,238 @@
+use std::path::PathBuf;
+use iced::widget::text_editor;
+use crate::widget_helper::{Properties, PropertyChange, WidgetId};
+
+/// Past this many options a pick list or radio group gets slow to draw and hard to scan
+pub const LARGE_OPTION_LIST: usize = 1000;
+
+/// Which option list of a widget the import writes to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionTarget {
+    Radio,
+    PickList,
+    ComboBox,
+}
+
+impl OptionTarget {
+    pub fn current(self, props: &Properties) -> &[String] {
+        match self {
+            Self::Radio => &props.radio_options,
+            Self::PickList => &props.picklist_options,
+            Self::ComboBox => &props.combobox_options,
+        }
+    }
+
+    /// The same change the inline editor sends, so the combo box state is rebuilt and the
+    /// selection checked against the new list
+    pub fn change(self, options: Vec<String>) -> PropertyChange {
+        match self {
+            Self::Radio => PropertyChange::RadioOptions(options),
+            Self::PickList => PropertyChange::PickListOptions(options),
+            Self::ComboBox => PropertyChange::ComboBoxState(options),
+        }
+    }
+
+    /// Whether a list this long is better off in a combo box, which filters as you type
+    pub fn is_too_long(self, len: usize) -> bool {
+        self != Self::ComboBox && len >= LARGE_OPTION_LIST
+    }
+}
+
+/// Inline form under an option list that takes pasted text or a file's contents
+pub struct OptionImportForm {
+    pub widget_id: WidgetId,
+    pub target: OptionTarget,
+    pub text: text_editor::Content,
+    /// Add after the current options instead of replacing them
+    pub append: bool,
+    /// File the text was loaded from, if any
+    pub source: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+impl OptionImportForm {
+    pub fn new(widget_id: WidgetId, target: OptionTarget) -> Self {
+        Self {
+            widget_id,
+            target,
+            text: text_editor::Content::new(),
+            append: false,
+            source: None,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum OptionImportMessage {
+    Open(WidgetId, OptionTarget),
+    TextEdited(text_editor::Action),
+    AppendToggled(bool),
+    ChooseFile,
+    FileLoaded(Result<(PathBuf, String), String>),
+    Apply,
+    Cancel,
+    Export(WidgetId, OptionTarget),
+}
+
+/// Options an import produces, with what was dropped on the way
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedOptions {
+    pub options: Vec<String>,
+    /// Values that were already in the list, or earlier in the import
+    pub duplicates: usize,
+    /// Blank values, like the gap left by a trailing comma
+    pub empties: usize,
+}
+
+impl ImportedOptions {
+    /// The new list from `text`, after `current` when appending
+    pub fn parse(text: &str, current: &[String], append: bool) -> Self {
+        let mut options: Vec<String> = if append { current.to_vec() } else { Vec::new() };
+        let mut duplicates = 0;
+        let mut empties = 0;
+        for value in csv_values(text) {
+            let value = value.trim();
+            if value.is_empty() {
+                empties += 1;
+            } else if options.iter().any(|option| option == value) {
+                duplicates += 1;
+            } else {
+                options.push(value.to_string());
+            }
+        }
+        Self { options, duplicates, empties }
+    }
+
+    /// "Imported 12 options, dropped 2 duplicates and 1 empty value"
+    pub fn report(&self, added: usize) -> String {
+        let mut dropped = Vec::new();
+        if self.duplicates > 0 {
+            dropped.push(plural(self.duplicates, "duplicate", "duplicates"));
+        }
+        if self.empties > 0 {
+            dropped.push(plural(self.empties, "empty value", "empty values"));
+        }
+        let imported = format!("Imported {}", plural(added, "option", "options"));
+        match dropped.as_slice() {
+            [] => imported,
+            parts => format!("{}, dropped {}", imported, parts.join(" and ")),
+        }
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+/// Every field of comma or newline separated text, in order. A field in double quotes may hold
+/// commas, line breaks and doubled `""` quotes; the quotes themselves are dropped.
+pub fn csv_values(text: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    // Whether the current field has begun, so blank lines add no field
+    let mut started = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+                started = true;
+            }
+            ',' => {
+                values.push(std::mem::take(&mut field));
+                // A comma always has a field after it, even an empty one
+                started = true;
+            }
+            '\n' | '\r' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                if started {
+                    values.push(std::mem::take(&mut field));
+                }
+                started = false;
+            }
+            _ => {
+                field.push(c);
+                started = true;
+            }
+        }
+    }
+    if started || quoted {
+        values.push(field);
+    }
+    values
+}
+
+/// One option per line, the counterpart of an import
+pub fn export_text(options: &[String]) -> String {
+    let mut out = options.join("\n");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_commas_and_newlines_both_separate() {
+        assert_eq!(csv_values("Red, Green\nBlue\r\nCyan,"), ["Red", " Green", "Blue", "Cyan", ""]);
+        assert!(csv_values("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_quoted_fields_keep_commas_quotes_and_line_breaks() {
+        assert_eq!(
+            csv_values("\"Korea, Republic of\",\"Say \"\"hi\"\"\"\n\"two\nlines\""),
+            ["Korea, Republic of", "Say \"hi\"", "two\nlines"],
+        );
+        // A quote inside an unquoted field is just a character
+        assert_eq!(csv_values("5\" screen"), ["5\" screen"]);
+    }
+
+    #[test]
+    fn test_import_trims_and_counts_what_it_drops() {
+        let imported = ImportedOptions::parse(" Sales ,Support\n\nSales, ,Legal", &[], false);
+        assert_eq!(imported.options, strings(&["Sales", "Support", "Legal"]));
+        assert_eq!((imported.duplicates, imported.empties), (1, 1));
+        assert_eq!(imported.report(3), "Imported 3 options, dropped 1 duplicate and 1 empty value");
+    }
+
+    #[test]
+    fn test_append_skips_options_already_in_the_list() {
+        let current = strings(&["Sales", "Legal"]);
+        let imported = ImportedOptions::parse("Legal\nHR", &current, true);
+        assert_eq!(imported.options, strings(&["Sales", "Legal", "HR"]));
+        assert_eq!(imported.duplicates, 1);
+        assert_eq!(imported.report(1), "Imported 1 option, dropped 1 duplicate");
+
+        let replaced = ImportedOptions::parse("Legal\nHR", &current, false);
+        assert_eq!(replaced.options, strings(&["Legal", "HR"]));
+    }
+
+    #[test]
+    fn test_export_round_trips() {
+        let options = strings(&["A", "B"]);
+        assert_eq!(ImportedOptions::parse(&export_text(&options), &[], false).options, options);
+    }
+}
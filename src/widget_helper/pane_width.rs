@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use iced::widget::{column, row};
+use iced::{Element, Length};
+
+/// Width a panel has to reach before it lays its groups out in two columns
+pub const TWO_COLUMNS_AT: f32 = 560.0;
+/// Width it has to drop below to go back to one. The gap keeps a panel resized across the
+/// threshold, or one growing a scrollbar as a field fills up, from flipping back and forth.
+pub const ONE_COLUMN_BELOW: f32 = 520.0;
+
+/// Whether a panel that was (or wasn't) in two columns stays or goes there at `width`
+pub fn is_wide(was_wide: bool, width: f32) -> bool {
+    if was_wide { width >= ONE_COLUMN_BELOW } else { width >= TWO_COLUMNS_AT }
+}
+
+/// Column count per panel, for panels measured with `responsive`.
+///
+/// The width is only known while the view is built, so the last answer per panel is
+/// kept behind a RefCell for the hysteresis to compare against.
+#[derive(Debug)]
+pub struct PaneWidths<K> {
+    wide: RefCell<HashSet<K>>,
+}
+
+impl<K> Default for PaneWidths<K> {
+    fn default() -> Self {
+        Self { wide: RefCell::new(HashSet::new()) }
+    }
+}
+
+impl<K: Eq + Hash + Copy> PaneWidths<K> {
+    /// Whether `pane` gets two columns at `width`, remembered for its next layout
+    pub fn is_wide(&self, pane: K, width: f32) -> bool {
+        let mut wide = self.wide.borrow_mut();
+        if is_wide(wide.contains(&pane), width) {
+            wide.insert(pane);
+            true
+        } else {
+            wide.remove(&pane);
+            false
+        }
+    }
+}
+
+/// `left` and `right` groups side by side when wide, otherwise one column with `left` first
+pub fn split_columns<'a, Message: 'a>(
+    wide: bool,
+    left: Vec<Element<'a, Message>>,
+    right: Vec<Element<'a, Message>>,
+    spacing: f32,
+) -> Element<'a, Message> {
+    if wide {
+        row![
+            column(left).spacing(spacing).width(Length::FillPortion(1)),
+            column(right).spacing(spacing).width(Length::FillPortion(1)),
+        ]
+        .spacing(spacing * 2.0)
+        .into()
+    } else {
+        column(left.into_iter().chain(right)).spacing(spacing).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_has_hysteresis() {
+        assert!(!is_wide(false, 540.0));
+        assert!(is_wide(false, TWO_COLUMNS_AT));
+        // Inside the band a panel keeps what it had
+        assert!(is_wide(true, 540.0));
+        assert!(!is_wide(true, ONE_COLUMN_BELOW - 1.0));
+    }
+
+    #[test]
+    fn test_panes_remember_their_own_layout() {
+        let widths = PaneWidths::default();
+        assert!(widths.is_wide(1, 600.0));
+        assert!(!widths.is_wide(2, 540.0));
+        assert!(widths.is_wide(1, 540.0));
+        assert!(!widths.is_wide(1, 500.0));
+        assert!(!widths.is_wide(1, 540.0));
+    }
+}
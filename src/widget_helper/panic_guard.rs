@@ -0,0 +1,213 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+
+use iced::widget::{button, column, container, row, text};
+use iced::{Element, Length};
+
+use crate::widget_helper::Message;
+
+/// Parts of the builder whose construction is run behind `catch_unwind`, so a bug in one
+/// of them doesn't take the whole window and the unsaved layout down with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GuardedPanel {
+    Properties,
+    Preview,
+    CodeGeneration,
+}
+
+impl GuardedPanel {
+    /// What the recovery view's button does besides retrying
+    pub fn reset_label(self) -> &'static str {
+        match self {
+            Self::Properties => "Clear selection and retry",
+            Self::Preview => "Show the code instead",
+            Self::CodeGeneration => "Retry",
+        }
+    }
+}
+
+impl std::fmt::Display for GuardedPanel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Properties => "properties panel",
+            Self::Preview => "preview",
+            Self::CodeGeneration => "code generation",
+        })
+    }
+}
+
+/// A panic caught at a panel's boundary
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaughtPanic {
+    pub panel: GuardedPanel,
+    /// The panic's message, or a note that the payload wasn't text
+    pub message: String,
+    /// What the panel was showing, like the selected widget
+    pub context: String,
+}
+
+thread_local! {
+    static CATCH_IN_TESTS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Release and debug runs of the app catch. Under `cargo test` panics stay loud, so a test
+/// can't pass on top of a hidden one, unless it opts in with `catching_in_tests`.
+fn catches_panics() -> bool {
+    !cfg!(test) || CATCH_IN_TESTS.with(Cell::get)
+}
+
+/// Runs `f` with catching turned on, for the tests that check the guard itself
+#[cfg(test)]
+pub fn catching_in_tests<T>(f: impl FnOnce() -> T) -> T {
+    CATCH_IN_TESTS.with(|catch| catch.set(true));
+    let result = f();
+    CATCH_IN_TESTS.with(|catch| catch.set(false));
+    result
+}
+
+/// Runs `build`, turning a panic into a `CaughtPanic` that is logged with its context.
+///
+/// Callers pass borrowed builder state, hence `AssertUnwindSafe`. View code only reads that
+/// state, and the background generation owns a copy of it, so an unwind can't leave any of
+/// it half-changed.
+pub fn catch<T>(panel: GuardedPanel, context: impl FnOnce() -> String, build: impl FnOnce() -> T) -> Result<T, CaughtPanic> {
+    if !catches_panics() {
+        return Ok(build());
+    }
+    panic::catch_unwind(AssertUnwindSafe(build)).map_err(|payload| {
+        let caught = CaughtPanic {
+            panel,
+            message: payload_message(payload.as_ref()),
+            context: context(),
+        };
+        eprintln!("Caught a panic in the {} ({}): {}", caught.panel, caught.context, caught.message);
+        caught
+    })
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "the panic payload was not a string".to_string()
+    }
+}
+
+/// Panels that panicked, each kept on its recovery view until reset. Checked from `view`,
+/// which only has `&self`, hence the `RefCell`.
+#[derive(Debug, Default)]
+pub struct PanicGuards {
+    tripped: RefCell<HashMap<GuardedPanel, CaughtPanic>>,
+}
+
+impl PanicGuards {
+    /// Builds the panel, or its recovery view if it panics now or did before. A tripped panel
+    /// isn't built again, so the same panic isn't hit and logged every frame.
+    pub fn view<'a>(
+        &self,
+        panel: GuardedPanel,
+        context: impl FnOnce() -> String,
+        build: impl FnOnce() -> Element<'a, Message>,
+    ) -> Element<'a, Message> {
+        if let Some(caught) = self.tripped(panel) {
+            return recovery_view(&caught);
+        }
+        match catch(panel, context, build) {
+            Ok(element) => element,
+            Err(caught) => {
+                let view = recovery_view(&caught);
+                self.trip(caught);
+                view
+            }
+        }
+    }
+
+    pub fn trip(&self, caught: CaughtPanic) {
+        self.tripped.borrow_mut().insert(caught.panel, caught);
+    }
+
+    pub fn tripped(&self, panel: GuardedPanel) -> Option<CaughtPanic> {
+        self.tripped.borrow().get(&panel).cloned()
+    }
+
+    pub fn reset(&self, panel: GuardedPanel) {
+        self.tripped.borrow_mut().remove(&panel);
+    }
+}
+
+/// Stands in for a panel that panicked. The rest of the window keeps working around it.
+pub fn recovery_view<'a>(caught: &CaughtPanic) -> Element<'a, Message> {
+    container(
+        column![
+            text(format!("The {} hit an error", caught.panel)).size(16).style(text::danger),
+            text(caught.message.clone()).size(12),
+            text(format!("While showing {}", caught.context)).size(12).style(text::secondary),
+            text("Your layout is untouched. The error was also written to the terminal.").size(12),
+            row![
+                button(text(caught.panel.reset_label()).size(12))
+                    .style(button::primary)
+                    .on_press(Message::RecoverPanel(caught.panel)),
+            ],
+        ]
+        .spacing(8)
+    )
+    .padding(20)
+    .width(Length::Fill)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_panel<'a>(fail: bool) -> Element<'a, Message> {
+        if fail {
+            panic!("fixture panel failed");
+        }
+        text("fixture").into()
+    }
+
+    #[test]
+    fn test_panicking_panel_shows_recovery_and_the_others_keep_working() {
+        catching_in_tests(|| {
+            let guards = PanicGuards::default();
+            let _ = guards.view(GuardedPanel::Properties, || "widget 3".to_string(), || fixture_panel(true));
+
+            let caught = guards.tripped(GuardedPanel::Properties).unwrap();
+            assert_eq!(caught.message, "fixture panel failed");
+            assert_eq!(caught.context, "widget 3");
+
+            // Other panels still build
+            let _ = guards.view(GuardedPanel::Preview, String::new, || fixture_panel(false));
+            assert!(guards.tripped(GuardedPanel::Preview).is_none());
+
+            // The tripped panel isn't built again until it's reset
+            let mut built = false;
+            let _ = guards.view(GuardedPanel::Properties, String::new, || { built = true; fixture_panel(false) });
+            assert!(!built);
+            guards.reset(GuardedPanel::Properties);
+            let _ = guards.view(GuardedPanel::Properties, String::new, || { built = true; fixture_panel(false) });
+            assert!(built);
+            assert!(guards.tripped(GuardedPanel::Properties).is_none());
+        });
+    }
+
+    #[test]
+    fn test_formatted_payloads_are_kept() {
+        let caught = catching_in_tests(|| {
+            catch::<()>(GuardedPanel::CodeGeneration, String::new, || panic!("index {} out of range", 7))
+        });
+        assert_eq!(caught.unwrap_err().message, "index 7 out of range");
+    }
+
+    #[test]
+    #[should_panic(expected = "fixture panel failed")]
+    fn test_tests_still_fail_loudly() {
+        let guards = PanicGuards::default();
+        let _ = guards.view(GuardedPanel::Preview, String::new, || fixture_panel(true));
+    }
+}
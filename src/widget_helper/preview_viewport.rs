@@ -0,0 +1,273 @@
+use iced::keyboard::Modifiers;
+use iced::mouse::ScrollDelta;
+use iced::widget::scrollable::AbsoluteOffset;
+use iced::{Point, Size, Vector};
+
+/// Id of the scrollable the preview canvas sits in
+pub const PREVIEW_VIEWPORT: &str = "preview_viewport";
+
+pub const MIN_ZOOM: f32 = 0.25;
+pub const MAX_ZOOM: f32 = 4.0;
+/// Factor one button press, shortcut or wheel notch zooms by
+const ZOOM_STEP: f32 = 1.25;
+
+/// Size the preview lays out into at 100%, matching iced's default window size
+pub const NOMINAL_CANVAS: Size = Size::new(1024.0, 768.0);
+
+/// Zoom and pan of the preview pane.
+///
+/// iced can't scale a widget tree, so zoom scales the canvas the preview lays out
+/// into instead and the pane scrolls over it. Layout responds like a window of that
+/// size would, but text and fixed sizes keep their pixel size at every zoom.
+#[derive(Debug, Clone)]
+pub struct PreviewViewport {
+    zoom: f32,
+    /// Scroll position of the pane over the canvas
+    offset: Vector,
+    /// Last known size of the pane, taken from pointer events over it
+    pane: Size,
+    cursor: Option<Point>,
+    panning: bool,
+    command_held: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum ViewportMessage {
+    ZoomIn,
+    ZoomOut,
+    /// Back to 100% at the top-left corner
+    Reset,
+    /// Zoom so the whole canvas fits a pane of this size
+    Fit(Size),
+    Wheel(ScrollDelta),
+    ModifiersChanged(Modifiers),
+    PointerMoved(Point, Size),
+    PointerLeft,
+    PanStarted,
+    PanEnded,
+    Scrolled(AbsoluteOffset),
+    /// Scroll so this height of the 100% canvas sits at the top of the pane
+    JumpTo(f32),
+}
+
+impl Default for PreviewViewport {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            offset: Vector::ZERO,
+            pane: NOMINAL_CANVAS,
+            cursor: None,
+            panning: false,
+            command_held: false,
+        }
+    }
+}
+
+impl PreviewViewport {
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Size the preview content lays out into at the current zoom
+    pub fn canvas_size(&self) -> Size {
+        Size::new(NOMINAL_CANVAS.width * self.zoom, NOMINAL_CANVAS.height * self.zoom)
+    }
+
+    pub fn is_panning(&self) -> bool {
+        self.panning
+    }
+
+    /// Maps a point in the pane to the 100% canvas, undoing the current pan and zoom
+    pub fn to_canvas(&self, point: Point) -> Point {
+        Point::new((point.x + self.offset.x) / self.zoom, (point.y + self.offset.y) / self.zoom)
+    }
+
+    /// Applies the message. Returns the offset the pane has to scroll to, if it moved.
+    pub fn update(&mut self, message: ViewportMessage) -> Option<AbsoluteOffset> {
+        match message {
+            ViewportMessage::ZoomIn => self.zoom_around(self.zoom * ZOOM_STEP, self.pane_center()),
+            ViewportMessage::ZoomOut => self.zoom_around(self.zoom / ZOOM_STEP, self.pane_center()),
+            ViewportMessage::Reset => {
+                self.zoom = 1.0;
+                self.offset = Vector::ZERO;
+                Some(self.absolute_offset())
+            }
+            ViewportMessage::Fit(pane) => {
+                self.pane = pane;
+                let scale = (self.pane.width / NOMINAL_CANVAS.width).min(self.pane.height / NOMINAL_CANVAS.height);
+                self.zoom = scale.clamp(MIN_ZOOM, MAX_ZOOM);
+                self.offset = Vector::ZERO;
+                Some(self.absolute_offset())
+            }
+            ViewportMessage::Wheel(delta) => {
+                let (Some(cursor), true) = (self.cursor, self.command_held) else { return None };
+                let notches = match delta {
+                    ScrollDelta::Lines { y, .. } => y,
+                    ScrollDelta::Pixels { y, .. } => y / 50.0,
+                };
+                if notches == 0.0 {
+                    return None;
+                }
+                self.zoom_around(self.zoom * ZOOM_STEP.powf(notches), cursor)
+            }
+            ViewportMessage::ModifiersChanged(modifiers) => {
+                self.command_held = modifiers.command();
+                None
+            }
+            ViewportMessage::PointerMoved(point, pane) => {
+                self.pane = pane;
+                let previous = self.cursor.replace(point);
+                match previous {
+                    Some(previous) if self.panning => {
+                        self.offset = self.offset + (previous - point);
+                        self.clamp_offset();
+                        Some(self.absolute_offset())
+                    }
+                    _ => None,
+                }
+            }
+            ViewportMessage::PointerLeft => {
+                self.cursor = None;
+                self.panning = false;
+                None
+            }
+            ViewportMessage::PanStarted => {
+                self.panning = true;
+                None
+            }
+            ViewportMessage::PanEnded => {
+                self.panning = false;
+                None
+            }
+            ViewportMessage::Scrolled(offset) => {
+                self.offset = Vector::new(offset.x, offset.y);
+                None
+            }
+            ViewportMessage::JumpTo(y) => {
+                self.offset.y = y * self.zoom;
+                self.clamp_offset();
+                Some(self.absolute_offset())
+            }
+        }
+    }
+
+    /// Zooms keeping the canvas point under `anchor` in place
+    fn zoom_around(&mut self, zoom: f32, anchor: Point) -> Option<AbsoluteOffset> {
+        let zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        if zoom == self.zoom {
+            return None;
+        }
+        let fixed = self.to_canvas(anchor);
+        self.zoom = zoom;
+        self.offset = Vector::new(fixed.x * zoom - anchor.x, fixed.y * zoom - anchor.y);
+        self.clamp_offset();
+        Some(self.absolute_offset())
+    }
+
+    fn clamp_offset(&mut self) {
+        let canvas = self.canvas_size();
+        self.offset.x = self.offset.x.clamp(0.0, (canvas.width - self.pane.width).max(0.0));
+        self.offset.y = self.offset.y.clamp(0.0, (canvas.height - self.pane.height).max(0.0));
+    }
+
+    fn pane_center(&self) -> Point {
+        Point::new(self.pane.width / 2.0, self.pane.height / 2.0)
+    }
+
+    fn absolute_offset(&self) -> AbsoluteOffset {
+        AbsoluteOffset { x: self.offset.x, y: self.offset.y }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A viewport whose pane is half the nominal canvas
+    fn half_pane() -> PreviewViewport {
+        let mut viewport = PreviewViewport::default();
+        viewport.update(ViewportMessage::PointerMoved(Point::ORIGIN, Size::new(512.0, 384.0)));
+        viewport
+    }
+
+    #[test]
+    fn test_zoom_is_clamped() {
+        let mut viewport = half_pane();
+        for _ in 0..20 {
+            viewport.update(ViewportMessage::ZoomIn);
+        }
+        assert_eq!(viewport.zoom(), MAX_ZOOM);
+        for _ in 0..40 {
+            viewport.update(ViewportMessage::ZoomOut);
+        }
+        assert_eq!(viewport.zoom(), MIN_ZOOM);
+    }
+
+    #[test]
+    fn test_fit_and_reset() {
+        let mut viewport = half_pane();
+        viewport.update(ViewportMessage::ZoomIn);
+        viewport.update(ViewportMessage::Fit(Size::new(512.0, 384.0)));
+        assert_eq!(viewport.zoom(), 0.5);
+        assert_eq!(viewport.canvas_size(), Size::new(512.0, 384.0));
+
+        viewport.update(ViewportMessage::ZoomIn);
+        viewport.update(ViewportMessage::Reset);
+        assert_eq!(viewport.zoom(), 1.0);
+        assert_eq!(viewport.to_canvas(Point::new(10.0, 20.0)), Point::new(10.0, 20.0));
+    }
+
+    #[test]
+    fn test_zoom_keeps_point_under_cursor() {
+        let mut viewport = half_pane();
+        let cursor = Point::new(100.0, 50.0);
+        viewport.update(ViewportMessage::PointerMoved(cursor, Size::new(512.0, 384.0)));
+        viewport.update(ViewportMessage::ModifiersChanged(Modifiers::COMMAND));
+        let before = viewport.to_canvas(cursor);
+
+        viewport.update(ViewportMessage::Wheel(ScrollDelta::Lines { x: 0.0, y: 1.0 }));
+        assert_eq!(viewport.zoom(), ZOOM_STEP);
+        let after = viewport.to_canvas(cursor);
+        assert!((after.x - before.x).abs() < 0.01 && (after.y - before.y).abs() < 0.01, "{:?} moved to {:?}", before, after);
+    }
+
+    #[test]
+    fn test_jump_scales_with_zoom_and_clamps() {
+        let mut viewport = half_pane();
+        viewport.update(ViewportMessage::ZoomIn);
+        let offset = viewport.update(ViewportMessage::JumpTo(100.0)).unwrap();
+        assert_eq!(offset.y, 100.0 * ZOOM_STEP);
+
+        // Past the end of the canvas only scrolls as far as it goes
+        let offset = viewport.update(ViewportMessage::JumpTo(10_000.0)).unwrap();
+        assert_eq!(offset.y, viewport.canvas_size().height - 384.0);
+    }
+
+    #[test]
+    fn test_wheel_needs_command() {
+        let mut viewport = half_pane();
+        assert!(viewport.update(ViewportMessage::Wheel(ScrollDelta::Lines { x: 0.0, y: 1.0 })).is_none());
+        assert_eq!(viewport.zoom(), 1.0);
+    }
+
+    #[test]
+    fn test_pan_maps_pointer_through_offset() {
+        let mut viewport = half_pane();
+        viewport.update(ViewportMessage::ZoomIn);
+        viewport.update(ViewportMessage::Reset);
+        viewport.update(ViewportMessage::ZoomIn);
+        let pane = Size::new(512.0, 384.0);
+        viewport.update(ViewportMessage::PointerMoved(Point::new(200.0, 200.0), pane));
+        let start = viewport.to_canvas(Point::new(200.0, 200.0));
+
+        viewport.update(ViewportMessage::PanStarted);
+        let offset = viewport.update(ViewportMessage::PointerMoved(Point::new(180.0, 190.0), pane));
+        assert!(offset.is_some());
+        // Dragging left and up moves the canvas with the pointer, so the grabbed point stays under it
+        let held = viewport.to_canvas(Point::new(180.0, 190.0));
+        assert!((held.x - start.x).abs() < 0.01 && (held.y - start.y).abs() < 0.01, "{:?} moved to {:?}", start, held);
+
+        viewport.update(ViewportMessage::PanEnded);
+        assert!(viewport.update(ViewportMessage::PointerMoved(Point::new(0.0, 0.0), pane)).is_none());
+    }
+}
@@ -0,0 +1,250 @@
+use iced::Theme;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::widget_helper::*;
+use crate::widget_helper::code_generator::CodeGenerator;
+use crate::widget_helper::layout_report::key_properties;
+use crate::widget_helper::type_system::TypeSystem;
+
+pub const DEFAULT_LINE_WIDTH: usize = 100;
+/// Narrower than this, the code's line numbers and markers leave too little room for the code
+pub const MIN_LINE_WIDTH: usize = 40;
+pub const MAX_LINE_WIDTH: usize = 400;
+/// Starts each piece of a wrapped line after the first
+pub const CONTINUATION: &str = "↪ ";
+/// Spaces between aligned columns
+const COLUMN_GAP: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrintFormat {
+    #[default]
+    PlainText,
+    /// The plain text inside a `<pre>`, for browsers that print to PDF
+    Html,
+}
+
+impl PrintFormat {
+    pub const ALL: [PrintFormat; 2] = [PrintFormat::PlainText, PrintFormat::Html];
+
+    /// File extensions offered when saving, the first one for the suggested name
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            PrintFormat::PlainText => &["txt"],
+            PrintFormat::Html => &["html", "htm"],
+        }
+    }
+
+    pub fn filter_name(self) -> &'static str {
+        match self {
+            PrintFormat::PlainText => "Text",
+            PrintFormat::Html => "HTML",
+        }
+    }
+}
+
+impl std::fmt::Display for PrintFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PrintFormat::PlainText => "Plain text",
+            PrintFormat::Html => "HTML",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintSettings {
+    pub line_width: usize,
+    pub format: PrintFormat,
+}
+
+impl Default for PrintSettings {
+    fn default() -> Self {
+        Self { line_width: DEFAULT_LINE_WIDTH, format: PrintFormat::PlainText }
+    }
+}
+
+/// A review copy of the layout: the tree with each widget's key properties in aligned
+/// columns, then the generated code with line numbers, no line longer than the set width
+pub fn generate_printable(
+    hierarchy: &WidgetHierarchy,
+    type_system: &TypeSystem,
+    app_name: &str,
+    theme: &Theme,
+    code: &str,
+    settings: PrintSettings,
+) -> String {
+    let width = settings.line_width.clamp(MIN_LINE_WIDTH, MAX_LINE_WIDTH);
+    let names = CodeGenerator::new(hierarchy, theme.clone(), Some(type_system)).resolved_widget_names();
+
+    let mut lines = Vec::new();
+    lines.push(app_name.to_string());
+    lines.push("=".repeat(app_name.chars().count().clamp(1, width)));
+    lines.push(String::new());
+
+    lines.push("HIERARCHY".to_string());
+    lines.push(String::new());
+    let mut rows = vec![vec!["Widget".to_string(), "Type".to_string(), "Size".to_string(), "Details".to_string()]];
+    outline_rows(hierarchy.root(), &names, 0, &mut rows);
+    for line in align_columns(&rows) {
+        lines.extend(wrap(&line, width));
+    }
+    lines.push(String::new());
+
+    lines.push("CODE".to_string());
+    lines.push(String::new());
+    lines.extend(number_lines(code, width));
+
+    let mut out = String::new();
+    for line in lines {
+        let _ = writeln!(out, "{}", line);
+    }
+    match settings.format {
+        PrintFormat::PlainText => out,
+        PrintFormat::Html => wrap_html(&out, app_name),
+    }
+}
+
+/// One table row per widget, its name indented by depth, in tree order
+fn outline_rows(widget: &Widget, names: &HashMap<WidgetId, String>, depth: usize, rows: &mut Vec<Vec<String>>) {
+    let name = names.get(&widget.id).cloned().unwrap_or_else(|| widget.name.clone());
+    let mut details = key_properties(widget).into_iter();
+    let size = details.next().unwrap_or_default();
+    rows.push(vec![
+        format!("{}{}", "  ".repeat(depth), name),
+        widget.widget_type.to_string(),
+        size,
+        details.collect::<Vec<_>>().join(", ").replace('\n', " "),
+    ]);
+    for child in &widget.children {
+        outline_rows(child, names, depth + 1, rows);
+    }
+}
+
+/// Rows with every column but the last padded to its widest cell. Widths are counted in chars,
+/// so wide glyphs like CJK or emoji push the rest of their row over a little.
+pub fn align_columns(rows: &[Vec<String>]) -> Vec<String> {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().filter_map(|row| row.get(i)).map(|cell| cell.chars().count()).max().unwrap_or(0))
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let mut line = String::new();
+            for (i, cell) in row.iter().enumerate() {
+                line.push_str(cell);
+                if i + 1 < row.len() {
+                    let pad = widths[i] - cell.chars().count() + COLUMN_GAP;
+                    line.push_str(&" ".repeat(pad));
+                }
+            }
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// `line` cut into pieces of at most `width` chars, each after the first led by the
+/// continuation marker. Breaks after a space when one falls in the back half of a piece.
+pub fn wrap(line: &str, width: usize) -> Vec<String> {
+    let marker = CONTINUATION.chars().count();
+    let width = width.max(marker + 1);
+    let mut chars: &[char] = &line.chars().collect::<Vec<_>>();
+    let mut pieces = Vec::new();
+    let mut room = width;
+    while chars.len() > room {
+        let cut = chars[room / 2..room]
+            .iter()
+            .rposition(|&c| c == ' ')
+            .map_or(room, |space| room / 2 + space + 1);
+        pieces.push(chars[..cut].iter().collect::<String>().trim_end().to_string());
+        chars = &chars[cut..];
+        room = width - marker;
+    }
+    pieces.push(chars.iter().collect());
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(i, piece)| if i == 0 { piece } else { format!("{}{}", CONTINUATION, piece) })
+        .collect()
+}
+
+/// Code lines behind a right-aligned line number gutter. Wrapped pieces get a blank gutter,
+/// so every number still starts a line of the source.
+pub fn number_lines(code: &str, width: usize) -> Vec<String> {
+    let digits = code.lines().count().max(1).to_string().len();
+    let gutter = digits + 3;
+    let room = width.saturating_sub(gutter).max(CONTINUATION.chars().count() + 1);
+    let mut out = Vec::new();
+    for (i, line) in code.lines().enumerate() {
+        for (piece_index, piece) in wrap(line, room).into_iter().enumerate() {
+            let number = if piece_index == 0 { (i + 1).to_string() } else { String::new() };
+            let numbered = format!("{:>digits$} | {}", number, piece, digits = digits);
+            out.push(numbered.trim_end().to_string());
+        }
+    }
+    out
+}
+
+/// The text in a `<pre>` page, escaped, with just enough styling to print in a monospace font
+pub fn wrap_html(text: &str, title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n\
+         <pre style=\"font-family: monospace; font-size: 9pt; white-space: pre;\">{}</pre>\n</body>\n</html>\n",
+        escape_html(title),
+        escape_html(text),
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_align_by_chars() {
+        let rows = vec![
+            vec!["Widget".to_string(), "Type".to_string(), "Details".to_string()],
+            vec!["  Grüße".to_string(), "Text".to_string(), "\"hi\"".to_string()],
+            vec!["  save_button".to_string(), "Button".to_string(), String::new()],
+        ];
+        assert_eq!(align_columns(&rows), [
+            "Widget         Type    Details",
+            "  Grüße        Text    \"hi\"",
+            "  save_button  Button",
+        ]);
+    }
+
+    #[test]
+    fn test_wrap_marks_continuations_and_prefers_spaces() {
+        assert_eq!(wrap("short", 10), ["short"]);
+        assert_eq!(wrap("aaaa bbbb cccc", 10), ["aaaa bbbb", "↪ cccc"]);
+        // No space in the back half, so the line is cut at the width
+        assert_eq!(wrap("abcdefghijklmnop", 8), ["abcdefgh", "↪ ijklmn", "↪ op"]);
+        // Counted in chars, not bytes
+        assert_eq!(wrap("ééééé", 5), ["ééééé"]);
+        for line in wrap(&"word ".repeat(40), 30) {
+            assert!(line.chars().count() <= 30, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_numbered_code_keeps_the_gutter_on_wrapped_pieces() {
+        let code = format!("fn main() {{\n    let x = \"{}\";\n}}", "y".repeat(30));
+        let lines = number_lines(&code, 30);
+        assert_eq!(lines[0], "1 | fn main() {");
+        assert!(lines[1].starts_with("2 |     let x = "), "{:?}", lines);
+        assert!(lines[2].starts_with("  | ↪ "), "{:?}", lines);
+        assert_eq!(lines.last().unwrap(), "3 | }");
+        assert!(lines.iter().all(|line| line.chars().count() <= 30), "{:?}", lines);
+    }
+
+    #[test]
+    fn test_html_escapes_inside_pre() {
+        let html = wrap_html("Element<'a, Message> & \"more\"", "A<B>");
+        assert!(html.contains("<title>A&lt;B&gt;</title>"));
+        assert!(html.contains(">Element&lt;'a, Message&gt; &amp; &quot;more&quot;</pre>"));
+    }
+}
@@ -0,0 +1,505 @@
+use std::collections::HashMap;
+
+use crate::palette_file::color_to_hex;
+use crate::widget_helper::*;
+
+/// One line of the comparison report, in tree order of the newer hierarchy
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    pub depth: usize,
+    pub label: String,
+    /// The widget in the newer hierarchy, None for removed widgets
+    pub current: Option<WidgetId>,
+    pub kind: DiffKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    /// Present in both. With no move and no property changes, the entry is only
+    /// listed because something below it changed.
+    Matched {
+        moved: Option<Move>,
+        properties: Vec<PropertyDiff>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Move {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyDiff {
+    pub key: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+impl DiffEntry {
+    /// Unchanged ancestor of a change
+    pub fn is_context(&self) -> bool {
+        matches!(&self.kind, DiffKind::Matched { moved: None, properties } if properties.is_empty())
+    }
+}
+
+/// Flattened tree, pre-order, root first
+struct Flat<'a> {
+    widgets: Vec<&'a Widget>,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+}
+
+impl<'a> Flat<'a> {
+    fn new(root: &'a Widget) -> Self {
+        let mut flat = Flat { widgets: Vec::new(), parents: Vec::new(), children: Vec::new() };
+        flat.push(root, None);
+        flat
+    }
+
+    fn push(&mut self, widget: &'a Widget, parent: Option<usize>) {
+        let index = self.widgets.len();
+        self.widgets.push(widget);
+        self.parents.push(parent);
+        self.children.push(Vec::new());
+        if let Some(parent) = parent {
+            self.children[parent].push(index);
+        }
+        for child in &widget.children {
+            self.push(child, Some(index));
+        }
+    }
+
+    fn sibling_index(&self, node: usize) -> usize {
+        self.parents[node]
+            .map(|parent| self.children[parent].iter().position(|&c| c == node).unwrap_or(0))
+            .unwrap_or(0)
+    }
+}
+
+/// Structural diff of `old` against `new`.
+///
+/// Widgets are paired by user-given name and type when that pair is unique in both
+/// trees. The remaining children of each paired parent are then paired in order by
+/// type, so inserting or renaming a widget doesn't disturb its siblings.
+pub fn diff(old: &Widget, new: &Widget) -> Vec<DiffEntry> {
+    let old = Flat::new(old);
+    let new = Flat::new(new);
+    let (old_to_new, new_to_old) = pair(&old, &new);
+
+    walk(&old, &new, &old_to_new, &new_to_old, 0, 0, false)
+}
+
+fn pair(old: &Flat, new: &Flat) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+    let mut old_to_new = vec![None; old.widgets.len()];
+    let mut new_to_old = vec![None; new.widgets.len()];
+    let mut pending = vec![(0, 0)];
+    old_to_new[0] = Some(0);
+    new_to_old[0] = Some(0);
+
+    let by_name = |flat: &Flat| {
+        let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, widget) in flat.widgets.iter().enumerate().skip(1) {
+            if !widget.properties.widget_name.is_empty() {
+                let key = format!("{}:{:?}", widget.properties.widget_name, widget.widget_type);
+                names.entry(key).or_default().push(index);
+            }
+        }
+        names
+    };
+    let new_names = by_name(new);
+    for (key, old_nodes) in by_name(old) {
+        if let ([o], Some([n])) = (old_nodes.as_slice(), new_names.get(&key).map(Vec::as_slice)) {
+            old_to_new[*o] = Some(*n);
+            new_to_old[*n] = Some(*o);
+            pending.push((*o, *n));
+        }
+    }
+
+    while let Some((o, n)) = pending.pop() {
+        let mut cursor = 0;
+        for &old_child in &old.children[o] {
+            if old_to_new[old_child].is_some() {
+                continue;
+            }
+            let candidates = &new.children[n][cursor..];
+            let found = candidates.iter().position(|&new_child| {
+                new_to_old[new_child].is_none()
+                    && new.widgets[new_child].widget_type == old.widgets[old_child].widget_type
+            });
+            if let Some(offset) = found {
+                let new_child = candidates[offset];
+                cursor += offset + 1;
+                old_to_new[old_child] = Some(new_child);
+                new_to_old[new_child] = Some(old_child);
+                pending.push((old_child, new_child));
+            }
+        }
+    }
+
+    (old_to_new, new_to_old)
+}
+
+fn walk(
+    old: &Flat,
+    new: &Flat,
+    old_to_new: &[Option<usize>],
+    new_to_old: &[Option<usize>],
+    node: usize,
+    depth: usize,
+    parent_added: bool,
+) -> Vec<DiffEntry> {
+    let widget = new.widgets[node];
+    let counterpart = new_to_old[node];
+
+    let mut below = Vec::new();
+    for &child in &new.children[node] {
+        below.extend(walk(old, new, old_to_new, new_to_old, child, depth + 1, counterpart.is_none()));
+    }
+    if let Some(o) = counterpart {
+        for &old_child in &old.children[o] {
+            if old_to_new[old_child].is_none() {
+                below.push(DiffEntry {
+                    depth: depth + 1,
+                    label: label(old.widgets[old_child]),
+                    current: None,
+                    kind: DiffKind::Removed,
+                });
+            }
+        }
+    }
+
+    let entry = match counterpart {
+        // Everything below a new widget is new too, only say so once
+        None if parent_added => None,
+        None => Some(DiffKind::Added),
+        Some(o) => {
+            let moved = moved(old, new, old_to_new, new_to_old, o, node);
+            let properties = property_diffs(&old.widgets[o].properties, &widget.properties);
+            if moved.is_none() && properties.is_empty() && below.is_empty() {
+                None
+            } else {
+                Some(DiffKind::Matched { moved, properties })
+            }
+        }
+    };
+
+    let mut entries = Vec::new();
+    if let Some(kind) = entry {
+        entries.push(DiffEntry { depth, label: label(widget), current: Some(widget.id), kind });
+    }
+    entries.extend(below);
+    entries
+}
+
+/// Moved when the parent changed, or the widget changed places among the siblings
+/// present in both trees
+fn moved(old: &Flat, new: &Flat, old_to_new: &[Option<usize>], new_to_old: &[Option<usize>], o: usize, n: usize) -> Option<Move> {
+    let (old_parent, new_parent) = (old.parents[o]?, new.parents[n]?);
+    if old_to_new[old_parent] == Some(new_parent) {
+        // Count only earlier siblings that stayed under the same parent
+        let rank = |flat: &Flat, node: usize, parent: usize, stayed: &dyn Fn(usize) -> bool| {
+            flat.children[parent].iter().take_while(|&&c| c != node).filter(|&&c| stayed(c)).count()
+        };
+        let old_rank = rank(old, o, old_parent, &|c| old_to_new[c].is_some_and(|x| new.parents[x] == Some(new_parent)));
+        let new_rank = rank(new, n, new_parent, &|c| new_to_old[c].is_some_and(|y| old.parents[y] == Some(old_parent)));
+        if old_rank == new_rank {
+            return None;
+        }
+    }
+    Some(Move {
+        from: format!("{} #{}", label(old.widgets[old_parent]), old.sibling_index(o)),
+        to: format!("{} #{}", label(new.widgets[new_parent]), new.sibling_index(n)),
+    })
+}
+
+fn label(widget: &Widget) -> String {
+    match widget.properties.widget_name.as_str() {
+        "" => widget.widget_type.to_string(),
+        name => format!("{} \"{}\"", widget.widget_type, name),
+    }
+}
+
+fn property_diffs(before: &Properties, after: &Properties) -> Vec<PropertyDiff> {
+    property_values(before)
+        .into_iter()
+        .zip(property_values(after))
+        .filter(|((_, b), (_, a))| b != a)
+        .map(|((key, before), (_, after))| PropertyDiff { key, before, after })
+        .collect()
+}
+
+/// Every user-editable property as a key and a comparable value, named like the history timeline
+fn property_values(p: &Properties) -> Vec<(&'static str, String)> {
+    let num = |v: f32| if v.fract() == 0.0 { format!("{:.0}", v) } else { format!("{:.2}", v) };
+    let debug = |v: &dyn std::fmt::Debug| format!("{:?}", v);
+    let quoted = |s: &str| format!("\"{}\"", s);
+    let optional = |v: Option<String>| v.unwrap_or_else(|| "none".to_string());
+    let list = |items: &[String]| format!("[{}]", items.join(", "));
+
+    vec![
+        ("widget_name", quoted(&p.widget_name)),
+        ("width", length_to_string(p.width)),
+        ("height", length_to_string(p.height)),
+        ("max_width", optional(p.max_width.map(num))),
+        ("max_height", optional(p.max_height.map(num))),
+        ("clip", p.clip.to_string()),
+        ("widget_id", optional(p.widget_id.as_deref().map(quoted))),
+        ("padding", format!("{} {} {} {}", num(p.padding.top), num(p.padding.right), num(p.padding.bottom), num(p.padding.left))),
+
+        ("align_x", debug(&p.align_x)),
+        ("align_y", debug(&p.align_y)),
+        ("border_width", num(p.border_width)),
+        ("border_radius", num(p.border_radius)),
+        ("border_color", color_to_hex(p.border_color)),
+        ("background_color", color_to_hex(p.background_color)),
+        ("gradient_enabled", p.gradient_enabled.to_string()),
+        ("gradient_start", color_to_hex(p.gradient_start)),
+        ("gradient_end", color_to_hex(p.gradient_end)),
+        ("gradient_angle", num(p.gradient_angle)),
+        ("float_anchor", optional(p.float_anchor.map(|a| debug(&a)))),
+        ("float_offset_x", num(p.float_offset_x)),
+        ("float_offset_y", num(p.float_offset_y)),
+        ("section_anchor", p.section_anchor.to_string()),
+        ("section_name", quoted(&p.section_name)),
+        ("breakpoint_enabled", p.breakpoint_enabled.to_string()),
+        ("breakpoint_width", num(p.breakpoint_width)),
+        ("stack_below_breakpoint", p.stack_below_breakpoint.to_string()),
+        ("container_sizing_mode", debug(&p.container_sizing_mode)),
+        ("container_center_length", length_to_string(p.container_center_length)),
+
+        ("is_wrapping_row", p.is_wrapping_row.to_string()),
+        ("wrapping_vertical_spacing", optional(p.wrapping_vertical_spacing.map(num))),
+        ("wrapping_align_x", debug(&p.wrapping_align_x)),
+        ("spacing", num(p.spacing)),
+        ("align_items", debug(&p.align_items)),
+
+        ("text_content", quoted(&p.text_content)),
+        ("text_size", num(p.text_size)),
+        ("text_color", color_to_hex(p.text_color)),
+        ("font", debug(&p.font)),
+        ("line_height", debug(&p.line_height)),
+        ("wrap", debug(&p.wrap)),
+        ("shaping", debug(&p.shaping)),
+        ("text_align_x", debug(&p.text_align_x)),
+        ("text_align_y", debug(&p.text_align_y)),
+
+        ("button_style", debug(&p.button_style)),
+        ("button_on_press", format!("{} {} {}", p.button_on_press_enabled, p.button_on_press_with_enabled, p.button_on_press_maybe_enabled)),
+
+        ("text_input_value", quoted(&p.text_input_value)),
+        ("text_input_placeholder", quoted(&p.text_input_placeholder)),
+        ("text_input_size", num(p.text_input_size)),
+        ("text_input_padding", num(p.text_input_padding)),
+        ("is_secure", p.is_secure.to_string()),
+        ("text_input_on_submit", p.text_input_on_submit.to_string()),
+        ("text_input_on_paste", p.text_input_on_paste.to_string()),
+        ("text_input_font", debug(&p.text_input_font)),
+        ("text_input_line_height", debug(&p.text_input_line_height)),
+        ("text_input_alignment", debug(&p.text_input_alignment)),
+
+        ("checkbox_checked", p.checkbox_checked.to_string()),
+        ("checkbox_label", quoted(&p.checkbox_label)),
+        ("checkbox_size", num(p.checkbox_size)),
+        ("checkbox_spacing", num(p.checkbox_spacing)),
+
+        ("radio_selected_index", p.radio_selected_index.to_string()),
+        ("radio_options", list(&p.radio_options)),
+        ("radio_label", quoted(&p.radio_label)),
+        ("radio_size", num(p.radio_size)),
+        ("radio_spacing", num(p.radio_spacing)),
+
+        ("slider_value", num(p.slider_value)),
+        ("slider_min", num(p.slider_min)),
+        ("slider_max", num(p.slider_max)),
+        ("slider_step", num(p.slider_step)),
+        ("slider_width", num(p.slider_width)),
+        ("slider_height", num(p.slider_height)),
+
+        ("progress_value", num(p.progress_value)),
+        ("progress_min", num(p.progress_min)),
+        ("progress_max", num(p.progress_max)),
+        ("progress_length", length_to_string(p.progress_length)),
+        ("progress_girth", num(p.progress_girth)),
+        ("progress_vertical", p.progress_vertical.to_string()),
+
+        ("toggler_active", p.toggler_active.to_string()),
+        ("toggler_label", quoted(&p.toggler_label)),
+        ("toggler_size", num(p.toggler_size)),
+        ("toggler_spacing", num(p.toggler_spacing)),
+        ("label_position", debug(&p.label_position)),
+
+        ("picklist_selected", optional(p.picklist_selected.as_deref().map(quoted))),
+        ("picklist_placeholder", quoted(&p.picklist_placeholder)),
+        ("picklist_options", list(&p.picklist_options)),
+
+        ("scroll_dir", debug(&p.scroll_dir)),
+        ("anchor_x", debug(&p.anchor_x)),
+        ("anchor_y", debug(&p.anchor_y)),
+        ("rule_thickness", num(p.rule_thickness)),
+        ("orientation", debug(&p.orientation)),
+
+        ("image_path", quoted(&p.image_path)),
+        ("image_fit", p.image_fit.to_string()),
+        ("svg_path", quoted(&p.svg_path)),
+        ("svg_fit", p.svg_fit.to_string()),
+        ("svg_tint", p.svg_tint.to_string()),
+        ("svg_tint_color", color_to_hex(p.svg_tint_color)),
+        ("library_asset", optional(p.library_asset.map(|id| id.to_string()))),
+
+        ("tooltip_text", quoted(&p.tooltip_text)),
+        ("tooltip_position", debug(&p.tooltip_position)),
+        ("tooltip_gap", num(p.tooltip_gap)),
+
+        ("combobox_placeholder", quoted(&p.combobox_placeholder)),
+        ("combobox_selected", optional(p.combobox_selected.as_deref().map(quoted))),
+        ("combobox_options", list(&p.combobox_options)),
+        ("combobox_size", num(p.combobox_size)),
+        ("combobox_padding", num(p.combobox_padding)),
+        ("combobox_use_on_input", p.combobox_use_on_input.to_string()),
+        ("combobox_use_on_option_hovered", p.combobox_use_on_option_hovered.to_string()),
+        ("combobox_use_on_open", p.combobox_use_on_open.to_string()),
+        ("combobox_use_on_close", p.combobox_use_on_close.to_string()),
+        ("combobox_input_background", optional(p.combobox_input_background.map(color_to_hex))),
+        ("combobox_input_text_color", optional(p.combobox_input_text_color.map(color_to_hex))),
+        ("combobox_menu_background", optional(p.combobox_menu_background.map(color_to_hex))),
+        ("combobox_menu_height", optional(p.combobox_menu_height.map(num))),
+        ("referenced_enum", optional(p.referenced_enum.map(|id| id.to_string()))),
+
+        ("markdown_source", quoted(&p.markdown_source.text())),
+        ("markdown_text_size", num(p.markdown_text_size)),
+        ("qrcode_data", quoted(&p.qrcode_data)),
+        ("qrcode_cell_size", num(p.qrcode_cell_size)),
+        ("themer_theme", optional(p.themer_theme.as_ref().map(|t| t.to_string()))),
+        ("pin_point", format!("{} {}", num(p.pin_point.x), num(p.pin_point.y))),
+
+        ("mousearea_on_press", p.mousearea_on_press.to_string()),
+        ("mousearea_on_release", p.mousearea_on_release.to_string()),
+        ("mousearea_on_double_click", p.mousearea_on_double_click.to_string()),
+        ("mousearea_on_right_press", p.mousearea_on_right_press.to_string()),
+        ("mousearea_on_right_release", p.mousearea_on_right_release.to_string()),
+        ("mousearea_on_middle_press", p.mousearea_on_middle_press.to_string()),
+        ("mousearea_on_middle_release", p.mousearea_on_middle_release.to_string()),
+        ("mousearea_on_scroll", p.mousearea_on_scroll.to_string()),
+        ("mousearea_on_enter", p.mousearea_on_enter.to_string()),
+        ("mousearea_on_move", p.mousearea_on_move.to_string()),
+        ("mousearea_on_exit", p.mousearea_on_exit.to_string()),
+        ("mousearea_interaction", optional(p.mousearea_interaction.map(|i| debug(&i)))),
+        ("file_dialog", p.file_dialog.to_string()),
+        ("style_condition", optional(p.style_condition.as_ref().map(|c| debug(c)))),
+        ("condition_border_color", color_to_hex(p.condition_border_color)),
+        ("condition_background_color", color_to_hex(p.condition_background_color)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::type_system::TypeSystem;
+
+    /// Container > Column, the shape every hierarchy here starts from
+    fn base() -> (WidgetHierarchy, WidgetId) {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        (hierarchy, column)
+    }
+
+    fn named(hierarchy: &mut WidgetHierarchy, parent: WidgetId, widget_type: WidgetType, name: &str) -> WidgetId {
+        let id = hierarchy.add_child(parent, widget_type).unwrap();
+        hierarchy.apply_property_change(id, PropertyChange::WidgetName(name.to_string()), &TypeSystem::new());
+        id
+    }
+
+    fn changes(entries: &[DiffEntry]) -> Vec<&DiffEntry> {
+        entries.iter().filter(|e| !e.is_context()).collect()
+    }
+
+    #[test]
+    fn test_identical_trees_have_no_entries() {
+        let (mut hierarchy, column) = base();
+        named(&mut hierarchy, column, WidgetType::Button, "submit");
+        assert!(diff(hierarchy.root(), hierarchy.root()).is_empty());
+    }
+
+    #[test]
+    fn test_rename_is_a_property_change() {
+        let (mut old, column) = base();
+        named(&mut old, column, WidgetType::Button, "submit");
+        let (mut new, column) = base();
+        let button = named(&mut new, column, WidgetType::Button, "send");
+
+        let entries = diff(old.root(), new.root());
+        let changes = changes(&entries);
+        assert_eq!(changes.len(), 1, "{:#?}", entries);
+        assert_eq!(changes[0].current, Some(button));
+        assert_eq!(changes[0].kind, DiffKind::Matched {
+            moved: None,
+            properties: vec![PropertyDiff { key: "widget_name", before: "\"submit\"".into(), after: "\"send\"".into() }],
+        });
+    }
+
+    #[test]
+    fn test_named_widget_moved_into_sibling() {
+        let (mut old, column) = base();
+        old.add_child(column, WidgetType::Row).unwrap();
+        named(&mut old, column, WidgetType::Button, "go");
+        let (mut new, column) = base();
+        let row = new.add_child(column, WidgetType::Row).unwrap();
+        let button = named(&mut new, row, WidgetType::Button, "go");
+
+        let entries = diff(old.root(), new.root());
+        let changes = changes(&entries);
+        assert_eq!(changes.len(), 1, "{:#?}", entries);
+        assert_eq!(changes[0].current, Some(button));
+        assert_eq!(changes[0].depth, 3);
+        assert_eq!(changes[0].kind, DiffKind::Matched {
+            moved: Some(Move { from: "Column #1".into(), to: "Row #0".into() }),
+            properties: Vec::new(),
+        });
+    }
+
+    #[test]
+    fn test_nested_property_change_keeps_ancestors_for_context() {
+        let build = |size: f32| {
+            let (mut hierarchy, column) = base();
+            let container = hierarchy.add_child(column, WidgetType::Container).unwrap();
+            let row = hierarchy.add_child(container, WidgetType::Row).unwrap();
+            let text = hierarchy.add_child(row, WidgetType::Text).unwrap();
+            hierarchy.apply_property_change(text, PropertyChange::TextSize(size), &TypeSystem::new());
+            hierarchy
+        };
+        let (old, new) = (build(16.0), build(24.0));
+
+        let entries = diff(old.root(), new.root());
+        let shape: Vec<_> = entries.iter().map(|e| (e.depth, e.label.as_str(), e.is_context())).collect();
+        assert_eq!(shape, vec![(0, "Container", true), (1, "Column", true), (2, "Container", true), (3, "Row", true), (4, "Text", false)]);
+        assert_eq!(entries[4].kind, DiffKind::Matched {
+            moved: None,
+            properties: vec![PropertyDiff { key: "text_size", before: "16".into(), after: "24".into() }],
+        });
+    }
+
+    #[test]
+    fn test_insert_and_remove_leave_siblings_alone() {
+        let (mut old, column) = base();
+        old.add_child(column, WidgetType::Text).unwrap();
+        old.add_child(column, WidgetType::Slider).unwrap();
+        old.add_child(column, WidgetType::Button).unwrap();
+        let (mut new, column) = base();
+        let checkbox = new.add_child(column, WidgetType::Checkbox).unwrap();
+        let added_row = new.add_child(column, WidgetType::Row).unwrap();
+        new.add_child(added_row, WidgetType::Text).unwrap();
+        new.add_child(column, WidgetType::Text).unwrap();
+        new.add_child(column, WidgetType::Button).unwrap();
+
+        let entries = diff(old.root(), new.root());
+        let changes: Vec<_> = changes(&entries).into_iter().map(|e| (e.label.as_str(), e.current, &e.kind)).collect();
+        assert_eq!(changes, vec![
+            ("Checkbox", Some(checkbox), &DiffKind::Added),
+            ("Row", Some(added_row), &DiffKind::Added),
+            ("Slider", None, &DiffKind::Removed),
+        ]);
+    }
+}
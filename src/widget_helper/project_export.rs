@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::widget_helper::*;
+use crate::widget_helper::code_generation::{GenerationSettings, configured_generator};
+use crate::widget_helper::code_generator::{used_widget_modules, uses_file_dialogs};
+use crate::widget_helper::type_system::TypeSystem;
+use crate::widget_helper::asset_library::{self, AssetLibrary};
+
+/// iced dependency the generated code is written against
+pub const ICED_GIT: &str = "https://github.com/iced-rs/iced";
+pub const ICED_BRANCH: &str = "master";
+/// rfd version added for the file dialog capability
+pub const RFD_VERSION: &str = "0.15";
+
+/// Everything needed to write a runnable Cargo project to disk
+#[derive(Debug, Clone)]
+pub struct ProjectExport {
+    pub package_name: String,
+    pub cargo_toml: String,
+    pub main_rs: String,
+    pub readme: String,
+    /// Files the app loads at runtime, as written in the generated code
+    pub assets: Vec<String>,
+    /// Files copied into the project: where each is now, and where it goes relative to the project
+    pub asset_copies: Vec<(PathBuf, PathBuf)>,
+}
+
+/// Dynamic pieces injected into the README template
+#[derive(Debug, Clone)]
+pub struct ReadmeInfo<'a> {
+    pub app_name: &'a str,
+    pub window_title: &'a str,
+    pub package_name: &'a str,
+    pub features: &'a [&'static str],
+    pub widgets: &'a [&'static str],
+    pub assets: &'a [String],
+    pub uses_monospace: bool,
+}
+
+pub fn build_project(
+    hierarchy: &WidgetHierarchy,
+    type_system: &TypeSystem,
+    settings: &GenerationSettings,
+    library: &AssetLibrary,
+) -> ProjectExport {
+    let assets = collect_assets(hierarchy, library);
+    let (app_name, window_title) = (settings.app_name.as_str(), settings.window_title.as_str());
+
+    let mut generator = configured_generator(hierarchy, type_system, settings);
+    generator.set_asset_paths(assets.moved);
+    let main_rs = generator.generate_app_source();
+
+    let package_name = package_name(app_name);
+    let features = required_features(hierarchy);
+    let widgets = used_widget_modules(hierarchy);
+
+    let readme = generate_readme(&ReadmeInfo {
+        app_name,
+        window_title,
+        package_name: &package_name,
+        features: &features,
+        widgets: &widgets,
+        assets: &assets.written,
+        uses_monospace: uses_monospace(hierarchy.root()),
+    });
+
+    ProjectExport {
+        cargo_toml: generate_cargo_toml(&package_name, &features, uses_file_dialogs(hierarchy.root()), settings.file_header.cargo_license()),
+        package_name,
+        main_rs,
+        readme,
+        assets: assets.written,
+        asset_copies: assets.copies,
+    }
+}
+
+/// Cargo package names are lowercase with hyphens
+pub fn package_name(app_name: &str) -> String {
+    let name: String = app_name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let name = name.trim_matches('-').to_string();
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("app{}", if name.is_empty() { String::new() } else { format!("-{}", name) })
+    } else {
+        name
+    }
+}
+
+/// iced cargo features the layout needs, in a stable order
+pub fn required_features(hierarchy: &WidgetHierarchy) -> Vec<&'static str> {
+    let mut features = Vec::new();
+    collect_features(hierarchy.root(), &mut features);
+    features.sort();
+    features.dedup();
+    features
+}
+
+fn collect_features(widget: &Widget, features: &mut Vec<&'static str>) {
+    match widget.widget_type {
+        WidgetType::Image    => features.push("image"),
+        WidgetType::Svg      => features.push("svg"),
+        WidgetType::Markdown => features.push("markdown"),
+        WidgetType::QRCode   => features.push("qr_code"),
+        _ => {}
+    }
+    for child in &widget.children {
+        collect_features(child, features);
+    }
+}
+
+/// Files the app loads at runtime, gathered once per export
+#[derive(Debug, Default)]
+struct ExportAssets {
+    /// As written in the generated code, each once
+    written: Vec<String>,
+    /// Source and project-relative target of each file that gets copied
+    copies: Vec<(PathBuf, PathBuf)>,
+    /// Bundled paths written in place of a widget's own
+    moved: HashMap<WidgetId, String>,
+}
+
+/// Library assets are copied into `assets/` once each, however many widgets use them.
+/// Other relative paths are copied to the same relative place; absolute ones are left out.
+fn collect_assets(hierarchy: &WidgetHierarchy, library: &AssetLibrary) -> ExportAssets {
+    fn walk(widget: &Widget, library: &AssetLibrary, assets: &mut ExportAssets, files: &mut Vec<(String, Option<PathBuf>)>) {
+        if let Some(path) = asset_library::widget_path(widget).filter(|p| !p.trim().is_empty()) {
+            let bundled = library.linked(widget.properties.library_asset, path)
+                .and_then(|asset| Some((library.export_path(asset.id)?, asset.path.clone())));
+            match bundled {
+                Some((target, source)) => {
+                    assets.moved.insert(widget.id, target.clone());
+                    files.push((target, Some(source)));
+                }
+                None => {
+                    let source = PathBuf::from(path);
+                    files.push((path.to_string(), (!source.is_absolute()).then_some(source)));
+                }
+            }
+        }
+        for child in &widget.children {
+            walk(child, library, assets, files);
+        }
+    }
+
+    let mut assets = ExportAssets::default();
+    let mut files = Vec::new();
+    walk(hierarchy.root(), library, &mut assets, &mut files);
+    files.sort();
+    files.dedup_by(|a, b| a.0 == b.0);
+    for (written, source) in files {
+        if let Some(source) = source {
+            assets.copies.push((source, PathBuf::from(&written)));
+        }
+        assets.written.push(written);
+    }
+    assets
+}
+
+fn uses_monospace(widget: &Widget) -> bool {
+    let props = &widget.properties;
+    props.font == FontType::Monospace
+        || props.text_input_font == FontType::Monospace
+        || widget.children.iter().any(uses_monospace)
+}
+
+pub fn generate_cargo_toml(package_name: &str, features: &[&'static str], file_dialogs: bool, license: Option<&str>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "[package]");
+    let _ = writeln!(out, "name = \"{}\"", package_name);
+    let _ = writeln!(out, "version = \"0.1.0\"");
+    let _ = writeln!(out, "edition = \"2024\"");
+    if let Some(license) = license {
+        let _ = writeln!(out, "license = {:?}", license);
+    }
+    out.push('\n');
+    let _ = writeln!(out, "[dependencies]");
+    let _ = write!(out, "iced = {{ git = \"{}\", branch = \"{}\"", ICED_GIT, ICED_BRANCH);
+    if !features.is_empty() {
+        let quoted: Vec<String> = features.iter().map(|f| format!("\"{}\"", f)).collect();
+        let _ = write!(out, ", features = [{}]", quoted.join(", "));
+    }
+    let _ = writeln!(out, " }}");
+    if file_dialogs {
+        let _ = writeln!(out, "rfd = \"{}\"", RFD_VERSION);
+    }
+    out
+}
+
+/// README template. Only the injected pieces vary, so the output is stable for a given layout.
+pub fn generate_readme(info: &ReadmeInfo) -> String {
+    let title = if info.window_title.trim().is_empty() { info.app_name } else { info.window_title };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}", info.app_name);
+    out.push('\n');
+    let _ = writeln!(out, "Generated with theme-viewer. The window opens with the title \"{}\".", title);
+    out.push('\n');
+
+    let _ = writeln!(out, "## Running");
+    out.push('\n');
+    let _ = writeln!(out, "Install a Rust toolchain from https://rustup.rs, then from this directory run:");
+    out.push('\n');
+    let _ = writeln!(out, "```sh");
+    let _ = writeln!(out, "cargo run --release");
+    let _ = writeln!(out, "```");
+    out.push('\n');
+    let _ = writeln!(out, "The binary is written to `target/release/{}`.", info.package_name);
+    out.push('\n');
+
+    let _ = writeln!(out, "## iced");
+    out.push('\n');
+    let _ = writeln!(out, "`Cargo.toml` pins iced to the `{}` branch of {}.", ICED_BRANCH, ICED_GIT);
+    if info.features.is_empty() {
+        let _ = writeln!(out, "No optional iced features are enabled.");
+    } else {
+        let _ = writeln!(out, "Enabled features: {}.", backticked(info.features.iter().copied()));
+    }
+    out.push('\n');
+
+    let _ = writeln!(out, "## Widgets");
+    out.push('\n');
+    for widget in info.widgets {
+        let _ = writeln!(out, "- `{}`", widget);
+    }
+    out.push('\n');
+
+    let _ = writeln!(out, "## Assets");
+    out.push('\n');
+    if info.assets.is_empty() {
+        let _ = writeln!(out, "The app does not load any files at runtime.");
+    } else {
+        let _ = writeln!(out, "The app loads these files at runtime:");
+        out.push('\n');
+        for asset in info.assets {
+            let _ = writeln!(out, "- `{}`", asset);
+        }
+        out.push('\n');
+        let _ = writeln!(
+            out,
+            "Relative paths are resolved against the working directory, not the binary. \
+             `cargo run` uses this directory; if you ship the binary, keep the files at the same \
+             relative location next to wherever it is launched from."
+        );
+    }
+    out.push('\n');
+
+    let _ = writeln!(out, "## Fonts");
+    out.push('\n');
+    if info.uses_monospace {
+        let _ = writeln!(out, "Text uses iced's default and monospace fonts, which are built in. No fonts need loading.");
+    } else {
+        let _ = writeln!(out, "Text uses iced's built-in default font. No fonts need loading.");
+    }
+
+    out
+}
+
+fn backticked<'s>(items: impl Iterator<Item = &'s str>) -> String {
+    items.map(|i| format!("`{}`", i)).collect::<Vec<_>>().join(", ")
+}
+
+/// Files to write, relative to the chosen project directory
+pub fn project_files(export: &ProjectExport) -> Vec<(PathBuf, String)> {
+    vec![
+        (PathBuf::from("Cargo.toml"), export.cargo_toml.clone()),
+        (PathBuf::from("src").join("main.rs"), export.main_rs.clone()),
+        (PathBuf::from("README.md"), export.readme.clone()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::Theme;
+    use crate::widget_helper::code_generator::{CodeStyle, StubStyle};
+    use crate::widget_helper::window_preset::WindowSettings;
+    use crate::widget_helper::file_header::FileHeader;
+    use crate::widget_helper::named_styles::StyleRegistry;
+
+    fn settings(app_name: &str) -> GenerationSettings {
+        GenerationSettings {
+            theme: Theme::Light,
+            app_name: app_name.to_string(),
+            window_title: String::new(),
+            doc_comments: false,
+            code_style: CodeStyle::Verbose,
+            stub_style: StubStyle::Comment,
+            derive_default: false,
+            view_builders: false,
+            view_helpers: None,
+            multi_window: false,
+            layout_only: false,
+            window: WindowSettings::default(),
+            file_header: FileHeader::default(),
+            named_styles: StyleRegistry::default(),
+        }
+    }
+
+    #[test]
+    fn test_readme_golden() {
+        let readme = generate_readme(&ReadmeInfo {
+            app_name: "Counter",
+            window_title: "My Counter",
+            package_name: "counter",
+            features: &["image"],
+            widgets: &["button", "column", "image", "text"],
+            assets: &["assets/logo.png".to_string()],
+            uses_monospace: false,
+        });
+
+        let expected = "\
+# Counter
+
+Generated with theme-viewer. The window opens with the title \"My Counter\".
+
+## Running
+
+Install a Rust toolchain from https://rustup.rs, then from this directory run:
+
+```sh
+cargo run --release
+```
+
+The binary is written to `target/release/counter`.
+
+## iced
+
+`Cargo.toml` pins iced to the `master` branch of https://github.com/iced-rs/iced.
+Enabled features: `image`.
+
+## Widgets
+
+- `button`
+- `column`
+- `image`
+- `text`
+
+## Assets
+
+The app loads these files at runtime:
+
+- `assets/logo.png`
+
+Relative paths are resolved against the working directory, not the binary. `cargo run` uses this directory; if you ship the binary, keep the files at the same relative location next to wherever it is launched from.
+
+## Fonts
+
+Text uses iced's built-in default font. No fonts need loading.
+";
+        assert_eq!(readme, expected);
+    }
+
+    #[test]
+    fn test_project_export_features_and_assets() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let image_id = hierarchy.add_child(column_id, WidgetType::Image).unwrap();
+        hierarchy.add_child(column_id, WidgetType::Svg).unwrap();
+        hierarchy.apply_property_change(image_id, PropertyChange::ImagePath("logo.png".to_string()), &type_system);
+
+        let export = build_project(&hierarchy, &type_system, &settings("My App"), &AssetLibrary::default());
+        assert_eq!(export.package_name, "my-app");
+        assert!(export.cargo_toml.contains("features = [\"image\", \"svg\"]"));
+        assert!(export.assets.contains(&"logo.png".to_string()));
+        assert!(export.readme.contains("- `logo.png`"));
+        assert_eq!(export.readme, build_project(&hierarchy, &type_system, &settings("My App"), &AssetLibrary::default()).readme);
+    }
+
+    #[test]
+    fn test_library_assets_are_bundled_once() {
+        let type_system = TypeSystem::new();
+        let mut library = AssetLibrary::default();
+        library.add_files([PathBuf::from("/icons/home.svg")]);
+        let home = &library.assets()[0];
+
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        for _ in 0..2 {
+            let svg_id = hierarchy.add_child(WidgetId(0), WidgetType::Svg).unwrap();
+            hierarchy.apply_property_change(svg_id, PropertyChange::SvgAsset(Some(home.id), home.path_property()), &type_system);
+        }
+        let image_id = hierarchy.add_child(WidgetId(0), WidgetType::Image).unwrap();
+        hierarchy.apply_property_change(image_id, PropertyChange::ImagePath("logo.png".to_string()), &type_system);
+
+        let export = build_project(&hierarchy, &type_system, &settings("App"), &library);
+        assert_eq!(export.assets, ["assets/home.svg", "logo.png"]);
+        assert_eq!(export.asset_copies, [
+            (home.path.clone(), PathBuf::from("assets/home.svg")),
+            (PathBuf::from("logo.png"), PathBuf::from("logo.png")),
+        ]);
+        assert_eq!(export.main_rs.matches("r\"assets/home.svg\"").count(), 2);
+        assert!(!export.main_rs.contains("/icons/home.svg"));
+    }
+
+    #[test]
+    fn test_file_dialogs_add_rfd() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let button_id = hierarchy.add_child(WidgetId(0), WidgetType::Button).unwrap();
+        let export = |hierarchy: &WidgetHierarchy| build_project(hierarchy, &type_system, &settings("App"), &AssetLibrary::default());
+        assert!(!export(&hierarchy).cargo_toml.contains("rfd"));
+
+        hierarchy.apply_property_change(button_id, PropertyChange::FileDialog(FileDialog::Open), &type_system);
+        assert!(export(&hierarchy).cargo_toml.ends_with("rfd = \"0.15\"\n"));
+    }
+
+    #[test]
+    fn test_license_header_reaches_main_rs_and_cargo_toml() {
+        let type_system = TypeSystem::new();
+        let hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        let header = FileHeader {
+            enabled: true,
+            author: "Ada".to_string(),
+            license: "Apache-2.0".to_string(),
+            year: 2030,
+            template: "{app_name} by {author}, {year}\nSPDX-License-Identifier: {license}".to_string(),
+        };
+        let export = build_project(&hierarchy, &type_system, &GenerationSettings { file_header: header, ..settings("Counter") }, &AssetLibrary::default());
+        assert!(export.main_rs.starts_with("// Counter by Ada, 2030\n// SPDX-License-Identifier: Apache-2.0\n\nuse "));
+        assert!(export.cargo_toml.contains("edition = \"2024\"\nlicense = \"Apache-2.0\"\n"));
+
+        let plain = build_project(&hierarchy, &type_system, &settings("Counter"), &AssetLibrary::default());
+        assert!(!plain.cargo_toml.contains("license"));
+        assert!(plain.main_rs.starts_with("use "));
+    }
+
+    #[test]
+    fn test_package_name() {
+        assert_eq!(package_name("Counter"), "counter");
+        assert_eq!(package_name("  "), "app");
+        assert_eq!(package_name("3D View"), "app-3d-view");
+    }
+}
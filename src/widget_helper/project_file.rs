@@ -0,0 +1,461 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::widget_helper::*;
+use crate::widget_helper::asset_library::AssetLibrary;
+use crate::widget_helper::file_io;
+use crate::widget_helper::file_header::FileHeader;
+use crate::widget_helper::named_styles::StyleRegistry;
+use crate::widget_helper::type_system::TypeSystem;
+use crate::widget_helper::window_preset::WindowSettings;
+
+/// Bumped whenever a saved project stops meaning the same thing to an older builder
+pub const FORMAT_VERSION: u32 = 2;
+/// First format where a Radio group's orientation means something. Older files saved every
+/// group as horizontal while it was drawn as a column.
+const RADIO_ORIENTATION_VERSION: u64 = 2;
+pub const EXTENSIONS: &[&str] = &["json"];
+
+/// A builder project as written to disk: every tab's tree and the sections the tabs share
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFile {
+    pub version: u32,
+    #[serde(default)]
+    pub app_name: String,
+    #[serde(default)]
+    pub window_title: String,
+    /// Theme name, looked up among the built-in and imported themes on load
+    #[serde(default)]
+    pub theme: Option<String>,
+    #[serde(default)]
+    pub type_system: TypeSystem,
+    #[serde(default)]
+    pub project_defaults: ProjectDefaults,
+    #[serde(default)]
+    pub window_settings: WindowSettings,
+    #[serde(default)]
+    pub file_header: FileHeader,
+    #[serde(default)]
+    pub named_styles: StyleRegistry,
+    #[serde(default)]
+    pub asset_library: AssetLibrary,
+    #[serde(default)]
+    pub tabs: Vec<SavedTab>,
+    #[serde(default)]
+    pub active_tab: usize,
+    /// A file with a single tree instead of tabs, read as one tab
+    #[serde(default, skip_serializing)]
+    root: Option<Widget>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedTab {
+    pub name: String,
+    pub root: Widget,
+}
+
+impl ProjectFile {
+    pub fn new(tabs: Vec<SavedTab>, active_tab: usize) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            app_name: String::new(),
+            window_title: String::new(),
+            theme: None,
+            type_system: TypeSystem::new(),
+            project_defaults: ProjectDefaults::default(),
+            window_settings: WindowSettings::default(),
+            file_header: FileHeader::default(),
+            named_styles: StyleRegistry::default(),
+            asset_library: AssetLibrary::default(),
+            tabs,
+            active_tab,
+            root: None,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    /// Reads a saved project. Files from a newer builder are refused rather than half read.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|e| format!("Not a project file: {}", e))?;
+        let version = value.get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| "Not a project file: it has no format version".to_string())?;
+        if version > FORMAT_VERSION as u64 {
+            return Err(format!(
+                "The project was saved in format {}, newer than this builder reads ({})", version, FORMAT_VERSION,
+            ));
+        }
+        let mut file: Self = serde_json::from_value(value).map_err(|e| format!("The project file is damaged: {}", e))?;
+        if file.tabs.is_empty() {
+            let root = file.root.take().ok_or_else(|| "The project file has no layout".to_string())?;
+            file.tabs.push(SavedTab { name: "Screen 1".to_string(), root });
+        }
+        file.active_tab = file.active_tab.min(file.tabs.len() - 1);
+        if version < RADIO_ORIENTATION_VERSION {
+            for tab in &mut file.tabs {
+                stack_radios(&mut tab.root);
+            }
+        }
+        Ok(file)
+    }
+}
+
+/// A project read and checked off the update thread, ready to replace the open one
+#[derive(Debug, Clone)]
+pub struct LoadedProject {
+    pub path: PathBuf,
+    /// The shared sections, its tabs taken out into `tabs`
+    pub file: ProjectFile,
+    pub tabs: Vec<(String, WidgetHierarchy)>,
+}
+
+/// Reads, parses and checks the project at `path`, rebuilding each tab's runtime state.
+/// Every step is linear in the widget count.
+pub fn load(path: &Path) -> Result<LoadedProject, String> {
+    let fail = |e: String| format!("Couldn't open {}: {}", path.display(), e);
+    let contents = std::fs::read_to_string(path).map_err(|e| fail(format!("Failed to read it: {}", e)))?;
+    let mut file = ProjectFile::parse(&contents).map_err(fail)?;
+    let tabs = std::mem::take(&mut file.tabs)
+        .into_iter()
+        .map(|tab| WidgetHierarchy::from_root(tab.root).map(|hierarchy| (tab.name, hierarchy)))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| fail(format!("the project file is damaged: {}", e)))?;
+    Ok(LoadedProject { path: path.to_path_buf(), file, tabs })
+}
+
+/// `load` as a task, so a large project doesn't hold up the window while it's read
+pub async fn read(path: PathBuf) -> Result<Arc<LoadedProject>, String> {
+    load(&path).map(Arc::new)
+}
+
+/// Ask where to save `file`, then serialize and write it as a task
+pub async fn save(file: ProjectFile, default_name: String) -> Result<PathBuf, String> {
+    let json = file.to_json().map_err(|e| format!("Couldn't save the project: {}", e))?;
+    file_io::save_text_file(json, default_name, "Builder project", EXTENSIONS).await
+}
+
+/// Lays out every Radio group below `widget` as a column, as the builder drew them before
+/// groups had an orientation
+fn stack_radios(widget: &mut Widget) {
+    if widget.widget_type == WidgetType::Radio {
+        widget.properties.orientation = Orientation::Vertical;
+    }
+    for child in &mut widget.children {
+        stack_radios(child);
+    }
+}
+
+/// Serde `with` module for an iced type saved as the builder's own mirror of it
+macro_rules! via_mirror {
+    ($module:ident, $iced:ty, $mirror:ty) => {
+        pub mod $module {
+            use super::*;
+            use serde::{Deserializer, Serializer};
+
+            pub fn serialize<S: Serializer>(value: &$iced, serializer: S) -> Result<S::Ok, S::Error> {
+                <$mirror>::from(*value).serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<$iced, D::Error> {
+                <$mirror>::deserialize(deserializer).map(Into::into)
+            }
+        }
+    };
+}
+
+via_mirror!(alignment, Alignment, AlignmentXOption);
+via_mirror!(vertical, iced::alignment::Vertical, AlignmentYOption);
+via_mirror!(wrapping, text::Wrapping, TextWrapping);
+via_mirror!(shaping, text::Shaping, TextShaping);
+via_mirror!(text_alignment, iced::advanced::text::Alignment, AlignText);
+via_mirror!(anchor, scrollable::Anchor, AnchorChoice);
+via_mirror!(length, Length, SavedLength);
+via_mirror!(padding, Padding, SavedPadding);
+via_mirror!(line_height, text::LineHeight, SavedLineHeight);
+
+#[derive(Serialize, Deserialize)]
+enum SavedLength {
+    Fill,
+    FillPortion(u16),
+    Shrink,
+    Fixed(f32),
+}
+
+impl From<Length> for SavedLength {
+    fn from(length: Length) -> Self {
+        match length {
+            Length::Fill => Self::Fill,
+            Length::FillPortion(portion) => Self::FillPortion(portion),
+            Length::Shrink => Self::Shrink,
+            Length::Fixed(pixels) => Self::Fixed(pixels),
+        }
+    }
+}
+
+impl From<SavedLength> for Length {
+    fn from(length: SavedLength) -> Self {
+        match length {
+            SavedLength::Fill => Self::Fill,
+            SavedLength::FillPortion(portion) => Self::FillPortion(portion),
+            SavedLength::Shrink => Self::Shrink,
+            SavedLength::Fixed(pixels) => Self::Fixed(pixels),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedPadding {
+    top: f32,
+    right: f32,
+    bottom: f32,
+    left: f32,
+}
+
+impl From<Padding> for SavedPadding {
+    fn from(p: Padding) -> Self {
+        Self { top: p.top, right: p.right, bottom: p.bottom, left: p.left }
+    }
+}
+
+impl From<SavedPadding> for Padding {
+    fn from(p: SavedPadding) -> Self {
+        Padding { top: p.top, right: p.right, bottom: p.bottom, left: p.left }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum SavedLineHeight {
+    Relative(f32),
+    Absolute(f32),
+}
+
+impl From<text::LineHeight> for SavedLineHeight {
+    fn from(line_height: text::LineHeight) -> Self {
+        match line_height {
+            text::LineHeight::Relative(factor) => Self::Relative(factor),
+            text::LineHeight::Absolute(pixels) => Self::Absolute(pixels.0),
+        }
+    }
+}
+
+impl From<SavedLineHeight> for text::LineHeight {
+    fn from(line_height: SavedLineHeight) -> Self {
+        match line_height {
+            SavedLineHeight::Relative(factor) => Self::Relative(factor),
+            SavedLineHeight::Absolute(pixels) => Self::Absolute(iced::Pixels(pixels)),
+        }
+    }
+}
+
+pub mod option_length {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<Length>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(SavedLength::from).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Length>, D::Error> {
+        Ok(Option::<SavedLength>::deserialize(deserializer)?.map(Into::into))
+    }
+}
+
+/// Colors as `#RRGGBB` or `#RRGGBBAA`, the form palette files use
+pub mod hex_color {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+    use crate::palette_file::{color_to_hex, parse_hex};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        color_to_hex(*color).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        parse_hex(&hex).map_err(serde::de::Error::custom)
+    }
+}
+
+pub mod option_hex_color {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+    use crate::palette_file::{color_to_hex, parse_hex};
+
+    pub fn serialize<S: Serializer>(color: &Option<Color>, serializer: S) -> Result<S::Ok, S::Error> {
+        color.map(color_to_hex).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Color>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|hex| parse_hex(&hex).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+pub mod vector {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Vector, serializer: S) -> Result<S::Ok, S::Error> {
+        (value.x, value.y).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vector, D::Error> {
+        let (x, y) = <(f32, f32)>::deserialize(deserializer)?;
+        Ok(Vector::new(x, y))
+    }
+}
+
+pub mod point {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Point, serializer: S) -> Result<S::Ok, S::Error> {
+        (value.x, value.y).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Point, D::Error> {
+        let (x, y) = <(f32, f32)>::deserialize(deserializer)?;
+        Ok(Point::new(x, y))
+    }
+}
+
+/// Scroll direction by axis. Scrollbar settings aren't editable, so they come back as defaults.
+pub mod direction {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &scrollable::Direction, serializer: S) -> Result<S::Ok, S::Error> {
+        DirChoice::to_choice(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<scrollable::Direction, D::Error> {
+        DirChoice::deserialize(deserializer).map(DirChoice::from_choice)
+    }
+}
+
+/// Built-in themes by name. A theme the builder doesn't ship, like an imported one, is
+/// dropped on load and the Themer follows the app's theme again.
+pub mod option_theme {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<Theme>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.as_ref().map(Theme::to_string).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Theme>, D::Error> {
+        let name = Option::<String>::deserialize(deserializer)?;
+        Ok(name.and_then(|name| Theme::ALL.iter().find(|theme| theme.to_string() == name).cloned()))
+    }
+}
+
+/// A text editor's content as its text
+pub mod editor_text {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &text_editor::Content, serializer: S) -> Result<S::Ok, S::Error> {
+        value.text().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<text_editor::Content, D::Error> {
+        Ok(text_editor::Content::with_text(&String::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_root() -> Widget {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let text_id = hierarchy.add_child(column, WidgetType::Text).unwrap();
+        let combo = hierarchy.add_child(column, WidgetType::ComboBox).unwrap();
+        let markdown = hierarchy.add_child(column, WidgetType::Markdown).unwrap();
+        let types = TypeSystem::new();
+        hierarchy.apply_property_change(column, PropertyChange::Width(Length::FillPortion(3)), &types);
+        hierarchy.apply_property_change(text_id, PropertyChange::TextContent("Hello".to_string()), &types);
+        hierarchy.apply_property_change(
+            text_id,
+            PropertyChange::TextLineHeight(text::LineHeight::Absolute(iced::Pixels(24.0))),
+            &types,
+        );
+        hierarchy.apply_property_change(combo, PropertyChange::ComboBoxState(vec!["One".to_string(), "Two".to_string()]), &types);
+        if let Some(root) = hierarchy.get_widget_by_id_mut(WidgetId(0)) {
+            root.properties.padding = Padding { top: 1.0, right: 2.0, bottom: 3.0, left: 4.0 };
+            root.properties.background_color = Color::from_rgb8(0x33, 0x66, 0x99);
+        }
+        if let Some(widget) = hierarchy.get_widget_by_id_mut(markdown) {
+            widget.properties.markdown_source = text_editor::Content::with_text("# Title");
+        }
+        hierarchy.root().clone()
+    }
+
+    #[test]
+    fn test_layout_round_trips_and_ids_continue_past_the_highest() {
+        let file = ProjectFile::new(vec![SavedTab { name: "Home".to_string(), root: sample_root() }], 0);
+        let json = file.to_json().unwrap();
+        let back = ProjectFile::parse(&json).unwrap();
+        assert_eq!(back.to_json().unwrap(), json);
+
+        let mut hierarchy = WidgetHierarchy::from_root(back.tabs[0].root.clone()).unwrap();
+        let root = hierarchy.root();
+        assert_eq!(root.properties.padding, Padding { top: 1.0, right: 2.0, bottom: 3.0, left: 4.0 });
+        let column = &root.children[0];
+        assert_eq!(column.properties.width, Length::FillPortion(3));
+        assert_eq!(column.children[0].properties.line_height, text::LineHeight::Absolute(iced::Pixels(24.0)));
+        // Runtime state is rebuilt from what was saved
+        assert!(!column.children[2].properties.markdown_content.is_empty());
+
+        let added = hierarchy.add_child(WidgetId(0), WidgetType::Text).unwrap();
+        assert_eq!(added, WidgetId(5));
+    }
+
+    #[test]
+    fn test_newer_and_damaged_files_are_refused() {
+        let newer = format!("{{\"version\": {}, \"tabs\": []}}", FORMAT_VERSION + 1);
+        assert!(ProjectFile::parse(&newer).unwrap_err().contains("newer"));
+        assert!(ProjectFile::parse("{\"version\": 1, \"tabs\": 3}").unwrap_err().contains("damaged"));
+        assert!(ProjectFile::parse("not json").is_err());
+        assert!(ProjectFile::parse("{\"tabs\": []}").is_err());
+    }
+
+    #[test]
+    fn test_single_tree_file_loads_as_one_tab() {
+        let root = serde_json::to_value(sample_root()).unwrap();
+        let json = serde_json::json!({ "version": 1, "root": root }).to_string();
+        let file = ProjectFile::parse(&json).unwrap();
+        assert_eq!(file.tabs.len(), 1);
+        assert_eq!(file.tabs[0].name, "Screen 1");
+        assert_eq!(file.tabs[0].root.children.len(), 1);
+    }
+
+    #[test]
+    fn test_radios_from_before_orientation_stay_columns() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        let radio_id = hierarchy.add_child(WidgetId(0), WidgetType::Radio).unwrap();
+        hierarchy.get_widget_by_id_mut(radio_id).unwrap().properties.orientation = Orientation::Horizontal;
+        let root = serde_json::to_value(hierarchy.root()).unwrap();
+
+        let old = serde_json::json!({ "version": 1, "root": root }).to_string();
+        let file = ProjectFile::parse(&old).unwrap();
+        assert_eq!(file.tabs[0].root.children[0].properties.orientation, Orientation::Vertical);
+
+        let current = serde_json::json!({ "version": FORMAT_VERSION, "root": root }).to_string();
+        let file = ProjectFile::parse(&current).unwrap();
+        assert_eq!(file.tabs[0].root.children[0].properties.orientation, Orientation::Horizontal);
+    }
+
+    #[test]
+    fn test_repeated_ids_are_refused() {
+        let mut root = sample_root();
+        root.children[0].children[1].id = root.children[0].children[0].id;
+        assert!(WidgetHierarchy::from_root(root).is_err());
+    }
+}
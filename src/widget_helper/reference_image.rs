@@ -0,0 +1,213 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use iced::widget::image::Handle;
+use iced::{Color, Point, Size};
+use crate::widget_helper::{PropertyChange, WidgetId};
+
+/// Longer side a reference is scaled down to on load, which bounds the buffer kept for the
+/// session to 16 MB
+pub const MAX_REFERENCE_SIDE: u32 = 2048;
+
+/// Width the reference is shown at in its panel
+pub const REFERENCE_PANEL_WIDTH: f32 = 280.0;
+
+pub const REFERENCE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp"];
+
+/// A decoded reference image, kept as RGBA so sampling doesn't decode again
+pub struct ReferenceImage {
+    pub path: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<u8>,
+    pub handle: Handle,
+}
+
+impl std::fmt::Debug for ReferenceImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReferenceImage")
+            .field("path", &self.path)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ReferenceImage {
+    /// Size the image is drawn at for a panel `width` wide, keeping its aspect ratio
+    pub fn display_size(&self, width: f32) -> Size {
+        Size::new(width, width * self.height as f32 / self.width as f32)
+    }
+
+    /// Color under `point` of the image drawn at `display`, None outside it
+    pub fn sample_at(&self, point: Point, display: Size, average: bool) -> Option<Color> {
+        let (x, y) = source_pixel(point, display, self.width, self.height)?;
+        Some(sample(&self.pixels, self.width, self.height, x, y, average))
+    }
+}
+
+/// Decodes the image at `path`, scaling it down if either side is over `MAX_REFERENCE_SIDE`
+pub async fn load(path: PathBuf) -> Result<Arc<ReferenceImage>, String> {
+    let decoded = image::open(&path)
+        .map_err(|e| format!("Couldn't read {} as an image: {}", path.display(), e))?;
+    let decoded = if decoded.width().max(decoded.height()) > MAX_REFERENCE_SIDE {
+        decoded.thumbnail(MAX_REFERENCE_SIDE, MAX_REFERENCE_SIDE)
+    } else {
+        decoded
+    };
+    let rgba = decoded.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    if width == 0 || height == 0 {
+        return Err(format!("{} has no pixels", path.display()));
+    }
+    let pixels = rgba.into_raw();
+    Ok(Arc::new(ReferenceImage {
+        path,
+        width,
+        height,
+        handle: Handle::from_rgba(width, height, pixels.clone()),
+        pixels,
+    }))
+}
+
+/// Maps a point in the image as drawn at `display` to the source pixel under it
+pub fn source_pixel(point: Point, display: Size, width: u32, height: u32) -> Option<(u32, u32)> {
+    if point.x < 0.0 || point.y < 0.0 || point.x >= display.width || point.y >= display.height {
+        return None;
+    }
+    let x = (point.x / display.width * width as f32) as u32;
+    let y = (point.y / display.height * height as f32) as u32;
+    Some((x.min(width - 1), y.min(height - 1)))
+}
+
+/// Color of the pixel at `(x, y)`, or the mean of the 3x3 block around it to smooth out
+/// noise and compression artifacts. At the edges only the pixels inside the image count.
+pub fn sample(pixels: &[u8], width: u32, height: u32, x: u32, y: u32, average: bool) -> Color {
+    let radius = if average { 1 } else { 0 };
+    let mut sum = [0u32; 4];
+    let mut count = 0;
+    for row in y.saturating_sub(radius)..=(y + radius).min(height - 1) {
+        for column in x.saturating_sub(radius)..=(x + radius).min(width - 1) {
+            let start = ((row * width + column) * 4) as usize;
+            for (total, &channel) in sum.iter_mut().zip(&pixels[start..start + 4]) {
+                *total += channel as u32;
+            }
+            count += 1;
+        }
+    }
+    let [r, g, b, a] = sum.map(|total| (total as f32 / count as f32).round() as u8);
+    Color::from_rgba8(r, g, b, a as f32 / 255.0)
+}
+
+/// The same change with its color swapped for `color`, None if it isn't a color property
+pub fn recolor(change: &PropertyChange, color: Color) -> Option<PropertyChange> {
+    Some(match change {
+        PropertyChange::TextColor(_) => PropertyChange::TextColor(color),
+        PropertyChange::BackgroundColor(_) => PropertyChange::BackgroundColor(color),
+        PropertyChange::BorderColor(_) => PropertyChange::BorderColor(color),
+        PropertyChange::ShadowColor(_) => PropertyChange::ShadowColor(color),
+        PropertyChange::GradientStart(_) => PropertyChange::GradientStart(color),
+        PropertyChange::GradientEnd(_) => PropertyChange::GradientEnd(color),
+        PropertyChange::SvgTintColor(_) => PropertyChange::SvgTintColor(color),
+        PropertyChange::ConditionBorderColor(_) => PropertyChange::ConditionBorderColor(color),
+        PropertyChange::ConditionBackgroundColor(_) => PropertyChange::ConditionBackgroundColor(color),
+        PropertyChange::ComboBoxInputBackground(_) => PropertyChange::ComboBoxInputBackground(Some(color)),
+        PropertyChange::ComboBoxInputTextColor(_) => PropertyChange::ComboBoxInputTextColor(Some(color)),
+        PropertyChange::ComboBoxMenuBackground(_) => PropertyChange::ComboBoxMenuBackground(Some(color)),
+        _ => return None,
+    })
+}
+
+/// Color property a click on the reference fills, as the change its input would send
+#[derive(Debug, Clone)]
+pub struct ColorTarget {
+    pub widget_id: WidgetId,
+    pub change: PropertyChange,
+}
+
+/// The reference image panel next to the properties
+#[derive(Debug, Default)]
+pub struct ReferencePanel {
+    pub open: bool,
+    pub image: Option<Arc<ReferenceImage>>,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Sample the 3x3 block around the cursor instead of a single pixel
+    pub average: bool,
+    /// Pointer over the drawn image, in its own coordinates
+    pub hover: Option<Point>,
+    pub target: Option<ColorTarget>,
+}
+
+impl ReferencePanel {
+    /// Color under the pointer, for the live readout and for clicks
+    pub fn hovered_color(&self) -> Option<Color> {
+        let image = self.image.as_ref()?;
+        image.sample_at(self.hover?, image.display_size(REFERENCE_PANEL_WIDTH), self.average)
+    }
+
+    /// The change a click makes now, filling the target with the hovered color
+    pub fn sampled_change(&self) -> Option<(WidgetId, PropertyChange)> {
+        let target = self.target.as_ref()?;
+        Some((target.widget_id, recolor(&target.change, self.hovered_color()?)?))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ReferenceMessage {
+    /// Opens the panel with a color input as the one clicks fill
+    Target(WidgetId, PropertyChange),
+    ChooseImage,
+    Chosen(Result<PathBuf, String>),
+    Loaded(Result<Arc<ReferenceImage>, String>),
+    PointerMoved(Point),
+    PointerLeft,
+    Sample,
+    AverageToggled(bool),
+    Close,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3 image whose red channel counts up from 0 in steps of 10, other channels 0
+    fn counting_pixels() -> Vec<u8> {
+        (0..9u8).flat_map(|i| [i * 10, 0, 0, 255]).collect()
+    }
+
+    #[test]
+    fn test_display_points_map_through_the_scale() {
+        // A 1000x500 image drawn at 250x125 is a quarter of its size
+        let display = Size::new(250.0, 125.0);
+        assert_eq!(source_pixel(Point::new(0.0, 0.0), display, 1000, 500), Some((0, 0)));
+        assert_eq!(source_pixel(Point::new(100.0, 50.0), display, 1000, 500), Some((400, 200)));
+        assert_eq!(source_pixel(Point::new(249.9, 124.9), display, 1000, 500), Some((999, 499)));
+        assert_eq!(source_pixel(Point::new(250.0, 10.0), display, 1000, 500), None);
+        assert_eq!(source_pixel(Point::new(-1.0, 10.0), display, 1000, 500), None);
+
+        // Drawn bigger than the source, several display points share a pixel
+        let display = Size::new(300.0, 300.0);
+        assert_eq!(source_pixel(Point::new(99.0, 199.0), display, 3, 3), Some((0, 1)));
+        assert_eq!(source_pixel(Point::new(101.0, 201.0), display, 3, 3), Some((1, 2)));
+    }
+
+    #[test]
+    fn test_single_pixel_and_averaged_samples() {
+        let pixels = counting_pixels();
+        assert_eq!(sample(&pixels, 3, 3, 2, 0, false), Color::from_rgba8(20, 0, 0, 1.0));
+        // The centre's block is the whole image, 0 + 10 + .. + 80 over 9
+        assert_eq!(sample(&pixels, 3, 3, 1, 1, true), Color::from_rgba8(40, 0, 0, 1.0));
+        // A corner only averages the 4 pixels inside the image: 0, 10, 30 and 40
+        assert_eq!(sample(&pixels, 3, 3, 0, 0, true), Color::from_rgba8(20, 0, 0, 1.0));
+    }
+
+    #[test]
+    fn test_recolor_keeps_the_property() {
+        let color = Color::from_rgb8(1, 2, 3);
+        assert!(matches!(
+            recolor(&PropertyChange::ComboBoxMenuBackground(None), color),
+            Some(PropertyChange::ComboBoxMenuBackground(Some(c))) if c == color
+        ));
+        assert!(recolor(&PropertyChange::Noop, color).is_none());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::widget_helper::*;
+use crate::widget_helper::hints::{estimate_text_height, DEFAULT_LABEL_SIZE};
+
+/// Id of the scrollable the generated view wraps its content in when it has sections
+pub const SECTIONS_SCROLLABLE: &str = "sections";
+
+/// Height of controls whose size the builder doesn't track, like sliders and pick lists
+const CONTROL_HEIGHT: f32 = 30.0;
+/// Images, SVGs and other media without a fixed height, which really take their source's
+const MEDIA_HEIGHT: f32 = 100.0;
+
+/// A Column child marked as a section, with its estimated distance from the top of the view
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub id: WidgetId,
+    pub name: String,
+    /// Name of the generated offset constant, unique among the sections
+    pub constant: String,
+    pub offset: f32,
+}
+
+/// Marked sections in tree order. Only direct children of a Column count, like the builder shows.
+pub fn sections(root: &Widget) -> Vec<Section> {
+    fn walk(widget: &Widget, top: f32, sections: &mut Vec<Section>) {
+        let props = &widget.properties;
+        let is_column = widget.widget_type == WidgetType::Column;
+        let mut y = top + props.padding.top;
+        for child in &widget.children {
+            if is_column && child.properties.section_anchor {
+                sections.push(Section {
+                    id: child.id,
+                    name: section_label(child),
+                    constant: String::new(),
+                    offset: y,
+                });
+            }
+            walk(child, y, sections);
+            // Everything but a Column lays its children over or beside each other
+            if is_column {
+                y += estimate_height(child) + props.spacing;
+            }
+        }
+    }
+
+    let mut sections = Vec::new();
+    walk(root, 0.0, &mut sections);
+
+    let mut taken: Vec<String> = Vec::new();
+    for (i, section) in sections.iter_mut().enumerate() {
+        let base = constant_name(&section.name).unwrap_or_else(|| format!("SECTION_{}", i + 1));
+        let mut constant = base.clone();
+        let mut n = 2;
+        while taken.contains(&constant) {
+            constant = format!("{}_{}", base, n);
+            n += 1;
+        }
+        taken.push(constant.clone());
+        section.constant = constant;
+    }
+    sections
+}
+
+/// Button text for a section: its section name, or the widget's name when that is empty
+pub fn section_label(widget: &Widget) -> String {
+    let name = widget.properties.section_name.trim();
+    if name.is_empty() { widget.name.clone() } else { name.to_string() }
+}
+
+/// `SECTION_GETTING_STARTED` for "Getting started", None when nothing of the name is usable
+fn constant_name(name: &str) -> Option<String> {
+    if !name.chars().any(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_uppercase)
+        .collect();
+    Some(format!("SECTION_{}", words.join("_")))
+}
+
+/// Rough rendered height of a widget, from fixed heights, text sizes and padding. Fill and
+/// Shrink children are measured by their content, so the result runs short under Fill.
+pub fn estimate_height(widget: &Widget) -> f32 {
+    let props = &widget.properties;
+    if let Length::Fixed(height) = props.height {
+        return height;
+    }
+    let padding = props.padding.top + props.padding.bottom;
+
+    match widget.widget_type {
+        WidgetType::Column => {
+            let children: f32 = widget.children.iter().map(estimate_height).sum();
+            let gaps = widget.children.len().saturating_sub(1) as f32 * props.spacing;
+            padding + children + gaps
+        }
+        WidgetType::Row | WidgetType::Container | WidgetType::Scrollable | WidgetType::Stack
+        | WidgetType::Tooltip | WidgetType::MouseArea | WidgetType::Themer | WidgetType::Pin => {
+            let tallest = widget.children.iter().map(estimate_height).fold(0.0, f32::max);
+            padding + tallest
+        }
+        WidgetType::Text => estimate_text_height(&props.text_content, props.text_size),
+        WidgetType::Button => estimate_text_height(&props.text_content, DEFAULT_LABEL_SIZE) + padding,
+        WidgetType::TextInput => {
+            estimate_text_height("", props.text_input_size) + props.text_input_padding * 2.0
+        }
+        WidgetType::Checkbox => props.checkbox_size.max(estimate_text_height(&props.checkbox_label, DEFAULT_LABEL_SIZE)),
+        WidgetType::Toggler => props.toggler_size.max(estimate_text_height(&props.toggler_label, DEFAULT_LABEL_SIZE)),
+        WidgetType::Radio => {
+            let options = props.radio_options.len().max(1) as f32;
+            let line = props.radio_size.max(estimate_text_height("", DEFAULT_LABEL_SIZE));
+            options * line + (options - 1.0) * props.radio_spacing
+        }
+        WidgetType::ProgressBar if !props.progress_vertical => props.progress_girth,
+        WidgetType::Rule => props.rule_thickness,
+        WidgetType::Space => 0.0,
+        WidgetType::Markdown => estimate_text_height(&props.markdown_source.text(), props.markdown_text_size),
+        WidgetType::Image | WidgetType::Svg | WidgetType::QRCode => MEDIA_HEIGHT,
+        _ => CONTROL_HEIGHT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::type_system::TypeSystem;
+
+    fn text_child(hierarchy: &mut WidgetHierarchy, parent: WidgetId, content: &str, size: f32) -> WidgetId {
+        let type_system = TypeSystem::new();
+        let id = hierarchy.add_child(parent, WidgetType::Text).unwrap();
+        hierarchy.apply_property_change(id, PropertyChange::TextContent(content.to_string()), &type_system);
+        hierarchy.apply_property_change(id, PropertyChange::TextSize(size), &type_system);
+        id
+    }
+
+    fn mark(hierarchy: &mut WidgetHierarchy, id: WidgetId, name: &str) {
+        let type_system = TypeSystem::new();
+        hierarchy.apply_property_change(id, PropertyChange::SectionAnchor(true), &type_system);
+        hierarchy.apply_property_change(id, PropertyChange::SectionName(name.to_string()), &type_system);
+    }
+
+    #[test]
+    fn test_column_height_adds_children_spacing_and_padding() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        hierarchy.apply_property_change(column_id, PropertyChange::Spacing(10.0), &type_system);
+        hierarchy.apply_property_change(column_id, PropertyChange::PaddingUniform(5.0), &type_system);
+        text_child(&mut hierarchy, column_id, "one", 20.0);
+        text_child(&mut hierarchy, column_id, "two\nlines", 20.0);
+
+        let height = estimate_height(hierarchy.get_widget_by_id(column_id).unwrap());
+        // 26 + 52 of text, one 10 gap, 5 above and below
+        assert!((height - 98.0).abs() < 0.01, "{}", height);
+    }
+
+    #[test]
+    fn test_fixed_height_wins() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let id = text_child(&mut hierarchy, WidgetId(0), "tall", 20.0);
+        hierarchy.apply_property_change(id, PropertyChange::Height(Length::Fixed(240.0)), &type_system);
+        assert_eq!(estimate_height(hierarchy.get_widget_by_id(id).unwrap()), 240.0);
+    }
+
+    #[test]
+    fn test_section_offsets_and_constants() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        hierarchy.apply_property_change(column_id, PropertyChange::Spacing(10.0), &type_system);
+        let intro = text_child(&mut hierarchy, column_id, "Intro", 20.0);
+        let filler = text_child(&mut hierarchy, column_id, "Filler", 20.0);
+        hierarchy.apply_property_change(filler, PropertyChange::Height(Length::Fixed(300.0)), &type_system);
+        let details = text_child(&mut hierarchy, column_id, "Details", 20.0);
+        let again = text_child(&mut hierarchy, column_id, "Again", 20.0);
+        mark(&mut hierarchy, intro, "Getting started");
+        mark(&mut hierarchy, details, "Details!");
+        mark(&mut hierarchy, again, "details");
+
+        let sections = sections(hierarchy.root());
+        let constants: Vec<&str> = sections.iter().map(|s| s.constant.as_str()).collect();
+        assert_eq!(constants, ["SECTION_GETTING_STARTED", "SECTION_DETAILS", "SECTION_DETAILS_2"]);
+        assert_eq!(sections[0].offset, hierarchy.root().properties.padding.top);
+        // The intro text and the fixed filler, each followed by the 10px spacing
+        let expected = sections[0].offset + 26.0 + 10.0 + 300.0 + 10.0;
+        assert!((sections[1].offset - expected).abs() < 0.01, "{} vs {}", sections[1].offset, expected);
+    }
+
+    #[test]
+    fn test_marks_outside_a_column_are_ignored() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let row_id = hierarchy.add_child(WidgetId(0), WidgetType::Row).unwrap();
+        let id = text_child(&mut hierarchy, row_id, "In a row", 16.0);
+        mark(&mut hierarchy, id, "Nope");
+        assert!(sections(hierarchy.root()).is_empty());
+    }
+}
@@ -65,4 +65,34 @@ pub fn error_box(theme: &Theme) -> Style {
         },
         ..Default::default()
     }
-}
\ No newline at end of file
+}
+/// Tree row of the primary selection, the one single-target actions apply to
+pub fn primary_selection(theme: &Theme) -> Style {
+    let palette = theme.extended_palette();
+
+    Style {
+        background: Some(Background::Color(palette.primary.weak.color)),
+        text_color: Some(palette.primary.weak.text),
+        border: Border {
+            color: palette.primary.strong.color,
+            width: 2.0,
+            radius: 5.0.into(),
+        },
+        ..Default::default()
+    }
+}
+
+/// Tree row that is selected alongside the primary one
+pub fn secondary_selection(theme: &Theme) -> Style {
+    let palette = theme.extended_palette();
+
+    Style {
+        background: Some(Background::Color(palette.primary.weak.color.scale_alpha(0.4))),
+        border: Border {
+            color: Color::TRANSPARENT,
+            width: 2.0,
+            radius: 5.0.into(),
+        },
+        ..Default::default()
+    }
+}
@@ -1,9 +1,10 @@
-use iced::widget::{button, checkbox, column, container, rule::horizontal as horizontal_rule, space::horizontal as horizontal_space, slider, row, scrollable, text, text_input, Space};
+use iced::widget::{button, checkbox, column, container, pick_list, rule::horizontal as horizontal_rule, space::horizontal as horizontal_space, slider, row, scrollable, text, text_input, Space};
 use iced::Length::FillPortion;
 use iced::{Alignment, Background, Border, Color, Element, Length, Shadow, Theme, Padding, Task,};
 use std::collections::BTreeMap;
 use widgets::color_picker;
 use crate::widget::generic_overlay::{overlay_button, OverlayButton};
+use crate::widget_helper::color_names;
 
 
 
@@ -27,6 +28,108 @@ impl std::fmt::Display for ThemePaneEnum {
     }
 }
 
+/// Extended palette colors a custom style can follow instead of a fixed color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteSlot {
+    BackgroundBase,
+    BackgroundWeak,
+    BackgroundStrong,
+    PrimaryBase,
+    PrimaryStrong,
+    PrimaryWeak,
+    SuccessBase,
+    WarningBase,
+    DangerBase,
+    Text,
+}
+
+impl PaletteSlot {
+    pub const ALL: [PaletteSlot; 10] = [
+        PaletteSlot::BackgroundBase,
+        PaletteSlot::BackgroundWeak,
+        PaletteSlot::BackgroundStrong,
+        PaletteSlot::PrimaryBase,
+        PaletteSlot::PrimaryStrong,
+        PaletteSlot::PrimaryWeak,
+        PaletteSlot::SuccessBase,
+        PaletteSlot::WarningBase,
+        PaletteSlot::DangerBase,
+        PaletteSlot::Text,
+    ];
+
+    /// Field path under `theme.extended_palette()`
+    pub fn path(self) -> &'static str {
+        match self {
+            PaletteSlot::BackgroundBase => "background.base.color",
+            PaletteSlot::BackgroundWeak => "background.weak.color",
+            PaletteSlot::BackgroundStrong => "background.strong.color",
+            PaletteSlot::PrimaryBase => "primary.base.color",
+            PaletteSlot::PrimaryStrong => "primary.strong.color",
+            PaletteSlot::PrimaryWeak => "primary.weak.color",
+            PaletteSlot::SuccessBase => "success.base.color",
+            PaletteSlot::WarningBase => "warning.base.color",
+            PaletteSlot::DangerBase => "danger.base.color",
+            PaletteSlot::Text => "background.base.text",
+        }
+    }
+
+    pub fn resolve(self, theme: &Theme) -> Color {
+        let palette = theme.extended_palette();
+        match self {
+            PaletteSlot::BackgroundBase => palette.background.base.color,
+            PaletteSlot::BackgroundWeak => palette.background.weak.color,
+            PaletteSlot::BackgroundStrong => palette.background.strong.color,
+            PaletteSlot::PrimaryBase => palette.primary.base.color,
+            PaletteSlot::PrimaryStrong => palette.primary.strong.color,
+            PaletteSlot::PrimaryWeak => palette.primary.weak.color,
+            PaletteSlot::SuccessBase => palette.success.base.color,
+            PaletteSlot::WarningBase => palette.warning.base.color,
+            PaletteSlot::DangerBase => palette.danger.base.color,
+            PaletteSlot::Text => palette.background.base.text,
+        }
+    }
+}
+
+/// A custom style color, either fixed or read from the theme so it follows theme switches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StyleColor {
+    Literal(Color),
+    Palette(PaletteSlot),
+}
+
+impl StyleColor {
+    pub fn resolve(self, theme: &Theme) -> Color {
+        match self {
+            StyleColor::Literal(color) => color,
+            StyleColor::Palette(slot) => slot.resolve(theme),
+        }
+    }
+}
+
+impl std::fmt::Display for StyleColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StyleColor::Literal(_) => write!(f, "Literal color"),
+            StyleColor::Palette(slot) => write!(f, "{}", slot.path().trim_end_matches(".color")),
+        }
+    }
+}
+
+/// Hex and nearest color name under a swatch, so a color can be referred to out loud
+fn color_caption<'a>(color: Color) -> Element<'a, Message> {
+    match color_names::caption(color) {
+        Some(caption) => text(caption).size(11).into(),
+        None => Space::new().into(),
+    }
+}
+
+/// Palette slot name with the caption of its color, for the palette swatches
+fn swatch_label<'a>(label: &'a str, color: Color) -> Element<'a, Message> {
+    column![text(label), color_caption(color)]
+        .align_x(Alignment::Center)
+        .into()
+}
+
 /// StyleFn Builders
 
 /// Function to build a custom container style in app
@@ -56,16 +159,16 @@ pub struct CustomThemes {
 
     // Container
     container_styles: BTreeMap<usize, iced::widget::container::Style>,
-    container_text_color: Color,
-    container_border_color: Color,
+    container_text_color: StyleColor,
+    container_border_color: StyleColor,
     container_border_width: f32,
     container_border_radius_top_left: f32,
     container_border_radius_top_right: f32,
     container_border_radius_bottom_right: f32,
     container_border_radius_bottom_left: f32,
-    container_background_color: Color,
+    container_background_color: StyleColor,
     container_shadow_enabled: bool,
-    container_shadow_color: Color,
+    container_shadow_color: StyleColor,
     container_shadow_offset_x: f32,
     container_shadow_offset_y: f32,
     container_shadow_blur_radius: f32,
@@ -84,16 +187,16 @@ impl CustomThemes {
 
             // Container
             container_styles: BTreeMap::new(),
-            container_text_color: palette.background.base.text,
-            container_border_color: palette.background.strong.color,
+            container_text_color: StyleColor::Literal(palette.background.base.text),
+            container_border_color: StyleColor::Literal(palette.background.strong.color),
             container_border_width: 0.0,
             container_border_radius_top_left: 0.0,
             container_border_radius_top_right: 0.0,
             container_border_radius_bottom_right: 0.0,
             container_border_radius_bottom_left: 0.0,
-            container_background_color: palette.background.base.color,
+            container_background_color: StyleColor::Literal(palette.background.base.color),
             container_shadow_enabled: false,
-            container_shadow_color: palette.background.weak.color,
+            container_shadow_color: StyleColor::Literal(palette.background.weak.color),
             container_shadow_offset_x: 0.0,
             container_shadow_offset_y: 0.0,
             container_shadow_blur_radius: 0.0,
@@ -127,10 +230,10 @@ impl CustomThemes {
 
             Message::SaveContainerStyle => {
                 let style = container_stylefn_builder(
-                    self.container_text_color,
-                    Background::Color(self.container_background_color),
+                    self.resolved(self.container_text_color),
+                    Background::Color(self.resolved(self.container_background_color)),
                     Border {
-                        color: self.container_border_color,
+                        color: self.resolved(self.container_border_color),
                         width: self.container_border_width,
                         radius: iced::border::Radius {
                             top_left: self.container_border_radius_top_left,
@@ -141,7 +244,7 @@ impl CustomThemes {
                     },
                     if self.container_shadow_enabled {
                         Shadow {
-                            color: self.container_shadow_color,
+                            color: self.resolved(self.container_shadow_color),
                             offset: iced::Vector {
                                 x: self.container_shadow_offset_x,
                                 y: self.container_shadow_offset_y,
@@ -163,12 +266,12 @@ impl CustomThemes {
                 if let Some(style) = self.container_styles.get(&id) {
                     // Extract properties from the saved style
                     if let Some(text_color) = style.text_color {
-                        self.container_text_color = text_color;
+                        self.container_text_color = StyleColor::Literal(text_color);
                     }
                     if let Some(Background::Color(bg_color)) = style.background {
-                        self.container_background_color = bg_color;
+                        self.container_background_color = StyleColor::Literal(bg_color);
                     }
-                    self.container_border_color = style.border.color;
+                    self.container_border_color = StyleColor::Literal(style.border.color);
                     self.container_border_width = style.border.width;
                     self.container_border_radius_top_left = style.border.radius.top_left;
                     self.container_border_radius_top_right = style.border.radius.top_right;
@@ -176,7 +279,7 @@ impl CustomThemes {
                     self.container_border_radius_bottom_left = style.border.radius.bottom_left;
                     self.container_shadow_enabled = style.shadow.color.a > 0.0;
                     if self.container_shadow_enabled {
-                        self.container_shadow_color = style.shadow.color;
+                        self.container_shadow_color = StyleColor::Literal(style.shadow.color);
                         self.container_shadow_offset_x = style.shadow.offset.x;
                         self.container_shadow_offset_y = style.shadow.offset.y;
                         self.container_shadow_blur_radius = style.shadow.blur_radius;
@@ -187,16 +290,16 @@ impl CustomThemes {
 
             Message::ResetToTheme => {
                 let palette = self.theme.extended_palette();
-                self.container_text_color = palette.background.base.text;
-                self.container_border_color = palette.background.strong.color;
+                self.container_text_color = StyleColor::Literal(palette.background.base.text);
+                self.container_border_color = StyleColor::Literal(palette.background.strong.color);
                 self.container_border_width = 0.0;
                 self.container_border_radius_top_left = 0.0;
                 self.container_border_radius_top_right = 0.0;
                 self.container_border_radius_bottom_right = 0.0;
                 self.container_border_radius_bottom_left = 0.0;
-                self.container_background_color = palette.background.base.color;
+                self.container_background_color = StyleColor::Literal(palette.background.base.color);
                 self.container_shadow_enabled = false;
-                self.container_shadow_color = palette.background.weak.color;
+                self.container_shadow_color = StyleColor::Literal(palette.background.weak.color);
                 self.container_shadow_offset_x = 0.0;
                 self.container_shadow_offset_y = 0.0;
                 self.container_shadow_blur_radius = 0.0;
@@ -206,6 +309,21 @@ impl CustomThemes {
         Task::none()
     }
 
+    fn resolved(&self, color: StyleColor) -> Color {
+        color.resolve(&self.theme)
+    }
+
+    /// Picks between a literal color and an extended palette slot, so generated code can follow the theme
+    fn color_source_picker<'a>(&self, current: StyleColor, on_select: fn(StyleColor) -> Message) -> Element<'a, Message> {
+        let options: Vec<StyleColor> = std::iter::once(StyleColor::Literal(self.resolved(current)))
+            .chain(PaletteSlot::ALL.into_iter().map(StyleColor::Palette))
+            .collect();
+        pick_list(options, Some(current), on_select)
+            .text_size(12)
+            .width(Length::Fill)
+            .into()
+    }
+
     pub fn view(&self) -> Element<Message> {
         let content = match self.selected_view {
             ThemePaneEnum::ExtendedPalette => self.show_theme_colors(&self.theme),
@@ -250,26 +368,30 @@ impl CustomThemes {
                 column![
                     container(text("text_color").size(16)).center_x(Length::Fill),
                     color_picker::ColorButton::new(
-                        self.container_text_color, 
-                        |color| Message::UpdateContainerTextColor(color)
+                        self.resolved(self.container_text_color), 
+                        |color| Message::UpdateContainerTextColor(StyleColor::Literal(color))
                     )
                     .title("text_color")
                     .width(Length::Fill)
                     .height(Length::Fixed(50.0))
                     .show_hex(),
+                    color_caption(self.resolved(self.container_text_color)),
+                    self.color_source_picker(self.container_text_color, Message::UpdateContainerTextColor),
                 ]
                 .width(Length::FillPortion(1)),
 
                 column![
                     container(text("background color").size(16)).center_x(Length::Fill),
                     color_picker::ColorButton::new(
-                        self.container_background_color, 
-                        |color| Message::UpdateContainerBackgroundColor(color)
+                        self.resolved(self.container_background_color), 
+                        |color| Message::UpdateContainerBackgroundColor(StyleColor::Literal(color))
                     )
                     .title("background color")
                     .width(Length::Fill)
                     .height(Length::Fixed(50.0))
                     .show_hex(),
+                    color_caption(self.resolved(self.container_background_color)),
+                    self.color_source_picker(self.container_background_color, Message::UpdateContainerBackgroundColor),
                 ]
                 .width(Length::FillPortion(1)),
             ].spacing(10),
@@ -291,13 +413,15 @@ impl CustomThemes {
                     column![
                         container(text("border color").size(16)).center_x(Length::Fill),
                         color_picker::ColorButton::new(
-                            self.container_border_color, 
-                            |color| Message::UpdateContainerBorderColor(color)
+                            self.resolved(self.container_border_color), 
+                            |color| Message::UpdateContainerBorderColor(StyleColor::Literal(color))
                         )
                         .title("border color")
                         .width(Length::Fill)
                         .height(Length::Fixed(50.0))
                         .show_hex(),
+                        color_caption(self.resolved(self.container_border_color)),
+                        self.color_source_picker(self.container_border_color, Message::UpdateContainerBorderColor),
                     ]
                     .width(Length::FillPortion(1)),
                 ].spacing(10).align_y(Alignment::Center),
@@ -365,13 +489,15 @@ impl CustomThemes {
                             container(text("shadow color").size(16)).center_x(Length::Fill),
 
                             color_picker::ColorButton::new(
-                                self.container_shadow_color, 
-                                |color| Message::UpdateContainerShadowColor(color)
+                                self.resolved(self.container_shadow_color), 
+                                |color| Message::UpdateContainerShadowColor(StyleColor::Literal(color))
                             )
                             .title("shadow color")
                             .width(Length::Fill)
                             .height(Length::Fixed(50.0))
-                            .show_hex()
+                            .show_hex(),
+                            color_caption(self.resolved(self.container_shadow_color)),
+                            self.color_source_picker(self.container_shadow_color, Message::UpdateContainerShadowColor)
                         ]
                         .width(Length::FillPortion(1))
                     } else { column![].width(Length::FillPortion(1)) }
@@ -488,10 +614,10 @@ impl CustomThemes {
         )
         .style(move |_: &Theme| {
             container_stylefn_builder(
-                self.container_text_color,
-                Background::Color(self.container_background_color),
+                self.resolved(self.container_text_color),
+                Background::Color(self.resolved(self.container_background_color)),
                 Border {
-                    color: self.container_border_color,
+                    color: self.resolved(self.container_border_color),
                     width: self.container_border_width,
                     radius: iced::border::Radius {
                         top_left: self.container_border_radius_top_left,
@@ -502,7 +628,7 @@ impl CustomThemes {
                 },
                 if self.container_shadow_enabled {
                     Shadow {
-                        color: self.container_shadow_color,
+                        color: self.resolved(self.container_shadow_color),
                         offset: iced::Vector {
                             x: self.container_shadow_offset_x,
                             y: self.container_shadow_offset_y,
@@ -606,7 +732,7 @@ impl CustomThemes {
                 container(text("Palette").size(24).color(palette.background.strong.text)).center(Length::Fill),
                 row![
                     container(
-                        text("Background").center(),
+                        swatch_label("Background", base.background),
                     ).style(move |_: &Theme| container::Style { 
                         text_color: Some(base.text), 
                         background: Some( Background::Color(base.background)),
@@ -619,7 +745,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Text").center(),
+                        swatch_label("Text", base.text),
                     ).style(move |_: &Theme| container::Style { 
                         text_color: Some(base.background), 
                         background: Some( Background::Color(base.text)), 
@@ -634,7 +760,7 @@ impl CustomThemes {
 
                 row![
                     container(
-                        text("Primary").center(),
+                        swatch_label("Primary", base.primary),
                     ).style(move |_: &Theme| container::Style { 
                         text_color: Some(base.background), 
                         background: Some( Background::Color(base.primary)),
@@ -646,7 +772,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Success").center(),
+                        swatch_label("Success", base.success),
                     ).style(move |_: &Theme| container::Style { 
                         text_color: Some(base.background), 
                         background: Some( Background::Color(base.success)),
@@ -661,7 +787,7 @@ impl CustomThemes {
 
                 row![
                     container(
-                        text("Warning").center(),
+                        swatch_label("Warning", base.warning),
                     ).style(move |_: &Theme| container::Style { 
                         text_color: Some(base.background), 
                         background: Some( Background::Color(base.warning)),
@@ -673,7 +799,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Danger").center(),
+                        swatch_label("Danger", base.danger),
                     ).style(move |_: &Theme| container::Style { 
                         text_color: Some(base.background), 
                         background: Some( Background::Color(base.danger)),
@@ -703,7 +829,7 @@ impl CustomThemes {
                 column![
                     row![
                         container(
-                            text("Base").center(),
+                            swatch_label("Base", palette.background.base.color),
                         ).style(|_| container::Style { 
                             text_color: Some(palette.background.base.text), 
                             background: Some( Background::Color(palette.background.base.color)),
@@ -716,7 +842,7 @@ impl CustomThemes {
                         .height(Length::Fixed(50.0)),
 
                         container(
-                            text("Neutral").center(),
+                            swatch_label("Neutral", palette.background.neutral.color),
                         ).style(|_| container::Style { 
                             text_color: Some(palette.background.neutral.text), 
                             background: Some( Background::Color(palette.background.neutral.color)
@@ -731,7 +857,7 @@ impl CustomThemes {
 
                     row![
                         container(
-                            text("Weak").center(),
+                            swatch_label("Weak", palette.background.weak.color),
                         ).style(|_| container::Style { 
                             text_color: Some(palette.background.weak.text), 
                             background: Some( Background::Color(palette.background.weak.color)
@@ -744,7 +870,7 @@ impl CustomThemes {
                         .height(Length::Fixed(50.0)),
 
                         container(
-                            text("Weaker").center(),
+                            swatch_label("Weaker", palette.background.weaker.color),
                         ).style(|_| container::Style { 
                             text_color: Some(palette.background.weaker.text), 
                             background: Some( Background::Color(palette.background.weaker.color)
@@ -757,7 +883,7 @@ impl CustomThemes {
                         .height(Length::Fixed(50.0)),
 
                         container(
-                            text("Weakest").center(),
+                            swatch_label("Weakest", palette.background.weakest.color),
                         ).style(|_| container::Style { 
                             text_color: Some(palette.background.weakest.text), 
                             background: Some( Background::Color(palette.background.weakest.color)
@@ -772,7 +898,7 @@ impl CustomThemes {
 
                     row![
                         container(
-                            text("Strong").center(),
+                            swatch_label("Strong", palette.background.strong.color),
                         ).style(|_| container::Style { 
                             text_color: Some(palette.background.strong.text), 
                             background: Some( Background::Color(palette.background.strong.color)
@@ -785,7 +911,7 @@ impl CustomThemes {
                         .height(Length::Fixed(50.0)),
 
                         container(
-                            text("Stronger").center(),
+                            swatch_label("Stronger", palette.background.stronger.color),
                         ).style(|_| container::Style { 
                             text_color: Some(palette.background.stronger.text), 
                             background: Some( Background::Color(palette.background.stronger.color)
@@ -798,7 +924,7 @@ impl CustomThemes {
                         .height(Length::Fixed(50.0)),
 
                         container(
-                            text("Strongest").center(),
+                            swatch_label("Strongest", palette.background.strongest.color),
                         ).style(|_| container::Style { 
                             text_color: Some(palette.background.strongest.text), 
                             background: Some( Background::Color(palette.background.strongest.color)
@@ -817,7 +943,7 @@ impl CustomThemes {
                 container(text("Primary").size(16).color(palette.background.base.text)).center(Length::Fill),
                 row![
                     container(
-                        text("Base").center(),
+                        swatch_label("Base", palette.primary.base.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.primary.base.text), 
                         background: Some( Background::Color(palette.primary.base.color)
@@ -830,7 +956,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Weak").center(),
+                        swatch_label("Weak", palette.primary.weak.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.primary.weak.text), 
                         background: Some( Background::Color(palette.primary.weak.color)
@@ -843,7 +969,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Strong").center(),
+                        swatch_label("Strong", palette.primary.strong.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.primary.strong.text), 
                         background: Some( Background::Color(palette.primary.strong.color)
@@ -860,7 +986,7 @@ impl CustomThemes {
                 container(text("Secondary").size(16).color(palette.background.base.text)).center(Length::Fill),
                 row![
                     container(
-                        text("Base").center(),
+                        swatch_label("Base", palette.secondary.base.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.secondary.base.text), 
                         background: Some( Background::Color(palette.secondary.base.color)
@@ -873,7 +999,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Weak").center(),
+                        swatch_label("Weak", palette.secondary.weak.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.secondary.weak.text), 
                         background: Some( Background::Color(palette.secondary.weak.color)
@@ -886,7 +1012,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Strong").center(),
+                        swatch_label("Strong", palette.secondary.strong.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.secondary.strong.text), 
                         background: Some( Background::Color(palette.secondary.strong.color)
@@ -903,7 +1029,7 @@ impl CustomThemes {
                 container(text("Success").size(16).color(palette.background.base.text)).center(Length::Fill),
                 row![
                     container(
-                        text("Base").center(),
+                        swatch_label("Base", palette.success.base.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.success.base.text), 
                         background: Some( Background::Color(palette.success.base.color)
@@ -916,7 +1042,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Weak").center(),
+                        swatch_label("Weak", palette.success.weak.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.success.weak.text), 
                         background: Some( Background::Color(palette.success.weak.color)
@@ -929,7 +1055,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Strong").center(),
+                        swatch_label("Strong", palette.success.strong.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.success.strong.text), 
                         background: Some( Background::Color(palette.success.strong.color)
@@ -946,7 +1072,7 @@ impl CustomThemes {
                 container(text("Warning").size(16).color(palette.background.base.text)).center(Length::Fill),
                 row![
                     container(
-                        text("Base").center(),
+                        swatch_label("Base", palette.warning.base.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.warning.base.text), 
                         background: Some( Background::Color(palette.warning.base.color)
@@ -959,7 +1085,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Weak").center(),
+                        swatch_label("Weak", palette.warning.weak.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.warning.weak.text), 
                         background: Some( Background::Color(palette.warning.weak.color)
@@ -972,7 +1098,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Strong").center(),
+                        swatch_label("Strong", palette.warning.strong.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.warning.strong.text), 
                         background: Some( Background::Color(palette.warning.strong.color)
@@ -989,7 +1115,7 @@ impl CustomThemes {
                 container(text("Danger").size(16).color(palette.background.base.text)).center(Length::Fill),
                 row![
                     container(
-                        text("Base").center(),
+                        swatch_label("Base", palette.danger.base.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.danger.base.text), 
                         background: Some( Background::Color(palette.danger.base.color)
@@ -1002,7 +1128,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Weak").center(),
+                        swatch_label("Weak", palette.danger.weak.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.danger.weak.text), 
                         background: Some( Background::Color(palette.danger.weak.color)
@@ -1015,7 +1141,7 @@ impl CustomThemes {
                     .height(Length::Fixed(50.0)),
 
                     container(
-                        text("Strong").center(),
+                        swatch_label("Strong", palette.danger.strong.color),
                     ).style(|_| container::Style { 
                         text_color: Some(palette.danger.strong.text), 
                         background: Some( Background::Color(palette.danger.strong.color)
@@ -1078,16 +1204,16 @@ pub enum Message {
 
     // Containers
     SaveCustomContainerStyle(usize, iced::widget::container::Style),
-    UpdateContainerTextColor(Color),
-    UpdateContainerBorderColor(Color),
+    UpdateContainerTextColor(StyleColor),
+    UpdateContainerBorderColor(StyleColor),
     UpdateContainerBorderWidth(f32),
     UpdateContainerBorderRadiusTopLeft(f32),
     UpdateContainerBorderRadiusTopRight(f32),
     UpdateContainerBorderRadiusBottomRight(f32),
     UpdateContainerBorderRadiusBottomLeft(f32),
-    UpdateContainerBackgroundColor(Color),
+    UpdateContainerBackgroundColor(StyleColor),
     UpdateContainerShadowEnabled(bool),
-    UpdateContainerShadowColor(Color),
+    UpdateContainerShadowColor(StyleColor),
     UpdateContainerShadowOffsetX(f32),
     UpdateContainerShadowOffsetY(f32),
     UpdateContainerShadowBlurRadius(f32),
@@ -0,0 +1,140 @@
+use std::collections::{BTreeMap, HashSet};
+use iced::Color;
+use crate::widget_helper::{Widget, WidgetId};
+
+/// Suggestions listed under the tag input
+const MAX_SUGGESTIONS: usize = 6;
+
+/// A tag as typed, trimmed and with inner runs of whitespace turned into single dashes.
+/// None for a blank one.
+pub fn normalize(tag: &str) -> Option<String> {
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join("-");
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Adds `tag` unless the list has it already. Returns whether the list changed.
+pub fn add(tags: &mut Vec<String>, tag: &str) -> bool {
+    match normalize(tag) {
+        Some(tag) if !tags.contains(&tag) => {
+            tags.push(tag);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Chip color of a tag, the same for the same name in every session and project
+pub fn color(tag: &str) -> Color {
+    // FNV-1a, so the hue doesn't depend on the std hasher's random seed
+    let hash = tag.bytes().fold(0x811c9dc5u32, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193));
+    let hue = (hash % 360) as f32;
+    hsl(hue, 0.55, 0.45)
+}
+
+fn hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    Color::from_rgb(r + m, g + m, b + m)
+}
+
+/// A project tag with how many widgets carry it, as the tree's filter lists it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+impl std::fmt::Display for TagCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.tag, self.count)
+    }
+}
+
+/// Project tags that complete `draft`, leaving out the ones in `existing`. Tags starting
+/// with the draft come before tags that only contain it.
+pub fn suggestions(known: &BTreeMap<String, usize>, draft: &str, existing: &[String]) -> Vec<String> {
+    let draft = draft.trim().to_lowercase();
+    let mut matches: Vec<&String> = known.keys()
+        .filter(|tag| !existing.contains(tag))
+        .filter(|tag| tag.to_lowercase().contains(&draft))
+        .collect();
+    matches.sort_by_key(|tag| !tag.to_lowercase().starts_with(&draft));
+    matches.into_iter().take(MAX_SUGGESTIONS).cloned().collect()
+}
+
+/// Widgets the tree shows while filtering on `tag`: the ones carrying it, and their
+/// ancestors so each still sits where it is in the layout
+pub fn visible_ids(root: &Widget, tag: &str) -> HashSet<WidgetId> {
+    let mut visible = HashSet::new();
+    collect_visible(root, tag, &mut visible);
+    visible.insert(root.id);
+    visible
+}
+
+fn collect_visible(widget: &Widget, tag: &str, visible: &mut HashSet<WidgetId>) -> bool {
+    let mut shown = widget.properties.tags.iter().any(|t| t == tag);
+    for child in &widget.children {
+        shown |= collect_visible(child, tag, visible);
+    }
+    if shown {
+        visible.insert(widget.id);
+    }
+    shown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::{PropertyChange, WidgetHierarchy, WidgetType};
+    use crate::widget_helper::type_system::TypeSystem;
+
+    fn tag(hierarchy: &mut WidgetHierarchy, id: WidgetId, name: &str) {
+        hierarchy.apply_property_change(id, PropertyChange::AddTag(name.to_string()), &TypeSystem::new());
+    }
+
+    #[test]
+    fn test_tags_are_normalized_and_not_repeated() {
+        let mut tags = Vec::new();
+        assert!(add(&mut tags, "  needs  review "));
+        assert!(!add(&mut tags, "needs review"));
+        assert!(!add(&mut tags, "   "));
+        assert_eq!(tags, ["needs-review"]);
+    }
+
+    #[test]
+    fn test_chip_color_is_stable() {
+        assert_eq!(color("header"), color("header"));
+        assert_ne!(color("header"), color("footer"));
+    }
+
+    #[test]
+    fn test_filter_keeps_ancestors_of_tagged_widgets() {
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let tagged_id = hierarchy.add_child(row_id, WidgetType::Text).unwrap();
+        let sibling_id = hierarchy.add_child(row_id, WidgetType::Text).unwrap();
+        let other_id = hierarchy.add_child(column_id, WidgetType::Button).unwrap();
+        tag(&mut hierarchy, tagged_id, "todo");
+        tag(&mut hierarchy, other_id, "header");
+
+        let visible = visible_ids(hierarchy.root(), "todo");
+        assert_eq!(visible, HashSet::from([WidgetId(0), column_id, row_id, tagged_id]));
+        assert!(!visible.contains(&sibling_id));
+
+        let counts = hierarchy.stats().by_tag;
+        assert_eq!(counts.get("todo"), Some(&1));
+        assert_eq!(suggestions(&counts, "D", &[]), ["header", "todo"]);
+        assert_eq!(suggestions(&counts, "h", &["header".to_string()]), Vec::<String>::new());
+    }
+}
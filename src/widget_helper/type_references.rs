@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::widget_helper::*;
+
+/// A widget bound to a type, listed under that type in the type editor
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeReference {
+    pub widget_id: WidgetId,
+    pub widget_type: WidgetType,
+    /// Widget names from the root down, "App > Form > Size"
+    pub path: String,
+}
+
+/// Which widgets point at which enum. Rebuilt when the tree changes, not every frame.
+#[derive(Debug, Clone, Default)]
+pub struct TypeReferences {
+    by_enum: HashMap<Uuid, Vec<TypeReference>>,
+}
+
+impl TypeReferences {
+    pub fn rebuild(&mut self, root: &Widget) {
+        fn walk(widget: &Widget, path: &str, by_enum: &mut HashMap<Uuid, Vec<TypeReference>>) {
+            let path = if path.is_empty() { widget.name.clone() } else { format!("{} > {}", path, widget.name) };
+            if let Some(enum_id) = widget.properties.referenced_enum {
+                by_enum.entry(enum_id).or_default().push(TypeReference {
+                    widget_id: widget.id,
+                    widget_type: widget.widget_type,
+                    path: path.clone(),
+                });
+            }
+            for child in &widget.children {
+                walk(child, &path, by_enum);
+            }
+        }
+
+        self.by_enum.clear();
+        walk(root, "", &mut self.by_enum);
+    }
+
+    /// Widgets bound to the enum, in tree order
+    pub fn of_enum(&self, enum_id: Uuid) -> &[TypeReference] {
+        self.by_enum.get(&enum_id).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_unused(&self, enum_id: Uuid) -> bool {
+        self.of_enum(enum_id).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget_helper::type_system::TypeSystem;
+
+    fn bind(hierarchy: &mut WidgetHierarchy, id: WidgetId, enum_id: Uuid, type_system: &TypeSystem) {
+        hierarchy.apply_property_change(id, PropertyChange::ComboBoxEnumId(Some(enum_id)), type_system);
+    }
+
+    #[test]
+    fn test_references_are_found_at_any_depth() {
+        let mut type_system = TypeSystem::new();
+        let size = type_system.add_enum("Size".to_string(), vec!["Small".to_string()]).unwrap();
+        let shape = type_system.add_enum("Shape".to_string(), vec!["Round".to_string()]).unwrap();
+
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let row_id = hierarchy.add_child(column_id, WidgetType::Row).unwrap();
+        let top = hierarchy.add_child(column_id, WidgetType::ComboBox).unwrap();
+        let nested = hierarchy.add_child(row_id, WidgetType::ComboBox).unwrap();
+        bind(&mut hierarchy, top, size, &type_system);
+        bind(&mut hierarchy, nested, size, &type_system);
+
+        let mut references = TypeReferences::default();
+        references.rebuild(hierarchy.root());
+
+        let ids: Vec<WidgetId> = references.of_enum(size).iter().map(|r| r.widget_id).collect();
+        assert_eq!(ids, [nested, top]);
+        let nested_ref = &references.of_enum(size)[0];
+        assert_eq!(nested_ref.widget_type, WidgetType::ComboBox);
+        assert_eq!(nested_ref.path.matches(" > ").count(), 3);
+        assert!(references.is_unused(shape));
+    }
+
+    #[test]
+    fn test_deleting_a_widget_drops_its_reference() {
+        let mut type_system = TypeSystem::new();
+        let size = type_system.add_enum("Size".to_string(), vec!["Small".to_string()]).unwrap();
+
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Container);
+        let column_id = hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+        let combo = hierarchy.add_child(column_id, WidgetType::ComboBox).unwrap();
+        bind(&mut hierarchy, combo, size, &type_system);
+
+        let mut references = TypeReferences::default();
+        references.rebuild(hierarchy.root());
+        assert_eq!(references.of_enum(size).len(), 1);
+
+        hierarchy.delete_widget(column_id).unwrap();
+        references.rebuild(hierarchy.root());
+        assert!(references.is_unused(size));
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::time::Instant;
+
+use crate::widget_helper::*;
+use crate::widget_helper::history::COALESCE_WINDOW;
+
+/// Steps kept on the stack, the oldest is dropped first
+pub const MAX_UNDO_STEPS: usize = 100;
+
+/// The whole tree as it was after an operation, with what the operation was
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    /// What the operation did, e.g. "Deleted Row 'toolbar' (4 widgets)"
+    pub label: String,
+    pub at: Instant,
+    pub root: Widget,
+    pub selected: HashSet<WidgetId>,
+    /// Widget and property of an edit that later edits to the same property extend
+    coalesce: Option<(WidgetId, &'static str)>,
+}
+
+/// Session-only snapshots of the hierarchy, oldest first. Entries past `position`
+/// are the ones undone, dropped by the next recorded operation.
+#[derive(Debug, Clone)]
+pub struct UndoStack {
+    entries: VecDeque<UndoEntry>,
+    position: usize,
+}
+
+impl UndoStack {
+    /// Starts with the hierarchy as it is, so the first operation can be undone
+    pub fn new(hierarchy: &WidgetHierarchy, now: Instant) -> Self {
+        let mut entries = VecDeque::new();
+        entries.push_back(UndoEntry {
+            label: "Opened layout".to_string(),
+            at: now,
+            root: hierarchy.root().clone(),
+            selected: hierarchy.selected_ids().clone(),
+            coalesce: None,
+        });
+        Self { entries, position: 0 }
+    }
+
+    /// Records the hierarchy after an operation. Another edit to the property in
+    /// `coalesce` within the coalescing window replaces the step instead.
+    pub fn record(
+        &mut self,
+        label: String,
+        coalesce: Option<(WidgetId, &'static str)>,
+        hierarchy: &WidgetHierarchy,
+        now: Instant,
+    ) {
+        self.entries.truncate(self.position + 1);
+
+        let entry = UndoEntry {
+            label,
+            at: now,
+            root: hierarchy.root().clone(),
+            selected: hierarchy.selected_ids().clone(),
+            coalesce,
+        };
+
+        // Never folds into the baseline, which has nothing to coalesce with
+        if let Some(last) = self.entries.back_mut()
+            && coalesce.is_some()
+            && last.coalesce == coalesce
+            && now.saturating_duration_since(last.at) <= COALESCE_WINDOW
+        {
+            *last = entry;
+            return;
+        }
+
+        self.entries.push_back(entry);
+        if self.entries.len() > MAX_UNDO_STEPS {
+            self.entries.pop_front();
+        }
+        self.position = self.entries.len() - 1;
+    }
+
+    pub fn entries(&self) -> &VecDeque<UndoEntry> {
+        &self.entries
+    }
+
+    /// Index of the entry the hierarchy currently matches
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn current(&self) -> &UndoEntry {
+        &self.entries[self.position]
+    }
+
+    /// Moves to entry `index`, as that many undos or redos would. None when the index is out of range.
+    pub fn jump(&mut self, index: usize) -> Option<&UndoEntry> {
+        let entry = self.entries.get(index)?;
+        self.position = index;
+        Some(entry)
+    }
+
+    pub fn undo(&mut self) -> Option<&UndoEntry> {
+        let index = self.position.checked_sub(1)?;
+        self.jump(index)
+    }
+
+    pub fn redo(&mut self) -> Option<&UndoEntry> {
+        self.jump(self.position + 1)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.position + 1 < self.entries.len()
+    }
+}
+
+/// "Row 'toolbar'" for a named widget, otherwise just its type
+pub fn describe(widget: &Widget) -> String {
+    if widget.properties.widget_name.is_empty() {
+        format!("{:?}", widget.widget_type)
+    } else {
+        format!("{:?} '{}'", widget.widget_type, widget.properties.widget_name)
+    }
+}
+
+/// Widgets in the tree starting at `widget`, itself included
+pub fn subtree_size(widget: &Widget) -> usize {
+    1 + widget.children.iter().map(subtree_size).sum::<usize>()
+}
+
+/// Widgets of `to` that `from` doesn't have, without their descendants: what undoing a
+/// delete brings back
+pub fn restored_roots(from: &Widget, to: &Widget) -> HashSet<WidgetId> {
+    fn ids(widget: &Widget, found: &mut HashSet<WidgetId>) {
+        found.insert(widget.id);
+        for child in &widget.children {
+            ids(child, found);
+        }
+    }
+    fn walk(widget: &Widget, existing: &HashSet<WidgetId>, restored: &mut HashSet<WidgetId>) {
+        if !existing.contains(&widget.id) {
+            restored.insert(widget.id);
+            return;
+        }
+        for child in &widget.children {
+            walk(child, existing, restored);
+        }
+    }
+
+    let mut existing = HashSet::new();
+    ids(from, &mut existing);
+    let mut restored = HashSet::new();
+    walk(to, &existing, &mut restored);
+    restored
+}
+
+/// Per-type widget counts of `to` against `from`, e.g. "-3 Button, +1 Column"
+pub fn structure_diff(from: &Widget, to: &Widget) -> String {
+    fn count(widget: &Widget, sign: isize, counts: &mut BTreeMap<String, isize>) {
+        *counts.entry(format!("{:?}", widget.widget_type)).or_default() += sign;
+        for child in &widget.children {
+            count(child, sign, counts);
+        }
+    }
+
+    let mut counts = BTreeMap::new();
+    count(from, -1, &mut counts);
+    count(to, 1, &mut counts);
+
+    let changes: Vec<String> = counts.into_iter()
+        .filter(|&(_, delta)| delta != 0)
+        .map(|(widget_type, delta)| format!("{:+} {}", delta, widget_type))
+        .collect();
+    if changes.is_empty() {
+        "No structural change".to_string()
+    } else {
+        changes.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::widget_helper::type_system::TypeSystem;
+
+    fn stack_with_buttons(count: usize) -> (WidgetHierarchy, UndoStack, Instant) {
+        let start = Instant::now();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        let mut stack = UndoStack::new(&hierarchy, start);
+        for i in 0..count {
+            hierarchy.add_child(WidgetId(0), WidgetType::Button).unwrap();
+            stack.record(format!("Added Button {}", i), None, &hierarchy, start + COALESCE_WINDOW * 2 * (i as u32 + 1));
+        }
+        (hierarchy, stack, start)
+    }
+
+    #[test]
+    fn test_jump_matches_repeated_undo_and_redo() {
+        let (_, mut stack, _) = stack_with_buttons(3);
+        assert_eq!(stack.position(), 3);
+
+        assert_eq!(stack.jump(1).unwrap().root.children.len(), 1);
+        assert!(stack.can_undo() && stack.can_redo());
+        let mut stepped = stack.clone();
+        stepped.jump(3);
+        stepped.undo();
+        stepped.undo();
+        assert_eq!(stepped.position(), stack.position());
+
+        assert_eq!(stack.redo().unwrap().root.children.len(), 2);
+        assert!(stack.jump(9).is_none());
+        assert_eq!(stack.position(), 2);
+        stack.jump(0);
+        assert!(!stack.can_undo());
+        assert!(stack.undo().is_none());
+    }
+
+    #[test]
+    fn test_recording_after_a_jump_drops_the_undone_steps() {
+        let (mut hierarchy, mut stack, start) = stack_with_buttons(3);
+        stack.jump(1);
+        hierarchy.add_child(WidgetId(0), WidgetType::Text).unwrap();
+        stack.record("Added Text".to_string(), None, &hierarchy, start + Duration::from_secs(60));
+
+        assert_eq!(stack.entries().len(), 3);
+        assert_eq!(stack.position(), 2);
+        assert!(!stack.can_redo());
+        assert_eq!(stack.current().label, "Added Text");
+    }
+
+    #[test]
+    fn test_stack_is_capped() {
+        let (_, stack, _) = stack_with_buttons(MAX_UNDO_STEPS + 5);
+        assert_eq!(stack.entries().len(), MAX_UNDO_STEPS);
+        assert_eq!(stack.position(), MAX_UNDO_STEPS - 1);
+        // The baseline and the oldest steps went first
+        assert_eq!(stack.entries()[0].label, "Added Button 5");
+    }
+
+    #[test]
+    fn test_edits_to_one_property_coalesce() {
+        let (hierarchy, mut stack, start) = stack_with_buttons(1);
+        let key = Some((WidgetId(1), "padding"));
+        let at = start + Duration::from_secs(10);
+        stack.record("Changed padding on Button".to_string(), key, &hierarchy, at);
+        stack.record("Changed padding on Button".to_string(), key, &hierarchy, at + COALESCE_WINDOW / 2);
+        assert_eq!(stack.entries().len(), 3);
+
+        stack.record("Changed width on Button".to_string(), Some((WidgetId(1), "width")), &hierarchy, at + COALESCE_WINDOW);
+        stack.record("Changed padding on Button".to_string(), key, &hierarchy, at + COALESCE_WINDOW * 4);
+        assert_eq!(stack.entries().len(), 5);
+    }
+
+    #[test]
+    fn test_restore_drops_selections_of_missing_widgets() {
+        let (mut hierarchy, mut stack, _) = stack_with_buttons(3);
+        hierarchy.set_selected_ids([WidgetId(3), WidgetId(1)].into_iter().collect());
+        let entry = stack.jump(1).unwrap();
+        hierarchy.restore(entry.root.clone(), &[WidgetId(3), WidgetId(1)].into_iter().collect());
+
+        assert_eq!(hierarchy.root().children.len(), 1);
+        assert_eq!(hierarchy.selected_ids(), &[WidgetId(1)].into_iter().collect::<HashSet<_>>());
+        // Ids handed out since stay retired, so a new widget can't take over a redo step's id
+        assert_eq!(hierarchy.add_child(WidgetId(0), WidgetType::Text).unwrap(), WidgetId(4));
+    }
+
+    #[test]
+    fn test_undoing_a_delete_selects_the_restored_container() {
+        let mut visualizer = WidgetVisualizer::new();
+        let row = visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Row).unwrap();
+        for _ in 0..3 {
+            visualizer.hierarchy.add_child(row, WidgetType::Button).unwrap();
+        }
+        visualizer.undo.record("Added Row".to_string(), None, &visualizer.hierarchy, Instant::now());
+
+        visualizer.update(Message::DeleteWidget(row));
+        assert!(visualizer.hierarchy.root().children.is_empty());
+        assert!(visualizer.undo.can_undo());
+
+        visualizer.update(Message::Undo);
+        assert_eq!(visualizer.hierarchy.root().children[0].children.len(), 3);
+        assert_eq!(visualizer.hierarchy.selected_ids(), &HashSet::from([row]));
+
+        visualizer.update(Message::Redo);
+        assert!(visualizer.hierarchy.root().children.is_empty());
+        assert!(!visualizer.undo.can_redo());
+        // Nothing to redo leaves the tree alone
+        visualizer.update(Message::Redo);
+        assert!(visualizer.hierarchy.root().children.is_empty());
+    }
+
+    #[test]
+    fn test_structure_diff_counts_per_type() {
+        let mut before = WidgetHierarchy::new(WidgetType::Column);
+        for _ in 0..3 {
+            before.add_child(WidgetId(0), WidgetType::Button).unwrap();
+        }
+        let mut after = WidgetHierarchy::new(WidgetType::Column);
+        after.add_child(WidgetId(0), WidgetType::Column).unwrap();
+
+        assert_eq!(structure_diff(before.root(), after.root()), "-3 Button, +1 Column");
+        assert_eq!(structure_diff(before.root(), before.root()), "No structural change");
+        assert_eq!(before.root(), &before.root().clone());
+        assert_ne!(before.root(), after.root());
+        assert_eq!(subtree_size(before.root()), 4);
+    }
+
+    #[test]
+    fn test_trees_compare_by_what_the_user_set() {
+        let type_system = TypeSystem::new();
+        let mut hierarchy = WidgetHierarchy::new(WidgetType::Column);
+        let markdown_id = hierarchy.add_child(WidgetId(0), WidgetType::Markdown).unwrap();
+        let before = hierarchy.root().clone();
+
+        // Parse state isn't part of the comparison, the source is
+        hierarchy.get_widget_by_id_mut(markdown_id).unwrap().properties.markdown_edited_at = Some(Instant::now());
+        assert_eq!(&before, hierarchy.root());
+        hierarchy.apply_property_change(markdown_id, PropertyChange::MarkdownTextSize(20.0), &type_system);
+        assert_ne!(&before, hierarchy.root());
+        let mut edited = before.clone();
+        edited.children[0].properties.markdown_source = iced::widget::text_editor::Content::with_text("# Title");
+        assert_ne!(before, edited);
+    }
+}
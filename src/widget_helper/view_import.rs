@@ -0,0 +1,650 @@
+use std::collections::HashMap;
+
+use iced::widget::text_editor;
+use iced::{Length, Padding};
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{Expr, Lit, Stmt, Token, UnOp};
+
+use crate::widget_helper::{
+    ButtonStyleType, Orientation, PaddingMode, Properties, WidgetHierarchy, WidgetId, WidgetType,
+};
+
+/// Longest stretch of source quoted back in a warning or placeholder
+const SNIPPET_LENGTH: usize = 60;
+
+/// Modal form the view code is pasted into
+pub struct ViewImportForm {
+    pub code: text_editor::Content,
+    /// What the last import skipped or stood in for, kept up after it went through
+    pub warnings: Vec<String>,
+    /// Why the code couldn't be read at all
+    pub error: Option<String>,
+}
+
+impl ViewImportForm {
+    pub fn new() -> Self {
+        Self { code: text_editor::Content::new(), warnings: Vec::new(), error: None }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ViewImportMessage {
+    Open,
+    CodeEdited(text_editor::Action),
+    Import,
+    Close,
+}
+
+/// A layout read back from view code, with whatever couldn't be carried over
+pub struct ImportedView {
+    pub hierarchy: WidgetHierarchy,
+    pub warnings: Vec<String>,
+    /// Widgets made, placeholders included
+    pub widget_count: usize,
+}
+
+/// Reads a `view` function, its body, or a bare widget expression into a layout. Only the
+/// builders and methods the generator itself writes are understood; anything else becomes a
+/// placeholder Text and a warning. Errors only when the code isn't Rust at all.
+pub fn import_view(source: &str) -> Result<ImportedView, String> {
+    let (locals, expr) = view_expression(source)?;
+    let mut importer = Importer {
+        hierarchy: WidgetHierarchy::new(WidgetType::Container),
+        warnings: Vec::new(),
+        locals,
+        widget_count: 0,
+    };
+
+    let root = importer.hierarchy.root().id;
+    let expr = importer.resolve(&expr);
+    let top = importer.add(root, &expr);
+    if top.is_none() {
+        importer.warnings.push("Nothing from the view could be placed".to_string());
+    }
+    importer.hierarchy.set_selected_ids([root].into_iter().collect());
+
+    Ok(ImportedView {
+        hierarchy: importer.hierarchy,
+        warnings: importer.warnings,
+        widget_count: importer.widget_count,
+    })
+}
+
+/// The expression the view returns, with the `let` bindings before it so names can be followed
+fn view_expression(source: &str) -> Result<(HashMap<String, Expr>, Expr), String> {
+    if let Ok(file) = syn::parse_file(source) {
+        if let Some(block) = file.items.iter().find_map(view_fn_body) {
+            return block_result(&block.stmts);
+        }
+    }
+    if let Ok(function) = syn::parse_str::<syn::ItemFn>(source) {
+        return block_result(&function.block.stmts);
+    }
+    if let Ok(block) = syn::parse_str::<syn::Block>(&format!("{{\n{}\n}}", source)) {
+        if let Ok(found) = block_result(&block.stmts) {
+            return Ok(found);
+        }
+    }
+    syn::parse_str::<Expr>(source)
+        .map(|expr| (HashMap::new(), expr))
+        .map_err(|e| format!("Couldn't read the code as Rust: {}", e))
+}
+
+/// Body of a `fn view`, free or inside an impl
+fn view_fn_body(item: &syn::Item) -> Option<&syn::Block> {
+    match item {
+        syn::Item::Fn(function) if function.sig.ident == "view" => Some(&function.block),
+        syn::Item::Impl(block) => block.items.iter().find_map(|item| match item {
+            syn::ImplItem::Fn(function) if function.sig.ident == "view" => Some(&function.block),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn block_result(stmts: &[Stmt]) -> Result<(HashMap<String, Expr>, Expr), String> {
+    let mut locals = HashMap::new();
+    let mut result = None;
+    for stmt in stmts {
+        match stmt {
+            Stmt::Local(local) => {
+                let name = match &local.pat {
+                    syn::Pat::Ident(pat) => Some(pat.ident.to_string()),
+                    syn::Pat::Type(typed) => match &*typed.pat {
+                        syn::Pat::Ident(pat) => Some(pat.ident.to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let (Some(name), Some(init)) = (name, &local.init) {
+                    locals.insert(name, (*init.expr).clone());
+                }
+            }
+            Stmt::Expr(Expr::Return(ret), _) => result = ret.expr.as_deref().cloned(),
+            Stmt::Expr(expr, None) => result = Some(expr.clone()),
+            _ => {}
+        }
+    }
+    result
+        .map(|expr| (locals, expr))
+        .ok_or_else(|| "The code doesn't end in a widget".to_string())
+}
+
+struct Importer {
+    hierarchy: WidgetHierarchy,
+    warnings: Vec<String>,
+    locals: HashMap<String, Expr>,
+    widget_count: usize,
+}
+
+impl Importer {
+    /// Looks through parentheses, references, `.into()` and names bound earlier in the view
+    fn resolve(&mut self, expr: &Expr) -> Expr {
+        match expr {
+            Expr::Paren(inner) => self.resolve(&inner.expr),
+            Expr::Group(inner) => self.resolve(&inner.expr),
+            Expr::Reference(inner) => self.resolve(&inner.expr),
+            Expr::MethodCall(call) if call.method == "into" && call.args.is_empty() => self.resolve(&call.receiver),
+            Expr::Path(path) if path.path.segments.len() == 1 => {
+                // Taken out while it's read, so a binding that names itself can't loop
+                let name = path.path.segments[0].ident.to_string();
+                match self.locals.remove(&name) {
+                    Some(bound) => {
+                        let resolved = self.resolve(&bound);
+                        self.locals.insert(name, bound);
+                        resolved
+                    }
+                    None => expr.clone(),
+                }
+            }
+            _ => expr.clone(),
+        }
+    }
+
+    /// Adds the widget `expr` builds under `parent`, and its children under it. Returns its id,
+    /// or None when the parent can't take it.
+    fn add(&mut self, parent: WidgetId, expr: &Expr) -> Option<WidgetId> {
+        // Peel the method chain off, outermost last, down to the call that builds the widget
+        let mut methods = Vec::new();
+        let mut base = self.resolve(expr);
+        while let Expr::MethodCall(call) = &base {
+            let call = call.clone();
+            if !(call.method == "into" && call.args.is_empty()) {
+                methods.push((call.method.to_string(), call.args.iter().cloned().collect::<Vec<_>>()));
+            }
+            base = self.resolve(&call.receiver);
+        }
+        methods.reverse();
+
+        match self.builder(&base) {
+            Some(built) => self.place(parent, built, &methods, &base),
+            None => {
+                let code = snippet(expr);
+                self.warnings.push(format!("Unsupported: `{}`, replaced by a placeholder Text", code));
+                let placeholder = Built::new(WidgetType::Text)
+                    .with(move |props| props.text_content = format!("Unsupported: {}", code));
+                self.place(parent, placeholder, &[], expr)
+            }
+        }
+    }
+
+    /// Adds a widget built from `base` with `methods` chained on it, then its children
+    fn place(&mut self, parent: WidgetId, built: Built, methods: &[(String, Vec<Expr>)], base: &Expr) -> Option<WidgetId> {
+        // The root holds a single Column or Row, anything else is put in one
+        let parent = if parent == self.hierarchy.root().id
+            && !matches!(built.widget_type, WidgetType::Column | WidgetType::Row)
+        {
+            self.warnings.push(format!("The view's top widget is a {}, so it was put in a Column", built.widget_type));
+            self.add(parent, &syn::parse_quote!(column![]))?
+        } else {
+            parent
+        };
+
+        let id = match self.hierarchy.add_child(parent, built.widget_type) {
+            Ok(id) => id,
+            Err(e) => {
+                self.warnings.push(format!("Skipped `{}`: {}", snippet(base), e));
+                return None;
+            }
+        };
+        self.widget_count += 1;
+
+        let widget_type = built.widget_type;
+        if let Some(widget) = self.hierarchy.get_widget_by_id_mut(id) {
+            for configure in built.configure {
+                configure(&mut widget.properties);
+            }
+        }
+        self.warnings.extend(built.warnings);
+        for (method, args) in methods {
+            self.apply_method(id, widget_type, method, args);
+        }
+        for child in &built.children {
+            self.add(id, child);
+        }
+        Some(id)
+    }
+
+    /// What the call at the bottom of a method chain builds
+    fn builder(&mut self, base: &Expr) -> Option<Built> {
+        match base {
+            Expr::Macro(mac) => {
+                let widget_type = match last_segment(&mac.mac.path)?.as_str() {
+                    "column" => WidgetType::Column,
+                    "row" => WidgetType::Row,
+                    _ => return None,
+                };
+                let children = mac.mac
+                    .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                    .ok()?;
+                Some(Built::new(widget_type).with_children(children.into_iter().collect()))
+            }
+            Expr::Call(call) => {
+                let Expr::Path(function) = &*call.func else { return None };
+                let segments: Vec<String> = function.path.segments.iter().map(|s| s.ident.to_string()).collect();
+                let args: Vec<Expr> = call.args.iter().map(|arg| self.resolve(arg)).collect();
+                self.call_builder(&segments, &args)
+            }
+            _ => None,
+        }
+    }
+
+    fn call_builder(&mut self, path: &[String], args: &[Expr]) -> Option<Built> {
+        let name = path.last()?.as_str();
+        let module = path.len().checked_sub(2).map(|i| path[i].as_str());
+        let first = args.first();
+
+        let built = match (module, name) {
+            (_, "column" | "row") => {
+                let widget_type = if name == "column" { WidgetType::Column } else { WidgetType::Row };
+                let children = match first {
+                    None => Vec::new(),
+                    Some(Expr::Macro(mac)) if last_segment(&mac.mac.path).as_deref() == Some("vec") => mac.mac
+                        .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                        .ok()?
+                        .into_iter()
+                        .collect(),
+                    Some(Expr::Array(array)) => array.elems.iter().cloned().collect(),
+                    Some(_) => return None,
+                };
+                Built::new(widget_type).with_children(children)
+            }
+            (_, "container") => Built::new(WidgetType::Container).with_children(first.cloned().into_iter().collect()),
+            (_, "scrollable") => Built::new(WidgetType::Scrollable).with_children(first.cloned().into_iter().collect()),
+            (_, "text") => {
+                let mut built = Built::new(WidgetType::Text);
+                let content = match first.and_then(string) {
+                    Some(content) => content,
+                    None => {
+                        let code = first.map(snippet).unwrap_or_default();
+                        built.warn(format!("Text `{}` isn't a literal, so it was kept as written", code));
+                        code
+                    }
+                };
+                built.set(move |props| props.text_content = content);
+                built
+            }
+            (_, "button") => {
+                let mut built = Built::new(WidgetType::Button);
+                let label = first.and_then(|arg| match arg {
+                    Expr::Call(inner) if matches!(&*inner.func, Expr::Path(p) if last_segment(&p.path).as_deref() == Some("text")) => {
+                        inner.args.first().and_then(string)
+                    }
+                    other => string(other),
+                });
+                match label {
+                    Some(label) => built.set(move |props| props.text_content = label),
+                    None => built.warn(format!("Button content `{}` isn't plain text, so it was dropped", first.map(snippet).unwrap_or_default())),
+                }
+                built
+            }
+            (_, "text_input") => {
+                let mut built = Built::new(WidgetType::TextInput);
+                if let Some(placeholder) = first.and_then(string) {
+                    built.set(move |props| props.text_input_placeholder = placeholder);
+                }
+                if let Some(value) = args.get(1).and_then(string) {
+                    built.set(move |props| props.text_input_value = value);
+                }
+                built
+            }
+            (_, "checkbox") => {
+                let mut built = Built::new(WidgetType::Checkbox);
+                if let Some(label) = args.iter().find_map(string) {
+                    built.set(move |props| props.checkbox_label = label);
+                }
+                if let Some(checked) = args.iter().find_map(boolean) {
+                    built.set(move |props| props.checkbox_checked = checked);
+                }
+                built
+            }
+            (_, "toggler") => {
+                let mut built = Built::new(WidgetType::Toggler);
+                if let Some(active) = args.iter().find_map(boolean) {
+                    built.set(move |props| props.toggler_active = active);
+                }
+                built
+            }
+            (_, "slider" | "vertical_slider") => {
+                let widget_type = if name == "slider" { WidgetType::Slider } else { WidgetType::VerticalSlider };
+                let mut built = Built::new(widget_type);
+                match first.and_then(range) {
+                    Some((min, max)) => built.set(move |props| {
+                        props.slider_min = min;
+                        props.slider_max = max;
+                    }),
+                    None => built.warn("Slider range isn't a literal, so the default was kept".to_string()),
+                }
+                if let Some(value) = args.get(1).and_then(number) {
+                    built.set(move |props| props.slider_value = value);
+                }
+                built
+            }
+            (_, "progress_bar") => {
+                let mut built = Built::new(WidgetType::ProgressBar);
+                if let Some((min, max)) = first.and_then(range) {
+                    built.set(move |props| {
+                        props.progress_min = min;
+                        props.progress_max = max;
+                    });
+                }
+                if let Some(value) = args.get(1).and_then(number) {
+                    built.set(move |props| props.progress_value = value);
+                }
+                built
+            }
+            (Some("space"), "horizontal") | (None, "horizontal_space") => Built::new(WidgetType::Space)
+                .with(|props| {
+                    props.orientation = Orientation::Horizontal;
+                    props.width = Length::Fill;
+                }),
+            (Some("space"), "vertical") | (None, "vertical_space") => Built::new(WidgetType::Space)
+                .with(|props| {
+                    props.orientation = Orientation::Vertical;
+                    props.height = Length::Fill;
+                }),
+            (Some("rule"), "horizontal" | "vertical") | (None, "horizontal_rule" | "vertical_rule") => {
+                let orientation = if name.starts_with("horizontal") { Orientation::Horizontal } else { Orientation::Vertical };
+                let thickness = first.and_then(number);
+                Built::new(WidgetType::Rule).with(move |props| {
+                    props.orientation = orientation;
+                    if let Some(thickness) = thickness {
+                        props.rule_thickness = thickness;
+                    }
+                })
+            }
+            (_, "image") => {
+                let path = first.and_then(string);
+                Built::new(WidgetType::Image).with(move |props| props.image_path = path.unwrap_or_default())
+            }
+            (_, "svg") => {
+                let path = first.and_then(string);
+                Built::new(WidgetType::Svg).with(move |props| props.svg_path = path.unwrap_or_default())
+            }
+            _ => return None,
+        };
+        Some(built)
+    }
+
+    /// Carries a chained method over to the widget's properties, warning about any it can't
+    fn apply_method(&mut self, id: WidgetId, widget_type: WidgetType, method: &str, args: &[Expr]) {
+        // Handlers are wired up again from the hierarchy's own settings
+        if method.starts_with("on_") || method == "id" {
+            return;
+        }
+        let arg = args.first().map(|arg| self.resolve(arg));
+        let Some(props) = self.hierarchy.get_widget_by_id_mut(id).map(|widget| &mut widget.properties) else { return };
+
+        let understood = match (method, arg.as_ref()) {
+            ("spacing", Some(arg)) => number(arg).map(|spacing| match widget_type {
+                WidgetType::Checkbox => props.checkbox_spacing = spacing,
+                WidgetType::Toggler => props.toggler_spacing = spacing,
+                _ => props.spacing = spacing,
+            }),
+            ("padding", Some(arg)) => padding(arg).map(|(padding, mode)| {
+                props.padding = padding;
+                props.padding_mode = mode;
+            }),
+            ("width", Some(arg)) => length(arg).map(|width| props.width = width),
+            ("height", Some(arg)) => length(arg).map(|height| props.height = height),
+            ("size" | "text_size", Some(arg)) => number(arg).map(|size| match widget_type {
+                WidgetType::TextInput => props.text_input_size = size,
+                WidgetType::Checkbox => props.checkbox_size = size,
+                WidgetType::Toggler => props.toggler_size = size,
+                _ => props.text_size = size,
+            }),
+            ("label", Some(arg)) => string(arg).map(|label| match widget_type {
+                WidgetType::Toggler => props.toggler_label = label,
+                _ => props.checkbox_label = label,
+            }),
+            ("step", Some(arg)) => number(arg).map(|step| props.slider_step = step),
+            ("secure", Some(arg)) => boolean(arg).map(|secure| props.is_secure = secure),
+            ("style", Some(arg)) if widget_type == WidgetType::Button => button_style(arg).map(|style| props.button_style = style),
+            _ => None,
+        };
+        if understood.is_none() {
+            let call = format!(".{}({})", method, args.iter().map(snippet).collect::<Vec<_>>().join(", "));
+            self.warnings.push(format!("Ignored `{}` on a {}", shorten(&call), widget_type));
+        }
+    }
+}
+
+/// A widget about to be added: its type, the properties its builder call sets and the
+/// expressions of its children
+struct Built {
+    widget_type: WidgetType,
+    configure: Vec<Box<dyn FnOnce(&mut Properties)>>,
+    children: Vec<Expr>,
+    warnings: Vec<String>,
+}
+
+impl Built {
+    fn new(widget_type: WidgetType) -> Self {
+        Self { widget_type, configure: Vec::new(), children: Vec::new(), warnings: Vec::new() }
+    }
+
+    fn with(mut self, configure: impl FnOnce(&mut Properties) + 'static) -> Self {
+        self.set(configure);
+        self
+    }
+
+    fn set(&mut self, configure: impl FnOnce(&mut Properties) + 'static) {
+        self.configure.push(Box::new(configure));
+    }
+
+    fn with_children(mut self, children: Vec<Expr>) -> Self {
+        self.children = children;
+        self
+    }
+
+    fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+}
+
+fn last_segment(path: &syn::Path) -> Option<String> {
+    path.segments.last().map(|segment| segment.ident.to_string())
+}
+
+/// The code as written, cut short for a warning
+fn snippet(expr: &Expr) -> String {
+    shorten(&expr.to_token_stream().to_string())
+}
+
+fn shorten(code: &str) -> String {
+    match code.char_indices().nth(SNIPPET_LENGTH) {
+        Some((end, _)) => format!("{}…", &code[..end]),
+        None => code.to_string(),
+    }
+}
+
+fn string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: Lit::Str(s), .. }) => Some(s.value()),
+        Expr::Reference(inner) => string(&inner.expr),
+        Expr::MethodCall(call) if matches!(call.method.to_string().as_str(), "to_string" | "into" | "to_owned") => string(&call.receiver),
+        _ => None,
+    }
+}
+
+fn boolean(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: Lit::Bool(b), .. }) => Some(b.value),
+        _ => None,
+    }
+}
+
+fn number(expr: &Expr) -> Option<f32> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: Lit::Int(n), .. }) => n.base10_parse().ok(),
+        Expr::Lit(syn::ExprLit { lit: Lit::Float(n), .. }) => n.base10_parse().ok(),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => number(&unary.expr).map(|n| -n),
+        Expr::Paren(inner) => number(&inner.expr),
+        _ => None,
+    }
+}
+
+/// `a..=b` or `a..b` with literal ends
+fn range(expr: &Expr) -> Option<(f32, f32)> {
+    match expr {
+        Expr::Range(range) => Some((number(range.start.as_deref()?)?, number(range.end.as_deref()?)?)),
+        Expr::Paren(inner) => self::range(&inner.expr),
+        _ => None,
+    }
+}
+
+/// `Length::Fill`, `Fill`, `Length::FillPortion(n)`, `Length::Fixed(x)` or a plain number
+fn length(expr: &Expr) -> Option<Length> {
+    match expr {
+        Expr::Path(path) => match last_segment(&path.path)?.as_str() {
+            "Fill" => Some(Length::Fill),
+            "Shrink" => Some(Length::Shrink),
+            _ => None,
+        },
+        Expr::Call(call) => {
+            let Expr::Path(function) = &*call.func else { return None };
+            let value = call.args.first()?;
+            match last_segment(&function.path)?.as_str() {
+                "FillPortion" => number(value).map(|portion| Length::FillPortion(portion as u16)),
+                "Fixed" => number(value).map(Length::Fixed),
+                _ => None,
+            }
+        }
+        _ => number(expr).map(Length::Fixed),
+    }
+}
+
+/// A number pads every side alike, `[v, h]` pads top and bottom by `v` and the sides by `h`,
+/// and `[t, r, b, l]` pads each side on its own
+fn padding(expr: &Expr) -> Option<(Padding, PaddingMode)> {
+    if let Some(all) = number(expr) {
+        return Some((Padding::new(all), PaddingMode::Uniform));
+    }
+    let Expr::Array(array) = expr else { return None };
+    let sides: Vec<f32> = array.elems.iter().map(number).collect::<Option<_>>()?;
+    match sides[..] {
+        [vertical, horizontal] => Some((
+            Padding { top: vertical, right: horizontal, bottom: vertical, left: horizontal },
+            PaddingMode::Symmetric,
+        )),
+        [top, right, bottom, left] => Some((Padding { top, right, bottom, left }, PaddingMode::Individual)),
+        _ => None,
+    }
+}
+
+/// `button::primary` and the other styles the editor offers
+fn button_style(expr: &Expr) -> Option<ButtonStyleType> {
+    let Expr::Path(path) = expr else { return None };
+    match last_segment(&path.path)?.as_str() {
+        "primary" => Some(ButtonStyleType::Primary),
+        "secondary" => Some(ButtonStyleType::Secondary),
+        "success" => Some(ButtonStyleType::Success),
+        "danger" => Some(ButtonStyleType::Danger),
+        "text" => Some(ButtonStyleType::Text),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_function_becomes_a_tree() {
+        let imported = import_view(r#"
+            impl App {
+                fn view(&self) -> Element<'_, Message> {
+                    let header = text("Settings").size(24);
+                    column![
+                        header,
+                        row![
+                            text_input("Name", &self.name).on_input(Message::NameChanged).width(Length::FillPortion(2)),
+                            button(text("Save")).style(button::success).on_press(Message::Save),
+                        ]
+                        .spacing(8),
+                        checkbox("Remember me", self.remember),
+                        slider(0.0..=10.0, self.volume, Message::Volume).step(0.5),
+                    ]
+                    .padding([10, 20])
+                    .into()
+                }
+            }
+        "#).unwrap();
+
+        assert!(imported.warnings.is_empty(), "{:?}", imported.warnings);
+        assert_eq!(imported.widget_count, 7);
+        let column = &imported.hierarchy.root().children[0];
+        assert_eq!(column.widget_type, WidgetType::Column);
+        assert_eq!(column.properties.padding, Padding { top: 10.0, right: 20.0, bottom: 10.0, left: 20.0 });
+        assert_eq!(column.properties.padding_mode, PaddingMode::Symmetric);
+
+        let header = &column.children[0];
+        assert_eq!(header.properties.text_content, "Settings");
+        assert_eq!(header.properties.text_size, 24.0);
+
+        let row = &column.children[1];
+        assert_eq!(row.properties.spacing, 8.0);
+        assert_eq!(row.children[0].properties.text_input_placeholder, "Name");
+        assert_eq!(row.children[0].properties.width, Length::FillPortion(2));
+        assert_eq!(row.children[1].properties.text_content, "Save");
+        assert_eq!(row.children[1].properties.button_style, ButtonStyleType::Success);
+
+        assert_eq!(column.children[2].properties.checkbox_label, "Remember me");
+        let slider = &column.children[3].properties;
+        assert_eq!((slider.slider_min, slider.slider_max, slider.slider_step), (0.0, 10.0, 0.5));
+    }
+
+    #[test]
+    fn test_unsupported_code_leaves_a_placeholder() {
+        let imported = import_view(r#"
+            container(
+                column![
+                    text("Kept"),
+                    my_custom_widget(&self.state),
+                    text("Also kept").font(Font::MONOSPACE),
+                ]
+            )
+            .center_x(Length::Fill)
+        "#).unwrap();
+
+        // The container went into a Column of its own, since the root only takes a Column or Row
+        let wrapper = &imported.hierarchy.root().children[0];
+        assert_eq!(wrapper.widget_type, WidgetType::Column);
+        let column = &wrapper.children[0].children[0];
+        assert_eq!(column.children.len(), 3);
+        assert_eq!(column.children[1].widget_type, WidgetType::Text);
+        assert!(column.children[1].properties.text_content.starts_with("Unsupported: my_custom_widget"));
+        assert_eq!(column.children[2].properties.text_content, "Also kept");
+
+        assert!(imported.warnings.iter().any(|w| w.contains("put in a Column")));
+        assert!(imported.warnings.iter().any(|w| w.contains("my_custom_widget")));
+        assert!(imported.warnings.iter().any(|w| w.contains(".font(")));
+        assert!(imported.warnings.iter().any(|w| w.contains(".center_x(")));
+    }
+
+    #[test]
+    fn test_code_that_isnt_rust_is_refused() {
+        assert!(import_view("column![ text(\"unclosed ]").is_err());
+    }
+}
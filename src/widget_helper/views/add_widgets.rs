@@ -1,147 +1,204 @@
-use iced::{Element, Settings, Theme, Length,
-    widget::{ button, column, row, rule, scrollable, text },
+use iced::{Element, Length,
+    widget::{ button, column, container, row, rule, scrollable, text, text_input, tooltip, Space },
 };
-use crate::widget_helper::{WidgetType, WidgetId};
+use crate::widget_helper::WidgetType;
+
+/// Id of the filter above the palette, focused by its shortcut
+pub const ADD_WIDGET_FILTER_INPUT: &str = "add_widget_filter_input";
+
+/// Buttons per palette row
+const PER_ROW: usize = 3;
+
+/// The palette's sections, each type with its button label
+const GROUPS: [(&str, &[(WidgetType, &str)]); 3] = [
+    ("Containers", &[
+        (WidgetType::Container, "Container"),
+        (WidgetType::Scrollable, "Scrollable"),
+        (WidgetType::Themer, "Themer"),
+    ]),
+    ("Layout", &[
+        (WidgetType::Row, "Row"),
+        (WidgetType::Column, "Column"),
+        (WidgetType::Stack, "Stack"),
+    ]),
+    ("Widgets", &[
+        (WidgetType::Text, "Text"),
+        (WidgetType::TextInput, "Text Input"),
+        (WidgetType::Button, "Button"),
+        (WidgetType::Checkbox, "Checkbox"),
+        (WidgetType::Radio, "Radio"),
+        (WidgetType::Toggler, "Toggler"),
+        (WidgetType::Slider, "Slider"),
+        (WidgetType::VerticalSlider, "Vert. Slider"),
+        (WidgetType::ProgressBar, "Progress"),
+        (WidgetType::PickList, "Pick List"),
+        (WidgetType::Space, "Space"),
+        (WidgetType::Rule, "Rule"),
+        (WidgetType::Image, "Image"),
+        (WidgetType::Svg, "SVG"),
+        (WidgetType::Tooltip, "Tooltip"),
+        (WidgetType::ComboBox, "ComboBox"),
+        (WidgetType::Markdown, "Markdown"),
+        (WidgetType::MouseArea, "MouseArea"),
+        (WidgetType::Pin, "Pin"),
+        (WidgetType::QRCode, "QRCode"),
+    ]),
+];
+
+/// Other words people reach for when looking for a type
+fn aliases(widget_type: WidgetType) -> &'static [&'static str] {
+    match widget_type {
+        WidgetType::PickList | WidgetType::ComboBox => &["dropdown", "select", "choice"],
+        WidgetType::Text => &["label", "heading"],
+        WidgetType::Rule => &["hr", "divider", "separator", "line"],
+        WidgetType::TextInput => &["entry", "field", "textbox"],
+        WidgetType::Toggler => &["switch"],
+        WidgetType::Checkbox => &["tick"],
+        WidgetType::Space => &["spacer", "gap"],
+        WidgetType::Column => &["vstack", "vertical"],
+        WidgetType::Row => &["hstack", "horizontal"],
+        WidgetType::Stack => &["layers", "overlay"],
+        WidgetType::Image => &["picture", "photo", "png"],
+        WidgetType::Svg => &["icon", "vector"],
+        WidgetType::Scrollable => &["scroll"],
+        WidgetType::Container => &["box", "frame"],
+        WidgetType::Tooltip => &["hint", "hover"],
+        WidgetType::MouseArea => &["click", "hover"],
+        WidgetType::ProgressBar => &["loading"],
+        WidgetType::Markdown => &["rich text"],
+        WidgetType::Themer => &["theme"],
+        WidgetType::Pin => &["absolute", "position"],
+        WidgetType::QRCode => &["barcode"],
+        _ => &[],
+    }
+}
+
+/// Whether `query` finds this type, by its name, its label or an alias, ignoring case
+pub fn matches(widget_type: WidgetType, label: &str, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return true;
+    }
+    [widget_type.to_string().as_str(), label]
+        .into_iter()
+        .chain(aliases(widget_type).iter().copied())
+        .any(|name| name.to_lowercase().contains(&query))
+}
 
 // Application messages
 #[derive(Debug, Clone)]
 pub enum Message {
     SelectWidgetType(WidgetType),
+    FilterChanged(String),
 }
 
+/// `refusals` holds why each type that can't go under the parent is refused; those are shown
+/// greyed out with the reason as a tooltip
 pub fn view<'a>(
-    parent_id: WidgetId,
-    available_types: &[WidgetType],
+    refusals: &[(WidgetType, String)],
+    filter: &'a str,
 ) -> Element<'a, Message> {
-    // Helper to create a button if the type is available
-    let widget_button = |widget_type: WidgetType, label: &'a str| -> Element<'a, Message> {
-        if available_types.contains(&widget_type) {
-            button(text(label).center())
-                .on_press(Message::SelectWidgetType(widget_type))
-                .style(button::secondary)
-                .width(Length::FillPortion(1))
-                .into()
-        } else {
-            button(text(label).center())
-                .style(button::secondary)
-                .width(Length::FillPortion(1))
-                .into()
+    let refusal = |widget_type: WidgetType| {
+        refusals.iter().find(|(refused, _)| *refused == widget_type).map(|(_, reason)| reason.clone())
+    };
+
+    let widget_button = |widget_type: WidgetType, label: &'static str| -> Element<'a, Message> {
+        let button = button(text(label).center())
+            .style(button::secondary)
+            .width(Length::FillPortion(1));
+        match refusal(widget_type) {
+            None => button.on_press(Message::SelectWidgetType(widget_type)).into(),
+            Some(reason) => tooltip(
+                button,
+                container(text(reason).size(12)).padding(5).style(container::rounded_box),
+                tooltip::Position::Top,
+            )
+            .into(),
         }
     };
 
-    scrollable(
-        column![
-            if available_types.contains(&WidgetType::Container) 
-                || available_types.contains(&WidgetType::Scrollable) {
-                column![
-                    text("Containers").size(18),
-                    rule::horizontal(2),
-                    row![
-                        widget_button(WidgetType::Container, "Container"),
-                        widget_button(WidgetType::Scrollable, "Scrollable")
-                    ]
-                    .spacing(10)
-                    .padding(5),
-                ]
-            } else {
-                column![]
-            },
-            
-            if available_types.contains(&WidgetType::Row) 
-                || available_types.contains(&WidgetType::Column) {
-                column![
-                    text("Layout").size(18),
-                    rule::horizontal(2),
-                    row![
-                        widget_button(WidgetType::Row, "Row"),
-                        widget_button(WidgetType::Column, "Column")
-                    ]
-                    .spacing(10)
-                    .padding(5),
-                ]
-            } else {
-                column![]
-            },
+    let mut sections = column![].spacing(10);
+    for (title, types) in GROUPS {
+        let shown: Vec<(WidgetType, &str)> = types
+            .iter()
+            .copied()
+            .filter(|(widget_type, label)| matches(*widget_type, label, filter))
+            .collect();
+        if shown.is_empty() {
+            continue;
+        }
+        let mut section = column![text(title).size(18), rule::horizontal(2)];
+        for chunk in shown.chunks(PER_ROW) {
+            let mut buttons = row![].spacing(10).padding(5);
+            for &(widget_type, label) in chunk {
+                buttons = buttons.push(widget_button(widget_type, label));
+            }
+            // Keep the last row's buttons as wide as the rest
+            for _ in chunk.len()..PER_ROW {
+                buttons = buttons.push(Space::new().width(Length::FillPortion(1)));
+            }
+            section = section.push(buttons);
+        }
+        sections = sections.push(section);
+    }
 
-            if !available_types.is_empty(){
-                column![
-                    text("Widgets").size(18),
-                    rule::horizontal(2),
-                    
-                    row![
-                        widget_button(WidgetType::Text, "Text"),
-                        widget_button(WidgetType::TextInput, "Text Input"),
-                        widget_button(WidgetType::Button, "Button")
-                    ]
-                    .spacing(10)
-                    .padding(5),
-                    
-                    row![
-                        widget_button(WidgetType::Checkbox, "Checkbox"),
-                        widget_button(WidgetType::Radio, "Radio"),
-                        widget_button(WidgetType::Toggler, "Toggler")
-                    ]
-                    .spacing(10)
-                    .padding(5),
-                    
-                    row![
-                        widget_button(WidgetType::Slider, "Slider"),
-                        widget_button(WidgetType::VerticalSlider, "Vert. Slider"),
-                        widget_button(WidgetType::ProgressBar, "Progress")
-                    ]
-                    .spacing(10)
-                    .padding(5),
-                    
-                    row![
-                        widget_button(WidgetType::PickList, "Pick List"),
-                        widget_button(WidgetType::Space, "Space"),
-                        widget_button(WidgetType::Rule, "Rule")
-                    ]
-                    .spacing(10)
-                    .padding(5),
-                    
-                    row![
-                        widget_button(WidgetType::Image, "Image"),
-                        widget_button(WidgetType::Svg, "SVG"),
-                        widget_button(WidgetType::Tooltip, "Tooltip")
-                    ]
-                    .spacing(10)
-                    .padding(5),
+    let addable = GROUPS.iter().flat_map(|(_, types)| types.iter()).any(|(widget_type, _)| refusal(*widget_type).is_none());
+    let nothing_found = GROUPS.iter()
+        .flat_map(|(_, types)| types.iter())
+        .all(|(widget_type, label)| !matches(*widget_type, label, filter));
+    let note = if !addable {
+        Some("No widgets can be added to this parent".to_string())
+    } else if nothing_found {
+        Some(format!("No widget matches \"{}\"", filter.trim()))
+    } else {
+        None
+    };
+    if let Some(note) = note {
+        sections = sections.push(
+            column![
+                text(note).size(14)
+                    .color(iced::Color::from_rgb(0.6, 0.6, 0.6)),
+            ]
+            .padding(10),
+        );
+    }
 
-                    row![
-                        widget_button(WidgetType::ComboBox, "ComboBox"),
-                        widget_button(WidgetType::Markdown, "Markdown"),
-                        widget_button(WidgetType::MouseArea, "MouseArea")
-                    ]
-                    .spacing(10)
-                    .padding(5),
+    column![
+        text_input("Filter widgets ( / )", filter)
+            .id(ADD_WIDGET_FILTER_INPUT)
+            .on_input(Message::FilterChanged)
+            .size(14),
+        scrollable(sections)
+            .width(Length::Fill)
+            .height(Length::Fill),
+    ]
+    .spacing(5)
+    .into()
+}
 
-                    row![
-                        widget_button(WidgetType::Pin, "Pin"),
-                        widget_button(WidgetType::QRCode, "QRCode"),
-                    ]
-                    .spacing(10)
-                    .padding(5),
-                ]
-            } else {
-                column![]
-            }, 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn test_filter_matches_names_labels_and_aliases() {
+        assert!(matches(WidgetType::PickList, "Pick List", "DROPDOWN"));
+        assert!(matches(WidgetType::ComboBox, "ComboBox", "drop"));
+        assert!(matches(WidgetType::Text, "Text", "label"));
+        assert!(matches(WidgetType::Rule, "Rule", "hr"));
+        assert!(matches(WidgetType::VerticalSlider, "Vert. Slider", "vert."));
+        assert!(matches(WidgetType::QRCode, "QRCode", "  qr "));
+        assert!(!matches(WidgetType::Button, "Button", "dropdown"));
+        assert!(matches(WidgetType::Button, "Button", ""));
+    }
 
-            
-            if available_types.is_empty() {
-                column![
-                    text("No widgets can be added to this parent").size(14)
-                        .color(iced::Color::from_rgb(0.6, 0.6, 0.6)),
-                ]
-                .padding(10)
-            } else {
-                column![]
-            }
-        ]
-        .spacing(10)
-    )
-    .width(Length::Fill)
-    .height(Length::Fill)
-    .into()
-}
\ No newline at end of file
+    #[test]
+    fn test_every_type_is_in_the_palette() {
+        for widget_type in WidgetType::ALL {
+            assert!(
+                GROUPS.iter().any(|(_, types)| types.iter().any(|(listed, _)| *listed == widget_type)),
+                "{:?} missing from the palette", widget_type
+            );
+        }
+    }
+}
@@ -5,7 +5,9 @@ use iced::{
 use uuid::Uuid;
 
 use crate::icon;
+use crate::widget_helper::WidgetId;
 use crate::widget_helper::type_system::*;
+use crate::widget_helper::type_references::{TypeReference, TypeReferences};
 use crate::widget_helper::styles::container::*;
 
 // ==================== STATE ====================
@@ -83,6 +85,14 @@ impl TypeEditorView {
             }
         }
     }
+
+    /// Expand one enum and collapse the rest, for jumping here from a widget bound to it
+    pub fn focus_enum(&mut self, type_system: &TypeSystem, enum_id: Uuid) {
+        self.sync_with_type_system(type_system);
+        for state in &mut self.editor_states {
+            state.is_expanded = state.enum_id == enum_id;
+        }
+    }
 }
 
 // ==================== MESSAGES ====================
@@ -104,6 +114,9 @@ pub enum Message {
     EnumNameInputChanged { enum_id: Uuid, value: String },
     NewVariantInputChanged { enum_id: Uuid, value: String },
     SaveEnum(Uuid),
+
+    /// Jump to a widget listed under References
+    SelectWidget(WidgetId),
     
     // Undo/Redo
     Undo,
@@ -242,6 +255,9 @@ pub fn update(
             }
         }
         
+        // The builder owns the selection and handles this before it gets here
+        Message::SelectWidget(_) => {}
+        
         Message::Undo => {
             if let Err(e) = type_system.undo() {
                 eprintln!("Undo failed: {}", e);
@@ -265,6 +281,7 @@ pub fn update(
 pub fn view<'a>(
     type_system: &'a TypeSystem,
     editor_view: &'a TypeEditorView,
+    references: &'a TypeReferences,
 ) -> Element<'a, Message> {
     let mut content = column![
         // Header
@@ -291,7 +308,7 @@ pub fn view<'a>(
     // List all enums
     for state in &editor_view.editor_states {
         if let Some(enum_def) = type_system.get_enum(state.enum_id) {
-            let enum_view = view_single_enum(type_system, enum_def, state);
+            let enum_view = view_single_enum(type_system, enum_def, state, references.of_enum(enum_def.id));
             content = content.push(enum_view);
         }
     }
@@ -316,14 +333,14 @@ fn view_single_enum<'a>(
     type_system: &'a TypeSystem,
     enum_def: &'a EnumDef,
     state: &'a EnumEditorState,
+    references: &'a [TypeReference],
 ) -> Element<'a, Message> {
-    let dependents = type_system.get_dependents(enum_def.id);
-    let is_in_use = !dependents.is_empty();
+    let is_in_use = !references.is_empty();
     
     if state.is_expanded {
-        view_enum_expanded(type_system, enum_def, state, is_in_use, &dependents)
+        view_enum_expanded(type_system, enum_def, state, is_in_use, references)
     } else {
-        view_enum_collapsed(enum_def, is_in_use, dependents.len())
+        view_enum_collapsed(enum_def, is_in_use, references.len())
     }
 }
 
@@ -348,7 +365,7 @@ fn view_enum_collapsed<'a>(
                     .size(12)
                     .style(text::secondary)
             } else {
-                text("").size(12).style(text::secondary)
+                text("unused").size(12).style(text::warning)
             },
             
             space::horizontal(),
@@ -370,7 +387,7 @@ fn view_enum_expanded<'a>(
     enum_def: &'a EnumDef,
     state: &'a EnumEditorState,
     is_in_use: bool,
-    dependents: &[String],
+    references: &'a [TypeReference],
 ) -> Element<'a, Message> {
     let mut content = column![].spacing(10);
     
@@ -405,21 +422,38 @@ fn view_enum_expanded<'a>(
     
     content = content.push(header);
     
-    // Show usage warning if in use
+    // Widgets bound to this enum, each one selects its widget in the tree
+    content = content.push(text("References:").size(14));
     if is_in_use {
-        let warning = container(
-            column![
-                text(format!("This enum is used by {} widget(s)", dependents.len()))
-                    .size(12),
-                text(format!("Widgets: {}", dependents.join(", ")))
-                    .size(11),
-            ]
-            .spacing(5)
-        )
-        .padding(10)
-        .style(warning_box);
-        
-        content = content.push(warning);
+        let entries = column(references.iter().map(|reference| {
+            button(
+                row![
+                    text(&reference.path).size(12),
+                    text(format!("{:?}", reference.widget_type)).size(11).style(text::secondary),
+                ]
+                .spacing(8)
+            )
+            .on_press(Message::SelectWidget(reference.widget_id))
+            .style(button::text)
+            .padding(2)
+            .into()
+        }))
+        .spacing(2);
+
+        content = content.push(
+            container(
+                column![
+                    text(format!("Used by {} widget(s), so it can't be deleted", references.len()))
+                        .size(12),
+                    entries,
+                ]
+                .spacing(5)
+            )
+            .padding(10)
+            .style(warning_box)
+        );
+    } else {
+        content = content.push(text("No widget uses this enum").size(12).style(text::secondary));
     }
     
     // Variants section
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// How the generated app opens its window. A preset fills every field, and each field
+/// stays editable afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowSettings {
+    pub width: f32,
+    pub height: f32,
+    /// Smallest size the window can be resized to, None for no limit
+    pub min_size: Option<(f32, f32)>,
+    pub resizable: bool,
+    /// Open centered on the screen instead of wherever the OS puts it
+    pub centered: bool,
+    pub decorations: bool,
+    pub always_on_top: bool,
+    /// Start covering the whole monitor
+    pub fullscreen: bool,
+}
+
+impl Default for WindowSettings {
+    /// Matches `window::Settings::default()`, so an untouched window emits nothing
+    fn default() -> Self {
+        Self {
+            width: 1024.0,
+            height: 768.0,
+            min_size: None,
+            resizable: true,
+            centered: false,
+            decorations: true,
+            always_on_top: false,
+            fullscreen: false,
+        }
+    }
+}
+
+impl WindowSettings {
+    /// Whether the generated settings spell out a size, so the code needs `Size`
+    pub fn names_a_size(&self) -> bool {
+        let standard = Self::default();
+        self.min_size.is_some() || (self.width, self.height) != (standard.width, standard.height)
+    }
+
+    /// The preset these settings are exactly, None once a field was changed after picking one
+    pub fn preset(&self) -> Option<WindowPreset> {
+        WindowPreset::ALL.into_iter().find(|preset| preset.settings() == *self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowPreset {
+    Standard,
+    Dialog,
+    ToolPalette,
+    Kiosk,
+}
+
+impl WindowPreset {
+    pub const ALL: [Self; 4] = [Self::Standard, Self::Dialog, Self::ToolPalette, Self::Kiosk];
+
+    pub fn settings(self) -> WindowSettings {
+        let standard = WindowSettings::default();
+        match self {
+            Self::Standard => standard,
+            Self::Dialog => WindowSettings {
+                width: 480.0,
+                height: 320.0,
+                min_size: Some((320.0, 200.0)),
+                resizable: false,
+                centered: true,
+                ..standard
+            },
+            Self::ToolPalette => WindowSettings {
+                width: 280.0,
+                height: 600.0,
+                min_size: Some((200.0, 300.0)),
+                always_on_top: true,
+                ..standard
+            },
+            Self::Kiosk => WindowSettings {
+                resizable: false,
+                decorations: false,
+                fullscreen: true,
+                ..standard
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for WindowPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Standard => "Standard window",
+            Self::Dialog => "Fixed-size dialog",
+            Self::ToolPalette => "Tool palette",
+            Self::Kiosk => "Kiosk",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_recognized_until_a_field_changes() {
+        for preset in WindowPreset::ALL {
+            assert_eq!(preset.settings().preset(), Some(preset));
+        }
+        let edited = WindowSettings { width: 500.0, ..WindowPreset::Dialog.settings() };
+        assert_eq!(edited.preset(), None);
+    }
+}
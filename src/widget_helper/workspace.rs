@@ -0,0 +1,396 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use crate::widget_helper::*;
+use crate::widget_helper::project_file::SavedTab;
+
+/// Everything a tab owns, set aside while another tab is shown. The shown tab's own state
+/// lives in the visualizer's fields, so the rest of the builder never looks past them.
+pub struct ParkedTab {
+    hierarchy: WidgetHierarchy,
+    undo: UndoStack,
+    code_generation: CodeGeneration,
+    tree_rows: TreeRows,
+    tag_filter: Option<(String, HashSet<WidgetId>)>,
+    selection_focus: SelectionFocus,
+    pinned_inspectors: Vec<WidgetId>,
+    wide_inspectors: HashSet<WidgetId>,
+    editor_widths: PaneWidths<WidgetId>,
+    comparison_snapshot: Option<Comparison>,
+    show_comparison: bool,
+    last_bulk_rename: Option<Vec<(WidgetId, String)>>,
+    hint: Option<Hint>,
+    refused_add: Option<RefusedAdd>,
+    dismissed_hints: HashSet<(HintRule, WidgetId)>,
+    dismissed_contrast: HashMap<WidgetId, Color>,
+}
+
+impl ParkedTab {
+    /// A tab with an empty layout, as the builder starts with
+    fn empty() -> Self {
+        Self::with_hierarchy(WidgetHierarchy::new(WidgetType::Container))
+    }
+
+    /// A tab showing `hierarchy`, with nothing to undo yet
+    fn with_hierarchy(hierarchy: WidgetHierarchy) -> Self {
+        let undo = UndoStack::new(&hierarchy, Instant::now());
+        let mut tree_rows = TreeRows::default();
+        tree_rows.rebuild(hierarchy.root());
+        Self {
+            hierarchy,
+            undo,
+            code_generation: CodeGeneration::default(),
+            tree_rows,
+            tag_filter: None,
+            selection_focus: SelectionFocus::default(),
+            pinned_inspectors: Vec::new(),
+            wide_inspectors: HashSet::new(),
+            editor_widths: PaneWidths::default(),
+            comparison_snapshot: None,
+            show_comparison: false,
+            last_bulk_rename: None,
+            hint: None,
+            refused_add: None,
+            dismissed_hints: HashSet::new(),
+            dismissed_contrast: HashMap::new(),
+        }
+    }
+
+    /// Whether closing the tab would throw work away: anything added, or any recorded edit
+    fn has_work(&self) -> bool {
+        has_work(&self.hierarchy, &self.undo)
+    }
+}
+
+fn has_work(hierarchy: &WidgetHierarchy, undo: &UndoStack) -> bool {
+    !hierarchy.root().children.is_empty() || undo.entries().len() > 1
+}
+
+pub struct WorkspaceTab {
+    pub name: String,
+    /// None for the shown tab
+    parked: Option<ParkedTab>,
+}
+
+/// The builder's tabs, each one screen with its own tree, selection, undo and code. The
+/// type system, project defaults, assets and styles are shared by all of them.
+pub struct Workspace {
+    tabs: Vec<WorkspaceTab>,
+    active: usize,
+    /// Tab being renamed, with the name typed so far
+    pub rename: Option<(usize, String)>,
+    /// Tab waiting for its close to be confirmed
+    pub closing: Option<usize>,
+    /// Tab pressed for a drag, and the tab it's over
+    pub dragging: Option<(usize, usize)>,
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self {
+            tabs: vec![WorkspaceTab { name: "Screen 1".to_string(), parked: None }],
+            active: 0,
+            rename: None,
+            closing: None,
+            dragging: None,
+        }
+    }
+}
+
+impl Workspace {
+    pub fn tabs(&self) -> &[WorkspaceTab] {
+        &self.tabs
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    pub fn active_name(&self) -> &str {
+        &self.tabs[self.active].name
+    }
+
+    /// "Screen N" with the lowest N no tab uses
+    fn next_name(&self) -> String {
+        (1..)
+            .map(|n| format!("Screen {}", n))
+            .find(|name| !self.tabs.iter().any(|tab| tab.name == *name))
+            .expect("some number is free")
+    }
+
+    /// Renames a tab, refusing a blank name or another tab's
+    pub fn rename(&mut self, index: usize, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("A tab needs a name".to_string());
+        }
+        if self.tabs.iter().enumerate().any(|(i, tab)| i != index && tab.name == name) {
+            return Err(format!("Another tab is called {}", name));
+        }
+        if let Some(tab) = self.tabs.get_mut(index) {
+            tab.name = name.to_string();
+        }
+        Ok(())
+    }
+
+    /// Moves the tab at `from` to `to`, the shown tab staying shown
+    pub fn move_tab(&mut self, from: usize, to: usize) {
+        if from >= self.tabs.len() || to >= self.tabs.len() || from == to {
+            return;
+        }
+        let tab = self.tabs.remove(from);
+        self.tabs.insert(to, tab);
+        self.active = if self.active == from {
+            to
+        } else if from < self.active && to >= self.active {
+            self.active - 1
+        } else if from > self.active && to <= self.active {
+            self.active + 1
+        } else {
+            self.active
+        };
+    }
+}
+
+impl WidgetVisualizer {
+    /// Sets the shown tab's state aside, leaving a fresh layout in its place
+    fn park_active(&mut self) -> ParkedTab {
+        let mut fresh = ParkedTab::empty();
+        std::mem::swap(&mut fresh.hierarchy, &mut self.hierarchy);
+        std::mem::swap(&mut fresh.undo, &mut self.undo);
+        std::mem::swap(&mut fresh.code_generation, &mut self.code_generation);
+        std::mem::swap(&mut fresh.tree_rows, &mut self.tree_rows);
+        std::mem::swap(&mut fresh.tag_filter, &mut self.tag_filter);
+        std::mem::swap(&mut fresh.selection_focus, &mut self.selection_focus);
+        std::mem::swap(&mut fresh.pinned_inspectors, &mut self.pinned_inspectors);
+        std::mem::swap(&mut fresh.wide_inspectors, &mut self.wide_inspectors);
+        std::mem::swap(&mut fresh.editor_widths, &mut self.editor_widths);
+        std::mem::swap(&mut fresh.comparison_snapshot, &mut self.comparison_snapshot);
+        std::mem::swap(&mut fresh.show_comparison, &mut self.show_comparison);
+        std::mem::swap(&mut fresh.last_bulk_rename, &mut self.last_bulk_rename);
+        std::mem::swap(&mut fresh.hint, &mut self.hint);
+        std::mem::swap(&mut fresh.refused_add, &mut self.refused_add);
+        std::mem::swap(&mut fresh.dismissed_hints, &mut self.dismissed_hints);
+        std::mem::swap(&mut fresh.dismissed_contrast, &mut self.dismissed_contrast);
+        fresh
+    }
+
+    /// Shows a parked tab's state, dropping the layout that was shown
+    fn unpark(&mut self, tab: ParkedTab) {
+        self.hierarchy = tab.hierarchy;
+        self.undo = tab.undo;
+        self.code_generation = tab.code_generation;
+        self.tree_rows = tab.tree_rows;
+        self.tag_filter = tab.tag_filter;
+        self.selection_focus = tab.selection_focus;
+        self.pinned_inspectors = tab.pinned_inspectors;
+        self.wide_inspectors = tab.wide_inspectors;
+        self.editor_widths = tab.editor_widths;
+        self.comparison_snapshot = tab.comparison_snapshot;
+        self.show_comparison = tab.show_comparison;
+        self.last_bulk_rename = tab.last_bulk_rename;
+        self.hint = tab.hint;
+        self.refused_add = tab.refused_add;
+        self.dismissed_hints = tab.dismissed_hints;
+        self.dismissed_contrast = tab.dismissed_contrast;
+
+        // Forms and hover state point at widgets of the tab that was shown
+        self.option_import = None;
+        self.enum_form = None;
+        self.style_promote = None;
+        self.hovered_undo = None;
+        // The tab's last code may be from before shared settings changed
+        self.code_generation.invalidate(Instant::now());
+    }
+
+    /// Shows the tab at `index`
+    pub(crate) fn select_tab(&mut self, index: usize) {
+        if index == self.workspace.active || index >= self.workspace.tabs.len() {
+            return;
+        }
+        let Some(incoming) = self.workspace.tabs[index].parked.take() else { return };
+        let outgoing = self.park_active();
+        let previous = self.workspace.active;
+        self.workspace.tabs[previous].parked = Some(outgoing);
+        self.workspace.active = index;
+        self.unpark(incoming);
+    }
+
+    /// Adds a tab with an empty layout after the others and shows it
+    pub(crate) fn new_tab(&mut self) {
+        self.open_tab(WidgetHierarchy::new(WidgetType::Container));
+    }
+
+    /// Adds a tab showing `hierarchy` after the others and shows it
+    pub(crate) fn open_tab(&mut self, hierarchy: WidgetHierarchy) {
+        let name = self.workspace.next_name();
+        self.workspace.tabs.push(WorkspaceTab { name, parked: Some(ParkedTab::with_hierarchy(hierarchy)) });
+        self.select_tab(self.workspace.tabs.len() - 1);
+    }
+
+    /// Whether the tab at `index` has a layout closing it would lose
+    pub(crate) fn tab_has_work(&self, index: usize) -> bool {
+        match self.workspace.tabs.get(index).map(|tab| tab.parked.as_ref()) {
+            Some(Some(parked)) => parked.has_work(),
+            Some(None) => has_work(&self.hierarchy, &self.undo),
+            None => false,
+        }
+    }
+
+    /// Every tab's name and tree, in strip order
+    pub(crate) fn saved_tabs(&self) -> Vec<SavedTab> {
+        self.workspace.tabs.iter()
+            .map(|tab| SavedTab {
+                name: tab.name.clone(),
+                root: tab.parked.as_ref().map_or(&self.hierarchy, |parked| &parked.hierarchy).root().clone(),
+            })
+            .collect()
+    }
+
+    /// Swaps every tab for the given ones, as a project was saved, and shows the one at `active`
+    pub(crate) fn replace_tabs(&mut self, tabs: Vec<(String, WidgetHierarchy)>, active: usize) {
+        if tabs.is_empty() {
+            return;
+        }
+        let active = active.min(tabs.len() - 1);
+        let mut tabs: Vec<WorkspaceTab> = tabs.into_iter()
+            .map(|(name, hierarchy)| WorkspaceTab { name, parked: Some(ParkedTab::with_hierarchy(hierarchy)) })
+            .collect();
+        let shown = tabs[active].parked.take().expect("every tab was just parked");
+        self.workspace = Workspace { tabs, active, rename: None, closing: None, dragging: None };
+        self.unpark(shown);
+    }
+
+    /// Closes a tab, showing its neighbour first if it's the shown one. The last tab stays.
+    pub(crate) fn close_tab(&mut self, index: usize) {
+        if self.workspace.tabs.len() < 2 || index >= self.workspace.tabs.len() {
+            return;
+        }
+        if index == self.workspace.active {
+            self.select_tab(if index == 0 { 1 } else { index - 1 });
+        }
+        self.workspace.tabs.remove(index);
+        if index < self.workspace.active {
+            self.workspace.active -= 1;
+        }
+        self.workspace.rename = None;
+        self.workspace.closing = None;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum WorkspaceMessage {
+    Select(usize),
+    New,
+    /// Pressed on a tab, a click once released over it and a drag otherwise
+    Press(usize),
+    Hover(usize),
+    Release,
+    StartRename(usize),
+    RenameEdited(String),
+    CommitRename,
+    CancelRename,
+    /// Asks first when the tab has a layout
+    Close(usize),
+    ConfirmClose,
+    CancelClose,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(workspace: &Workspace) -> Vec<&str> {
+        workspace.tabs().iter().map(|tab| tab.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_tabs_keep_their_own_layout_and_undo() {
+        let mut visualizer = WidgetVisualizer::new();
+        visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Text).unwrap();
+        visualizer.undo.record("Added Text".to_string(), None, &visualizer.hierarchy, Instant::now());
+
+        visualizer.new_tab();
+        assert_eq!(names(&visualizer.workspace), ["Screen 1", "Screen 2"]);
+        assert_eq!(visualizer.workspace.active(), 1);
+        assert!(visualizer.hierarchy.root().children.is_empty());
+        assert_eq!(visualizer.undo.entries().len(), 1);
+        assert!(visualizer.tab_has_work(0));
+        assert!(!visualizer.tab_has_work(1));
+
+        visualizer.select_tab(0);
+        assert_eq!(visualizer.hierarchy.root().children.len(), 1);
+        assert_eq!(visualizer.undo.entries().len(), 2);
+    }
+
+    #[test]
+    fn test_closing_the_shown_tab_shows_its_neighbour() {
+        let mut visualizer = WidgetVisualizer::new();
+        visualizer.new_tab();
+        visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Button).unwrap();
+        visualizer.new_tab();
+        visualizer.select_tab(1);
+
+        visualizer.close_tab(1);
+        assert_eq!(names(&visualizer.workspace), ["Screen 1", "Screen 3"]);
+        assert_eq!(visualizer.workspace.active(), 0);
+        assert!(visualizer.hierarchy.root().children.is_empty());
+
+        // A new tab takes the lowest free number, and the last tab can't be closed
+        visualizer.new_tab();
+        assert_eq!(names(&visualizer.workspace), ["Screen 1", "Screen 3", "Screen 2"]);
+        visualizer.close_tab(1);
+        visualizer.close_tab(0);
+        visualizer.close_tab(0);
+        assert_eq!(visualizer.workspace.len(), 1);
+    }
+
+    #[test]
+    fn test_saved_tabs_come_back_in_order_with_the_same_one_shown() {
+        let mut visualizer = WidgetVisualizer::new();
+        visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Text).unwrap();
+        visualizer.new_tab();
+        visualizer.workspace.rename(1, "Settings").unwrap();
+        visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Row).unwrap();
+        visualizer.hierarchy.add_child(WidgetId(0), WidgetType::Column).unwrap();
+
+        let saved = visualizer.saved_tabs();
+        let mut loaded = WidgetVisualizer::new();
+        loaded.replace_tabs(
+            saved.into_iter().map(|tab| (tab.name, WidgetHierarchy::from_root(tab.root).unwrap())).collect(),
+            visualizer.workspace.active(),
+        );
+        assert_eq!(names(&loaded.workspace), ["Screen 1", "Settings"]);
+        assert_eq!(loaded.workspace.active(), 1);
+        assert_eq!(loaded.hierarchy.root().children.len(), 2);
+        loaded.select_tab(0);
+        assert_eq!(loaded.hierarchy.root().children[0].widget_type, WidgetType::Text);
+    }
+
+    #[test]
+    fn test_moving_tabs_keeps_the_shown_one() {
+        let mut workspace = Workspace::default();
+        for name in ["B", "C", "D"] {
+            workspace.tabs.push(WorkspaceTab { name: name.to_string(), parked: None });
+        }
+        workspace.rename(0, "A").unwrap();
+        workspace.active = 1;
+
+        workspace.move_tab(0, 3);
+        assert_eq!(names(&workspace), ["B", "C", "D", "A"]);
+        assert_eq!(workspace.active_name(), "B");
+        workspace.move_tab(3, 0);
+        assert_eq!(names(&workspace), ["A", "B", "C", "D"]);
+        assert_eq!(workspace.active_name(), "B");
+        workspace.move_tab(1, 2);
+        assert_eq!(workspace.active_name(), "B");
+        assert_eq!(workspace.active(), 2);
+
+        assert!(workspace.rename(0, "C").is_err());
+        assert!(workspace.rename(0, "  ").is_err());
+    }
+}
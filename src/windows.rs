@@ -0,0 +1,358 @@
+use iced::{window, Point, Size, Task};
+use std::collections::BTreeMap;
+
+use crate::settings::AppSettings;
+
+const MAIN_WINDOW_SIZE: Size = Size::new(700_f32, 1130_f32);
+const MAIN_WINDOW_MIN_SIZE: Size = Size::new(700_f32, 1105_f32);
+
+const BUILDER_WINDOW_SIZE: Size = Size::new(1920_f32 * 0.8, 1080_f32 * 0.8);
+const BUILDER_WINDOW_MIN_SIZE: Size = Size::new(700_f32, 975_f32);
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub enum WindowEnum {
+    #[default]
+    Main,
+    WidgetVisualizer
+}
+
+impl WindowEnum {
+    /// Kinds that only ever have one window. Asking for another focuses the open one.
+    fn is_single_instance(&self) -> bool {
+        matches!(self, WindowEnum::WidgetVisualizer)
+    }
+
+    /// Size a window of this kind opens at when no placement was saved
+    fn default_size(&self) -> Size {
+        match self {
+            WindowEnum::Main => MAIN_WINDOW_SIZE,
+            WindowEnum::WidgetVisualizer => BUILDER_WINDOW_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Clone,)]
+pub struct Window {
+    pub title: String,
+    pub windowtype: WindowEnum,
+    /// Tick of the last focus event, 0 if the window was never focused
+    focused_at: u64,
+}
+
+impl Window {
+    pub fn new(_id: window::Id, title: String, window_type: WindowEnum) -> Self {
+        Self {
+            title: title,
+            windowtype: window_type,
+            focused_at: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Closed(window::Id),
+    RequestOpen(WindowEnum),
+    Opened(window::Id, WindowEnum),
+    Moved(window::Id, Point),
+    Resized(window::Id, Size),
+    Focused(window::Id),
+    Unfocused(window::Id),
+}
+
+/// What asking for a window of some kind comes down to
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OpenAction {
+    Open,
+    Focus(window::Id),
+}
+
+/// Open windows and what each one shows
+#[derive(Debug, Default)]
+pub struct Windows {
+    windows: BTreeMap<window::Id, Window>,
+    /// Counts focus events, so windows can be ordered by how recently they had focus
+    focus_clock: u64,
+    /// The window that has focus right now, if one of ours does
+    focused: Option<window::Id>,
+}
+
+impl Windows {
+    pub fn get(&self, window_id: &window::Id) -> Option<&Window> {
+        self.windows.get(window_id)
+    }
+
+    pub fn is_open(&self, window_type: &WindowEnum) -> bool {
+        self.windows.values().any(|w| w.windowtype == *window_type)
+    }
+
+    pub fn title(&self, window_id: window::Id) -> String {
+        self.windows.get(&window_id).map(|window| window.title.clone()).unwrap_or_default()
+    }
+
+    /// What the window with focus right now shows, None while none of ours has it
+    pub fn focused_type(&self) -> Option<&WindowEnum> {
+        self.focused.and_then(|id| self.windows.get(&id)).map(|window| &window.windowtype)
+    }
+
+    /// The window of this kind that had focus most recently. Windows never focused
+    /// rank below those that were, newest first.
+    pub fn find_window(&self, window_type: &WindowEnum) -> Option<window::Id> {
+        self.windows.iter()
+            .filter(|(_, w)| w.windowtype == *window_type)
+            .max_by_key(|(id, w)| (w.focused_at, **id))
+            .map(|(id, _)| *id)
+    }
+
+    /// Applies the message, recording moves and resizes into `settings`.
+    /// The flag is true when `settings` changed and should be saved.
+    pub fn update(&mut self, message: Message, settings: &mut AppSettings) -> (Task<Message>, bool) {
+        match message {
+            Message::Closed(window_id) => {
+                self.windows.remove(&window_id);
+                if self.focused == Some(window_id) {
+                    self.focused = None;
+                }
+                if self.windows.is_empty() {
+                    (iced::exit(), false)
+                }
+                else {
+                    (Task::none(), false)
+                }
+            },
+            Message::RequestOpen(window_type) => (self.focus_or_open(window_type, settings), false),
+            Message::Opened(window_id, window_type) => {
+                let title = match window_type {
+                    WindowEnum::Main => { String::from("Theme Viewer") }
+                    WindowEnum::WidgetVisualizer => { String::from("UI Builder") }
+                };
+
+                let new_window = Window::new(window_id, title, window_type);
+
+                self.windows.insert(window_id, new_window);
+
+                (Task::none(), false)
+            },
+            Message::Moved(window_id, position) => {
+                if let Some(window) = self.windows.get(&window_id) {
+                    let current_size = settings.placement(&window.windowtype)
+                        .map(|p| Size::new(p.width, p.height))
+                        .unwrap_or(window.windowtype.default_size());
+                    settings.record_position(&window.windowtype, position, current_size);
+                    return (Task::none(), true);
+                }
+                (Task::none(), false)
+            }
+            Message::Resized(window_id, size) => {
+                if let Some(window) = self.windows.get(&window_id) {
+                    settings.record_size(&window.windowtype, size);
+                    return (Task::none(), true);
+                }
+                (Task::none(), false)
+            }
+            Message::Focused(window_id) => {
+                if let Some(window) = self.windows.get_mut(&window_id) {
+                    self.focus_clock += 1;
+                    window.focused_at = self.focus_clock;
+                    self.focused = Some(window_id);
+                }
+                (Task::none(), false)
+            }
+            Message::Unfocused(window_id) => {
+                if self.focused == Some(window_id) {
+                    self.focused = None;
+                }
+                (Task::none(), false)
+            }
+        }
+    }
+
+    /// Whether asking for `window_type` opens a new window or brings an open one forward
+    fn open_action(&self, window_type: &WindowEnum) -> OpenAction {
+        match self.find_window(window_type) {
+            Some(window_id) if window_type.is_single_instance() => OpenAction::Focus(window_id),
+            _ => OpenAction::Open,
+        }
+    }
+
+    /// Brings the most recently focused window of the given type to the front, or opens
+    /// one if the type allows several windows or none is open yet
+    pub fn focus_or_open(&self, window_type: WindowEnum, settings: &AppSettings) -> Task<Message> {
+        if let OpenAction::Focus(window_id) = self.open_action(&window_type) {
+            return iced::Task::batch([
+                window::minimize(window_id, false),
+                window::gain_focus(window_id)
+            ]);
+        }
+
+        match window_type {
+            WindowEnum::Main => {
+                let (size, position) = window_placement(settings, &WindowEnum::Main, MAIN_WINDOW_SIZE, MAIN_WINDOW_MIN_SIZE);
+                let (_id, open) = iced::window::open(
+                    iced::window::Settings {
+                        position,
+                        size,
+                        min_size: Some(MAIN_WINDOW_MIN_SIZE),
+                        exit_on_close_request: true,
+                        ..iced::window::Settings::default()
+                    }
+                );
+                open.map(|id| Message::Opened(id, WindowEnum::Main))
+            }
+            WindowEnum::WidgetVisualizer => {
+                let (size, position) = window_placement(settings, &WindowEnum::WidgetVisualizer, BUILDER_WINDOW_SIZE, BUILDER_WINDOW_MIN_SIZE);
+                let (_id, open) = iced::window::open(window::Settings {
+                    position,
+                    size,
+                    min_size: Some(BUILDER_WINDOW_MIN_SIZE),
+                    ..window::Settings::default()
+                });
+                open.map(|id| Message::Opened(id, WindowEnum::WidgetVisualizer))
+            }
+        }
+    }
+}
+
+/// Saved size and position for a window, or the defaults when nothing usable is stored
+fn window_placement(settings: &AppSettings, window_type: &WindowEnum, default_size: Size, min_size: Size) -> (Size, window::Position) {
+    match settings.placement(window_type) {
+        Some(placement) => (
+            placement.size(min_size),
+            placement.position()
+                .map(window::Position::Specific)
+                .unwrap_or(window::Position::Centered),
+        ),
+        None => (default_size, window::Position::Centered),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opened(windows: &mut Windows, settings: &mut AppSettings, window_type: WindowEnum) -> window::Id {
+        let id = window::Id::unique();
+        windows.update(Message::Opened(id, window_type), settings);
+        id
+    }
+
+    #[test]
+    fn test_opened_windows_are_titled_by_type() {
+        let (mut windows, mut settings) = (Windows::default(), AppSettings::default());
+        let main = opened(&mut windows, &mut settings, WindowEnum::Main);
+        let builder = opened(&mut windows, &mut settings, WindowEnum::WidgetVisualizer);
+
+        assert_eq!(windows.title(main), "Theme Viewer");
+        assert_eq!(windows.title(builder), "UI Builder");
+        assert_eq!(windows.find_window(&WindowEnum::WidgetVisualizer), Some(builder));
+        assert!(windows.is_open(&WindowEnum::WidgetVisualizer));
+    }
+
+    #[test]
+    fn test_closing_forgets_the_window() {
+        let (mut windows, mut settings) = (Windows::default(), AppSettings::default());
+        let main = opened(&mut windows, &mut settings, WindowEnum::Main);
+        let builder = opened(&mut windows, &mut settings, WindowEnum::WidgetVisualizer);
+
+        let (_, dirty) = windows.update(Message::Closed(builder), &mut settings);
+        assert!(!dirty);
+        assert!(!windows.is_open(&WindowEnum::WidgetVisualizer));
+        assert!(windows.get(&main).is_some());
+        assert_eq!(windows.title(builder), "");
+    }
+
+    #[test]
+    fn test_move_and_resize_record_placement() {
+        let (mut windows, mut settings) = (Windows::default(), AppSettings::default());
+        let builder = opened(&mut windows, &mut settings, WindowEnum::WidgetVisualizer);
+
+        let (_, dirty) = windows.update(Message::Resized(builder, Size::new(900.0, 1000.0)), &mut settings);
+        assert!(dirty);
+        let (_, dirty) = windows.update(Message::Moved(builder, Point::new(40.0, 60.0)), &mut settings);
+        assert!(dirty);
+
+        let placement = settings.placement(&WindowEnum::WidgetVisualizer).unwrap();
+        assert_eq!((placement.width, placement.height), (900.0, 1000.0));
+        assert_eq!(placement.position(), Some(Point::new(40.0, 60.0)));
+        assert!(settings.placement(&WindowEnum::Main).is_none());
+    }
+
+    #[test]
+    fn test_first_move_keeps_the_default_size_of_the_kind() {
+        let (mut windows, mut settings) = (Windows::default(), AppSettings::default());
+        let main = opened(&mut windows, &mut settings, WindowEnum::Main);
+        let builder = opened(&mut windows, &mut settings, WindowEnum::WidgetVisualizer);
+
+        windows.update(Message::Moved(builder, Point::new(40.0, 60.0)), &mut settings);
+        windows.update(Message::Moved(main, Point::new(10.0, 20.0)), &mut settings);
+
+        let placement = settings.placement(&WindowEnum::WidgetVisualizer).unwrap();
+        assert_eq!(Size::new(placement.width, placement.height), BUILDER_WINDOW_SIZE);
+        let placement = settings.placement(&WindowEnum::Main).unwrap();
+        assert_eq!(Size::new(placement.width, placement.height), MAIN_WINDOW_SIZE);
+    }
+
+    #[test]
+    fn test_find_window_prefers_most_recently_focused() {
+        let (mut windows, mut settings) = (Windows::default(), AppSettings::default());
+        let first = opened(&mut windows, &mut settings, WindowEnum::Main);
+        let second = opened(&mut windows, &mut settings, WindowEnum::Main);
+        // Neither focused yet: the newest wins
+        assert_eq!(windows.find_window(&WindowEnum::Main), Some(second));
+
+        windows.update(Message::Focused(first), &mut settings);
+        assert_eq!(windows.find_window(&WindowEnum::Main), Some(first));
+        assert_eq!(windows.focused, Some(first));
+
+        windows.update(Message::Unfocused(first), &mut settings);
+        windows.update(Message::Focused(second), &mut settings);
+        assert_eq!(windows.find_window(&WindowEnum::Main), Some(second));
+
+        // Losing focus keeps the ordering, it only clears the current window
+        windows.update(Message::Unfocused(second), &mut settings);
+        assert_eq!(windows.focused, None);
+        assert_eq!(windows.find_window(&WindowEnum::Main), Some(second));
+
+        windows.update(Message::Closed(second), &mut settings);
+        assert_eq!(windows.find_window(&WindowEnum::Main), Some(first));
+        assert_eq!(windows.find_window(&WindowEnum::WidgetVisualizer), None);
+    }
+
+    #[test]
+    fn test_closing_focused_window_clears_focus() {
+        let (mut windows, mut settings) = (Windows::default(), AppSettings::default());
+        let main = opened(&mut windows, &mut settings, WindowEnum::Main);
+        let builder = opened(&mut windows, &mut settings, WindowEnum::WidgetVisualizer);
+        windows.update(Message::Focused(builder), &mut settings);
+        assert_eq!(windows.focused_type(), Some(&WindowEnum::WidgetVisualizer));
+
+        windows.update(Message::Closed(builder), &mut settings);
+        assert_eq!(windows.focused, None);
+        // A late unfocus from another window doesn't matter
+        windows.update(Message::Focused(main), &mut settings);
+        windows.update(Message::Unfocused(builder), &mut settings);
+        assert_eq!(windows.focused, Some(main));
+    }
+
+    #[test]
+    fn test_only_single_instance_kinds_are_focused_instead_of_opened() {
+        let (mut windows, mut settings) = (Windows::default(), AppSettings::default());
+        assert_eq!(windows.open_action(&WindowEnum::WidgetVisualizer), OpenAction::Open);
+        assert_eq!(windows.open_action(&WindowEnum::Main), OpenAction::Open);
+
+        opened(&mut windows, &mut settings, WindowEnum::Main);
+        let builder = opened(&mut windows, &mut settings, WindowEnum::WidgetVisualizer);
+        assert_eq!(windows.open_action(&WindowEnum::WidgetVisualizer), OpenAction::Focus(builder));
+        assert_eq!(windows.open_action(&WindowEnum::Main), OpenAction::Open);
+    }
+
+    #[test]
+    fn test_events_for_unknown_windows_are_ignored() {
+        let (mut windows, mut settings) = (Windows::default(), AppSettings::default());
+        let (_, dirty) = windows.update(Message::Moved(window::Id::unique(), Point::ORIGIN), &mut settings);
+        assert!(!dirty);
+        assert!(settings.placement(&WindowEnum::Main).is_none());
+        windows.update(Message::Focused(window::Id::unique()), &mut settings);
+        assert_eq!(windows.focused, None);
+    }
+}